@@ -85,6 +85,15 @@ touch $install_dir/bin/kcl-language-server
 rm $install_dir/bin/kcl-language-server
 cp $topdir/kclvm/target/release/kcl-language-server $install_dir/bin/kcl-language-server
 
+# build kcl debug adapter
+
+cd $topdir/kclvm/tools/src/DAP
+cargo build --release
+
+touch $install_dir/bin/kcl-debug-adapter
+rm $install_dir/bin/kcl-debug-adapter
+cp $topdir/kclvm/target/release/kcl-debug-adapter $install_dir/bin/kcl-debug-adapter
+
 
 cd $topdir/kclvm_cli
 cargo build --release
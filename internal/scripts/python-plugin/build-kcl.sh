@@ -128,6 +128,15 @@ touch $kcl_install_dir/bin/kcl-language-server
 rm $kcl_install_dir/bin/kcl-language-server
 cp $topdir/kclvm/target/release/kcl-language-server $kcl_install_dir/bin/kcl-language-server
 
+# build kcl debug adapter
+
+cd $topdir/kclvm/tools/src/DAP
+cargo build --release
+
+touch $kcl_install_dir/bin/kcl-debug-adapter
+rm $kcl_install_dir/bin/kcl-debug-adapter
+cp $topdir/kclvm/target/release/kcl-debug-adapter $kcl_install_dir/bin/kcl-debug-adapter
+
 
 cd $topdir/kclvm_cli
 cargo build --release
@@ -31,6 +31,16 @@ fn test_c_api_call_exec_program_with_external_pkg() {
     );
 }
 
+#[test]
+fn test_c_api_call_exec_program_with_fast_eval() {
+    test_c_api::<ExecProgramArgs, ExecProgramResult, _>(
+        "KclvmService.ExecProgram",
+        "exec-program-with-fast-eval.json",
+        "exec-program-with-fast-eval.response.json",
+        |res| res.escaped_time = "0".to_owned(),
+    );
+}
+
 #[test]
 fn test_c_api_call_override_file() {
     test_c_api_without_wrapper::<OverrideFileArgs, OverrideFileResult>(
@@ -3,15 +3,31 @@ use crate::service::service_impl::KclvmServiceImpl;
 use core::fmt::Display;
 use jsonrpc_stdio_server::jsonrpc_core::{Error, ErrorCode, IoHandler, Params};
 use jsonrpc_stdio_server::ServerBuilder;
+use kclvm_runner::cache::ProgramCache;
 use serde::Serialize;
+use std::sync::Arc;
 const KCLVM_SERVER_ERROR_CODE: i64 = 0x4B434C; // the ASCII code of "KCL"
 
 /// Start a json rpc server via Stdin/Stdout
+pub fn start_stdio_server() -> Result<(), anyhow::Error> {
+    start_stdio_server_with_service(KclvmServiceImpl::default())
+}
+
+/// Start a long-running json rpc server via Stdin/Stdout, which keeps the parsed
+/// and resolved programs and the compiled libraries in memory, and a program is
+/// loaded again only when its source files are changed.
+pub fn start_stdio_daemon_server() -> Result<(), anyhow::Error> {
+    start_stdio_server_with_service(KclvmServiceImpl {
+        program_cache: Some(Arc::new(ProgramCache::default())),
+        ..Default::default()
+    })
+}
+
 #[tokio::main]
-pub async fn start_stdio_server() -> Result<(), anyhow::Error> {
+async fn start_stdio_server_with_service(serv: KclvmServiceImpl) -> Result<(), anyhow::Error> {
     let mut io = IoHandler::default();
     // KclvmService
-    register_kclvm_service(&mut io);
+    register_kclvm_service(&mut io, &serv);
     // BuiltinService
     register_builtin_service(&mut io);
     let server = ServerBuilder::new(io).build();
@@ -58,7 +74,7 @@ where
     }
 }
 
-fn register_kclvm_service(io: &mut IoHandler) {
+fn register_kclvm_service(io: &mut IoHandler, serv: &KclvmServiceImpl) {
     io.add_method("KclvmService.Ping", |params: Params| {
         let kclvm_service_impl = KclvmServiceImpl::default();
         let args: PingArgs = match params.parse() {
@@ -67,8 +83,10 @@ fn register_kclvm_service(io: &mut IoHandler) {
         };
         futures::future::ready(catch!(kclvm_service_impl, args, ping))
     });
-    io.add_method("KclvmService.ExecProgram", |params: Params| {
-        let kclvm_service_impl = KclvmServiceImpl::default();
+    // ExecProgram shares the program cache of the server between requests.
+    let exec_serv = serv.clone();
+    io.add_method("KclvmService.ExecProgram", move |params: Params| {
+        let kclvm_service_impl = exec_serv.clone();
         let args: ExecProgramArgs = match params.parse() {
             Ok(val) => val,
             Err(err) => return futures::future::ready(Err(err)),
@@ -11,7 +11,8 @@ use kclvm_driver::canonicalize_input_files;
 use kclvm_parser::ParseSession;
 use kclvm_query::get_schema_type;
 use kclvm_query::override_file;
-use kclvm_runner::exec_program;
+use kclvm_runner::cache::ProgramCache;
+use kclvm_runner::{exec_program, exec_program_with_cache};
 use kclvm_tools::format::{format, format_source, FormatOptions};
 use kclvm_tools::lint::lint_files;
 use kclvm_tools::vet::validator::validate;
@@ -27,6 +28,9 @@ use super::util::transform_str_para;
 #[derive(Debug, Clone, Default)]
 pub struct KclvmServiceImpl {
     pub plugin_agent: u64,
+    /// The program cache shared by the long-running server, and programs are
+    /// loaded and compiled for every execution when it is `None`.
+    pub program_cache: Option<Arc<ProgramCache>>,
 }
 
 impl KclvmServiceImpl {
@@ -99,10 +103,11 @@ impl KclvmServiceImpl {
         let args_json = serde_json::to_string(args).unwrap();
 
         let sess = Arc::new(ParseSession::default());
-        let result = exec_program(
-            sess,
-            &kclvm_runner::ExecProgramArgs::from_str(args_json.as_str()),
-        )?;
+        let args = kclvm_runner::ExecProgramArgs::from_str(args_json.as_str());
+        let result = match &self.program_cache {
+            Some(cache) => exec_program_with_cache(sess, &args, cache)?,
+            None => exec_program(sess, &args)?,
+        };
 
         Ok(ExecProgramResult {
             json_result: result.json_result,
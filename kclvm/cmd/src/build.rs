@@ -0,0 +1,32 @@
+use anyhow::Result;
+use clap::ArgMatches;
+use kclvm_error::StringError;
+use kclvm_parser::ParseSession;
+use kclvm_runner::{build_program, ExecProgramArgs};
+use std::sync::Arc;
+
+use crate::run::emit_from_matches;
+use crate::settings::must_build_settings;
+
+/// Run the KCL build command.
+pub fn build_command(matches: &ArgMatches) -> Result<()> {
+    // Config settings building
+    let settings = must_build_settings(matches);
+    let output = settings
+        .output()
+        .ok_or_else(|| anyhow::anyhow!("the artifact output path is not specified by -o"))?;
+    let mut args: ExecProgramArgs = settings.try_into()?;
+    if let Some(target) = matches.get_one::<String>("target") {
+        args.target = target.to_string();
+    }
+    args.debug_info = matches.get_flag("debug_info");
+    let sess = Arc::new(ParseSession::default());
+    emit_from_matches(matches, sess.clone(), &args)?;
+    if let Err(msg) = build_program(sess.clone(), &args, &output) {
+        if !sess.0.diag_handler.has_errors()? {
+            sess.0.add_err(StringError(msg))?;
+        }
+        sess.0.emit_stashed_diagnostics_and_abort()?;
+    }
+    Ok(())
+}
@@ -3,6 +3,7 @@
 #[macro_use]
 extern crate clap;
 
+pub mod build;
 pub mod fmt;
 pub mod lint;
 pub mod run;
@@ -18,6 +19,7 @@ use clap::{ArgAction, Command};
 use std::io;
 
 use anyhow::Result;
+use build::build_command;
 use fmt::fmt_command;
 use lint::lint_command;
 use run::run_command;
@@ -29,10 +31,17 @@ pub fn main(args: &[&str]) -> Result<()> {
     // Sub commands
     match matches.subcommand() {
         Some(("run", sub_matches)) => run_command(sub_matches, &mut io::stdout()),
+        Some(("build", sub_matches)) => build_command(sub_matches),
         Some(("lint", sub_matches)) => lint_command(sub_matches),
         Some(("fmt", sub_matches)) => fmt_command(sub_matches),
         Some(("vet", sub_matches)) => vet_command(sub_matches),
-        Some(("server", _)) => kclvm_api::service::jsonrpc::start_stdio_server(),
+        Some(("server", sub_matches)) => {
+            if sub_matches.get_flag("daemon") {
+                kclvm_api::service::jsonrpc::start_stdio_daemon_server()
+            } else {
+                kclvm_api::service::jsonrpc::start_stdio_server()
+            }
+        }
         Some(("version", _)) => {
             println!("{}", kclvm_version::get_version_info());
             Ok(())
@@ -50,7 +59,13 @@ pub fn app() -> Command {
             Command::new("run")
             .about("run")
             .arg(arg!([input] ... "Specify the input files to run").num_args(0..))
-            .arg(arg!(output: -o --output <output> "Specify the YAML output file path"))
+            .arg(arg!(output: -o --output <output> "Specify the output file path"))
+            .arg(arg!(format: --format <format> "Specify the output format, yaml by default").value_parser(["yaml", "json", "toml", "properties", "env"]))
+            .arg(arg!(output_dir: --output_dir <output_dir> "Write each document of the result into a separate file in the output directory").conflicts_with("output"))
+            .arg(arg!(output_template: --output_template <output_template> "Specify the file name template of --output_dir, e.g., {kind}-{metadata.name}").requires("output_dir"))
+            .arg(arg!(yaml_comments: --yaml_comments "Emit the schema attribute docs as the comments of the YAML output"))
+            .arg(arg!(frozen_now: --frozen_now <frozen_now> "Specify the RFC 3339 time used as the current time of the datetime module for reproducible outputs"))
+            .arg(arg!(deterministic: --deterministic "Refuse the functions with random results e.g., uuid.v4 for reproducible outputs"))
             .arg(arg!(setting: -Y --setting <setting> ... "Specify the input setting file").num_args(1..))
             .arg(arg!(verbose: -v --verbose "Print test information verbosely").action(ArgAction::Count))
             .arg(arg!(disable_none: -n --disable_none "Disable dumping None values"))
@@ -61,6 +76,38 @@ pub fn app() -> Command {
             .arg(arg!(path_selector: -S --path_selector <path_selector> ... "Specify the path selector").num_args(1..))
             .arg(arg!(overrides: -O --overrides <overrides> ... "Specify the configuration override path and value").num_args(1..))
             .arg(arg!(target: --target <target> "Specify the target type"))
+            .arg(arg!(package_map: -E --external <package_map> ... "Mapping of package name and path where the package is located").num_args(1..))
+            .arg(arg!(profile: --profile "Print the time spent in the phases, files, schemas and lambdas"))
+            .arg(arg!(emit: --emit <emit> "Emit the generated code of each package for inspection").value_parser(["llvm-ir", "bc", "obj"]))
+            .arg(arg!(emit_dir: --emit_dir <emit_dir> "Specify the output directory of the emitted code, the current directory by default"))
+            .arg(arg!(debug_info: -g --debug_info "Emit the DWARF debug info of the KCL sources into the generated code for native debuggers and profilers"))
+            .arg(arg!(profile_trace: --profile_trace <profile_trace> "Write the profile as a Chrome trace event file, which implies --profile"))
+            .arg(arg!(artifact: --artifact <artifact> "Run the artifact compiled by the build command instead of the input files"))
+            .arg(arg!(fast_eval: --fast_eval "Evaluate the program with the AST evaluator instead of compiling it"))
+            .arg(arg!(jit: --jit "Compile and execute the program in memory without any temp files").conflicts_with("fast_eval"))
+            .arg(arg!(sandbox: --sandbox "Refuse the plugins and the functions with file system and output side effects"))
+            .arg(arg!(timeout: --timeout <timeout> "Specify the wall-clock timeout of the evaluation in milliseconds, 0 denotes no limit").value_parser(value_parser!(u64)))
+            .arg(arg!(max_call_depth: --max_call_depth <max_call_depth> "Specify the maximum depth of lambda and schema calls, 0 denotes no limit").value_parser(value_parser!(u64)))
+            .arg(arg!(max_objects: --max_objects <max_objects> "Specify the maximum number of allocated values, 0 denotes no limit").value_parser(value_parser!(u64))),
+        )
+        .subcommand(
+            Command::new("build")
+            .about("Compile the KCL program into a shared library artifact, which can be run by `run --artifact`")
+            .arg(arg!([input] ... "Specify the input files to build").num_args(0..))
+            .arg(arg!(output: -o --output <output> "Specify the artifact output path"))
+            .arg(arg!(setting: -Y --setting <setting> ... "Specify the input setting file").num_args(1..))
+            .arg(arg!(verbose: -v --verbose "Print test information verbosely").action(ArgAction::Count))
+            .arg(arg!(disable_none: -n --disable_none "Disable dumping None values"))
+            .arg(arg!(strict_range_check: -r --strict_range_check "Do perform strict numeric range checks"))
+            .arg(arg!(debug: -d --debug "Run in debug mode (for developers only)"))
+            .arg(arg!(sort_keys: -k --sort_keys "Sort result keys"))
+            .arg(arg!(arguments: -D --argument <arguments> ... "Specify the top-level argument").num_args(1..))
+            .arg(arg!(path_selector: -S --path_selector <path_selector> ... "Specify the path selector").num_args(1..))
+            .arg(arg!(overrides: -O --overrides <overrides> ... "Specify the configuration override path and value, which is compiled into the artifact").num_args(1..))
+            .arg(arg!(target: --target <target> "Specify the target triple of the artifact, e.g., wasm32-wasi"))
+            .arg(arg!(emit: --emit <emit> "Emit the generated code of each package for inspection").value_parser(["llvm-ir", "bc", "obj"]))
+            .arg(arg!(emit_dir: --emit_dir <emit_dir> "Specify the output directory of the emitted code, the current directory by default"))
+            .arg(arg!(debug_info: -g --debug_info "Emit the DWARF debug info of the KCL sources into the generated code for native debuggers and profilers"))
             .arg(arg!(package_map: -E --external <package_map> ... "Mapping of package name and path where the package is located").num_args(1..)),
         )
         .subcommand(
@@ -87,6 +134,10 @@ pub fn app() -> Command {
                 .arg(arg!(attribute_name: -n --attribute_name <attribute_name> "The attribute name for the data loading"))
                 .arg(arg!(format: --format <format> "Validation data file format, support YAML and JSON, default is JSON")),
         )
-    .subcommand(Command::new("server").about("Start a rpc server for APIs"))
+    .subcommand(
+        Command::new("server")
+            .about("Start a rpc server for APIs")
+            .arg(arg!(daemon: --daemon "Keep the loaded programs in memory to serve repeated runs incrementally")),
+    )
     .subcommand(Command::new("version").about("Show the KCL version"))
 }
@@ -2,8 +2,11 @@ use anyhow::Result;
 use clap::ArgMatches;
 use kclvm_error::StringError;
 use kclvm_parser::ParseSession;
-use kclvm_runner::exec_program;
+use kclvm_runner::output::{write_documents, DEFAULT_OUTPUT_TEMPLATE};
+use kclvm_runner::{emit_program, exec_artifact, exec_program, EmitKind, ExecProgramArgs};
+use kclvm_runtime::PlanFormat;
 use std::io::Write;
+use std::path::Path;
 use std::sync::Arc;
 
 use crate::settings::must_build_settings;
@@ -13,16 +16,74 @@ pub fn run_command<W: Write>(matches: &ArgMatches, writer: &mut W) -> Result<()>
     // Config settings building
     let settings = must_build_settings(matches);
     let output = settings.output();
+    let profile_trace = matches.get_one::<String>("profile_trace");
+    let format: PlanFormat = match matches.get_one::<String>("format") {
+        Some(format) => format.parse().map_err(anyhow::Error::msg)?,
+        None => PlanFormat::default(),
+    };
+    let mut args: ExecProgramArgs = settings.try_into()?;
+    args.profile = matches.get_flag("profile") || profile_trace.is_some();
+    args.debug_info = matches.get_flag("debug_info");
+    args.yaml_comments = matches.get_flag("yaml_comments");
+    if let Some(frozen_now) = matches.get_one::<String>("frozen_now") {
+        args.frozen_now = frozen_now.clone();
+    }
+    args.deterministic = matches.get_flag("deterministic");
+    args.fast_eval = matches.get_flag("fast_eval");
+    args.jit = matches.get_flag("jit");
+    args.sandbox = matches.get_flag("sandbox");
+    if let Some(timeout) = matches.get_one::<u64>("timeout") {
+        args.timeout = *timeout;
+    }
+    if let Some(max_call_depth) = matches.get_one::<u64>("max_call_depth") {
+        args.max_call_depth = *max_call_depth;
+    }
+    if let Some(max_objects) = matches.get_one::<u64>("max_objects") {
+        args.max_objects = *max_objects;
+    }
     let sess = Arc::new(ParseSession::default());
-    match exec_program(sess.clone(), &settings.try_into()?) {
-        Ok(result) => match output {
-            Some(o) => {
-                std::fs::write(o, result.yaml_result)?;
+    emit_from_matches(matches, sess.clone(), &args)?;
+    // The compiled artifact is run with the arguments and overrides without recompiling.
+    let result = match matches.get_one::<String>("artifact") {
+        Some(artifact) => exec_artifact(artifact, &args),
+        None => exec_program(sess.clone(), &args),
+    };
+    match result {
+        Ok(result) => {
+            if let Some(output_dir) = matches.get_one::<String>("output_dir") {
+                let template = matches
+                    .get_one::<String>("output_template")
+                    .map(|template| template.as_str())
+                    .unwrap_or(DEFAULT_OUTPUT_TEMPLATE);
+                write_documents(
+                    &result,
+                    format,
+                    args.sort_keys,
+                    Path::new(output_dir),
+                    template,
+                )
+                .map_err(anyhow::Error::msg)?;
+            } else {
+                let content = result
+                    .format_result(format, args.sort_keys)
+                    .map_err(anyhow::Error::msg)?;
+                match output {
+                    Some(o) => {
+                        std::fs::write(o, &content)?;
+                    }
+                    // [`println!`] is not a good way to output content to stdout,
+                    // using [`writeln`] can be better to redirect the output.
+                    None => writeln!(writer, "{}", content)?,
+                }
             }
-            // [`println!`] is not a good way to output content to stdout,
-            // using [`writeln`] can be better to redirect the output.
-            None => writeln!(writer, "{}", result.yaml_result)?,
-        },
+            // The profile report is printed to stderr to keep the YAML output clean.
+            if let Some(profile) = &result.profile {
+                eprint!("{}", profile);
+                if let Some(trace) = profile_trace {
+                    std::fs::write(trace, profile.to_trace_json())?;
+                }
+            }
+        }
         Err(msg) => {
             if !sess.0.diag_handler.has_errors()? {
                 sess.0.add_err(StringError(msg))?;
@@ -32,3 +93,25 @@ pub fn run_command<W: Write>(matches: &ArgMatches, writer: &mut W) -> Result<()>
     }
     Ok(())
 }
+
+/// Emit the generated code of each package when `--emit` is set.
+pub(crate) fn emit_from_matches(
+    matches: &ArgMatches,
+    sess: Arc<ParseSession>,
+    args: &ExecProgramArgs,
+) -> Result<()> {
+    if let Some(emit_kind) = matches.get_one::<String>("emit") {
+        let emit_kind: EmitKind = emit_kind.parse().map_err(anyhow::Error::msg)?;
+        let emit_dir = matches
+            .get_one::<String>("emit_dir")
+            .map(|dir| dir.as_str())
+            .unwrap_or(".");
+        if let Err(msg) = emit_program(sess.clone(), args, emit_kind, emit_dir) {
+            if !sess.0.diag_handler.has_errors()? {
+                sess.0.add_err(StringError(msg))?;
+            }
+            sess.0.emit_stashed_diagnostics_and_abort()?;
+        }
+    }
+    Ok(())
+}
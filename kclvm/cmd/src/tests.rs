@@ -3,8 +3,8 @@ use std::{env, fs, path::PathBuf};
 use kclvm_config::modfile::KCL_PKG_PATH;
 
 use crate::{
-    app, fmt::fmt_command, run::run_command, settings::build_settings, util::hashmaps_from_matches,
-    vet::vet_command,
+    app, build::build_command, fmt::fmt_command, run::run_command, settings::build_settings,
+    util::hashmaps_from_matches, vet::vet_command,
 };
 
 const ROOT_CMD: &str = "kclvm_cli";
@@ -122,6 +122,16 @@ fn test_version_cmd() {
     assert!(matches.subcommand_matches("version").is_some())
 }
 
+#[test]
+fn test_server_cmd() {
+    let matches = app().get_matches_from(&[ROOT_CMD, "server"]);
+    let matches = matches.subcommand_matches("server").unwrap();
+    assert!(!matches.get_flag("daemon"));
+    let matches = app().get_matches_from(&[ROOT_CMD, "server", "--daemon"]);
+    let matches = matches.subcommand_matches("server").unwrap();
+    assert!(matches.get_flag("daemon"));
+}
+
 #[test]
 fn test_multi_external_cmd() {
     let matches = app().get_matches_from(&[
@@ -193,6 +203,13 @@ fn test_run_command() {
     test_run_command_with_import();
     test_run_command_with_konfig();
     test_load_cache_with_different_pkg();
+    test_run_command_with_profile();
+    test_run_command_with_format();
+    test_run_command_with_fast_eval_and_jit();
+    test_run_command_with_limits_and_sandbox();
+    test_build_command_and_run_artifact();
+    #[cfg(feature = "llvm")]
+    test_run_command_with_emit();
 }
 
 fn test_run_command_with_import() {
@@ -227,6 +244,164 @@ fn test_run_command_with_konfig() {
     }
 }
 
+fn test_run_command_with_profile() {
+    let main_path = PathBuf::from("./src/test_data/profile/main.k");
+    let trace_path = env::temp_dir().join("kcl_profile_trace.json");
+    let matches = app().get_matches_from(&[
+        ROOT_CMD,
+        "run",
+        main_path.to_str().unwrap(),
+        "--profile_trace",
+        trace_path.to_str().unwrap(),
+    ]);
+    let matches = matches.subcommand_matches("run").unwrap();
+    let mut buf = Vec::new();
+    run_command(matches, &mut buf).unwrap();
+    // The profile report must not be mixed into the YAML output.
+    assert_eq!(
+        String::from_utf8(buf).unwrap(),
+        "alice:\n  name: Alice\n  age: 18\n"
+    );
+    let trace = fs::read_to_string(&trace_path).unwrap();
+    assert!(trace.contains("\"traceEvents\":["));
+    assert!(trace.contains("\"cat\":\"schema\""));
+    assert!(trace.contains("\"name\":\"__main__.Person\""));
+    fs::remove_file(trace_path).unwrap();
+}
+
+fn test_run_command_with_format() {
+    let main_path = PathBuf::from("./src/test_data/profile/main.k");
+    let cases = [
+        (
+            "json",
+            "{\n    \"alice\": {\n        \"name\": \"Alice\",\n        \"age\": 18\n    }\n}\n",
+        ),
+        ("toml", "[alice]\nname = \"Alice\"\nage = 18\n"),
+        ("properties", "alice.name=Alice\nalice.age=18\n"),
+        ("env", "ALICE_NAME=Alice\nALICE_AGE=18\n"),
+    ];
+    for (format, expected) in cases {
+        let matches = app().get_matches_from(&[
+            ROOT_CMD,
+            "run",
+            main_path.to_str().unwrap(),
+            "--format",
+            format,
+        ]);
+        let matches = matches.subcommand_matches("run").unwrap();
+        let mut buf = Vec::new();
+        run_command(matches, &mut buf).unwrap();
+        assert_eq!(String::from_utf8(buf).unwrap(), expected);
+    }
+    // Unknown output formats are rejected by the argument parser.
+    assert!(app()
+        .try_get_matches_from(&[ROOT_CMD, "run", "main.k", "--format", "xml"])
+        .is_err());
+}
+
+fn test_run_command_with_fast_eval_and_jit() {
+    let main_path = PathBuf::from("./src/test_data/profile/main.k");
+    for flag in ["--fast_eval", "--jit"] {
+        let matches = app().get_matches_from(&[ROOT_CMD, "run", main_path.to_str().unwrap(), flag]);
+        let matches = matches.subcommand_matches("run").unwrap();
+        let mut buf = Vec::new();
+        run_command(matches, &mut buf).unwrap();
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            "alice:\n  name: Alice\n  age: 18\n"
+        );
+    }
+    // The fast evaluator and the JIT can not be used at the same time.
+    assert!(app()
+        .try_get_matches_from(&[ROOT_CMD, "run", "main.k", "--fast_eval", "--jit"])
+        .is_err());
+}
+
+fn test_run_command_with_limits_and_sandbox() {
+    let test_data_path = PathBuf::from("../runner/src/test_datas");
+    let run = |path: PathBuf, flags: &[&str]| {
+        let path = path.canonicalize().unwrap();
+        let mut argv = vec![ROOT_CMD, "run", path.to_str().unwrap()];
+        argv.extend_from_slice(flags);
+        let matches = app().get_matches_from(&argv);
+        let matches = matches.subcommand_matches("run").unwrap();
+        let mut buf = Vec::new();
+        run_command(matches, &mut buf).map(|_| String::from_utf8(buf).unwrap())
+    };
+    let recursion_path = test_data_path.join("limits").join("recursion.k");
+    assert!(run(recursion_path.clone(), &["--max_call_depth", "50"]).is_err());
+    assert_eq!(
+        run(recursion_path, &["--max_call_depth", "0"]).unwrap(),
+        "a: 0\n"
+    );
+    let sandbox_path = test_data_path.join("sandbox").join("main.k");
+    assert!(run(sandbox_path, &["--sandbox"]).is_err());
+    // The limits must be non-negative integers.
+    assert!(app()
+        .try_get_matches_from(&[ROOT_CMD, "run", "main.k", "--timeout", "-1"])
+        .is_err());
+}
+
+fn test_build_command_and_run_artifact() {
+    let main_path = PathBuf::from("./src/test_data/profile/main.k");
+    let artifact_path = env::temp_dir().join("kcl_build_artifact.so");
+    let matches = app().get_matches_from(&[
+        ROOT_CMD,
+        "build",
+        main_path.to_str().unwrap(),
+        "-o",
+        artifact_path.to_str().unwrap(),
+    ]);
+    let matches = matches.subcommand_matches("build").unwrap();
+    build_command(matches).unwrap();
+    assert!(artifact_path.exists());
+
+    let matches = app().get_matches_from(&[
+        ROOT_CMD,
+        "run",
+        "--artifact",
+        artifact_path.to_str().unwrap(),
+        "-O",
+        "alice.age=20",
+    ]);
+    let matches = matches.subcommand_matches("run").unwrap();
+    let mut buf = Vec::new();
+    run_command(matches, &mut buf).unwrap();
+    assert_eq!(
+        String::from_utf8(buf).unwrap(),
+        "alice:\n  name: Alice\n  age: 20\n"
+    );
+    fs::remove_file(artifact_path).unwrap();
+}
+
+#[cfg(feature = "llvm")]
+fn test_run_command_with_emit() {
+    let main_path = PathBuf::from("./src/test_data/profile/main.k");
+    let emit_dir = env::temp_dir().join("kcl_emit_llvm_ir");
+    let matches = app().get_matches_from(&[
+        ROOT_CMD,
+        "run",
+        main_path.to_str().unwrap(),
+        "--emit",
+        "llvm-ir",
+        "--emit_dir",
+        emit_dir.to_str().unwrap(),
+    ]);
+    let matches = matches.subcommand_matches("run").unwrap();
+    let mut buf = Vec::new();
+    run_command(matches, &mut buf).unwrap();
+    assert!(emit_dir.join("__main__.ll").is_file());
+    assert_eq!(
+        String::from_utf8(buf).unwrap(),
+        "alice:\n  name: Alice\n  age: 18\n"
+    );
+    fs::remove_dir_all(emit_dir).unwrap();
+    // Unknown emit kinds are rejected by the argument parser.
+    assert!(app()
+        .try_get_matches_from(&[ROOT_CMD, "run", "main.k", "--emit", "asm"])
+        .is_err());
+}
+
 fn test_load_cache_with_different_pkg() {
     let main_path = PathBuf::from("./src/test_data/cache/main/main.k");
     let main_v1_path = PathBuf::from("./src/test_data/cache/main/main.k.v1");
@@ -13,6 +13,17 @@ pub enum OverflowOp {
     Mul,
 }
 
+/// Integer comparison predicates of the SSA icmp instruction.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum IntPredicate {
+    /// lhs == rhs
+    Equal,
+    /// lhs != rhs
+    NotEqual,
+    /// lhs < rhs, the operands are treated as unsigned integers.
+    UnsignedLessThan,
+}
+
 /// An identifier that specifies the address space that some operation
 /// should operate on. Special address spaces have an effect on code generation,
 /// depending on the target and the address spaces it implements.
@@ -0,0 +1,2523 @@
+// Copyright 2021 The KCL Authors. All rights reserved.
+
+use cranelift_codegen::cursor::{Cursor, CursorPosition, FuncCursor};
+use cranelift_codegen::ir::{
+    self, condcodes::IntCC, types, AbiParam, Block, FuncRef, GlobalValue, InstBuilder, MemFlags,
+    StackSlotData, StackSlotKind, TrapCode, UserFuncName,
+};
+use cranelift_codegen::isa::unwind::UnwindInfo;
+use cranelift_module::{DataDescription, DataId, FuncId, Linkage, Module};
+use indexmap::{IndexMap, IndexSet};
+use once_cell::sync::Lazy;
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::rc::Rc;
+use std::str;
+
+use kclvm_ast::ast;
+use kclvm_error::*;
+use kclvm_runtime::{ApiFunc, MAIN_PKG_PATH, PKG_PATH_PREFIX};
+use kclvm_sema::builtin;
+use kclvm_sema::plugin;
+
+use crate::codegen::abi::{AddressSpace, Align, IntPredicate};
+use crate::codegen::node::NodeCodeGen;
+use crate::codegen::schema::SchemaCodeGen;
+use crate::codegen::{error as kcl_error, BacktrackMeta, INNER_LEVEL};
+use crate::codegen::{
+    traits::*, GLOBAL_LEVEL, GLOBAL_VAL_ALIGNMENT, MODULE_NAME, PKG_INIT_FUNCTION_SUFFIX,
+};
+use crate::pkgpath_without_prefix;
+use crate::value;
+
+/// SCALAR_KEY denotes the temp scalar key for the global variable json plan process.
+const SCALAR_KEY: &str = "";
+/// The trap code of the unreachable blocks.
+const UNREACHABLE_TRAP_CODE: TrapCode = TrapCode::unwrap_user(1);
+/// The runtime function declarations, which are the same as the LLVM backend.
+static RUNTIME_DECLARATIONS: &str = include_str!("../../../../runtime/src/_kclvm.ll");
+
+/// The runtime function signatures parsed from the runtime declarations, where
+/// `None` denotes the pointer type and the return type `None` denotes void.
+static RUNTIME_SIGNATURES: Lazy<HashMap<&'static str, RuntimeSignature>> =
+    Lazy::new(parse_runtime_signatures);
+
+type RuntimeSignature = (Vec<Option<ir::Type>>, Option<Option<ir::Type>>);
+
+/// The compiler function result
+pub type CompileResult = Result<ir::Value, kcl_error::KCLError>;
+
+/// The variable storage, locals are stack slots of a function and globals
+/// are data objects of the module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Variable {
+    /// The stack slot and the unique id of the function it belongs to.
+    Local(ir::StackSlot, usize),
+    /// The global data object.
+    Global(DataId),
+}
+
+/// The compiler scope.
+#[derive(Debug, Default)]
+pub struct Scope {
+    /// Scalars denotes the expression statement values without attribute.
+    pub scalars: RefCell<Vec<Variable>>,
+    /// schema_scalar_idxdenotes whether a schema exists in the scalar list.
+    pub schema_scalar_idx: RefCell<usize>,
+    pub variables: RefCell<IndexMap<String, Variable>>,
+    pub closures: RefCell<IndexMap<String, Variable>>,
+    pub arguments: RefCell<IndexSet<String>>,
+}
+
+/// The function being generated, instructions are inserted at the position.
+pub struct FunctionState {
+    pub id: FuncId,
+    /// The unique id of the function generation, which is used to check
+    /// whether a local variable belongs to the function.
+    pub uid: usize,
+    pub func: ir::Function,
+    pub entry: Block,
+    position: CursorPosition,
+    func_refs: HashMap<FuncId, FuncRef>,
+    data_refs: HashMap<DataId, GlobalValue>,
+    /// The function and data addresses computed in the entry block, thus
+    /// they dominate all their uses.
+    func_addrs: HashMap<FuncId, ir::Value>,
+    data_addrs: HashMap<DataId, ir::Value>,
+    function_sig: Option<ir::SigRef>,
+}
+
+/// The function signature index of the code generator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FunctionSignature(usize);
+
+/// The Cranelift code generator
+pub struct CraneliftCodeGenContext<'ctx, M: Module> {
+    pub module: RefCell<M>,
+    /// The unwind information of the defined functions.
+    pub unwind_infos: RefCell<Vec<(FuncId, UnwindInfo)>>,
+    pub program: &'ctx ast::Program,
+    pub pkg_scopes: RefCell<HashMap<String, Vec<Rc<Scope>>>>,
+    pub functions: RefCell<Vec<FunctionState>>,
+    pub imported: RefCell<HashSet<String>>,
+    pub local_vars: RefCell<HashSet<String>>,
+    pub schema_stack: RefCell<Vec<value::SchemaType>>,
+    pub lambda_stack: RefCell<Vec<bool>>,
+    pub schema_expr_stack: RefCell<Vec<()>>,
+    pub pkgpath_stack: RefCell<Vec<String>>,
+    pub filename_stack: RefCell<Vec<String>>,
+    pub target_vars: RefCell<Vec<String>>,
+    pub global_strings: RefCell<HashMap<String, DataId>>,
+    pub global_vars: RefCell<IndexMap<String, IndexMap<String, DataId>>>,
+    pub current_line: RefCell<u64>,
+    pub handler: RefCell<Handler>,
+    // Schema attr backtrack meta
+    pub backtrack_meta: RefCell<Option<BacktrackMeta>>,
+    /// Import names mapping
+    pub import_names: IndexMap<String, IndexMap<String, String>>,
+    // No link mode
+    pub no_link: bool,
+    /// The native pointer type of the target.
+    pub pointer_type: ir::Type,
+    /// The declared functions including the runtime functions.
+    declared_functions: RefCell<HashMap<String, FuncId>>,
+    /// The symbol names of the declared functions and globals.
+    symbols: RefCell<HashSet<String>>,
+    function_names: RefCell<HashMap<FuncId, String>>,
+    signatures: RefCell<Vec<ir::Signature>>,
+    function_uid: Cell<usize>,
+}
+
+impl CodeGenObject for ir::Value {}
+
+impl CodeGenObject for ir::Type {}
+
+impl<'ctx, M: Module> BackendTypes for CraneliftCodeGenContext<'ctx, M> {
+    type Value = ir::Value;
+    type Type = ir::Type;
+    type BasicBlock = Block;
+    type Function = FuncId;
+    type FunctionLet = FunctionSignature;
+}
+
+impl<'ctx, M: Module> BuilderMethods for CraneliftCodeGenContext<'ctx, M> {
+    /// SSA append a basic block named `name`.
+    #[inline]
+    fn append_block(&self, _name: &str) -> Self::BasicBlock {
+        let mut functions = self.functions.borrow_mut();
+        let state = functions.last_mut().expect(kcl_error::INTERNAL_ERROR_MSG);
+        let block = state.func.dfg.make_block();
+        state.func.layout.append_block(block);
+        block
+    }
+    /// SSA switch to the block.
+    #[inline]
+    fn switch_to_block(&self, block: Self::BasicBlock) {
+        let mut functions = self.functions.borrow_mut();
+        let state = functions.last_mut().expect(kcl_error::INTERNAL_ERROR_MSG);
+        state.position = CursorPosition::After(block);
+    }
+    /// SSA alloca instruction.
+    #[inline]
+    fn alloca(&self, ty: Self::Type, _name: &str, align: Option<Align>) -> Self::Value {
+        let align = align
+            .map(|a| a.bytes())
+            .unwrap_or(ty.bytes() as u64)
+            .trailing_zeros() as u8;
+        let pointer_type = self.pointer_type;
+        self.with_cursor(|cursor| {
+            let slot = cursor.func.create_sized_stack_slot(StackSlotData::new(
+                StackSlotKind::ExplicitSlot,
+                ty.bytes(),
+                align,
+            ));
+            cursor.ins().stack_addr(pointer_type, slot, 0)
+        })
+    }
+    /// SSA array alloca instruction.
+    #[inline]
+    fn array_alloca(
+        &self,
+        ty: Self::Type,
+        len: u32,
+        _name: &str,
+        align: Align,
+    ) -> Result<Self::Value, kcl_error::KCLError> {
+        let size = ty
+            .bytes()
+            .checked_mul(len)
+            .ok_or_else(|| unsupported_error("stack allocations larger than 4 GiB"))?;
+        let align = align.bytes().trailing_zeros() as u8;
+        let pointer_type = self.pointer_type;
+        Ok(self.with_cursor(|cursor| {
+            let slot = cursor.func.create_sized_stack_slot(StackSlotData::new(
+                StackSlotKind::ExplicitSlot,
+                size,
+                align,
+            ));
+            cursor.ins().stack_addr(pointer_type, slot, 0)
+        }))
+    }
+    /// SSA ret instruction.
+    #[inline]
+    fn ret_void(&self) {
+        self.with_cursor(|cursor| cursor.ins().return_(&[]));
+    }
+    /// SSA ret instruction with returned value.
+    #[inline]
+    fn ret(&self, v: Self::Value) {
+        self.with_cursor(|cursor| {
+            let ty = cursor.func.signature.returns[0].value_type;
+            let v = coerce(cursor, v, ty);
+            cursor.ins().return_(&[v])
+        });
+    }
+    /// SSA br instruction.
+    #[inline]
+    fn br(&self, dest: Self::BasicBlock) {
+        self.with_cursor(|cursor| cursor.ins().jump(dest, &[]));
+    }
+    /// SSA br instruction with the block arguments.
+    #[inline]
+    fn br_with_args(&self, dest: Self::BasicBlock, args: &[Self::Value]) {
+        self.with_cursor(|cursor| cursor.ins().jump(dest, args));
+    }
+    /// Append a parameter to the block and return it, which is used to
+    /// merge the values from the predecessors like a phi node.
+    fn append_block_param(&self, block: Self::BasicBlock) -> Self::Value {
+        let mut functions = self.functions.borrow_mut();
+        let state = functions.last_mut().expect(kcl_error::INTERNAL_ERROR_MSG);
+        state.func.dfg.append_block_param(block, self.pointer_type)
+    }
+    /// SSA cond br instruction.
+    #[inline]
+    fn cond_br(&self, cond: Self::Value, then_bb: Self::BasicBlock, else_bb: Self::BasicBlock) {
+        self.with_cursor(|cursor| cursor.ins().brif(cond, then_bb, &[], else_bb, &[]));
+    }
+    /// SSA select instruction.
+    #[inline]
+    fn select(
+        &self,
+        cond: Self::Value,
+        then_val: Self::Value,
+        else_val: Self::Value,
+    ) -> Self::Value {
+        self.with_cursor(|cursor| cursor.ins().select(cond, then_val, else_val))
+    }
+    /// SSA icmp instruction.
+    #[inline]
+    fn icmp(&self, op: IntPredicate, lhs: Self::Value, rhs: Self::Value) -> Self::Value {
+        let cond = match op {
+            IntPredicate::Equal => IntCC::Equal,
+            IntPredicate::NotEqual => IntCC::NotEqual,
+            IntPredicate::UnsignedLessThan => IntCC::UnsignedLessThan,
+        };
+        self.with_cursor(|cursor| {
+            let ty = cursor.func.dfg.value_type(lhs);
+            let rhs = coerce(cursor, rhs, ty);
+            cursor.ins().icmp(cond, lhs, rhs)
+        })
+    }
+    /// SSA extract element instruction.
+    #[inline]
+    fn extract_element(&self, vec: Self::Value, idx: Self::Value) -> Self::Value {
+        let idx = self
+            .const_int_value(idx)
+            .expect("the element index must be a constant");
+        self.with_cursor(|cursor| cursor.ins().extractlane(vec, idx as u8))
+    }
+    /// SSA function invoke instruction.
+    #[inline]
+    fn invoke(
+        &self,
+        ty: Self::Type,
+        fn_value: Self::Function,
+        args: &[Self::Value],
+        then: Self::BasicBlock,
+        _catch: Self::BasicBlock,
+    ) -> Self::Value {
+        // Runtime errors are reported by panics, thus the unwind block is never reached.
+        let value = self.call(ty, fn_value, args);
+        self.br(then);
+        value
+    }
+    /// SSA function call instruction.
+    #[inline]
+    fn call(&self, _ty: Self::Type, fn_value: Self::Function, args: &[Self::Value]) -> Self::Value {
+        self.build_call_function(fn_value, args)
+            .expect(kcl_error::FUNCTION_RETURN_VALUE_NOT_FOUND_MSG)
+    }
+    /// SSA load instruction.
+    #[inline]
+    fn load(&self, ptr: Self::Value, _name: &str) -> Self::Value {
+        let pointer_type = self.pointer_type;
+        self.with_cursor(|cursor| cursor.ins().load(pointer_type, MemFlags::trusted(), ptr, 0))
+    }
+    /// SSA store instruction.
+    #[inline]
+    fn store(&self, ptr: Self::Value, val: Self::Value) {
+        self.with_cursor(|cursor| cursor.ins().store(MemFlags::trusted(), val, ptr, 0));
+    }
+    /// SSA gep instruction.
+    #[inline]
+    fn gep(&self, ty: Self::Type, ptr: Self::Value, indices: &[Self::Value]) -> Self::Value {
+        // Without the aggregate types, all the indices are element offsets of the pointer.
+        let size = ty.bytes() as i64;
+        let pointer_type = self.pointer_type;
+        self.with_cursor(|cursor| {
+            indices.iter().fold(ptr, |ptr, index| {
+                let index = coerce(cursor, *index, pointer_type);
+                let offset = cursor.ins().imul_imm(index, size);
+                cursor.ins().iadd(ptr, offset)
+            })
+        })
+    }
+    /// SSA inbounds gep instruction.
+    #[inline]
+    fn inbounds_gep(
+        &self,
+        ty: Self::Type,
+        ptr: Self::Value,
+        indices: &[Self::Value],
+    ) -> Self::Value {
+        self.gep(ty, ptr, indices)
+    }
+    /// SSA cast pointer to int.
+    #[inline]
+    fn ptr_to_int(&self, val: Self::Value, dest_ty: Self::Type) -> Self::Value {
+        self.with_cursor(|cursor| coerce(cursor, val, dest_ty))
+    }
+    /// SSA cast int to pointer.
+    #[inline]
+    fn int_to_ptr(&self, val: Self::Value, dest_ty: Self::Type) -> Self::Value {
+        self.with_cursor(|cursor| coerce(cursor, val, dest_ty))
+    }
+    /// SSA bit cast.
+    #[inline]
+    fn bit_cast(&self, val: Self::Value, dest_ty: Self::Type) -> Self::Value {
+        self.with_cursor(|cursor| {
+            if cursor.func.dfg.value_type(val) == dest_ty {
+                val
+            } else {
+                cursor.ins().bitcast(dest_ty, MemFlags::new(), val)
+            }
+        })
+    }
+    /// SSA int cast.
+    #[inline]
+    fn int_cast(&self, val: Self::Value, dest_ty: Self::Type, is_signed: bool) -> Self::Value {
+        self.with_cursor(|cursor| {
+            let ty = cursor.func.dfg.value_type(val);
+            if ty.bits() < dest_ty.bits() && is_signed {
+                cursor.ins().sextend(dest_ty, val)
+            } else {
+                coerce(cursor, val, dest_ty)
+            }
+        })
+    }
+    /// SSA pointer cast.
+    #[inline]
+    fn ptr_cast(&self, val: Self::Value, _dest_ty: Self::Type) -> Self::Value {
+        // All the pointers have the same native pointer type.
+        val
+    }
+    /// Get the nth parameter of the current function.
+    #[inline]
+    fn function_param(&self, index: usize) -> Self::Value {
+        let functions = self.functions.borrow();
+        let state = functions.last().expect(kcl_error::INTERNAL_ERROR_MSG);
+        state.func.dfg.block_params(state.entry)[index]
+    }
+    /// Lookup a known function named `name`.
+    fn lookup_function(&self, name: &str) -> Self::Function {
+        if let Some(function) = self.declared_functions.borrow().get(name) {
+            return *function;
+        }
+        let signature = self
+            .runtime_signature(name)
+            .unwrap_or_else(|| panic!("known function {} not found", name));
+        let function = self
+            .module
+            .borrow_mut()
+            .declare_function(name, Linkage::Import, &signature)
+            .expect(kcl_error::CODE_GEN_ERROR_MSG);
+        self.symbols.borrow_mut().insert(name.to_string());
+        self.function_names
+            .borrow_mut()
+            .insert(function, name.to_string());
+        self.declared_functions
+            .borrow_mut()
+            .insert(name.to_string(), function);
+        function
+    }
+    /// Add a function named `name`.
+    fn add_function(&self, name: &str) -> Self::Function {
+        let signature = self.signature(self.function_type());
+        self.declare_function(name, Linkage::Local, &signature)
+    }
+}
+
+/* Value methods */
+
+impl<'ctx, M: Module> ValueMethods for CraneliftCodeGenContext<'ctx, M> {
+    /// Construct a 64-bit int value using i64
+    fn int_value(&self, v: i64) -> Self::Value {
+        let value = self.iconst(types::I64, v);
+        self.build_call(
+            &ApiFunc::kclvm_value_Int.name(),
+            &[self.current_runtime_ctx_ptr(), value],
+        )
+    }
+
+    /// Construct a 64-bit float value using f64
+    fn float_value(&self, v: f64) -> Self::Value {
+        let value = self.with_cursor(|cursor| cursor.ins().f64const(v));
+        self.build_call(
+            &ApiFunc::kclvm_value_Float.name(),
+            &[self.current_runtime_ctx_ptr(), value],
+        )
+    }
+
+    /// Construct a string value using &str
+    fn string_value(&self, v: &str) -> Self::Value {
+        let string_ptr_value = self.native_global_string(v, "");
+        self.build_call(
+            &ApiFunc::kclvm_value_Str.name(),
+            &[self.current_runtime_ctx_ptr(), string_ptr_value],
+        )
+    }
+
+    /// Construct a bool value
+    fn bool_value(&self, v: bool) -> Self::Value {
+        let value = self.native_i8(v as i8);
+        self.build_call(&ApiFunc::kclvm_value_Bool.name(), &[value])
+    }
+
+    /// Construct a None value
+    fn none_value(&self) -> Self::Value {
+        self.build_call(
+            &ApiFunc::kclvm_value_None.name(),
+            &[self.current_runtime_ctx_ptr()],
+        )
+    }
+
+    /// Construct a Undefined value
+    fn undefined_value(&self) -> Self::Value {
+        self.build_call(
+            &ApiFunc::kclvm_value_Undefined.name(),
+            &[self.current_runtime_ctx_ptr()],
+        )
+    }
+
+    /// Construct a empty kcl list value
+    fn list_value(&self) -> Self::Value {
+        self.build_call(
+            &ApiFunc::kclvm_value_List.name(),
+            &[self.current_runtime_ctx_ptr()],
+        )
+    }
+
+    /// Construct a list value with `n` elements
+    fn list_values(&self, values: &[Self::Value]) -> Self::Value {
+        let mut args = vec![self.current_runtime_ctx_ptr()];
+        args.extend_from_slice(values);
+        self.build_call(
+            &format!("{}{}", ApiFunc::kclvm_value_List.name(), values.len()),
+            &args,
+        )
+    }
+
+    /// Construct a empty kcl dict value.
+    fn dict_value(&self) -> Self::Value {
+        self.build_call(
+            &ApiFunc::kclvm_value_Dict.name(),
+            &[self.current_runtime_ctx_ptr()],
+        )
+    }
+
+    /// Construct a unit value
+    fn unit_value(&self, v: f64, raw: i64, unit: &str) -> Self::Value {
+        let value = self.with_cursor(|cursor| cursor.ins().f64const(v));
+        let raw = self.iconst(types::I64, raw);
+        let unit_native_str = self.native_global_string(unit, "");
+        self.build_call(
+            &ApiFunc::kclvm_value_Unit.name(),
+            &[self.current_runtime_ctx_ptr(), value, raw, unit_native_str],
+        )
+    }
+    /// Construct a function value using a native function.
+    fn function_value(&self, function: FuncId) -> Self::Value {
+        let func_name = self.function_name(function);
+        let func_name_ptr = self.native_global_string(&func_name, &func_name);
+        let lambda_fn_ptr = self.function_ptr(function);
+        self.build_call(
+            &ApiFunc::kclvm_value_Function_using_ptr.name(),
+            &[self.current_runtime_ctx_ptr(), lambda_fn_ptr, func_name_ptr],
+        )
+    }
+    /// Get the native function pointer of the function id.
+    fn function_ptr(&self, function: Self::Function) -> Self::Value {
+        let func_ref = self.func_ref(function);
+        let pointer_type = self.pointer_type;
+        let mut functions = self.functions.borrow_mut();
+        let state = functions.last_mut().expect(kcl_error::INTERNAL_ERROR_MSG);
+        let entry = state.entry;
+        *state.func_addrs.entry(function).or_insert_with(|| {
+            FuncCursor::new(&mut state.func)
+                .at_first_insertion_point(entry)
+                .ins()
+                .func_addr(pointer_type, func_ref)
+        })
+    }
+    /// Construct a closure function value with the closure variable.
+    fn closure_value(&self, function: FuncId, closure: Self::Value) -> Self::Value {
+        let func_name = self.function_name(function);
+        let func_name_ptr = self.native_global_string(&func_name, &func_name);
+        let fn_ptr = self.function_ptr(function);
+        self.build_call(
+            &ApiFunc::kclvm_value_Function.name(),
+            &[
+                self.current_runtime_ctx_ptr(),
+                fn_ptr,
+                closure,
+                func_name_ptr,
+                self.native_i8_zero(),
+            ],
+        )
+    }
+    /// Construct a schema function value using native functions.
+    fn struct_function_value(&self, functions: &[FuncId], runtime_type: &str) -> Self::Value {
+        if functions.is_empty() {
+            return self.none_value();
+        }
+        let schema_body_fn_ptr = self.function_ptr(functions[0]);
+        let check_block_fn_ptr = if functions.len() > 1 {
+            self.function_ptr(functions[1])
+        } else {
+            self.iconst(self.pointer_type, 0)
+        };
+        let runtime_type_native_str = self.native_global_string_value(runtime_type);
+        self.build_call(
+            &ApiFunc::kclvm_value_schema_function.name(),
+            &[
+                self.current_runtime_ctx_ptr(),
+                schema_body_fn_ptr,
+                check_block_fn_ptr,
+                runtime_type_native_str,
+            ],
+        )
+    }
+    /// Construct a builtin function value using the function name.
+    fn builtin_function_value(&self, function_name: &str) -> Self::Value {
+        let function = self.lookup_function(function_name);
+        self.function_value(function)
+    }
+    /// Get a global value pointer named `name`.
+    fn global_value_ptr(&self, name: &str) -> Self::Value {
+        let global_var = self.new_global_kcl_value_ptr(name);
+        self.variable_ptr(global_var)
+    }
+    /// Get the runtime context pointer of the current function, which is
+    /// the first parameter of all the KCL functions.
+    fn current_runtime_ctx_ptr(&self) -> Self::Value {
+        self.function_param(0)
+    }
+}
+
+impl<'ctx, M: Module> ValueCalculationMethods for CraneliftCodeGenContext<'ctx, M> {
+    /// lhs + rhs
+    fn add(&self, lhs: Self::Value, rhs: Self::Value) -> Self::Value {
+        self.build_call(
+            &ApiFunc::kclvm_value_op_add.name(),
+            &[self.current_runtime_ctx_ptr(), lhs, rhs],
+        )
+    }
+    /// lhs - rhs
+    fn sub(&self, lhs: Self::Value, rhs: Self::Value) -> Self::Value {
+        self.build_call(
+            &ApiFunc::kclvm_value_op_sub.name(),
+            &[self.current_runtime_ctx_ptr(), lhs, rhs],
+        )
+    }
+    /// lhs * rhs
+    fn mul(&self, lhs: Self::Value, rhs: Self::Value) -> Self::Value {
+        self.build_call(
+            &ApiFunc::kclvm_value_op_mul.name(),
+            &[self.current_runtime_ctx_ptr(), lhs, rhs],
+        )
+    }
+    /// lhs / rhs
+    fn div(&self, lhs: Self::Value, rhs: Self::Value) -> Self::Value {
+        self.build_call(
+            &ApiFunc::kclvm_value_op_div.name(),
+            &[self.current_runtime_ctx_ptr(), lhs, rhs],
+        )
+    }
+    /// lhs // rhs
+    fn floor_div(&self, lhs: Self::Value, rhs: Self::Value) -> Self::Value {
+        self.build_call(
+            &ApiFunc::kclvm_value_op_floor_div.name(),
+            &[self.current_runtime_ctx_ptr(), lhs, rhs],
+        )
+    }
+    /// lhs % rhs
+    fn r#mod(&self, lhs: Self::Value, rhs: Self::Value) -> Self::Value {
+        self.build_call(
+            &ApiFunc::kclvm_value_op_mod.name(),
+            &[self.current_runtime_ctx_ptr(), lhs, rhs],
+        )
+    }
+    /// lhs ** rhs
+    fn pow(&self, lhs: Self::Value, rhs: Self::Value) -> Self::Value {
+        self.build_call(
+            &ApiFunc::kclvm_value_op_pow.name(),
+            &[self.current_runtime_ctx_ptr(), lhs, rhs],
+        )
+    }
+    /// lhs << rhs
+    fn bit_lshift(&self, lhs: Self::Value, rhs: Self::Value) -> Self::Value {
+        self.build_call(
+            &ApiFunc::kclvm_value_op_bit_lshift.name(),
+            &[self.current_runtime_ctx_ptr(), lhs, rhs],
+        )
+    }
+    /// lhs >> rhs
+    fn bit_rshift(&self, lhs: Self::Value, rhs: Self::Value) -> Self::Value {
+        self.build_call(
+            &ApiFunc::kclvm_value_op_bit_rshift.name(),
+            &[self.current_runtime_ctx_ptr(), lhs, rhs],
+        )
+    }
+    /// lhs & rhs
+    fn bit_and(&self, lhs: Self::Value, rhs: Self::Value) -> Self::Value {
+        self.build_call(
+            &ApiFunc::kclvm_value_op_bit_and.name(),
+            &[self.current_runtime_ctx_ptr(), lhs, rhs],
+        )
+    }
+    /// lhs | rhs
+    fn bit_or(&self, lhs: Self::Value, rhs: Self::Value) -> Self::Value {
+        self.build_call(
+            &ApiFunc::kclvm_value_op_bit_or.name(),
+            &[self.current_runtime_ctx_ptr(), lhs, rhs],
+        )
+    }
+    /// lhs ^ rhs
+    fn bit_xor(&self, lhs: Self::Value, rhs: Self::Value) -> Self::Value {
+        self.build_call(
+            &ApiFunc::kclvm_value_op_bit_xor.name(),
+            &[self.current_runtime_ctx_ptr(), lhs, rhs],
+        )
+    }
+    /// lhs and rhs
+    fn logic_and(&self, lhs: Self::Value, rhs: Self::Value) -> Self::Value {
+        self.build_call(
+            &ApiFunc::kclvm_value_logic_and.name(),
+            &[self.current_runtime_ctx_ptr(), lhs, rhs],
+        )
+    }
+    /// lhs or rhs
+    fn logic_or(&self, lhs: Self::Value, rhs: Self::Value) -> Self::Value {
+        self.build_call(
+            &ApiFunc::kclvm_value_logic_or.name(),
+            &[self.current_runtime_ctx_ptr(), lhs, rhs],
+        )
+    }
+    /// lhs == rhs
+    fn cmp_equal_to(&self, lhs: Self::Value, rhs: Self::Value) -> Self::Value {
+        self.build_call(
+            &ApiFunc::kclvm_value_cmp_equal_to.name(),
+            &[self.current_runtime_ctx_ptr(), lhs, rhs],
+        )
+    }
+    /// lhs != rhs
+    fn cmp_not_equal_to(&self, lhs: Self::Value, rhs: Self::Value) -> Self::Value {
+        self.build_call(
+            &ApiFunc::kclvm_value_cmp_not_equal_to.name(),
+            &[self.current_runtime_ctx_ptr(), lhs, rhs],
+        )
+    }
+    /// lhs > rhs
+    fn cmp_greater_than(&self, lhs: Self::Value, rhs: Self::Value) -> Self::Value {
+        self.build_call(
+            &ApiFunc::kclvm_value_cmp_greater_than.name(),
+            &[self.current_runtime_ctx_ptr(), lhs, rhs],
+        )
+    }
+    /// lhs >= rhs
+    fn cmp_greater_than_or_equal(&self, lhs: Self::Value, rhs: Self::Value) -> Self::Value {
+        self.build_call(
+            &ApiFunc::kclvm_value_cmp_greater_than_or_equal.name(),
+            &[self.current_runtime_ctx_ptr(), lhs, rhs],
+        )
+    }
+    /// lhs < rhs
+    fn cmp_less_than(&self, lhs: Self::Value, rhs: Self::Value) -> Self::Value {
+        self.build_call(
+            &ApiFunc::kclvm_value_cmp_less_than.name(),
+            &[self.current_runtime_ctx_ptr(), lhs, rhs],
+        )
+    }
+    /// lhs <= rhs
+    fn cmp_less_than_or_equal(&self, lhs: Self::Value, rhs: Self::Value) -> Self::Value {
+        self.build_call(
+            &ApiFunc::kclvm_value_cmp_less_than_or_equal.name(),
+            &[self.current_runtime_ctx_ptr(), lhs, rhs],
+        )
+    }
+    /// lhs as rhs
+    fn r#as(&self, lhs: Self::Value, rhs: Self::Value) -> Self::Value {
+        self.build_call(
+            &ApiFunc::kclvm_value_as.name(),
+            &[self.current_runtime_ctx_ptr(), lhs, rhs],
+        )
+    }
+    /// lhs is rhs
+    fn is(&self, lhs: Self::Value, rhs: Self::Value) -> Self::Value {
+        self.build_call(
+            &ApiFunc::kclvm_value_is.name(),
+            &[self.current_runtime_ctx_ptr(), lhs, rhs],
+        )
+    }
+    /// lhs is not rhs
+    fn is_not(&self, lhs: Self::Value, rhs: Self::Value) -> Self::Value {
+        self.build_call(
+            &ApiFunc::kclvm_value_is_not.name(),
+            &[self.current_runtime_ctx_ptr(), lhs, rhs],
+        )
+    }
+    /// lhs in rhs
+    fn r#in(&self, lhs: Self::Value, rhs: Self::Value) -> Self::Value {
+        self.build_call(
+            &ApiFunc::kclvm_value_in.name(),
+            &[self.current_runtime_ctx_ptr(), lhs, rhs],
+        )
+    }
+    /// lhs not in rhs
+    fn not_in(&self, lhs: Self::Value, rhs: Self::Value) -> Self::Value {
+        self.build_call(
+            &ApiFunc::kclvm_value_not_in.name(),
+            &[self.current_runtime_ctx_ptr(), lhs, rhs],
+        )
+    }
+}
+
+impl<'ctx, M: Module> DerivedValueCalculationMethods for CraneliftCodeGenContext<'ctx, M> {
+    /// Value subscript a[b]
+    #[inline]
+    fn value_subscript(&self, value: Self::Value, item: Self::Value) -> Self::Value {
+        self.build_call(
+            &ApiFunc::kclvm_value_subscr.name(),
+            &[self.current_runtime_ctx_ptr(), value, item],
+        )
+    }
+    /// Value is truth function, return i1 value.
+    fn value_is_truthy(&self, value: Self::Value) -> Self::Value {
+        let is_truth = self.build_call(&ApiFunc::kclvm_value_is_truthy.name(), &[value]);
+        self.with_cursor(|cursor| cursor.ins().icmp_imm(IntCC::NotEqual, is_truth, 0))
+    }
+    /// Value deep copy
+    #[inline]
+    fn value_deep_copy(&self, value: Self::Value) -> Self::Value {
+        self.build_call(
+            &ApiFunc::kclvm_value_deep_copy.name(),
+            &[self.current_runtime_ctx_ptr(), value],
+        )
+    }
+    /// value_union unions two collection elements.
+    #[inline]
+    fn value_union(&self, lhs: Self::Value, rhs: Self::Value) {
+        self.build_void_call(
+            &ApiFunc::kclvm_value_union.name(),
+            &[self.current_runtime_ctx_ptr(), lhs, rhs],
+        );
+    }
+    // List get the item using the index.
+    #[inline]
+    fn list_get(&self, list: Self::Value, index: Self::Value) -> Self::Value {
+        self.build_call(
+            &ApiFunc::kclvm_list_get.name(),
+            &[self.current_runtime_ctx_ptr(), list, index],
+        )
+    }
+    // List set the item using the index.
+    #[inline]
+    fn list_set(&self, list: Self::Value, index: Self::Value, value: Self::Value) {
+        self.build_void_call(&ApiFunc::kclvm_list_set.name(), &[list, index, value])
+    }
+    // List slice.
+    #[inline]
+    fn list_slice(
+        &self,
+        list: Self::Value,
+        start: Self::Value,
+        stop: Self::Value,
+        step: Self::Value,
+    ) -> Self::Value {
+        self.build_call(
+            &ApiFunc::kclvm_value_slice.name(),
+            &[self.current_runtime_ctx_ptr(), list, start, stop, step],
+        )
+    }
+    /// Append a item into the list.
+    #[inline]
+    fn list_append(&self, list: Self::Value, item: Self::Value) {
+        self.build_void_call(&ApiFunc::kclvm_list_append.name(), &[list, item])
+    }
+    /// Append a list item and unpack it into the list.
+    #[inline]
+    fn list_append_unpack(&self, list: Self::Value, item: Self::Value) {
+        self.build_void_call(&ApiFunc::kclvm_list_append_unpack.name(), &[list, item]);
+    }
+    /// Runtime list value pop
+    #[inline]
+    fn list_pop(&self, list: Self::Value) -> Self::Value {
+        self.build_call(
+            &ApiFunc::kclvm_list_pop.name(),
+            &[self.current_runtime_ctx_ptr(), list],
+        )
+    }
+    /// Runtime list pop the first value
+    #[inline]
+    fn list_pop_first(&self, list: Self::Value) -> Self::Value {
+        self.build_call(
+            &ApiFunc::kclvm_list_pop_first.name(),
+            &[self.current_runtime_ctx_ptr(), list],
+        )
+    }
+    /// List clear value.
+    #[inline]
+    fn list_clear(&self, list: Self::Value) {
+        self.build_void_call(&ApiFunc::kclvm_list_clear.name(), &[list])
+    }
+    /// Return number of occurrences of the list value.
+    #[inline]
+    fn list_count(&self, list: Self::Value, item: Self::Value) -> Self::Value {
+        self.build_call(
+            &ApiFunc::kclvm_list_count.name(),
+            &[self.current_runtime_ctx_ptr(), list, item],
+        )
+    }
+    /// Return first index of the list value. Panic if the value is not present.
+    #[inline]
+    fn list_find(&self, list: Self::Value, item: Self::Value) -> Self::Value {
+        self.build_call(
+            &ApiFunc::kclvm_list_find.name(),
+            &[self.current_runtime_ctx_ptr(), list, item],
+        )
+    }
+    /// Insert object before index of the list value.
+    #[inline]
+    fn list_insert(&self, list: Self::Value, index: Self::Value, value: Self::Value) {
+        self.build_void_call(&ApiFunc::kclvm_list_insert.name(), &[list, index, value])
+    }
+    /// List length.
+    #[inline]
+    fn list_len(&self, list: Self::Value) -> Self::Value {
+        self.build_call(&ApiFunc::kclvm_list_len.name(), &[list])
+    }
+    /// Dict get the value of the key.
+    #[inline]
+    fn dict_get(&self, dict: Self::Value, key: Self::Value) -> Self::Value {
+        self.build_call(
+            &ApiFunc::kclvm_dict_get_value.name(),
+            &[self.current_runtime_ctx_ptr(), dict, key],
+        )
+    }
+    /// Dict set the value of the key.
+    #[inline]
+    fn dict_set(&self, dict: Self::Value, key: Self::Value, value: Self::Value) {
+        self.build_void_call(
+            &ApiFunc::kclvm_dict_set_value.name(),
+            &[self.current_runtime_ctx_ptr(), dict, key, value],
+        )
+    }
+    /// Return all dict keys.
+    #[inline]
+    fn dict_keys(&self, dict: Self::Value) -> Self::Value {
+        self.build_call(
+            &ApiFunc::kclvm_dict_keys.name(),
+            &[self.current_runtime_ctx_ptr(), dict],
+        )
+    }
+    /// Return all dict values.
+    #[inline]
+    fn dict_values(&self, dict: Self::Value) -> Self::Value {
+        self.build_call(
+            &ApiFunc::kclvm_dict_values.name(),
+            &[self.current_runtime_ctx_ptr(), dict],
+        )
+    }
+    /// Dict clear value.
+    #[inline]
+    fn dict_clear(&self, dict: Self::Value) {
+        self.build_void_call(&ApiFunc::kclvm_dict_clear.name(), &[dict])
+    }
+    /// Dict pop the value of the key.
+    #[inline]
+    fn dict_pop(&self, dict: Self::Value, key: Self::Value) -> Self::Value {
+        self.build_call(&ApiFunc::kclvm_dict_remove.name(), &[dict, key])
+    }
+    /// Dict length.
+    #[inline]
+    fn dict_len(&self, dict: Self::Value) -> Self::Value {
+        self.build_call(&ApiFunc::kclvm_dict_len.name(), &[dict])
+    }
+    /// Insert a dict entry including key, value, op and insert_index into the dict,
+    /// and the type of key is `&str`
+    #[inline]
+    fn dict_insert(
+        &self,
+        dict: Self::Value,
+        key: &str,
+        value: Self::Value,
+        op: i32,
+        insert_index: i32,
+    ) {
+        let name = self.native_global_string(key, "");
+        let op = self.native_int_value(op);
+        let insert_index = self.native_int_value(insert_index);
+        self.build_void_call(
+            &ApiFunc::kclvm_dict_insert.name(),
+            &[
+                self.current_runtime_ctx_ptr(),
+                dict,
+                name,
+                value,
+                op,
+                insert_index,
+            ],
+        );
+    }
+
+    /// Insert a dict entry including key, value, op and insert_index into the dict.
+    /// and the type of key is `Self::Value`
+    #[inline]
+    fn dict_insert_with_key_value(
+        &self,
+        dict: Self::Value,
+        key: Self::Value,
+        value: Self::Value,
+        op: i32,
+        insert_index: i32,
+    ) {
+        let op = self.native_int_value(op);
+        let insert_index = self.native_int_value(insert_index);
+        self.build_void_call(
+            &ApiFunc::kclvm_dict_insert_value.name(),
+            &[
+                self.current_runtime_ctx_ptr(),
+                dict,
+                key,
+                value,
+                op,
+                insert_index,
+            ],
+        );
+    }
+}
+
+impl<'ctx, M: Module> ValueCodeGen for CraneliftCodeGenContext<'ctx, M> {}
+
+/* Type methods */
+
+impl<'ctx, M: Module> BaseTypeMethods for CraneliftCodeGenContext<'ctx, M> {
+    /// Native i8 type
+    fn i8_type(&self) -> Self::Type {
+        types::I8
+    }
+    /// Native i16 type
+    fn i16_type(&self) -> Self::Type {
+        types::I16
+    }
+    /// Native i32 type
+    fn i32_type(&self) -> Self::Type {
+        types::I32
+    }
+    /// Native i64 type
+    fn i64_type(&self) -> Self::Type {
+        types::I64
+    }
+    /// Native i128 type
+    fn i128_type(&self) -> Self::Type {
+        types::I128
+    }
+    /// Native f32 type
+    fn f32_type(&self) -> Self::Type {
+        types::F32
+    }
+    /// Native f64 type
+    fn f64_type(&self) -> Self::Type {
+        types::F64
+    }
+    /// Native pointer type of `ty`.
+    #[inline]
+    fn ptr_type_to(&self, ty: Self::Type) -> Self::Type {
+        self.ptr_type_to_ext(ty, AddressSpace::DATA)
+    }
+    /// Native pointer type of `ty` with the address space, Cranelift pointers
+    /// are untyped integers with the native pointer width.
+    #[inline]
+    fn ptr_type_to_ext(&self, _ty: Self::Type, _address_space: AddressSpace) -> Self::Type {
+        self.pointer_type
+    }
+    /// Native array element type.
+    #[inline]
+    fn element_type(&self, ty: Self::Type) -> Self::Type {
+        if ty.is_vector() {
+            ty.lane_type()
+        } else {
+            panic!("element_type called on unsupported type {:?}", ty)
+        }
+    }
+    /// Returns the number of elements in `self` if it is a vector type.
+    #[inline]
+    fn vector_length(&self, ty: Self::Type) -> usize {
+        ty.lane_count() as usize
+    }
+    /// Retrieves the bit width of the float type `self`.
+    #[inline]
+    fn float_width(&self, ty: Self::Type) -> usize {
+        if ty.is_float() {
+            ty.bits() as usize
+        } else {
+            panic!("float_width called on unsupported type {:?}", ty)
+        }
+    }
+    /// Retrieves the bit width of the integer type `self`.
+    #[inline]
+    fn int_width(&self, ty: Self::Type) -> usize {
+        ty.bits() as usize
+    }
+    /// Get the value type.
+    #[inline]
+    fn val_type(&self, v: Self::Value) -> Self::Type {
+        let functions = self.functions.borrow();
+        let state = functions.last().expect(kcl_error::INTERNAL_ERROR_MSG);
+        state.func.dfg.value_type(v)
+    }
+    /// Native function type
+    #[inline]
+    fn function_let(&self, args: &[Self::Type], ret: Self::Type) -> Self::FunctionLet {
+        let mut signature = self.module.borrow().make_signature();
+        signature
+            .params
+            .extend(args.iter().map(|arg| AbiParam::new(*arg)));
+        signature.returns.push(AbiParam::new(ret));
+        let mut signatures = self.signatures.borrow_mut();
+        signatures.push(signature);
+        FunctionSignature(signatures.len() - 1)
+    }
+}
+
+impl<'ctx, M: Module> DerivedTypeMethods for CraneliftCodeGenContext<'ctx, M> {
+    /// Lookup type by the type name, all the runtime types are opaque and
+    /// only used through pointers.
+    #[inline]
+    fn get_intrinsic_type(&self, _name: &str) -> Self::Type {
+        self.pointer_type
+    }
+}
+
+impl<'ctx, M: Module> TypeCodeGen for CraneliftCodeGenContext<'ctx, M> {}
+
+impl<'ctx, M: Module> ProgramCodeGen for CraneliftCodeGenContext<'ctx, M> {
+    /// Current package path
+    fn current_pkgpath(&self) -> String {
+        self.pkgpath_stack
+            .borrow_mut()
+            .last()
+            .expect(kcl_error::INTERNAL_ERROR_MSG)
+            .to_string()
+    }
+
+    /// Current filename
+    fn current_filename(&self) -> String {
+        self.filename_stack
+            .borrow_mut()
+            .last()
+            .expect(kcl_error::INTERNAL_ERROR_MSG)
+            .to_string()
+    }
+    /// Init a scope named `pkgpath` with all builtin functions
+    fn init_scope(&self, pkgpath: &str) {
+        {
+            let mut pkg_scopes = self.pkg_scopes.borrow_mut();
+            if pkg_scopes.contains_key(pkgpath) {
+                return;
+            }
+            let scopes = vec![Rc::new(Scope::default())];
+            pkg_scopes.insert(String::from(pkgpath), scopes);
+        }
+        let msg = format!("pkgpath {} is not found", pkgpath);
+        // Init all global types including schema and rule
+        let module_list: &Vec<ast::Module> = if self.program.pkgs.contains_key(pkgpath) {
+            self.program.pkgs.get(pkgpath).expect(&msg)
+        } else if pkgpath.starts_with(kclvm_runtime::PKG_PATH_PREFIX)
+            && self.program.pkgs.contains_key(&pkgpath[1..])
+        {
+            self.program
+                .pkgs
+                .get(&pkgpath[1..])
+                .expect(kcl_error::INTERNAL_ERROR_MSG)
+        } else {
+            panic!("pkgpath {} not found", pkgpath);
+        };
+        for module in module_list {
+            for stmt in &module.body {
+                let name = match &stmt.node {
+                    ast::Stmt::Schema(schema_stmt) => schema_stmt.name.node.clone(),
+                    ast::Stmt::Rule(rule_stmt) => rule_stmt.name.node.clone(),
+                    _ => "".to_string(),
+                };
+                if !name.is_empty() {
+                    let name = name.as_str();
+                    let var_name = format!("${}.${}", pkgpath_without_prefix!(pkgpath), name);
+                    let global_var = self.new_global_kcl_value_ptr(&var_name);
+                    self.add_variable(name, global_var);
+                }
+            }
+        }
+        // Init all builtin functions
+        for symbol in builtin::BUILTIN_FUNCTION_NAMES {
+            let function_name =
+                format!("{}_{}", builtin::KCL_BUILTIN_FUNCTION_MANGLE_PREFIX, symbol);
+            let function_value = self.builtin_function_value(function_name.as_str());
+            let builtin_function_name = format!(
+                "{}_{}_{}",
+                builtin::BUILTIN_FUNCTION_PREFIX,
+                pkgpath_without_prefix!(pkgpath),
+                function_name
+            );
+            let global_var = self.new_global_kcl_value_ptr(&builtin_function_name);
+            self.store_to_variable(global_var, function_value);
+            self.add_variable(symbol, global_var);
+        }
+        self.enter_scope();
+    }
+
+    /// Get the scope level
+    fn scope_level(&self) -> usize {
+        let current_pkgpath = self.current_pkgpath();
+        let pkg_scopes = self.pkg_scopes.borrow_mut();
+        let msg = format!("pkgpath {} is not found", current_pkgpath);
+        let scopes = pkg_scopes.get(&current_pkgpath).expect(&msg);
+        // Sub the builtin global scope
+        scopes.len() - 1
+    }
+
+    /// Enter scope
+    fn enter_scope(&self) {
+        let current_pkgpath = self.current_pkgpath();
+        let mut pkg_scopes = self.pkg_scopes.borrow_mut();
+        let msg = format!("pkgpath {} is not found", current_pkgpath);
+        let scopes = pkg_scopes.get_mut(&current_pkgpath).expect(&msg);
+        let scope = Rc::new(Scope::default());
+        scopes.push(scope);
+    }
+
+    /// Leave scope
+    fn leave_scope(&self) {
+        let current_pkgpath = self.current_pkgpath();
+        let mut pkg_scopes = self.pkg_scopes.borrow_mut();
+        let msg = format!("pkgpath {} is not found", current_pkgpath);
+        let scopes = pkg_scopes.get_mut(&current_pkgpath).expect(&msg);
+        scopes.pop();
+    }
+}
+
+impl<'ctx, M: Module> CraneliftCodeGenContext<'ctx, M> {
+    /// New a CraneliftCodeGenContext using the Cranelift module and AST Program
+    pub fn new(
+        module: M,
+        program: &'ctx ast::Program,
+        import_names: IndexMap<String, IndexMap<String, String>>,
+        no_link: bool,
+    ) -> CraneliftCodeGenContext<'ctx, M> {
+        let pointer_type = module.target_config().pointer_type();
+        CraneliftCodeGenContext {
+            module: RefCell::new(module),
+            unwind_infos: RefCell::new(vec![]),
+            program,
+            pkg_scopes: RefCell::new(HashMap::new()),
+            functions: RefCell::new(vec![]),
+            imported: RefCell::new(HashSet::new()),
+            local_vars: RefCell::new(HashSet::new()),
+            schema_stack: RefCell::new(vec![]),
+            lambda_stack: RefCell::new(vec![false]),
+            schema_expr_stack: RefCell::new(vec![]),
+            pkgpath_stack: RefCell::new(vec![String::from(MAIN_PKG_PATH)]),
+            filename_stack: RefCell::new(vec![String::from("")]),
+            target_vars: RefCell::new(vec![String::from("")]),
+            global_strings: RefCell::new(HashMap::default()),
+            global_vars: RefCell::new(IndexMap::default()),
+            current_line: RefCell::new(0),
+            handler: RefCell::new(Handler::default()),
+            backtrack_meta: RefCell::new(None),
+            import_names,
+            no_link,
+            pointer_type,
+            declared_functions: RefCell::new(HashMap::new()),
+            symbols: RefCell::new(HashSet::new()),
+            function_names: RefCell::new(HashMap::new()),
+            signatures: RefCell::new(vec![]),
+            function_uid: Cell::new(0),
+        }
+    }
+
+    /// Generate the Cranelift IR of the AST program and define all the functions
+    /// into the module, returns the main or package init function id.
+    pub(crate) fn emit_code(&self) -> Result<FuncId, Box<dyn Error>> {
+        let has_main_pkg = self.program.pkgs.contains_key(MAIN_PKG_PATH);
+        let no_link_pkgpath = if self.no_link && !has_main_pkg {
+            // When compiling a pkgpath separately, only one pkgpath is required in the AST Program
+            assert!(self.program.pkgs.len() == 1);
+            let pkgpath = self
+                .program
+                .pkgs
+                .keys()
+                .next()
+                .expect(kcl_error::INTERNAL_ERROR_MSG);
+            Some(format!("{}{}", kclvm_runtime::PKG_PATH_PREFIX, pkgpath))
+        } else {
+            None
+        };
+        let function = match &no_link_pkgpath {
+            Some(pkgpath) => {
+                let function_name = format!(
+                    "${}.{}",
+                    pkgpath_without_prefix!(pkgpath),
+                    PKG_INIT_FUNCTION_SUFFIX
+                );
+                self.declare_function(&function_name, Linkage::Export, &self.init_signature())
+            }
+            None => {
+                let signature = self
+                    .signature(self.function_let(&[self.context_ptr_type()], self.pointer_type));
+                self.declare_function(MODULE_NAME, Linkage::Export, &signature)
+            }
+        };
+        self.push_function(function);
+        // Get the runtime context
+        let ctx_value = self.function_param(0);
+        if let Some(pkgpath) = &no_link_pkgpath {
+            self.pkgpath_stack.borrow_mut().push(pkgpath.clone());
+        }
+        if !self.import_names.is_empty() {
+            let import_names = self.dict_value();
+            for (k, v) in &self.import_names {
+                let map = self.dict_value();
+                for (pkgname, pkgpath) in v {
+                    self.dict_insert_override_item(
+                        map,
+                        pkgname,
+                        self.string_value(&format!("@{}", pkgpath)),
+                    );
+                }
+                self.dict_insert_override_item(import_names, k, map);
+            }
+            self.build_void_call(
+                &ApiFunc::kclvm_context_set_import_names.name(),
+                &[ctx_value, import_names],
+            );
+        }
+        if let Some(pkgpath) = &no_link_pkgpath {
+            // pkgs may not contains main pkg in no link mode
+            for modules in self.program.pkgs.values() {
+                self.pkgpath_stack.borrow_mut().push(pkgpath.clone());
+                // Init all builtin functions.
+                self.init_scope(pkgpath.as_str());
+                // Compile the ast module in the pkgpath.
+                for ast_module in modules {
+                    {
+                        self.filename_stack
+                            .borrow_mut()
+                            .push(ast_module.filename.clone());
+                    }
+                    self.compile_module_import_and_types(ast_module)?;
+                }
+                for ast_module in modules {
+                    {
+                        self.filename_stack
+                            .borrow_mut()
+                            .push(ast_module.filename.clone());
+                    }
+                    self.walk_stmts_except_import(&ast_module.body)?;
+                }
+            }
+            self.ret_void();
+        } else {
+            // Init scope and all builtin functions
+            self.init_scope(MAIN_PKG_PATH);
+            let main_pkg_modules = self
+                .program
+                .pkgs
+                .get(MAIN_PKG_PATH)
+                .expect(kcl_error::INTERNAL_ERROR_MSG);
+            // Compile the AST Program to Cranelift IR
+            for ast_module in main_pkg_modules {
+                {
+                    self.filename_stack
+                        .borrow_mut()
+                        .push(ast_module.filename.clone());
+                }
+                self.walk_module(ast_module)?;
+            }
+            // Get the JSON string including all global variables
+            let json_str_value = self.globals_to_json_str();
+            // Build a return in the current block
+            self.ret(json_str_value);
+        }
+        self.pop_function()?;
+        Ok(function)
+    }
+}
+
+impl<'ctx, M: Module> CraneliftCodeGenContext<'ctx, M> {
+    /// Run `f` with the cursor at the insertion position of the current function.
+    pub(crate) fn with_cursor<R>(&self, f: impl FnOnce(&mut FuncCursor) -> R) -> R {
+        let mut functions = self.functions.borrow_mut();
+        let state = functions.last_mut().expect(kcl_error::INTERNAL_ERROR_MSG);
+        let mut cursor = FuncCursor::new(&mut state.func).at_position(state.position);
+        // The instructions after a terminator are unreachable, thus insert them
+        // into a new block instead of the terminated one.
+        if let Some(block) = cursor.current_block() {
+            if is_terminated(cursor.func, block) {
+                let block = cursor.func.dfg.make_block();
+                cursor.func.layout.append_block(block);
+                cursor.goto_bottom(block);
+            }
+        }
+        let result = f(&mut cursor);
+        state.position = cursor.position();
+        result
+    }
+
+    /// Construct a native integer constant with the type `ty`.
+    #[inline]
+    pub(crate) fn iconst(&self, ty: ir::Type, v: i64) -> ir::Value {
+        self.with_cursor(|cursor| cursor.ins().iconst(ty, v))
+    }
+
+    /// Get the integer constant of the value if it is defined by an `iconst`.
+    fn const_int_value(&self, value: ir::Value) -> Option<i64> {
+        let functions = self.functions.borrow();
+        let state = functions.last().expect(kcl_error::INTERNAL_ERROR_MSG);
+        let inst = state.func.dfg.value_def(value).inst()?;
+        match state.func.dfg.insts[inst] {
+            ir::InstructionData::UnaryImm {
+                opcode: ir::Opcode::Iconst,
+                imm,
+            } => Some(imm.bits()),
+            _ => None,
+        }
+    }
+
+    /// Get the function reference of the function id in the current function.
+    fn func_ref(&self, function: FuncId) -> FuncRef {
+        let mut functions = self.functions.borrow_mut();
+        let state = functions.last_mut().expect(kcl_error::INTERNAL_ERROR_MSG);
+        if let Some(func_ref) = state.func_refs.get(&function) {
+            return *func_ref;
+        }
+        let func_ref = self
+            .module
+            .borrow_mut()
+            .declare_func_in_func(function, &mut state.func);
+        state.func_refs.insert(function, func_ref);
+        func_ref
+    }
+
+    /// Get the global value of the data id in the current function.
+    fn data_ref(&self, data: DataId) -> GlobalValue {
+        let mut functions = self.functions.borrow_mut();
+        let state = functions.last_mut().expect(kcl_error::INTERNAL_ERROR_MSG);
+        if let Some(global_value) = state.data_refs.get(&data) {
+            return *global_value;
+        }
+        let global_value = self
+            .module
+            .borrow()
+            .declare_data_in_func(data, &mut state.func);
+        state.data_refs.insert(data, global_value);
+        global_value
+    }
+
+    /// Get the symbol name of the function id.
+    pub(crate) fn function_name(&self, function: FuncId) -> String {
+        self.function_names
+            .borrow()
+            .get(&function)
+            .cloned()
+            .expect(kcl_error::INTERNAL_ERROR_MSG)
+    }
+
+    /// Get the Cranelift signature of the function signature index.
+    pub(crate) fn signature(&self, signature: FunctionSignature) -> ir::Signature {
+        self.signatures.borrow()[signature.0].clone()
+    }
+
+    /// The signature of the package init function `(ctx) -> void`.
+    pub(crate) fn init_signature(&self) -> ir::Signature {
+        let mut signature = self.module.borrow().make_signature();
+        signature.params.push(AbiParam::new(self.pointer_type));
+        signature
+    }
+
+    /// Get the signature of the runtime function named `name`.
+    fn runtime_signature(&self, name: &str) -> Option<ir::Signature> {
+        let (params, ret) = RUNTIME_SIGNATURES.get(name)?;
+        let abi_param = |ty: &Option<ir::Type>| match ty {
+            Some(ty) if *ty == types::I8 => AbiParam::new(*ty).uext(),
+            Some(ty) if *ty == types::I32 => AbiParam::new(*ty).sext(),
+            Some(ty) => AbiParam::new(*ty),
+            None => AbiParam::new(self.pointer_type),
+        };
+        let mut signature = self.module.borrow().make_signature();
+        signature.params.extend(params.iter().map(abi_param));
+        if let Some(ret) = ret {
+            signature.returns.push(abi_param(ret));
+        }
+        Some(signature)
+    }
+
+    /// Declare a function with the linkage and the signature, the internal
+    /// functions are renamed when the name is used.
+    pub(crate) fn declare_function(
+        &self,
+        name: &str,
+        linkage: Linkage,
+        signature: &ir::Signature,
+    ) -> FuncId {
+        let name = if linkage == Linkage::Local {
+            self.unique_symbol(name)
+        } else {
+            self.symbols.borrow_mut().insert(name.to_string());
+            name.to_string()
+        };
+        let function = self
+            .module
+            .borrow_mut()
+            .declare_function(&name, linkage, signature)
+            .expect(kcl_error::CODE_GEN_ERROR_MSG);
+        self.function_names.borrow_mut().insert(function, name);
+        function
+    }
+
+    /// Get an unused symbol name with the prefix `name`.
+    fn unique_symbol(&self, name: &str) -> String {
+        let mut symbols = self.symbols.borrow_mut();
+        let mut symbol = name.to_string();
+        let mut index = 0;
+        while symbols.contains(&symbol) {
+            index += 1;
+            symbol = format!("{}.{}", name, index);
+        }
+        symbols.insert(symbol.clone());
+        symbol
+    }
+
+    /// Get the unique id of the current function.
+    pub(crate) fn current_function_uid(&self) -> usize {
+        self.functions
+            .borrow()
+            .last()
+            .expect(kcl_error::INTERNAL_ERROR_MSG)
+            .uid
+    }
+
+    /// Get the address of the data object.
+    fn data_ptr(&self, data: DataId) -> ir::Value {
+        let global_value = self.data_ref(data);
+        let pointer_type = self.pointer_type;
+        let mut functions = self.functions.borrow_mut();
+        let state = functions.last_mut().expect(kcl_error::INTERNAL_ERROR_MSG);
+        let entry = state.entry;
+        *state.data_addrs.entry(data).or_insert_with(|| {
+            FuncCursor::new(&mut state.func)
+                .at_first_insertion_point(entry)
+                .ins()
+                .symbol_value(pointer_type, global_value)
+        })
+    }
+
+    /// Get the address of the variable.
+    pub(crate) fn variable_ptr(&self, var: Variable) -> ir::Value {
+        match var {
+            Variable::Local(slot, _) => {
+                let pointer_type = self.pointer_type;
+                self.with_cursor(|cursor| cursor.ins().stack_addr(pointer_type, slot, 0))
+            }
+            Variable::Global(data) => self.data_ptr(data),
+        }
+    }
+
+    /// Load the value of the variable.
+    pub(crate) fn load_variable(&self, var: Variable) -> ir::Value {
+        let pointer_type = self.pointer_type;
+        match var {
+            Variable::Local(slot, uid) => {
+                debug_assert_eq!(uid, self.current_function_uid());
+                self.with_cursor(|cursor| cursor.ins().stack_load(pointer_type, slot, 0))
+            }
+            Variable::Global(_) => {
+                let ptr = self.variable_ptr(var);
+                self.with_cursor(|cursor| {
+                    cursor.ins().load(pointer_type, MemFlags::trusted(), ptr, 0)
+                })
+            }
+        }
+    }
+
+    /// Whether the variable can be accessed directly in the current function.
+    pub(crate) fn is_accessible(&self, var: &Variable) -> bool {
+        match var {
+            Variable::Local(_, uid) => *uid == self.current_function_uid(),
+            Variable::Global(_) => true,
+        }
+    }
+
+    /// Get the variable named `name` in the current scope.
+    pub(crate) fn get_variable_in_current_scope(&self, name: &str) -> Option<Variable> {
+        let current_pkgpath = self.current_pkgpath();
+        let pkg_scopes = self.pkg_scopes.borrow();
+        let msg = format!("pkgpath {} is not found", current_pkgpath);
+        let scopes = pkg_scopes.get(&current_pkgpath).expect(&msg);
+        let index = scopes.len() - 1;
+        let variables = scopes[index].variables.borrow();
+        variables.get(name).cloned()
+    }
+
+    /// Lookup the variable named `name` from the innermost scope to the outermost one,
+    /// the variables of the enclosing functions are captured through the closure map
+    /// of the nearest lambda scope.
+    fn lookup_variable_in_scopes(&self, name: &str, scopes: &[Rc<Scope>]) -> Option<ir::Value> {
+        // Scopes 0 is builtin scope, Scopes 1 is the global scope, Scopes 2~ are the local scopes
+        let scopes_len = scopes.len();
+        for i in 0..scopes_len {
+            let index = scopes_len - i - 1;
+            let var = match scopes[index].variables.borrow().get(name) {
+                Some(var) => *var,
+                None => continue,
+            };
+            if self.is_accessible(&var) {
+                return Some(self.load_variable(var));
+            }
+            // The variable belongs to an enclosing function, record it into the closures
+            // and get it from the closure map of the nearest lambda scope.
+            let closure_scope = scopes[index + 1..].iter().rev().find(|scope| {
+                scope
+                    .variables
+                    .borrow()
+                    .get(value::LAMBDA_CLOSURE)
+                    .map(|var| self.is_accessible(var))
+                    .unwrap_or_default()
+            });
+            if let Some(closure_scope) = closure_scope {
+                closure_scope
+                    .closures
+                    .borrow_mut()
+                    .insert(name.to_string(), var);
+                let closure_var = closure_scope.variables.borrow()[value::LAMBDA_CLOSURE];
+                let closure_map = self.load_variable(closure_var);
+                let string_ptr_value = self.native_global_string(name, "");
+                return Some(self.build_call(
+                    &ApiFunc::kclvm_dict_get_value.name(),
+                    &[
+                        self.current_runtime_ctx_ptr(),
+                        closure_map,
+                        string_ptr_value,
+                    ],
+                ));
+            }
+        }
+        None
+    }
+
+    /// Plan globals to a json string
+    pub fn globals_to_json_str(&self) -> ir::Value {
+        let current_pkgpath = self.current_pkgpath();
+        let (scalars, globals) = {
+            let pkg_scopes = self.pkg_scopes.borrow();
+            let scopes = pkg_scopes
+                .get(&current_pkgpath)
+                .unwrap_or_else(|| panic!("pkgpath {} is not found", current_pkgpath));
+            // The global scope.
+            let scope = scopes.last().expect(kcl_error::INTERNAL_ERROR_MSG);
+            let scalars = scope.scalars.borrow().clone();
+            let globals = scope.variables.borrow().clone();
+            (scalars, globals)
+        };
+        // Construct a plan object.
+        let global_dict = self.dict_value();
+        // Deal scalars
+        for scalar in scalars.iter() {
+            let scalar = self.load_variable(*scalar);
+            self.dict_safe_insert(global_dict, SCALAR_KEY, scalar, 0, -1);
+        }
+        // Deal global variables
+        for (name, var) in globals.iter() {
+            // Omit private variables and function variables
+            if name.starts_with(kclvm_runtime::KCL_PRIVATE_VAR_PREFIX) {
+                continue;
+            }
+            let value = self.load_variable(*var);
+            let value_dict = self.dict_value();
+            self.dict_safe_insert(value_dict, name.as_str(), value, 0, -1);
+            self.dict_safe_insert(global_dict, SCALAR_KEY, value_dict, 0, -1);
+        }
+        // Plan result to json string.
+        self.build_call(
+            &ApiFunc::kclvm_value_plan_to_json.name(),
+            &[
+                self.current_runtime_ctx_ptr(),
+                self.dict_get(global_dict, self.native_global_string(SCALAR_KEY, "")),
+            ],
+        )
+    }
+
+    /// Insert a dict entry including key, value, op and insert_index into the dict.
+    #[inline]
+    fn dict_safe_insert(
+        &self,
+        dict: ir::Value,
+        key: &str,
+        value: ir::Value,
+        op: i32,
+        insert_index: i32,
+    ) {
+        let name = self.native_global_string(key, "");
+        let op = self.native_int_value(op);
+        let insert_index = self.native_int_value(insert_index);
+        self.build_void_call(
+            &ApiFunc::kclvm_dict_safe_insert.name(),
+            &[
+                self.current_runtime_ctx_ptr(),
+                dict,
+                name,
+                value,
+                op,
+                insert_index,
+            ],
+        );
+    }
+}
+
+impl<'ctx, M: Module> ContextMethods<'ctx> for CraneliftCodeGenContext<'ctx, M> {
+    type Variable = Variable;
+
+    #[inline]
+    fn program(&self) -> &'ctx ast::Program {
+        self.program
+    }
+
+    #[inline]
+    fn no_link(&self) -> bool {
+        self.no_link
+    }
+
+    #[inline]
+    fn imported(&self) -> &RefCell<HashSet<String>> {
+        &self.imported
+    }
+
+    #[inline]
+    fn local_vars(&self) -> &RefCell<HashSet<String>> {
+        &self.local_vars
+    }
+
+    #[inline]
+    fn schema_stack(&self) -> &RefCell<Vec<value::SchemaType>> {
+        &self.schema_stack
+    }
+
+    #[inline]
+    fn lambda_stack(&self) -> &RefCell<Vec<bool>> {
+        &self.lambda_stack
+    }
+
+    #[inline]
+    fn schema_expr_stack(&self) -> &RefCell<Vec<()>> {
+        &self.schema_expr_stack
+    }
+
+    #[inline]
+    fn pkgpath_stack(&self) -> &RefCell<Vec<String>> {
+        &self.pkgpath_stack
+    }
+
+    #[inline]
+    fn filename_stack(&self) -> &RefCell<Vec<String>> {
+        &self.filename_stack
+    }
+
+    #[inline]
+    fn target_vars(&self) -> &RefCell<Vec<String>> {
+        &self.target_vars
+    }
+
+    #[inline]
+    fn current_line(&self) -> &RefCell<u64> {
+        &self.current_line
+    }
+
+    #[inline]
+    fn backtrack_meta(&self) -> &RefCell<Option<BacktrackMeta>> {
+        &self.backtrack_meta
+    }
+
+    /// Get compiler default ok result
+    #[inline]
+    fn ok_result(&self) -> CompileResult {
+        Ok(self.iconst(self.pointer_type, 0))
+    }
+
+    /// Build a void function call
+    #[inline]
+    fn build_void_call(&self, name: &str, args: &[ir::Value]) {
+        self.build_call_function(self.lookup_function(name), args);
+    }
+
+    /// Build a function call with the return value
+    #[inline]
+    fn build_call(&self, name: &str, args: &[ir::Value]) -> ir::Value {
+        self.build_call_function(self.lookup_function(name), args)
+            .expect(kcl_error::FUNCTION_RETURN_VALUE_NOT_FOUND_MSG)
+    }
+
+    /// Build a function call of the function id and return the first result if exists.
+    fn build_call_function(&self, function: FuncId, args: &[ir::Value]) -> Option<ir::Value> {
+        let func_ref = self.func_ref(function);
+        self.with_cursor(|cursor| {
+            let sig_ref = cursor.func.dfg.ext_funcs[func_ref].signature;
+            let params: Vec<ir::Type> = cursor.func.dfg.signatures[sig_ref]
+                .params
+                .iter()
+                .map(|p| p.value_type)
+                .collect();
+            debug_assert_eq!(args.len(), params.len(), "mismatched function arguments");
+            let args: Vec<ir::Value> = args
+                .iter()
+                .zip(params)
+                .map(|(arg, ty)| coerce(cursor, *arg, ty))
+                .collect();
+            let inst = cursor.ins().call(func_ref, &args);
+            cursor.func.dfg.inst_results(inst).first().cloned()
+        })
+    }
+
+    /// Build an indirect call of the KCL function pointer with the KCL function
+    /// signature `(ctx, args, kwargs) -> value`.
+    fn build_function_ptr_call(&self, fn_ptr: ir::Value, args: &[ir::Value]) -> ir::Value {
+        let sig_ref = {
+            let mut functions = self.functions.borrow_mut();
+            let state = functions.last_mut().expect(kcl_error::INTERNAL_ERROR_MSG);
+            match state.function_sig {
+                Some(sig_ref) => sig_ref,
+                None => {
+                    let signature = self.signature(self.function_type());
+                    let sig_ref = state.func.import_signature(signature);
+                    state.function_sig = Some(sig_ref);
+                    sig_ref
+                }
+            }
+        };
+        self.with_cursor(|cursor| {
+            let inst = cursor.ins().call_indirect(sig_ref, fn_ptr, args);
+            cursor.func.dfg.inst_results(inst)[0]
+        })
+    }
+
+    /// Push a function into the function stack and start to generate its body
+    /// from the entry block.
+    fn push_function(&self, function: FuncId) {
+        let signature = self
+            .module
+            .borrow()
+            .declarations()
+            .get_function_decl(function)
+            .signature
+            .clone();
+        let mut func = ir::Function::with_name_signature(
+            UserFuncName::user(0, function.as_u32()),
+            signature.clone(),
+        );
+        let entry = func.dfg.make_block();
+        func.layout.append_block(entry);
+        for param in &signature.params {
+            func.dfg.append_block_param(entry, param.value_type);
+        }
+        let uid = self.function_uid.get();
+        self.function_uid.set(uid + 1);
+        self.functions.borrow_mut().push(FunctionState {
+            id: function,
+            uid,
+            func,
+            entry,
+            position: CursorPosition::After(entry),
+            func_refs: HashMap::new(),
+            data_refs: HashMap::new(),
+            func_addrs: HashMap::new(),
+            data_addrs: HashMap::new(),
+            function_sig: None,
+        });
+    }
+
+    /// Pop the current function from the function stack and define it into
+    /// the module, the unterminated blocks are unreachable and end with a trap.
+    fn pop_function(&self) -> Result<(), kcl_error::KCLError> {
+        let mut state = self
+            .functions
+            .borrow_mut()
+            .pop()
+            .expect(kcl_error::INTERNAL_ERROR_MSG);
+        let blocks: Vec<Block> = state.func.layout.blocks().collect();
+        for block in blocks {
+            if !is_terminated(&state.func, block) {
+                FuncCursor::new(&mut state.func)
+                    .at_bottom(block)
+                    .ins()
+                    .trap(UNREACHABLE_TRAP_CODE);
+            }
+        }
+        let mut context = cranelift_codegen::Context::for_function(state.func);
+        let mut module = self.module.borrow_mut();
+        module
+            .define_function(state.id, &mut context)
+            .map_err(|err| match err {
+                cranelift_module::ModuleError::Compilation(err) => kcl_error::KCLError::new(
+                    &cranelift_codegen::print_errors::pretty_error(&context.func, err),
+                ),
+                err => kcl_error::KCLError::new(&err.to_string()),
+            })?;
+        // Record the unwind information to unwind the runtime panics through the function.
+        let compiled_code = context
+            .compiled_code()
+            .expect(kcl_error::INTERNAL_ERROR_MSG);
+        if let Some(info) = compiled_code
+            .create_unwind_info(module.isa())
+            .map_err(|err| kcl_error::KCLError::new(&err.to_string()))?
+        {
+            self.unwind_infos.borrow_mut().push((state.id, info));
+        }
+        Ok(())
+    }
+
+    /// Add the package init function `(ctx) -> void` named `name`.
+    fn add_pkg_init_function(&self, name: &str) -> FuncId {
+        self.declare_function(name, Linkage::Export, &self.init_signature())
+    }
+
+    /// Declare the external package init function named `name`.
+    fn declare_pkg_init_function(&self, name: &str) -> FuncId {
+        self.declare_function(name, Linkage::Import, &self.init_signature())
+    }
+
+    /// Creates global string in the module with initializer
+    fn native_global_string(&self, value: &str, _name: &str) -> ir::Value {
+        let data = {
+            let mut global_strings = self.global_strings.borrow_mut();
+            match global_strings.get(value) {
+                Some(data) => *data,
+                None => {
+                    let mut module = self.module.borrow_mut();
+                    let data = module
+                        .declare_anonymous_data(false, false)
+                        .expect(kcl_error::CODE_GEN_ERROR_MSG);
+                    let mut description = DataDescription::new();
+                    let mut bytes = value.as_bytes().to_vec();
+                    bytes.push(0);
+                    description.define(bytes.into_boxed_slice());
+                    module
+                        .define_data(data, &description)
+                        .expect(kcl_error::CODE_GEN_ERROR_MSG);
+                    global_strings.insert(value.to_string(), data);
+                    data
+                }
+            }
+        };
+        self.data_ptr(data)
+    }
+
+    /// Creates global string value in the module with initializer
+    fn native_global_string_value(&self, value: &str) -> ir::Value {
+        self.native_global_string(value, "")
+    }
+
+    /// Get i8 zero value
+    fn native_i8_zero(&self) -> ir::Value {
+        self.native_i8(0)
+    }
+
+    /// Get i8 value
+    fn native_i8(&self, v: i8) -> ir::Value {
+        self.iconst(types::I8, v as i64)
+    }
+
+    /// Construct a int value using i32
+    fn native_int_value(&self, v: i32) -> ir::Value {
+        self.iconst(types::I32, v as i64)
+    }
+
+    /// Construct a global value pointer named `name`, the anonymous or the
+    /// duplicated names denote the internal global values.
+    fn new_global_kcl_value_ptr(&self, name: &str) -> Variable {
+        let mut module = self.module.borrow_mut();
+        let data = if name.is_empty() || self.symbols.borrow().contains(name) {
+            module.declare_anonymous_data(true, false)
+        } else {
+            self.symbols.borrow_mut().insert(name.to_string());
+            module.declare_data(name, Linkage::Export, true, false)
+        }
+        .expect(kcl_error::CODE_GEN_ERROR_MSG);
+        let mut description = DataDescription::new();
+        description.define_zeroinit(self.pointer_type.bytes() as usize);
+        description.set_align(GLOBAL_VAL_ALIGNMENT as u64);
+        module
+            .define_data(data, &description)
+            .expect(kcl_error::CODE_GEN_ERROR_MSG);
+        Variable::Global(data)
+    }
+
+    /// Create a local variable in the current function, which is initialized
+    /// with the undefined value at the function entry, thus it is always
+    /// initialized whatever the control flow is.
+    fn new_local_variable(&self) -> Variable {
+        let undefined = self.func_ref(self.lookup_function(&ApiFunc::kclvm_value_Undefined.name()));
+        let pointer_type = self.pointer_type;
+        let mut functions = self.functions.borrow_mut();
+        let state = functions.last_mut().expect(kcl_error::INTERNAL_ERROR_MSG);
+        let slot = state.func.create_sized_stack_slot(StackSlotData::new(
+            StackSlotKind::ExplicitSlot,
+            pointer_type.bytes(),
+            pointer_type.bytes().trailing_zeros() as u8,
+        ));
+        let ctx = state.func.dfg.block_params(state.entry)[0];
+        let mut cursor = FuncCursor::new(&mut state.func).at_first_insertion_point(state.entry);
+        let inst = cursor.ins().call(undefined, &[ctx]);
+        let value = cursor.func.dfg.inst_results(inst)[0];
+        cursor.ins().stack_store(value, slot, 0);
+        Variable::Local(slot, state.uid)
+    }
+
+    /// Create a local variable in the current function with the initial value.
+    fn new_local_variable_with_value(&self, value: ir::Value) -> Variable {
+        let var = self.new_local_variable();
+        self.store_to_variable(var, value);
+        var
+    }
+
+    /// Store the value into the variable.
+    fn store_to_variable(&self, var: Variable, value: ir::Value) {
+        match var {
+            Variable::Local(slot, uid) => {
+                debug_assert_eq!(uid, self.current_function_uid());
+                self.with_cursor(|cursor| cursor.ins().stack_store(value, slot, 0));
+            }
+            Variable::Global(_) => {
+                let ptr = self.variable_ptr(var);
+                self.with_cursor(|cursor| {
+                    cursor.ins().store(MemFlags::trusted(), value, ptr, 0);
+                });
+            }
+        }
+    }
+
+    /// Append a scalar value into the scope.
+    fn add_scalar(&self, scalar: ir::Value, is_schema: bool) {
+        // Scalars are read at the end of the function, thus save them into
+        // variables instead of using the values which may not dominate the end.
+        let scalar = self.new_local_variable_with_value(scalar);
+        let current_pkgpath = self.current_pkgpath();
+        let mut pkg_scopes = self.pkg_scopes.borrow_mut();
+        let scopes = pkg_scopes
+            .get_mut(&current_pkgpath)
+            .unwrap_or_else(|| panic!("pkgpath {} is not found", current_pkgpath));
+        if let Some(last) = scopes.last_mut() {
+            let mut scalars = last.scalars.borrow_mut();
+            // TODO: To avoid conflicts, only the last schema scalar expressions are allowed.
+            let mut schema_scalar_idx = last.schema_scalar_idx.borrow_mut();
+            if is_schema {
+                // Remove the last schema scalar.
+                if *schema_scalar_idx < scalars.len() {
+                    scalars.remove(*schema_scalar_idx);
+                }
+                // Override the last schema scalar.
+                scalars.push(scalar);
+                *schema_scalar_idx = scalars.len() - 1;
+            } else {
+                scalars.push(scalar);
+            }
+        }
+    }
+
+    /// Append a variable into the scope
+    fn add_variable(&self, name: &str, var: Variable) {
+        let current_pkgpath = self.current_pkgpath();
+        let mut pkg_scopes = self.pkg_scopes.borrow_mut();
+        let msg = format!("pkgpath {} is not found", current_pkgpath);
+        let scopes = pkg_scopes.get_mut(&current_pkgpath).expect(&msg);
+        if let Some(last) = scopes.last_mut() {
+            let mut variables = last.variables.borrow_mut();
+            if !variables.contains_key(name) {
+                variables.insert(name.to_string(), var);
+            }
+        }
+    }
+
+    /// Store the argument named `name` in the current scope.
+    fn store_argument_in_current_scope(&self, name: &str) {
+        // Find argument name in the scope
+        let current_pkgpath = self.current_pkgpath();
+        let mut pkg_scopes = self.pkg_scopes.borrow_mut();
+        let msg = format!("pkgpath {} is not found", current_pkgpath);
+        let scopes = pkg_scopes.get_mut(&current_pkgpath).expect(&msg);
+        let index = scopes.len() - 1;
+        let mut arguments_mut = scopes[index].arguments.borrow_mut();
+        arguments_mut.insert(name.to_string());
+    }
+
+    /// Store the variable named `name` with `value` from the current scope, return false when not found
+    fn store_variable_in_current_scope(&self, name: &str, value: ir::Value) -> bool {
+        match self.get_variable_in_current_scope(name) {
+            Some(var) if self.is_accessible(&var) => {
+                self.store_to_variable(var, value);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Store the variable named `name` with `value` from the scope, return false when not found
+    fn store_variable(&self, name: &str, value: ir::Value) -> bool {
+        let var = {
+            let current_pkgpath = self.current_pkgpath();
+            let pkg_scopes = self.pkg_scopes.borrow();
+            let msg = format!("pkgpath {} is not found", current_pkgpath);
+            let scopes = pkg_scopes.get(&current_pkgpath).expect(&msg);
+            scopes
+                .iter()
+                .rev()
+                .find_map(|scope| scope.variables.borrow().get(name).cloned())
+        };
+        match var {
+            Some(var) if self.is_accessible(&var) => {
+                self.store_to_variable(var, value);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Resolve variable in scope, return false when not found
+    fn resolve_variable(&self, name: &str) -> bool {
+        let current_pkgpath = self.current_pkgpath();
+        let pkg_scopes = self.pkg_scopes.borrow();
+        let msg = format!("pkgpath {} is not found", current_pkgpath);
+        let scopes = pkg_scopes.get(&current_pkgpath).expect(&msg);
+        scopes
+            .iter()
+            .any(|scope| scope.variables.borrow().contains_key(name))
+    }
+
+    /// Append a variable or update the existed local variable.
+    fn add_or_update_local_variable(&self, name: &str, value: ir::Value) {
+        let vars: Vec<Variable> = {
+            let current_pkgpath = self.current_pkgpath();
+            let pkg_scopes = self.pkg_scopes.borrow();
+            let msg = format!("pkgpath {} is not found", current_pkgpath);
+            let scopes = pkg_scopes.get(&current_pkgpath).expect(&msg);
+            // Query the variable in all scopes.
+            // We cannot update rule/lambda/schema arguments because they are read-only.
+            scopes
+                .iter()
+                .enumerate()
+                .filter(|(index, scope)| {
+                    *index > GLOBAL_LEVEL
+                        && !self.local_vars.borrow().contains(name)
+                        && !scope.arguments.borrow().contains(name)
+                })
+                .filter_map(|(_, scope)| scope.variables.borrow().get(name).cloned())
+                .filter(|var| self.is_accessible(var))
+                .collect()
+        };
+        // If the local varibale is found, store the new value for the variable.
+        for var in &vars {
+            self.store_to_variable(*var, value);
+        }
+        // If not found, create a new varibale.
+        if vars.is_empty() {
+            let var = self.new_local_variable_with_value(value);
+            // Store the value for the variable and add the varibale into the current scope.
+            let current_pkgpath = self.current_pkgpath();
+            let pkg_scopes = self.pkg_scopes.borrow();
+            let msg = format!("pkgpath {} is not found", current_pkgpath);
+            let scopes = pkg_scopes.get(&current_pkgpath).expect(&msg);
+            if let Some(last) = scopes.last() {
+                let mut variables = last.variables.borrow_mut();
+                variables.insert(name.to_string(), var);
+            }
+        }
+    }
+
+    /// Append a variable or update the existed variable
+    fn add_or_update_global_variable(&self, name: &str, value: ir::Value) {
+        if let Some(var) = self.get_variable_in_current_scope(name) {
+            self.store_to_variable(var, value);
+        } else {
+            let pkgpath = self.current_pkgpath();
+            let var_name = format!("${}.${}", pkgpath_without_prefix!(pkgpath), name);
+            let var = self.new_global_kcl_value_ptr(&var_name);
+            self.store_to_variable(var, value);
+            self.add_variable(name, var);
+        }
+    }
+
+    /// Get the variable value named `name` from the scope, return Err when not found
+    fn get_variable(&self, name: &str) -> CompileResult {
+        let current_pkgpath = self.current_pkgpath();
+        self.get_variable_in_pkgpath(name, &current_pkgpath)
+    }
+
+    /// Get the variable value named `name` from the scope, return Err when not found
+    fn get_variable_in_schema(&self, name: &str) -> CompileResult {
+        let schema_value = self
+            .get_variable(value::SCHEMA_SELF_NAME)
+            .expect(kcl_error::INTERNAL_ERROR_MSG);
+        let cal_map = self
+            .get_variable(value::SCHEMA_CAL_MAP)
+            .expect(kcl_error::INTERNAL_ERROR_MSG);
+        let string_ptr_value = self.native_global_string(name, "");
+        let cal_map_has_key = self.build_call(
+            &ApiFunc::kclvm_dict_has_value.name(),
+            &[cal_map, string_ptr_value],
+        );
+        let schema_has_key = self.build_call(
+            &ApiFunc::kclvm_dict_has_value.name(),
+            &[schema_value, string_ptr_value],
+        );
+        // has_key = cal_map_has_key or schema_has_key
+        let has_key = self.with_cursor(|cursor| {
+            let has_key = cursor.ins().bor(cal_map_has_key, schema_has_key);
+            cursor.ins().icmp_imm(IntCC::NotEqual, has_key, 0)
+        });
+        let then_block = self.append_block("");
+        let else_block = self.append_block("");
+        let end_block = self.append_block("");
+        let value = self.append_block_param(end_block);
+        self.cond_br(has_key, then_block, else_block);
+        self.switch_to_block(then_block);
+        let target_attr = self
+            .target_vars
+            .borrow_mut()
+            .last()
+            .expect(kcl_error::INTERNAL_ERROR_MSG)
+            .clone();
+        let target_attr = self.native_global_string_value(&target_attr);
+        let config_attr_value = {
+            let config = self
+                .get_variable(value::SCHEMA_CONFIG_NAME)
+                .expect(kcl_error::INTERNAL_ERROR_MSG);
+            let config_meta = self
+                .get_variable(value::SCHEMA_CONFIG_META_NAME)
+                .expect(kcl_error::INTERNAL_ERROR_MSG);
+            let backtrack_level_map = self
+                .get_variable(value::BACKTRACK_LEVEL_MAP)
+                .expect(kcl_error::INTERNAL_ERROR_MSG);
+            let backtrack_cache = self
+                .get_variable(value::BACKTRACK_CACHE)
+                .expect(kcl_error::INTERNAL_ERROR_MSG);
+            let args = self
+                .get_variable(value::SCHEMA_ARGS)
+                .expect(kcl_error::INTERNAL_ERROR_MSG);
+            let kwargs = self
+                .get_variable(value::SCHEMA_KWARGS)
+                .expect(kcl_error::INTERNAL_ERROR_MSG);
+            self.build_call(
+                &ApiFunc::kclvm_schema_get_value.name(),
+                &[
+                    self.current_runtime_ctx_ptr(),
+                    schema_value,
+                    string_ptr_value,
+                    config,
+                    config_meta,
+                    cal_map,
+                    target_attr,
+                    backtrack_level_map,
+                    backtrack_cache,
+                    args,
+                    kwargs,
+                ],
+            )
+        };
+        self.br_with_args(end_block, &[config_attr_value]);
+        self.switch_to_block(else_block);
+        let current_pkgpath = self.current_pkgpath();
+        let result = self.get_variable_in_pkgpath(name, &current_pkgpath);
+        let else_value = match result {
+            Ok(v) => v,
+            Err(_) => self.undefined_value(),
+        };
+        self.br_with_args(end_block, &[else_value]);
+        self.switch_to_block(end_block);
+        Ok(value)
+    }
+
+    /// Get the variable value named `name` from the scope named `pkgpath`, return Err when not found
+    fn get_variable_in_pkgpath(&self, name: &str, pkgpath: &str) -> CompileResult {
+        let pkgpath =
+            if !pkgpath.starts_with(kclvm_runtime::PKG_PATH_PREFIX) && pkgpath != MAIN_PKG_PATH {
+                format!("{}{}", kclvm_runtime::PKG_PATH_PREFIX, pkgpath)
+            } else {
+                pkgpath.to_string()
+            };
+        let result = Err(kcl_error::KCLError {
+            message: format!("name '{}' is not defined", name),
+            ty: kcl_error::KCLErrorType::Compile,
+        });
+        // System module
+        if builtin::STANDARD_SYSTEM_MODULE_NAMES_WITH_AT.contains(&pkgpath.as_str()) {
+            let pkgpath = &pkgpath[1..];
+            let mangle_func_name = format!(
+                "{}{}_{}",
+                builtin::KCL_SYSTEM_MODULE_MANGLE_PREFIX,
+                pkgpath_without_prefix!(pkgpath),
+                name
+            );
+            let value = if pkgpath == builtin::system_module::UNITS
+                && builtin::system_module::UNITS_FIELD_NAMES.contains(&name)
+            {
+                let value_float: f64 = kclvm_runtime::f64_unit_value(name);
+                let value_int: u64 = kclvm_runtime::u64_unit_value(name);
+                if value_int != 1 {
+                    self.int_value(value_int as i64)
+                } else {
+                    self.float_value(value_float)
+                }
+            } else {
+                let function = self.lookup_function(&mangle_func_name);
+                let lambda_fn_ptr = self.function_ptr(function);
+                let func_name_ptr = self.native_global_string(&mangle_func_name, "");
+                let none_value = self.none_value();
+                self.build_call(
+                    &ApiFunc::kclvm_value_Function.name(),
+                    &[
+                        self.current_runtime_ctx_ptr(),
+                        lambda_fn_ptr,
+                        none_value,
+                        func_name_ptr,
+                        self.native_i8_zero(),
+                    ],
+                )
+            };
+            Ok(value)
+        }
+        // Plugin pkgpath
+        else if pkgpath.starts_with(plugin::PLUGIN_PREFIX_WITH_AT) {
+            let null_fn_ptr = self.iconst(self.pointer_type, 0);
+            let name = format!("{}.{}", &pkgpath[1..], name);
+            let name = self.native_global_string(&name, "");
+            let none_value = self.none_value();
+            Ok(self.build_call(
+                &ApiFunc::kclvm_value_Function.name(),
+                &[
+                    self.current_runtime_ctx_ptr(),
+                    null_fn_ptr,
+                    none_value,
+                    name,
+                    self.native_i8(1),
+                ],
+            ))
+        // User pkgpath
+        } else {
+            let found = {
+                let pkg_scopes = self.pkg_scopes.borrow();
+                let scopes = pkg_scopes
+                    .get(&pkgpath)
+                    .unwrap_or_else(|| panic!("package {} is not found", pkgpath));
+                self.lookup_variable_in_scopes(name, scopes)
+            };
+            match found {
+                Some(value) => Ok(value),
+                None => {
+                    let is_in_schema = self.schema_stack.borrow().len() > 0;
+                    if !is_in_schema {
+                        let mut handler = self.handler.borrow_mut();
+                        if let Err(err) = &result {
+                            handler.add_compile_error(
+                                &err.message,
+                                Position {
+                                    filename: self.current_filename(),
+                                    line: *self.current_line.borrow(),
+                                    column: None,
+                                },
+                            );
+                        }
+                        handler.abort_if_any_errors()
+                    }
+                    result
+                }
+            }
+        }
+    }
+
+    /// Get the variable value named `name` from the scope named `pkgpath`, return Err when not found
+    fn get_external_variable_in_pkgpath(&self, name: &str, pkgpath: &str) -> CompileResult {
+        let ext_pkgpath = if !pkgpath.starts_with(kclvm_runtime::PKG_PATH_PREFIX)
+            && pkgpath != kclvm_runtime::MAIN_PKG_PATH
+        {
+            format!("{}{}", kclvm_runtime::PKG_PATH_PREFIX, pkgpath)
+        } else {
+            pkgpath.to_string()
+        };
+        // System module or plugin module
+        if builtin::STANDARD_SYSTEM_MODULE_NAMES_WITH_AT.contains(&ext_pkgpath.as_str())
+            || ext_pkgpath.starts_with(plugin::PLUGIN_PREFIX_WITH_AT)
+        {
+            return self.get_variable_in_pkgpath(name, pkgpath);
+        }
+        // User module external variable
+        let external_var_name = format!("${}.${}", pkgpath_without_prefix!(ext_pkgpath), name);
+        let pkgpath = self.current_pkgpath();
+        let data = {
+            let mut global_var_maps = self.global_vars.borrow_mut();
+            // Add or update a external variable
+            let global_vars = global_var_maps.entry(pkgpath).or_default();
+            match global_vars.get(external_var_name.as_str()) {
+                Some(data) => *data,
+                None => {
+                    let data = self
+                        .module
+                        .borrow_mut()
+                        .declare_data(&external_var_name, Linkage::Import, true, false)
+                        .expect(kcl_error::CODE_GEN_ERROR_MSG);
+                    self.symbols.borrow_mut().insert(external_var_name.clone());
+                    global_vars.insert(external_var_name, data);
+                    data
+                }
+            }
+        };
+        Ok(self.load_variable(Variable::Global(data)))
+    }
+
+    /// Get closure map in the current scope.
+    fn get_closure_map(&self) -> ir::Value {
+        // Get closures in the current scope.
+        let closure_map = self.dict_value();
+        {
+            let pkgpath = self.current_pkgpath();
+            let pkgpath = if !pkgpath.starts_with(PKG_PATH_PREFIX) && pkgpath != MAIN_PKG_PATH {
+                format!("{}{}", PKG_PATH_PREFIX, pkgpath)
+            } else {
+                pkgpath
+            };
+            let closures: Vec<String> = {
+                let pkg_scopes = self.pkg_scopes.borrow();
+                let scopes = pkg_scopes
+                    .get(&pkgpath)
+                    .unwrap_or_else(|| panic!("package {} is not found", pkgpath));
+                // Clouure variable must be inner of the global scope.
+                if scopes.len() > INNER_LEVEL {
+                    let closures = scopes
+                        .last()
+                        .expect(kcl_error::INTERNAL_ERROR_MSG)
+                        .closures
+                        .borrow();
+                    closures.keys().cloned().collect()
+                } else {
+                    vec![]
+                }
+            };
+            // Capture the closures from the enclosing scopes of the current scope.
+            for key in &closures {
+                let value = {
+                    let pkg_scopes = self.pkg_scopes.borrow();
+                    let scopes = pkg_scopes
+                        .get(&pkgpath)
+                        .unwrap_or_else(|| panic!("package {} is not found", pkgpath));
+                    self.lookup_variable_in_scopes(key, &scopes[..scopes.len() - 1])
+                };
+                if let Some(value) = value {
+                    self.dict_insert_override_item(closure_map, key.as_str(), value);
+                }
+            }
+        }
+        // Capture schema `self` closure.
+        let is_in_schema = self.schema_stack.borrow().len() > 0;
+        if is_in_schema {
+            for shcmea_closure_name in value::SCHEMA_VARIABLE_LIST {
+                let value = self
+                    .get_variable(shcmea_closure_name)
+                    .expect(kcl_error::INTERNAL_ERROR_MSG);
+                self.dict_insert_override_item(closure_map, shcmea_closure_name, value);
+            }
+        }
+        closure_map
+    }
+
+    /// Merge a dict entry including key, value, op and insert_index into the dict
+    /// without the idempotent check.
+    #[inline]
+    fn dict_merge(&self, dict: ir::Value, key: &str, value: ir::Value, op: i32, insert_index: i32) {
+        let name = self.native_global_string(key, "");
+        let op = self.native_int_value(op);
+        let insert_index = self.native_int_value(insert_index);
+        self.build_void_call(
+            &ApiFunc::kclvm_dict_merge.name(),
+            &[
+                self.current_runtime_ctx_ptr(),
+                dict,
+                name,
+                value,
+                op,
+                insert_index,
+            ],
+        );
+    }
+
+    /// default_dict(list) insert a key-value pair, and the value is a int pointer
+    #[inline]
+    fn default_collection_insert_int_pointer(&self, dict: ir::Value, key: &str, value: ir::Value) {
+        let name = self.native_global_string(key, "");
+        self.build_void_call(
+            ApiFunc::kclvm_default_collection_insert_int_pointer
+                .name()
+                .as_str(),
+            &[dict, name, value],
+        );
+    }
+
+    /// default_dict(list) insert a key-value pair
+    #[inline]
+    fn default_collection_insert_value(&self, dict: ir::Value, key: &str, value: ir::Value) {
+        let name = self.native_global_string(key, "");
+        self.build_void_call(
+            ApiFunc::kclvm_default_collection_insert_value
+                .name()
+                .as_str(),
+            &[dict, name, value],
+        );
+    }
+}
+
+impl<'ctx, M: Module> SchemaCodeGen<'ctx> for CraneliftCodeGenContext<'ctx, M> {}
+
+impl<'ctx, M: Module> NodeCodeGen<'ctx> for CraneliftCodeGenContext<'ctx, M> {}
+
+/// Whether the block ends with a terminator instruction.
+fn is_terminated(func: &ir::Function, block: Block) -> bool {
+    func.layout
+        .last_inst(block)
+        .map(|inst| func.dfg.insts[inst].opcode().is_terminator())
+        .unwrap_or_default()
+}
+
+/// The code generation error of the `construct` which has no Cranelift lowering.
+fn unsupported_error(construct: &str) -> kcl_error::KCLError {
+    kcl_error::KCLError::new(&format!(
+        "{construct} are not supported by the Cranelift backend"
+    ))
+}
+
+/// Convert the integer value to the integer type `ty`, the narrower values
+/// are zero extended.
+fn coerce(cursor: &mut FuncCursor, value: ir::Value, ty: ir::Type) -> ir::Value {
+    let value_ty = cursor.func.dfg.value_type(value);
+    if value_ty == ty || !value_ty.is_int() || !ty.is_int() {
+        value
+    } else if value_ty.bits() < ty.bits() {
+        cursor.ins().uextend(ty, value)
+    } else {
+        cursor.ins().ireduce(ty, value)
+    }
+}
+
+/// Parse the runtime function declarations in the LLVM IR format e.g.,
+/// `declare %kclvm_value_ref_t* @kclvm_value_Int(i64 %v);`
+fn parse_runtime_signatures() -> HashMap<&'static str, RuntimeSignature> {
+    let mut aliases: HashMap<&str, &str> = HashMap::new();
+    let mut signatures = HashMap::new();
+    let parse_type = |aliases: &HashMap<&str, &str>, ty: &str| -> Option<Option<ir::Type>> {
+        let ty = ty.trim();
+        if ty.ends_with('*') {
+            return Some(None);
+        }
+        let ty = aliases
+            .get(ty.trim_start_matches('%').trim_matches('"'))
+            .cloned()
+            .unwrap_or(ty);
+        match ty {
+            "void" => None,
+            "i8" => Some(Some(types::I8)),
+            "i32" => Some(Some(types::I32)),
+            "i64" => Some(Some(types::I64)),
+            "double" => Some(Some(types::F64)),
+            // The opaque and struct types are only used through pointers.
+            _ => Some(None),
+        }
+    };
+    for line in RUNTIME_DECLARATIONS.lines() {
+        if let Some((name, ty)) = line.split_once(" = type ") {
+            aliases.insert(name.trim_start_matches('%').trim_matches('"'), ty.trim());
+        } else if let Some(decl) = line.strip_prefix("declare ") {
+            let (ret, rest) = decl.split_once(" @").expect(kcl_error::INTERNAL_ERROR_MSG);
+            let (name, params) = rest.split_once('(').expect(kcl_error::INTERNAL_ERROR_MSG);
+            let params = params.trim_end_matches(';').trim_end_matches(')');
+            let params = params
+                .split(',')
+                .filter(|param| !param.trim().is_empty())
+                .map(|param| {
+                    let ty = param
+                        .trim()
+                        .rsplit_once(' ')
+                        .map(|(ty, _)| ty)
+                        .unwrap_or(param);
+                    parse_type(&aliases, ty).unwrap_or_default()
+                })
+                .collect();
+            signatures.insert(name, (params, parse_type(&aliases, ret)));
+        }
+    }
+    signatures
+}
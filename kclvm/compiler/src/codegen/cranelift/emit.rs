@@ -0,0 +1,120 @@
+// Copyright 2021 The KCL Authors. All rights reserved.
+
+use cranelift_codegen::gimli::write::Address;
+use cranelift_codegen::isa::OwnedTargetIsa;
+use cranelift_codegen::settings::{self, Configurable};
+use cranelift_jit::{JITBuilder, JITModule};
+use cranelift_module::{default_libcall_names, Module};
+use cranelift_object::{ObjectBuilder, ObjectModule};
+use indexmap::IndexMap;
+use kclvm_ast::ast;
+use kclvm_runtime::_kclvm_get_fn_ptr_by_name;
+use std::error;
+
+use crate::codegen::{EmitKind, EmitOptions, MODULE_NAME, OBJECT_FILE_SUFFIX};
+
+use super::context::CraneliftCodeGenContext;
+use super::unwind::UnwindContext;
+
+/// Create the host target ISA with the optimization level from 0 to 3, the
+/// object files are position independent and the JIT code is not.
+pub(super) fn host_isa(
+    opt_level: u32,
+    is_pic: bool,
+) -> Result<OwnedTargetIsa, Box<dyn error::Error>> {
+    let mut flags = settings::builder();
+    flags.set("opt_level", if opt_level == 0 { "none" } else { "speed" })?;
+    flags.set("is_pic", if is_pic { "true" } else { "false" })?;
+    flags.set("use_colocated_libcalls", "false")?;
+    let isa = cranelift_native::builder()?.finish(settings::Flags::new(flags))?;
+    Ok(isa)
+}
+
+/// Generate the native object file of KCL ast module using Cranelift.
+///
+/// Only the object files of the host target are supported, and the runtime
+/// functions are resolved when the object files are linked.
+pub fn emit_code(
+    program: &ast::Program,
+    import_names: IndexMap<String, IndexMap<String, String>>,
+    opt: &EmitOptions,
+) -> Result<(), Box<dyn error::Error>> {
+    if let Some(target) = opt.target {
+        return Err(format!("the Cranelift backend does not support the target {target}").into());
+    }
+    if opt.emit_kind != EmitKind::Object {
+        return Err(format!(
+            "the Cranelift backend does not support the emit kind {:?}",
+            opt.emit_kind
+        )
+        .into());
+    }
+    let isa = host_isa(opt.opt_level, true)?;
+    let module = ObjectModule::new(ObjectBuilder::new(
+        isa,
+        MODULE_NAME,
+        default_libcall_names(),
+    )?);
+    // Create a KCL Cranelift code generator using the KCL AST and the object module
+    let ctx = CraneliftCodeGenContext::new(module, program, import_names, opt.no_link);
+    ctx.emit_code()?;
+    let mut unwind_context = UnwindContext::new(ctx.module.borrow().isa(), true);
+    for (function, info) in ctx.unwind_infos.borrow().iter() {
+        let address = Address::Symbol {
+            symbol: function.as_u32() as usize,
+            addend: 0,
+        };
+        unwind_context.add_function(address, info);
+    }
+    let mut product = ctx.module.into_inner().finish();
+    unwind_context.emit(&mut product)?;
+    let object = product.emit()?;
+    let path = format!(
+        "{}{}",
+        opt.emit_path.unwrap_or(MODULE_NAME),
+        OBJECT_FILE_SUFFIX
+    );
+    std::fs::write(path, object)?;
+    Ok(())
+}
+
+/// Generate the code of KCL ast module and compile it in memory using the
+/// Cranelift JIT module, then call `run` with the address of the KCL main function.
+///
+/// All the runtime functions imported by the module are mapped to the runtime
+/// functions linked in the current process, thus no object files and dynamic
+/// link libraries are written to or loaded from the filesystem.
+///
+/// `opt_level` is the optimization level from 0 to 3 of the generated code.
+pub fn jit_code<T>(
+    program: &ast::Program,
+    import_names: IndexMap<String, IndexMap<String, String>>,
+    opt_level: u32,
+    run: impl FnOnce(u64) -> T,
+) -> Result<T, Box<dyn error::Error>> {
+    let isa = host_isa(opt_level, false)?;
+    let mut builder = JITBuilder::with_isa(isa, default_libcall_names());
+    // Map the runtime function declarations to the runtime function addresses
+    builder.symbol_lookup_fn(Box::new(|name| {
+        let fn_ptr = _kclvm_get_fn_ptr_by_name(name);
+        (fn_ptr != 0).then_some(fn_ptr as *const u8)
+    }));
+    // Create a KCL Cranelift code generator which links all packages into one module
+    let ctx = CraneliftCodeGenContext::new(JITModule::new(builder), program, import_names, false);
+    let main = ctx.emit_code()?;
+    let mut module = ctx.module.into_inner();
+    module.finalize_definitions()?;
+    let mut unwind_context = UnwindContext::new(module.isa(), false);
+    for (function, info) in ctx.unwind_infos.into_inner() {
+        let address = Address::Constant(module.get_finalized_function(function) as u64);
+        unwind_context.add_function(address, &info);
+    }
+    // SAFETY: the functions are freed after the registration is dropped.
+    let registration = unsafe { unwind_context.register()? };
+    let kclvm_main_ptr = module.get_finalized_function(main);
+    let result = run(kclvm_main_ptr as u64);
+    drop(registration);
+    // SAFETY: the generated code is not referenced after the run.
+    unsafe { module.free_memory() };
+    Ok(result)
+}
@@ -0,0 +1,16 @@
+//! The goal of this module is to translate KCL Program into native code using Cranelift, which
+//! is a second code generation backend implementing the same codegen traits as the LLVM backend.
+//! It generates the host object files of each KCL pkgpath that are linked with the runtime, or
+//! compiles all the packages in memory using the Cranelift JIT module, and it does not require an
+//! LLVM installation at build time.
+//!
+//! Copyright 2021 The KCL Authors. All rights reserved.
+
+mod context;
+mod emit;
+mod unwind;
+
+#[cfg(test)]
+mod tests;
+
+pub use emit::{emit_code, jit_code};
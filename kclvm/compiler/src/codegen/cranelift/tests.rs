@@ -0,0 +1,46 @@
+use cranelift_codegen::ir::types;
+use cranelift_module::default_libcall_names;
+use cranelift_object::{ObjectBuilder, ObjectModule};
+use indexmap::IndexMap;
+use kclvm_ast::ast;
+use std::collections::HashMap;
+
+use crate::codegen::abi::Align;
+use crate::codegen::traits::*;
+use crate::codegen::MODULE_NAME;
+
+use super::context::CraneliftCodeGenContext;
+use super::emit::host_isa;
+
+fn test_program() -> ast::Program {
+    ast::Program {
+        root: String::new(),
+        main: String::new(),
+        pkgs: HashMap::new(),
+    }
+}
+
+fn test_module() -> ObjectModule {
+    let isa = host_isa(0, true).unwrap();
+    ObjectModule::new(ObjectBuilder::new(isa, MODULE_NAME, default_libcall_names()).unwrap())
+}
+
+#[test]
+fn test_array_alloca() {
+    let program = test_program();
+    let ctx = CraneliftCodeGenContext::new(test_module(), &program, IndexMap::new(), false);
+    let function = ctx.add_function("test_array_alloca");
+    ctx.push_function(function);
+    let align = Align::from_bytes(8).unwrap();
+    // The arrays are allocated in the stack slots with static sizes.
+    let ptr = ctx.array_alloca(types::I64, 4, "", align).unwrap();
+    // The stack slots larger than 4 GiB can not be allocated.
+    assert_eq!(
+        ctx.array_alloca(types::I64, u32::MAX, "", align)
+            .unwrap_err()
+            .message,
+        "stack allocations larger than 4 GiB are not supported by the Cranelift backend"
+    );
+    ctx.ret(ptr);
+    ctx.pop_function().unwrap();
+}
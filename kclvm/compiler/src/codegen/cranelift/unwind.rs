@@ -0,0 +1,245 @@
+// Copyright 2021 The KCL Authors. All rights reserved.
+
+use cranelift_codegen::gimli::write::{
+    Address, CieId, EhFrame, EndianVec, Error as GimliError, FrameTable, Result as GimliResult,
+    Writer,
+};
+use cranelift_codegen::gimli::{self, RunTimeEndian};
+use cranelift_codegen::isa::{unwind::UnwindInfo, TargetIsa};
+use cranelift_module::FuncId;
+use cranelift_object::ObjectProduct;
+use object::write::{Relocation, StandardSegment};
+use object::{RelocationEncoding, RelocationFlags, RelocationKind, SectionKind};
+use std::error::Error;
+
+/// The relocation of a function address written in the `.eh_frame` section.
+struct EhFrameReloc {
+    offset: u64,
+    size: u8,
+    function: FuncId,
+    addend: i64,
+    kind: RelocationKind,
+}
+
+/// EhFrameWriter writes the `.eh_frame` section and records the relocations of
+/// the function addresses, which are resolved by the linker.
+struct EhFrameWriter {
+    writer: EndianVec<RunTimeEndian>,
+    relocs: Vec<EhFrameReloc>,
+}
+
+impl Writer for EhFrameWriter {
+    type Endian = RunTimeEndian;
+
+    fn endian(&self) -> Self::Endian {
+        self.writer.endian()
+    }
+
+    fn len(&self) -> usize {
+        self.writer.len()
+    }
+
+    fn write(&mut self, bytes: &[u8]) -> GimliResult<()> {
+        self.writer.write(bytes)
+    }
+
+    fn write_at(&mut self, offset: usize, bytes: &[u8]) -> GimliResult<()> {
+        self.writer.write_at(offset, bytes)
+    }
+
+    fn write_address(&mut self, address: Address, size: u8) -> GimliResult<()> {
+        match address {
+            Address::Constant(val) => self.write_udata(val, size),
+            Address::Symbol { symbol, addend } => {
+                self.relocs.push(EhFrameReloc {
+                    offset: self.len() as u64,
+                    size,
+                    function: FuncId::from_u32(symbol as u32),
+                    addend,
+                    kind: RelocationKind::Absolute,
+                });
+                self.write_udata(0, size)
+            }
+        }
+    }
+
+    fn write_eh_pointer(
+        &mut self,
+        address: Address,
+        eh_pe: gimli::DwEhPe,
+        size: u8,
+    ) -> GimliResult<()> {
+        match address {
+            Address::Constant(val) if eh_pe.application() == gimli::DW_EH_PE_absptr => {
+                self.write_eh_pointer_data(val, eh_pe.format(), size)
+            }
+            Address::Symbol { symbol, addend } if eh_pe.application() == gimli::DW_EH_PE_pcrel => {
+                let size = match eh_pe.format() {
+                    gimli::DW_EH_PE_sdata4 => 4,
+                    gimli::DW_EH_PE_sdata8 => 8,
+                    _ => return Err(GimliError::UnsupportedPointerEncoding(eh_pe)),
+                };
+                self.relocs.push(EhFrameReloc {
+                    offset: self.len() as u64,
+                    size,
+                    function: FuncId::from_u32(symbol as u32),
+                    addend,
+                    kind: RelocationKind::Relative,
+                });
+                self.write_udata(0, size)
+            }
+            _ => Err(GimliError::UnsupportedPointerEncoding(eh_pe)),
+        }
+    }
+}
+
+/// UnwindContext collects the unwind information of the generated functions into the
+/// `.eh_frame` section, so that the runtime panics can unwind through the generated
+/// code to the runtime entry which catches them.
+pub(crate) struct UnwindContext {
+    endian: RunTimeEndian,
+    frame_table: FrameTable,
+    cie_id: Option<CieId>,
+}
+
+impl UnwindContext {
+    /// New an UnwindContext, the function addresses are encoded relative to the
+    /// `.eh_frame` section for the position independent object files.
+    pub(crate) fn new(isa: &dyn TargetIsa, pic: bool) -> Self {
+        let endian = match isa.endianness() {
+            cranelift_codegen::ir::Endianness::Little => RunTimeEndian::Little,
+            cranelift_codegen::ir::Endianness::Big => RunTimeEndian::Big,
+        };
+        let mut frame_table = FrameTable::default();
+        let cie_id = isa.create_systemv_cie().map(|mut cie| {
+            if pic {
+                cie.fde_address_encoding =
+                    gimli::DwEhPe(gimli::DW_EH_PE_pcrel.0 | gimli::DW_EH_PE_sdata4.0);
+            }
+            frame_table.add_cie(cie)
+        });
+        UnwindContext {
+            endian,
+            frame_table,
+            cie_id,
+        }
+    }
+
+    /// Add the unwind information of the function at the address, which is the
+    /// function symbol for the object files and the code address for the JIT code.
+    pub(crate) fn add_function(&mut self, address: Address, info: &UnwindInfo) {
+        if let (Some(cie_id), UnwindInfo::SystemV(info)) = (self.cie_id, info) {
+            self.frame_table.add_fde(cie_id, info.to_fde(address));
+        }
+    }
+
+    /// Write the `.eh_frame` section with the function address relocations.
+    fn write(&self) -> Result<EhFrameWriter, Box<dyn Error>> {
+        let mut eh_frame = EhFrame(EhFrameWriter {
+            writer: EndianVec::new(self.endian),
+            relocs: vec![],
+        });
+        self.frame_table.write_eh_frame(&mut eh_frame)?;
+        Ok(eh_frame.0)
+    }
+
+    /// Emit the `.eh_frame` section into the object file.
+    pub(crate) fn emit(&self, product: &mut ObjectProduct) -> Result<(), Box<dyn Error>> {
+        let eh_frame = self.write()?;
+        if eh_frame.writer.slice().is_empty() {
+            return Ok(());
+        }
+        let segment = product.object.segment_name(StandardSegment::Data).to_vec();
+        let section =
+            product
+                .object
+                .add_section(segment, b".eh_frame".to_vec(), SectionKind::ReadOnlyData);
+        product
+            .object
+            .set_section_data(section, eh_frame.writer.into_vec(), 8);
+        for reloc in eh_frame.relocs {
+            let symbol = product.function_symbol(reloc.function);
+            let (symbol, offset) = product
+                .object
+                .symbol_section_and_offset(symbol)
+                .unwrap_or((symbol, 0));
+            product.object.add_relocation(
+                section,
+                Relocation {
+                    offset: reloc.offset,
+                    symbol,
+                    addend: offset as i64 + reloc.addend,
+                    flags: RelocationFlags::Generic {
+                        kind: reloc.kind,
+                        encoding: RelocationEncoding::Generic,
+                        size: reloc.size * 8,
+                    },
+                },
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Register the `.eh_frame` section of the JIT code to the unwinder, and the
+    /// section is deregistered when the returned registration is dropped.
+    ///
+    /// # Safety
+    ///
+    /// The functions in the section must be alive until the registration is dropped.
+    pub(crate) unsafe fn register(&self) -> Result<UnwindRegistration, Box<dyn Error>> {
+        let eh_frame = self.write()?;
+        let mut eh_frame = eh_frame.writer.into_vec();
+        if eh_frame.is_empty() {
+            return Ok(UnwindRegistration {
+                eh_frame: vec![],
+                registrations: vec![],
+            });
+        }
+        // The terminator of the section required by the libgcc unwinder.
+        eh_frame.extend_from_slice(&[0; 4]);
+        let mut registrations = vec![];
+        if cfg!(target_os = "macos") {
+            // The libunwind unwinder registers one FDE at a time, so walk the entries
+            // of the section and skip the CIE at the beginning.
+            let start = eh_frame.as_ptr();
+            let end = start.add(eh_frame.len() - 4);
+            let mut current = start;
+            while current < end {
+                let len = std::ptr::read_unaligned(current as *const u32) as usize;
+                if current != start {
+                    __register_frame(current);
+                    registrations.push(current as usize);
+                }
+                current = current.add(len + 4);
+            }
+        } else {
+            // The libgcc unwinder registers all the FDEs in the section.
+            __register_frame(eh_frame.as_ptr());
+            registrations.push(eh_frame.as_ptr() as usize);
+        }
+        Ok(UnwindRegistration {
+            eh_frame,
+            registrations,
+        })
+    }
+}
+
+/// The `.eh_frame` section registered to the unwinder.
+pub(crate) struct UnwindRegistration {
+    #[allow(dead_code)]
+    eh_frame: Vec<u8>,
+    registrations: Vec<usize>,
+}
+
+impl Drop for UnwindRegistration {
+    fn drop(&mut self) {
+        for fde in self.registrations.iter().rev() {
+            unsafe { __deregister_frame(*fde as *const u8) };
+        }
+    }
+}
+
+extern "C" {
+    fn __register_frame(fde: *const u8);
+    fn __deregister_frame(fde: *const u8);
+}
@@ -3,6 +3,7 @@
 use std::error;
 use std::fmt::{self, Debug};
 
+#[cfg(feature = "llvm")]
 pub(crate) const VALUE_TYPE_NOT_FOUND_MSG: &str = "Type is not found";
 pub(crate) const CONTEXT_VAR_NOT_FOUND_MSG: &str = "Context variable is not found";
 pub(crate) const FUNCTION_RETURN_VALUE_NOT_FOUND_MSG: &str = "Function return value is not found";
@@ -7,10 +7,11 @@ use inkwell::context::Context;
 use inkwell::memory_buffer::MemoryBuffer;
 use inkwell::module::{Linkage, Module};
 use inkwell::support::LLVMString;
-use inkwell::targets::{CodeModel, FileType, RelocMode};
+use inkwell::targets::{CodeModel, FileType, RelocMode, Target, TargetMachine, TargetTriple};
 use inkwell::types::{BasicMetadataTypeEnum, BasicType, BasicTypeEnum, FunctionType};
 use inkwell::values::{
-    BasicMetadataValueEnum, BasicValueEnum, FunctionValue, IntValue, PointerValue,
+    BasicMetadataValueEnum, BasicValueEnum, CallableValue, FunctionValue, InstructionOpcode,
+    IntValue, PhiValue, PointerValue,
 };
 use inkwell::{AddressSpace, IntPredicate};
 use phf::{phf_map, Map};
@@ -22,23 +23,25 @@ use std::rc::Rc;
 use std::str;
 
 use kclvm_ast::ast;
-use kclvm_ast::walker::TypedResultWalker;
 use kclvm_error::*;
 use kclvm_runtime::{ApiFunc, MAIN_PKG_PATH, PKG_PATH_PREFIX};
 use kclvm_sema::builtin;
 use kclvm_sema::plugin;
 
-use crate::codegen::abi::Align;
-use crate::codegen::{error as kcl_error, EmitOptions, INNER_LEVEL};
+use crate::codegen::abi::{self, Align};
+use crate::codegen::node::NodeCodeGen;
+use crate::codegen::schema::SchemaCodeGen;
+use crate::codegen::{error as kcl_error, BacktrackMeta, EmitKind, EmitOptions, INNER_LEVEL};
 use crate::codegen::{
-    traits::*, ENTRY_NAME, GLOBAL_VAL_ALIGNMENT, KCL_CONTEXT_VAR_NAME, MODULE_NAME,
-    PKG_INIT_FUNCTION_SUFFIX,
+    traits::*, ENTRY_NAME, GLOBAL_VAL_ALIGNMENT, MODULE_NAME, PKG_INIT_FUNCTION_SUFFIX,
 };
 use crate::codegen::{CodeGenContext, GLOBAL_LEVEL};
 use crate::pkgpath_without_prefix;
 use crate::value;
 
-use super::OBJECT_FILE_SUFFIX;
+use super::debug::DebugInfo;
+use super::emit_file_suffix;
+use super::passes::{optimization_level, run_passes};
 
 /// SCALAR_KEY denotes the temp scalar key for the global variable json plan process.
 const SCALAR_KEY: &str = "";
@@ -67,12 +70,12 @@ pub struct Scope<'ctx> {
     pub arguments: RefCell<IndexSet<String>>,
 }
 
-/// Schema internal order independent computation backtracking meta information.
-pub struct BacktrackMeta {
-    pub target: String,
-    pub level: usize,
-    pub count: usize,
-    pub stop: bool,
+/// The phi nodes emulating the parameters of a basic block and the incoming
+/// values of the predecessors.
+#[derive(Default)]
+pub struct BlockParams<'ctx> {
+    pub phis: Vec<PhiValue<'ctx>>,
+    pub incoming: Vec<(BasicBlock<'ctx>, Vec<BasicValueEnum<'ctx>>)>,
 }
 
 /// The LLVM code generator
@@ -103,6 +106,12 @@ pub struct LLVMCodeGenContext<'ctx> {
     // No link mode
     pub no_link: bool,
     pub modules: RefCell<HashMap<String, RefCell<Module<'ctx>>>>,
+    /// The parameters of the basic blocks.
+    pub block_params: RefCell<HashMap<BasicBlock<'ctx>, BlockParams<'ctx>>>,
+    /// The insert blocks of the enclosing functions in the function stack.
+    pub insert_blocks: RefCell<Vec<Option<BasicBlock<'ctx>>>>,
+    /// The DWARF debug info of the generated code, `None` denotes no debug info is emitted.
+    pub debug_info: Option<DebugInfo<'ctx>>,
 }
 
 impl<'ctx> CodeGenObject for BasicValueEnum<'ctx> {}
@@ -139,13 +148,12 @@ impl<'ctx> BuilderMethods for LLVMCodeGenContext<'ctx> {
     fn array_alloca(
         &self,
         ty: Self::Type,
-        len: Self::Value,
+        len: u32,
         name: &str,
         _align: Align,
-    ) -> Self::Value {
-        self.builder
-            .build_array_alloca(ty, len.into_int_value(), name)
-            .into()
+    ) -> Result<Self::Value, kcl_error::KCLError> {
+        let len = self.context.i32_type().const_int(len as u64, false);
+        Ok(self.builder.build_array_alloca(ty, len, name).into())
     }
     /// SSA ret instruction.
     #[inline]
@@ -162,6 +170,46 @@ impl<'ctx> BuilderMethods for LLVMCodeGenContext<'ctx> {
     fn br(&self, dest: Self::BasicBlock) {
         self.builder.build_unconditional_branch(dest);
     }
+    /// SSA br instruction with the block arguments, which are the incoming
+    /// values of the phi nodes of the destination block.
+    fn br_with_args(&self, dest: Self::BasicBlock, args: &[Self::Value]) {
+        let block = self
+            .builder
+            .get_insert_block()
+            .expect(kcl_error::INTERNAL_ERROR_MSG);
+        let mut block_params = self.block_params.borrow_mut();
+        let params = block_params.entry(dest).or_default();
+        for (phi, arg) in params.phis.iter().zip(args) {
+            phi.add_incoming(&[(arg, block)]);
+        }
+        params.incoming.push((block, args.to_vec()));
+        self.builder.build_unconditional_branch(dest);
+    }
+    /// Append a phi node to the block and return it, which is used to
+    /// merge the values from the predecessors.
+    fn append_block_param(&self, block: Self::BasicBlock) -> Self::Value {
+        let cur_bb = self.builder.get_insert_block();
+        let mut inst = block.get_first_instruction();
+        while let Some(phi) = inst.filter(|inst| inst.get_opcode() == InstructionOpcode::Phi) {
+            inst = phi.get_next_instruction();
+        }
+        match inst {
+            Some(inst) => self.builder.position_before(&inst),
+            None => self.builder.position_at_end(block),
+        };
+        let phi = self.builder.build_phi(self.value_ptr_type(), "");
+        let mut block_params = self.block_params.borrow_mut();
+        let params = block_params.entry(block).or_default();
+        let index = params.phis.len();
+        for (pred, args) in &params.incoming {
+            phi.add_incoming(&[(&args[index], *pred)]);
+        }
+        params.phis.push(phi);
+        if let Some(cur_bb) = cur_bb {
+            self.builder.position_at_end(cur_bb);
+        }
+        phi.as_basic_value()
+    }
     /// SSA cond br instruction.
     #[inline]
     fn cond_br(&self, cond: Self::Value, then_bb: Self::BasicBlock, else_bb: Self::BasicBlock) {
@@ -179,10 +227,17 @@ impl<'ctx> BuilderMethods for LLVMCodeGenContext<'ctx> {
         self.builder
             .build_select(cond.into_int_value(), then_val, else_val, "")
     }
-    /// SSA va arg instruction.
+    /// SSA icmp instruction.
     #[inline]
-    fn va_arg(&self, list: Self::Value, ty: Self::Type) -> Self::Value {
-        self.builder.build_va_arg(list.into_pointer_value(), ty, "")
+    fn icmp(&self, op: abi::IntPredicate, lhs: Self::Value, rhs: Self::Value) -> Self::Value {
+        let op = match op {
+            abi::IntPredicate::Equal => IntPredicate::EQ,
+            abi::IntPredicate::NotEqual => IntPredicate::NE,
+            abi::IntPredicate::UnsignedLessThan => IntPredicate::ULT,
+        };
+        self.builder
+            .build_int_compare(op, lhs.into_int_value(), rhs.into_int_value(), "")
+            .into()
     }
     /// SSA extract element instruction.
     #[inline]
@@ -190,22 +245,6 @@ impl<'ctx> BuilderMethods for LLVMCodeGenContext<'ctx> {
         self.builder
             .build_extract_element(vec.into_vector_value(), idx.into_int_value(), "")
     }
-    /// SSA extract value instruction.
-    #[inline]
-    fn extract_value(&self, agg_val: Self::Value, idx: u32) -> Self::Value {
-        self.builder
-            .build_extract_value(agg_val.into_array_value(), idx, "")
-            .expect(kcl_error::INTERNAL_ERROR_MSG)
-    }
-    /// SSA insert value instruction.
-    #[inline]
-    fn insert_value(&self, agg_val: Self::Value, elt: Self::Value, idx: u32) -> Self::Value {
-        self.builder
-            .build_insert_value(agg_val.into_array_value(), elt, idx, "")
-            .expect(kcl_error::INTERNAL_ERROR_MSG)
-            .into_array_value()
-            .into()
-    }
     /// SSA function invoke instruction.
     #[inline]
     fn invoke(
@@ -266,14 +305,6 @@ impl<'ctx> BuilderMethods for LLVMCodeGenContext<'ctx> {
                 .into()
         }
     }
-    /// SSA struct gep instruction.
-    #[inline]
-    fn struct_gep(&self, _ty: Self::Type, ptr: Self::Value, idx: u32) -> Self::Value {
-        self.builder
-            .build_struct_gep(ptr.into_pointer_value(), idx, "")
-            .expect(kcl_error::INTERNAL_ERROR_MSG)
-            .into()
-    }
     /// SSA cast pointer to int.
     #[inline]
     fn ptr_to_int(&self, val: Self::Value, dest_ty: Self::Type) -> Self::Value {
@@ -307,6 +338,13 @@ impl<'ctx> BuilderMethods for LLVMCodeGenContext<'ctx> {
             .build_pointer_cast(val.into_pointer_value(), dest_ty.into_pointer_type(), "")
             .into()
     }
+    /// Get the nth parameter of the current function.
+    #[inline]
+    fn function_param(&self, index: usize) -> Self::Value {
+        self.current_function()
+            .get_nth_param(index as u32)
+            .expect(kcl_error::INTERNAL_ERROR_MSG)
+    }
     /// Lookup a known function named `name`.
     fn lookup_function(&self, name: &str) -> Self::Function {
         if self.no_link {
@@ -345,6 +383,43 @@ impl<'ctx> BuilderMethods for LLVMCodeGenContext<'ctx> {
     }
 }
 
+impl<'ctx> AggregateBuilderMethods for LLVMCodeGenContext<'ctx> {
+    /// Native struct type.
+    #[inline]
+    fn struct_type(&self, els: &[Self::Type], packed: bool) -> Self::Type {
+        self.context.struct_type(els, packed).into()
+    }
+    /// SSA va arg instruction.
+    #[inline]
+    fn va_arg(&self, list: Self::Value, ty: Self::Type) -> Self::Value {
+        self.builder.build_va_arg(list.into_pointer_value(), ty, "")
+    }
+    /// SSA extract value instruction.
+    #[inline]
+    fn extract_value(&self, agg_val: Self::Value, idx: u32) -> Self::Value {
+        self.builder
+            .build_extract_value(agg_val.into_array_value(), idx, "")
+            .expect(kcl_error::INTERNAL_ERROR_MSG)
+    }
+    /// SSA insert value instruction.
+    #[inline]
+    fn insert_value(&self, agg_val: Self::Value, elt: Self::Value, idx: u32) -> Self::Value {
+        self.builder
+            .build_insert_value(agg_val.into_array_value(), elt, idx, "")
+            .expect(kcl_error::INTERNAL_ERROR_MSG)
+            .into_array_value()
+            .into()
+    }
+    /// SSA struct gep instruction.
+    #[inline]
+    fn struct_gep(&self, _ty: Self::Type, ptr: Self::Value, idx: u32) -> Self::Value {
+        self.builder
+            .build_struct_gep(ptr.into_pointer_value(), idx, "")
+            .expect(kcl_error::INTERNAL_ERROR_MSG)
+            .into()
+    }
+}
+
 /* Value methods */
 
 impl<'ctx> ValueMethods for LLVMCodeGenContext<'ctx> {
@@ -353,7 +428,10 @@ impl<'ctx> ValueMethods for LLVMCodeGenContext<'ctx> {
         let i64_type = self.context.i64_type();
         self.build_call(
             &ApiFunc::kclvm_value_Int.name(),
-            &[i64_type.const_int(v as u64, false).into()],
+            &[
+                self.current_runtime_ctx_ptr(),
+                i64_type.const_int(v as u64, false).into(),
+            ],
         )
     }
 
@@ -362,14 +440,20 @@ impl<'ctx> ValueMethods for LLVMCodeGenContext<'ctx> {
         let f64_type = self.context.f64_type();
         self.build_call(
             &ApiFunc::kclvm_value_Float.name(),
-            &[f64_type.const_float(v).into()],
+            &[
+                self.current_runtime_ctx_ptr(),
+                f64_type.const_float(v).into(),
+            ],
         )
     }
 
     /// Construct a string value using &str
     fn string_value(&self, v: &str) -> Self::Value {
         let string_ptr_value = self.native_global_string(v, "");
-        self.build_call(&ApiFunc::kclvm_value_Str.name(), &[string_ptr_value.into()])
+        self.build_call(
+            &ApiFunc::kclvm_value_Str.name(),
+            &[self.current_runtime_ctx_ptr(), string_ptr_value],
+        )
     }
 
     /// Construct a bool value
@@ -383,30 +467,44 @@ impl<'ctx> ValueMethods for LLVMCodeGenContext<'ctx> {
 
     /// Construct a None value
     fn none_value(&self) -> Self::Value {
-        self.build_call(&ApiFunc::kclvm_value_None.name(), &[])
+        self.build_call(
+            &ApiFunc::kclvm_value_None.name(),
+            &[self.current_runtime_ctx_ptr()],
+        )
     }
 
     /// Construct a Undefined value
     fn undefined_value(&self) -> Self::Value {
-        self.build_call(&ApiFunc::kclvm_value_Undefined.name(), &[])
+        self.build_call(
+            &ApiFunc::kclvm_value_Undefined.name(),
+            &[self.current_runtime_ctx_ptr()],
+        )
     }
 
     /// Construct a empty kcl list value
     fn list_value(&self) -> Self::Value {
-        self.build_call(&ApiFunc::kclvm_value_List.name(), &[])
+        self.build_call(
+            &ApiFunc::kclvm_value_List.name(),
+            &[self.current_runtime_ctx_ptr()],
+        )
     }
 
     /// Construct a list value with `n` elements
     fn list_values(&self, values: &[Self::Value]) -> Self::Value {
+        let mut args = vec![self.current_runtime_ctx_ptr()];
+        args.extend_from_slice(values);
         self.build_call(
             &format!("{}{}", ApiFunc::kclvm_value_List.name(), values.len()),
-            values,
+            &args,
         )
     }
 
     /// Construct a empty kcl dict value.
     fn dict_value(&self) -> Self::Value {
-        self.build_call(&ApiFunc::kclvm_value_Dict.name(), &[])
+        self.build_call(
+            &ApiFunc::kclvm_value_Dict.name(),
+            &[self.current_runtime_ctx_ptr()],
+        )
     }
 
     /// Construct a unit value
@@ -417,39 +515,46 @@ impl<'ctx> ValueMethods for LLVMCodeGenContext<'ctx> {
         self.build_call(
             &ApiFunc::kclvm_value_Unit.name(),
             &[
+                self.current_runtime_ctx_ptr(),
                 f64_type.const_float(v).into(),
                 i64_type.const_int(raw as u64, false).into(),
-                unit_native_str.into(),
+                unit_native_str,
             ],
         )
     }
     /// Construct a function value using a native function.
     fn function_value(&self, function: FunctionValue<'ctx>) -> Self::Value {
         let func_name = function.get_name().to_str().unwrap();
-        let func_name_ptr = self.native_global_string(func_name, func_name).into();
-        let lambda_fn_ptr = self.builder.build_bitcast(
+        let func_name_ptr = self.native_global_string(func_name, func_name);
+        let lambda_fn_ptr = self.function_ptr(function);
+        self.build_call(
+            &ApiFunc::kclvm_value_Function_using_ptr.name(),
+            &[self.current_runtime_ctx_ptr(), lambda_fn_ptr, func_name_ptr],
+        )
+    }
+    /// Get the native function pointer of the function.
+    fn function_ptr(&self, function: FunctionValue<'ctx>) -> Self::Value {
+        self.builder.build_bitcast(
             function.as_global_value().as_pointer_value(),
             self.context.i64_type().ptr_type(AddressSpace::default()),
             "",
-        );
-        self.build_call(
-            &ApiFunc::kclvm_value_Function_using_ptr.name(),
-            &[lambda_fn_ptr, func_name_ptr],
         )
     }
     /// Construct a closure function value with the closure variable.
     fn closure_value(&self, function: FunctionValue<'ctx>, closure: Self::Value) -> Self::Value {
         let func_name = function.get_name().to_str().unwrap();
-        let func_name_ptr = self.native_global_string(func_name, func_name).into();
+        let func_name_ptr = self.native_global_string(func_name, func_name);
         // Convert the function to a i64 pointer to store it into the function value.
-        let fn_ptr = self.builder.build_bitcast(
-            function.as_global_value().as_pointer_value(),
-            self.context.i64_type().ptr_type(AddressSpace::default()),
-            "",
-        );
+        let fn_ptr = self.function_ptr(function);
         self.build_call(
             &ApiFunc::kclvm_value_Function.name(),
-            &[fn_ptr, closure, func_name_ptr, self.native_i8_zero().into()],
+            &[
+                self.current_runtime_ctx_ptr(),
+                fn_ptr,
+                closure,
+                func_name_ptr,
+                self.native_i8_zero(),
+            ],
         )
     }
     /// Construct a schema function value using native functions.
@@ -486,6 +591,7 @@ impl<'ctx> ValueMethods for LLVMCodeGenContext<'ctx> {
             .build_call(
                 self.lookup_function(&ApiFunc::kclvm_value_schema_function.name()),
                 &[
+                    self.current_runtime_ctx_ptr().into(),
                     schema_body_fn_ptr.into(),
                     check_block_fn_ptr.into(),
                     runtime_type_native_str.into(),
@@ -530,127 +636,190 @@ impl<'ctx> ValueMethods for LLVMCodeGenContext<'ctx> {
         global_var.set_initializer(&tpe.const_zero());
         global_var.as_pointer_value().into()
     }
-    /// Get the global runtime context pointer.
-    fn global_ctx_ptr(&self) -> Self::Value {
-        if self.no_link {
-            self.build_call(&ApiFunc::kclvm_context_current.name(), &[])
-        } else {
-            let ctx_ptr = self
-                .module
-                .get_global(KCL_CONTEXT_VAR_NAME)
-                .expect(kcl_error::CONTEXT_VAR_NOT_FOUND_MSG)
-                .as_pointer_value();
-            self.builder.build_load(ctx_ptr, "")
-        }
+    /// Get the runtime context pointer of the current function, which is
+    /// the first parameter of all the KCL functions.
+    fn current_runtime_ctx_ptr(&self) -> Self::Value {
+        self.current_function()
+            .get_first_param()
+            .expect(kcl_error::CONTEXT_VAR_NOT_FOUND_MSG)
     }
 }
 
 impl<'ctx> ValueCalculationMethods for LLVMCodeGenContext<'ctx> {
     /// lhs + rhs
     fn add(&self, lhs: Self::Value, rhs: Self::Value) -> Self::Value {
-        self.build_call(&ApiFunc::kclvm_value_op_add.name(), &[lhs, rhs])
+        self.build_call(
+            &ApiFunc::kclvm_value_op_add.name(),
+            &[self.current_runtime_ctx_ptr(), lhs, rhs],
+        )
     }
     /// lhs - rhs
     fn sub(&self, lhs: Self::Value, rhs: Self::Value) -> Self::Value {
-        self.build_call(&ApiFunc::kclvm_value_op_sub.name(), &[lhs, rhs])
+        self.build_call(
+            &ApiFunc::kclvm_value_op_sub.name(),
+            &[self.current_runtime_ctx_ptr(), lhs, rhs],
+        )
     }
     /// lhs * rhs
     fn mul(&self, lhs: Self::Value, rhs: Self::Value) -> Self::Value {
-        self.build_call(&ApiFunc::kclvm_value_op_mul.name(), &[lhs, rhs])
+        self.build_call(
+            &ApiFunc::kclvm_value_op_mul.name(),
+            &[self.current_runtime_ctx_ptr(), lhs, rhs],
+        )
     }
     /// lhs / rhs
     fn div(&self, lhs: Self::Value, rhs: Self::Value) -> Self::Value {
-        self.build_call(&ApiFunc::kclvm_value_op_div.name(), &[lhs, rhs])
+        self.build_call(
+            &ApiFunc::kclvm_value_op_div.name(),
+            &[self.current_runtime_ctx_ptr(), lhs, rhs],
+        )
     }
     /// lhs // rhs
     fn floor_div(&self, lhs: Self::Value, rhs: Self::Value) -> Self::Value {
-        self.build_call(&ApiFunc::kclvm_value_op_floor_div.name(), &[lhs, rhs])
+        self.build_call(
+            &ApiFunc::kclvm_value_op_floor_div.name(),
+            &[self.current_runtime_ctx_ptr(), lhs, rhs],
+        )
     }
     /// lhs % rhs
     fn r#mod(&self, lhs: Self::Value, rhs: Self::Value) -> Self::Value {
-        self.build_call(&ApiFunc::kclvm_value_op_mod.name(), &[lhs, rhs])
+        self.build_call(
+            &ApiFunc::kclvm_value_op_mod.name(),
+            &[self.current_runtime_ctx_ptr(), lhs, rhs],
+        )
     }
     /// lhs ** rhs
     fn pow(&self, lhs: Self::Value, rhs: Self::Value) -> Self::Value {
-        self.build_call(&ApiFunc::kclvm_value_op_pow.name(), &[lhs, rhs])
+        self.build_call(
+            &ApiFunc::kclvm_value_op_pow.name(),
+            &[self.current_runtime_ctx_ptr(), lhs, rhs],
+        )
     }
     /// lhs << rhs
     fn bit_lshift(&self, lhs: Self::Value, rhs: Self::Value) -> Self::Value {
-        self.build_call(&ApiFunc::kclvm_value_op_bit_lshift.name(), &[lhs, rhs])
+        self.build_call(
+            &ApiFunc::kclvm_value_op_bit_lshift.name(),
+            &[self.current_runtime_ctx_ptr(), lhs, rhs],
+        )
     }
     /// lhs >> rhs
     fn bit_rshift(&self, lhs: Self::Value, rhs: Self::Value) -> Self::Value {
-        self.build_call(&ApiFunc::kclvm_value_op_bit_rshift.name(), &[lhs, rhs])
+        self.build_call(
+            &ApiFunc::kclvm_value_op_bit_rshift.name(),
+            &[self.current_runtime_ctx_ptr(), lhs, rhs],
+        )
     }
     /// lhs & rhs
     fn bit_and(&self, lhs: Self::Value, rhs: Self::Value) -> Self::Value {
-        self.build_call(&ApiFunc::kclvm_value_op_bit_and.name(), &[lhs, rhs])
+        self.build_call(
+            &ApiFunc::kclvm_value_op_bit_and.name(),
+            &[self.current_runtime_ctx_ptr(), lhs, rhs],
+        )
     }
     /// lhs | rhs
     fn bit_or(&self, lhs: Self::Value, rhs: Self::Value) -> Self::Value {
-        self.build_call(&ApiFunc::kclvm_value_op_bit_or.name(), &[lhs, rhs])
+        self.build_call(
+            &ApiFunc::kclvm_value_op_bit_or.name(),
+            &[self.current_runtime_ctx_ptr(), lhs, rhs],
+        )
     }
     /// lhs ^ rhs
     fn bit_xor(&self, lhs: Self::Value, rhs: Self::Value) -> Self::Value {
-        self.build_call(&ApiFunc::kclvm_value_op_bit_xor.name(), &[lhs, rhs])
+        self.build_call(
+            &ApiFunc::kclvm_value_op_bit_xor.name(),
+            &[self.current_runtime_ctx_ptr(), lhs, rhs],
+        )
     }
     /// lhs and rhs
     fn logic_and(&self, lhs: Self::Value, rhs: Self::Value) -> Self::Value {
-        self.build_call(&ApiFunc::kclvm_value_logic_and.name(), &[lhs, rhs])
+        self.build_call(
+            &ApiFunc::kclvm_value_logic_and.name(),
+            &[self.current_runtime_ctx_ptr(), lhs, rhs],
+        )
     }
     /// lhs or rhs
     fn logic_or(&self, lhs: Self::Value, rhs: Self::Value) -> Self::Value {
-        self.build_call(&ApiFunc::kclvm_value_logic_or.name(), &[lhs, rhs])
+        self.build_call(
+            &ApiFunc::kclvm_value_logic_or.name(),
+            &[self.current_runtime_ctx_ptr(), lhs, rhs],
+        )
     }
     /// lhs == rhs
     fn cmp_equal_to(&self, lhs: Self::Value, rhs: Self::Value) -> Self::Value {
-        self.build_call(&ApiFunc::kclvm_value_cmp_equal_to.name(), &[lhs, rhs])
+        self.build_call(
+            &ApiFunc::kclvm_value_cmp_equal_to.name(),
+            &[self.current_runtime_ctx_ptr(), lhs, rhs],
+        )
     }
     /// lhs != rhs
     fn cmp_not_equal_to(&self, lhs: Self::Value, rhs: Self::Value) -> Self::Value {
-        self.build_call(&ApiFunc::kclvm_value_cmp_not_equal_to.name(), &[lhs, rhs])
+        self.build_call(
+            &ApiFunc::kclvm_value_cmp_not_equal_to.name(),
+            &[self.current_runtime_ctx_ptr(), lhs, rhs],
+        )
     }
     /// lhs > rhs
     fn cmp_greater_than(&self, lhs: Self::Value, rhs: Self::Value) -> Self::Value {
-        self.build_call(&ApiFunc::kclvm_value_cmp_greater_than.name(), &[lhs, rhs])
+        self.build_call(
+            &ApiFunc::kclvm_value_cmp_greater_than.name(),
+            &[self.current_runtime_ctx_ptr(), lhs, rhs],
+        )
     }
     /// lhs >= rhs
     fn cmp_greater_than_or_equal(&self, lhs: Self::Value, rhs: Self::Value) -> Self::Value {
         self.build_call(
             &ApiFunc::kclvm_value_cmp_greater_than_or_equal.name(),
-            &[lhs, rhs],
+            &[self.current_runtime_ctx_ptr(), lhs, rhs],
         )
     }
     /// lhs < rhs
     fn cmp_less_than(&self, lhs: Self::Value, rhs: Self::Value) -> Self::Value {
-        self.build_call(&ApiFunc::kclvm_value_cmp_less_than.name(), &[lhs, rhs])
+        self.build_call(
+            &ApiFunc::kclvm_value_cmp_less_than.name(),
+            &[self.current_runtime_ctx_ptr(), lhs, rhs],
+        )
     }
     /// lhs <= rhs
     fn cmp_less_than_or_equal(&self, lhs: Self::Value, rhs: Self::Value) -> Self::Value {
         self.build_call(
             &ApiFunc::kclvm_value_cmp_less_than_or_equal.name(),
-            &[lhs, rhs],
+            &[self.current_runtime_ctx_ptr(), lhs, rhs],
         )
     }
     /// lhs as rhs
     fn r#as(&self, lhs: Self::Value, rhs: Self::Value) -> Self::Value {
-        self.build_call(&ApiFunc::kclvm_value_as.name(), &[lhs, rhs])
+        self.build_call(
+            &ApiFunc::kclvm_value_as.name(),
+            &[self.current_runtime_ctx_ptr(), lhs, rhs],
+        )
     }
     /// lhs is rhs
     fn is(&self, lhs: Self::Value, rhs: Self::Value) -> Self::Value {
-        self.build_call(&ApiFunc::kclvm_value_is.name(), &[lhs, rhs])
+        self.build_call(
+            &ApiFunc::kclvm_value_is.name(),
+            &[self.current_runtime_ctx_ptr(), lhs, rhs],
+        )
     }
     /// lhs is not rhs
     fn is_not(&self, lhs: Self::Value, rhs: Self::Value) -> Self::Value {
-        self.build_call(&ApiFunc::kclvm_value_is_not.name(), &[lhs, rhs])
+        self.build_call(
+            &ApiFunc::kclvm_value_is_not.name(),
+            &[self.current_runtime_ctx_ptr(), lhs, rhs],
+        )
     }
     /// lhs in rhs
     fn r#in(&self, lhs: Self::Value, rhs: Self::Value) -> Self::Value {
-        self.build_call(&ApiFunc::kclvm_value_in.name(), &[lhs, rhs])
+        self.build_call(
+            &ApiFunc::kclvm_value_in.name(),
+            &[self.current_runtime_ctx_ptr(), lhs, rhs],
+        )
     }
     /// lhs not in rhs
     fn not_in(&self, lhs: Self::Value, rhs: Self::Value) -> Self::Value {
-        self.build_call(&ApiFunc::kclvm_value_not_in.name(), &[lhs, rhs])
+        self.build_call(
+            &ApiFunc::kclvm_value_not_in.name(),
+            &[self.current_runtime_ctx_ptr(), lhs, rhs],
+        )
     }
 }
 
@@ -658,7 +827,10 @@ impl<'ctx> DerivedValueCalculationMethods for LLVMCodeGenContext<'ctx> {
     /// Value subscript a[b]
     #[inline]
     fn value_subscript(&self, value: Self::Value, item: Self::Value) -> Self::Value {
-        self.build_call(&ApiFunc::kclvm_value_subscr.name(), &[value, item])
+        self.build_call(
+            &ApiFunc::kclvm_value_subscr.name(),
+            &[self.current_runtime_ctx_ptr(), value, item],
+        )
     }
     /// Value is truth function, return i1 value.
     fn value_is_truthy(&self, value: Self::Value) -> Self::Value {
@@ -666,23 +838,37 @@ impl<'ctx> DerivedValueCalculationMethods for LLVMCodeGenContext<'ctx> {
             .build_call(&ApiFunc::kclvm_value_is_truthy.name(), &[value])
             .into_int_value();
         self.builder
-            .build_int_compare(IntPredicate::NE, is_truth, self.native_i8_zero(), "")
+            .build_int_compare(
+                IntPredicate::NE,
+                is_truth,
+                self.native_i8_zero().into_int_value(),
+                "",
+            )
             .into()
     }
     /// Value deep copy
     #[inline]
     fn value_deep_copy(&self, value: Self::Value) -> Self::Value {
-        self.build_call(&ApiFunc::kclvm_value_deep_copy.name(), &[value])
+        self.build_call(
+            &ApiFunc::kclvm_value_deep_copy.name(),
+            &[self.current_runtime_ctx_ptr(), value],
+        )
     }
     /// value_union unions two collection elements.
     #[inline]
     fn value_union(&self, lhs: Self::Value, rhs: Self::Value) {
-        self.build_void_call(&ApiFunc::kclvm_value_union.name(), &[lhs, rhs]);
+        self.build_void_call(
+            &ApiFunc::kclvm_value_union.name(),
+            &[self.current_runtime_ctx_ptr(), lhs, rhs],
+        );
     }
     // List get the item using the index.
     #[inline]
     fn list_get(&self, list: Self::Value, index: Self::Value) -> Self::Value {
-        self.build_call(&ApiFunc::kclvm_list_get.name(), &[list, index])
+        self.build_call(
+            &ApiFunc::kclvm_list_get.name(),
+            &[self.current_runtime_ctx_ptr(), list, index],
+        )
     }
     // List set the item using the index.
     #[inline]
@@ -700,7 +886,7 @@ impl<'ctx> DerivedValueCalculationMethods for LLVMCodeGenContext<'ctx> {
     ) -> Self::Value {
         self.build_call(
             &ApiFunc::kclvm_value_slice.name(),
-            &[list, start, stop, step],
+            &[self.current_runtime_ctx_ptr(), list, start, stop, step],
         )
     }
     /// Append a item into the list.
@@ -716,12 +902,18 @@ impl<'ctx> DerivedValueCalculationMethods for LLVMCodeGenContext<'ctx> {
     /// Runtime list value pop
     #[inline]
     fn list_pop(&self, list: Self::Value) -> Self::Value {
-        self.build_call(&ApiFunc::kclvm_list_pop.name(), &[list])
+        self.build_call(
+            &ApiFunc::kclvm_list_pop.name(),
+            &[self.current_runtime_ctx_ptr(), list],
+        )
     }
     /// Runtime list pop the first value
     #[inline]
     fn list_pop_first(&self, list: Self::Value) -> Self::Value {
-        self.build_call(&ApiFunc::kclvm_list_pop_first.name(), &[list])
+        self.build_call(
+            &ApiFunc::kclvm_list_pop_first.name(),
+            &[self.current_runtime_ctx_ptr(), list],
+        )
     }
     /// List clear value.
     #[inline]
@@ -731,12 +923,18 @@ impl<'ctx> DerivedValueCalculationMethods for LLVMCodeGenContext<'ctx> {
     /// Return number of occurrences of the list value.
     #[inline]
     fn list_count(&self, list: Self::Value, item: Self::Value) -> Self::Value {
-        self.build_call(&ApiFunc::kclvm_list_count.name(), &[list, item])
+        self.build_call(
+            &ApiFunc::kclvm_list_count.name(),
+            &[self.current_runtime_ctx_ptr(), list, item],
+        )
     }
     /// Return first index of the list value. Panic if the value is not present.
     #[inline]
     fn list_find(&self, list: Self::Value, item: Self::Value) -> Self::Value {
-        self.build_call(&ApiFunc::kclvm_list_find.name(), &[list, item])
+        self.build_call(
+            &ApiFunc::kclvm_list_find.name(),
+            &[self.current_runtime_ctx_ptr(), list, item],
+        )
     }
     /// Insert object before index of the list value.
     #[inline]
@@ -751,27 +949,39 @@ impl<'ctx> DerivedValueCalculationMethods for LLVMCodeGenContext<'ctx> {
     /// Dict get the value of the key.
     #[inline]
     fn dict_get(&self, dict: Self::Value, key: Self::Value) -> Self::Value {
-        self.build_call(&ApiFunc::kclvm_dict_get_value.name(), &[dict, key])
+        self.build_call(
+            &ApiFunc::kclvm_dict_get_value.name(),
+            &[self.current_runtime_ctx_ptr(), dict, key],
+        )
     }
     /// Dict set the value of the key.
     #[inline]
     fn dict_set(&self, dict: Self::Value, key: Self::Value, value: Self::Value) {
-        self.build_void_call(&ApiFunc::kclvm_dict_set_value.name(), &[dict, key, value])
+        self.build_void_call(
+            &ApiFunc::kclvm_dict_set_value.name(),
+            &[self.current_runtime_ctx_ptr(), dict, key, value],
+        )
     }
     /// Return all dict keys.
     #[inline]
     fn dict_keys(&self, dict: Self::Value) -> Self::Value {
-        self.build_call(&ApiFunc::kclvm_dict_keys.name(), &[dict])
+        self.build_call(
+            &ApiFunc::kclvm_dict_keys.name(),
+            &[self.current_runtime_ctx_ptr(), dict],
+        )
     }
     /// Return all dict values.
     #[inline]
     fn dict_values(&self, dict: Self::Value) -> Self::Value {
-        self.build_call(&ApiFunc::kclvm_dict_values.name(), &[dict])
+        self.build_call(
+            &ApiFunc::kclvm_dict_values.name(),
+            &[self.current_runtime_ctx_ptr(), dict],
+        )
     }
     /// Dict clear value.
     #[inline]
     fn dict_clear(&self, dict: Self::Value) {
-        self.build_void_call(&ApiFunc::kclvm_dict_insert_value.name(), &[dict])
+        self.build_void_call(&ApiFunc::kclvm_dict_clear.name(), &[dict])
     }
     /// Dict pop the value of the key.
     #[inline]
@@ -794,12 +1004,19 @@ impl<'ctx> DerivedValueCalculationMethods for LLVMCodeGenContext<'ctx> {
         op: i32,
         insert_index: i32,
     ) {
-        let name = self.native_global_string(key, "").into();
+        let name = self.native_global_string(key, "");
         let op = self.native_int_value(op);
         let insert_index = self.native_int_value(insert_index);
         self.build_void_call(
             &ApiFunc::kclvm_dict_insert.name(),
-            &[dict, name, value, op, insert_index],
+            &[
+                self.current_runtime_ctx_ptr(),
+                dict,
+                name,
+                value,
+                op,
+                insert_index,
+            ],
         );
     }
 
@@ -818,7 +1035,14 @@ impl<'ctx> DerivedValueCalculationMethods for LLVMCodeGenContext<'ctx> {
         let insert_index = self.native_int_value(insert_index);
         self.build_void_call(
             &ApiFunc::kclvm_dict_insert_value.name(),
-            &[dict, key, value, op, insert_index],
+            &[
+                self.current_runtime_ctx_ptr(),
+                dict,
+                key,
+                value,
+                op,
+                insert_index,
+            ],
         );
     }
 }
@@ -856,11 +1080,6 @@ impl<'ctx> BaseTypeMethods for LLVMCodeGenContext<'ctx> {
     fn f64_type(&self) -> Self::Type {
         self.context.f64_type().into()
     }
-    /// Native struct type.
-    #[inline]
-    fn struct_type(&self, els: &[Self::Type], packed: bool) -> Self::Type {
-        self.context.struct_type(els, packed).into()
-    }
     /// Native pointer type of `ty`.
     #[inline]
     fn ptr_type_to(&self, ty: Self::Type) -> Self::Type {
@@ -1061,6 +1280,7 @@ impl<'ctx> LLVMCodeGenContext<'ctx> {
         program: &'ctx ast::Program,
         import_names: IndexMap<String, IndexMap<String, String>>,
         no_link: bool,
+        debug_info: Option<DebugInfo<'ctx>>,
     ) -> LLVMCodeGenContext<'ctx> {
         LLVMCodeGenContext {
             context,
@@ -1086,6 +1306,9 @@ impl<'ctx> LLVMCodeGenContext<'ctx> {
             import_names,
             no_link,
             modules: RefCell::new(HashMap::new()),
+            block_params: RefCell::new(HashMap::new()),
+            insert_blocks: RefCell::new(vec![]),
+            debug_info,
         }
     }
 
@@ -1145,10 +1368,6 @@ impl<'ctx> LLVMCodeGenContext<'ctx> {
             )
         };
         self.push_function(function);
-        // Add a block named entry into the function
-        let basic_block = self.append_block(ENTRY_NAME);
-        // Set position to the basic block
-        self.builder.position_at_end(basic_block);
         // Get the runtime context
         let ctx_value = function
             .get_first_param()
@@ -1177,18 +1396,6 @@ impl<'ctx> LLVMCodeGenContext<'ctx> {
                 &[ctx_value, import_names],
             );
         }
-        // Store the runtime context to global
-        if !self.no_link {
-            let global_ctx = self.module.add_global(
-                context_ptr_type,
-                Some(AddressSpace::default()),
-                KCL_CONTEXT_VAR_NAME,
-            );
-            global_ctx.set_alignment(GLOBAL_VAL_ALIGNMENT);
-            global_ctx.set_initializer(&context_ptr_type.const_zero());
-            self.builder
-                .build_store(global_ctx.as_pointer_value(), ctx_value);
-        }
         if self.no_link && !has_main_pkg {
             // When compiling a pkgpath separately, only one pkgpath is required in the AST Program
             assert!(self.program.pkgs.len() == 1);
@@ -1205,7 +1412,7 @@ impl<'ctx> LLVMCodeGenContext<'ctx> {
                             .borrow_mut()
                             .push(ast_module.filename.clone());
                     }
-                    self.compile_module_import_and_types(ast_module)
+                    self.compile_module_import_and_types(ast_module)?;
                 }
                 for ast_module in modules {
                     {
@@ -1213,8 +1420,7 @@ impl<'ctx> LLVMCodeGenContext<'ctx> {
                             .borrow_mut()
                             .push(ast_module.filename.clone());
                     }
-                    self.walk_stmts_except_import(&ast_module.body)
-                        .expect(kcl_error::COMPILE_ERROR_MSG);
+                    self.walk_stmts_except_import(&ast_module.body)?;
                 }
             }
             self.ret_void();
@@ -1233,80 +1439,255 @@ impl<'ctx> LLVMCodeGenContext<'ctx> {
                         .borrow_mut()
                         .push(ast_module.filename.clone());
                 }
-                self.walk_module(ast_module)
-                    .expect(kcl_error::COMPILE_ERROR_MSG);
+                self.walk_module(ast_module)?;
             }
             // Get the JSON string including all global variables
             let json_str_value = self.globals_to_json_str();
             // Build a return in the current block
-            self.pop_function();
+            self.pop_function()?;
             self.builder
                 .build_return(Some(&json_str_value.into_pointer_value()));
         }
+        self.debug_finalize();
         if let Some(path_str) = &opt.emit_path {
             let path = std::path::Path::new(&path_str);
             if opt.no_link {
                 let modules = self.modules.borrow_mut();
                 for (index, (_, module)) in modules.iter().enumerate() {
+                    let suffix = emit_file_suffix(opt.emit_kind);
                     let path = if modules.len() == 1 {
-                        format!("{}{}", path_str, OBJECT_FILE_SUFFIX)
+                        format!("{}{}", path_str, suffix)
                     } else {
-                        format!("{}_{}{}", path_str, index, OBJECT_FILE_SUFFIX)
+                        format!("{}_{}{}", path_str, index, suffix)
                     };
                     let path = std::path::Path::new(&path);
-                    // Build LLVM module to a `.o` object file.
-                    self.build_object_file(&module.borrow(), path)?;
+                    self.emit_module(&module.borrow(), path, opt)?;
                 }
             } else {
-                // Build LLVM module to a `.o` object file.
-                self.build_object_file(&self.module, path)?;
+                self.emit_module(&self.module, path, opt)?;
             }
         }
         Ok(())
     }
 
-    /// Build LLVM module to a `.o` object file.
-    ///
-    /// TODO: WASM and cross platform build.
+    /// Write LLVM module to the path in the format of the emit kind.
+    fn emit_module(
+        self: &LLVMCodeGenContext<'ctx>,
+        module: &Module,
+        path: &Path,
+        opt: &EmitOptions,
+    ) -> Result<(), Box<dyn Error>> {
+        match opt.emit_kind {
+            // Build LLVM module to a `.o` object file.
+            EmitKind::Object => self.build_object_file(module, path, opt)?,
+            EmitKind::LlvmIr => {
+                run_passes(module, opt.opt_level);
+                module.print_to_file(path)?;
+            }
+            EmitKind::Bitcode => {
+                run_passes(module, opt.opt_level);
+                if !module.write_bitcode_to_path(path) {
+                    return Err(format!("failed to write the bitcode to {}", path.display()).into());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Build LLVM module to a `.o` object file for the host target or the
+    /// target triple in the emit options.
     fn build_object_file(
         self: &LLVMCodeGenContext<'ctx>,
         module: &Module,
         path: &Path,
+        opt: &EmitOptions,
     ) -> Result<(), LLVMString> {
-        let triple = inkwell::targets::TargetMachine::get_default_triple();
-        let target = inkwell::targets::Target::from_triple(&triple)?;
+        let triple = match opt.target {
+            Some(target) => TargetTriple::create(target),
+            None => TargetMachine::get_default_triple(),
+        };
+        let target = Target::from_triple(&triple)?;
         // Convert LLVM module to ll file.
         module.print_to_file(path)?;
         let buf = MemoryBuffer::create_from_file(path)?;
         let module = self.context.create_module_from_ir(buf)?;
+        // WebAssembly does not support the position independent code.
+        let reloc_mode = if triple.as_str().to_string_lossy().starts_with("wasm") {
+            RelocMode::Static
+        } else {
+            RelocMode::PIC
+        };
         // Read ll file and use target machine to generate native object file.
         let target_machine = target
             .create_target_machine(
                 &triple,
                 "",
                 "",
-                // We do not enable any optimization, so that
-                // the sum of compile time and run time is as small as possible
-                inkwell::OptimizationLevel::None,
-                RelocMode::PIC,
+                // No optimization is enabled by default, so that the sum of
+                // compile time and run time is as small as possible for small configs.
+                optimization_level(opt.opt_level),
+                reloc_mode,
                 CodeModel::Default,
             )
             .expect(kcl_error::CODE_GEN_ERROR_MSG);
+        if opt.target.is_some() {
+            module.set_triple(&triple);
+            module.set_data_layout(&target_machine.get_target_data().get_data_layout());
+        }
+        run_passes(&module, opt.opt_level);
         target_machine.write_to_file(&module, FileType::Object, path)
     }
 }
 
 impl<'ctx> LLVMCodeGenContext<'ctx> {
+    /// Get the current function
+    #[inline]
+    pub fn current_function(&self) -> FunctionValue<'ctx> {
+        **self
+            .functions
+            .borrow()
+            .last()
+            .expect(kcl_error::INTERNAL_ERROR_MSG)
+    }
+
+    /// Plan globals to a json string
+    pub fn globals_to_json_str(&self) -> BasicValueEnum<'ctx> {
+        let current_pkgpath = self.current_pkgpath();
+        let mut pkg_scopes = self.pkg_scopes.borrow_mut();
+        let scopes = pkg_scopes
+            .get_mut(&current_pkgpath)
+            .expect(&format!("pkgpath {} is not found", current_pkgpath));
+        // The global scope.
+        let scope = scopes.last().expect(kcl_error::INTERNAL_ERROR_MSG);
+        let scalars = scope.scalars.borrow();
+        let globals = scope.variables.borrow();
+        // Construct a plan object.
+        let global_dict = self.dict_value();
+        // Deal scalars
+        for scalar in scalars.iter() {
+            self.dict_safe_insert(global_dict, SCALAR_KEY, scalar.clone(), 0, -1);
+        }
+        // Deal global variables
+        for (name, ptr) in globals.iter() {
+            // Omit private variables and function variables
+            if name.starts_with(kclvm_runtime::KCL_PRIVATE_VAR_PREFIX) {
+                continue;
+            }
+            let value = self.builder.build_load(*ptr, "");
+            let value_dict = self.dict_value();
+            self.dict_safe_insert(value_dict, name.as_str(), value, 0, -1);
+            self.dict_safe_insert(global_dict, SCALAR_KEY, value_dict, 0, -1);
+        }
+        // Plan result to json string.
+        self.build_call(
+            &ApiFunc::kclvm_value_plan_to_json.name(),
+            &[
+                self.current_runtime_ctx_ptr(),
+                self.dict_get(global_dict, self.native_global_string(SCALAR_KEY, "")),
+            ],
+        )
+    }
+
+    /// Insert a dict entry including key, value, op and insert_index into the dict.
+    #[inline]
+    fn dict_safe_insert(
+        &self,
+        dict: BasicValueEnum<'ctx>,
+        key: &str,
+        value: BasicValueEnum<'ctx>,
+        op: i32,
+        insert_index: i32,
+    ) {
+        let name = self.native_global_string(key, "");
+        let op = self.native_int_value(op);
+        let insert_index = self.native_int_value(insert_index);
+        self.build_void_call(
+            &ApiFunc::kclvm_dict_safe_insert.name(),
+            &[
+                self.current_runtime_ctx_ptr(),
+                dict,
+                name,
+                value,
+                op,
+                insert_index,
+            ],
+        );
+    }
+}
+
+impl<'ctx> ContextMethods<'ctx> for LLVMCodeGenContext<'ctx> {
+    type Variable = PointerValue<'ctx>;
+
+    #[inline]
+    fn program(&self) -> &'ctx ast::Program {
+        self.program
+    }
+
+    #[inline]
+    fn no_link(&self) -> bool {
+        self.no_link
+    }
+
+    #[inline]
+    fn imported(&self) -> &RefCell<HashSet<String>> {
+        &self.imported
+    }
+
+    #[inline]
+    fn local_vars(&self) -> &RefCell<HashSet<String>> {
+        &self.local_vars
+    }
+
+    #[inline]
+    fn schema_stack(&self) -> &RefCell<Vec<value::SchemaType>> {
+        &self.schema_stack
+    }
+
+    #[inline]
+    fn lambda_stack(&self) -> &RefCell<Vec<bool>> {
+        &self.lambda_stack
+    }
+
+    #[inline]
+    fn schema_expr_stack(&self) -> &RefCell<Vec<()>> {
+        &self.schema_expr_stack
+    }
+
+    #[inline]
+    fn pkgpath_stack(&self) -> &RefCell<Vec<String>> {
+        &self.pkgpath_stack
+    }
+
+    #[inline]
+    fn filename_stack(&self) -> &RefCell<Vec<String>> {
+        &self.filename_stack
+    }
+
+    #[inline]
+    fn target_vars(&self) -> &RefCell<Vec<String>> {
+        &self.target_vars
+    }
+
+    #[inline]
+    fn current_line(&self) -> &RefCell<u64> {
+        &self.current_line
+    }
+
+    #[inline]
+    fn backtrack_meta(&self) -> &RefCell<Option<BacktrackMeta>> {
+        &self.backtrack_meta
+    }
+
     /// Get compiler default ok result
     #[inline]
-    pub fn ok_result(&self) -> CompileResult<'ctx> {
+    fn ok_result(&self) -> CompileResult<'ctx> {
         let i32_type = self.context.i32_type();
         Ok(i32_type.const_int(0u64, false).into())
     }
 
     /// Build a void function call
     #[inline]
-    pub fn build_void_call(&self, name: &str, args: &[BasicValueEnum]) {
+    fn build_void_call(&self, name: &str, args: &[BasicValueEnum<'ctx>]) {
         let args: Vec<BasicMetadataValueEnum> = args.iter().map(|arg| (*arg).into()).collect();
         self.builder
             .build_call(self.lookup_function(name), &args, "");
@@ -1314,7 +1695,7 @@ impl<'ctx> LLVMCodeGenContext<'ctx> {
 
     /// Build a function call with the return value
     #[inline]
-    pub fn build_call(&self, name: &str, args: &[BasicValueEnum<'ctx>]) -> BasicValueEnum<'ctx> {
+    fn build_call(&self, name: &str, args: &[BasicValueEnum<'ctx>]) -> BasicValueEnum<'ctx> {
         let args: Vec<BasicMetadataValueEnum> = args.iter().map(|arg| (*arg).into()).collect();
         self.builder
             .build_call(self.lookup_function(name), &args, "")
@@ -1323,8 +1704,92 @@ impl<'ctx> LLVMCodeGenContext<'ctx> {
             .expect(kcl_error::FUNCTION_RETURN_VALUE_NOT_FOUND_MSG)
     }
 
+    /// Build a function call and return the return value if exists.
+    #[inline]
+    fn build_call_function(
+        &self,
+        function: FunctionValue<'ctx>,
+        args: &[BasicValueEnum<'ctx>],
+    ) -> Option<BasicValueEnum<'ctx>> {
+        let args: Vec<BasicMetadataValueEnum> = args.iter().map(|arg| (*arg).into()).collect();
+        self.builder
+            .build_call(function, &args, "")
+            .try_as_basic_value()
+            .left()
+    }
+
+    /// Build an indirect call of the KCL function pointer with the KCL function
+    /// signature `(ctx, args, kwargs) -> value`.
+    fn build_function_ptr_call(
+        &self,
+        fn_ptr: BasicValueEnum<'ctx>,
+        args: &[BasicValueEnum<'ctx>],
+    ) -> BasicValueEnum<'ctx> {
+        let fn_ty = self.function_type().ptr_type(AddressSpace::default());
+        let fn_ptr = self.builder.build_bitcast(fn_ptr, fn_ty, "");
+        let args: Vec<BasicMetadataValueEnum> = args.iter().map(|arg| (*arg).into()).collect();
+        self.builder
+            .build_call(
+                CallableValue::try_from(fn_ptr.into_pointer_value())
+                    .expect(kcl_error::INTERNAL_ERROR_MSG),
+                &args,
+                "",
+            )
+            .try_as_basic_value()
+            .left()
+            .expect(kcl_error::FUNCTION_RETURN_VALUE_NOT_FOUND_MSG)
+    }
+
+    /// Push a function call frame into the function stack and position
+    /// the builder at the end of its entry block.
+    fn push_function(&self, function: FunctionValue<'ctx>) {
+        self.insert_blocks
+            .borrow_mut()
+            .push(self.builder.get_insert_block());
+        self.functions.borrow_mut().push(Rc::new(function));
+        let block = self.append_block(ENTRY_NAME);
+        self.builder.position_at_end(block);
+        self.debug_enter_function();
+    }
+
+    /// Pop a function from the function stack and restore the insert block
+    /// of the enclosing function.
+    fn pop_function(&self) -> Result<(), kcl_error::KCLError> {
+        self.functions.borrow_mut().pop();
+        self.debug_leave_function();
+        if let Some(Some(block)) = self.insert_blocks.borrow_mut().pop() {
+            self.builder.position_at_end(block);
+        }
+        Ok(())
+    }
+
+    /// Add the package init function `(ctx) -> void` named `name` into
+    /// a new module of the current package.
+    fn add_pkg_init_function(&self, name: &str) -> FunctionValue<'ctx> {
+        let pkgpath = self.current_pkgpath();
+        let module = self.context.create_module(&pkgpath);
+        let tpe = self.context.void_type();
+        let fn_type = tpe.fn_type(&[self.context_ptr_type().into()], false);
+        let function = module.add_function(name, fn_type, None);
+        self.modules
+            .borrow_mut()
+            .insert(pkgpath, RefCell::new(module));
+        function
+    }
+
+    /// Declare the external package init function named `name`.
+    fn declare_pkg_init_function(&self, name: &str) -> FunctionValue<'ctx> {
+        let pkgpath = self.current_pkgpath();
+        let modules = self.modules.borrow_mut();
+        let msg = format!("pkgpath {} is not found", pkgpath);
+        let module = modules.get(&pkgpath).expect(&msg).borrow_mut();
+        let tpe = self.context.void_type();
+        let fn_type = tpe.fn_type(&[self.context_ptr_type().into()], false);
+        module.add_function(name, fn_type, Some(Linkage::External))
+    }
+
     /// Creates global string in the llvm module with initializer
-    pub fn native_global_string(&self, value: &str, name: &str) -> PointerValue<'ctx> {
+    fn native_global_string(&self, value: &str, name: &str) -> BasicValueEnum<'ctx> {
         let mut global_string_maps = self.global_strings.borrow_mut();
         let pkgpath = self.current_pkgpath();
         let str_name = format!("${}_{}_str", pkgpath_without_prefix!(pkgpath), name);
@@ -1334,7 +1799,7 @@ impl<'ctx> LLVMCodeGenContext<'ctx> {
         let msg = format!("pkgpath {} is not found", pkgpath);
         let global_strings = global_string_maps.get_mut(&pkgpath).expect(&msg);
         if let Some(ptr) = global_strings.get(value) {
-            *ptr
+            (*ptr).into()
         } else {
             let gv = unsafe { self.builder.build_global_string(value, &str_name) };
             let ptr = self
@@ -1344,37 +1809,37 @@ impl<'ctx> LLVMCodeGenContext<'ctx> {
                 )
                 .into_pointer_value();
             global_strings.insert(value.to_string(), ptr);
-            ptr
+            ptr.into()
         }
     }
 
     /// Creates global string value in the llvm module with initializer
-    pub fn native_global_string_value(&self, value: &str) -> BasicValueEnum<'ctx> {
+    fn native_global_string_value(&self, value: &str) -> BasicValueEnum<'ctx> {
         let pkgpath = self.current_pkgpath();
         let str_name = format!("${}_str", pkgpath_without_prefix!(pkgpath));
-        self.native_global_string(value, &str_name).into()
+        self.native_global_string(value, &str_name)
     }
 
     /// Get LLVM i8 zero value
-    pub fn native_i8_zero(&self) -> IntValue<'ctx> {
+    fn native_i8_zero(&self) -> BasicValueEnum<'ctx> {
         let i8_type = self.context.i8_type();
-        i8_type.const_int(0u64, false)
+        i8_type.const_int(0u64, false).into()
     }
 
     /// Get LLVM i8 zero value
-    pub fn native_i8(&self, v: i8) -> IntValue<'ctx> {
+    fn native_i8(&self, v: i8) -> BasicValueEnum<'ctx> {
         let i8_type = self.context.i8_type();
-        i8_type.const_int(v as u64, false)
+        i8_type.const_int(v as u64, false).into()
     }
 
     /// Construct a LLVM int value using i32
-    pub fn native_int_value(&self, v: i32) -> BasicValueEnum<'ctx> {
+    fn native_int_value(&self, v: i32) -> BasicValueEnum<'ctx> {
         let i32_type = self.context.i32_type();
         i32_type.const_int(v as u64, false).into()
     }
 
     /// Construct a global value pointer named `name`
-    pub fn new_global_kcl_value_ptr(&self, name: &str) -> PointerValue<'ctx> {
+    fn new_global_kcl_value_ptr(&self, name: &str) -> PointerValue<'ctx> {
         let tpe = self.value_ptr_type();
         // Builtin function value is a global one
         let global_var = if self.no_link {
@@ -1392,8 +1857,43 @@ impl<'ctx> LLVMCodeGenContext<'ctx> {
         global_var.as_pointer_value()
     }
 
+    /// Create a local variable in the entry block of the current function
+    /// initialized with the undefined value.
+    fn new_local_variable(&self) -> PointerValue<'ctx> {
+        let cur_bb = self
+            .builder
+            .get_insert_block()
+            .expect(kcl_error::INTERNAL_ERROR_MSG);
+        let entry_bb = self
+            .current_function()
+            .get_first_basic_block()
+            .expect(kcl_error::INTERNAL_ERROR_MSG);
+        match entry_bb.get_first_instruction() {
+            Some(inst) => self.builder.position_before(&inst),
+            None => self.builder.position_at_end(entry_bb),
+        };
+        let var = self.builder.build_alloca(self.value_ptr_type(), "");
+        let undefined_val = self.undefined_value();
+        self.builder.build_store(var, undefined_val);
+        self.builder.position_at_end(cur_bb);
+        var
+    }
+
+    /// Create a local variable in the current function with the initial value.
+    fn new_local_variable_with_value(&self, value: BasicValueEnum<'ctx>) -> PointerValue<'ctx> {
+        let var = self.builder.build_alloca(self.value_ptr_type(), "");
+        self.builder.build_store(var, value);
+        var
+    }
+
+    /// Store the value into the variable.
+    #[inline]
+    fn store_to_variable(&self, var: PointerValue<'ctx>, value: BasicValueEnum<'ctx>) {
+        self.builder.build_store(var, value);
+    }
+
     /// Append a scalar value into the scope.
-    pub fn add_scalar(&self, scalar: BasicValueEnum<'ctx>, is_schema: bool) {
+    fn add_scalar(&self, scalar: BasicValueEnum<'ctx>, is_schema: bool) {
         let current_pkgpath = self.current_pkgpath();
         let mut pkg_scopes = self.pkg_scopes.borrow_mut();
         let scopes = pkg_scopes
@@ -1418,7 +1918,7 @@ impl<'ctx> LLVMCodeGenContext<'ctx> {
     }
 
     /// Append a variable into the scope
-    pub fn add_variable(&self, name: &str, pointer: PointerValue<'ctx>) {
+    fn add_variable(&self, name: &str, pointer: PointerValue<'ctx>) {
         let current_pkgpath = self.current_pkgpath();
         let mut pkg_scopes = self.pkg_scopes.borrow_mut();
         let msg = format!("pkgpath {} is not found", current_pkgpath);
@@ -1432,7 +1932,7 @@ impl<'ctx> LLVMCodeGenContext<'ctx> {
     }
 
     /// Store the argument named `name` in the current scope.
-    pub(crate) fn store_argument_in_current_scope(&self, name: &str) {
+    fn store_argument_in_current_scope(&self, name: &str) {
         // Find argument name in the scope
         let current_pkgpath = self.current_pkgpath();
         let mut pkg_scopes = self.pkg_scopes.borrow_mut();
@@ -1444,7 +1944,7 @@ impl<'ctx> LLVMCodeGenContext<'ctx> {
     }
 
     /// Store the variable named `name` with `value` from the current scope, return false when not found
-    pub fn store_variable_in_current_scope(&self, name: &str, value: BasicValueEnum<'ctx>) -> bool {
+    fn store_variable_in_current_scope(&self, name: &str, value: BasicValueEnum<'ctx>) -> bool {
         // Find argument name in the scope
         let current_pkgpath = self.current_pkgpath();
         let mut pkg_scopes = self.pkg_scopes.borrow_mut();
@@ -1460,7 +1960,7 @@ impl<'ctx> LLVMCodeGenContext<'ctx> {
     }
 
     /// Store the variable named `name` with `value` from the scope, return false when not found
-    pub fn store_variable(&self, name: &str, value: BasicValueEnum<'ctx>) -> bool {
+    fn store_variable(&self, name: &str, value: BasicValueEnum<'ctx>) -> bool {
         // Find argument name in the scope
         let current_pkgpath = self.current_pkgpath();
         let mut pkg_scopes = self.pkg_scopes.borrow_mut();
@@ -1478,7 +1978,7 @@ impl<'ctx> LLVMCodeGenContext<'ctx> {
     }
 
     /// Resolve variable in scope, return false when not found
-    pub fn resolve_variable(&self, name: &str) -> bool {
+    fn resolve_variable(&self, name: &str) -> bool {
         // Find argument name in the scope
         let current_pkgpath = self.current_pkgpath();
         let mut pkg_scopes = self.pkg_scopes.borrow_mut();
@@ -1497,7 +1997,7 @@ impl<'ctx> LLVMCodeGenContext<'ctx> {
     }
 
     /// Append a variable or update the existed local variable.
-    pub fn add_or_update_local_variable(&self, name: &str, value: BasicValueEnum<'ctx>) {
+    fn add_or_update_local_variable(&self, name: &str, value: BasicValueEnum<'ctx>) {
         let current_pkgpath = self.current_pkgpath();
         let mut pkg_scopes = self.pkg_scopes.borrow_mut();
         let msg = format!("pkgpath {} is not found", current_pkgpath);
@@ -1534,7 +2034,7 @@ impl<'ctx> LLVMCodeGenContext<'ctx> {
     }
 
     /// Append a variable or update the existed variable
-    pub fn add_or_update_global_variable(&self, name: &str, value: BasicValueEnum<'ctx>) {
+    fn add_or_update_global_variable(&self, name: &str, value: BasicValueEnum<'ctx>) {
         // Find argument name in the scope
         let current_pkgpath = self.current_pkgpath();
         let mut pkg_scopes = self.pkg_scopes.borrow_mut();
@@ -1563,20 +2063,20 @@ impl<'ctx> LLVMCodeGenContext<'ctx> {
     }
 
     /// Get the variable value named `name` from the scope, return Err when not found
-    pub fn get_variable(&self, name: &str) -> CompileResult<'ctx> {
+    fn get_variable(&self, name: &str) -> CompileResult<'ctx> {
         let current_pkgpath = self.current_pkgpath();
         self.get_variable_in_pkgpath(name, &current_pkgpath)
     }
 
     /// Get the variable value named `name` from the scope, return Err when not found
-    pub fn get_variable_in_schema(&self, name: &str) -> CompileResult<'ctx> {
+    fn get_variable_in_schema(&self, name: &str) -> CompileResult<'ctx> {
         let schema_value = self
             .get_variable(value::SCHEMA_SELF_NAME)
             .expect(kcl_error::INTERNAL_ERROR_MSG);
         let cal_map = self
             .get_variable(value::SCHEMA_CAL_MAP)
             .expect(kcl_error::INTERNAL_ERROR_MSG);
-        let string_ptr_value = self.native_global_string(name, "").into();
+        let string_ptr_value = self.native_global_string(name, "");
         let cal_map_has_key = self
             .build_call(
                 &ApiFunc::kclvm_dict_has_value.name(),
@@ -1593,9 +2093,12 @@ impl<'ctx> LLVMCodeGenContext<'ctx> {
         let has_key = self
             .builder
             .build_int_add(cal_map_has_key, schema_has_key, "");
-        let has_key =
-            self.builder
-                .build_int_compare(IntPredicate::NE, has_key, self.native_i8_zero(), "");
+        let has_key = self.builder.build_int_compare(
+            IntPredicate::NE,
+            has_key,
+            self.native_i8_zero().into_int_value(),
+            "",
+        );
         let then_block = self.append_block("");
         let else_block = self.append_block("");
         let end_block = self.append_block("");
@@ -1631,6 +2134,7 @@ impl<'ctx> LLVMCodeGenContext<'ctx> {
             self.build_call(
                 &ApiFunc::kclvm_schema_get_value.name(),
                 &[
+                    self.current_runtime_ctx_ptr(),
                     schema_value,
                     string_ptr_value,
                     config,
@@ -1662,7 +2166,7 @@ impl<'ctx> LLVMCodeGenContext<'ctx> {
     }
 
     /// Get the variable value named `name` from the scope named `pkgpath`, return Err when not found
-    pub fn get_variable_in_pkgpath(&self, name: &str, pkgpath: &str) -> CompileResult<'ctx> {
+    fn get_variable_in_pkgpath(&self, name: &str, pkgpath: &str) -> CompileResult<'ctx> {
         let pkg_scopes = self.pkg_scopes.borrow_mut();
         let pkgpath =
             if !pkgpath.starts_with(kclvm_runtime::PKG_PATH_PREFIX) && pkgpath != MAIN_PKG_PATH {
@@ -1702,15 +2206,16 @@ impl<'ctx> LLVMCodeGenContext<'ctx> {
                     "",
                 );
                 let func_name = function.get_name().to_str().unwrap();
-                let func_name_ptr = self.native_global_string(func_name, func_name).into();
+                let func_name_ptr = self.native_global_string(func_name, func_name);
                 let none_value = self.none_value();
                 self.build_call(
                     &ApiFunc::kclvm_value_Function.name(),
                     &[
+                        self.current_runtime_ctx_ptr(),
                         lambda_fn_ptr,
                         none_value,
                         func_name_ptr,
-                        self.native_i8_zero().into(),
+                        self.native_i8_zero(),
                     ],
                 )
             };
@@ -1725,11 +2230,17 @@ impl<'ctx> LLVMCodeGenContext<'ctx> {
                 .const_zero()
                 .into();
             let name = format!("{}.{}", &pkgpath[1..], name);
-            let name = self.native_global_string(&name, "").into();
+            let name = self.native_global_string(&name, "");
             let none_value = self.none_value();
             return Ok(self.build_call(
                 &ApiFunc::kclvm_value_Function.name(),
-                &[null_fn_ptr, none_value, name, self.native_i8(1).into()],
+                &[
+                    self.current_runtime_ctx_ptr(),
+                    null_fn_ptr,
+                    none_value,
+                    name,
+                    self.native_i8(1),
+                ],
             ));
         // User pkgpath
         } else {
@@ -1753,10 +2264,14 @@ impl<'ctx> LLVMCodeGenContext<'ctx> {
                         match ptr {
                             Some(ptr) => {
                                 let closure_map = self.builder.build_load(*ptr, "");
-                                let string_ptr_value = self.native_global_string(name, "").into();
+                                let string_ptr_value = self.native_global_string(name, "");
                                 self.build_call(
                                     &ApiFunc::kclvm_dict_get_value.name(),
-                                    &[closure_map, string_ptr_value],
+                                    &[
+                                        self.current_runtime_ctx_ptr(),
+                                        closure_map,
+                                        string_ptr_value,
+                                    ],
                                 )
                             }
                             None => self.builder.build_load(*var, name),
@@ -1791,11 +2306,7 @@ impl<'ctx> LLVMCodeGenContext<'ctx> {
     }
 
     /// Get the variable value named `name` from the scope named `pkgpath`, return Err when not found
-    pub fn get_external_variable_in_pkgpath(
-        &self,
-        name: &str,
-        pkgpath: &str,
-    ) -> CompileResult<'ctx> {
+    fn get_external_variable_in_pkgpath(&self, name: &str, pkgpath: &str) -> CompileResult<'ctx> {
         let ext_pkgpath = if !pkgpath.starts_with(kclvm_runtime::PKG_PATH_PREFIX)
             && pkgpath != kclvm_runtime::MAIN_PKG_PATH
         {
@@ -1839,7 +2350,7 @@ impl<'ctx> LLVMCodeGenContext<'ctx> {
     }
 
     /// Get closure map in the current scope.
-    pub(crate) fn get_closure_map(&self) -> BasicValueEnum<'ctx> {
+    fn get_closure_map(&self) -> BasicValueEnum<'ctx> {
         // Get closures in the current scope.
         let closure_map = self.dict_value();
         {
@@ -1888,89 +2399,10 @@ impl<'ctx> LLVMCodeGenContext<'ctx> {
         closure_map
     }
 
-    /// Push a function call frame into the function stack
-    #[inline]
-    pub fn push_function(&self, function: FunctionValue<'ctx>) {
-        self.functions.borrow_mut().push(Rc::new(function));
-    }
-
-    /// Pop a function from the function stack
-    #[inline]
-    pub fn pop_function(&self) {
-        self.functions.borrow_mut().pop();
-    }
-
-    /// Get the current function
-    #[inline]
-    pub fn current_function(&self) -> FunctionValue<'ctx> {
-        **self
-            .functions
-            .borrow()
-            .last()
-            .expect(kcl_error::INTERNAL_ERROR_MSG)
-    }
-
-    /// Plan globals to a json string
-    pub fn globals_to_json_str(&self) -> BasicValueEnum<'ctx> {
-        let current_pkgpath = self.current_pkgpath();
-        let mut pkg_scopes = self.pkg_scopes.borrow_mut();
-        let scopes = pkg_scopes
-            .get_mut(&current_pkgpath)
-            .expect(&format!("pkgpath {} is not found", current_pkgpath));
-        // The global scope.
-        let scope = scopes.last().expect(kcl_error::INTERNAL_ERROR_MSG);
-        let scalars = scope.scalars.borrow();
-        let globals = scope.variables.borrow();
-        // Construct a plan object.
-        let global_dict = self.dict_value();
-        // Deal scalars
-        for scalar in scalars.iter() {
-            self.dict_safe_insert(global_dict, SCALAR_KEY, scalar.clone(), 0, -1);
-        }
-        // Deal global variables
-        for (name, ptr) in globals.iter() {
-            // Omit private variables and function variables
-            if name.starts_with(kclvm_runtime::KCL_PRIVATE_VAR_PREFIX) {
-                continue;
-            }
-            let value = self.builder.build_load(*ptr, "");
-            let value_dict = self.dict_value();
-            self.dict_safe_insert(value_dict, name.as_str(), value, 0, -1);
-            self.dict_safe_insert(global_dict, SCALAR_KEY, value_dict, 0, -1);
-        }
-        // Plan result to json string.
-        self.build_call(
-            &ApiFunc::kclvm_value_plan_to_json.name(),
-            &[self.dict_get(
-                global_dict,
-                self.native_global_string(SCALAR_KEY, "").into(),
-            )],
-        )
-    }
-
-    /// Insert a dict entry including key, value, op and insert_index into the dict.
-    #[inline]
-    fn dict_safe_insert(
-        &self,
-        dict: BasicValueEnum<'ctx>,
-        key: &str,
-        value: BasicValueEnum<'ctx>,
-        op: i32,
-        insert_index: i32,
-    ) {
-        let name = self.native_global_string(key, "").into();
-        let op = self.native_int_value(op);
-        let insert_index = self.native_int_value(insert_index);
-        self.build_void_call(
-            &ApiFunc::kclvm_dict_safe_insert.name(),
-            &[dict, name, value, op, insert_index],
-        );
-    }
-
     /// Merge a dict entry including key, value, op and insert_index into the dict
     /// without the idempotent check.
     #[inline]
-    pub fn dict_merge(
+    fn dict_merge(
         &self,
         dict: BasicValueEnum<'ctx>,
         key: &str,
@@ -1978,24 +2410,31 @@ impl<'ctx> LLVMCodeGenContext<'ctx> {
         op: i32,
         insert_index: i32,
     ) {
-        let name = self.native_global_string(key, "").into();
+        let name = self.native_global_string(key, "");
         let op = self.native_int_value(op);
         let insert_index = self.native_int_value(insert_index);
         self.build_void_call(
             &ApiFunc::kclvm_dict_merge.name(),
-            &[dict, name, value, op, insert_index],
+            &[
+                self.current_runtime_ctx_ptr(),
+                dict,
+                name,
+                value,
+                op,
+                insert_index,
+            ],
         );
     }
 
     /// default_dict(list) insert a key-value pair, and the value is a int pointer
     #[inline]
-    pub fn default_collection_insert_int_pointer(
+    fn default_collection_insert_int_pointer(
         &self,
         dict: BasicValueEnum<'ctx>,
         key: &str,
         value: BasicValueEnum<'ctx>,
     ) {
-        let name = self.native_global_string(key, "").into();
+        let name = self.native_global_string(key, "");
         self.build_void_call(
             ApiFunc::kclvm_default_collection_insert_int_pointer
                 .name()
@@ -2006,13 +2445,13 @@ impl<'ctx> LLVMCodeGenContext<'ctx> {
 
     /// default_dict(list) insert a key-value pair
     #[inline]
-    pub fn default_collection_insert_value(
+    fn default_collection_insert_value(
         &self,
         dict: BasicValueEnum<'ctx>,
         key: &str,
         value: BasicValueEnum<'ctx>,
     ) {
-        let name = self.native_global_string(key, "").into();
+        let name = self.native_global_string(key, "");
         self.build_void_call(
             ApiFunc::kclvm_default_collection_insert_value
                 .name()
@@ -2020,4 +2459,20 @@ impl<'ctx> LLVMCodeGenContext<'ctx> {
             &[dict, name, value],
         );
     }
+
+    /// Set the debug location of the following instructions to the node.
+    #[inline]
+    fn debug_set_location<T>(&self, node: &ast::Node<T>) {
+        LLVMCodeGenContext::debug_set_location(self, node)
+    }
+
+    /// Declare the debug info of the variable named `name`.
+    #[inline]
+    fn debug_declare_variable(&self, name: &str, value: BasicValueEnum<'ctx>) {
+        LLVMCodeGenContext::debug_declare_variable(self, name, value)
+    }
 }
+
+impl<'ctx> SchemaCodeGen<'ctx> for LLVMCodeGenContext<'ctx> {}
+
+impl<'ctx> NodeCodeGen<'ctx> for LLVMCodeGenContext<'ctx> {}
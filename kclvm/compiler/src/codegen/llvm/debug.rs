@@ -0,0 +1,331 @@
+// Copyright 2021 The KCL Authors. All rights reserved.
+
+use inkwell::context::Context;
+use inkwell::debug_info::{
+    debug_metadata_version, AsDIScope, DICompileUnit, DIFlags, DIFlagsConstants, DILocation,
+    DIScope, DISubroutineType, DIType, DWARFEmissionKind, DWARFSourceLanguage, DebugInfoBuilder,
+};
+use inkwell::module::{FlagBehavior, Module};
+use inkwell::values::BasicValueEnum;
+use inkwell::AddressSpace;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::Path;
+
+use kclvm_ast::ast;
+
+use crate::codegen::error as kcl_error;
+use crate::codegen::traits::*;
+
+use super::context::LLVMCodeGenContext;
+
+/// The producer of the DWARF compile units.
+const DEBUG_INFO_PRODUCER: &str = "kclvm";
+/// The DWARF version of the debug info.
+const DWARF_VERSION: u64 = 4;
+/// The name of the KCL value pointer debug type.
+const VALUE_TYPE_NAME: &str = "kclvm_value_ref_t";
+
+/// The compile unit key denotes the LLVM module name and the KCL filename.
+type UnitKey = (String, String);
+
+/// The DWARF compile unit of a KCL file in a LLVM module.
+struct DebugUnit<'ctx> {
+    builder: DebugInfoBuilder<'ctx>,
+    unit: DICompileUnit<'ctx>,
+    /// The debug type of the KCL value pointer `kclvm_value_ref_t*`.
+    value_type: DIType<'ctx>,
+    /// The debug type of the generated functions, which return a KCL value pointer.
+    function_type: DISubroutineType<'ctx>,
+}
+
+/// The debug scopes of a function in the function stack.
+#[derive(Default)]
+struct DebugFunction<'ctx> {
+    /// The subprogram of the function, which is created at the first debug location
+    /// of the function, so it belongs to the compile unit of the first KCL file.
+    subprogram: Option<(UnitKey, DIScope<'ctx>)>,
+    /// The lexical block scopes of the other KCL files in the function e.g., the main
+    /// function of a package consisting of multiple files.
+    file_scopes: HashMap<String, DIScope<'ctx>>,
+    /// The current compile unit, scope and debug location of the function.
+    location: Option<(UnitKey, DIScope<'ctx>, DILocation<'ctx>)>,
+}
+
+/// DebugInfo records the DWARF debug info of the generated code, including a compile unit
+/// per KCL file, the line locations of the statements and expressions, and the variables of
+/// the schema attributes, so that native debuggers and profilers such as `gdb` and `perf`
+/// can map the generated code back to the KCL sources.
+pub struct DebugInfo<'ctx> {
+    context: &'ctx Context,
+    is_optimized: bool,
+    /// The pointer size in bits of the target.
+    pointer_bits: u64,
+    units: RefCell<HashMap<UnitKey, DebugUnit<'ctx>>>,
+    functions: RefCell<Vec<DebugFunction<'ctx>>>,
+}
+
+impl<'ctx> DebugInfo<'ctx> {
+    /// New a DebugInfo with the optimization level and the target triple of the generated
+    /// code, `None` denotes the host target.
+    pub fn new(context: &'ctx Context, opt_level: u32, target: Option<&str>) -> Self {
+        let pointer_bits = match target {
+            Some(target) if target.starts_with("wasm32") => 32,
+            _ => std::mem::size_of::<usize>() as u64 * 8,
+        };
+        DebugInfo {
+            context,
+            is_optimized: opt_level > 0,
+            pointer_bits,
+            units: RefCell::new(HashMap::new()),
+            functions: RefCell::new(vec![]),
+        }
+    }
+
+    /// Get the compile unit key of the KCL file in the module, and the compile unit is
+    /// created when it does not exist.
+    fn unit_key(&self, module: &Module<'ctx>, filename: &str) -> UnitKey {
+        let key = (
+            module.get_name().to_string_lossy().to_string(),
+            filename.to_string(),
+        );
+        let mut units = self.units.borrow_mut();
+        if units.contains_key(&key) {
+            return key;
+        }
+        // The module flags are required by the LLVM backend to emit the DWARF sections.
+        let i32_type = self.context.i32_type();
+        if module.get_flag("Debug Info Version").is_none() {
+            module.add_basic_value_flag(
+                "Debug Info Version",
+                FlagBehavior::Warning,
+                i32_type.const_int(debug_metadata_version() as u64, false),
+            );
+        }
+        if module.get_flag("Dwarf Version").is_none() {
+            module.add_basic_value_flag(
+                "Dwarf Version",
+                FlagBehavior::Warning,
+                i32_type.const_int(DWARF_VERSION, false),
+            );
+        }
+        let path = Path::new(filename);
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| filename.to_string());
+        let directory = path
+            .parent()
+            .map(|dir| dir.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let (builder, unit) = module.create_debug_info_builder(
+            true,
+            // KCL is not a DWARF source language, and C is the most widely supported one.
+            DWARFSourceLanguage::C,
+            &name,
+            &directory,
+            DEBUG_INFO_PRODUCER,
+            self.is_optimized,
+            "",
+            0,
+            "",
+            DWARFEmissionKind::Full,
+            0,
+            false,
+            false,
+            "",
+            "",
+        );
+        let file = unit.get_file();
+        let value_struct_type = builder.create_struct_type(
+            unit.as_debug_info_scope(),
+            VALUE_TYPE_NAME,
+            file,
+            0,
+            0,
+            0,
+            DIFlags::FWD_DECL,
+            None,
+            &[],
+            0,
+            None,
+            VALUE_TYPE_NAME,
+        );
+        let value_type = builder
+            .create_pointer_type(
+                &format!("{}*", VALUE_TYPE_NAME),
+                value_struct_type.as_type(),
+                self.pointer_bits,
+                self.pointer_bits as u32,
+                AddressSpace::default(),
+            )
+            .as_type();
+        let function_type =
+            builder.create_subroutine_type(file, Some(value_type), &[], DIFlags::PUBLIC);
+        units.insert(
+            key.clone(),
+            DebugUnit {
+                builder,
+                unit,
+                value_type,
+                function_type,
+            },
+        );
+        key
+    }
+}
+
+impl<'ctx> LLVMCodeGenContext<'ctx> {
+    /// Enter a function in the debug info. The debug location is cleared until the first
+    /// location in the function is set, because a location can not refer to the scope of
+    /// another function.
+    pub(crate) fn debug_enter_function(&self) {
+        if let Some(debug_info) = &self.debug_info {
+            debug_info
+                .functions
+                .borrow_mut()
+                .push(DebugFunction::default());
+            self.builder.unset_current_debug_location();
+        }
+    }
+
+    /// Leave a function in the debug info and restore the debug location of the outer function.
+    pub(crate) fn debug_leave_function(&self) {
+        if let Some(debug_info) = &self.debug_info {
+            let mut functions = debug_info.functions.borrow_mut();
+            functions.pop();
+            match functions.last().and_then(|f| f.location.as_ref()) {
+                Some((_, _, location)) => self.builder.set_current_debug_location(*location),
+                None => self.builder.unset_current_debug_location(),
+            }
+        }
+    }
+
+    /// Set the debug location of the following instructions to the line and column of the node.
+    pub(crate) fn debug_set_location<T>(&self, node: &ast::Node<T>) {
+        let debug_info = match &self.debug_info {
+            Some(debug_info) => debug_info,
+            None => return,
+        };
+        let filename = if node.filename.is_empty() {
+            self.current_filename()
+        } else {
+            node.filename.clone()
+        };
+        if filename.is_empty() || self.functions.borrow().is_empty() {
+            return;
+        }
+        let line = node.line as u32;
+        let key = self.with_current_module(|module| debug_info.unit_key(module, &filename));
+        let units = debug_info.units.borrow();
+        let mut functions = debug_info.functions.borrow_mut();
+        let function = functions.last_mut().expect(kcl_error::INTERNAL_ERROR_MSG);
+        let subprogram = match &function.subprogram {
+            Some(subprogram) => subprogram.clone(),
+            None => {
+                let unit = &units[&key];
+                let current_function = self.current_function();
+                let name = current_function.get_name().to_string_lossy().to_string();
+                let subprogram = unit.builder.create_function(
+                    unit.unit.as_debug_info_scope(),
+                    &name,
+                    Some(&name),
+                    unit.unit.get_file(),
+                    line,
+                    unit.function_type,
+                    false,
+                    true,
+                    line,
+                    DIFlags::PUBLIC,
+                    debug_info.is_optimized,
+                );
+                current_function.set_subprogram(subprogram);
+                let subprogram = (key.clone(), subprogram.as_debug_info_scope());
+                function.subprogram = Some(subprogram.clone());
+                subprogram
+            }
+        };
+        let (subprogram_key, subprogram_scope) = subprogram;
+        let scope = if subprogram_key == key {
+            subprogram_scope
+        } else {
+            *function.file_scopes.entry(filename).or_insert_with(|| {
+                units[&subprogram_key]
+                    .builder
+                    .create_lexical_block(subprogram_scope, units[&key].unit.get_file(), line, 0)
+                    .as_debug_info_scope()
+            })
+        };
+        let location = units[&key].builder.create_debug_location(
+            self.context,
+            line,
+            node.column as u32 + 1,
+            scope,
+            None,
+        );
+        self.builder.set_current_debug_location(location);
+        function.location = Some((key, scope, location));
+    }
+
+    /// Declare a debug variable named `name` holding the value at the current debug location,
+    /// so that the values of the schema attributes can be inspected in the native debuggers.
+    pub(crate) fn debug_declare_variable(&self, name: &str, value: BasicValueEnum<'ctx>) {
+        let debug_info = match &self.debug_info {
+            Some(debug_info) => debug_info,
+            None => return,
+        };
+        let functions = debug_info.functions.borrow();
+        let (key, scope, location) = match functions.last().and_then(|f| f.location.as_ref()) {
+            Some(location) => location,
+            None => return,
+        };
+        let block = match self.builder.get_insert_block() {
+            Some(block) => block,
+            None => return,
+        };
+        let units = debug_info.units.borrow();
+        let unit = &units[key];
+        let ptr = self.builder.build_alloca(self.value_ptr_type(), name);
+        self.builder.build_store(ptr, value);
+        let variable = unit.builder.create_auto_variable(
+            *scope,
+            name,
+            unit.unit.get_file(),
+            location.get_line(),
+            unit.value_type,
+            true,
+            DIFlags::ZERO,
+            debug_info.pointer_bits as u32,
+        );
+        unit.builder.insert_declare_at_end(
+            ptr,
+            Some(variable),
+            Some(unit.builder.create_expression(vec![])),
+            *location,
+            block,
+        );
+    }
+
+    /// Finalize the debug info of all the compile units, which must be called before
+    /// the LLVM modules are emitted.
+    pub(crate) fn debug_finalize(&self) {
+        if let Some(debug_info) = &self.debug_info {
+            for unit in debug_info.units.borrow().values() {
+                unit.builder.finalize();
+            }
+        }
+    }
+
+    /// Call `f` with the LLVM module which the functions of the current pkgpath are added to.
+    fn with_current_module<R>(&self, f: impl FnOnce(&Module<'ctx>) -> R) -> R {
+        if self.no_link {
+            let pkgpath = self.current_pkgpath();
+            let modules = self.modules.borrow();
+            let msg = format!("pkgpath {} is not found", pkgpath);
+            let module = modules.get(&pkgpath).expect(&msg).borrow();
+            f(&module)
+        } else {
+            f(&self.module)
+        }
+    }
+}
@@ -1,15 +1,19 @@
 // Copyright 2021 The KCL Authors. All rights reserved.
 
 use indexmap::IndexMap;
+use inkwell::execution_engine::ExecutionEngine;
 use inkwell::module::Module;
 use inkwell::{context::Context, memory_buffer::MemoryBuffer};
 use kclvm_ast::ast;
+use kclvm_runtime::_kclvm_get_fn_ptr_by_name;
 use once_cell::sync::OnceCell;
 use std::error;
 
 use crate::codegen::{EmitOptions, MODULE_NAME};
 
 use super::context::LLVMCodeGenContext;
+use super::debug::DebugInfo;
+use super::passes::{optimization_level, run_passes};
 
 static LLVM_INIT: OnceCell<()> = OnceCell::new();
 static RUNTIME_LLVM_BC: &[u8] = include_bytes!("../../../../runtime/src/_kclvm.bc");
@@ -20,20 +24,27 @@ fn load_runtime(context: &'_ Context) -> Module<'_> {
     Module::parse_bitcode_from_buffer(&memory, context).unwrap()
 }
 
+/// Init LLVM targets
+fn init_llvm() {
+    LLVM_INIT.get_or_init(|| {
+        // TODO: linux arm target.
+        #[cfg(target_os = "linux")]
+        {
+            inkwell::targets::Target::initialize_x86(&Default::default());
+            inkwell::targets::Target::initialize_webassembly(&Default::default());
+        }
+        #[cfg(not(target_os = "linux"))]
+        inkwell::targets::Target::initialize_all(&Default::default());
+    });
+}
+
 /// Generate LLVM IR of KCL ast module.
 pub fn emit_code(
     program: &ast::Program,
     import_names: IndexMap<String, IndexMap<String, String>>,
     opt: &EmitOptions,
 ) -> Result<(), Box<dyn error::Error>> {
-    // Init LLVM targets
-    LLVM_INIT.get_or_init(|| {
-        // TODO: linux arm and WASM target.
-        #[cfg(target_os = "linux")]
-        inkwell::targets::Target::initialize_x86(&Default::default());
-        #[cfg(not(target_os = "linux"))]
-        inkwell::targets::Target::initialize_all(&Default::default());
-    });
+    init_llvm();
     // Create a LLVM context
     let context = Context::create();
     // Create a LLVM module using an exist LLVM bitcode file
@@ -42,8 +53,61 @@ pub fn emit_code(
     } else {
         load_runtime(&context)
     };
+    // Create the debug info recorder when the debug info is required
+    let debug_info = opt
+        .debug_info
+        .then(|| DebugInfo::new(&context, opt.opt_level, opt.target));
     // Create a KCL LLVM code generator using the KCL AST and the LLVM module
-    let ctx = LLVMCodeGenContext::new(&context, module, program, import_names, opt.no_link);
+    let ctx = LLVMCodeGenContext::new(
+        &context,
+        module,
+        program,
+        import_names,
+        opt.no_link,
+        debug_info,
+    );
     // Generate user KCL code LLVM IR
     crate::codegen::emit_code(ctx, opt)
 }
+
+/// Generate LLVM IR of KCL ast module and compile it in memory using the LLVM
+/// JIT execution engine, then call `run` with the address of the KCL main function.
+///
+/// All the runtime functions declared in the module are mapped to the runtime
+/// functions linked in the current process, thus no object files and dynamic
+/// link libraries are written to or loaded from the filesystem.
+///
+/// `opt_level` is the optimization level from 0 to 3 of the generated code.
+pub fn jit_code<T>(
+    program: &ast::Program,
+    import_names: IndexMap<String, IndexMap<String, String>>,
+    opt_level: u32,
+    run: impl FnOnce(u64) -> T,
+) -> Result<T, Box<dyn error::Error>> {
+    init_llvm();
+    // Create a LLVM context
+    let context = Context::create();
+    // Create a LLVM module using the runtime LLVM bitcode declarations
+    let module = load_runtime(&context);
+    // Create a KCL LLVM code generator which links all packages into one module
+    let ctx = LLVMCodeGenContext::new(&context, module, program, import_names, false, None);
+    // Generate user KCL code LLVM IR
+    ctx.emit_code(&EmitOptions::default())?;
+    run_passes(&ctx.module, opt_level);
+    ExecutionEngine::link_in_mc_jit();
+    let engine = ctx
+        .module
+        .create_jit_execution_engine(optimization_level(opt_level))?;
+    // Map the runtime function declarations to the runtime function addresses
+    for function in ctx.module.get_functions() {
+        if function.count_basic_blocks() == 0 {
+            let name = function.get_name().to_str()?;
+            let fn_ptr = _kclvm_get_fn_ptr_by_name(name);
+            if fn_ptr != 0 {
+                engine.add_global_mapping(&function, fn_ptr as usize);
+            }
+        }
+    }
+    let kclvm_main_ptr = engine.get_function_address(MODULE_NAME)?;
+    Ok(run(kclvm_main_ptr as u64))
+}
@@ -6,18 +6,26 @@
 //! Copyright 2021 The KCL Authors. All rights reserved.
 
 mod context;
+mod debug;
 mod emit;
-mod module;
-mod node;
-mod schema;
-mod utils;
+mod passes;
 
-pub use emit::emit_code;
+pub use emit::{emit_code, jit_code};
+
+pub use super::{OBJECT_FILE_SUFFIX, WASM32_WASI_TARGET, WASM_FILE_SUFFIX};
+
+use super::EmitKind;
 
-/// Object file type format suffix.
-#[cfg(target_os = "windows")]
-pub const OBJECT_FILE_SUFFIX: &str = ".obj";
-#[cfg(not(target_os = "windows"))]
-pub const OBJECT_FILE_SUFFIX: &str = ".o";
 /// LLVM IR text format suffix .ll
 pub const LL_FILE_SUFFIX: &str = ".ll";
+/// LLVM bitcode format suffix .bc
+pub const BC_FILE_SUFFIX: &str = ".bc";
+
+/// Get the file suffix of the emitted code format.
+pub fn emit_file_suffix(kind: EmitKind) -> &'static str {
+    match kind {
+        EmitKind::Object => OBJECT_FILE_SUFFIX,
+        EmitKind::LlvmIr => LL_FILE_SUFFIX,
+        EmitKind::Bitcode => BC_FILE_SUFFIX,
+    }
+}
@@ -0,0 +1,44 @@
+// Copyright 2021 The KCL Authors. All rights reserved.
+
+use inkwell::module::Module;
+use inkwell::passes::{PassManager, PassManagerBuilder};
+use inkwell::OptimizationLevel;
+
+/// The function inlining threshold of the `-O2` and `-O3` pipelines, which is the same as clang.
+const INLINE_THRESHOLD: u32 = 225;
+
+/// Convert the optimization level number in the [`crate::codegen::EmitOptions`] to the
+/// LLVM optimization level, and the levels larger than 3 are treated as 3.
+pub(crate) fn optimization_level(opt_level: u32) -> OptimizationLevel {
+    match opt_level {
+        0 => OptimizationLevel::None,
+        1 => OptimizationLevel::Less,
+        2 => OptimizationLevel::Default,
+        _ => OptimizationLevel::Aggressive,
+    }
+}
+
+/// Run the standard LLVM function and module pass pipelines of the optimization level
+/// over the module. Nothing is done when the optimization level is 0.
+pub(crate) fn run_passes(module: &Module, opt_level: u32) {
+    if opt_level == 0 {
+        return;
+    }
+    let builder = PassManagerBuilder::create();
+    builder.set_optimization_level(optimization_level(opt_level));
+    if opt_level >= 2 {
+        builder.set_inliner_with_threshold(INLINE_THRESHOLD);
+    }
+    // Function passes, e.g., mem2reg, instcombine and simplifycfg.
+    let fpm = PassManager::create(module);
+    builder.populate_function_pass_manager(&fpm);
+    fpm.initialize();
+    for function in module.get_functions() {
+        fpm.run_on(&function);
+    }
+    fpm.finalize();
+    // Module passes, e.g., inlining, global dead code elimination and constant merging.
+    let mpm = PassManager::create(());
+    builder.populate_module_pass_manager(&mpm);
+    mpm.run_on(module);
+}
@@ -1,20 +1,25 @@
 //! Copyright 2021 The KCL Authors. All rights reserved.
 
 mod abi;
+#[cfg(feature = "cranelift")]
+pub mod cranelift;
 pub mod error;
+#[cfg(feature = "llvm")]
 pub mod llvm;
+mod node;
+mod schema;
 mod traits;
+mod utils;
 
 /// The kclvm runner main function name.
 pub(crate) const MODULE_NAME: &str = "kclvm_main";
 /// The kclvm runner main function entry block name.
+#[cfg(feature = "llvm")]
 pub(crate) const ENTRY_NAME: &str = "entry";
 /// The kclvm runtime value type name.
 pub(crate) const VALUE_TYPE_NAME: &str = "kclvm_value_ref_t";
 /// The kclvm runtime context type name.
 pub(crate) const CONTEXT_TYPE_NAME: &str = "kclvm_context_t";
-/// The kclvm context variable name.
-pub(crate) const KCL_CONTEXT_VAR_NAME: &str = "context";
 /// Package init function name suffix
 pub(crate) const PKG_INIT_FUNCTION_SUFFIX: &str = "init";
 /// Global level
@@ -24,11 +29,56 @@ pub(crate) const INNER_LEVEL: usize = 2;
 /// Global variable alignment
 pub(crate) const GLOBAL_VAL_ALIGNMENT: u32 = 8;
 
+/// Object file type format suffix.
+#[cfg(target_os = "windows")]
+pub const OBJECT_FILE_SUFFIX: &str = ".obj";
+#[cfg(not(target_os = "windows"))]
+pub const OBJECT_FILE_SUFFIX: &str = ".o";
+/// WebAssembly module format suffix .wasm
+pub const WASM_FILE_SUFFIX: &str = ".wasm";
+/// WebAssembly WASI target triple.
+pub const WASM32_WASI_TARGET: &str = "wasm32-wasi";
+
+/// Schema internal order independent computation backtracking meta information.
+pub struct BacktrackMeta {
+    pub target: String,
+    pub level: usize,
+    pub count: usize,
+    pub stop: bool,
+}
+
 /// CodeGenContext is a trait used by the compiler to emit code to different targets.
 pub trait CodeGenContext: traits::ProgramCodeGen {
     fn emit(&self, opt: &EmitOptions) -> Result<(), Box<dyn std::error::Error>>;
 }
 
+/// EmitKind represents the format of the emitted code.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum EmitKind {
+    /// Native object file.
+    #[default]
+    Object,
+    /// Textual LLVM IR.
+    LlvmIr,
+    /// LLVM bitcode.
+    Bitcode,
+}
+
+impl std::str::FromStr for EmitKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "obj" => Ok(EmitKind::Object),
+            "llvm-ir" => Ok(EmitKind::LlvmIr),
+            "bc" => Ok(EmitKind::Bitcode),
+            _ => Err(format!(
+                "invalid emit kind {s}, expected one of llvm-ir, bc and obj"
+            )),
+        }
+    }
+}
+
 /// EmitOptions represents the general emit options
 #[derive(Debug, Default)]
 pub struct EmitOptions<'a> {
@@ -38,6 +88,15 @@ pub struct EmitOptions<'a> {
     pub emit_path: Option<&'a str>,
     /// no_link indicates whether to link the generated code of different KCL packages to the same module.
     pub no_link: bool,
+    /// Optimization level of the generated code from 0 to 3, 0 denotes no optimization.
+    pub opt_level: u32,
+    /// Target triple of the emitted object file e.g., `wasm32-wasi`, if not set, use the host target.
+    pub target: Option<&'a str>,
+    /// Format of the emitted code, the object file by default.
+    pub emit_kind: EmitKind,
+    /// Whether to emit the DWARF debug info of the KCL sources into the generated code,
+    /// which is only supported by the LLVM backend.
+    pub debug_info: bool,
 }
 
 /// Emit code with the options using CodeGenContext.
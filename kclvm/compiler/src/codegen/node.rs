@@ -1,43 +1,37 @@
 // Copyright 2021 The KCL Authors. All rights reserved.
 
-use std::cell::RefCell;
 use std::collections::HashMap;
-use std::convert::TryFrom;
 
-use inkwell::basic_block::BasicBlock;
-use inkwell::module::Linkage;
-use inkwell::values::{BasicValueEnum, CallableValue, FunctionValue};
-use inkwell::{AddressSpace, IntPredicate};
 use kclvm_ast::ast::{self, CallExpr, ConfigEntry, NodeRef};
-use kclvm_ast::walker::TypedResultWalker;
-use kclvm_runtime::{ApiFunc, PKG_PATH_PREFIX};
+use kclvm_runtime::{ApiFunc, PKG_PATH_PREFIX, PROFILE_KIND_CHECK};
+use kclvm_sema::builtin;
+use kclvm_sema::plugin;
 
+use crate::codegen::abi::IntPredicate;
 use crate::codegen::error as kcl_error;
-use crate::codegen::llvm::context::BacktrackMeta;
-use crate::codegen::llvm::utils;
-use crate::codegen::traits::*;
-use crate::codegen::{ENTRY_NAME, GLOBAL_LEVEL, INNER_LEVEL, PKG_INIT_FUNCTION_SUFFIX};
-use crate::{check_backtrack_stop, pkgpath_without_prefix};
-
-use super::context::{CompileResult, LLVMCodeGenContext};
+use crate::codegen::schema::SchemaCodeGen;
+use crate::codegen::utils;
+use crate::codegen::{BacktrackMeta, GLOBAL_LEVEL, INNER_LEVEL, PKG_INIT_FUNCTION_SUFFIX};
 use crate::value;
-use kclvm_sema::builtin;
-use kclvm_sema::plugin;
+use crate::{check_backtrack_stop, pkgpath_without_prefix};
 
-/// Impl TypedResultWalker for LLVMCodeGenContext to visit AST nodes to emit LLVM IR.
-impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
-    type Result = CompileResult<'ctx>;
+/// The compiler function result
+pub(crate) type CompileResult<V> = Result<V, kcl_error::KCLError>;
 
+/// NodeCodeGen visits the AST nodes to emit the SSA instructions using the codegen traits,
+/// which is shared by all backends and follows the methods of the TypedResultWalker.
+pub(crate) trait NodeCodeGen<'ctx>: SchemaCodeGen<'ctx> {
     /*
      * Stmt
      */
 
-    fn walk_stmt(&self, stmt: &'ctx ast::Node<ast::Stmt>) -> Self::Result {
+    fn walk_stmt(&self, stmt: &'ctx ast::Node<ast::Stmt>) -> CompileResult<Self::Value> {
         check_backtrack_stop!(self);
         utils::update_ctx_filename(self, stmt);
         utils::update_ctx_line_col(self, stmt);
-        self.target_vars.borrow_mut().clear();
-        self.target_vars.borrow_mut().push("".to_string());
+        self.debug_set_location(stmt);
+        self.target_vars().borrow_mut().clear();
+        self.target_vars().borrow_mut().push("".to_string());
         match &stmt.node {
             ast::Stmt::TypeAlias(type_alias) => self.walk_type_alias_stmt(type_alias),
             ast::Stmt::Expr(expr_stmt) => self.walk_expr_stmt(expr_stmt),
@@ -55,7 +49,7 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
         }
     }
 
-    fn walk_expr_stmt(&self, expr_stmt: &'ctx ast::ExprStmt) -> Self::Result {
+    fn walk_expr_stmt(&self, expr_stmt: &'ctx ast::ExprStmt) -> CompileResult<Self::Value> {
         check_backtrack_stop!(self);
         let mut result = self.ok_result();
         for expr in &expr_stmt.exprs {
@@ -69,125 +63,127 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
         result
     }
 
-    fn walk_unification_stmt(&self, unification_stmt: &'ctx ast::UnificationStmt) -> Self::Result {
+    fn walk_unification_stmt(
+        &self,
+        unification_stmt: &'ctx ast::UnificationStmt,
+    ) -> CompileResult<Self::Value> {
         check_backtrack_stop!(self);
-        self.local_vars.borrow_mut().clear();
+        self.local_vars().borrow_mut().clear();
         let name = &unification_stmt.target.node.names[0];
-        self.target_vars.borrow_mut().push(name.clone());
+        self.target_vars().borrow_mut().push(name.clone());
         // The right value of the unification_stmt is a schema_expr.
-        let value = self
-            .walk_schema_expr(&unification_stmt.value.node)
-            .expect(kcl_error::COMPILE_ERROR_MSG);
+        let value = self.walk_schema_expr(&unification_stmt.value.node)?;
         if self.scope_level() == GLOBAL_LEVEL
             || *self
-                .lambda_stack
+                .lambda_stack()
                 .borrow_mut()
                 .last()
                 .expect(kcl_error::INTERNAL_ERROR_MSG)
         {
             if self.resolve_variable(name) {
-                let org_value = self
-                    .walk_identifier_with_ctx(
-                        &unification_stmt.target.node,
-                        &ast::ExprContext::Load,
-                        None,
-                    )
-                    .expect(kcl_error::COMPILE_ERROR_MSG);
+                let org_value = self.walk_identifier_with_ctx(
+                    &unification_stmt.target.node,
+                    &ast::ExprContext::Load,
+                    None,
+                )?;
                 let fn_name = ApiFunc::kclvm_value_op_aug_bit_or;
-                let value = self.build_call(&fn_name.name(), &[org_value, value]);
+                let value = self.build_call(
+                    &fn_name.name(),
+                    &[self.current_runtime_ctx_ptr(), org_value, value],
+                );
                 // Store the identifier value
                 self.walk_identifier_with_ctx(
                     &unification_stmt.target.node,
                     &ast::ExprContext::Store,
                     Some(value),
-                )
-                .expect(kcl_error::COMPILE_ERROR_MSG);
+                )?;
                 return Ok(value);
             } else {
                 self.walk_identifier_with_ctx(
                     &unification_stmt.target.node,
                     &unification_stmt.target.node.ctx,
                     Some(value),
-                )
-                .expect(kcl_error::COMPILE_ERROR_MSG);
+                )?;
                 return Ok(value);
             }
         // Local variables including schema/rule/lambda
-        } else if self.schema_stack.borrow().len() > 0 {
+        } else if !self.schema_stack().borrow().is_empty() {
             // Load the identifier value
-            let org_value = self
-                .walk_identifier_with_ctx(
-                    &unification_stmt.target.node,
-                    &ast::ExprContext::Load,
-                    None,
-                )
-                .expect(kcl_error::COMPILE_ERROR_MSG);
+            let org_value = self.walk_identifier_with_ctx(
+                &unification_stmt.target.node,
+                &ast::ExprContext::Load,
+                None,
+            )?;
             let fn_name = ApiFunc::kclvm_value_op_bit_or;
-            let value = self.build_call(&fn_name.name(), &[org_value, value]);
+            let value = self.build_call(
+                &fn_name.name(),
+                &[self.current_runtime_ctx_ptr(), org_value, value],
+            );
             // Store the identifier value
             self.walk_identifier_with_ctx(
                 &unification_stmt.target.node,
                 &ast::ExprContext::Store,
                 Some(value),
-            )
-            .expect(kcl_error::COMPILE_ERROR_MSG);
+            )?;
             return Ok(value);
         }
         Ok(value)
     }
 
-    fn walk_type_alias_stmt(&self, _type_alias_stmt: &'ctx ast::TypeAliasStmt) -> Self::Result {
+    fn walk_type_alias_stmt(
+        &self,
+        _type_alias_stmt: &'ctx ast::TypeAliasStmt,
+    ) -> CompileResult<Self::Value> {
         // Nothing to do, because all type aliases have been replaced at compile time
         self.ok_result()
     }
 
-    fn walk_assign_stmt(&self, assign_stmt: &'ctx ast::AssignStmt) -> Self::Result {
+    fn walk_assign_stmt(&self, assign_stmt: &'ctx ast::AssignStmt) -> CompileResult<Self::Value> {
         check_backtrack_stop!(self);
-        self.local_vars.borrow_mut().clear();
+        self.local_vars().borrow_mut().clear();
         for name in &assign_stmt.targets {
-            self.target_vars
+            self.target_vars()
                 .borrow_mut()
                 .push(name.node.names[0].clone());
         }
         // Load the right value
-        let mut value = self
-            .walk_expr(&assign_stmt.value)
-            .expect(kcl_error::COMPILE_ERROR_MSG);
+        let mut value = self.walk_expr(&assign_stmt.value)?;
         if let Some(type_annotation) = &assign_stmt.type_annotation {
             let type_annotation = self.native_global_string_value(&type_annotation.node);
             value = self.build_call(
                 &ApiFunc::kclvm_convert_collection_value.name(),
-                &[value, type_annotation],
+                &[self.current_runtime_ctx_ptr(), value, type_annotation],
             );
         }
         if assign_stmt.targets.len() == 1 {
             let name = &assign_stmt.targets[0];
-            self.walk_identifier_with_ctx(&name.node, &name.node.ctx, Some(value))
-                .expect(kcl_error::COMPILE_ERROR_MSG);
+            self.walk_identifier_with_ctx(&name.node, &name.node.ctx, Some(value))?;
         } else {
             // Store targets
             for name in &assign_stmt.targets {
                 let value = self.value_deep_copy(value);
-                self.walk_identifier_with_ctx(&name.node, &name.node.ctx, Some(value))
-                    .expect(kcl_error::COMPILE_ERROR_MSG);
+                self.walk_identifier_with_ctx(&name.node, &name.node.ctx, Some(value))?;
             }
         }
         Ok(value)
     }
 
-    fn walk_aug_assign_stmt(&self, aug_assign_stmt: &'ctx ast::AugAssignStmt) -> Self::Result {
+    fn walk_aug_assign_stmt(
+        &self,
+        aug_assign_stmt: &'ctx ast::AugAssignStmt,
+    ) -> CompileResult<Self::Value> {
         check_backtrack_stop!(self);
-        self.target_vars
+        self.target_vars()
             .borrow_mut()
             .push(aug_assign_stmt.target.node.names[0].clone());
         // Load the right value
-        let right_value = self
-            .walk_expr(&aug_assign_stmt.value)
-            .expect(kcl_error::COMPILE_ERROR_MSG);
+        let right_value = self.walk_expr(&aug_assign_stmt.value)?;
         // Load the identifier value
-        let org_value = self
-            .walk_identifier_with_ctx(&aug_assign_stmt.target.node, &ast::ExprContext::Load, None)
-            .expect(kcl_error::COMPILE_ERROR_MSG);
+        let org_value = self.walk_identifier_with_ctx(
+            &aug_assign_stmt.target.node,
+            &ast::ExprContext::Load,
+            None,
+        )?;
         let fn_name = match aug_assign_stmt.op {
             ast::AugOp::Add => ApiFunc::kclvm_value_op_aug_add,
             ast::AugOp::Sub => ApiFunc::kclvm_value_op_aug_sub,
@@ -205,71 +201,70 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
                 return Err(kcl_error::KCLError::new(kcl_error::INVALID_OPERATOR_MSG));
             }
         };
-        let value = self.build_call(&fn_name.name(), &[org_value, right_value]);
+        let value = self.build_call(
+            &fn_name.name(),
+            &[self.current_runtime_ctx_ptr(), org_value, right_value],
+        );
         // Store the identifier value
         self.walk_identifier_with_ctx(
             &aug_assign_stmt.target.node,
             &ast::ExprContext::Store,
             Some(value),
-        )
-        .expect(kcl_error::COMPILE_ERROR_MSG);
+        )?;
         Ok(value)
     }
 
-    fn walk_assert_stmt(&self, assert_stmt: &'ctx ast::AssertStmt) -> Self::Result {
+    fn walk_assert_stmt(&self, assert_stmt: &'ctx ast::AssertStmt) -> CompileResult<Self::Value> {
         check_backtrack_stop!(self);
         let start_block = self.append_block("");
         let end_block = self.append_block("");
         if let Some(if_cond) = &assert_stmt.if_cond {
-            let if_value = self.walk_expr(if_cond).expect(kcl_error::COMPILE_ERROR_MSG);
+            let if_value = self.walk_expr(if_cond)?;
             let is_truth = self.value_is_truthy(if_value);
             self.cond_br(is_truth, start_block, end_block);
         } else {
             self.br(start_block);
         }
-        self.builder.position_at_end(start_block);
-        let assert_result = self
-            .walk_expr(&assert_stmt.test)
-            .expect(kcl_error::COMPILE_ERROR_MSG);
+        self.switch_to_block(start_block);
+        let assert_result = self.walk_expr(&assert_stmt.test)?;
         let msg = {
             if let Some(msg) = &assert_stmt.msg {
-                self.walk_expr(msg).expect(kcl_error::COMPILE_ERROR_MSG)
+                self.walk_expr(msg)?
             } else {
                 self.string_value("")
             }
         };
-        self.build_void_call(&ApiFunc::kclvm_assert.name(), &[assert_result, msg]);
+        self.build_void_call(
+            &ApiFunc::kclvm_assert.name(),
+            &[self.current_runtime_ctx_ptr(), assert_result, msg],
+        );
         self.br(end_block);
-        self.builder.position_at_end(end_block);
+        self.switch_to_block(end_block);
         self.ok_result()
     }
 
-    fn walk_if_stmt(&self, if_stmt: &'ctx ast::IfStmt) -> Self::Result {
+    fn walk_if_stmt(&self, if_stmt: &'ctx ast::IfStmt) -> CompileResult<Self::Value> {
         check_backtrack_stop!(self);
-        let cond = self
-            .walk_expr(&if_stmt.cond)
-            .expect(kcl_error::COMPILE_ERROR_MSG);
+        let cond = self.walk_expr(&if_stmt.cond)?;
         let then_block = self.append_block("");
         let else_block = self.append_block("");
         let end_block = self.append_block("");
         let is_truth = self.value_is_truthy(cond);
         self.cond_br(is_truth, then_block, else_block);
-        self.builder.position_at_end(then_block);
-        self.walk_stmts(&if_stmt.body)
-            .expect(kcl_error::COMPILE_ERROR_MSG);
+        self.switch_to_block(then_block);
+        self.walk_stmts(&if_stmt.body)?;
         self.br(end_block);
-        self.builder.position_at_end(else_block);
-        self.walk_stmts(&if_stmt.orelse)
-            .expect(kcl_error::COMPILE_ERROR_MSG);
+        self.switch_to_block(else_block);
+        self.walk_stmts(&if_stmt.orelse)?;
         self.br(end_block);
-        self.builder.position_at_end(end_block);
+        self.switch_to_block(end_block);
         Ok(self.none_value())
     }
-    fn walk_import_stmt(&self, import_stmt: &'ctx ast::ImportStmt) -> Self::Result {
+    fn walk_import_stmt(&self, import_stmt: &'ctx ast::ImportStmt) -> CompileResult<Self::Value> {
         check_backtrack_stop!(self);
         let pkgpath = import_stmt.path.as_str();
         {
-            let imported = self.imported.borrow_mut();
+            let imported = self.imported().borrow_mut();
             if imported.contains(pkgpath) {
                 return self.ok_result();
             }
@@ -282,140 +277,90 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
             return self.ok_result();
         } else {
             let pkgpath = format!("{}{}", PKG_PATH_PREFIX, import_stmt.path);
-            self.pkgpath_stack.borrow_mut().push(pkgpath);
+            self.pkgpath_stack().borrow_mut().push(pkgpath);
             let pkgpath = format!("{}{}", PKG_PATH_PREFIX, import_stmt.path);
-            let has_pkgpath = self.program.pkgs.contains_key(&import_stmt.path);
-            let func_before_block = if self.no_link {
-                if has_pkgpath {
-                    let func_before_block = self.append_block("");
-                    self.br(func_before_block);
-                    let mut modules = self.modules.borrow_mut();
-                    let name = pkgpath.clone();
-                    let module = self.context.create_module(&name);
-                    let module_name = format!(
-                        "${}.{}",
-                        pkgpath_without_prefix!(pkgpath),
-                        PKG_INIT_FUNCTION_SUFFIX
-                    );
-                    let tpe = self.context.void_type();
-                    let fn_type = tpe.fn_type(&[self.context_ptr_type().into()], false);
-                    let function = module.add_function(
-                        // Function name
-                        &module_name,
-                        // Function type
-                        fn_type,
-                        None,
-                    );
-                    // Add a block named entry into the function
-                    let basic_block = self.context.append_basic_block(function, ENTRY_NAME);
-                    self.builder.position_at_end(basic_block);
-                    self.push_function(function);
-                    modules.insert(name, RefCell::new(module));
-                    Some(func_before_block)
-                } else {
-                    None
-                }
-            } else {
-                None
-            };
+            let has_pkgpath = self.program().pkgs.contains_key(&import_stmt.path);
+            let init_function_name = format!(
+                "${}.{}",
+                pkgpath_without_prefix!(pkgpath),
+                PKG_INIT_FUNCTION_SUFFIX
+            );
+            // In the no link mode, the package is initialized by its own init function.
+            if self.no_link() && has_pkgpath {
+                let function = self.add_pkg_init_function(&init_function_name);
+                self.push_function(function);
+            }
             if has_pkgpath {
                 // Init all builtin functions.
                 self.init_scope(pkgpath.as_str());
                 // Compile the ast module in the pkgpath.
                 for ast_module in self
-                    .program
+                    .program()
                     .pkgs
                     .get(&import_stmt.path)
                     .expect(kcl_error::INTERNAL_ERROR_MSG)
                 {
                     {
-                        self.filename_stack
+                        self.filename_stack()
                             .borrow_mut()
                             .push(ast_module.filename.clone());
                     }
-                    self.compile_module_import_and_types(ast_module);
+                    self.compile_module_import_and_types(ast_module)?;
                     {
-                        self.filename_stack.borrow_mut().pop();
+                        self.filename_stack().borrow_mut().pop();
                     }
                 }
                 for ast_module in self
-                    .program
+                    .program()
                     .pkgs
                     .get(&import_stmt.path)
                     .expect(kcl_error::INTERNAL_ERROR_MSG)
                 {
                     {
-                        self.filename_stack
+                        self.filename_stack()
                             .borrow_mut()
                             .push(ast_module.filename.clone());
                     }
-                    self.walk_stmts_except_import(&ast_module.body)
-                        .expect(kcl_error::COMPILE_ERROR_MSG);
+                    self.walk_stmts_except_import(&ast_module.body)?;
                     {
-                        self.filename_stack.borrow_mut().pop();
+                        self.filename_stack().borrow_mut().pop();
                     }
                 }
             }
-            self.pkgpath_stack.borrow_mut().pop();
-            if self.no_link {
-                let name = format!(
-                    "${}.{}",
-                    pkgpath_without_prefix!(pkgpath),
-                    PKG_INIT_FUNCTION_SUFFIX
-                );
-                let function = {
-                    let pkgpath = self.current_pkgpath();
-                    let modules = self.modules.borrow_mut();
-                    let msg = format!("pkgpath {} is not found", pkgpath);
-                    let module = modules.get(&pkgpath).expect(&msg).borrow_mut();
-                    if has_pkgpath {
-                        self.ret_void();
-                        self.pop_function();
-                        self.builder.position_at_end(
-                            func_before_block.expect(kcl_error::INTERNAL_ERROR_MSG),
-                        );
-                    }
-                    let tpe = self.context.void_type();
-                    let fn_type = tpe.fn_type(&[self.context_ptr_type().into()], false);
-                    module.add_function(&name, fn_type, Some(Linkage::External))
-                };
-                let ctx = self.global_ctx_ptr();
-                let pkgpath_value = self.native_global_string_value(&name);
-                let is_imported = self
-                    .build_call(
-                        &ApiFunc::kclvm_context_pkgpath_is_imported.name(),
-                        &[pkgpath_value],
-                    )
-                    .into_int_value();
-                let is_not_imported = self.builder.build_int_compare(
-                    IntPredicate::EQ,
-                    is_imported,
-                    self.native_i8_zero(),
-                    "",
+            self.pkgpath_stack().borrow_mut().pop();
+            if self.no_link() {
+                if has_pkgpath {
+                    self.ret_void();
+                    self.pop_function()?;
+                }
+                let function = self.declare_pkg_init_function(&init_function_name);
+                let ctx = self.current_runtime_ctx_ptr();
+                let pkgpath_value = self.native_global_string_value(&init_function_name);
+                let is_imported = self.build_call(
+                    &ApiFunc::kclvm_context_pkgpath_is_imported.name(),
+                    &[self.current_runtime_ctx_ptr(), pkgpath_value],
                 );
+                let is_not_imported =
+                    self.icmp(IntPredicate::Equal, is_imported, self.native_i8_zero());
                 let then_block = self.append_block("");
                 let else_block = self.append_block("");
-                self.builder
-                    .build_conditional_branch(is_not_imported, then_block, else_block);
-                self.builder.position_at_end(then_block);
-                self.builder.build_call(function, &[ctx.into()], "");
+                self.cond_br(is_not_imported, then_block, else_block);
+                self.switch_to_block(then_block);
+                self.build_call_function(function, &[ctx]);
                 self.br(else_block);
-                self.builder.position_at_end(else_block);
+                self.switch_to_block(else_block);
             }
         };
         {
-            let mut imported = self.imported.borrow_mut();
+            let mut imported = self.imported().borrow_mut();
             (*imported).insert(pkgpath.to_string());
             // Deref the borrow mut
         }
         self.ok_result()
     }
 
-    fn walk_schema_stmt(&self, schema_stmt: &'ctx ast::SchemaStmt) -> Self::Result {
+    fn walk_schema_stmt(&self, schema_stmt: &'ctx ast::SchemaStmt) -> CompileResult<Self::Value> {
         check_backtrack_stop!(self);
-        let func_before_block = self.append_block("");
-        self.br(func_before_block);
-        let value_ptr_type = self.value_ptr_type();
         let schema_name = &schema_stmt.name.node;
         let schema_pkgpath = &self.current_pkgpath();
         let filename = &self.current_filename();
@@ -432,28 +377,23 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
             value::SCHEMA_CHECK_BLOCK_NAME,
             pkgpath_without_prefix!(runtime_type),
         ));
-        let mut place_holder_map: HashMap<String, Vec<FunctionValue<'ctx>>> = HashMap::new();
+        let mut place_holder_map: HashMap<String, Vec<Self::Function>> = HashMap::new();
         let mut body_map: HashMap<String, Vec<&ast::Node<ast::Stmt>>> = HashMap::new();
         // Enter the function
         self.push_function(function);
-        // Lambda function body
-        let block = self.append_block(ENTRY_NAME);
-        self.builder.position_at_end(block);
         self.build_void_call(
             &ApiFunc::kclvm_context_set_kcl_filename.name(),
-            &[self.native_global_string_value(filename)],
+            &[
+                self.current_runtime_ctx_ptr(),
+                self.native_global_string_value(filename),
+            ],
         );
         utils::update_ctx_pkgpath(self, schema_pkgpath);
-        let args = function
-            .get_nth_param(1)
-            .expect(kcl_error::INTERNAL_ERROR_MSG);
-        let kwargs = function
-            .get_nth_param(2)
-            .expect(kcl_error::INTERNAL_ERROR_MSG);
+        let args = self.function_param(1);
+        let kwargs = self.function_param(2);
         self.enter_scope();
-        let add_variable = |name: &str, value: BasicValueEnum| {
-            let var = self.builder.build_alloca(value_ptr_type, name);
-            self.builder.build_store(var, value);
+        let add_variable = |name: &str, value: Self::Value| {
+            let var = self.new_local_variable_with_value(value);
             self.add_variable(name, var);
         };
         // Schema function closures
@@ -475,7 +415,7 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
         add_variable(value::SCHEMA_ARGS, args);
         add_variable(value::SCHEMA_KWARGS, kwargs);
         add_variable(value::SCHEMA_RUNTIME_TYPE, self.string_value(&runtime_type));
-        self.walk_arguments(&schema_stmt.args, args, kwargs);
+        self.walk_arguments(&schema_stmt.args, args, kwargs)?;
         let schema = value::SchemaType::new(
             schema_name,
             schema_pkgpath,
@@ -483,9 +423,8 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
             schema_stmt.is_mixin,
         );
         let schema_value = if let Some(parent_name) = &schema_stmt.parent_name {
-            let base_constructor_func = self
-                .walk_identifier_with_ctx(&parent_name.node, &ast::ExprContext::Load, None)
-                .expect(kcl_error::COMPILE_ERROR_MSG);
+            let base_constructor_func =
+                self.walk_identifier_with_ctx(&parent_name.node, &ast::ExprContext::Load, None)?;
             // Schema function closures
             let list_value = self.list_values(&[
                 // is_sub_schema
@@ -505,32 +444,23 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
                 &ApiFunc::kclvm_value_function_ptr.name(),
                 &[base_constructor_func],
             );
-            let fn_ty = self.function_type().ptr_type(AddressSpace::default());
-            let func_ptr_cast = self.builder.build_bitcast(func_ptr, fn_ty, "");
-            self.builder
-                .build_call(
-                    CallableValue::try_from(func_ptr_cast.into_pointer_value())
-                        .expect(kcl_error::INTERNAL_ERROR_MSG),
-                    &[
-                        self.global_ctx_ptr().into(),
-                        list_value.into(),
-                        dict_value.into(),
-                    ],
-                    "",
-                )
-                .try_as_basic_value()
-                .left()
-                .expect(kcl_error::FUNCTION_RETURN_VALUE_NOT_FOUND_MSG)
+            self.build_function_ptr_call(
+                func_ptr,
+                &[self.current_runtime_ctx_ptr(), list_value, dict_value],
+            )
         } else {
             schema_value
         };
         if schema_stmt.parent_name.is_some() {
             self.build_void_call(
                 &ApiFunc::kclvm_context_set_kcl_filename.name(),
-                &[self.native_global_string_value(filename)],
+                &[
+                    self.current_runtime_ctx_ptr(),
+                    self.native_global_string_value(filename),
+                ],
             );
         }
-        self.schema_stack.borrow_mut().push(schema);
+        self.schema_stack().borrow_mut().push(schema);
         add_variable(value::SCHEMA_SELF_NAME, schema_value);
         self.emit_schema_left_identifiers(
             &schema_stmt.body,
@@ -540,20 +470,19 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
             &mut place_holder_map,
             &mut body_map,
             &mut vec![],
-        );
+        )?;
         let do_run_i1 = self.value_is_truthy(record_instance);
         let do_run_block = self.append_block("");
         let end_run_block = self.append_block("");
         self.cond_br(do_run_i1, do_run_block, end_run_block);
-        self.builder.position_at_end(do_run_block);
+        self.switch_to_block(do_run_block);
         // Run schema compiled function
         for stmt in &schema_stmt.body {
-            self.walk_stmt(stmt).expect(kcl_error::COMPILE_ERROR_MSG);
+            self.walk_stmt(stmt)?;
         }
         // Schema decorators check
         for decorator in &schema_stmt.decorators {
-            self.walk_decorator_with_name(&decorator.node, Some(schema_name), true)
-                .expect(kcl_error::COMPILE_ERROR_MSG);
+            self.walk_decorator_with_name(&decorator.node, Some(schema_name), true)?;
         }
         // Append schema default settings
         self.build_void_call(
@@ -565,12 +494,11 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
             ],
         );
         self.br(end_run_block);
-        self.builder.position_at_end(end_run_block);
+        self.switch_to_block(end_run_block);
         // Schema mixin
         for mixin in &schema_stmt.mixins {
-            let mixin_func = self
-                .walk_identifier_with_ctx(&mixin.node, &ast::ExprContext::Load, None)
-                .expect(kcl_error::COMPILE_ERROR_MSG);
+            let mixin_func =
+                self.walk_identifier_with_ctx(&mixin.node, &ast::ExprContext::Load, None)?;
             // Schema function closures
             let list_value = self.list_values(&[
                 // is_sub_schema
@@ -588,21 +516,16 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
             let dict_value = self.dict_value();
             let func_ptr =
                 self.build_call(&ApiFunc::kclvm_value_function_ptr.name(), &[mixin_func]);
-            let fn_ty = self.function_type().ptr_type(AddressSpace::default());
-            let func_ptr_cast = self.builder.build_bitcast(func_ptr, fn_ty, "");
-            self.builder.build_call(
-                CallableValue::try_from(func_ptr_cast.into_pointer_value())
-                    .expect(kcl_error::INTERNAL_ERROR_MSG),
-                &[
-                    self.global_ctx_ptr().into(),
-                    list_value.into(),
-                    dict_value.into(),
-                ],
-                "",
+            self.build_function_ptr_call(
+                func_ptr,
+                &[self.current_runtime_ctx_ptr(), list_value, dict_value],
             );
             self.build_void_call(
                 &ApiFunc::kclvm_context_set_kcl_filename.name(),
-                &[self.native_global_string_value(filename)],
+                &[
+                    self.current_runtime_ctx_ptr(),
+                    self.native_global_string_value(filename),
+                ],
             );
         }
         // Schema Attribute optional check
@@ -615,17 +538,17 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
                 )
             }
         }
+        let schema_name_native_str = self.native_global_string_value(&schema_stmt.name.node);
+        let schema_pkgpath_native_str = self.native_global_string_value(&self.current_pkgpath());
         let is_sub_schema_i1 = self.value_is_truthy(is_sub_schema);
         let do_check_block = self.append_block("");
         let end_check_block = self.append_block("");
         self.cond_br(is_sub_schema_i1, do_check_block, end_check_block);
-        self.builder.position_at_end(do_check_block);
-        let schema_name_native_str = self.native_global_string_value(&schema_stmt.name.node);
-        let schema_pkgpath_native_str = self.native_global_string_value(&self.current_pkgpath());
+        self.switch_to_block(do_check_block);
         // Schema runtime index signature and relaxed check
         if let Some(index_signature) = &schema_stmt.index_signature {
             let index_sign_value = if let Some(value) = &index_signature.node.value {
-                self.walk_expr(value).expect(kcl_error::COMPILE_ERROR_MSG)
+                self.walk_expr(value)?
             } else {
                 self.none_value()
             };
@@ -637,34 +560,34 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
             self.build_void_call(
                 &ApiFunc::kclvm_schema_value_check.name(),
                 &[
+                    self.current_runtime_ctx_ptr(),
                     schema_value,
                     schema_config,
                     schema_config_meta,
                     schema_name_native_str,
                     index_sign_value,
-                    key_name_str_ptr.into(),
-                    self.native_global_string(index_signature.node.key_type.node.as_str(), "")
-                        .into(),
-                    self.native_global_string(index_signature.node.value_type.node.as_str(), "")
-                        .into(),
-                    self.native_i8(index_signature.node.any_other as i8).into(),
-                    self.native_i8(false as i8).into(),
+                    key_name_str_ptr,
+                    self.native_global_string(index_signature.node.key_type.node.as_str(), ""),
+                    self.native_global_string(index_signature.node.value_type.node.as_str(), ""),
+                    self.native_i8(index_signature.node.any_other as i8),
+                    self.native_i8(false as i8),
                 ],
             );
         } else {
             self.build_void_call(
                 &ApiFunc::kclvm_schema_value_check.name(),
                 &[
+                    self.current_runtime_ctx_ptr(),
                     schema_value,
                     schema_config,
                     schema_config_meta,
                     schema_name_native_str,
                     self.none_value(),
-                    self.native_global_string("", "").into(),
-                    self.native_global_string("", "").into(),
-                    self.native_global_string("", "").into(),
-                    self.native_i8(0).into(),
-                    self.native_i8(false as i8).into(),
+                    self.native_global_string("", ""),
+                    self.native_global_string("", ""),
+                    self.native_global_string("", ""),
+                    self.native_i8(0),
+                    self.native_i8(false as i8),
                 ],
             );
         }
@@ -689,29 +612,20 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
             self.list_append(list_value, backtrack_cache);
             if index_sign_key_name.is_empty() {
                 // Call schema check block function
-                self.builder.build_call(
+                self.build_call_function(
                     check_function,
-                    &[
-                        self.global_ctx_ptr().into(),
-                        list_value.into(),
-                        dict_value.into(),
-                    ],
-                    "",
+                    &[self.current_runtime_ctx_ptr(), list_value, dict_value],
                 );
             } else {
                 // Call schema check block function with index sign attribute name loop set
-                let check_lambda_fn_ptr = self.builder.build_bitcast(
-                    check_function.as_global_value().as_pointer_value(),
-                    self.context.i64_type().ptr_type(AddressSpace::default()),
-                    "",
-                );
+                let check_lambda_fn_ptr = self.function_ptr(check_function);
                 let attr_name = self.native_global_string_value(index_sign_key_name);
                 self.build_void_call(
                     ApiFunc::kclvm_schema_do_check_with_index_sign_attr
                         .name()
                         .as_str(),
                     &[
-                        self.global_ctx_ptr(),
+                        self.current_runtime_ctx_ptr(),
                         list_value,
                         dict_value,
                         check_lambda_fn_ptr,
@@ -721,11 +635,12 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
             }
         }
         self.br(end_check_block);
-        self.builder.position_at_end(end_check_block);
+        self.switch_to_block(end_check_block);
         // Build a schema value and record instance
         let schema_value = self.build_call(
             &ApiFunc::kclvm_value_schema_with_config.name(),
             &[
+                self.current_runtime_ctx_ptr(),
                 schema_value,
                 schema_config,
                 schema_config_meta,
@@ -738,17 +653,13 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
             ],
         );
         // Schema constructor function returns a schema
-        self.builder.build_return(Some(&schema_value));
-        // Exist the function
-        self.builder.position_at_end(func_before_block);
+        self.ret(schema_value);
         // Build schema check function
         {
             self.push_function(check_function);
-            let check_block = self.append_block(ENTRY_NAME);
-            self.builder.position_at_end(check_block);
-            let args = function
-                .get_nth_param(1)
-                .expect(kcl_error::INTERNAL_ERROR_MSG);
+            self.enter_scope();
+            let args = self.function_param(1);
+            let kwargs = self.function_param(2);
             // Schema check function closure
             let backtrack_cache = self.list_pop(args);
             let backtrack_level_map = self.list_pop(args);
@@ -766,23 +677,23 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
             add_variable(value::SCHEMA_KWARGS, kwargs);
             add_variable(value::SCHEMA_RUNTIME_TYPE, self.string_value(&runtime_type));
             let schema = self
-                .schema_stack
+                .schema_stack()
                 .borrow_mut()
                 .pop()
                 .expect(kcl_error::INTERNAL_ERROR_MSG);
-            self.walk_arguments(&schema_stmt.args, args, kwargs);
-            self.schema_stack.borrow_mut().push(schema);
+            self.walk_arguments(&schema_stmt.args, args, kwargs)?;
+            self.schema_stack().borrow_mut().push(schema);
             // Call base check function
             if let Some(parent_name) = &schema_stmt.parent_name {
-                let base_constructor_func = self
-                    .walk_identifier_with_ctx(&parent_name.node, &ast::ExprContext::Load, None)
-                    .expect(kcl_error::COMPILE_ERROR_MSG);
+                let base_constructor_func = self.walk_identifier_with_ctx(
+                    &parent_name.node,
+                    &ast::ExprContext::Load,
+                    None,
+                )?;
                 let func_ptr = self.build_call(
                     &ApiFunc::kclvm_value_check_function_ptr.name(),
                     &[base_constructor_func],
                 );
-                let fn_ty = self.function_type().ptr_type(AddressSpace::default());
-                let func_ptr_cast = self.builder.build_bitcast(func_ptr, fn_ty, "");
                 // Schema check function closure
                 let list_value = self.list_values(&[
                     schema_config_meta,
@@ -793,37 +704,42 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
                     backtrack_cache,
                 ]);
                 let dict_value = self.dict_value();
-                self.builder.build_call(
-                    CallableValue::try_from(func_ptr_cast.into_pointer_value())
-                        .expect(kcl_error::INTERNAL_ERROR_MSG),
-                    &[
-                        self.global_ctx_ptr().into(),
-                        list_value.into(),
-                        dict_value.into(),
-                    ],
-                    "",
+                self.build_function_ptr_call(
+                    func_ptr,
+                    &[self.current_runtime_ctx_ptr(), list_value, dict_value],
                 );
                 self.build_void_call(
                     &ApiFunc::kclvm_context_set_kcl_filename.name(),
-                    &[self.native_global_string_value(filename)],
+                    &[
+                        self.current_runtime_ctx_ptr(),
+                        self.native_global_string_value(filename),
+                    ],
                 );
             }
             // Call self check function
+            self.build_void_call(
+                &ApiFunc::kclvm_context_profile_begin.name(),
+                &[
+                    self.current_runtime_ctx_ptr(),
+                    self.native_global_string_value(PROFILE_KIND_CHECK),
+                    self.native_global_string_value(&runtime_type),
+                ],
+            );
             for check_expr in &schema_stmt.checks {
-                self.walk_check_expr(&check_expr.node)
-                    .expect(kcl_error::COMPILE_ERROR_MSG);
+                self.walk_check_expr(&check_expr.node)?;
             }
+            self.build_void_call(
+                &ApiFunc::kclvm_context_profile_end.name(),
+                &[self.current_runtime_ctx_ptr()],
+            );
             // Call mixin check functions
             for mixin in &schema_stmt.mixins {
-                let mixin_func = self
-                    .walk_identifier_with_ctx(&mixin.node, &ast::ExprContext::Load, None)
-                    .expect(kcl_error::COMPILE_ERROR_MSG);
+                let mixin_func =
+                    self.walk_identifier_with_ctx(&mixin.node, &ast::ExprContext::Load, None)?;
                 let func_ptr = self.build_call(
                     &ApiFunc::kclvm_value_check_function_ptr.name(),
                     &[mixin_func],
                 );
-                let fn_ty = self.function_type().ptr_type(AddressSpace::default());
-                let func_ptr_cast = self.builder.build_bitcast(func_ptr, fn_ty, "");
                 // Schema check function closure
                 let list_value = self.list_values(&[
                     schema_config_meta,
@@ -834,24 +750,21 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
                     backtrack_cache,
                 ]);
                 let dict_value = self.dict_value();
-                self.builder.build_call(
-                    CallableValue::try_from(func_ptr_cast.into_pointer_value())
-                        .expect(kcl_error::INTERNAL_ERROR_MSG),
-                    &[
-                        self.global_ctx_ptr().into(),
-                        list_value.into(),
-                        dict_value.into(),
-                    ],
-                    "",
+                self.build_function_ptr_call(
+                    func_ptr,
+                    &[self.current_runtime_ctx_ptr(), list_value, dict_value],
                 );
                 self.build_void_call(
                     &ApiFunc::kclvm_context_set_kcl_filename.name(),
-                    &[self.native_global_string_value(filename)],
+                    &[
+                        self.current_runtime_ctx_ptr(),
+                        self.native_global_string_value(filename),
+                    ],
                 );
             }
-            self.builder.build_return(Some(&schema_value));
-            self.builder.position_at_end(func_before_block);
-            self.pop_function();
+            self.ret(schema_value);
+            self.leave_scope();
+            self.pop_function()?;
         }
         // Build schema attr backtrack functions
         {
@@ -859,23 +772,10 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
                 let stmt_list = body_map.get(&k).expect(kcl_error::INTERNAL_ERROR_MSG);
                 let mut if_level = 0;
                 for (attr_func, stmt) in functions.iter().zip(stmt_list) {
-                    let function = *attr_func;
-                    let name = function
-                        .get_name()
-                        .to_str()
-                        .expect(kcl_error::INTERNAL_ERROR_MSG);
-                    // Get schema attr function from the module
-                    let function = self.lookup_function(name);
-                    self.push_function(function);
+                    self.push_function(*attr_func);
                     self.enter_scope();
-                    let attr_block = self.append_block(ENTRY_NAME);
-                    self.builder.position_at_end(attr_block);
-                    let args = function
-                        .get_nth_param(1)
-                        .expect(kcl_error::INTERNAL_ERROR_MSG);
-                    let kwargs = function
-                        .get_nth_param(2)
-                        .expect(kcl_error::INTERNAL_ERROR_MSG);
+                    let args = self.function_param(1);
+                    let kwargs = self.function_param(2);
                     // Schema attr function closure
                     let backtrack_cache = self.list_pop(args);
                     let backtrack_level_map = self.list_pop(args);
@@ -895,19 +795,22 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
                     add_variable(value::SCHEMA_RUNTIME_TYPE, self.string_value(&runtime_type));
                     self.build_void_call(
                         &ApiFunc::kclvm_context_set_kcl_filename.name(),
-                        &[self.native_global_string_value(filename)],
+                        &[
+                            self.current_runtime_ctx_ptr(),
+                            self.native_global_string_value(filename),
+                        ],
                     );
                     let schema = self
-                        .schema_stack
+                        .schema_stack()
                         .borrow_mut()
                         .pop()
                         .expect(kcl_error::INTERNAL_ERROR_MSG);
-                    self.walk_arguments(&schema_stmt.args, args, kwargs);
-                    self.schema_stack.borrow_mut().push(schema);
+                    self.walk_arguments(&schema_stmt.args, args, kwargs)?;
+                    self.schema_stack().borrow_mut().push(schema);
                     // Backtrack meta begin
                     if matches!(&stmt.node, ast::Stmt::If(..)) {
                         if_level += 1;
-                        *self.backtrack_meta.borrow_mut() = Some(BacktrackMeta {
+                        *self.backtrack_meta().borrow_mut() = Some(BacktrackMeta {
                             target: k.clone(),
                             level: if_level,
                             count: 0,
@@ -916,38 +819,33 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
                     } else {
                         if_level = 0;
                     }
-                    self.walk_stmt(*stmt).expect(kcl_error::COMPILE_ERROR_MSG);
+                    self.walk_stmt(stmt)?;
                     // Backtrack meta end
                     if matches!(&stmt.node, ast::Stmt::If(..)) {
-                        *self.backtrack_meta.borrow_mut() = None
+                        *self.backtrack_meta().borrow_mut() = None
                     }
                     // Build return
-                    self.builder.build_return(Some(&schema_value));
-                    // Position at global main function block
-                    self.builder.position_at_end(func_before_block);
+                    self.ret(schema_value);
                     self.leave_scope();
-                    self.pop_function();
+                    self.pop_function()?;
                 }
             }
         }
-        let function = self.struct_function_value(&[function, check_function], &runtime_type);
         self.leave_scope();
-        self.pop_function();
-        self.schema_stack.borrow_mut().pop();
+        self.pop_function()?;
+        let function = self.struct_function_value(&[function, check_function], &runtime_type);
+        self.schema_stack().borrow_mut().pop();
         // Store or add the variable in the scope
         if !self.store_variable(schema_name, function) {
-            let global_var_ptr = self.new_global_kcl_value_ptr("");
-            self.builder.build_store(global_var_ptr, function);
-            self.add_variable(schema_name, global_var_ptr);
+            let global_var = self.new_global_kcl_value_ptr("");
+            self.store_to_variable(global_var, function);
+            self.add_variable(schema_name, global_var);
         }
         Ok(function)
     }
 
-    fn walk_rule_stmt(&self, rule_stmt: &'ctx ast::RuleStmt) -> Self::Result {
+    fn walk_rule_stmt(&self, rule_stmt: &'ctx ast::RuleStmt) -> CompileResult<Self::Value> {
         check_backtrack_stop!(self);
-        let func_before_block = self.append_block("");
-        self.br(func_before_block);
-        let value_ptr_type = self.value_ptr_type();
         let name = &rule_stmt.name.node;
         let pkgpath = &self.current_pkgpath();
         let filename = &self.current_filename();
@@ -966,19 +864,15 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
         ));
         // Enter the function
         self.push_function(function);
-        // Lambda function body
-        let block = self.append_block(ENTRY_NAME);
-        self.builder.position_at_end(block);
         self.build_void_call(
             &ApiFunc::kclvm_context_set_kcl_filename.name(),
-            &[self.native_global_string_value(filename)],
+            &[
+                self.current_runtime_ctx_ptr(),
+                self.native_global_string_value(filename),
+            ],
         );
-        let args = function
-            .get_nth_param(1)
-            .expect(kcl_error::INTERNAL_ERROR_MSG);
-        let kwargs = function
-            .get_nth_param(2)
-            .expect(kcl_error::INTERNAL_ERROR_MSG);
+        let args = self.function_param(1);
+        let kwargs = self.function_param(2);
         self.enter_scope();
         // Schema function closures
         let instance_pkgpath = self.list_pop(args);
@@ -991,9 +885,8 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
         let schema_config = self.list_pop(args);
         let schema_config_meta = self.list_pop(args);
         let is_sub_schema = self.list_pop(args);
-        let add_variable = |name: &str, value: BasicValueEnum| {
-            let var = self.builder.build_alloca(value_ptr_type, name);
-            self.builder.build_store(var, value);
+        let add_variable = |name: &str, value: Self::Value| {
+            let var = self.new_local_variable_with_value(value);
             self.add_variable(name, var);
         };
         add_variable(value::BACKTRACK_CACHE, backtrack_cache);
@@ -1004,15 +897,14 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
         add_variable(value::SCHEMA_ARGS, args);
         add_variable(value::SCHEMA_KWARGS, kwargs);
         add_variable(value::SCHEMA_RUNTIME_TYPE, self.string_value(&runtime_type));
-        self.walk_arguments(&rule_stmt.args, args, kwargs);
+        self.walk_arguments(&rule_stmt.args, args, kwargs)?;
         let schema = value::SchemaType::new(name, pkgpath, &runtime_type, false);
-        self.schema_stack.borrow_mut().push(schema);
+        self.schema_stack().borrow_mut().push(schema);
         add_variable(value::SCHEMA_SELF_NAME, schema_value);
         // construct for protocol
         let schema_value = if let Some(for_host_name) = &rule_stmt.for_host_name {
-            let base_constructor_func = self
-                .walk_identifier_with_ctx(&for_host_name.node, &ast::ExprContext::Load, None)
-                .expect(kcl_error::COMPILE_ERROR_MSG);
+            let base_constructor_func =
+                self.walk_identifier_with_ctx(&for_host_name.node, &ast::ExprContext::Load, None)?;
             // Schema function closures
             let list_value = self.list_values(&[
                 // is_sub_schema
@@ -1032,22 +924,10 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
                 &ApiFunc::kclvm_value_function_ptr.name(),
                 &[base_constructor_func],
             );
-            let fn_ty = self.function_type().ptr_type(AddressSpace::default());
-            let func_ptr_cast = self.builder.build_bitcast(func_ptr, fn_ty, "");
-            self.builder
-                .build_call(
-                    CallableValue::try_from(func_ptr_cast.into_pointer_value())
-                        .expect(kcl_error::INTERNAL_ERROR_MSG),
-                    &[
-                        self.global_ctx_ptr().into(),
-                        list_value.into(),
-                        dict_value.into(),
-                    ],
-                    "",
-                )
-                .try_as_basic_value()
-                .left()
-                .expect(kcl_error::FUNCTION_RETURN_VALUE_NOT_FOUND_MSG)
+            self.build_function_ptr_call(
+                func_ptr,
+                &[self.current_runtime_ctx_ptr(), list_value, dict_value],
+            )
         } else {
             schema_value
         };
@@ -1055,19 +935,18 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
         let do_run_block = self.append_block("");
         let end_run_block = self.append_block("");
         self.cond_br(do_run_i1, do_run_block, end_run_block);
-        self.builder.position_at_end(do_run_block);
+        self.switch_to_block(do_run_block);
         // Rule decorators check
         for decorator in &rule_stmt.decorators {
-            self.walk_decorator_with_name(&decorator.node, Some(name), true)
-                .expect(kcl_error::INTERNAL_ERROR_MSG);
+            self.walk_decorator_with_name(&decorator.node, Some(name), true)?;
         }
         self.br(end_run_block);
-        self.builder.position_at_end(end_run_block);
+        self.switch_to_block(end_run_block);
         let is_sub_schema_i1 = self.value_is_truthy(is_sub_schema);
         let do_check_block = self.append_block("");
         let end_check_block = self.append_block("");
         self.cond_br(is_sub_schema_i1, do_check_block, end_check_block);
-        self.builder.position_at_end(do_check_block);
+        self.switch_to_block(do_check_block);
         {
             // Schema check function closure
             let list_value = self.list_values(&[
@@ -1080,30 +959,21 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
             ]);
             let dict_value = self.dict_value();
             // Call schema check block function
-            self.builder.build_call(
+            self.build_call_function(
                 check_function,
-                &[
-                    self.global_ctx_ptr().into(),
-                    list_value.into(),
-                    dict_value.into(),
-                ],
-                "",
+                &[self.current_runtime_ctx_ptr(), list_value, dict_value],
             );
         }
         self.br(end_check_block);
-        self.builder.position_at_end(end_check_block);
+        self.switch_to_block(end_check_block);
         // Rule constructor function returns a rule
-        self.builder.build_return(Some(&schema_value));
-        // Exist the function
-        self.builder.position_at_end(func_before_block);
+        self.ret(schema_value);
         // Build rule check function
         {
             self.push_function(check_function);
-            let check_block = self.append_block(ENTRY_NAME);
-            self.builder.position_at_end(check_block);
-            let args = function
-                .get_nth_param(1)
-                .expect(kcl_error::INTERNAL_ERROR_MSG);
+            self.enter_scope();
+            let args = self.function_param(1);
+            let kwargs = self.function_param(2);
             // Schema check function closure
             let backtrack_cache = self.list_pop(args);
             let backtrack_level_map = self.list_pop(args);
@@ -1121,23 +991,23 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
             add_variable(value::SCHEMA_KWARGS, kwargs);
             add_variable(value::SCHEMA_RUNTIME_TYPE, self.string_value(&runtime_type));
             let schema = self
-                .schema_stack
+                .schema_stack()
                 .borrow_mut()
                 .pop()
                 .expect(kcl_error::INTERNAL_ERROR_MSG);
-            self.walk_arguments(&rule_stmt.args, args, kwargs);
-            self.schema_stack.borrow_mut().push(schema);
+            self.walk_arguments(&rule_stmt.args, args, kwargs)?;
+            self.schema_stack().borrow_mut().push(schema);
             // Call base check function
             for parent_name in &rule_stmt.parent_rules {
-                let base_constructor_func = self
-                    .walk_identifier_with_ctx(&parent_name.node, &ast::ExprContext::Load, None)
-                    .expect(kcl_error::COMPILE_ERROR_MSG);
+                let base_constructor_func = self.walk_identifier_with_ctx(
+                    &parent_name.node,
+                    &ast::ExprContext::Load,
+                    None,
+                )?;
                 let func_ptr = self.build_call(
                     &ApiFunc::kclvm_value_check_function_ptr.name(),
                     &[base_constructor_func],
                 );
-                let fn_ty = self.function_type().ptr_type(AddressSpace::default());
-                let func_ptr_cast = self.builder.build_bitcast(func_ptr, fn_ty, "");
                 // Schema check function closure
                 let list_value = self.list_values(&[
                     schema_config_meta,
@@ -1148,35 +1018,28 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
                     backtrack_cache,
                 ]);
                 let dict_value = self.dict_value();
-                self.builder.build_call(
-                    CallableValue::try_from(func_ptr_cast.into_pointer_value())
-                        .expect(kcl_error::INTERNAL_ERROR_MSG),
-                    &[
-                        self.global_ctx_ptr().into(),
-                        list_value.into(),
-                        dict_value.into(),
-                    ],
-                    "",
+                self.build_function_ptr_call(
+                    func_ptr,
+                    &[self.current_runtime_ctx_ptr(), list_value, dict_value],
                 );
             }
             // Call self rule check expressions
             for check_expr in &rule_stmt.checks {
-                self.walk_check_expr(&check_expr.node)
-                    .expect(kcl_error::COMPILE_ERROR_MSG);
+                self.walk_check_expr(&check_expr.node)?;
             }
-            self.builder.build_return(Some(&schema_value));
-            self.builder.position_at_end(func_before_block);
-            self.pop_function();
+            self.ret(schema_value);
+            self.leave_scope();
+            self.pop_function()?;
         }
-        let function = self.struct_function_value(&[function, check_function], &runtime_type);
         self.leave_scope();
-        self.pop_function();
-        self.schema_stack.borrow_mut().pop();
+        self.pop_function()?;
+        let function = self.struct_function_value(&[function, check_function], &runtime_type);
+        self.schema_stack().borrow_mut().pop();
         // Store or add the variable in the scope
         if !self.store_variable(name, function) {
-            let global_var_ptr = self.new_global_kcl_value_ptr(&runtime_type);
-            self.builder.build_store(global_var_ptr, function);
-            self.add_variable(name, global_var_ptr);
+            let global_var = self.new_global_kcl_value_ptr(&runtime_type);
+            self.store_to_variable(global_var, function);
+            self.add_variable(name, global_var);
         }
         Ok(function)
     }
@@ -1185,10 +1048,11 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
      * Expr
      */
 
-    fn walk_expr(&self, expr: &'ctx ast::Node<ast::Expr>) -> Self::Result {
+    fn walk_expr(&self, expr: &'ctx ast::Node<ast::Expr>) -> CompileResult<Self::Value> {
         check_backtrack_stop!(self);
         utils::update_ctx_filename(self, expr);
         utils::update_ctx_line_col(self, expr);
+        self.debug_set_location(expr);
         match &expr.node {
             ast::Expr::Identifier(identifier) => self.walk_identifier(identifier),
             ast::Expr::Unary(unary_expr) => self.walk_unary_expr(unary_expr),
@@ -1230,16 +1094,15 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
         }
     }
 
-    fn walk_quant_expr(&self, quant_expr: &'ctx ast::QuantExpr) -> Self::Result {
+    fn walk_quant_expr(&self, quant_expr: &'ctx ast::QuantExpr) -> CompileResult<Self::Value> {
         check_backtrack_stop!(self);
         let result = match quant_expr.op {
             ast::QuantOperation::All => self.bool_value(true),
             ast::QuantOperation::Any => self.bool_value(false),
             ast::QuantOperation::Map => self.list_value(),
-            ast::QuantOperation::Filter => self.value_deep_copy(
-                self.walk_expr(&quant_expr.target)
-                    .expect(kcl_error::COMPILE_ERROR_MSG),
-            ),
+            ast::QuantOperation::Filter => {
+                self.value_deep_copy(self.walk_expr(&quant_expr.target)?)
+            }
         };
         // Blocks
         let start_block = self.append_block("");
@@ -1253,22 +1116,16 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
         let iter_host_value = if let ast::QuantOperation::Filter = quant_expr.op {
             self.value_deep_copy(result)
         } else {
-            self.walk_expr(&quant_expr.target)
-                .expect(kcl_error::COMPILE_ERROR_MSG)
+            self.walk_expr(&quant_expr.target)?
         };
         let iter_value = self.build_call(&ApiFunc::kclvm_value_iter.name(), &[iter_host_value]);
         self.br(start_block);
-        self.builder.position_at_end(start_block);
+        self.switch_to_block(start_block);
         self.enter_scope();
-        let is_end = self
-            .build_call(&ApiFunc::kclvm_iterator_is_end.name(), &[iter_value])
-            .into_int_value();
-        let is_end =
-            self.builder
-                .build_int_compare(IntPredicate::NE, is_end, self.native_i8_zero(), "");
-        self.builder
-            .build_conditional_branch(is_end, end_for_block, next_value_block);
-        self.builder.position_at_end(next_value_block);
+        let is_end = self.build_call(&ApiFunc::kclvm_iterator_is_end.name(), &[iter_value]);
+        let is_end = self.icmp(IntPredicate::NotEqual, is_end, self.native_i8_zero());
+        self.cond_br(is_end, end_for_block, next_value_block);
+        self.switch_to_block(next_value_block);
         let next_value = self.build_call(
             &ApiFunc::kclvm_iterator_next_value.name(),
             &[iter_value, iter_host_value],
@@ -1276,7 +1133,7 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
         let key = self.build_call(&ApiFunc::kclvm_iterator_cur_key.name(), &[iter_value]);
         let variables = &quant_expr.variables;
         {
-            let mut local_vars = self.local_vars.borrow_mut();
+            let mut local_vars = self.local_vars().borrow_mut();
             for v in variables {
                 let name = &v.node.names[0];
                 local_vars.insert(name.clone());
@@ -1285,43 +1142,36 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
         if variables.len() == 1 {
             // Store the target
             self.walk_identifier_with_ctx(
-                &variables.get(0).expect(kcl_error::INTERNAL_ERROR_MSG).node,
+                &variables[0].node,
                 &ast::ExprContext::Store,
                 Some(next_value),
-            )
-            .expect(kcl_error::COMPILE_ERROR_MSG);
+            )?;
         } else if variables.len() == 2 {
             let value = self.build_call(&ApiFunc::kclvm_iterator_cur_value.name(), &[iter_value]);
             // Store the target
+            self.walk_identifier_with_ctx(&variables[0].node, &ast::ExprContext::Store, Some(key))?;
             self.walk_identifier_with_ctx(
-                &variables.get(0).expect(kcl_error::INTERNAL_ERROR_MSG).node,
-                &ast::ExprContext::Store,
-                Some(key),
-            )
-            .expect(kcl_error::COMPILE_ERROR_MSG);
-            self.walk_identifier_with_ctx(
-                &variables.get(1).expect(kcl_error::INTERNAL_ERROR_MSG).node,
+                &variables[1].node,
                 &ast::ExprContext::Store,
                 Some(value),
-            )
-            .expect(kcl_error::COMPILE_ERROR_MSG);
+            )?;
         } else {
-            panic!(
+            return Err(kcl_error::KCLError::new(&format!(
                 "the number of loop variables is {}, which can only be 1 or 2",
                 variables.len()
-            )
+            )));
         }
         if let Some(if_expr) = &quant_expr.if_cond {
-            let if_truth = self.walk_expr(if_expr).expect(kcl_error::COMPILE_ERROR_MSG);
+            let if_truth = self.walk_expr(if_expr)?;
             let is_truth = self.value_is_truthy(if_truth);
             self.cond_br(is_truth, continue_block, start_block);
         } else {
             self.br(continue_block);
         }
-        self.builder.position_at_end(continue_block);
+        self.switch_to_block(continue_block);
         // Body block
         let test = &quant_expr.test;
-        let value = self.walk_expr(test).expect(kcl_error::COMPILE_ERROR_MSG);
+        let value = self.walk_expr(test)?;
         let is_truth = self.value_is_truthy(value);
         match quant_expr.op {
             ast::QuantOperation::All => {
@@ -1333,7 +1183,7 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
             ast::QuantOperation::Filter => {
                 let then_block = self.append_block("");
                 self.cond_br(is_truth, start_block, then_block);
-                self.builder.position_at_end(then_block);
+                self.switch_to_block(then_block);
                 self.build_void_call(
                     &ApiFunc::kclvm_value_remove_item.name(),
                     &[result, next_value],
@@ -1345,54 +1195,39 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
                 self.br(start_block);
             }
         }
-        self.builder.position_at_end(all_break_block);
+        let phi = self.append_block_param(result_block);
+        self.switch_to_block(all_break_block);
         let all_false_value = self.bool_value(false);
-        self.br(result_block);
-        self.builder.position_at_end(any_break_block);
+        self.br_with_args(result_block, &[all_false_value]);
+        self.switch_to_block(any_break_block);
         let any_true_value = self.bool_value(true);
-        self.br(result_block);
-        self.builder.position_at_end(end_for_block);
-        let tpe = self.value_ptr_type();
-        let ptr = self.builder.build_alloca(tpe, "");
-        self.builder.build_store(ptr, result);
-        let value = self.builder.build_load(ptr, "");
-        self.br(result_block);
-        self.builder.position_at_end(result_block);
-        let phi = self.builder.build_phi(tpe, "");
-        phi.add_incoming(&[
-            (&all_false_value, all_break_block),
-            (&any_true_value, any_break_block),
-            (&value, end_for_block),
-        ]);
+        self.br_with_args(result_block, &[any_true_value]);
+        self.switch_to_block(end_for_block);
+        self.br_with_args(result_block, &[result]);
+        self.switch_to_block(result_block);
         self.leave_scope();
-        self.local_vars.borrow_mut().clear();
+        self.local_vars().borrow_mut().clear();
         self.build_void_call(&ApiFunc::kclvm_iterator_delete.name(), &[iter_value]);
-        Ok(phi.as_basic_value())
+        Ok(phi)
     }
 
-    fn walk_schema_attr(&self, schema_attr: &'ctx ast::SchemaAttr) -> Self::Result {
+    fn walk_schema_attr(&self, schema_attr: &'ctx ast::SchemaAttr) -> CompileResult<Self::Value> {
         check_backtrack_stop!(self);
-        self.local_vars.borrow_mut().clear();
+        self.local_vars().borrow_mut().clear();
         let name = schema_attr.name.node.as_str();
-        self.target_vars.borrow_mut().push(name.to_string());
+        self.target_vars().borrow_mut().push(name.to_string());
         for decorator in &schema_attr.decorators {
-            self.walk_decorator_with_name(&decorator.node, Some(name), false)
-                .expect(kcl_error::COMPILE_ERROR_MSG);
+            self.walk_decorator_with_name(&decorator.node, Some(name), false)?;
         }
         let value = match &schema_attr.value {
-            Some(value) => self.walk_expr(value).expect(kcl_error::COMPILE_ERROR_MSG),
+            Some(value) => self.walk_expr(value)?,
             None => self.undefined_value(),
         };
-        let config_value = self
-            .get_variable(value::SCHEMA_CONFIG_NAME)
-            .expect(kcl_error::INTERNAL_ERROR_MSG);
-        let schema_value = self
-            .get_variable(value::SCHEMA_SELF_NAME)
-            .expect(kcl_error::INTERNAL_ERROR_MSG);
-        let string_ptr_value = self.native_global_string(name, "").into();
-        let type_str_ptr_value = self
-            .native_global_string(&schema_attr.type_str.node, "")
-            .into();
+        self.debug_declare_variable(name, value);
+        let config_value = self.get_variable(value::SCHEMA_CONFIG_NAME)?;
+        let schema_value = self.get_variable(value::SCHEMA_SELF_NAME)?;
+        let string_ptr_value = self.native_global_string(name, "");
+        let type_str_ptr_value = self.native_global_string(&schema_attr.type_str.node, "");
         self.build_void_call(
             &ApiFunc::kclvm_config_attr_map.name(),
             &[schema_value, string_ptr_value, type_str_ptr_value],
@@ -1403,47 +1238,48 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
                 ast::BinOrAugOp::Aug(ast::AugOp::BitOr) => {
                     let org_value = self.build_call(
                         &ApiFunc::kclvm_dict_get_value.name(),
-                        &[schema_value, string_ptr_value],
+                        &[
+                            self.current_runtime_ctx_ptr(),
+                            schema_value,
+                            string_ptr_value,
+                        ],
                     );
                     let fn_name = ApiFunc::kclvm_value_op_bit_or;
-                    let value = self.build_call(&fn_name.name(), &[org_value, value]);
+                    let value = self.build_call(
+                        &fn_name.name(),
+                        &[self.current_runtime_ctx_ptr(), org_value, value],
+                    );
                     self.dict_merge(schema_value, name, value, 1, -1);
                 }
                 // Assign
                 _ => self.dict_merge(schema_value, name, value, 1, -1),
             }
         }
-        let has_key = self
-            .build_call(
-                &ApiFunc::kclvm_dict_has_value.name(),
-                &[config_value, string_ptr_value],
-            )
-            .into_int_value();
-        let has_key =
-            self.builder
-                .build_int_compare(IntPredicate::NE, has_key, self.native_i8_zero(), "");
+        let has_key = self.build_call(
+            &ApiFunc::kclvm_dict_has_value.name(),
+            &[config_value, string_ptr_value],
+        );
+        let has_key = self.icmp(IntPredicate::NotEqual, has_key, self.native_i8_zero());
         let then_block = self.append_block("");
         let else_block = self.append_block("");
-        self.builder
-            .build_conditional_branch(has_key, then_block, else_block);
-        self.builder.position_at_end(then_block);
+        self.cond_br(has_key, then_block, else_block);
+        self.switch_to_block(then_block);
         let config_attr_value = self.build_call(
             &ApiFunc::kclvm_dict_get_entry.name(),
-            &[config_value, string_ptr_value],
+            &[
+                self.current_runtime_ctx_ptr(),
+                config_value,
+                string_ptr_value,
+            ],
         );
         self.value_union(schema_value, config_attr_value);
-        let cal_map = self
-            .get_variable(value::SCHEMA_CAL_MAP)
-            .expect(kcl_error::INTERNAL_ERROR_MSG);
-        let backtrack_cache = self
-            .get_variable(value::BACKTRACK_CACHE)
-            .expect(kcl_error::INTERNAL_ERROR_MSG);
-        let runtime_type = self
-            .get_variable(value::SCHEMA_RUNTIME_TYPE)
-            .expect(kcl_error::INTERNAL_ERROR_MSG);
+        let cal_map = self.get_variable(value::SCHEMA_CAL_MAP)?;
+        let backtrack_cache = self.get_variable(value::BACKTRACK_CACHE)?;
+        let runtime_type = self.get_variable(value::SCHEMA_RUNTIME_TYPE)?;
         self.build_void_call(
             &ApiFunc::kclvm_schema_backtrack_cache.name(),
             &[
+                self.current_runtime_ctx_ptr(),
                 schema_value,
                 backtrack_cache,
                 cal_map,
@@ -1453,7 +1289,7 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
         );
         // Update backtrack meta
         {
-            if let Some(backtrack_meta) = self.backtrack_meta.borrow_mut().as_mut() {
+            if let Some(backtrack_meta) = self.backtrack_meta().borrow_mut().as_mut() {
                 if name == backtrack_meta.target {
                     backtrack_meta.count += 1;
                     if backtrack_meta.count >= backtrack_meta.level {
@@ -1465,64 +1301,41 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
             }
         }
         self.br(else_block);
-        self.builder.position_at_end(else_block);
+        self.switch_to_block(else_block);
         Ok(schema_value)
     }
 
-    fn walk_if_expr(&self, if_expr: &'ctx ast::IfExpr) -> Self::Result {
+    fn walk_if_expr(&self, if_expr: &'ctx ast::IfExpr) -> CompileResult<Self::Value> {
         check_backtrack_stop!(self);
-        let cond = self
-            .walk_expr(&if_expr.cond)
-            .expect(kcl_error::COMPILE_ERROR_MSG);
+        let cond = self.walk_expr(&if_expr.cond)?;
         let then_block = self.append_block("");
         let else_block = self.append_block("");
         let end_block = self.append_block("");
         let is_truth = self.value_is_truthy(cond);
-        let tpe = self.value_ptr_type();
         self.cond_br(is_truth, then_block, else_block);
-        self.builder.position_at_end(then_block);
-        let then_value = self
-            .walk_expr(&if_expr.body)
-            .expect(kcl_error::COMPILE_ERROR_MSG);
-        let then_block = self.append_block("");
-        self.br(then_block);
-        self.builder.position_at_end(then_block);
-        let ptr = self.builder.build_alloca(tpe, "");
-        self.builder.build_store(ptr, then_value);
-        let then_value = self.builder.build_load(ptr, "");
-        self.br(end_block);
-        self.builder.position_at_end(else_block);
-        let else_value = self
-            .walk_expr(&if_expr.orelse)
-            .expect(kcl_error::COMPILE_ERROR_MSG);
-        let else_block = self.append_block("");
-        self.br(else_block);
-        self.builder.position_at_end(else_block);
-        let ptr = self.alloca(tpe, "", None).into_pointer_value();
-        self.builder.build_store(ptr, else_value);
-        let else_value = self.builder.build_load(ptr, "");
-        self.br(end_block);
-        self.builder.position_at_end(end_block);
-        let phi = self.builder.build_phi(tpe, "");
-        phi.add_incoming(&[(&then_value, then_block), (&else_value, else_block)]);
-        Ok(phi.as_basic_value())
+        self.switch_to_block(then_block);
+        let then_value = self.walk_expr(&if_expr.body)?;
+        self.br_with_args(end_block, &[then_value]);
+        self.switch_to_block(else_block);
+        let else_value = self.walk_expr(&if_expr.orelse)?;
+        self.br_with_args(end_block, &[else_value]);
+        self.switch_to_block(end_block);
+        Ok(self.append_block_param(end_block))
     }
 
-    fn walk_unary_expr(&self, unary_expr: &'ctx ast::UnaryExpr) -> Self::Result {
+    fn walk_unary_expr(&self, unary_expr: &'ctx ast::UnaryExpr) -> CompileResult<Self::Value> {
         check_backtrack_stop!(self);
-        let value = self
-            .walk_expr(&unary_expr.operand)
-            .expect(kcl_error::COMPILE_ERROR_MSG);
+        let value = self.walk_expr(&unary_expr.operand)?;
         let fn_name = match unary_expr.op {
             ast::UnaryOp::UAdd => ApiFunc::kclvm_value_unary_plus,
             ast::UnaryOp::USub => ApiFunc::kclvm_value_unary_minus,
             ast::UnaryOp::Invert => ApiFunc::kclvm_value_unary_not,
             ast::UnaryOp::Not => ApiFunc::kclvm_value_unary_l_not,
         };
-        Ok(self.build_call(&fn_name.name(), &[value]))
+        Ok(self.build_call(&fn_name.name(), &[self.current_runtime_ctx_ptr(), value]))
     }
 
-    fn walk_binary_expr(&self, binary_expr: &'ctx ast::BinaryExpr) -> Self::Result {
+    fn walk_binary_expr(&self, binary_expr: &'ctx ast::BinaryExpr) -> CompileResult<Self::Value> {
         check_backtrack_stop!(self);
         let is_logic_op = matches!(
             binary_expr.op,
@@ -1530,9 +1343,7 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
         );
         let is_membership_as_op = matches!(binary_expr.op, ast::BinOrCmpOp::Bin(ast::BinOp::As));
         if !is_logic_op {
-            let left_value = self
-                .walk_expr(&binary_expr.left)
-                .expect(kcl_error::COMPILE_ERROR_MSG);
+            let left_value = self.walk_expr(&binary_expr.left)?;
             let right_value = if is_membership_as_op {
                 match &binary_expr.right.node {
                     ast::Expr::Identifier(id) => {
@@ -1542,8 +1353,7 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
                     _ => self.none_value(),
                 }
             } else {
-                self.walk_expr(&binary_expr.right)
-                    .expect(kcl_error::COMPILE_ERROR_MSG)
+                self.walk_expr(&binary_expr.right)?
             };
             let value = match binary_expr.op {
                 ast::BinOrCmpOp::Bin(ast::BinOp::Add) => self.add(left_value, right_value),
@@ -1593,71 +1403,62 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
             let start_block = self.append_block("");
             let value_block = self.append_block("");
             let end_block = self.append_block("");
-            let left_value = self
-                .walk_expr(&binary_expr.left)
-                .expect(kcl_error::COMPILE_ERROR_MSG);
+            let left_value = self.walk_expr(&binary_expr.left)?;
             self.br(start_block);
-            self.builder.position_at_end(start_block);
+            self.switch_to_block(start_block);
             let is_truth = self.value_is_truthy(left_value);
-            let tpe = self.value_ptr_type();
+            let left_block = self.append_block("");
             if jump_if_false {
                 // Jump if false on logic and
-                self.cond_br(is_truth, value_block, end_block);
+                self.cond_br(is_truth, value_block, left_block);
             } else {
                 // Jump if true on logic or
-                self.cond_br(is_truth, end_block, value_block);
+                self.cond_br(is_truth, left_block, value_block);
             };
-            self.builder.position_at_end(value_block);
-            let right_value = self
-                .walk_expr(&binary_expr.right)
-                .expect(kcl_error::COMPILE_ERROR_MSG);
-            let value_block = self.append_block("");
-            self.br(value_block);
-            self.builder.position_at_end(value_block);
-            let ptr = self.builder.build_alloca(tpe, "");
-            self.builder.build_store(ptr, right_value);
-            let right_value = self.builder.build_load(ptr, "");
-            self.br(end_block);
-            self.builder.position_at_end(end_block);
-            let phi = self.builder.build_phi(tpe, "");
-            phi.add_incoming(&[(&left_value, start_block), (&right_value, value_block)]);
-            Ok(phi.as_basic_value())
+            self.switch_to_block(left_block);
+            self.br_with_args(end_block, &[left_value]);
+            self.switch_to_block(value_block);
+            let right_value = self.walk_expr(&binary_expr.right)?;
+            self.br_with_args(end_block, &[right_value]);
+            self.switch_to_block(end_block);
+            Ok(self.append_block_param(end_block))
         }
     }
 
-    fn walk_selector_expr(&self, selector_expr: &'ctx ast::SelectorExpr) -> Self::Result {
+    fn walk_selector_expr(
+        &self,
+        selector_expr: &'ctx ast::SelectorExpr,
+    ) -> CompileResult<Self::Value> {
         check_backtrack_stop!(self);
-        let mut value = self
-            .walk_expr(&selector_expr.value)
-            .expect(kcl_error::COMPILE_ERROR_MSG);
-        let string_ptr_value = self
-            .native_global_string(selector_expr.attr.node.names[0].as_str(), "")
-            .into();
+        let mut value = self.walk_expr(&selector_expr.value)?;
+        let string_ptr_value =
+            self.native_global_string(selector_expr.attr.node.names[0].as_str(), "");
         let fn_name = if selector_expr.has_question {
             &ApiFunc::kclvm_value_load_attr_option
         } else {
             &ApiFunc::kclvm_value_load_attr
         };
-        value = self.build_call(&fn_name.name(), &[value, string_ptr_value]);
+        value = self.build_call(
+            &fn_name.name(),
+            &[self.current_runtime_ctx_ptr(), value, string_ptr_value],
+        );
         for name in &selector_expr.attr.node.names[1..] {
-            let string_ptr_value = self.native_global_string(name, "").into();
+            let string_ptr_value = self.native_global_string(name, "");
             value = self.build_call(
                 &ApiFunc::kclvm_value_load_attr.name(),
-                &[value, string_ptr_value],
+                &[self.current_runtime_ctx_ptr(), value, string_ptr_value],
             );
         }
         Ok(value)
     }
 
-    fn walk_call_expr(&self, call_expr: &'ctx ast::CallExpr) -> Self::Result {
+    fn walk_call_expr(&self, call_expr: &'ctx ast::CallExpr) -> CompileResult<Self::Value> {
         check_backtrack_stop!(self);
-        let func = self
-            .walk_expr(&call_expr.func)
-            .expect(kcl_error::COMPILE_ERROR_MSG);
+        let func = self.walk_expr(&call_expr.func)?;
         // args
         let list_value = self.list_value();
         for arg in &call_expr.args {
-            let value = self.walk_expr(arg).expect(kcl_error::COMPILE_ERROR_MSG);
+            let value = self.walk_expr(arg)?;
             self.list_append(list_value, value);
         }
         let dict_value = self.dict_value();
@@ -1665,20 +1466,20 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
         for keyword in &call_expr.keywords {
             let name = &keyword.node.arg.node.names[0];
             let value = if let Some(value) = &keyword.node.value {
-                self.walk_expr(value).expect(kcl_error::COMPILE_ERROR_MSG)
+                self.walk_expr(value)?
             } else {
                 self.none_value()
             };
             self.dict_insert(dict_value, name.as_str(), value, 0, -1);
         }
         let pkgpath = self.native_global_string_value(&self.current_pkgpath());
-        let is_in_schema =
-            self.schema_stack.borrow().len() > 0 || self.schema_expr_stack.borrow().len() > 0;
+        let is_in_schema = !self.schema_stack().borrow().is_empty()
+            || !self.schema_expr_stack().borrow().is_empty();
         Ok(self.build_call(
             &ApiFunc::kclvm_value_function_invoke.name(),
             &[
                 func,
-                self.global_ctx_ptr(),
+                self.current_runtime_ctx_ptr(),
                 list_value,
                 dict_value,
                 pkgpath,
@@ -1687,38 +1488,39 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
         ))
     }
 
-    fn walk_subscript(&self, subscript: &'ctx ast::Subscript) -> Self::Result {
+    fn walk_subscript(&self, subscript: &'ctx ast::Subscript) -> CompileResult<Self::Value> {
         check_backtrack_stop!(self);
-        let mut value = self
-            .walk_expr(&subscript.value)
-            .expect(kcl_error::COMPILE_ERROR_MSG);
+        let mut value = self.walk_expr(&subscript.value)?;
         if let Some(index) = &subscript.index {
             // index
-            let index = self.walk_expr(index).expect(kcl_error::COMPILE_ERROR_MSG);
+            let index = self.walk_expr(index)?;
             let fn_name = if subscript.has_question {
                 &ApiFunc::kclvm_value_subscr_option
             } else {
                 &ApiFunc::kclvm_value_subscr
             };
-            value = self.build_call(&fn_name.name(), &[value, index]);
+            value = self.build_call(
+                &fn_name.name(),
+                &[self.current_runtime_ctx_ptr(), value, index],
+            );
         } else {
             let lower = {
                 if let Some(lower) = &subscript.lower {
-                    self.walk_expr(lower).expect(kcl_error::COMPILE_ERROR_MSG)
+                    self.walk_expr(lower)?
                 } else {
                     self.none_value()
                 }
             };
             let upper = {
                 if let Some(upper) = &subscript.upper {
-                    self.walk_expr(upper).expect(kcl_error::COMPILE_ERROR_MSG)
+                    self.walk_expr(upper)?
                 } else {
                     self.none_value()
                 }
             };
             let step = {
                 if let Some(step) = &subscript.step {
-                    self.walk_expr(step).expect(kcl_error::COMPILE_ERROR_MSG)
+                    self.walk_expr(step)?
                 } else {
                     self.none_value()
                 }
@@ -1728,21 +1530,24 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
             } else {
                 &ApiFunc::kclvm_value_slice
             };
-            value = self.build_call(&fn_name.name(), &[value, lower, upper, step]);
+            value = self.build_call(
+                &fn_name.name(),
+                &[self.current_runtime_ctx_ptr(), value, lower, upper, step],
+            );
         }
         Ok(value)
     }
 
-    fn walk_paren_expr(&self, paren_expr: &'ctx ast::ParenExpr) -> Self::Result {
+    fn walk_paren_expr(&self, paren_expr: &'ctx ast::ParenExpr) -> CompileResult<Self::Value> {
         check_backtrack_stop!(self);
         self.walk_expr(&paren_expr.expr)
     }
 
-    fn walk_list_expr(&self, list_expr: &'ctx ast::ListExpr) -> Self::Result {
+    fn walk_list_expr(&self, list_expr: &'ctx ast::ListExpr) -> CompileResult<Self::Value> {
         check_backtrack_stop!(self);
         let list_value = self.list_value();
         for item in &list_expr.elts {
-            let value = self.walk_expr(item).expect(kcl_error::COMPILE_ERROR_MSG);
+            let value = self.walk_expr(item)?;
             let fn_name = match &item.node {
                 ast::Expr::Starred(_) | ast::Expr::ListIfItem(_) => {
                     ApiFunc::kclvm_list_append_unpack
@@ -1754,21 +1559,21 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
         Ok(list_value)
     }
 
-    fn walk_list_if_item_expr(&self, list_if_item_expr: &'ctx ast::ListIfItemExpr) -> Self::Result {
+    fn walk_list_if_item_expr(
+        &self,
+        list_if_item_expr: &'ctx ast::ListIfItemExpr,
+    ) -> CompileResult<Self::Value> {
         check_backtrack_stop!(self);
-        let cond = self
-            .walk_expr(&list_if_item_expr.if_cond)
-            .expect(kcl_error::COMPILE_ERROR_MSG);
+        let cond = self.walk_expr(&list_if_item_expr.if_cond)?;
         let then_block = self.append_block("");
         let else_block = self.append_block("");
         let end_block = self.append_block("");
         let is_truth = self.value_is_truthy(cond);
-        let tpe = self.value_ptr_type();
         self.cond_br(is_truth, then_block, else_block);
-        self.builder.position_at_end(then_block);
+        self.switch_to_block(then_block);
         let then_value = self.list_value();
         for expr in &list_if_item_expr.exprs {
-            let value = self.walk_expr(expr).expect(kcl_error::COMPILE_ERROR_MSG);
+            let value = self.walk_expr(expr)?;
             match &expr.node {
                 ast::Expr::Starred(_) | ast::Expr::ListIfItem(_) => {
                     self.list_append_unpack(then_value, value)
@@ -1776,38 +1581,27 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
                 _ => self.list_append(then_value, value),
             };
         }
-        let then_block = self.append_block("");
-        self.br(then_block);
-        self.builder.position_at_end(then_block);
-        let ptr = self.builder.build_alloca(tpe, "");
-        self.builder.build_store(ptr, then_value);
-        let then_value = self.builder.build_load(ptr, "");
-        self.br(end_block);
-        self.builder.position_at_end(else_block);
+        self.br_with_args(end_block, &[then_value]);
+        self.switch_to_block(else_block);
         let else_value = if let Some(orelse) = &list_if_item_expr.orelse {
-            self.walk_expr(orelse).expect(kcl_error::COMPILE_ERROR_MSG)
+            self.walk_expr(orelse)?
         } else {
             self.none_value()
         };
-        let else_block = self.append_block("");
-        self.br(else_block);
-        self.builder.position_at_end(else_block);
-        let ptr = self.builder.build_alloca(tpe, "");
-        self.builder.build_store(ptr, else_value);
-        let else_value = self.builder.build_load(ptr, "");
-        self.br(end_block);
-        self.builder.position_at_end(end_block);
-        let phi = self.builder.build_phi(tpe, "");
-        phi.add_incoming(&[(&then_value, then_block), (&else_value, else_block)]);
-        Ok(phi.as_basic_value())
+        self.br_with_args(end_block, &[else_value]);
+        self.switch_to_block(end_block);
+        Ok(self.append_block_param(end_block))
     }
 
-    fn walk_starred_expr(&self, starred_expr: &'ctx ast::StarredExpr) -> Self::Result {
+    fn walk_starred_expr(
+        &self,
+        starred_expr: &'ctx ast::StarredExpr,
+    ) -> CompileResult<Self::Value> {
         check_backtrack_stop!(self);
         self.walk_expr(&starred_expr.value)
     }
 
-    fn walk_list_comp(&self, list_comp: &'ctx ast::ListComp) -> Self::Result {
+    fn walk_list_comp(&self, list_comp: &'ctx ast::ListComp) -> CompileResult<Self::Value> {
         check_backtrack_stop!(self);
         let collection_value = self.list_value();
         self.enter_scope();
@@ -1819,16 +1613,12 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
             0,
             collection_value,
             ast::CompType::List,
-        );
+        )?;
         self.leave_scope();
-        let tpe = self.value_ptr_type();
-        let ptr = self.builder.build_alloca(tpe, "");
-        self.builder.build_store(ptr, collection_value);
-        let value = self.builder.build_load(ptr, "");
-        Ok(value)
+        Ok(collection_value)
     }
 
-    fn walk_dict_comp(&self, dict_comp: &'ctx ast::DictComp) -> Self::Result {
+    fn walk_dict_comp(&self, dict_comp: &'ctx ast::DictComp) -> CompileResult<Self::Value> {
         check_backtrack_stop!(self);
         let collection_value = self.dict_value();
         self.enter_scope();
@@ -1845,7 +1635,7 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
             0,
             collection_value,
             ast::CompType::Dict,
-        );
+        )?;
         self.leave_scope();
         Ok(collection_value)
     }
@@ -1853,80 +1643,63 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
     fn walk_config_if_entry_expr(
         &self,
         config_if_entry_expr: &'ctx ast::ConfigIfEntryExpr,
-    ) -> Self::Result {
+    ) -> CompileResult<Self::Value> {
         check_backtrack_stop!(self);
-        let cond = self
-            .walk_expr(&config_if_entry_expr.if_cond)
-            .expect(kcl_error::COMPILE_ERROR_MSG);
+        let cond = self.walk_expr(&config_if_entry_expr.if_cond)?;
         let then_block = self.append_block("");
         let else_block = self.append_block("");
         let end_block = self.append_block("");
         let is_truth = self.value_is_truthy(cond);
-        let tpe = self.value_ptr_type();
         self.cond_br(is_truth, then_block, else_block);
-        self.builder.position_at_end(then_block);
+        self.switch_to_block(then_block);
         let then_value = self.walk_config_entries(&config_if_entry_expr.items)?;
-        let then_block = self.append_block("");
-        self.br(then_block);
-        self.builder.position_at_end(then_block);
-        let ptr = self.builder.build_alloca(tpe, "");
-        self.builder.build_store(ptr, then_value);
-        let then_value = self.builder.build_load(ptr, "");
-        self.br(end_block);
-        self.builder.position_at_end(else_block);
+        self.br_with_args(end_block, &[then_value]);
+        self.switch_to_block(else_block);
         let else_value = if let Some(orelse) = &config_if_entry_expr.orelse {
-            self.walk_expr(orelse).expect(kcl_error::COMPILE_ERROR_MSG)
+            self.walk_expr(orelse)?
         } else {
             self.none_value()
         };
-        let else_block = self.append_block("");
-        self.br(else_block);
-        self.builder.position_at_end(else_block);
-        let ptr = self.builder.build_alloca(tpe, "");
-        self.builder.build_store(ptr, else_value);
-        let else_value = self.builder.build_load(ptr, "");
-        self.br(end_block);
-        self.builder.position_at_end(end_block);
-        let phi = self.builder.build_phi(tpe, "");
-        phi.add_incoming(&[(&then_value, then_block), (&else_value, else_block)]);
-        Ok(phi.as_basic_value())
+        self.br_with_args(end_block, &[else_value]);
+        self.switch_to_block(end_block);
+        Ok(self.append_block_param(end_block))
     }
 
-    fn walk_comp_clause(&self, _comp_clause: &'ctx ast::CompClause) -> Self::Result {
+    fn walk_comp_clause(&self, _comp_clause: &'ctx ast::CompClause) -> CompileResult<Self::Value> {
         // Nothing to do on this AST node
         self.ok_result()
     }
 
-    fn walk_schema_expr(&self, schema_expr: &'ctx ast::SchemaExpr) -> Self::Result {
+    fn walk_schema_expr(&self, schema_expr: &'ctx ast::SchemaExpr) -> CompileResult<Self::Value> {
         check_backtrack_stop!(self);
         // Check the required attributes only when the values of all attributes
         // in the final schema are solved.
-        let is_in_schema =
-            self.schema_stack.borrow().len() > 0 || self.schema_expr_stack.borrow().len() > 0;
+        let is_in_schema = !self.schema_stack().borrow().is_empty()
+            || !self.schema_expr_stack().borrow().is_empty();
         {
-            self.schema_expr_stack.borrow_mut().push(());
-        }
-        let config_value = self
-            .walk_expr(&schema_expr.config)
-            .expect(kcl_error::COMPILE_ERROR_MSG);
-        let schema_type = self
-            .walk_identifier_with_ctx(&schema_expr.name.node, &schema_expr.name.node.ctx, None)
-            .expect(kcl_error::COMPILE_ERROR_MSG);
+            self.schema_expr_stack().borrow_mut().push(());
+        }
+        let config_value = self.walk_expr(&schema_expr.config)?;
+        let schema_type = self.walk_identifier_with_ctx(
+            &schema_expr.name.node,
+            &schema_expr.name.node.ctx,
+            None,
+        )?;
         let config_expr = match &schema_expr.config.node {
             ast::Expr::Config(config_expr) => config_expr,
-            _ => panic!("invalid schema config expr"),
+            _ => return Err(kcl_error::KCLError::new("invalid schema config expr")),
         };
         let config_meta = self.get_schema_config_meta(Some(&schema_expr.name), config_expr);
         let list_value = self.list_value();
         for arg in &schema_expr.args {
-            let value = self.walk_expr(arg).expect(kcl_error::COMPILE_ERROR_MSG);
+            let value = self.walk_expr(arg)?;
             self.list_append(list_value, value);
         }
         let dict_value = self.dict_value();
         for keyword in &schema_expr.kwargs {
             let name = &keyword.node.arg.node.names[0];
             let value = if let Some(value) = &keyword.node.value {
-                self.walk_expr(value).expect(kcl_error::COMPILE_ERROR_MSG)
+                self.walk_expr(value)?
             } else {
                 self.none_value()
             };
@@ -1936,7 +1709,7 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
         let schema = self.build_call(
             &ApiFunc::kclvm_schema_value_new.name(),
             &[
-                self.global_ctx_ptr(),
+                self.current_runtime_ctx_ptr(),
                 list_value,
                 dict_value,
                 schema_type,
@@ -1946,61 +1719,63 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
             ],
         );
         if !is_in_schema {
-            self.build_void_call(&ApiFunc::kclvm_schema_optional_check.name(), &[schema]);
+            self.build_void_call(
+                &ApiFunc::kclvm_schema_optional_check.name(),
+                &[self.current_runtime_ctx_ptr(), schema],
+            );
         }
         utils::update_ctx_filename(self, &schema_expr.config);
         {
-            self.schema_expr_stack.borrow_mut().pop();
+            self.schema_expr_stack().borrow_mut().pop();
         }
         Ok(schema)
     }
 
-    fn walk_config_expr(&self, config_expr: &'ctx ast::ConfigExpr) -> Self::Result {
+    fn walk_config_expr(&self, config_expr: &'ctx ast::ConfigExpr) -> CompileResult<Self::Value> {
         check_backtrack_stop!(self);
         self.walk_config_entries(&config_expr.items)
     }
 
-    fn walk_check_expr(&self, check_expr: &'ctx ast::CheckExpr) -> Self::Result {
+    fn walk_check_expr(&self, check_expr: &'ctx ast::CheckExpr) -> CompileResult<Self::Value> {
         check_backtrack_stop!(self);
         let start_block = self.append_block("");
         let end_block = self.append_block("");
         if let Some(if_cond) = &check_expr.if_cond {
-            let if_value = self.walk_expr(if_cond).expect(kcl_error::COMPILE_ERROR_MSG);
+            let if_value = self.walk_expr(if_cond)?;
             let is_truth = self.value_is_truthy(if_value);
             self.cond_br(is_truth, start_block, end_block);
         } else {
             self.br(start_block);
         }
-        self.builder.position_at_end(start_block);
-        let check_result = self
-            .walk_expr(&check_expr.test)
-            .expect(kcl_error::COMPILE_ERROR_MSG);
+        self.switch_to_block(start_block);
+        let check_result = self.walk_expr(&check_expr.test)?;
         let msg = {
             if let Some(msg) = &check_expr.msg {
-                self.walk_expr(msg).expect(kcl_error::COMPILE_ERROR_MSG)
+                self.walk_expr(msg)?
             } else {
                 self.string_value("")
             }
         };
-        let schema_config_meta = self
-            .get_variable(value::SCHEMA_CONFIG_META_NAME)
-            .expect(kcl_error::COMPILE_ERROR_MSG);
+        let schema_config_meta = self.get_variable(value::SCHEMA_CONFIG_META_NAME)?;
         utils::update_ctx_current_line(self);
         self.build_void_call(
             &ApiFunc::kclvm_schema_assert.name(),
-            &[check_result, msg, schema_config_meta],
+            &[
+                self.current_runtime_ctx_ptr(),
+                check_result,
+                msg,
+                schema_config_meta,
+            ],
         );
         self.br(end_block);
-        self.builder.position_at_end(end_block);
+        self.switch_to_block(end_block);
         self.ok_result()
     }
 
-    fn walk_lambda_expr(&self, lambda_expr: &'ctx ast::LambdaExpr) -> Self::Result {
+    fn walk_lambda_expr(&self, lambda_expr: &'ctx ast::LambdaExpr) -> CompileResult<Self::Value> {
         check_backtrack_stop!(self);
         let pkgpath = &self.current_pkgpath();
-        let is_in_schema = self.schema_stack.borrow().len() > 0;
-        let func_before_block = self.append_block("");
-        self.br(func_before_block);
+        let is_in_schema = !self.schema_stack().borrow().is_empty();
         // Use "pkgpath"+"kclvm_lambda" to name 'function' to prevent conflicts between lambdas with the same name in different packages
         let function = self.add_function(&format!(
             "{}.{}",
@@ -2009,82 +1784,57 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
         ));
         // Enter the function
         self.push_function(function);
-        self.lambda_stack.borrow_mut().push(true);
+        self.lambda_stack().borrow_mut().push(true);
         // Lambda function body
-        let block = self.context.append_basic_block(function, ENTRY_NAME);
-        self.builder.position_at_end(block);
-        let args = function
-            .get_nth_param(1)
-            .expect(kcl_error::INTERNAL_ERROR_MSG);
-        let kwargs = function
-            .get_nth_param(2)
-            .expect(kcl_error::INTERNAL_ERROR_MSG);
+        let args = self.function_param(1);
+        let kwargs = self.function_param(2);
         self.enter_scope();
         let closure_map = self.list_pop_first(args);
-        let tpe = self.value_ptr_type();
-        let var = self.builder.build_alloca(tpe, value::LAMBDA_CLOSURE);
-        self.builder.build_store(var, closure_map);
+        let var = self.new_local_variable_with_value(closure_map);
         self.add_variable(value::LAMBDA_CLOSURE, var);
         if is_in_schema {
             for shcmea_closure_name in value::SCHEMA_VARIABLE_LIST {
-                let string_ptr_value = self.native_global_string(shcmea_closure_name, "").into();
+                let string_ptr_value = self.native_global_string(shcmea_closure_name, "");
                 let schema_value = self.build_call(
                     &ApiFunc::kclvm_dict_get_value.name(),
-                    &[closure_map, string_ptr_value],
+                    &[
+                        self.current_runtime_ctx_ptr(),
+                        closure_map,
+                        string_ptr_value,
+                    ],
                 );
-                let value_ptr_type = self.value_ptr_type();
-                let var = self
-                    .builder
-                    .build_alloca(value_ptr_type, shcmea_closure_name);
-                self.builder.build_store(var, schema_value);
+                let var = self.new_local_variable_with_value(schema_value);
                 self.add_variable(shcmea_closure_name, var);
             }
         }
-        self.walk_arguments(&lambda_expr.args, args, kwargs);
-        let val = self
-            .walk_stmts(&lambda_expr.body)
-            .expect(kcl_error::COMPILE_ERROR_MSG);
-        self.builder.build_return(Some(&val));
+        self.walk_arguments(&lambda_expr.args, args, kwargs)?;
+        let val = self.walk_stmts(&lambda_expr.body)?;
+        self.ret(val);
         // Exist the function
-        self.builder.position_at_end(func_before_block);
+        self.pop_function()?;
         let closure = self.list_value();
         // Use closure map in the laste scope to construct curret closure map.
         // The default value of the closure map is `{}`.
         self.list_append(closure, self.get_closure_map());
         let function = self.closure_value(function, closure);
         self.leave_scope();
-        self.pop_function();
-        self.lambda_stack.borrow_mut().pop();
+        self.lambda_stack().borrow_mut().pop();
         Ok(function)
     }
 
-    fn walk_keyword(&self, _keyword: &'ctx ast::Keyword) -> Self::Result {
+    fn walk_keyword(&self, _keyword: &'ctx ast::Keyword) -> CompileResult<Self::Value> {
         // Nothing to do
         self.ok_result()
     }
 
-    fn walk_arguments(&self, _arguments: &'ctx ast::Arguments) -> Self::Result {
-        // Nothing to do
-        self.ok_result()
-    }
-
-    fn walk_compare(&self, compare: &'ctx ast::Compare) -> Self::Result {
+    fn walk_compare(&self, compare: &'ctx ast::Compare) -> CompileResult<Self::Value> {
         check_backtrack_stop!(self);
-        let mut left_value = self
-            .walk_expr(&compare.left)
-            .expect(kcl_error::COMPILE_ERROR_MSG);
+        let mut left_value = self.walk_expr(&compare.left)?;
         if compare.comparators.len() > 1 {
-            let tpe = self.value_ptr_type();
-            let mut next_block = self.append_block("");
             let end_block = self.append_block("");
-            self.br(next_block);
-            self.builder.position_at_end(next_block);
-            let mut values_blocks: Vec<(BasicValueEnum, BasicBlock)> = vec![];
             for (i, op) in compare.ops.iter().enumerate() {
                 let has_next = i < (compare.ops.len() - 1);
-                let right_value = self
-                    .walk_expr(&compare.comparators[i])
-                    .expect(kcl_error::COMPILE_ERROR_MSG);
+                let right_value = self.walk_expr(&compare.comparators[i])?;
                 let fn_name = match op {
                     ast::CmpOp::Eq => ApiFunc::kclvm_value_cmp_equal_to,
                     ast::CmpOp::NotEq => ApiFunc::kclvm_value_cmp_not_equal_to,
@@ -2098,36 +1848,28 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
                     ast::CmpOp::NotIn => ApiFunc::kclvm_value_not_in,
                     ast::CmpOp::In => ApiFunc::kclvm_value_in,
                 };
-                let result_value = self.build_call(&fn_name.name(), &[left_value, right_value]);
+                let result_value = self.build_call(
+                    &fn_name.name(),
+                    &[self.current_runtime_ctx_ptr(), left_value, right_value],
+                );
                 let is_truth = self.value_is_truthy(result_value);
                 left_value = right_value;
-                // Get next value using a store/load temp block
-                let next_value_block = self.append_block("");
-                self.br(next_value_block);
-                self.builder.position_at_end(next_value_block);
-                let ptr = self.builder.build_alloca(tpe, "");
-                self.builder.build_store(ptr, result_value);
-                let result_value = self.builder.build_load(ptr, "");
-                // Append a value-block pair in the vec
-                values_blocks.push((result_value, next_value_block));
                 if has_next {
-                    next_block = self.append_block("");
-                    self.cond_br(is_truth, next_block, end_block);
-                    self.builder.position_at_end(next_block);
+                    // Pass the false result to the end block through a temp block.
+                    let false_block = self.append_block("");
+                    let next_block = self.append_block("");
+                    self.cond_br(is_truth, next_block, false_block);
+                    self.switch_to_block(false_block);
+                    self.br_with_args(end_block, &[result_value]);
+                    self.switch_to_block(next_block);
                 } else {
-                    self.br(end_block);
+                    self.br_with_args(end_block, &[result_value]);
                 }
             }
-            self.builder.position_at_end(end_block);
-            let phi = self.builder.build_phi(tpe, "");
-            for (value, block) in values_blocks {
-                phi.add_incoming(&[(&value, block)]);
-            }
-            Ok(phi.as_basic_value())
+            self.switch_to_block(end_block);
+            Ok(self.append_block_param(end_block))
         } else {
-            let right_value = self
-                .walk_expr(&compare.comparators[0])
-                .expect(kcl_error::COMPILE_ERROR_MSG);
+            let right_value = self.walk_expr(&compare.comparators[0])?;
             let fn_name = match &compare.ops[0] {
                 ast::CmpOp::Eq => ApiFunc::kclvm_value_cmp_equal_to,
                 ast::CmpOp::NotEq => ApiFunc::kclvm_value_cmp_not_equal_to,
@@ -2141,17 +1883,20 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
                 ast::CmpOp::NotIn => ApiFunc::kclvm_value_not_in,
                 ast::CmpOp::In => ApiFunc::kclvm_value_in,
             };
-            left_value = self.build_call(&fn_name.name(), &[left_value, right_value]);
+            left_value = self.build_call(
+                &fn_name.name(),
+                &[self.current_runtime_ctx_ptr(), left_value, right_value],
+            );
             Ok(left_value)
         }
     }
 
-    fn walk_identifier(&self, identifier: &'ctx ast::Identifier) -> Self::Result {
+    fn walk_identifier(&self, identifier: &'ctx ast::Identifier) -> CompileResult<Self::Value> {
         check_backtrack_stop!(self);
         self.walk_identifier_with_ctx(identifier, &identifier.ctx, None)
     }
 
-    fn walk_number_lit(&self, number_lit: &'ctx ast::NumberLit) -> Self::Result {
+    fn walk_number_lit(&self, number_lit: &'ctx ast::NumberLit) -> CompileResult<Self::Value> {
         check_backtrack_stop!(self);
         match number_lit.value {
             ast::NumberLitValue::Int(int_value) => match &number_lit.binary_suffix {
@@ -2166,18 +1911,19 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
         }
     }
 
-    fn walk_string_lit(&self, string_lit: &'ctx ast::StringLit) -> Self::Result {
+    fn walk_string_lit(&self, string_lit: &'ctx ast::StringLit) -> CompileResult<Self::Value> {
         check_backtrack_stop!(self);
-        let string_ptr_value = self
-            .native_global_string(string_lit.value.as_str(), "")
-            .into();
-        Ok(self.build_call(&ApiFunc::kclvm_value_Str.name(), &[string_ptr_value]))
+        let string_ptr_value = self.native_global_string(string_lit.value.as_str(), "");
+        Ok(self.build_call(
+            &ApiFunc::kclvm_value_Str.name(),
+            &[self.current_runtime_ctx_ptr(), string_ptr_value],
+        ))
     }
 
     fn walk_name_constant_lit(
         &self,
         name_constant_lit: &'ctx ast::NameConstantLit,
-    ) -> Self::Result {
+    ) -> CompileResult<Self::Value> {
         check_backtrack_stop!(self);
         match name_constant_lit.value {
             ast::NameConstant::True => Ok(self.bool_value(true)),
@@ -2187,70 +1933,77 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
         }
     }
 
-    fn walk_joined_string(&self, joined_string: &'ctx ast::JoinedString) -> Self::Result {
+    fn walk_joined_string(
+        &self,
+        joined_string: &'ctx ast::JoinedString,
+    ) -> CompileResult<Self::Value> {
         check_backtrack_stop!(self);
         let mut result_value = self.string_value("");
         for value in &joined_string.values {
             let value = &value.node;
             let value = match value {
-                ast::Expr::FormattedValue(formatted_value) => self
-                    .walk_formatted_value(formatted_value)
-                    .expect(kcl_error::INTERNAL_ERROR_MSG),
-                ast::Expr::StringLit(string_lit) => self
-                    .walk_string_lit(string_lit)
-                    .expect(kcl_error::INTERNAL_ERROR_MSG),
-                _ => panic!("{}", kcl_error::INVALID_JOINED_STR_MSG),
+                ast::Expr::FormattedValue(formatted_value) => {
+                    self.walk_formatted_value(formatted_value)?
+                }
+                ast::Expr::StringLit(string_lit) => self.walk_string_lit(string_lit)?,
+                _ => return Err(kcl_error::KCLError::new(kcl_error::INVALID_JOINED_STR_MSG)),
             };
-            result_value =
-                self.build_call(&ApiFunc::kclvm_value_op_add.name(), &[result_value, value]);
+            result_value = self.build_call(
+                &ApiFunc::kclvm_value_op_add.name(),
+                &[self.current_runtime_ctx_ptr(), result_value, value],
+            );
         }
         Ok(result_value)
     }
 
-    fn walk_formatted_value(&self, formatted_value: &'ctx ast::FormattedValue) -> Self::Result {
+    fn walk_formatted_value(
+        &self,
+        formatted_value: &'ctx ast::FormattedValue,
+    ) -> CompileResult<Self::Value> {
         check_backtrack_stop!(self);
-        let formatted_expr_value = self
-            .walk_expr(&formatted_value.value)
-            .expect(kcl_error::COMPILE_ERROR_MSG);
+        let formatted_expr_value = self.walk_expr(&formatted_value.value)?;
         let mut fn_name = ApiFunc::kclvm_value_to_str_value;
         if let Some(spec) = &formatted_value.format_spec {
             fn_name = match spec.to_lowercase().as_str() {
                 "#json" => ApiFunc::kclvm_value_to_json_value,
                 "#yaml" => ApiFunc::kclvm_value_to_yaml_value,
-                _ => panic!("{}", kcl_error::INVALID_STR_INTERPOLATION_SPEC_MSG),
+                _ => {
+                    return Err(kcl_error::KCLError::new(
+                        kcl_error::INVALID_STR_INTERPOLATION_SPEC_MSG,
+                    ))
+                }
             };
         }
-        Ok(self.build_call(&fn_name.name(), &[formatted_expr_value]))
-    }
-
-    fn walk_comment(&self, _comment: &'ctx ast::Comment) -> Self::Result {
-        // Nothing to do
-        self.ok_result()
+        Ok(self.build_call(
+            &fn_name.name(),
+            &[self.current_runtime_ctx_ptr(), formatted_expr_value],
+        ))
     }
 
-    fn walk_missing_expr(&self, _missing_expr: &'ctx ast::MissingExpr) -> Self::Result {
+    fn walk_missing_expr(
+        &self,
+        _missing_expr: &'ctx ast::MissingExpr,
+    ) -> CompileResult<Self::Value> {
         Err(kcl_error::KCLError::new(
             "compile error: missing expression",
         ))
     }
 
-    fn walk_module(&self, module: &'ctx ast::Module) -> Self::Result {
+    fn walk_module(&self, module: &'ctx ast::Module) -> CompileResult<Self::Value> {
         check_backtrack_stop!(self);
         if !module.body.is_empty() {
             utils::update_ctx_filename(self, &module.body[0]);
         }
         // Compile all schema and rule firstly
-        self.compile_module_import_and_types(module);
+        self.compile_module_import_and_types(module)?;
         // Compile all statements of the module
         self.walk_stmts_except_import(&module.body)
     }
-}
 
-impl<'ctx> LLVMCodeGenContext<'ctx> {
-    pub fn walk_stmts_except_import(
+    fn walk_stmts_except_import(
         &self,
         stmts: &'ctx [Box<ast::Node<ast::Stmt>>],
-    ) -> CompileResult<'ctx> {
+    ) -> CompileResult<Self::Value> {
         check_backtrack_stop!(self);
         let mut result = self.ok_result();
         for stmt in stmts {
@@ -2261,7 +2014,7 @@ impl<'ctx> LLVMCodeGenContext<'ctx> {
         result
     }
 
-    pub fn walk_stmts(&self, stmts: &'ctx [Box<ast::Node<ast::Stmt>>]) -> CompileResult<'ctx> {
+    fn walk_stmts(&self, stmts: &'ctx [Box<ast::Node<ast::Stmt>>]) -> CompileResult<Self::Value> {
         check_backtrack_stop!(self);
         // Empty statements return None value
         let mut result = Ok(self.none_value());
@@ -2271,19 +2024,18 @@ impl<'ctx> LLVMCodeGenContext<'ctx> {
         result
     }
 
-    pub fn walk_identifier_with_ctx(
+    fn walk_identifier_with_ctx(
         &self,
         identifier: &'ctx ast::Identifier,
         identifier_ctx: &ast::ExprContext,
-        right_value: Option<BasicValueEnum<'ctx>>,
-    ) -> CompileResult<'ctx> {
+        right_value: Option<Self::Value>,
+    ) -> CompileResult<Self::Value> {
         check_backtrack_stop!(self);
-        let is_in_schema = self.schema_stack.borrow().len() > 0;
+        let is_in_schema = !self.schema_stack().borrow().is_empty();
         match identifier_ctx {
             ast::ExprContext::Store => {
                 if identifier.names.len() == 1 {
                     let name = identifier.names[0].as_str();
-                    let tpe = self.value_ptr_type();
                     // Global variables
                     if self.scope_level() == GLOBAL_LEVEL {
                         self.add_or_update_global_variable(
@@ -2292,7 +2044,7 @@ impl<'ctx> LLVMCodeGenContext<'ctx> {
                         );
                     // Local variables including schema/rule/lambda
                     } else if *self
-                        .lambda_stack
+                        .lambda_stack()
                         .borrow_mut()
                         .last()
                         .expect(kcl_error::INTERNAL_ERROR_MSG)
@@ -2300,84 +2052,56 @@ impl<'ctx> LLVMCodeGenContext<'ctx> {
                         let value = right_value.expect(kcl_error::INTERNAL_ERROR_MSG);
                         // If variable exists in the scope and update it, if not, add it to the scope.
                         if !self.store_variable_in_current_scope(name, value) {
-                            let cur_bb = self.builder.get_insert_block().unwrap();
-                            let lambda_func = cur_bb.get_parent().unwrap();
-                            let entry_bb = lambda_func.get_first_basic_block().unwrap();
-                            match entry_bb.get_first_instruction() {
-                                Some(inst) => self.builder.position_before(&inst),
-                                None => self.builder.position_at_end(entry_bb),
-                            };
-                            let var = self.builder.build_alloca(tpe, name);
-                            let undefined_val = self.undefined_value();
-                            self.builder.build_store(var, undefined_val);
+                            let var = self.new_local_variable();
                             self.add_variable(name, var);
-                            self.builder.position_at_end(cur_bb);
                             self.store_variable(name, value);
                         }
                     } else {
                         let is_local_var = {
-                            let local_vars = self.local_vars.borrow_mut();
+                            let local_vars = self.local_vars().borrow_mut();
                             local_vars.contains(name)
                         };
                         let value = if is_in_schema {
                             let value = right_value.expect(kcl_error::INTERNAL_ERROR_MSG);
-                            let schema_value = self
-                                .get_variable(value::SCHEMA_SELF_NAME)
-                                .expect(kcl_error::INTERNAL_ERROR_MSG);
-                            let config_value = self
-                                .get_variable(value::SCHEMA_CONFIG_NAME)
-                                .expect(kcl_error::INTERNAL_ERROR_MSG);
-                            let string_ptr_value = self.native_global_string(name, "").into();
-                            let has_key = self
-                                .build_call(
-                                    &ApiFunc::kclvm_dict_has_value.name(),
-                                    &[config_value, string_ptr_value],
-                                )
-                                .into_int_value();
-                            let has_key = self.builder.build_int_compare(
-                                IntPredicate::NE,
-                                has_key,
-                                self.native_i8_zero(),
-                                "",
+                            let schema_value = self.get_variable(value::SCHEMA_SELF_NAME)?;
+                            let config_value = self.get_variable(value::SCHEMA_CONFIG_NAME)?;
+                            let string_ptr_value = self.native_global_string(name, "");
+                            let has_key = self.build_call(
+                                &ApiFunc::kclvm_dict_has_value.name(),
+                                &[config_value, string_ptr_value],
                             );
-                            let last_block = self.append_block("");
+                            let has_key =
+                                self.icmp(IntPredicate::NotEqual, has_key, self.native_i8_zero());
                             let then_block = self.append_block("");
                             let else_block = self.append_block("");
-                            self.br(last_block);
-                            self.builder.position_at_end(last_block);
-                            let none_value = self.none_value();
-                            self.builder
-                                .build_conditional_branch(has_key, then_block, else_block);
-                            self.builder.position_at_end(then_block);
+                            let merge_block = self.append_block("");
+                            self.cond_br(has_key, then_block, else_block);
+                            self.switch_to_block(then_block);
                             let config_entry = self.build_call(
                                 &ApiFunc::kclvm_dict_get_entry.name(),
-                                &[config_value, string_ptr_value],
+                                &[
+                                    self.current_runtime_ctx_ptr(),
+                                    config_value,
+                                    string_ptr_value,
+                                ],
                             );
-                            self.br(else_block);
-                            self.builder.position_at_end(else_block);
-                            let tpe = self.value_ptr_type();
-                            let phi = self.builder.build_phi(tpe, "");
-                            phi.add_incoming(&[
-                                (&none_value, last_block),
-                                (&config_entry, then_block),
-                            ]);
-                            let config_value = phi.as_basic_value();
+                            self.br_with_args(merge_block, &[config_entry]);
+                            self.switch_to_block(else_block);
+                            let none_value = self.none_value();
+                            self.br_with_args(merge_block, &[none_value]);
+                            self.switch_to_block(merge_block);
+                            let config_value = self.append_block_param(merge_block);
                             if self.scope_level() >= INNER_LEVEL && !is_local_var {
                                 self.dict_merge(schema_value, name, value, 1, -1);
                                 self.value_union(schema_value, config_value);
-                                let cal_map = self
-                                    .get_variable(value::SCHEMA_CAL_MAP)
-                                    .expect(kcl_error::INTERNAL_ERROR_MSG);
-                                let backtrack_cache = self
-                                    .get_variable(value::BACKTRACK_CACHE)
-                                    .expect(kcl_error::INTERNAL_ERROR_MSG);
-                                let runtime_type = self
-                                    .get_variable(value::SCHEMA_RUNTIME_TYPE)
-                                    .expect(kcl_error::INTERNAL_ERROR_MSG);
+                                let cal_map = self.get_variable(value::SCHEMA_CAL_MAP)?;
+                                let backtrack_cache = self.get_variable(value::BACKTRACK_CACHE)?;
+                                let runtime_type = self.get_variable(value::SCHEMA_RUNTIME_TYPE)?;
                                 let name_native_str = self.native_global_string_value(name);
                                 self.build_void_call(
                                     &ApiFunc::kclvm_schema_backtrack_cache.name(),
                                     &[
+                                        self.current_runtime_ctx_ptr(),
                                         schema_value,
                                         backtrack_cache,
                                         cal_map,
@@ -2388,7 +2112,7 @@ impl<'ctx> LLVMCodeGenContext<'ctx> {
                                 // Update backtrack meta
                                 {
                                     if let Some(backtrack_meta) =
-                                        self.backtrack_meta.borrow_mut().as_mut()
+                                        self.backtrack_meta().borrow_mut().as_mut()
                                     {
                                         if name == backtrack_meta.target {
                                             backtrack_meta.count += 1;
@@ -2406,8 +2130,7 @@ impl<'ctx> LLVMCodeGenContext<'ctx> {
                             right_value.expect(kcl_error::INTERNAL_ERROR_MSG)
                         };
                         if is_local_var || !is_in_schema {
-                            let var = self.builder.build_alloca(tpe, name);
-                            self.builder.build_store(var, value);
+                            let var = self.new_local_variable_with_value(value);
                             self.add_variable(name, var);
                         }
                     }
@@ -2415,11 +2138,9 @@ impl<'ctx> LLVMCodeGenContext<'ctx> {
                     let names = &identifier.names;
                     let name = names[0].as_str();
                     let mut value = if is_in_schema {
-                        self.get_variable_in_schema(name)
-                            .expect(kcl_error::INTERNAL_ERROR_MSG)
+                        self.get_variable_in_schema(name)?
                     } else {
-                        self.get_variable(name)
-                            .expect(kcl_error::INTERNAL_ERROR_MSG)
+                        self.get_variable(name)?
                     };
                     for i in 0..names.len() - 1 {
                         let attr = names[i + 1].as_str();
@@ -2433,17 +2154,18 @@ impl<'ctx> LLVMCodeGenContext<'ctx> {
                         };
                         match ctx {
                             ast::ExprContext::Load => {
-                                let attr = self.native_global_string(attr, "").into();
+                                let attr = self.native_global_string(attr, "");
                                 value = self.build_call(
                                     &ApiFunc::kclvm_value_load_attr.name(),
-                                    &[value, attr],
+                                    &[self.current_runtime_ctx_ptr(), value, attr],
                                 );
                             }
                             ast::ExprContext::Store => {
-                                let attr = self.native_global_string(attr, "").into();
+                                let attr = self.native_global_string(attr, "");
                                 self.build_void_call(
                                     &ApiFunc::kclvm_dict_set_value.name(),
                                     &[
+                                        self.current_runtime_ctx_ptr(),
                                         value,
                                         attr,
                                         right_value.expect(kcl_error::INTERNAL_ERROR_MSG),
@@ -2458,7 +2180,7 @@ impl<'ctx> LLVMCodeGenContext<'ctx> {
             ast::ExprContext::Load => {
                 let name = identifier.names[0].as_str();
                 let is_local_var = {
-                    let local_vars = self.local_vars.borrow_mut();
+                    let local_vars = self.local_vars().borrow_mut();
                     local_vars.contains(name)
                 };
                 if identifier.names.len() == 1 {
@@ -2472,14 +2194,12 @@ impl<'ctx> LLVMCodeGenContext<'ctx> {
                     let name = names[0].as_str();
                     let mut value = if identifier.pkgpath.is_empty() {
                         if is_in_schema && !is_local_var {
-                            self.get_variable_in_schema(name)
-                                .expect(kcl_error::INTERNAL_ERROR_MSG)
+                            self.get_variable_in_schema(name)?
                         } else {
-                            self.get_variable(name)
-                                .expect(kcl_error::INTERNAL_ERROR_MSG)
+                            self.get_variable(name)?
                         }
                     } else {
-                        self.ok_result().expect(kcl_error::INTERNAL_ERROR_MSG)
+                        self.ok_result()?
                     };
                     for i in 0..names.len() - 1 {
                         let attr = names[i + 1].as_str();
@@ -2494,29 +2214,28 @@ impl<'ctx> LLVMCodeGenContext<'ctx> {
                         match ctx {
                             ast::ExprContext::Load => {
                                 if i == 0 && !identifier.pkgpath.is_empty() {
-                                    value = if self.no_link {
+                                    value = if self.no_link() {
                                         self.get_external_variable_in_pkgpath(
                                             attr,
                                             &identifier.pkgpath,
-                                        )
-                                        .expect(kcl_error::INTERNAL_ERROR_MSG)
+                                        )?
                                     } else {
-                                        self.get_variable_in_pkgpath(attr, &identifier.pkgpath)
-                                            .expect(kcl_error::INTERNAL_ERROR_MSG)
+                                        self.get_variable_in_pkgpath(attr, &identifier.pkgpath)?
                                     }
                                 } else {
-                                    let attr = self.native_global_string(attr, "").into();
+                                    let attr = self.native_global_string(attr, "");
                                     value = self.build_call(
                                         &ApiFunc::kclvm_value_load_attr.name(),
-                                        &[value, attr],
+                                        &[self.current_runtime_ctx_ptr(), value, attr],
                                     );
                                 }
                             }
                             ast::ExprContext::Store => {
-                                let attr = self.native_global_string(attr, "").into();
+                                let attr = self.native_global_string(attr, "");
                                 self.build_void_call(
                                     &ApiFunc::kclvm_dict_set_value.name(),
                                     &[
+                                        self.current_runtime_ctx_ptr(),
                                         value,
                                         attr,
                                         right_value.expect(kcl_error::INTERNAL_ERROR_MSG),
@@ -2531,29 +2250,25 @@ impl<'ctx> LLVMCodeGenContext<'ctx> {
         }
     }
 
-    pub fn walk_decorator_with_name(
+    fn walk_decorator_with_name(
         &self,
         decorator: &'ctx CallExpr,
         attr_name: Option<&str>,
         is_schema_target: bool,
-    ) -> CompileResult<'ctx> {
+    ) -> CompileResult<Self::Value> {
         check_backtrack_stop!(self);
         let list_value = self.list_value();
         let dict_value = self.dict_value();
-        let schema_config_meta = self
-            .get_variable(value::SCHEMA_CONFIG_META_NAME)
-            .expect(kcl_error::INTERNAL_ERROR_MSG);
-        let schema_config_value = self
-            .get_variable(value::SCHEMA_CONFIG_NAME)
-            .expect(kcl_error::INTERNAL_ERROR_MSG);
+        let schema_config_meta = self.get_variable(value::SCHEMA_CONFIG_META_NAME)?;
+        let schema_config_value = self.get_variable(value::SCHEMA_CONFIG_NAME)?;
         for arg in &decorator.args {
-            let value = self.walk_expr(arg).expect(kcl_error::COMPILE_ERROR_MSG);
+            let value = self.walk_expr(arg)?;
             self.list_append(list_value, value);
         }
         for keyword in &decorator.keywords {
             let name = &keyword.node.arg.node.names[0];
             let value = if let Some(value) = &keyword.node.value {
-                self.walk_expr(value).expect(kcl_error::COMPILE_ERROR_MSG)
+                self.walk_expr(value)?
             } else {
                 self.none_value()
             };
@@ -2561,13 +2276,18 @@ impl<'ctx> LLVMCodeGenContext<'ctx> {
         }
         let name = match &decorator.func.node {
             ast::Expr::Identifier(ident) if ident.names.len() == 1 => ident.names[0].clone(),
-            _ => panic!("invalid decorator name, expect single identifier"),
+            _ => {
+                return Err(kcl_error::KCLError::new(
+                    "invalid decorator name, expect single identifier",
+                ))
+            }
         };
-        let attr_name = if let Some(v) = attr_name { v } else { "" };
+        let attr_name = attr_name.unwrap_or_default();
         let attr_name = self.native_global_string_value(attr_name);
         Ok(self.build_call(
             &ApiFunc::kclvm_value_Decorator.name(),
             &[
+                self.current_runtime_ctx_ptr(),
                 self.native_global_string_value(name.as_str()),
                 list_value,
                 dict_value,
@@ -2579,12 +2299,12 @@ impl<'ctx> LLVMCodeGenContext<'ctx> {
         ))
     }
 
-    pub fn walk_arguments(
+    fn walk_arguments(
         &self,
         arguments: &'ctx Option<ast::NodeRef<ast::Arguments>>,
-        args: BasicValueEnum<'ctx>,
-        kwargs: BasicValueEnum<'ctx>,
-    ) {
+        args: Self::Value,
+        kwargs: Self::Value,
+    ) -> CompileResult<()> {
         // Arguments names and defaults
         let (arg_names, arg_defaults) = if let Some(args) = &arguments {
             let names = &args.node.args;
@@ -2599,107 +2319,92 @@ impl<'ctx> LLVMCodeGenContext<'ctx> {
         // Default parameter values
         for (arg_name, value) in arg_names.iter().zip(arg_defaults.iter()) {
             let arg_value = if let Some(value) = value {
-                self.walk_expr(value).expect(kcl_error::COMPILE_ERROR_MSG)
+                self.walk_expr(value)?
             } else {
                 self.none_value()
             };
             self.store_argument_in_current_scope(&arg_name.get_name());
-            self.walk_identifier_with_ctx(arg_name, &ast::ExprContext::Store, Some(arg_value))
-                .expect(kcl_error::COMPILE_ERROR_MSG);
+            self.walk_identifier_with_ctx(arg_name, &ast::ExprContext::Store, Some(arg_value))?;
         }
         // for loop in 0..argument_len in LLVM begin
         let argument_len = self.build_call(&ApiFunc::kclvm_list_len.name(), &[args]);
         let end_block = self.append_block("");
         for (i, arg_name) in arg_names.iter().enumerate() {
             // Positional arguments
-            let is_in_range = self.builder.build_int_compare(
-                IntPredicate::ULT,
-                self.native_int_value(i as i32).into_int_value(),
-                argument_len.into_int_value(),
-                "",
-            );
+            let index = self.native_int_value(i as i32);
+            let is_in_range = self.icmp(IntPredicate::UnsignedLessThan, index, argument_len);
             let next_block = self.append_block("");
-            self.builder
-                .build_conditional_branch(is_in_range, next_block, end_block);
-            self.builder.position_at_end(next_block);
+            self.cond_br(is_in_range, next_block, end_block);
+            self.switch_to_block(next_block);
             let arg_value = self.build_call(
                 &ApiFunc::kclvm_list_get_option.name(),
-                &[args, self.native_int_value(i as i32)],
+                &[
+                    self.current_runtime_ctx_ptr(),
+                    args,
+                    self.native_int_value(i as i32),
+                ],
             );
             self.store_variable(&arg_name.names[0], arg_value);
         }
         // for loop in 0..argument_len in LLVM end
         self.br(end_block);
-        self.builder.position_at_end(end_block);
+        self.switch_to_block(end_block);
         // Keyword arguments
         for arg_name in arg_names.iter() {
             let name = &arg_name.names[0];
-            let string_ptr_value = self.native_global_string(name.as_str(), "").into();
-            let has_key = self
-                .build_call(
-                    &ApiFunc::kclvm_dict_has_value.name(),
-                    &[kwargs, string_ptr_value],
-                )
-                .into_int_value();
-            let has_key = self.builder.build_int_compare(
-                IntPredicate::NE,
-                has_key,
-                self.native_i8_zero(),
-                "",
+            let string_ptr_value = self.native_global_string(name.as_str(), "");
+            let has_key = self.build_call(
+                &ApiFunc::kclvm_dict_has_value.name(),
+                &[kwargs, string_ptr_value],
             );
+            let has_key = self.icmp(IntPredicate::NotEqual, has_key, self.native_i8_zero());
             let then_block = self.append_block("");
             let else_block = self.append_block("");
-            self.builder
-                .build_conditional_branch(has_key, then_block, else_block);
-            self.builder.position_at_end(then_block);
+            self.cond_br(has_key, then_block, else_block);
+            self.switch_to_block(then_block);
             let arg = self.build_call(
                 &ApiFunc::kclvm_dict_get_value.name(),
-                &[kwargs, string_ptr_value],
+                &[self.current_runtime_ctx_ptr(), kwargs, string_ptr_value],
             );
             // Find argument name in the scope
             self.store_variable(&arg_name.names[0], arg);
             self.br(else_block);
-            self.builder.position_at_end(else_block);
+            self.switch_to_block(else_block);
         }
+        Ok(())
     }
 
-    pub fn walk_generator(
+    #[allow(clippy::too_many_arguments)]
+    fn walk_generator(
         &self,
         generators: &'ctx [Box<ast::Node<ast::CompClause>>],
         elt: &'ctx ast::Node<ast::Expr>,
         val: Option<&'ctx ast::Node<ast::Expr>>,
         op: Option<&'ctx ast::ConfigEntryOperation>,
         gen_index: usize,
-        collection_value: BasicValueEnum<'ctx>,
+        collection_value: Self::Value,
         comp_type: ast::CompType,
-    ) {
+    ) -> CompileResult<()> {
         let start_block = self.append_block("");
         let next_value_block = self.append_block("");
         let continue_block = self.append_block("");
         let end_for_block = self.append_block("");
         let generator = &generators[gen_index];
-        let iter_host_value = self
-            .walk_expr(&generator.node.iter)
-            .expect(kcl_error::COMPILE_ERROR_MSG);
+        let iter_host_value = self.walk_expr(&generator.node.iter)?;
         let iter_value = self.build_call(&ApiFunc::kclvm_value_iter.name(), &[iter_host_value]);
         self.br(start_block);
-        self.builder.position_at_end(start_block);
-        let is_end = self
-            .build_call(&ApiFunc::kclvm_iterator_is_end.name(), &[iter_value])
-            .into_int_value();
-        let is_end =
-            self.builder
-                .build_int_compare(IntPredicate::NE, is_end, self.native_i8_zero(), "");
-        self.builder
-            .build_conditional_branch(is_end, end_for_block, next_value_block);
-        self.builder.position_at_end(next_value_block);
+        self.switch_to_block(start_block);
+        let is_end = self.build_call(&ApiFunc::kclvm_iterator_is_end.name(), &[iter_value]);
+        let is_end = self.icmp(IntPredicate::NotEqual, is_end, self.native_i8_zero());
+        self.cond_br(is_end, end_for_block, next_value_block);
+        self.switch_to_block(next_value_block);
         let next_value = self.build_call(
             &ApiFunc::kclvm_iterator_next_value.name(),
             &[iter_value, iter_host_value],
         );
         let targets = &generator.node.targets;
         {
-            let mut local_vars = self.local_vars.borrow_mut();
+            let mut local_vars = self.local_vars().borrow_mut();
             for v in targets {
                 let name = &v.node.names[0];
                 local_vars.insert(name.clone());
@@ -2708,54 +2413,41 @@ impl<'ctx> LLVMCodeGenContext<'ctx> {
         if targets.len() == 1 {
             // Store the target
             self.walk_identifier_with_ctx(
-                &targets.get(0).expect(kcl_error::INTERNAL_ERROR_MSG).node,
+                &targets[0].node,
                 &ast::ExprContext::Store,
                 Some(next_value),
-            )
-            .expect(kcl_error::COMPILE_ERROR_MSG);
+            )?;
         } else if targets.len() == 2 {
             let key = self.build_call(&ApiFunc::kclvm_iterator_cur_key.name(), &[iter_value]);
             let value = self.build_call(&ApiFunc::kclvm_iterator_cur_value.name(), &[iter_value]);
             // Store the target
-            self.walk_identifier_with_ctx(
-                &targets.get(0).expect(kcl_error::INTERNAL_ERROR_MSG).node,
-                &ast::ExprContext::Store,
-                Some(key),
-            )
-            .expect(kcl_error::COMPILE_ERROR_MSG);
-            self.walk_identifier_with_ctx(
-                &targets.get(1).expect(kcl_error::INTERNAL_ERROR_MSG).node,
-                &ast::ExprContext::Store,
-                Some(value),
-            )
-            .expect(kcl_error::COMPILE_ERROR_MSG);
+            self.walk_identifier_with_ctx(&targets[0].node, &ast::ExprContext::Store, Some(key))?;
+            self.walk_identifier_with_ctx(&targets[1].node, &ast::ExprContext::Store, Some(value))?;
         } else {
-            panic!(
+            return Err(kcl_error::KCLError::new(&format!(
                 "the number of loop variables is {}, which can only be 1 or 2",
                 generator.node.targets.len()
-            )
+            )));
         }
         for if_expr in &generator.node.ifs {
-            let is_truth = self.walk_expr(if_expr).expect(kcl_error::COMPILE_ERROR_MSG);
+            let is_truth = self.walk_expr(if_expr)?;
             let is_truth = self.value_is_truthy(is_truth);
-            self.cond_br(is_truth, continue_block, start_block);
-        }
-        if generator.node.ifs.is_empty() {
-            self.br(continue_block);
+            let next_if_block = self.append_block("");
+            self.cond_br(is_truth, next_if_block, start_block);
+            self.switch_to_block(next_if_block);
         }
-        self.builder.position_at_end(continue_block);
+        self.br(continue_block);
+        self.switch_to_block(continue_block);
         let next_gen_index = gen_index + 1;
         if next_gen_index >= generators.len() {
             match comp_type {
                 ast::CompType::List => {
-                    let item = self.walk_expr(elt).expect(kcl_error::COMPILE_ERROR_MSG);
+                    let item = self.walk_expr(elt)?;
                     self.list_append(collection_value, item);
                 }
                 ast::CompType::Dict => {
-                    let value = self
-                        .walk_expr(val.expect(kcl_error::INTERNAL_ERROR_MSG))
-                        .expect(kcl_error::COMPILE_ERROR_MSG);
-                    let key = self.walk_expr(elt).expect(kcl_error::COMPILE_ERROR_MSG);
+                    let value = self.walk_expr(val.expect(kcl_error::INTERNAL_ERROR_MSG))?;
+                    let key = self.walk_expr(elt)?;
                     let op = op.expect(kcl_error::INTERNAL_ERROR_MSG);
                     self.dict_insert_with_key_value(collection_value, key, value, op.value(), -1);
                 }
@@ -2769,24 +2461,25 @@ impl<'ctx> LLVMCodeGenContext<'ctx> {
                 next_gen_index,
                 collection_value,
                 comp_type,
-            );
+            )?;
         }
         self.br(start_block);
-        self.builder.position_at_end(end_for_block);
+        self.switch_to_block(end_for_block);
         self.build_void_call(&ApiFunc::kclvm_iterator_delete.name(), &[iter_value]);
         {
-            let mut local_vars = self.local_vars.borrow_mut();
+            let mut local_vars = self.local_vars().borrow_mut();
             for v in targets {
                 let name = &v.node.names[0];
                 local_vars.remove(name);
             }
         }
+        Ok(())
     }
 
-    pub(crate) fn walk_config_entries(
+    fn walk_config_entries(
         &self,
         items: &'ctx [NodeRef<ConfigEntry>],
-    ) -> CompileResult<'ctx> {
+    ) -> CompileResult<Self::Value> {
         let config_value = self.dict_value();
         self.enter_scope();
         for item in items {
@@ -2831,11 +2524,56 @@ impl<'ctx> LLVMCodeGenContext<'ctx> {
                 // If the key does not exist, execute the logic of unpacking expression `**expr` here.
                 self.build_void_call(
                     &ApiFunc::kclvm_dict_insert_unpack.name(),
-                    &[config_value, value],
+                    &[self.current_runtime_ctx_ptr(), config_value, value],
                 );
             }
         }
         self.leave_scope();
         Ok(config_value)
     }
+
+    fn compile_module_import_and_types(&self, module: &'ctx ast::Module) -> CompileResult<()> {
+        self.predefine_global_vars(module);
+        for stmt in &module.body {
+            match &stmt.node {
+                ast::Stmt::Import(import_stmt) => {
+                    self.walk_import_stmt(import_stmt)?;
+                }
+                ast::Stmt::Schema(schema_stmt) => {
+                    self.predefine_global_types(&schema_stmt.name.node);
+                    self.walk_schema_stmt(schema_stmt)?;
+                }
+                ast::Stmt::Rule(rule_stmt) => {
+                    self.predefine_global_types(&rule_stmt.name.node);
+                    self.walk_rule_stmt(rule_stmt)?;
+                }
+                _ => {}
+            };
+        }
+        Ok(())
+    }
+
+    fn predefine_global_types(&self, name: &str) {
+        // Store or add the variable in the scope
+        let function = self.undefined_value();
+        if !self.store_variable(name, function) {
+            let global_var = self.new_global_kcl_value_ptr("");
+            self.store_to_variable(global_var, function);
+            self.add_variable(name, global_var);
+        }
+    }
+
+    /// Predefine all global variables.
+    fn predefine_global_vars(&self, module: &'ctx ast::Module) {
+        for stmt in &module.body {
+            if let ast::Stmt::Assign(assign_stmt) = &stmt.node {
+                for target in &assign_stmt.targets {
+                    let names = &target.node.names;
+                    if names.len() == 1 {
+                        self.add_or_update_global_variable(&names[0], self.undefined_value());
+                    }
+                }
+            }
+        }
+    }
 }
@@ -1,38 +1,35 @@
 // Copyright 2021 The KCL Authors. All rights reserved.
 
-use inkwell::values::{BasicValueEnum, FunctionValue};
-use inkwell::AddressSpace;
 use kclvm_ast::ast;
 use std::collections::HashMap;
 use std::str;
 
-use super::context::LLVMCodeGenContext;
 use crate::codegen::error as kcl_error;
-use crate::codegen::traits::{BuilderMethods, DerivedValueCalculationMethods, ValueMethods};
+use crate::codegen::traits::ContextMethods;
 use crate::value;
 
 use crate::pkgpath_without_prefix;
 
-impl<'ctx> LLVMCodeGenContext<'ctx> {
+/// SchemaCodeGen defines the schema lowering helpers shared by all backends.
+pub(crate) trait SchemaCodeGen<'ctx>: ContextMethods<'ctx> {
     /// Emit all schema left identifiers because all the schema attribute can be forward referenced
-    pub fn emit_schema_left_identifiers(
+    #[allow(clippy::too_many_arguments)]
+    fn emit_schema_left_identifiers(
         &self,
         body: &'ctx [Box<ast::Node<ast::Stmt>>],
-        cal_map: BasicValueEnum<'ctx>,
+        cal_map: Self::Value,
         runtime_type: &str,
         is_in_if: bool,
-        place_holder_map: &mut HashMap<String, Vec<FunctionValue<'ctx>>>,
+        place_holder_map: &mut HashMap<String, Vec<Self::Function>>,
         body_map: &mut HashMap<String, Vec<&'ctx ast::Node<ast::Stmt>>>,
         in_if_names: &mut Vec<String>,
-    ) {
-        let schema_value = self
-            .get_variable(value::SCHEMA_SELF_NAME)
-            .expect(kcl_error::INTERNAL_ERROR_MSG);
+    ) -> Result<(), kcl_error::KCLError> {
+        let schema_value = self.get_variable(value::SCHEMA_SELF_NAME)?;
         let value = self.undefined_value();
         let add_stmt =
             |name: &str,
              stmt: &'ctx ast::Node<ast::Stmt>,
-             place_holder_map: &mut HashMap<String, Vec<FunctionValue<'ctx>>>,
+             place_holder_map: &mut HashMap<String, Vec<Self::Function>>,
              body_map: &mut HashMap<String, Vec<&'ctx ast::Node<ast::Stmt>>>| {
                 let function = self.add_function(&format!(
                     "{}.{}.{}",
@@ -40,18 +37,11 @@ impl<'ctx> LLVMCodeGenContext<'ctx> {
                     pkgpath_without_prefix!(runtime_type),
                     name
                 ));
-                let lambda_fn_ptr = self.builder.build_bitcast(
-                    function.as_global_value().as_pointer_value(),
-                    self.context.i64_type().ptr_type(AddressSpace::default()),
-                    "",
-                );
-                if !place_holder_map.contains_key(name) {
-                    place_holder_map.insert(name.to_string(), vec![]);
-                }
-                let name_vec = place_holder_map
-                    .get_mut(name)
-                    .expect(kcl_error::INTERNAL_ERROR_MSG);
-                name_vec.push(function);
+                let lambda_fn_ptr = self.function_ptr(function);
+                place_holder_map
+                    .entry(name.to_string())
+                    .or_default()
+                    .push(function);
                 self.default_collection_insert_int_pointer(cal_map, name, lambda_fn_ptr);
                 self.default_collection_insert_value(
                     cal_map,
@@ -63,11 +53,7 @@ impl<'ctx> LLVMCodeGenContext<'ctx> {
                     &format!("{}_{}", name, kclvm_runtime::CAL_MAP_META_LINE),
                     self.int_value(stmt.line as i64),
                 );
-                if !body_map.contains_key(name) {
-                    body_map.insert(name.to_string(), vec![]);
-                }
-                let body_vec = body_map.get_mut(name).expect(kcl_error::INTERNAL_ERROR_MSG);
-                body_vec.push(stmt);
+                body_map.entry(name.to_string()).or_default().push(stmt);
             };
         for stmt in body {
             match &stmt.node {
@@ -111,7 +97,7 @@ impl<'ctx> LLVMCodeGenContext<'ctx> {
                         place_holder_map,
                         body_map,
                         &mut names,
-                    );
+                    )?;
                     if is_in_if {
                         for name in &names {
                             in_if_names.push(name.to_string());
@@ -130,7 +116,7 @@ impl<'ctx> LLVMCodeGenContext<'ctx> {
                         place_holder_map,
                         body_map,
                         &mut names,
-                    );
+                    )?;
                     if is_in_if {
                         for name in &names {
                             in_if_names.push(name.to_string());
@@ -154,13 +140,15 @@ impl<'ctx> LLVMCodeGenContext<'ctx> {
                 _ => {}
             }
         }
+        Ok(())
     }
 
-    pub fn get_schema_config_meta(
+    /// Get the config meta including the filename, line and column of the config items.
+    fn get_schema_config_meta(
         &self,
         n: Option<&'ctx ast::Node<ast::Identifier>>,
         t: &'ctx ast::ConfigExpr,
-    ) -> BasicValueEnum<'ctx> {
+    ) -> Self::Value {
         let config_meta = self.dict_value();
         if let Some(n) = n {
             let value = self.string_value(&n.filename);
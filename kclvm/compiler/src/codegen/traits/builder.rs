@@ -1,8 +1,11 @@
 //! Copyright 2021 The KCL Authors. All rights reserved.
 
-use crate::codegen::abi::Align;
+use crate::codegen::abi::{Align, IntPredicate};
+use crate::codegen::error::KCLError;
 
 use super::BackendTypes;
+#[cfg(feature = "llvm")]
+use super::BaseTypeMethods;
 /// BuilderMethods defines SSA builder methods including calculation, condition, SSA instructions etc.
 pub trait BuilderMethods: BackendTypes {
     /// SSA append a basic block named `name`.
@@ -11,20 +14,26 @@ pub trait BuilderMethods: BackendTypes {
     fn switch_to_block(&self, block: Self::BasicBlock);
     /// SSA alloca instruction.
     fn alloca(&self, ty: Self::Type, name: &str, align: Option<Align>) -> Self::Value;
-    /// SSA array alloca instruction.
+    /// SSA array alloca instruction with the constant length `len`, returns an error
+    /// when the array does not fit in a stack frame of the backend.
     fn array_alloca(
         &self,
         ty: Self::Type,
-        len: Self::Value,
+        len: u32,
         name: &str,
         align: Align,
-    ) -> Self::Value;
+    ) -> Result<Self::Value, KCLError>;
     /// SSA ret instruction.
     fn ret_void(&self);
     /// SSA ret instruction with returned value.
     fn ret(&self, v: Self::Value);
     /// SSA br instruction.
     fn br(&self, dest: Self::BasicBlock);
+    /// SSA br instruction with the arguments passed to the block parameters of `dest`.
+    fn br_with_args(&self, dest: Self::BasicBlock, args: &[Self::Value]);
+    /// Append a parameter to the block and return it, which merges the values passed
+    /// by the predecessors of the block like a phi node.
+    fn append_block_param(&self, block: Self::BasicBlock) -> Self::Value;
     /// SSA cond br instruction.
     fn cond_br(&self, cond: Self::Value, then_bb: Self::BasicBlock, else_bb: Self::BasicBlock);
     /// SSA select instruction.
@@ -34,14 +43,10 @@ pub trait BuilderMethods: BackendTypes {
         then_val: Self::Value,
         else_val: Self::Value,
     ) -> Self::Value;
-    /// SSA va arg instruction.
-    fn va_arg(&self, list: Self::Value, ty: Self::Type) -> Self::Value;
+    /// SSA integer compare instruction, returns a boolean value.
+    fn icmp(&self, op: IntPredicate, lhs: Self::Value, rhs: Self::Value) -> Self::Value;
     /// SSA extract element instruction.
     fn extract_element(&self, vec: Self::Value, idx: Self::Value) -> Self::Value;
-    /// SSA extract value instruction.
-    fn extract_value(&self, agg_val: Self::Value, idx: u32) -> Self::Value;
-    /// SSA insert value instruction.
-    fn insert_value(&self, agg_val: Self::Value, elt: Self::Value, idx: u32) -> Self::Value;
     /// SSA function invoke instruction.
     fn invoke(
         &self,
@@ -66,8 +71,6 @@ pub trait BuilderMethods: BackendTypes {
         ptr: Self::Value,
         indices: &[Self::Value],
     ) -> Self::Value;
-    /// SSA struct gep instruction.
-    fn struct_gep(&self, ty: Self::Type, ptr: Self::Value, idx: u32) -> Self::Value;
     /// SSA cast pointer to int.
     fn ptr_to_int(&self, val: Self::Value, dest_ty: Self::Type) -> Self::Value;
     /// SSA cast int to pointer.
@@ -78,8 +81,28 @@ pub trait BuilderMethods: BackendTypes {
     fn int_cast(&self, val: Self::Value, dest_ty: Self::Type, is_signed: bool) -> Self::Value;
     /// SSA pointer cast.
     fn ptr_cast(&self, val: Self::Value, dest_ty: Self::Type) -> Self::Value;
+    /// Get the nth parameter of the current function.
+    fn function_param(&self, index: usize) -> Self::Value;
     /// Lookup a known function named `name`.
     fn lookup_function(&self, name: &str) -> Self::Function;
     /// Add a function named `name`.
     fn add_function(&self, name: &str) -> Self::Function;
 }
+
+/// AggregateBuilderMethods defines the aggregate type and value methods including struct
+/// types, variadic arguments etc. They are only implemented by the backends with aggregate
+/// types e.g., LLVM and are not used by the code generation shared by all backends, thus
+/// the backends without aggregate types e.g., Cranelift do not implement them.
+#[cfg(feature = "llvm")]
+pub trait AggregateBuilderMethods: BaseTypeMethods {
+    /// Native struct type.
+    fn struct_type(&self, els: &[Self::Type], packed: bool) -> Self::Type;
+    /// SSA va arg instruction.
+    fn va_arg(&self, list: Self::Value, ty: Self::Type) -> Self::Value;
+    /// SSA extract value instruction.
+    fn extract_value(&self, agg_val: Self::Value, idx: u32) -> Self::Value;
+    /// SSA insert value instruction.
+    fn insert_value(&self, agg_val: Self::Value, elt: Self::Value, idx: u32) -> Self::Value;
+    /// SSA struct gep instruction.
+    fn struct_gep(&self, ty: Self::Type, ptr: Self::Value, idx: u32) -> Self::Value;
+}
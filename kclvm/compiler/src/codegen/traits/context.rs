@@ -0,0 +1,159 @@
+//! Copyright 2021 The KCL Authors. All rights reserved.
+
+use std::cell::RefCell;
+use std::collections::HashSet;
+
+use kclvm_ast::ast;
+
+use crate::codegen::error::KCLError;
+use crate::codegen::BacktrackMeta;
+use crate::value;
+
+use super::ProgramCodeGen;
+
+/// ContextMethods defines the compiler states, function calls and variables of a backend
+/// context, which are used by the AST lowering shared by all backends.
+pub trait ContextMethods<'ctx>: ProgramCodeGen + Sized {
+    /// Variable is the storage of a KCL variable e.g., a stack slot or a global value.
+    type Variable: Copy;
+
+    /* Compiler states */
+
+    /// The AST program to compile.
+    fn program(&self) -> &'ctx ast::Program;
+    /// Whether to generate the code of each package into its own module.
+    fn no_link(&self) -> bool;
+    /// The imported package paths.
+    fn imported(&self) -> &RefCell<HashSet<String>>;
+    /// The local variable names of the current statement.
+    fn local_vars(&self) -> &RefCell<HashSet<String>>;
+    /// The schema types being compiled.
+    fn schema_stack(&self) -> &RefCell<Vec<value::SchemaType>>;
+    /// Whether the functions being compiled are lambda functions.
+    fn lambda_stack(&self) -> &RefCell<Vec<bool>>;
+    /// The schema expressions being compiled.
+    fn schema_expr_stack(&self) -> &RefCell<Vec<()>>;
+    /// The package paths being compiled.
+    fn pkgpath_stack(&self) -> &RefCell<Vec<String>>;
+    /// The filenames being compiled.
+    fn filename_stack(&self) -> &RefCell<Vec<String>>;
+    /// The target variable names of the current statement.
+    fn target_vars(&self) -> &RefCell<Vec<String>>;
+    /// The line of the current statement.
+    fn current_line(&self) -> &RefCell<u64>;
+    /// Schema attr backtrack meta.
+    fn backtrack_meta(&self) -> &RefCell<Option<BacktrackMeta>>;
+
+    /* Functions */
+
+    /// Get compiler default ok result
+    fn ok_result(&self) -> Result<Self::Value, KCLError>;
+    /// Build a void function call of the known function named `name`.
+    fn build_void_call(&self, name: &str, args: &[Self::Value]);
+    /// Build a function call of the known function named `name` with the return value.
+    fn build_call(&self, name: &str, args: &[Self::Value]) -> Self::Value;
+    /// Build a function call and return the return value if exists.
+    fn build_call_function(
+        &self,
+        function: Self::Function,
+        args: &[Self::Value],
+    ) -> Option<Self::Value>;
+    /// Build an indirect call of the KCL function pointer with the KCL function
+    /// signature `(ctx, args, kwargs) -> value`.
+    fn build_function_ptr_call(&self, fn_ptr: Self::Value, args: &[Self::Value]) -> Self::Value;
+    /// Push a function into the function stack and start to generate its body
+    /// from the entry block.
+    fn push_function(&self, function: Self::Function);
+    /// Pop the current function from the function stack and continue to generate
+    /// the enclosing function.
+    fn pop_function(&self) -> Result<(), KCLError>;
+    /// Add the package init function `(ctx) -> void` named `name`, which is defined
+    /// in the module of the current package in the no link mode.
+    fn add_pkg_init_function(&self, name: &str) -> Self::Function;
+    /// Declare the external package init function named `name`.
+    fn declare_pkg_init_function(&self, name: &str) -> Self::Function;
+
+    /* Native values */
+
+    /// Creates global string with initializer
+    fn native_global_string(&self, value: &str, name: &str) -> Self::Value;
+    /// Creates global string value with initializer
+    fn native_global_string_value(&self, value: &str) -> Self::Value;
+    /// Get i8 zero value
+    fn native_i8_zero(&self) -> Self::Value;
+    /// Get i8 value
+    fn native_i8(&self, v: i8) -> Self::Value;
+    /// Construct a int value using i32
+    fn native_int_value(&self, v: i32) -> Self::Value;
+
+    /* Variables */
+
+    /// Construct a global value pointer named `name`
+    fn new_global_kcl_value_ptr(&self, name: &str) -> Self::Variable;
+    /// Create a local variable in the current function initialized with the undefined value.
+    fn new_local_variable(&self) -> Self::Variable;
+    /// Create a local variable in the current function with the initial value.
+    fn new_local_variable_with_value(&self, value: Self::Value) -> Self::Variable;
+    /// Store the value into the variable.
+    fn store_to_variable(&self, var: Self::Variable, value: Self::Value);
+    /// Append a scalar value into the scope.
+    fn add_scalar(&self, scalar: Self::Value, is_schema: bool);
+    /// Append a variable into the scope
+    fn add_variable(&self, name: &str, var: Self::Variable);
+    /// Store the argument named `name` in the current scope.
+    fn store_argument_in_current_scope(&self, name: &str);
+    /// Store the variable named `name` with `value` from the current scope, return false when not found
+    fn store_variable_in_current_scope(&self, name: &str, value: Self::Value) -> bool;
+    /// Store the variable named `name` with `value` from the scope, return false when not found
+    fn store_variable(&self, name: &str, value: Self::Value) -> bool;
+    /// Resolve variable in scope, return false when not found
+    fn resolve_variable(&self, name: &str) -> bool;
+    /// Append a variable or update the existed local variable.
+    fn add_or_update_local_variable(&self, name: &str, value: Self::Value);
+    /// Append a variable or update the existed variable
+    fn add_or_update_global_variable(&self, name: &str, value: Self::Value);
+    /// Get the variable value named `name` from the scope, return Err when not found
+    fn get_variable(&self, name: &str) -> Result<Self::Value, KCLError>;
+    /// Get the variable value named `name` from the schema scope, return Err when not found
+    fn get_variable_in_schema(&self, name: &str) -> Result<Self::Value, KCLError>;
+    /// Get the variable value named `name` from the scope named `pkgpath`, return Err when not found
+    fn get_variable_in_pkgpath(&self, name: &str, pkgpath: &str) -> Result<Self::Value, KCLError>;
+    /// Get the external variable value named `name` of the package `pkgpath`, return Err when not found
+    fn get_external_variable_in_pkgpath(
+        &self,
+        name: &str,
+        pkgpath: &str,
+    ) -> Result<Self::Value, KCLError>;
+    /// Get closure map in the current scope.
+    fn get_closure_map(&self) -> Self::Value;
+
+    /* Collections */
+
+    /// Merge a dict entry including key, value, op and insert_index into the dict
+    /// without the idempotent check.
+    fn dict_merge(
+        &self,
+        dict: Self::Value,
+        key: &str,
+        value: Self::Value,
+        op: i32,
+        insert_index: i32,
+    );
+    /// default_dict(list) insert a key-value pair, and the value is a int pointer
+    fn default_collection_insert_int_pointer(
+        &self,
+        dict: Self::Value,
+        key: &str,
+        value: Self::Value,
+    );
+    /// default_dict(list) insert a key-value pair
+    fn default_collection_insert_value(&self, dict: Self::Value, key: &str, value: Self::Value);
+
+    /* Debug info */
+
+    /// Set the debug location of the following instructions to the node, nothing
+    /// to do when the backend does not emit the debug info.
+    fn debug_set_location<T>(&self, _node: &ast::Node<T>) {}
+    /// Declare the debug info of the variable named `name`.
+    fn debug_declare_variable(&self, _name: &str, _value: Self::Value) {}
+}
@@ -2,11 +2,13 @@
 
 mod backend;
 mod builder;
+mod context;
 mod r#type;
 mod value;
 
 pub use backend::*;
 pub use builder::*;
+pub use context::*;
 pub use r#type::*;
 pub use value::*;
 
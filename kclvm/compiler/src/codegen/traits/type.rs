@@ -21,8 +21,6 @@ pub trait BaseTypeMethods: BackendTypes {
     fn f32_type(&self) -> Self::Type;
     /// Native f64 type
     fn f64_type(&self) -> Self::Type;
-    /// Native struct type.
-    fn struct_type(&self, els: &[Self::Type], packed: bool) -> Self::Type;
     /// Native pointer type of `ty`.
     fn ptr_type_to(&self, ty: Self::Type) -> Self::Type;
     /// Native pointer type of `ty` with the address space.
@@ -26,6 +26,8 @@ pub trait ValueMethods: BackendTypes {
     fn unit_value(&self, v: f64, raw: i64, unit: &str) -> Self::Value;
     /// Construct a function value using a native function value.
     fn function_value(&self, function: Self::Function) -> Self::Value;
+    /// Get the native function pointer of the function.
+    fn function_ptr(&self, function: Self::Function) -> Self::Value;
     /// Construct a closure function value with the closure variable.
     fn closure_value(&self, function: Self::Function, closure: Self::Value) -> Self::Value;
     /// Construct a structure function value using native functions.
@@ -38,8 +40,8 @@ pub trait ValueMethods: BackendTypes {
     fn builtin_function_value(&self, function_name: &str) -> Self::Value;
     /// Get a global value pointer named `name`.
     fn global_value_ptr(&self, name: &str) -> Self::Value;
-    /// Get the global runtime context pointer.
-    fn global_ctx_ptr(&self) -> Self::Value;
+    /// Get the runtime context pointer of the current function.
+    fn current_runtime_ctx_ptr(&self) -> Self::Value;
 }
 
 /// DerivedValueCalculationMethods defines all value base calculation APIs.
@@ -1,6 +1,7 @@
 // Copyright 2021 The KCL Authors. All rights reserved.
 
 pub mod codegen;
+pub mod optimizer;
 pub mod value;
 
 #[macro_use]
@@ -0,0 +1,400 @@
+// Copyright 2021 The KCL Authors. All rights reserved.
+
+use std::cmp::Ordering;
+
+use kclvm_ast::{ast, walk_list_mut, walker::MutSelfMutWalker};
+use kclvm_runtime::{
+    is_f32_overflow, is_f32_overflow_add, is_f32_overflow_mul, is_f32_overflow_sub,
+    is_i32_overflow_add, is_i32_overflow_mul, is_i32_overflow_sub,
+};
+
+/// Fold the constant expressions in the AST module and prune the dead branches of
+/// the if statements and the config if entries whose conditions are constant.
+///
+/// - Before
+///
+/// ```kcl
+/// a = 1 + 2 * 3
+/// b = "x" + "y" if 1 < 2 else "z"
+/// ```
+///
+/// - After
+///
+/// ```kcl
+/// a = 7
+/// b = "xy"
+/// ```
+///
+/// Operations which may raise an error at runtime are never folded, e.g., the division
+/// by zero and the integer arithmetic out of the 32-bit range which is checked with the
+/// strict range check option, so the runtime diagnostics are preserved.
+pub fn fold_constants(module: &mut ast::Module) {
+    ConstFoldTransformer.walk_module(module);
+}
+
+/// ConstFoldTransformer folds every expression visited and prunes the dead branches.
+struct ConstFoldTransformer;
+
+impl<'ctx> MutSelfMutWalker<'ctx> for ConstFoldTransformer {
+    fn walk_if_stmt(&mut self, if_stmt: &'ctx mut ast::IfStmt) {
+        fold_expr(&mut if_stmt.cond.node);
+        prune_if_stmt(if_stmt);
+        self.walk_expr(&mut if_stmt.cond.node);
+        walk_list_mut!(self, walk_stmt, if_stmt.body);
+        walk_list_mut!(self, walk_stmt, if_stmt.orelse);
+    }
+    fn walk_expr(&mut self, expr: &'ctx mut ast::Expr) {
+        fold_expr(expr);
+        match expr {
+            ast::Expr::Identifier(identifier) => self.walk_identifier(identifier),
+            ast::Expr::Unary(unary_expr) => self.walk_unary_expr(unary_expr),
+            ast::Expr::Binary(binary_expr) => self.walk_binary_expr(binary_expr),
+            ast::Expr::If(if_expr) => self.walk_if_expr(if_expr),
+            ast::Expr::Selector(selector_expr) => self.walk_selector_expr(selector_expr),
+            ast::Expr::Call(call_expr) => self.walk_call_expr(call_expr),
+            ast::Expr::Paren(paren_expr) => self.walk_paren_expr(paren_expr),
+            ast::Expr::Quant(quant_expr) => self.walk_quant_expr(quant_expr),
+            ast::Expr::List(list_expr) => self.walk_list_expr(list_expr),
+            ast::Expr::ListIfItem(list_if_item_expr) => {
+                self.walk_list_if_item_expr(list_if_item_expr)
+            }
+            ast::Expr::ListComp(list_comp) => self.walk_list_comp(list_comp),
+            ast::Expr::Starred(starred_expr) => self.walk_starred_expr(starred_expr),
+            ast::Expr::DictComp(dict_comp) => self.walk_dict_comp(dict_comp),
+            ast::Expr::ConfigIfEntry(config_if_entry_expr) => {
+                self.walk_config_if_entry_expr(config_if_entry_expr)
+            }
+            ast::Expr::CompClause(comp_clause) => self.walk_comp_clause(comp_clause),
+            ast::Expr::Schema(schema_expr) => self.walk_schema_expr(schema_expr),
+            ast::Expr::Config(config_expr) => self.walk_config_expr(config_expr),
+            ast::Expr::Check(check) => self.walk_check_expr(check),
+            ast::Expr::Lambda(lambda) => self.walk_lambda_expr(lambda),
+            ast::Expr::Subscript(subscript) => self.walk_subscript(subscript),
+            ast::Expr::Keyword(keyword) => self.walk_keyword(keyword),
+            ast::Expr::Arguments(arguments) => self.walk_arguments(arguments),
+            ast::Expr::Compare(compare) => self.walk_compare(compare),
+            ast::Expr::NumberLit(number_lit) => self.walk_number_lit(number_lit),
+            ast::Expr::StringLit(string_lit) => self.walk_string_lit(string_lit),
+            ast::Expr::NameConstantLit(name_constant_lit) => {
+                self.walk_name_constant_lit(name_constant_lit)
+            }
+            ast::Expr::JoinedString(joined_string) => self.walk_joined_string(joined_string),
+            ast::Expr::FormattedValue(formatted_value) => {
+                self.walk_formatted_value(formatted_value)
+            }
+            ast::Expr::Missing(missing_expr) => self.walk_missing_expr(missing_expr),
+        }
+    }
+}
+
+/// Prune the dead branch of the if statement whose condition is constant. The if
+/// statement itself is kept because the schema attributes in its branches are
+/// backtracked by the if statement in the schema body, and the dead branch is kept
+/// if it declares any variable, which is declared before the statement is executed.
+fn prune_if_stmt(if_stmt: &mut ast::IfStmt) {
+    loop {
+        match Const::from_expr(&if_stmt.cond.node).map(|cond| cond.is_truthy()) {
+            Some(true) if !declares_variables(&if_stmt.orelse) => if_stmt.orelse.clear(),
+            Some(false) if !declares_variables(&if_stmt.body) => {
+                if_stmt.body.clear();
+                // Hoist the `elif` branch.
+                if if_stmt.orelse.len() == 1 && matches!(if_stmt.orelse[0].node, ast::Stmt::If(_)) {
+                    if let Some(ast::Stmt::If(mut elif_stmt)) = if_stmt.orelse.pop().map(|s| s.node)
+                    {
+                        fold_expr(&mut elif_stmt.cond.node);
+                        *if_stmt = elif_stmt;
+                        continue;
+                    }
+                }
+            }
+            _ => {}
+        }
+        break;
+    }
+}
+
+/// Whether the statements declare any variable or schema attribute.
+fn declares_variables(stmts: &[ast::NodeRef<ast::Stmt>]) -> bool {
+    stmts.iter().any(|stmt| match &stmt.node {
+        ast::Stmt::Expr(_) | ast::Stmt::Assert(_) => false,
+        ast::Stmt::If(if_stmt) => {
+            declares_variables(&if_stmt.body) || declares_variables(&if_stmt.orelse)
+        }
+        _ => true,
+    })
+}
+
+/// Fold the expression if it is a constant expression. The operands are folded
+/// before the operation, and other expressions are left to the walker.
+fn fold_expr(expr: &mut ast::Expr) {
+    let folded = match expr {
+        ast::Expr::Paren(paren_expr) => {
+            fold_expr(&mut paren_expr.expr.node);
+            Const::from_expr(&paren_expr.expr.node).map(Const::into_expr)
+        }
+        ast::Expr::Unary(unary_expr) => {
+            fold_expr(&mut unary_expr.operand.node);
+            Const::from_expr(&unary_expr.operand.node)
+                .and_then(|operand| operand.unary(&unary_expr.op))
+                .map(Const::into_expr)
+        }
+        ast::Expr::Binary(binary_expr) => {
+            fold_expr(&mut binary_expr.left.node);
+            fold_expr(&mut binary_expr.right.node);
+            let left = Const::from_expr(&binary_expr.left.node);
+            match (&binary_expr.op, left) {
+                // The logic operators return the left operand or the right operand.
+                (ast::BinOrCmpOp::Bin(ast::BinOp::And), Some(left)) => Some(if left.is_truthy() {
+                    binary_expr.right.node.clone()
+                } else {
+                    left.into_expr()
+                }),
+                (ast::BinOrCmpOp::Bin(ast::BinOp::Or), Some(left)) => Some(if left.is_truthy() {
+                    left.into_expr()
+                } else {
+                    binary_expr.right.node.clone()
+                }),
+                (op, Some(left)) => Const::from_expr(&binary_expr.right.node)
+                    .and_then(|right| match op {
+                        ast::BinOrCmpOp::Bin(op) => left.binary(op, &right),
+                        ast::BinOrCmpOp::Cmp(op) => left.compare(op, &right).map(Const::Bool),
+                    })
+                    .map(Const::into_expr),
+                (_, None) => None,
+            }
+        }
+        ast::Expr::Compare(compare) => {
+            fold_expr(&mut compare.left.node);
+            for comparator in compare.comparators.iter_mut() {
+                fold_expr(&mut comparator.node);
+            }
+            let mut left = Const::from_expr(&compare.left.node);
+            let mut result = Some(true);
+            for (op, comparator) in compare.ops.iter().zip(&compare.comparators) {
+                let right = Const::from_expr(&comparator.node);
+                result = match (&left, &right, result) {
+                    (Some(l), Some(r), Some(result)) => l.compare(op, r).map(|v| result && v),
+                    _ => None,
+                };
+                left = right;
+            }
+            result.map(|result| Const::Bool(result).into_expr())
+        }
+        ast::Expr::If(if_expr) => {
+            fold_expr(&mut if_expr.cond.node);
+            fold_expr(&mut if_expr.body.node);
+            fold_expr(&mut if_expr.orelse.node);
+            Const::from_expr(&if_expr.cond.node).map(|cond| {
+                if cond.is_truthy() {
+                    if_expr.body.node.clone()
+                } else {
+                    if_expr.orelse.node.clone()
+                }
+            })
+        }
+        ast::Expr::ConfigIfEntry(config_if_entry_expr) => {
+            fold_expr(&mut config_if_entry_expr.if_cond.node);
+            prune_config_if_entry_expr(config_if_entry_expr)
+        }
+        _ => None,
+    };
+    if let Some(folded) = folded {
+        *expr = folded;
+    }
+}
+
+/// Prune the dead branch of the config if entry whose condition is constant, and
+/// return the `elif` branch expression if it replaces the config if entry.
+fn prune_config_if_entry_expr(
+    config_if_entry_expr: &mut ast::ConfigIfEntryExpr,
+) -> Option<ast::Expr> {
+    let cond = Const::from_expr(&config_if_entry_expr.if_cond.node)?;
+    if cond.is_truthy() {
+        config_if_entry_expr.orelse = None;
+        return None;
+    }
+    config_if_entry_expr.items.clear();
+    let is_branch = matches!(
+        config_if_entry_expr
+            .orelse
+            .as_deref()
+            .map(|orelse| &orelse.node),
+        Some(ast::Expr::ConfigIfEntry(_) | ast::Expr::Config(_))
+    );
+    if !is_branch {
+        return None;
+    }
+    match config_if_entry_expr.orelse.take().map(|orelse| orelse.node) {
+        Some(ast::Expr::ConfigIfEntry(elif_expr)) => {
+            let mut elif = ast::Expr::ConfigIfEntry(elif_expr);
+            fold_expr(&mut elif);
+            Some(elif)
+        }
+        // The `else` branch is evaluated as the config entries of a truthy condition.
+        Some(ast::Expr::Config(config_expr)) => {
+            config_if_entry_expr.if_cond.node = Const::Bool(true).into_expr();
+            config_if_entry_expr.items = config_expr.items;
+            None
+        }
+        _ => None,
+    }
+}
+
+/// Const is the value of a literal expression which can be folded.
+#[derive(Debug, Clone)]
+enum Const {
+    Int(i64),
+    Float(f64),
+    Str(String),
+    Bool(bool),
+    None,
+}
+
+impl Const {
+    /// Get the constant value of the literal expression. The number literals with
+    /// the unit suffix are not constant because they are converted at runtime.
+    fn from_expr(expr: &ast::Expr) -> Option<Const> {
+        match expr {
+            ast::Expr::NumberLit(number_lit) if number_lit.binary_suffix.is_none() => {
+                match number_lit.value {
+                    ast::NumberLitValue::Int(v) => Some(Const::Int(v)),
+                    ast::NumberLitValue::Float(v) => Some(Const::Float(v)),
+                }
+            }
+            ast::Expr::StringLit(string_lit) => Some(Const::Str(string_lit.value.clone())),
+            ast::Expr::NameConstantLit(name_constant_lit) => match name_constant_lit.value {
+                ast::NameConstant::True => Some(Const::Bool(true)),
+                ast::NameConstant::False => Some(Const::Bool(false)),
+                ast::NameConstant::None => Some(Const::None),
+                ast::NameConstant::Undefined => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Convert the constant value into a literal expression.
+    fn into_expr(self) -> ast::Expr {
+        match self {
+            Const::Int(v) => ast::Expr::NumberLit(ast::NumberLit {
+                binary_suffix: None,
+                value: ast::NumberLitValue::Int(v),
+            }),
+            Const::Float(v) => ast::Expr::NumberLit(ast::NumberLit {
+                binary_suffix: None,
+                value: ast::NumberLitValue::Float(v),
+            }),
+            Const::Str(v) => ast::Expr::StringLit(ast::StringLit {
+                is_long_string: false,
+                raw_value: format!("{:?}", v),
+                value: v,
+            }),
+            Const::Bool(v) => ast::Expr::NameConstantLit(ast::NameConstantLit {
+                value: if v {
+                    ast::NameConstant::True
+                } else {
+                    ast::NameConstant::False
+                },
+            }),
+            Const::None => ast::Expr::NameConstantLit(ast::NameConstantLit {
+                value: ast::NameConstant::None,
+            }),
+        }
+    }
+
+    fn is_truthy(&self) -> bool {
+        match self {
+            Const::Int(v) => *v != 0,
+            Const::Float(v) => *v != 0.0,
+            Const::Str(v) => !v.is_empty(),
+            Const::Bool(v) => *v,
+            Const::None => false,
+        }
+    }
+
+    fn unary(self, op: &ast::UnaryOp) -> Option<Const> {
+        match (op, self) {
+            (ast::UnaryOp::Not, v) => Some(Const::Bool(!v.is_truthy())),
+            (ast::UnaryOp::UAdd, v @ (Const::Int(_) | Const::Float(_))) => Some(v),
+            (ast::UnaryOp::USub, Const::Int(v)) if !is_i32_overflow_sub(0, v) => {
+                Some(Const::Int(-v))
+            }
+            (ast::UnaryOp::USub, Const::Float(v)) if !is_f32_overflow(v) => Some(Const::Float(-v)),
+            (ast::UnaryOp::Invert, Const::Int(v)) => Some(Const::Int(!v)),
+            _ => None,
+        }
+    }
+
+    fn binary(&self, op: &ast::BinOp, right: &Const) -> Option<Const> {
+        match (op, self, right) {
+            (ast::BinOp::Add, Const::Str(l), Const::Str(r)) => Some(Const::Str(format!("{l}{r}"))),
+            (ast::BinOp::Add, Const::Int(l), Const::Int(r)) if !is_i32_overflow_add(*l, *r) => {
+                Some(Const::Int(l + r))
+            }
+            (ast::BinOp::Sub, Const::Int(l), Const::Int(r)) if !is_i32_overflow_sub(*l, *r) => {
+                Some(Const::Int(l - r))
+            }
+            (ast::BinOp::Mul, Const::Int(l), Const::Int(r)) if !is_i32_overflow_mul(*l, *r) => {
+                Some(Const::Int(l * r))
+            }
+            (ast::BinOp::BitAnd, Const::Int(l), Const::Int(r)) => Some(Const::Int(l & r)),
+            (ast::BinOp::BitOr, Const::Int(l), Const::Int(r)) => Some(Const::Int(l | r)),
+            (ast::BinOp::BitXor, Const::Int(l), Const::Int(r)) => Some(Const::Int(l ^ r)),
+            // The integer operations out of the 32-bit range are not folded.
+            (_, Const::Int(_), Const::Int(_)) => None,
+            (ast::BinOp::Add | ast::BinOp::Sub | ast::BinOp::Mul, l, r) => {
+                let (l, r) = (l.as_float()?, r.as_float()?);
+                let (overflow, v) = match op {
+                    ast::BinOp::Add => (is_f32_overflow_add(l, r), l + r),
+                    ast::BinOp::Sub => (is_f32_overflow_sub(l, r), l - r),
+                    _ => (is_f32_overflow_mul(l, r), l * r),
+                };
+                (!overflow).then_some(Const::Float(v))
+            }
+            _ => None,
+        }
+    }
+
+    fn compare(&self, op: &ast::CmpOp, right: &Const) -> Option<bool> {
+        let ordering = match (self, right) {
+            (Const::Int(l), Const::Int(r)) => l.cmp(r),
+            (Const::Str(l), Const::Str(r)) => l.cmp(r),
+            (l, r) if l.as_float().is_some() && r.as_float().is_some() => {
+                l.as_float()?.partial_cmp(&r.as_float()?)?
+            }
+            // Only the equality of the same type constants is folded.
+            (Const::Bool(l), Const::Bool(r)) => {
+                return match op {
+                    ast::CmpOp::Eq => Some(l == r),
+                    ast::CmpOp::NotEq => Some(l != r),
+                    _ => None,
+                }
+            }
+            (Const::None, Const::None) => {
+                return match op {
+                    ast::CmpOp::Eq => Some(true),
+                    ast::CmpOp::NotEq => Some(false),
+                    _ => None,
+                }
+            }
+            _ => return None,
+        };
+        match op {
+            ast::CmpOp::Eq => Some(ordering == Ordering::Equal),
+            ast::CmpOp::NotEq => Some(ordering != Ordering::Equal),
+            ast::CmpOp::Lt => Some(ordering == Ordering::Less),
+            ast::CmpOp::LtE => Some(ordering != Ordering::Greater),
+            ast::CmpOp::Gt => Some(ordering == Ordering::Greater),
+            ast::CmpOp::GtE => Some(ordering != Ordering::Less),
+            _ => None,
+        }
+    }
+
+    /// Get the float value of the number constant.
+    fn as_float(&self) -> Option<f64> {
+        match self {
+            Const::Int(v) => Some(*v as f64),
+            Const::Float(v) => Some(*v),
+            _ => None,
+        }
+    }
+}
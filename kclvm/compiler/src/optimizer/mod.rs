@@ -0,0 +1,20 @@
+// Copyright 2021 The KCL Authors. All rights reserved.
+
+mod const_fold;
+
+use kclvm_ast::ast;
+
+pub use const_fold::fold_constants;
+
+/// Optimize the resolved AST program before the code generation.
+///
+/// The optimization passes only rewrite the expressions and statements whose values
+/// are known at compile time, thus the program must be resolved and its diagnostics
+/// must be emitted before, and the program output and runtime errors are unchanged.
+pub fn optimize_program(program: &mut ast::Program) {
+    for modules in program.pkgs.values_mut() {
+        for module in modules.iter_mut() {
+            fold_constants(module);
+        }
+    }
+}
@@ -4,9 +4,9 @@ use super::modfile::KCL_FILE_SUFFIX;
 use crypto::digest::Digest;
 use crypto::md5::Md5;
 use fslock::LockFile;
-use kclvm_utils::pkgpath::parse_external_pkg_name;
+use kclvm_ast::ast;
 use serde::{de::DeserializeOwned, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::error;
 use std::fs::{create_dir_all, File};
 use std::io::{Read, Write};
@@ -35,66 +35,62 @@ impl Default for CacheOption {
     }
 }
 
-/// Load pkg cache.
-pub fn load_pkg_cache<T>(
-    root: &str,
-    target: &str,
-    pkgpath: &str,
-    option: CacheOption,
-    external_pkgs: &HashMap<String, String>,
-) -> Option<T>
-where
-    T: DeserializeOwned + Default,
-{
-    if root.is_empty() || pkgpath.is_empty() {
-        None
-    } else {
-        let filename = get_cache_filename(root, target, pkgpath, Some(&option.cache_dir));
-        if !Path::new(&filename).exists() {
-            None
-        } else {
-            // Compare the md5 using cache
-            let real_path = get_pkg_realpath_from_pkgpath(root, pkgpath);
-            // If the file exists and it is an internal package or an external package,
-            // Check the cache info.
-            let pkg_name = parse_external_pkg_name(pkgpath).ok()?;
-            if Path::new(&real_path).exists()
-                || (external_pkgs.get(&pkg_name).is_some()
-                    && Path::new(external_pkgs.get(&pkg_name)?).exists())
-            {
-                let cache_info = read_info_cache(root, target, Some(&option.cache_dir));
-                let relative_path = real_path.replacen(root, ".", 1);
-                match cache_info.get(&relative_path) {
-                    Some(path_info_in_cache) => {
-                        if get_cache_info(&real_path).ne(path_info_in_cache) {
-                            return None;
-                        }
-                    }
-                    None => return None,
-                };
-            }
-            load_data_from_file(&filename)
-        }
+/// Get the content-addressed cache keys of all the packages in the program.
+///
+/// The key of a package is the hash of its sources, the keys of its dependencies,
+/// the compiler version and the compile `options`, thus editing a package only
+/// invalidates the cache of the package itself and the packages depending on it.
+pub fn get_pkg_cache_keys(program: &ast::Program, options: &str) -> HashMap<String, String> {
+    let mut keys = HashMap::new();
+    for pkgpath in program.pkgs.keys() {
+        get_pkg_cache_key(program, pkgpath, options, &mut keys);
     }
+    keys
 }
 
-/// Save pkg cache.
-pub fn save_pkg_cache<T>(root: &str, target: &str, pkgpath: &str, data: T, option: CacheOption)
-where
-    T: Serialize,
-{
-    if root.is_empty() || pkgpath.is_empty() {
-        return;
+fn get_pkg_cache_key(
+    program: &ast::Program,
+    pkgpath: &str,
+    options: &str,
+    keys: &mut HashMap<String, String>,
+) -> String {
+    if let Some(key) = keys.get(pkgpath) {
+        return key.clone();
     }
-    let dst_filename = get_cache_filename(root, target, pkgpath, Some(&option.cache_dir));
-    let real_path = get_pkg_realpath_from_pkgpath(root, pkgpath);
-    if Path::new(&real_path).exists() {
-        write_info_cache(root, target, Some(&option.cache_dir), &real_path).unwrap();
+    // Stop the recursion on circular imports, which are reported by the resolver.
+    keys.insert(pkgpath.to_string(), String::new());
+    let mut md5 = Md5::new();
+    md5.input_str(version::VERSION);
+    md5.input_str(version::CHECK_SUM);
+    md5.input_str(options);
+    md5.input_str(pkgpath);
+    let mut modules: Vec<&ast::Module> = program
+        .pkgs
+        .get(pkgpath)
+        .map(|modules| modules.iter().collect())
+        .unwrap_or_default();
+    modules.sort_by(|a, b| a.filename.cmp(&b.filename));
+    let mut deps = BTreeSet::new();
+    for module in modules {
+        // Hash the module AST instead of the file content, because the AST
+        // may be changed by the overrides and the in-memory code list.
+        md5.input_str(&serde_json::to_string(module).unwrap_or_default());
+        for stmt in &module.body {
+            if let ast::Stmt::Import(import_stmt) = &stmt.node {
+                deps.insert(import_stmt.path.clone());
+            }
+        }
+    }
+    for dep in deps {
+        if program.pkgs.contains_key(&dep) {
+            let dep_key = get_pkg_cache_key(program, &dep, options, keys);
+            md5.input_str(&dep);
+            md5.input_str(&dep_key);
+        }
     }
-    let cache_dir = get_cache_dir(root, Some(&option.cache_dir));
-    create_dir_all(&cache_dir).unwrap();
-    let tmp_filename = temp_file(&cache_dir, pkgpath);
-    save_data_to_file(&dst_filename, &tmp_filename, data)
+    let key = md5.result_str();
+    keys.insert(pkgpath.to_string(), key.clone());
+    key
 }
 
 #[inline]
@@ -1,5 +1,8 @@
-use std::{env, path::PathBuf};
+use std::{collections::HashMap, env, path::PathBuf};
 
+use kclvm_ast::ast;
+
+use crate::cache::get_pkg_cache_keys;
 use crate::modfile::{get_vendor_home, KCL_PKG_PATH};
 
 #[test]
@@ -20,3 +23,56 @@ fn test_vendor_home() {
         .unwrap();
     assert_eq!(get_vendor_home(), kpm_home.display().to_string())
 }
+
+fn new_module(pkgpath: &str, imports: &[&str]) -> ast::Module {
+    ast::Module {
+        filename: format!("{}.k", pkgpath),
+        pkg: pkgpath.to_string(),
+        doc: "".to_string(),
+        name: pkgpath.to_string(),
+        body: imports
+            .iter()
+            .map(|path| {
+                Box::new(ast::Node::dummy_node(ast::Stmt::Import(ast::ImportStmt {
+                    path: path.to_string(),
+                    rawpath: path.to_string(),
+                    name: path.to_string(),
+                    asname: None,
+                    pkg_name: kclvm_ast::MAIN_PKG.to_string(),
+                })))
+            })
+            .collect(),
+        comments: vec![],
+    }
+}
+
+#[test]
+fn test_pkg_cache_keys() {
+    let mut pkgs = HashMap::new();
+    pkgs.insert(
+        kclvm_ast::MAIN_PKG.to_string(),
+        vec![new_module(kclvm_ast::MAIN_PKG, &["a", "c"])],
+    );
+    pkgs.insert("a".to_string(), vec![new_module("a", &["b"])]);
+    pkgs.insert("b".to_string(), vec![new_module("b", &[])]);
+    pkgs.insert("c".to_string(), vec![new_module("c", &[])]);
+    let mut program = ast::Program {
+        root: ".".to_string(),
+        main: kclvm_ast::MAIN_PKG.to_string(),
+        pkgs,
+    };
+    let keys = get_pkg_cache_keys(&program, "");
+    assert_eq!(keys, get_pkg_cache_keys(&program, ""));
+    // Editing the leaf package `b` only changes the keys of `b` and its dependents.
+    program.pkgs.get_mut("b").unwrap()[0].doc = "changed".to_string();
+    let new_keys = get_pkg_cache_keys(&program, "");
+    for pkgpath in [kclvm_ast::MAIN_PKG, "a", "b"] {
+        assert_ne!(keys[pkgpath], new_keys[pkgpath], "{pkgpath}");
+    }
+    assert_eq!(keys["c"], new_keys["c"]);
+    // Changing the compile options changes all the keys.
+    let option_keys = get_pkg_cache_keys(&program, "options");
+    for (pkgpath, key) in &new_keys {
+        assert_ne!(key, &option_keys[pkgpath], "{pkgpath}");
+    }
+}
@@ -46,6 +46,7 @@ register_errors! {
     E2L28: ErrorKind::UniqueKeyError, include_str!("./error_codes/E2L28.md"),
     E2D34: ErrorKind::IllegalInheritError, include_str!("./error_codes/E2D34.md"),
     E3M38: ErrorKind::EvaluationError, include_str!("./error_codes/E2D34.md"),
+    E3M39: ErrorKind::RecursionError, include_str!("./error_codes/E3M39.md"),
 }
 
 // Error messages for WXXXX errors. Each message should start and end with a
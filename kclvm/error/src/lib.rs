@@ -19,7 +19,7 @@ use compiler_base_error::{
 use compiler_base_session::{Session, SessionDiagnostic};
 use compiler_base_span::{span::new_byte_pos, Span};
 use indexmap::IndexSet;
-use kclvm_runtime::PanicInfo;
+use kclvm_runtime::{ErrType, PanicInfo};
 use std::{any::Any, sync::Arc};
 
 pub use diagnostic::{Diagnostic, DiagnosticId, Level, Message, Position, Style};
@@ -227,6 +227,12 @@ impl From<PanicInfo> for Diagnostic {
         } else {
             &panic_info.kcl_arg_msg
         };
+        // Recursion errors raised by the runtime limits are emitted with the error code.
+        let code = if panic_info.err_type_code == ErrType::RecursionError_TYPE as i32 {
+            Some(DiagnosticId::Error(E3M39.kind))
+        } else {
+            None
+        };
 
         let mut diag = if panic_info.backtrace.is_empty() {
             Diagnostic::new_with_code(
@@ -238,7 +244,7 @@ impl From<PanicInfo> for Diagnostic {
                     line: panic_info.kcl_line as u64,
                     column: None,
                 },
-                None,
+                code.clone(),
             )
         } else {
             let mut backtrace_msg = "backtrace:\n".to_string();
@@ -263,7 +269,7 @@ impl From<PanicInfo> for Diagnostic {
                     line: panic_info.kcl_line as u64,
                     column: None,
                 },
-                None,
+                code.clone(),
             )
         };
 
@@ -279,7 +285,7 @@ impl From<PanicInfo> for Diagnostic {
                 line: panic_info.kcl_config_meta_line as u64,
                 column: Some(panic_info.kcl_config_meta_col as u64),
             },
-            None,
+            code,
         );
         config_meta_diag.messages.append(&mut diag.messages);
         config_meta_diag
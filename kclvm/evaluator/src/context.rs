@@ -0,0 +1,146 @@
+// Copyright 2021 The KCL Authors. All rights reserved.
+
+use kclvm_ast::ast;
+use kclvm_runtime::ValueRef;
+
+use crate::error as kcl_error;
+use crate::schema::SchemaEvalContextRef;
+use crate::{EvalResult, Evaluator};
+
+impl<'ctx> Evaluator<'ctx> {
+    /// Current package path
+    #[inline]
+    pub(crate) fn current_pkgpath(&self) -> String {
+        self.pkgpath_stack
+            .borrow()
+            .last()
+            .expect(kcl_error::INTERNAL_ERROR_MSG)
+            .to_string()
+    }
+
+    /// Current filename
+    #[inline]
+    pub(crate) fn current_filename(&self) -> String {
+        self.filename_stack
+            .borrow()
+            .last()
+            .expect(kcl_error::INTERNAL_ERROR_MSG)
+            .to_string()
+    }
+
+    #[inline]
+    pub(crate) fn push_pkgpath(&self, pkgpath: &str) {
+        self.pkgpath_stack.borrow_mut().push(pkgpath.to_string());
+    }
+
+    #[inline]
+    pub(crate) fn pop_pkgpath(&self) {
+        self.pkgpath_stack.borrow_mut().pop();
+    }
+
+    #[inline]
+    pub(crate) fn push_filename(&self, filename: &str) {
+        self.filename_stack.borrow_mut().push(filename.to_string());
+    }
+
+    #[inline]
+    pub(crate) fn pop_filename(&self) {
+        self.filename_stack.borrow_mut().pop();
+    }
+
+    /// Get the current schema evaluation context.
+    #[inline]
+    pub(crate) fn schema_ctx(&self) -> SchemaEvalContextRef {
+        self.schema_stack
+            .borrow()
+            .last()
+            .expect(kcl_error::INTERNAL_ERROR_MSG)
+            .clone()
+    }
+
+    /// Whether the evaluator is evaluating the code in a schema body.
+    #[inline]
+    pub(crate) fn is_in_schema(&self) -> bool {
+        !self.schema_stack.borrow().is_empty()
+    }
+
+    /// Whether the evaluator is evaluating the code in a schema body or a schema expression.
+    #[inline]
+    pub(crate) fn is_in_schema_or_schema_expr(&self) -> bool {
+        self.is_in_schema() || *self.schema_expr_depth.borrow() > 0
+    }
+
+    /// Whether the evaluator is evaluating the code in a lambda body.
+    #[inline]
+    pub(crate) fn is_in_lambda(&self) -> bool {
+        *self
+            .lambda_stack
+            .borrow()
+            .last()
+            .expect(kcl_error::INTERNAL_ERROR_MSG)
+    }
+
+    /// Whether the schema attribute backtracking has been stopped.
+    #[inline]
+    pub(crate) fn is_backtrack_stop(&self) -> bool {
+        match self.backtrack_meta.borrow().as_ref() {
+            Some(backtrack_meta) => backtrack_meta.stop,
+            None => false,
+        }
+    }
+
+    /// Update the backtrack meta when the schema attribute `name` is set, and return
+    /// whether the backtracking is stopped.
+    pub(crate) fn update_backtrack_meta(&self, name: &str) -> bool {
+        if let Some(backtrack_meta) = self.backtrack_meta.borrow_mut().as_mut() {
+            if name == backtrack_meta.target {
+                backtrack_meta.count += 1;
+                if backtrack_meta.count >= backtrack_meta.level {
+                    backtrack_meta.stop = true;
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    /// The ok result of the evaluation.
+    #[inline]
+    pub(crate) fn ok_result(&self) -> EvalResult {
+        Ok(ValueRef::undefined())
+    }
+
+    /// Update the runtime context filename with the AST node filename.
+    #[inline]
+    pub(crate) fn update_ctx_filename<T>(&self, node: &'ctx ast::Node<T>) {
+        if !node.filename.is_empty() {
+            self.runtime_ctx
+                .borrow_mut()
+                .set_kcl_filename(&node.filename);
+        }
+    }
+
+    /// Update the runtime context line and column with the AST node position.
+    #[inline]
+    pub(crate) fn update_ctx_line_col<T>(&self, node: &'ctx ast::Node<T>) {
+        *self.current_line.borrow_mut() = node.line;
+        self.runtime_ctx
+            .borrow_mut()
+            .set_kcl_line_col(node.line as i32, 0);
+    }
+
+    /// Update the runtime context line with the current evaluation line.
+    #[inline]
+    pub(crate) fn update_ctx_current_line(&self) {
+        let current_line = *self.current_line.borrow();
+        self.runtime_ctx
+            .borrow_mut()
+            .set_kcl_line_col(current_line as i32, 0);
+    }
+
+    /// Update the runtime context pkgpath.
+    #[inline]
+    pub(crate) fn update_ctx_pkgpath(&self, pkgpath: &str) {
+        self.runtime_ctx.borrow_mut().set_kcl_pkgpath(pkgpath);
+    }
+}
@@ -0,0 +1,129 @@
+// Copyright 2021 The KCL Authors. All rights reserved.
+
+use indexmap::IndexMap;
+use kclvm_ast::ast;
+use kclvm_runtime::{ValueRef, MAIN_PKG_PATH};
+
+use crate::error as kcl_error;
+use crate::proxy::{Frame, Proxy};
+use crate::{Evaluator, GLOBAL_LEVEL};
+
+/// Debugger is notified before every statement is evaluated, and it can block
+/// the evaluation to wait for the user commands, e.g., at a line breakpoint.
+pub trait Debugger {
+    /// Called before the statement `stmt` is evaluated, the call stack and the
+    /// variables can be inspected with [`Evaluator::debug_frames`] and
+    /// [`Evaluator::debug_scopes`].
+    fn on_stmt(&self, evaluator: &Evaluator, stmt: &ast::Node<ast::Stmt>);
+}
+
+/// A function call frame on the evaluation stack.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DebugFrame {
+    /// The frame name, e.g., the schema runtime type, `lambda` or the main package.
+    pub name: String,
+    pub pkgpath: String,
+    pub filename: String,
+    /// The line of the statement being evaluated in the frame.
+    pub line: u64,
+}
+
+/// The named variables visible in the current statement, e.g., the local variables.
+#[derive(Clone, Debug)]
+pub struct DebugScope {
+    pub name: String,
+    pub variables: IndexMap<String, ValueRef>,
+}
+
+impl<'ctx> Evaluator<'ctx> {
+    /// Set the debugger which is notified before every statement is evaluated.
+    pub fn with_debugger(mut self, debugger: &'ctx dyn Debugger) -> Self {
+        self.debugger = Some(debugger);
+        self.debug_frames = std::cell::RefCell::new(vec![DebugFrame {
+            name: MAIN_PKG_PATH.to_string(),
+            pkgpath: MAIN_PKG_PATH.to_string(),
+            filename: "".to_string(),
+            line: 0,
+        }]);
+        self
+    }
+
+    /// Get the call frames from the outermost to the innermost.
+    pub fn debug_frames(&self) -> Vec<DebugFrame> {
+        self.debug_frames.borrow().clone()
+    }
+
+    /// Get the local variables, the schema attributes and the global variables
+    /// visible in the current statement.
+    pub fn debug_scopes(&self) -> Vec<DebugScope> {
+        let mut scopes = vec![DebugScope {
+            name: "Locals".to_string(),
+            variables: self.get_local_variables(),
+        }];
+        if self.is_in_schema() {
+            let value = self.schema_ctx().borrow().value.clone();
+            let mut variables = IndexMap::new();
+            if value.is_config() {
+                for (name, value) in &value.as_dict_ref().values {
+                    variables.insert(name.to_string(), value.clone());
+                }
+            }
+            scopes.push(DebugScope {
+                name: "Schema".to_string(),
+                variables,
+            });
+        }
+        let current_pkgpath = self.current_pkgpath();
+        let pkg_scopes = self.pkg_scopes.borrow();
+        let scope = pkg_scopes
+            .get(&current_pkgpath)
+            .and_then(|scopes| scopes.get(GLOBAL_LEVEL))
+            .expect(kcl_error::INTERNAL_ERROR_MSG);
+        scopes.push(DebugScope {
+            name: "Globals".to_string(),
+            variables: scope.variables.clone(),
+        });
+        scopes
+    }
+
+    /// Update the position of the innermost frame and notify the debugger.
+    pub(crate) fn debug_stmt(&self, stmt: &'ctx ast::Node<ast::Stmt>) {
+        if let Some(debugger) = self.debugger {
+            if let Some(frame) = self.debug_frames.borrow_mut().last_mut() {
+                frame.pkgpath = self.current_pkgpath();
+                frame.filename = if stmt.filename.is_empty() {
+                    self.current_filename()
+                } else {
+                    stmt.filename.clone()
+                };
+                frame.line = stmt.line;
+            }
+            debugger.on_stmt(self, stmt);
+        }
+    }
+
+    /// Push the call frame of the function `frame` when debugging.
+    pub(crate) fn debug_enter_frame(&self, frame: &Frame<'ctx>) {
+        if self.debugger.is_some() {
+            let name = match &frame.proxy {
+                Proxy::Lambda(_) => "lambda".to_string(),
+                Proxy::Schema(caller) => caller.runtime_type.clone(),
+                Proxy::Rule(caller) => caller.runtime_type.clone(),
+                Proxy::SchemaAttr(caller) => format!("{}.{}", caller.runtime_type, caller.name),
+            };
+            self.debug_frames.borrow_mut().push(DebugFrame {
+                name,
+                pkgpath: frame.pkgpath.clone(),
+                filename: frame.filename.clone(),
+                line: 0,
+            });
+        }
+    }
+
+    /// Pop the call frame pushed by `debug_enter_frame`.
+    pub(crate) fn debug_leave_frame(&self) {
+        if self.debugger.is_some() {
+            self.debug_frames.borrow_mut().pop();
+        }
+    }
+}
@@ -0,0 +1,7 @@
+// Copyright 2021 The KCL Authors. All rights reserved.
+
+pub(crate) const RUNTIME_ERROR_MSG: &str = "Runtime error";
+pub(crate) const INTERNAL_ERROR_MSG: &str = "Internal error, please report a bug to us";
+pub(crate) const INVALID_JOINED_STR_MSG: &str = "Invalid AST JoinedString value";
+pub(crate) const INVALID_STR_INTERPOLATION_SPEC_MSG: &str =
+    "Invalid string interpolation format specification";
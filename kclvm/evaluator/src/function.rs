@@ -0,0 +1,142 @@
+// Copyright 2021 The KCL Authors. All rights reserved.
+
+use std::ffi::CString;
+
+use kclvm_runtime::{
+    kclvm_value_function_invoke, ptr_as_ref, schema_config_meta, BacktraceFrame, ProfileSpan,
+    ValueRef, PROFILE_KIND_LAMBDA,
+};
+
+use crate::error as kcl_error;
+use crate::proxy::{LambdaCaller, Proxy};
+use crate::Evaluator;
+
+impl<'ctx> Evaluator<'ctx> {
+    /// Invoke the function value `func` with the arguments `args` and `kwargs`.
+    ///
+    /// Proxy functions including lambdas, schemas and rules are evaluated with
+    /// the AST, and the other builtin, system module and plugin functions are
+    /// invoked with the runtime function pointers.
+    pub(crate) fn invoke_function(
+        &self,
+        func: &ValueRef,
+        args: &ValueRef,
+        kwargs: &ValueRef,
+    ) -> ValueRef {
+        if !func.is_func() {
+            return ValueRef::none();
+        }
+        let (proxy, name) = {
+            let func_value = func.as_function();
+            (func_value.proxy, func_value.name.clone())
+        };
+        match proxy {
+            Some(index) => {
+                let frame = self.get_frame(index);
+                match &frame.proxy {
+                    Proxy::Lambda(lambda) => {
+                        // Lambdas are anonymous, so they are named by their positions.
+                        let span = ProfileSpan::begin(
+                            &mut self.runtime_ctx.borrow_mut(),
+                            PROFILE_KIND_LAMBDA,
+                            || match lambda.node.body.first() {
+                                Some(stmt) => format!("lambda@{}:{}", stmt.filename, stmt.line),
+                                None => "lambda".to_string(),
+                            },
+                        );
+                        let now_meta_info = {
+                            let mut ctx = self.runtime_ctx.borrow_mut();
+                            ctx.enter_call();
+                            if ctx.cfg.debug_mode {
+                                let frame = BacktraceFrame::from_panic_info(&ctx.panic_info);
+                                ctx.backtrace.push(frame);
+                                ctx.panic_info.kcl_func = name;
+                            }
+                            ctx.panic_info.clone()
+                        };
+                        let value = self
+                            .with_frame(&frame, true, || self.lambda_body(lambda, args, kwargs));
+                        let mut ctx = self.runtime_ctx.borrow_mut();
+                        if ctx.cfg.debug_mode {
+                            ctx.backtrace.pop();
+                        }
+                        drop(span);
+                        ctx.leave_call();
+                        ctx.panic_info = now_meta_info;
+                        value
+                    }
+                    Proxy::Schema(_) | Proxy::Rule(_) => {
+                        // Call the schema constructor directly, e.g., `Person()`.
+                        let config_meta = {
+                            let ctx = self.runtime_ctx.borrow();
+                            schema_config_meta(
+                                &ctx.panic_info.kcl_file,
+                                ctx.panic_info.kcl_line as u64,
+                                ctx.panic_info.kcl_col as u64,
+                            )
+                        };
+                        let value = self.construct_schema_value(
+                            func,
+                            args,
+                            kwargs,
+                            &ValueRef::dict(None),
+                            &config_meta,
+                            &self.current_pkgpath(),
+                        );
+                        if !self.is_in_schema() {
+                            value.schema_check_attr_optional(
+                                &mut self.runtime_ctx.borrow_mut(),
+                                true,
+                            );
+                        }
+                        value
+                    }
+                    Proxy::SchemaAttr(_) => panic!("{}", kcl_error::INTERNAL_ERROR_MSG),
+                }
+            }
+            None => {
+                let pkgpath =
+                    CString::new(self.current_pkgpath()).expect(kcl_error::INTERNAL_ERROR_MSG);
+                let is_in_schema = ValueRef::bool(self.is_in_schema_or_schema_expr());
+                let mut args = args.clone();
+                unsafe {
+                    let value = kclvm_value_function_invoke(
+                        func,
+                        &mut **self.runtime_ctx.borrow_mut(),
+                        &mut args,
+                        kwargs,
+                        pkgpath.as_ptr(),
+                        &is_in_schema,
+                    );
+                    ptr_as_ref(value).clone()
+                }
+            }
+        }
+    }
+
+    /// The lambda function body.
+    fn lambda_body(
+        &self,
+        lambda: &LambdaCaller<'ctx>,
+        args: &ValueRef,
+        kwargs: &ValueRef,
+    ) -> ValueRef {
+        self.enter_scope();
+        // Closure variables
+        for (name, value) in &lambda.closure {
+            self.add_variable(name, value.clone());
+        }
+        if let Some(schema_ctx) = &lambda.schema_ctx {
+            self.schema_stack.borrow_mut().push(schema_ctx.clone());
+        }
+        self.walk_arguments(&lambda.node.args, args, kwargs);
+        let value = self
+            .walk_stmts(&lambda.node.body)
+            .expect(kcl_error::RUNTIME_ERROR_MSG);
+        if lambda.schema_ctx.is_some() {
+            self.schema_stack.borrow_mut().pop();
+        }
+        self.leave_scope();
+        value
+    }
+}
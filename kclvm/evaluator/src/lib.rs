@@ -0,0 +1,207 @@
+//! The goal of this crate is to evaluate the KCL AST program directly with a tree-walking
+//! interpreter instead of lowering it to LLVM IR, linking a dynamic library and loading it.
+//! The evaluator shares the same value operations with the LLVM backend through the
+//! `kclvm_runtime` crate, so they produce the same `ValueRef` result.
+//!
+//! Copyright 2021 The KCL Authors. All rights reserved.
+
+mod context;
+mod debug;
+mod error;
+mod function;
+mod module;
+mod node;
+mod proxy;
+mod rule;
+mod schema;
+mod scope;
+mod ty;
+mod union;
+mod value;
+
+#[cfg(test)]
+mod tests;
+
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+use indexmap::IndexMap;
+use kclvm_ast::ast;
+use kclvm_runtime::{Context, ValueRef, MAIN_PKG_PATH};
+
+pub use crate::debug::{DebugFrame, DebugScope, Debugger};
+use crate::error as kcl_error;
+use crate::proxy::Frame;
+use crate::schema::{BacktrackMeta, SchemaEvalContextRef};
+use crate::scope::Scope;
+
+/// The evaluation result of a KCL AST node.
+pub type EvalResult = anyhow::Result<ValueRef>;
+
+/// SCALAR_KEY denotes the temp scalar key for the global variable json plan process.
+const SCALAR_KEY: &str = "";
+/// Global level
+const GLOBAL_LEVEL: usize = 1;
+/// Inner level
+const INNER_LEVEL: usize = 2;
+
+/// The evaluator function calling frame stack and all evaluation state.
+pub struct Evaluator<'ctx> {
+    pub program: &'ctx ast::Program,
+    /// Import names mapping
+    pub import_names: IndexMap<String, IndexMap<String, String>>,
+    pub pkgpath_stack: RefCell<Vec<String>>,
+    pub filename_stack: RefCell<Vec<String>>,
+    /// Package scope to store variable values.
+    pub pkg_scopes: RefCell<HashMap<String, Vec<Scope>>>,
+    /// All functions including lambdas, schemas, rules and schema attributes
+    /// defined in the program. A proxy function value refers to a frame by
+    /// its index.
+    pub frames: RefCell<Vec<Rc<Frame<'ctx>>>>,
+    /// Schema attribute frame index mapping keyed by the statement address, the
+    /// attribute name and the if level, which ensures that an attribute setter has
+    /// only one frame.
+    pub attr_frames: RefCell<HashMap<(usize, String, usize), u64>>,
+    /// Schema function values keyed by the schema runtime type.
+    pub schemas: RefCell<IndexMap<String, ValueRef>>,
+    pub schema_stack: RefCell<Vec<SchemaEvalContextRef>>,
+    /// The nesting depth of the schema expressions being evaluated.
+    pub schema_expr_depth: RefCell<usize>,
+    pub lambda_stack: RefCell<Vec<bool>>,
+    pub imported: RefCell<HashSet<String>>,
+    pub local_vars: RefCell<HashSet<String>>,
+    pub target_vars: RefCell<Vec<String>>,
+    pub current_line: RefCell<u64>,
+    /// Schema attr backtrack meta
+    pub backtrack_meta: RefCell<Option<BacktrackMeta>>,
+    /// The debugger notified before every statement is evaluated.
+    pub debugger: Option<&'ctx dyn Debugger>,
+    /// The call frames which are only recorded when debugging.
+    pub debug_frames: RefCell<Vec<DebugFrame>>,
+    /// The runtime context which the values are allocated in and the runtime
+    /// functions are invoked with.
+    pub runtime_ctx: RefCell<&'ctx mut Context>,
+}
+
+impl<'ctx> Evaluator<'ctx> {
+    /// New an Evaluator using the AST program, the import names mapping
+    /// produced by the resolver and the runtime context `runtime_ctx`.
+    #[inline]
+    pub fn new(
+        program: &'ctx ast::Program,
+        import_names: IndexMap<String, IndexMap<String, String>>,
+        runtime_ctx: &'ctx mut Context,
+    ) -> Evaluator<'ctx> {
+        Evaluator {
+            program,
+            import_names,
+            pkgpath_stack: RefCell::new(vec![String::from(MAIN_PKG_PATH)]),
+            filename_stack: RefCell::new(vec![String::from("")]),
+            pkg_scopes: RefCell::new(HashMap::new()),
+            frames: RefCell::new(vec![]),
+            attr_frames: RefCell::new(HashMap::new()),
+            schemas: RefCell::new(IndexMap::new()),
+            schema_stack: RefCell::new(vec![]),
+            schema_expr_depth: RefCell::new(0),
+            lambda_stack: RefCell::new(vec![false]),
+            imported: RefCell::new(HashSet::new()),
+            local_vars: RefCell::new(HashSet::new()),
+            target_vars: RefCell::new(vec![String::from("")]),
+            current_line: RefCell::new(0),
+            backtrack_meta: RefCell::new(None),
+            debugger: None,
+            debug_frames: RefCell::new(vec![]),
+            runtime_ctx: RefCell::new(runtime_ctx),
+        }
+    }
+
+    /// Evaluate the program in the runtime context and return the
+    /// global variable dict value which can be planned to JSON or YAML.
+    ///
+    /// Note that runtime errors are raised with panics and the caller should
+    /// catch them with the runtime panic hook like `_kcl_run`.
+    pub fn run(&self) -> EvalResult {
+        self.set_import_names();
+        self.init_scope(MAIN_PKG_PATH);
+        let modules = self
+            .program
+            .pkgs
+            .get(MAIN_PKG_PATH)
+            .ok_or_else(|| anyhow::anyhow!("main package {} is not found", MAIN_PKG_PATH))?;
+        for module in modules {
+            self.push_filename(&module.filename);
+            self.compile_module(module)?;
+            self.pop_filename();
+        }
+        Ok(self.globals_value())
+    }
+
+    /// Store the import names mapping into the runtime context which is
+    /// used to convert collection values to schema values.
+    fn set_import_names(&self) {
+        let mut ctx = self.runtime_ctx.borrow_mut();
+        for (k, v) in &self.import_names {
+            let mut map = IndexMap::default();
+            for (pkgname, pkgpath) in v {
+                map.insert(pkgname.to_string(), format!("@{}", pkgpath));
+            }
+            ctx.import_names.insert(k.to_string(), map);
+        }
+    }
+
+    /// Evaluate a module: all schema, rule and import statements are evaluated
+    /// firstly and then the other statements.
+    fn compile_module(&self, module: &'ctx ast::Module) -> EvalResult {
+        use kclvm_ast::walker::TypedResultWalker;
+        self.walk_module(module)
+    }
+
+    /// Get the global variable dict value including all the scalar values
+    /// and non-private global variables of the main package.
+    fn globals_value(&self) -> ValueRef {
+        let pkgpath = self.current_pkgpath();
+        let pkg_scopes = self.pkg_scopes.borrow();
+        let scopes = pkg_scopes
+            .get(&pkgpath)
+            .unwrap_or_else(|| panic!("pkgpath {} is not found", pkgpath));
+        // The global scope.
+        let scope = scopes.last().expect(kcl_error::INTERNAL_ERROR_MSG);
+        let mut global_dict = ValueRef::dict(None);
+        // Deal scalars
+        for scalar in scope.scalars.iter() {
+            global_dict.dict_insert(
+                &mut self.runtime_ctx.borrow_mut(),
+                SCALAR_KEY,
+                scalar,
+                kclvm_runtime::ConfigEntryOperationKind::Union,
+                -1,
+            );
+        }
+        // Deal global variables
+        for (name, value) in scope.variables.iter() {
+            // Omit private variables and function variables
+            if name.starts_with(kclvm_runtime::KCL_PRIVATE_VAR_PREFIX) {
+                continue;
+            }
+            let mut value_dict = ValueRef::dict(None);
+            value_dict.dict_insert(
+                &mut self.runtime_ctx.borrow_mut(),
+                name.as_str(),
+                value,
+                kclvm_runtime::ConfigEntryOperationKind::Union,
+                -1,
+            );
+            global_dict.dict_insert(
+                &mut self.runtime_ctx.borrow_mut(),
+                SCALAR_KEY,
+                &value_dict,
+                kclvm_runtime::ConfigEntryOperationKind::Union,
+                -1,
+            );
+        }
+        global_dict
+            .dict_get_value(SCALAR_KEY)
+            .unwrap_or_else(|| ValueRef::dict(None))
+    }
+}
@@ -0,0 +1,1541 @@
+// Copyright 2021 The KCL Authors. All rights reserved.
+
+use std::ffi::CString;
+
+use kclvm_ast::ast::{self, CallExpr, ConfigEntry, NodeRef};
+use kclvm_ast::walker::TypedResultWalker;
+use kclvm_runtime::{
+    kclvm_assert, kclvm_schema_assert, kclvm_value_load_attr, ptr_as_ref, ConfigEntryOperationKind,
+    DecoratorValue, ValueRef, PKG_PATH_PREFIX,
+};
+use kclvm_sema::{builtin, plugin};
+
+use crate::error as kcl_error;
+use crate::proxy::{LambdaCaller, Proxy, RuleCaller, SchemaCaller};
+use crate::{EvalResult, Evaluator, GLOBAL_LEVEL, INNER_LEVEL};
+
+macro_rules! check_backtrack_stop {
+    ($evaluator: expr) => {
+        if $evaluator.is_backtrack_stop() {
+            return $evaluator.ok_result();
+        }
+    };
+}
+
+/* Expression and statement evaluation. */
+
+impl<'ctx> TypedResultWalker<'ctx> for Evaluator<'ctx> {
+    type Result = EvalResult;
+
+    /*
+     * Statement
+     */
+
+    fn walk_stmt(&self, stmt: &'ctx ast::Node<ast::Stmt>) -> Self::Result {
+        check_backtrack_stop!(self);
+        self.update_ctx_filename(stmt);
+        self.update_ctx_line_col(stmt);
+        self.debug_stmt(stmt);
+        self.target_vars.borrow_mut().clear();
+        self.target_vars.borrow_mut().push("".to_string());
+        match &stmt.node {
+            ast::Stmt::TypeAlias(type_alias) => self.walk_type_alias_stmt(type_alias),
+            ast::Stmt::Expr(expr_stmt) => self.walk_expr_stmt(expr_stmt),
+            ast::Stmt::Unification(unification_stmt) => {
+                self.walk_unification_stmt(unification_stmt)
+            }
+            ast::Stmt::Assign(assign_stmt) => self.walk_assign_stmt(assign_stmt),
+            ast::Stmt::AugAssign(aug_assign_stmt) => self.walk_aug_assign_stmt(aug_assign_stmt),
+            ast::Stmt::Assert(assert_stmt) => self.walk_assert_stmt(assert_stmt),
+            ast::Stmt::If(if_stmt) => self.walk_if_stmt(if_stmt),
+            ast::Stmt::Import(import_stmt) => self.walk_import_stmt(import_stmt),
+            ast::Stmt::SchemaAttr(schema_attr) => self.walk_schema_attr(schema_attr),
+            ast::Stmt::Schema(schema_stmt) => self.walk_schema_stmt(schema_stmt),
+            ast::Stmt::Rule(rule_stmt) => self.walk_rule_stmt(rule_stmt),
+        }
+    }
+
+    fn walk_expr_stmt(&self, expr_stmt: &'ctx ast::ExprStmt) -> Self::Result {
+        check_backtrack_stop!(self);
+        let mut result = self.ok_result();
+        for expr in &expr_stmt.exprs {
+            let scalar = self.walk_expr(expr)?;
+            // Only non-call expressions are allowed to emit values bacause of the function void return type.
+            if !matches!(expr.node, ast::Expr::Call(_)) {
+                self.add_scalar(scalar.clone(), matches!(expr.node, ast::Expr::Schema(_)));
+            }
+            result = Ok(scalar);
+        }
+        result
+    }
+
+    fn walk_unification_stmt(&self, unification_stmt: &'ctx ast::UnificationStmt) -> Self::Result {
+        check_backtrack_stop!(self);
+        self.local_vars.borrow_mut().clear();
+        let name = &unification_stmt.target.node.names[0];
+        self.target_vars.borrow_mut().push(name.clone());
+        // The right value of the unification_stmt is a schema_expr.
+        let value = self
+            .walk_schema_expr(&unification_stmt.value.node)
+            .expect(kcl_error::RUNTIME_ERROR_MSG);
+        if self.scope_level() == GLOBAL_LEVEL || self.is_in_lambda() {
+            if self.resolve_variable(name) {
+                let mut org_value = self
+                    .walk_identifier_with_ctx(
+                        &unification_stmt.target.node,
+                        &ast::ExprContext::Load,
+                        None,
+                    )
+                    .expect(kcl_error::RUNTIME_ERROR_MSG);
+                let value = self.aug_bit_or(&mut org_value, &value);
+                // Store the identifier value
+                self.walk_identifier_with_ctx(
+                    &unification_stmt.target.node,
+                    &ast::ExprContext::Store,
+                    Some(value.clone()),
+                )
+                .expect(kcl_error::RUNTIME_ERROR_MSG);
+                return Ok(value);
+            } else {
+                self.walk_identifier_with_ctx(
+                    &unification_stmt.target.node,
+                    &unification_stmt.target.node.ctx,
+                    Some(value.clone()),
+                )
+                .expect(kcl_error::RUNTIME_ERROR_MSG);
+                return Ok(value);
+            }
+        // Local variables including schema/rule/lambda
+        } else if self.is_in_schema() {
+            // Load the identifier value
+            let org_value = self
+                .walk_identifier_with_ctx(
+                    &unification_stmt.target.node,
+                    &ast::ExprContext::Load,
+                    None,
+                )
+                .expect(kcl_error::RUNTIME_ERROR_MSG);
+            let value = self.bit_or(&org_value, &value);
+            // Store the identifier value
+            self.walk_identifier_with_ctx(
+                &unification_stmt.target.node,
+                &ast::ExprContext::Store,
+                Some(value.clone()),
+            )
+            .expect(kcl_error::RUNTIME_ERROR_MSG);
+            return Ok(value);
+        }
+        Ok(value)
+    }
+
+    fn walk_type_alias_stmt(&self, _type_alias_stmt: &'ctx ast::TypeAliasStmt) -> Self::Result {
+        // Nothing to do, because all type aliases have been replaced at compile time
+        self.ok_result()
+    }
+
+    fn walk_assign_stmt(&self, assign_stmt: &'ctx ast::AssignStmt) -> Self::Result {
+        check_backtrack_stop!(self);
+        self.local_vars.borrow_mut().clear();
+        // Set target vars.
+        for name in &assign_stmt.targets {
+            self.target_vars
+                .borrow_mut()
+                .push(name.node.names[0].clone());
+        }
+        // Load the right value
+        let mut value = self
+            .walk_expr(&assign_stmt.value)
+            .expect(kcl_error::RUNTIME_ERROR_MSG);
+        if let Some(ty) = &assign_stmt.type_annotation {
+            let type_annotation = &ty.node;
+            value = self.type_pack_and_check(&value, vec![type_annotation]);
+        }
+        if assign_stmt.targets.len() == 1 {
+            // Store the single target
+            let name = &assign_stmt.targets[0];
+            self.walk_identifier_with_ctx(&name.node, &name.node.ctx, Some(value.clone()))
+                .expect(kcl_error::RUNTIME_ERROR_MSG);
+        } else {
+            // Store multiple targets
+            for name in &assign_stmt.targets {
+                let value = value.deep_copy();
+                self.walk_identifier_with_ctx(&name.node, &name.node.ctx, Some(value.clone()))
+                    .expect(kcl_error::RUNTIME_ERROR_MSG);
+            }
+        }
+        Ok(value)
+    }
+
+    fn walk_aug_assign_stmt(&self, aug_assign_stmt: &'ctx ast::AugAssignStmt) -> Self::Result {
+        check_backtrack_stop!(self);
+        self.target_vars
+            .borrow_mut()
+            .push(aug_assign_stmt.target.node.names[0].clone());
+        // Load the right value
+        let right_value = self
+            .walk_expr(&aug_assign_stmt.value)
+            .expect(kcl_error::RUNTIME_ERROR_MSG);
+        // Load the identifier value
+        let mut org_value = self
+            .walk_identifier_with_ctx(&aug_assign_stmt.target.node, &ast::ExprContext::Load, None)
+            .expect(kcl_error::RUNTIME_ERROR_MSG);
+        let value = match aug_assign_stmt.op {
+            ast::AugOp::Add => org_value
+                .bin_aug_add(&mut self.runtime_ctx.borrow_mut(), &right_value)
+                .clone(),
+            ast::AugOp::Sub => org_value
+                .bin_aug_sub(&mut self.runtime_ctx.borrow_mut(), &right_value)
+                .clone(),
+            ast::AugOp::Mul => org_value
+                .bin_aug_mul(&mut self.runtime_ctx.borrow_mut(), &right_value)
+                .clone(),
+            ast::AugOp::Div => org_value.bin_aug_div(&right_value).clone(),
+            ast::AugOp::Mod => org_value.bin_aug_mod(&right_value).clone(),
+            ast::AugOp::Pow => org_value
+                .bin_aug_pow(&mut self.runtime_ctx.borrow_mut(), &right_value)
+                .clone(),
+            ast::AugOp::LShift => org_value
+                .bin_aug_bit_lshift(&mut self.runtime_ctx.borrow_mut(), &right_value)
+                .clone(),
+            ast::AugOp::RShift => org_value
+                .bin_aug_bit_rshift(&mut self.runtime_ctx.borrow_mut(), &right_value)
+                .clone(),
+            ast::AugOp::BitOr => self.bit_or(&org_value, &right_value),
+            ast::AugOp::BitXor => org_value.bin_aug_bit_xor(&right_value).clone(),
+            ast::AugOp::BitAnd => org_value.bin_aug_bit_and(&right_value).clone(),
+            ast::AugOp::FloorDiv => org_value.bin_aug_floor_div(&right_value).clone(),
+            ast::AugOp::Assign => {
+                return Err(anyhow::anyhow!("Invalid AST operator"));
+            }
+        };
+        // Store the identifier value
+        self.walk_identifier_with_ctx(
+            &aug_assign_stmt.target.node,
+            &ast::ExprContext::Store,
+            Some(value.clone()),
+        )
+        .expect(kcl_error::RUNTIME_ERROR_MSG);
+        Ok(value)
+    }
+
+    fn walk_assert_stmt(&self, assert_stmt: &'ctx ast::AssertStmt) -> Self::Result {
+        check_backtrack_stop!(self);
+        if let Some(if_cond) = &assert_stmt.if_cond {
+            let if_value = self.walk_expr(if_cond).expect(kcl_error::RUNTIME_ERROR_MSG);
+            if !if_value.is_truthy() {
+                return self.ok_result();
+            }
+        }
+        let assert_result = self
+            .walk_expr(&assert_stmt.test)
+            .expect(kcl_error::RUNTIME_ERROR_MSG);
+        let msg = match &assert_stmt.msg {
+            Some(msg) => self.walk_expr(msg).expect(kcl_error::RUNTIME_ERROR_MSG),
+            None => ValueRef::str(""),
+        };
+        unsafe { kclvm_assert(&mut **self.runtime_ctx.borrow_mut(), &assert_result, &msg) };
+        self.ok_result()
+    }
+
+    fn walk_if_stmt(&self, if_stmt: &'ctx ast::IfStmt) -> Self::Result {
+        check_backtrack_stop!(self);
+        let cond = self
+            .walk_expr(&if_stmt.cond)
+            .expect(kcl_error::RUNTIME_ERROR_MSG);
+        if cond.is_truthy() {
+            self.walk_stmts(&if_stmt.body)
+                .expect(kcl_error::RUNTIME_ERROR_MSG);
+        } else {
+            self.walk_stmts(&if_stmt.orelse)
+                .expect(kcl_error::RUNTIME_ERROR_MSG);
+        }
+        Ok(ValueRef::none())
+    }
+
+    fn walk_import_stmt(&self, import_stmt: &'ctx ast::ImportStmt) -> Self::Result {
+        check_backtrack_stop!(self);
+        let pkgpath = import_stmt.path.as_str();
+        if self.imported.borrow().contains(pkgpath) {
+            return self.ok_result();
+        }
+        if builtin::STANDARD_SYSTEM_MODULES.contains(&pkgpath)
+            || pkgpath.starts_with(plugin::PLUGIN_MODULE_PREFIX)
+        {
+            // Nothing to do on the builtin system module import because the check has been done.
+            return self.ok_result();
+        }
+        let pkgpath = format!("{}{}", PKG_PATH_PREFIX, import_stmt.path);
+        self.push_pkgpath(&pkgpath);
+        if let Some(modules) = self.program.pkgs.get(&import_stmt.path) {
+            // Init all builtin functions.
+            self.init_scope(pkgpath.as_str());
+            // Evaluate all schemas, rules and imports in the pkgpath firstly.
+            for ast_module in modules {
+                self.push_filename(&ast_module.filename);
+                self.compile_module_import_and_types(ast_module);
+                self.pop_filename();
+            }
+            for ast_module in modules {
+                self.push_filename(&ast_module.filename);
+                let result = self.walk_stmts_except_import(&ast_module.body);
+                self.pop_filename();
+                if let Err(err) = result {
+                    self.pop_pkgpath();
+                    return Err(err);
+                }
+            }
+        }
+        self.pop_pkgpath();
+        self.imported
+            .borrow_mut()
+            .insert(import_stmt.path.to_string());
+        self.ok_result()
+    }
+
+    fn walk_schema_stmt(&self, schema_stmt: &'ctx ast::SchemaStmt) -> Self::Result {
+        check_backtrack_stop!(self);
+        let runtime_type = self.schema_runtime_type(&schema_stmt.name.node);
+        let index = self.add_frame(Proxy::Schema(SchemaCaller {
+            node: schema_stmt,
+            runtime_type: runtime_type.clone(),
+        }));
+        let schema_value = ValueRef::proxy_func(index, "", &runtime_type);
+        // Store the schema function value to resolve schema values in the union.
+        self.schemas
+            .borrow_mut()
+            .insert(runtime_type, schema_value.clone());
+        let name = &schema_stmt.name.node;
+        if !self.store_variable(name, schema_value.clone()) {
+            self.add_variable(name, schema_value.clone());
+        }
+        Ok(schema_value)
+    }
+
+    fn walk_rule_stmt(&self, rule_stmt: &'ctx ast::RuleStmt) -> Self::Result {
+        check_backtrack_stop!(self);
+        let runtime_type = self.schema_runtime_type(&rule_stmt.name.node);
+        let index = self.add_frame(Proxy::Rule(RuleCaller {
+            node: rule_stmt,
+            runtime_type: runtime_type.clone(),
+        }));
+        let rule_value = ValueRef::proxy_func(index, "", &runtime_type);
+        let name = &rule_stmt.name.node;
+        if !self.store_variable(name, rule_value.clone()) {
+            self.add_variable(name, rule_value.clone());
+        }
+        Ok(rule_value)
+    }
+
+    /*
+     * Expression
+     */
+
+    fn walk_expr(&self, expr: &'ctx ast::Node<ast::Expr>) -> Self::Result {
+        check_backtrack_stop!(self);
+        self.update_ctx_filename(expr);
+        self.update_ctx_line_col(expr);
+        self.runtime_ctx.borrow_mut().count_evaluated_object();
+        match &expr.node {
+            ast::Expr::Identifier(identifier) => self.walk_identifier(identifier),
+            ast::Expr::Unary(unary_expr) => self.walk_unary_expr(unary_expr),
+            ast::Expr::Binary(binary_expr) => self.walk_binary_expr(binary_expr),
+            ast::Expr::If(if_expr) => self.walk_if_expr(if_expr),
+            ast::Expr::Selector(selector_expr) => self.walk_selector_expr(selector_expr),
+            ast::Expr::Call(call_expr) => self.walk_call_expr(call_expr),
+            ast::Expr::Paren(paren_expr) => self.walk_paren_expr(paren_expr),
+            ast::Expr::Quant(quant_expr) => self.walk_quant_expr(quant_expr),
+            ast::Expr::List(list_expr) => self.walk_list_expr(list_expr),
+            ast::Expr::ListIfItem(list_if_item_expr) => {
+                self.walk_list_if_item_expr(list_if_item_expr)
+            }
+            ast::Expr::ListComp(list_comp) => self.walk_list_comp(list_comp),
+            ast::Expr::Starred(starred_expr) => self.walk_starred_expr(starred_expr),
+            ast::Expr::DictComp(dict_comp) => self.walk_dict_comp(dict_comp),
+            ast::Expr::ConfigIfEntry(config_if_entry_expr) => {
+                self.walk_config_if_entry_expr(config_if_entry_expr)
+            }
+            ast::Expr::CompClause(comp_clause) => self.walk_comp_clause(comp_clause),
+            ast::Expr::Schema(schema_expr) => self.walk_schema_expr(schema_expr),
+            ast::Expr::Config(config_expr) => self.walk_config_expr(config_expr),
+            ast::Expr::Check(check) => self.walk_check_expr(check),
+            ast::Expr::Lambda(lambda) => self.walk_lambda_expr(lambda),
+            ast::Expr::Subscript(subscript) => self.walk_subscript(subscript),
+            ast::Expr::Keyword(keyword) => self.walk_keyword(keyword),
+            ast::Expr::Arguments(..) => self.ok_result(),
+            ast::Expr::Compare(compare) => self.walk_compare(compare),
+            ast::Expr::NumberLit(number_lit) => self.walk_number_lit(number_lit),
+            ast::Expr::StringLit(string_lit) => self.walk_string_lit(string_lit),
+            ast::Expr::NameConstantLit(name_constant_lit) => {
+                self.walk_name_constant_lit(name_constant_lit)
+            }
+            ast::Expr::JoinedString(joined_string) => self.walk_joined_string(joined_string),
+            ast::Expr::FormattedValue(formatted_value) => {
+                self.walk_formatted_value(formatted_value)
+            }
+            ast::Expr::Missing(missing_expr) => self.walk_missing_expr(missing_expr),
+        }
+    }
+
+    fn walk_quant_expr(&self, quant_expr: &'ctx ast::QuantExpr) -> Self::Result {
+        check_backtrack_stop!(self);
+        let mut result = match quant_expr.op {
+            ast::QuantOperation::All => ValueRef::bool(true),
+            ast::QuantOperation::Any => ValueRef::bool(false),
+            ast::QuantOperation::Map => ValueRef::list(None),
+            ast::QuantOperation::Filter => self
+                .walk_expr(&quant_expr.target)
+                .expect(kcl_error::RUNTIME_ERROR_MSG)
+                .deep_copy(),
+        };
+        // Iterator
+        let iter_host_value = if let ast::QuantOperation::Filter = quant_expr.op {
+            result.deep_copy()
+        } else {
+            self.walk_expr(&quant_expr.target)
+                .expect(kcl_error::RUNTIME_ERROR_MSG)
+        };
+        let mut iter_value = iter_host_value.iter();
+        self.enter_scope();
+        {
+            let mut local_vars = self.local_vars.borrow_mut();
+            for v in &quant_expr.variables {
+                let name = &v.node.names[0];
+                local_vars.insert(name.clone());
+            }
+        }
+        while let Some(next_value) = iter_value.next(&iter_host_value) {
+            self.runtime_ctx.borrow_mut().check_timeout();
+            let next_value = next_value.clone();
+            let variables = &quant_expr.variables;
+            match variables.len() {
+                1 => {
+                    // Store the target
+                    self.walk_identifier_with_ctx(
+                        &variables[0].node,
+                        &ast::ExprContext::Store,
+                        Some(next_value.clone()),
+                    )
+                    .expect(kcl_error::RUNTIME_ERROR_MSG);
+                }
+                2 => {
+                    let key = iter_value
+                        .key()
+                        .cloned()
+                        .unwrap_or_else(ValueRef::undefined);
+                    let value = iter_value
+                        .value()
+                        .cloned()
+                        .unwrap_or_else(ValueRef::undefined);
+                    // Store the target
+                    self.walk_identifier_with_ctx(
+                        &variables[0].node,
+                        &ast::ExprContext::Store,
+                        Some(key),
+                    )
+                    .expect(kcl_error::RUNTIME_ERROR_MSG);
+                    self.walk_identifier_with_ctx(
+                        &variables[1].node,
+                        &ast::ExprContext::Store,
+                        Some(value),
+                    )
+                    .expect(kcl_error::RUNTIME_ERROR_MSG);
+                }
+                _ => panic!(
+                    "the number of loop variables is {}, which can only be 1 or 2",
+                    variables.len()
+                ),
+            }
+            // Check the if filter
+            if let Some(if_cond) = &quant_expr.if_cond {
+                let is_truth = self.walk_expr(if_cond).expect(kcl_error::RUNTIME_ERROR_MSG);
+                if !is_truth.is_truthy() {
+                    continue;
+                }
+            }
+            let is_truth = self
+                .walk_expr(&quant_expr.test)
+                .expect(kcl_error::RUNTIME_ERROR_MSG);
+            match quant_expr.op {
+                ast::QuantOperation::All => {
+                    if !is_truth.is_truthy() {
+                        result = ValueRef::bool(false);
+                        break;
+                    }
+                }
+                ast::QuantOperation::Any => {
+                    if is_truth.is_truthy() {
+                        result = ValueRef::bool(true);
+                        break;
+                    }
+                }
+                ast::QuantOperation::Filter => {
+                    if !is_truth.is_truthy() {
+                        if result.is_dict() {
+                            result.dict_remove(&next_value.as_str());
+                        } else if result.is_list() {
+                            result.list_remove(&next_value);
+                        } else {
+                            panic!("only list, dict and schema can be removed item");
+                        }
+                    }
+                }
+                ast::QuantOperation::Map => {
+                    result.list_append(&is_truth);
+                }
+            }
+        }
+        self.leave_scope();
+        self.local_vars.borrow_mut().clear();
+        Ok(result)
+    }
+
+    fn walk_schema_attr(&self, schema_attr: &'ctx ast::SchemaAttr) -> Self::Result {
+        check_backtrack_stop!(self);
+        self.local_vars.borrow_mut().clear();
+        let name = schema_attr.name.node.as_str();
+        self.target_vars.borrow_mut().push(name.to_string());
+        for decorator in &schema_attr.decorators {
+            self.walk_decorator_with_name(&decorator.node, Some(name), false)
+                .expect(kcl_error::RUNTIME_ERROR_MSG);
+        }
+        let value = match &schema_attr.value {
+            Some(value) => self.walk_expr(value).expect(kcl_error::RUNTIME_ERROR_MSG),
+            None => ValueRef::undefined(),
+        };
+        let schema_ctx = self.schema_ctx();
+        let (mut schema_value, config_value) = {
+            let s = schema_ctx.borrow();
+            (s.value.clone(), s.config.clone())
+        };
+        schema_value.update_attr_map(name, &schema_attr.type_str.node);
+        if let Some(op) = &schema_attr.op {
+            match op {
+                // Union
+                ast::BinOrAugOp::Aug(ast::AugOp::BitOr) => {
+                    let org_value = schema_value
+                        .dict_get_value(name)
+                        .unwrap_or_else(ValueRef::undefined);
+                    let value = self.bit_or(&org_value, &value);
+                    self.dict_merge(
+                        &mut schema_value,
+                        name,
+                        &value,
+                        &ConfigEntryOperationKind::Override,
+                        -1,
+                    );
+                }
+                // Assign
+                _ => self.dict_merge(
+                    &mut schema_value,
+                    name,
+                    &value,
+                    &ConfigEntryOperationKind::Override,
+                    -1,
+                ),
+            }
+        }
+        if let Some(config_attr_value) = config_value.dict_get_entry(name) {
+            self.value_union(&mut schema_value, &config_attr_value);
+            self.schema_backtrack_cache(&schema_ctx, name);
+            // Update backtrack meta
+            if self.update_backtrack_meta(name) {
+                return Ok(schema_value);
+            }
+        }
+        Ok(schema_value)
+    }
+
+    fn walk_if_expr(&self, if_expr: &'ctx ast::IfExpr) -> Self::Result {
+        check_backtrack_stop!(self);
+        let cond = self
+            .walk_expr(&if_expr.cond)
+            .expect(kcl_error::RUNTIME_ERROR_MSG);
+        if cond.is_truthy() {
+            self.walk_expr(&if_expr.body)
+        } else {
+            self.walk_expr(&if_expr.orelse)
+        }
+    }
+
+    fn walk_unary_expr(&self, unary_expr: &'ctx ast::UnaryExpr) -> Self::Result {
+        check_backtrack_stop!(self);
+        let value = self
+            .walk_expr(&unary_expr.operand)
+            .expect(kcl_error::RUNTIME_ERROR_MSG);
+        Ok(match unary_expr.op {
+            ast::UnaryOp::UAdd => value.unary_plus(),
+            ast::UnaryOp::USub => value.unary_minus(),
+            ast::UnaryOp::Invert => value.unary_not(),
+            ast::UnaryOp::Not => value.unary_l_not(),
+        })
+    }
+
+    fn walk_binary_expr(&self, binary_expr: &'ctx ast::BinaryExpr) -> Self::Result {
+        check_backtrack_stop!(self);
+        let is_logic_op = matches!(
+            binary_expr.op,
+            ast::BinOrCmpOp::Bin(ast::BinOp::And) | ast::BinOrCmpOp::Bin(ast::BinOp::Or)
+        );
+        let is_membership_as_op = matches!(binary_expr.op, ast::BinOrCmpOp::Bin(ast::BinOp::As));
+        if !is_logic_op {
+            let left_value = self
+                .walk_expr(&binary_expr.left)
+                .expect(kcl_error::RUNTIME_ERROR_MSG);
+            let right_value = if is_membership_as_op {
+                match &binary_expr.right.node {
+                    ast::Expr::Identifier(id) => {
+                        let name = id.names.join(".");
+                        ValueRef::str(&name)
+                    }
+                    _ => ValueRef::none(),
+                }
+            } else {
+                self.walk_expr(&binary_expr.right)
+                    .expect(kcl_error::RUNTIME_ERROR_MSG)
+            };
+            let value = match &binary_expr.op {
+                ast::BinOrCmpOp::Bin(ast::BinOp::Add) => {
+                    left_value.bin_add(&mut self.runtime_ctx.borrow_mut(), &right_value)
+                }
+                ast::BinOrCmpOp::Bin(ast::BinOp::Sub) => {
+                    left_value.bin_sub(&mut self.runtime_ctx.borrow_mut(), &right_value)
+                }
+                ast::BinOrCmpOp::Bin(ast::BinOp::Mul) => {
+                    left_value.bin_mul(&mut self.runtime_ctx.borrow_mut(), &right_value)
+                }
+                ast::BinOrCmpOp::Bin(ast::BinOp::Div) => left_value.bin_div(&right_value),
+                ast::BinOrCmpOp::Bin(ast::BinOp::FloorDiv) => {
+                    left_value.bin_floor_div(&right_value)
+                }
+                ast::BinOrCmpOp::Bin(ast::BinOp::Mod) => left_value.bin_mod(&right_value),
+                ast::BinOrCmpOp::Bin(ast::BinOp::Pow) => {
+                    left_value.bin_pow(&mut self.runtime_ctx.borrow_mut(), &right_value)
+                }
+                ast::BinOrCmpOp::Bin(ast::BinOp::LShift) => {
+                    left_value.bin_bit_lshift(&mut self.runtime_ctx.borrow_mut(), &right_value)
+                }
+                ast::BinOrCmpOp::Bin(ast::BinOp::RShift) => {
+                    left_value.bin_bit_rshift(&mut self.runtime_ctx.borrow_mut(), &right_value)
+                }
+                ast::BinOrCmpOp::Bin(ast::BinOp::BitAnd) => left_value.bin_bit_and(&right_value),
+                ast::BinOrCmpOp::Bin(ast::BinOp::BitOr) => self.bit_or(&left_value, &right_value),
+                ast::BinOrCmpOp::Bin(ast::BinOp::BitXor) => left_value.bin_bit_xor(&right_value),
+                ast::BinOrCmpOp::Bin(ast::BinOp::And) => {
+                    ValueRef::bool(left_value.logic_and(&right_value))
+                }
+                ast::BinOrCmpOp::Bin(ast::BinOp::Or) => {
+                    ValueRef::bool(left_value.logic_or(&right_value))
+                }
+                ast::BinOrCmpOp::Bin(ast::BinOp::As) => {
+                    self.type_pack_and_check(&left_value, vec![&right_value.as_str()])
+                }
+                ast::BinOrCmpOp::Cmp(op) => compare_values(&left_value, &right_value, op),
+            };
+            Ok(value)
+        } else {
+            let jump_if_false = matches!(binary_expr.op, ast::BinOrCmpOp::Bin(ast::BinOp::And));
+            let left_value = self
+                .walk_expr(&binary_expr.left)
+                .expect(kcl_error::RUNTIME_ERROR_MSG);
+            let is_truth = left_value.is_truthy();
+            if jump_if_false {
+                // Jump if false on logic and
+                if is_truth {
+                    return self.walk_expr(&binary_expr.right);
+                }
+            } else {
+                // Jump if true on logic or
+                if !is_truth {
+                    return self.walk_expr(&binary_expr.right);
+                }
+            };
+            Ok(left_value)
+        }
+    }
+
+    fn walk_selector_expr(&self, selector_expr: &'ctx ast::SelectorExpr) -> Self::Result {
+        check_backtrack_stop!(self);
+        let value = self
+            .walk_expr(&selector_expr.value)
+            .expect(kcl_error::RUNTIME_ERROR_MSG);
+        let names = &selector_expr.attr.node.names;
+        let mut value = if selector_expr.has_question {
+            if value.is_truthy() {
+                self.load_attr(&value, &names[0])
+            } else {
+                ValueRef::none()
+            }
+        } else {
+            self.load_attr(&value, &names[0])
+        };
+        for name in &names[1..] {
+            value = self.load_attr(&value, name);
+        }
+        Ok(value)
+    }
+
+    fn walk_call_expr(&self, call_expr: &'ctx ast::CallExpr) -> Self::Result {
+        check_backtrack_stop!(self);
+        let func = self
+            .walk_expr(&call_expr.func)
+            .expect(kcl_error::RUNTIME_ERROR_MSG);
+        // args
+        let mut list_value = ValueRef::list(None);
+        for arg in &call_expr.args {
+            let value = self.walk_expr(arg).expect(kcl_error::RUNTIME_ERROR_MSG);
+            list_value.list_append(&value);
+        }
+        let mut dict_value = ValueRef::dict(None);
+        // kwargs
+        for keyword in &call_expr.keywords {
+            let name = &keyword.node.arg.node.names[0];
+            let value = if let Some(value) = &keyword.node.value {
+                self.walk_expr(value).expect(kcl_error::RUNTIME_ERROR_MSG)
+            } else {
+                ValueRef::none()
+            };
+            dict_value.dict_insert(
+                &mut self.runtime_ctx.borrow_mut(),
+                name.as_str(),
+                &value,
+                ConfigEntryOperationKind::Union,
+                -1,
+            );
+        }
+        Ok(self.invoke_function(&func, &list_value, &dict_value))
+    }
+
+    fn walk_subscript(&self, subscript: &'ctx ast::Subscript) -> Self::Result {
+        check_backtrack_stop!(self);
+        let value = self
+            .walk_expr(&subscript.value)
+            .expect(kcl_error::RUNTIME_ERROR_MSG);
+        if let Some(index) = &subscript.index {
+            // index
+            let index = self.walk_expr(index).expect(kcl_error::RUNTIME_ERROR_MSG);
+            Ok(if subscript.has_question {
+                value.bin_subscr_option(&index)
+            } else {
+                value.bin_subscr(&index)
+            })
+        } else {
+            let lower = {
+                if let Some(lower) = &subscript.lower {
+                    self.walk_expr(lower).expect(kcl_error::RUNTIME_ERROR_MSG)
+                } else {
+                    ValueRef::none()
+                }
+            };
+            let upper = {
+                if let Some(upper) = &subscript.upper {
+                    self.walk_expr(upper).expect(kcl_error::RUNTIME_ERROR_MSG)
+                } else {
+                    ValueRef::none()
+                }
+            };
+            let step = {
+                if let Some(step) = &subscript.step {
+                    self.walk_expr(step).expect(kcl_error::RUNTIME_ERROR_MSG)
+                } else {
+                    ValueRef::none()
+                }
+            };
+            Ok(if subscript.has_question {
+                if value.is_truthy() {
+                    value.list_slice(&lower, &upper, &step)
+                } else {
+                    ValueRef::none()
+                }
+            } else {
+                value.list_slice(&lower, &upper, &step)
+            })
+        }
+    }
+
+    fn walk_paren_expr(&self, paren_expr: &'ctx ast::ParenExpr) -> Self::Result {
+        check_backtrack_stop!(self);
+        self.walk_expr(&paren_expr.expr)
+    }
+
+    fn walk_list_expr(&self, list_expr: &'ctx ast::ListExpr) -> Self::Result {
+        check_backtrack_stop!(self);
+        let mut list_value = ValueRef::list(None);
+        for item in &list_expr.elts {
+            let value = self.walk_expr(item).expect(kcl_error::RUNTIME_ERROR_MSG);
+            match &item.node {
+                ast::Expr::Starred(_) | ast::Expr::ListIfItem(_) => {
+                    list_value.list_append_unpack(&value)
+                }
+                _ => list_value.list_append(&value),
+            };
+        }
+        Ok(list_value)
+    }
+
+    fn walk_list_if_item_expr(&self, list_if_item_expr: &'ctx ast::ListIfItemExpr) -> Self::Result {
+        check_backtrack_stop!(self);
+        let cond = self
+            .walk_expr(&list_if_item_expr.if_cond)
+            .expect(kcl_error::RUNTIME_ERROR_MSG);
+        Ok(if cond.is_truthy() {
+            let mut then_value = ValueRef::list(None);
+            for expr in &list_if_item_expr.exprs {
+                let value = self.walk_expr(expr).expect(kcl_error::RUNTIME_ERROR_MSG);
+                match &expr.node {
+                    ast::Expr::Starred(_) | ast::Expr::ListIfItem(_) => {
+                        then_value.list_append_unpack(&value)
+                    }
+                    _ => then_value.list_append(&value),
+                };
+            }
+            then_value
+        } else if let Some(orelse) = &list_if_item_expr.orelse {
+            self.walk_expr(orelse).expect(kcl_error::RUNTIME_ERROR_MSG)
+        } else {
+            ValueRef::none()
+        })
+    }
+
+    fn walk_starred_expr(&self, starred_expr: &'ctx ast::StarredExpr) -> Self::Result {
+        check_backtrack_stop!(self);
+        self.walk_expr(&starred_expr.value)
+    }
+
+    fn walk_list_comp(&self, list_comp: &'ctx ast::ListComp) -> Self::Result {
+        check_backtrack_stop!(self);
+        let mut collection_value = ValueRef::list(None);
+        self.enter_scope();
+        self.walk_generator(
+            &list_comp.generators,
+            &list_comp.elt,
+            None,
+            None,
+            0,
+            &mut collection_value,
+            &ast::CompType::List,
+        );
+        self.leave_scope();
+        Ok(collection_value)
+    }
+
+    fn walk_dict_comp(&self, dict_comp: &'ctx ast::DictComp) -> Self::Result {
+        check_backtrack_stop!(self);
+        let mut collection_value = ValueRef::dict(None);
+        self.enter_scope();
+        let key = dict_comp
+            .entry
+            .key
+            .as_ref()
+            .expect(kcl_error::INTERNAL_ERROR_MSG);
+        self.walk_generator(
+            &dict_comp.generators,
+            key,
+            Some(&dict_comp.entry.value),
+            Some(&dict_comp.entry.operation),
+            0,
+            &mut collection_value,
+            &ast::CompType::Dict,
+        );
+        self.leave_scope();
+        Ok(collection_value)
+    }
+
+    fn walk_config_if_entry_expr(
+        &self,
+        config_if_entry_expr: &'ctx ast::ConfigIfEntryExpr,
+    ) -> Self::Result {
+        check_backtrack_stop!(self);
+        let cond = self
+            .walk_expr(&config_if_entry_expr.if_cond)
+            .expect(kcl_error::RUNTIME_ERROR_MSG);
+        if cond.is_truthy() {
+            self.walk_config_entries(&config_if_entry_expr.items)
+        } else if let Some(orelse) = &config_if_entry_expr.orelse {
+            self.walk_expr(orelse)
+        } else {
+            Ok(ValueRef::none())
+        }
+    }
+
+    fn walk_comp_clause(&self, _comp_clause: &'ctx ast::CompClause) -> Self::Result {
+        // Nothing to do on this AST node
+        self.ok_result()
+    }
+
+    fn walk_schema_expr(&self, schema_expr: &'ctx ast::SchemaExpr) -> Self::Result {
+        check_backtrack_stop!(self);
+        // Check the required attributes only when the values of all attributes
+        // in the final schema are solved.
+        let is_in_schema = self.is_in_schema_or_schema_expr();
+        *self.schema_expr_depth.borrow_mut() += 1;
+        let config_value = self
+            .walk_expr(&schema_expr.config)
+            .expect(kcl_error::RUNTIME_ERROR_MSG);
+        let schema_type = self
+            .walk_identifier_with_ctx(&schema_expr.name.node, &schema_expr.name.node.ctx, None)
+            .expect(kcl_error::RUNTIME_ERROR_MSG);
+        let config_expr = match &schema_expr.config.node {
+            ast::Expr::Config(config_expr) => config_expr,
+            _ => panic!("invalid schema config expr"),
+        };
+        let config_meta = self.get_schema_config_meta(Some(&schema_expr.name), config_expr);
+        let mut list_value = ValueRef::list(None);
+        for arg in &schema_expr.args {
+            let value = self.walk_expr(arg).expect(kcl_error::RUNTIME_ERROR_MSG);
+            list_value.list_append(&value);
+        }
+        let mut dict_value = ValueRef::dict(None);
+        for keyword in &schema_expr.kwargs {
+            let name = &keyword.node.arg.node.names[0];
+            let value = if let Some(value) = &keyword.node.value {
+                self.walk_expr(value).expect(kcl_error::RUNTIME_ERROR_MSG)
+            } else {
+                ValueRef::none()
+            };
+            dict_value.dict_insert(
+                &mut self.runtime_ctx.borrow_mut(),
+                name.as_str(),
+                &value,
+                ConfigEntryOperationKind::Union,
+                -1,
+            );
+        }
+        let schema = self.schema_value_new(
+            &schema_type,
+            &list_value,
+            &dict_value,
+            &config_value,
+            &config_meta,
+            &self.current_pkgpath(),
+        );
+        if !is_in_schema && !self.runtime_ctx.borrow().cfg.disable_schema_check {
+            schema.schema_check_attr_optional(&mut self.runtime_ctx.borrow_mut(), true);
+        }
+        self.update_ctx_filename(&schema_expr.config);
+        *self.schema_expr_depth.borrow_mut() -= 1;
+        Ok(schema)
+    }
+
+    fn walk_config_expr(&self, config_expr: &'ctx ast::ConfigExpr) -> Self::Result {
+        check_backtrack_stop!(self);
+        self.walk_config_entries(&config_expr.items)
+    }
+
+    fn walk_check_expr(&self, check_expr: &'ctx ast::CheckExpr) -> Self::Result {
+        check_backtrack_stop!(self);
+        if let Some(if_cond) = &check_expr.if_cond {
+            let if_value = self.walk_expr(if_cond).expect(kcl_error::RUNTIME_ERROR_MSG);
+            if !if_value.is_truthy() {
+                return self.ok_result();
+            }
+        }
+        let check_result = self
+            .walk_expr(&check_expr.test)
+            .expect(kcl_error::RUNTIME_ERROR_MSG);
+        let msg = {
+            if let Some(msg) = &check_expr.msg {
+                self.walk_expr(msg).expect(kcl_error::RUNTIME_ERROR_MSG)
+            } else {
+                ValueRef::str("")
+            }
+        };
+        let schema_config_meta = self.schema_ctx().borrow().config_meta.clone();
+        self.update_ctx_current_line();
+        unsafe {
+            kclvm_schema_assert(
+                &mut **self.runtime_ctx.borrow_mut(),
+                &check_result,
+                &msg,
+                &schema_config_meta,
+            )
+        };
+        self.ok_result()
+    }
+
+    fn walk_lambda_expr(&self, lambda_expr: &'ctx ast::LambdaExpr) -> Self::Result {
+        check_backtrack_stop!(self);
+        // Capture the local variables and the schema context as the lambda closure.
+        let closure = self.get_local_variables();
+        let schema_ctx = if self.is_in_schema() {
+            Some(self.schema_ctx())
+        } else {
+            None
+        };
+        let index = self.add_frame(Proxy::Lambda(LambdaCaller {
+            node: lambda_expr,
+            closure,
+            schema_ctx,
+        }));
+        Ok(ValueRef::proxy_func(index, "", ""))
+    }
+
+    fn walk_keyword(&self, _keyword: &'ctx ast::Keyword) -> Self::Result {
+        // Nothing to do
+        self.ok_result()
+    }
+
+    fn walk_arguments(&self, _arguments: &'ctx ast::Arguments) -> Self::Result {
+        // Nothing to do
+        self.ok_result()
+    }
+
+    fn walk_compare(&self, compare: &'ctx ast::Compare) -> Self::Result {
+        check_backtrack_stop!(self);
+        let mut left_value = self
+            .walk_expr(&compare.left)
+            .expect(kcl_error::RUNTIME_ERROR_MSG);
+        let mut result_value = ValueRef::undefined();
+        for (i, op) in compare.ops.iter().enumerate() {
+            let right_value = self
+                .walk_expr(&compare.comparators[i])
+                .expect(kcl_error::RUNTIME_ERROR_MSG);
+            result_value = compare_values(&left_value, &right_value, op);
+            // Short circuit the chained comparison on the false result.
+            if !result_value.is_truthy() {
+                break;
+            }
+            left_value = right_value;
+        }
+        Ok(result_value)
+    }
+
+    fn walk_identifier(&self, identifier: &'ctx ast::Identifier) -> Self::Result {
+        check_backtrack_stop!(self);
+        self.walk_identifier_with_ctx(identifier, &identifier.ctx, None)
+    }
+
+    fn walk_number_lit(&self, number_lit: &'ctx ast::NumberLit) -> Self::Result {
+        check_backtrack_stop!(self);
+        match number_lit.value {
+            ast::NumberLitValue::Int(int_value) => match &number_lit.binary_suffix {
+                Some(binary_suffix) => {
+                    let unit = binary_suffix.value();
+                    let value = kclvm_runtime::cal_num(int_value, unit.as_str());
+                    Ok(ValueRef::unit(value, int_value, &unit))
+                }
+                None => Ok(ValueRef::int(int_value)),
+            },
+            ast::NumberLitValue::Float(float_value) => Ok(ValueRef::float(float_value)),
+        }
+    }
+
+    fn walk_string_lit(&self, string_lit: &'ctx ast::StringLit) -> Self::Result {
+        check_backtrack_stop!(self);
+        Ok(ValueRef::str(string_lit.value.as_str()))
+    }
+
+    fn walk_name_constant_lit(
+        &self,
+        name_constant_lit: &'ctx ast::NameConstantLit,
+    ) -> Self::Result {
+        check_backtrack_stop!(self);
+        match name_constant_lit.value {
+            ast::NameConstant::True => Ok(ValueRef::bool(true)),
+            ast::NameConstant::False => Ok(ValueRef::bool(false)),
+            ast::NameConstant::None => Ok(ValueRef::none()),
+            ast::NameConstant::Undefined => Ok(ValueRef::undefined()),
+        }
+    }
+
+    fn walk_joined_string(&self, joined_string: &'ctx ast::JoinedString) -> Self::Result {
+        check_backtrack_stop!(self);
+        let mut result_value = ValueRef::str("");
+        for value in &joined_string.values {
+            let value = &value.node;
+            let value = match value {
+                ast::Expr::FormattedValue(formatted_value) => self
+                    .walk_formatted_value(formatted_value)
+                    .expect(kcl_error::INTERNAL_ERROR_MSG),
+                ast::Expr::StringLit(string_lit) => self
+                    .walk_string_lit(string_lit)
+                    .expect(kcl_error::INTERNAL_ERROR_MSG),
+                _ => panic!("{}", kcl_error::INVALID_JOINED_STR_MSG),
+            };
+            result_value = result_value.bin_add(&mut self.runtime_ctx.borrow_mut(), &value);
+        }
+        Ok(result_value)
+    }
+
+    fn walk_formatted_value(&self, formatted_value: &'ctx ast::FormattedValue) -> Self::Result {
+        check_backtrack_stop!(self);
+        let formatted_expr_value = self
+            .walk_expr(&formatted_value.value)
+            .expect(kcl_error::RUNTIME_ERROR_MSG);
+        let value = if let Some(spec) = &formatted_value.format_spec {
+            match spec.to_lowercase().as_str() {
+                "#json" => formatted_expr_value.to_json_string(),
+                "#yaml" => formatted_expr_value.to_yaml_string(),
+                _ => panic!("{}", kcl_error::INVALID_STR_INTERPOLATION_SPEC_MSG),
+            }
+        } else {
+            formatted_expr_value.to_string()
+        };
+        Ok(ValueRef::str(&value))
+    }
+
+    fn walk_comment(&self, _comment: &'ctx ast::Comment) -> Self::Result {
+        // Nothing to do
+        self.ok_result()
+    }
+
+    fn walk_missing_expr(&self, _missing_expr: &'ctx ast::MissingExpr) -> Self::Result {
+        Err(anyhow::anyhow!("runtime error: missing expression"))
+    }
+
+    fn walk_module(&self, module: &'ctx ast::Module) -> Self::Result {
+        check_backtrack_stop!(self);
+        if !module.body.is_empty() {
+            self.update_ctx_filename(&module.body[0]);
+        }
+        // Evaluate all schemas, rules and imports firstly
+        self.compile_module_import_and_types(module);
+        // Evaluate all statements of the module
+        self.walk_stmts_except_import(&module.body)
+    }
+}
+
+impl<'ctx> Evaluator<'ctx> {
+    pub fn walk_stmts_except_import(&self, stmts: &'ctx [Box<ast::Node<ast::Stmt>>]) -> EvalResult {
+        check_backtrack_stop!(self);
+        let mut result = self.ok_result();
+        for stmt in stmts {
+            if !matches!(&stmt.node, ast::Stmt::Import(..)) {
+                result = self.walk_stmt(stmt);
+            }
+        }
+        result
+    }
+
+    pub fn walk_stmts(&self, stmts: &'ctx [Box<ast::Node<ast::Stmt>>]) -> EvalResult {
+        check_backtrack_stop!(self);
+        // Empty statements return None value
+        let mut result = Ok(ValueRef::none());
+        for stmt in stmts {
+            result = self.walk_stmt(stmt);
+        }
+        result
+    }
+
+    pub(crate) fn walk_identifier_with_ctx(
+        &self,
+        identifier: &'ctx ast::Identifier,
+        identifier_ctx: &ast::ExprContext,
+        right_value: Option<ValueRef>,
+    ) -> EvalResult {
+        check_backtrack_stop!(self);
+        let is_in_schema = self.is_in_schema();
+        match identifier_ctx {
+            ast::ExprContext::Store => {
+                if identifier.names.len() == 1 {
+                    let name = identifier.names[0].as_str();
+                    // Global variables
+                    if self.scope_level() == GLOBAL_LEVEL {
+                        self.add_or_update_global_variable(
+                            name,
+                            right_value.clone().expect(kcl_error::INTERNAL_ERROR_MSG),
+                        );
+                    // Lambda local variables.
+                    } else if self.is_in_lambda() {
+                        let value = right_value.clone().expect(kcl_error::INTERNAL_ERROR_MSG);
+                        // If variable exists in the scope and update it, if not, add it to the scope.
+                        if !self.store_variable_in_current_scope(name, value.clone()) {
+                            self.add_variable(name, value);
+                        }
+                    } else {
+                        let is_local_var = self.local_vars.borrow().contains(name);
+                        let value = right_value.clone().expect(kcl_error::INTERNAL_ERROR_MSG);
+                        // Schema attribute variables.
+                        if is_in_schema && self.scope_level() >= INNER_LEVEL && !is_local_var {
+                            let schema_ctx = self.schema_ctx();
+                            let (mut schema_value, config_value) = {
+                                let s = schema_ctx.borrow();
+                                (s.value.clone(), s.config.clone())
+                            };
+                            let config_value = config_value
+                                .dict_get_entry(name)
+                                .unwrap_or_else(ValueRef::none);
+                            self.dict_merge(
+                                &mut schema_value,
+                                name,
+                                &value,
+                                &ConfigEntryOperationKind::Override,
+                                -1,
+                            );
+                            self.value_union(&mut schema_value, &config_value);
+                            self.schema_backtrack_cache(&schema_ctx, name);
+                            // Update backtrack meta
+                            if self.update_backtrack_meta(name) {
+                                return Ok(schema_value);
+                            }
+                        }
+                        // Local variables including schema/rule/lambda
+                        if is_local_var || !is_in_schema {
+                            self.add_variable(name, value);
+                        }
+                    }
+                } else {
+                    let names = &identifier.names;
+                    let name = names[0].as_str();
+                    let mut value = if is_in_schema {
+                        self.get_variable_in_schema(name)
+                            .expect(kcl_error::INTERNAL_ERROR_MSG)
+                    } else {
+                        self.get_variable(name)
+                            .expect(kcl_error::INTERNAL_ERROR_MSG)
+                    };
+                    for i in 0..names.len() - 1 {
+                        let attr = names[i + 1].as_str();
+                        if i != names.len() - 2 && names.len() > 2 {
+                            value = self.load_attr(&value, attr);
+                        } else {
+                            self.dict_set_value(
+                                &mut value,
+                                attr,
+                                &right_value.clone().expect(kcl_error::INTERNAL_ERROR_MSG),
+                            );
+                        }
+                    }
+                }
+                Ok(right_value.expect(kcl_error::INTERNAL_ERROR_MSG))
+            }
+            ast::ExprContext::Load => {
+                let name = identifier.names[0].as_str();
+                let is_local_var = self.local_vars.borrow().contains(name);
+                if identifier.names.len() == 1 {
+                    if is_in_schema && !is_local_var {
+                        self.get_variable_in_schema(name)
+                    } else {
+                        // The variables defined in the branches which are not executed
+                        // are undefined, e.g., `if False: v = 1`.
+                        Ok(self
+                            .get_variable(name)
+                            .unwrap_or_else(|_| ValueRef::undefined()))
+                    }
+                } else {
+                    let names = &identifier.names;
+                    let mut value = if identifier.pkgpath.is_empty() {
+                        if is_in_schema && !is_local_var {
+                            self.get_variable_in_schema(name)
+                                .expect(kcl_error::INTERNAL_ERROR_MSG)
+                        } else {
+                            self.get_variable(name)
+                                .expect(kcl_error::INTERNAL_ERROR_MSG)
+                        }
+                    } else {
+                        ValueRef::undefined()
+                    };
+                    for i in 0..names.len() - 1 {
+                        let attr = names[i + 1].as_str();
+                        if i == 0 && !identifier.pkgpath.is_empty() {
+                            value = self
+                                .get_variable_in_pkgpath(attr, &identifier.pkgpath)
+                                .expect(kcl_error::INTERNAL_ERROR_MSG)
+                        } else {
+                            value = self.load_attr(&value, attr)
+                        }
+                    }
+                    Ok(value)
+                }
+            }
+        }
+    }
+
+    /// Evaluate the decorator `decorator` of the schema or the schema attribute `attr_name`.
+    pub(crate) fn walk_decorator_with_name(
+        &self,
+        decorator: &'ctx CallExpr,
+        attr_name: Option<&str>,
+        is_schema_target: bool,
+    ) -> EvalResult {
+        check_backtrack_stop!(self);
+        let mut list_value = ValueRef::list(None);
+        let mut dict_value = ValueRef::dict(None);
+        let (config_meta, config_value) = {
+            let schema_ctx = self.schema_ctx();
+            let s = schema_ctx.borrow();
+            (s.config_meta.clone(), s.config.clone())
+        };
+        for arg in &decorator.args {
+            let value = self.walk_expr(arg).expect(kcl_error::RUNTIME_ERROR_MSG);
+            list_value.list_append(&value);
+        }
+        for keyword in &decorator.keywords {
+            let name = &keyword.node.arg.node.names[0];
+            let value = if let Some(value) = &keyword.node.value {
+                self.walk_expr(value).expect(kcl_error::RUNTIME_ERROR_MSG)
+            } else {
+                ValueRef::none()
+            };
+            dict_value.dict_insert(
+                &mut self.runtime_ctx.borrow_mut(),
+                name.as_str(),
+                &value,
+                ConfigEntryOperationKind::Union,
+                -1,
+            );
+        }
+        let name = match &decorator.func.node {
+            ast::Expr::Identifier(ident) if ident.names.len() == 1 => ident.names[0].clone(),
+            _ => panic!("invalid decorator name, expect single identifier"),
+        };
+        let attr_name = attr_name.unwrap_or_default();
+        DecoratorValue::new(&name, &list_value, &dict_value).run(
+            &mut self.runtime_ctx.borrow_mut(),
+            attr_name,
+            is_schema_target,
+            &config_value,
+            &config_meta,
+        );
+        self.ok_result()
+    }
+
+    /// Store the lambda, schema or rule arguments in the current scope.
+    pub(crate) fn walk_arguments(
+        &self,
+        arguments: &'ctx Option<ast::NodeRef<ast::Arguments>>,
+        args: &ValueRef,
+        kwargs: &ValueRef,
+    ) {
+        // Arguments names and defaults
+        let (arg_names, arg_defaults) = if let Some(args) = &arguments {
+            let names = &args.node.args;
+            let defaults = &args.node.defaults;
+            (
+                names.iter().map(|identifier| &identifier.node).collect(),
+                defaults.iter().collect(),
+            )
+        } else {
+            (vec![], vec![])
+        };
+        // Default parameter values
+        for (arg_name, value) in arg_names.iter().zip(arg_defaults.iter()) {
+            let arg_value = if let Some(value) = value {
+                self.walk_expr(value).expect(kcl_error::RUNTIME_ERROR_MSG)
+            } else {
+                ValueRef::none()
+            };
+            self.store_argument_in_current_scope(&arg_name.get_name());
+            self.walk_identifier_with_ctx(arg_name, &ast::ExprContext::Store, Some(arg_value))
+                .expect(kcl_error::RUNTIME_ERROR_MSG);
+        }
+        // Positional arguments
+        let argument_len = args.len();
+        for (i, arg_name) in arg_names.iter().enumerate() {
+            if i >= argument_len {
+                break;
+            }
+            if let Some(arg_value) = args.list_get_option(i as isize) {
+                self.store_variable(&arg_name.names[0], arg_value);
+            }
+        }
+        // Keyword arguments
+        for arg_name in arg_names.iter() {
+            let name = &arg_name.names[0];
+            if let Some(arg) = kwargs.dict_get_value(name) {
+                // Find argument name in the scope
+                self.store_variable(&arg_name.names[0], arg);
+            }
+        }
+    }
+
+    /// Evaluate the comprehension generators and append the elements into `collection_value`.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn walk_generator(
+        &self,
+        generators: &'ctx [Box<ast::Node<ast::CompClause>>],
+        elt: &'ctx ast::Node<ast::Expr>,
+        val: Option<&'ctx ast::Node<ast::Expr>>,
+        op: Option<&'ctx ast::ConfigEntryOperation>,
+        gen_index: usize,
+        collection_value: &mut ValueRef,
+        comp_type: &ast::CompType,
+    ) {
+        let generator = &generators[gen_index];
+        let iter_host_value = self
+            .walk_expr(&generator.node.iter)
+            .expect(kcl_error::RUNTIME_ERROR_MSG);
+        let mut iter_value = iter_host_value.iter();
+        let targets = &generator.node.targets;
+        {
+            let mut local_vars = self.local_vars.borrow_mut();
+            for v in targets {
+                let name = &v.node.names[0];
+                local_vars.insert(name.clone());
+            }
+        }
+        while let Some(next_value) = iter_value.next(&iter_host_value) {
+            self.runtime_ctx.borrow_mut().check_timeout();
+            let next_value = next_value.clone();
+            if targets.len() == 1 {
+                // Store the target
+                self.walk_identifier_with_ctx(
+                    &targets[0].node,
+                    &ast::ExprContext::Store,
+                    Some(next_value),
+                )
+                .expect(kcl_error::RUNTIME_ERROR_MSG);
+            } else if targets.len() == 2 {
+                let key = iter_value
+                    .key()
+                    .cloned()
+                    .unwrap_or_else(ValueRef::undefined);
+                let value = iter_value
+                    .value()
+                    .cloned()
+                    .unwrap_or_else(ValueRef::undefined);
+                // Store the target
+                self.walk_identifier_with_ctx(
+                    &targets[0].node,
+                    &ast::ExprContext::Store,
+                    Some(key),
+                )
+                .expect(kcl_error::RUNTIME_ERROR_MSG);
+                self.walk_identifier_with_ctx(
+                    &targets[1].node,
+                    &ast::ExprContext::Store,
+                    Some(value),
+                )
+                .expect(kcl_error::RUNTIME_ERROR_MSG);
+            } else {
+                panic!(
+                    "the number of loop variables is {}, which can only be 1 or 2",
+                    generator.node.targets.len()
+                )
+            }
+            // Check the if filters
+            let mut skip = false;
+            for if_expr in &generator.node.ifs {
+                let value = self.walk_expr(if_expr).expect(kcl_error::RUNTIME_ERROR_MSG);
+                if !value.is_truthy() {
+                    skip = true;
+                    break;
+                }
+            }
+            if skip {
+                continue;
+            }
+            let next_gen_index = gen_index + 1;
+            if next_gen_index >= generators.len() {
+                match comp_type {
+                    ast::CompType::List => {
+                        let item = self.walk_expr(elt).expect(kcl_error::RUNTIME_ERROR_MSG);
+                        collection_value.list_append(&item);
+                    }
+                    ast::CompType::Dict => {
+                        let value = self
+                            .walk_expr(val.expect(kcl_error::INTERNAL_ERROR_MSG))
+                            .expect(kcl_error::RUNTIME_ERROR_MSG);
+                        let key = self.walk_expr(elt).expect(kcl_error::RUNTIME_ERROR_MSG);
+                        let op = op.expect(kcl_error::INTERNAL_ERROR_MSG);
+                        collection_value.dict_insert(
+                            &mut self.runtime_ctx.borrow_mut(),
+                            key.attr_str().as_str(),
+                            &value,
+                            ConfigEntryOperationKind::from_i32(op.value()),
+                            -1,
+                        );
+                    }
+                }
+            } else {
+                self.walk_generator(
+                    generators,
+                    elt,
+                    val,
+                    op,
+                    next_gen_index,
+                    collection_value,
+                    comp_type,
+                );
+            }
+        }
+        {
+            let mut local_vars = self.local_vars.borrow_mut();
+            for v in targets {
+                let name = &v.node.names[0];
+                local_vars.remove(name);
+            }
+        }
+    }
+
+    /// Evaluate the config entries to a dict value.
+    pub(crate) fn walk_config_entries(&self, items: &'ctx [NodeRef<ConfigEntry>]) -> EvalResult {
+        let mut config_value = ValueRef::dict(None);
+        self.enter_scope();
+        for item in items {
+            let value = self.walk_expr(&item.node.value)?;
+            if let Some(key) = &item.node.key {
+                let mut insert_index = -1;
+                let optional_name = match &key.node {
+                    ast::Expr::Identifier(identifier) => Some(identifier.names[0].clone()),
+                    ast::Expr::StringLit(string_lit) => Some(string_lit.value.clone()),
+                    ast::Expr::Subscript(subscript) => {
+                        let mut name = None;
+                        if let ast::Expr::Identifier(identifier) = &subscript.value.node {
+                            if let Some(index_node) = &subscript.index {
+                                if let ast::Expr::NumberLit(number) = &index_node.node {
+                                    if let ast::NumberLitValue::Int(v) = number.value {
+                                        insert_index = v;
+                                        name = Some(identifier.names[0].clone())
+                                    }
+                                }
+                            }
+                        }
+                        name
+                    }
+                    _ => None,
+                };
+                // Store a local variable for every entry key.
+                let key = match optional_name {
+                    Some(name) => {
+                        self.add_or_update_local_variable(&name, value.clone());
+                        ValueRef::str(&name)
+                    }
+                    None => self.walk_expr(key)?,
+                };
+                config_value.dict_insert(
+                    &mut self.runtime_ctx.borrow_mut(),
+                    key.attr_str().as_str(),
+                    &value,
+                    ConfigEntryOperationKind::from_i32(item.node.operation.value()),
+                    insert_index as i32,
+                );
+            } else {
+                // If the key does not exist, execute the logic of unpacking expression `**expr` here.
+                self.dict_insert_unpack(&mut config_value, &value);
+            }
+        }
+        self.leave_scope();
+        Ok(config_value)
+    }
+
+    /// Load the attribute `attr` of the value `value` including dict/schema
+    /// attributes, str member functions and schema type member functions.
+    pub(crate) fn load_attr(&self, value: &ValueRef, attr: &str) -> ValueRef {
+        let attr = CString::new(attr).expect(kcl_error::INTERNAL_ERROR_MSG);
+        unsafe {
+            ptr_as_ref(kclvm_value_load_attr(
+                &mut **self.runtime_ctx.borrow_mut(),
+                value,
+                attr.as_ptr(),
+            ))
+            .clone()
+        }
+    }
+}
+
+/// Compare the values `left` and `right` with the comparison operator `op`.
+fn compare_values(left: &ValueRef, right: &ValueRef, op: &ast::CmpOp) -> ValueRef {
+    ValueRef::bool(match op {
+        ast::CmpOp::Eq => left.cmp_equal(right),
+        ast::CmpOp::NotEq => !left.cmp_equal(right),
+        ast::CmpOp::Gt => left.cmp_greater_than(right),
+        ast::CmpOp::GtE => left.cmp_greater_than_or_equal(right),
+        ast::CmpOp::Lt => left.cmp_less_than(right),
+        ast::CmpOp::LtE => left.cmp_less_than_or_equal(right),
+        ast::CmpOp::Is => left == right,
+        ast::CmpOp::IsNot => left != right,
+        ast::CmpOp::Not => left != right,
+        ast::CmpOp::NotIn => left.not_in(right),
+        ast::CmpOp::In => left.r#in(right),
+    })
+}
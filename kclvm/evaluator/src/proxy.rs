@@ -0,0 +1,124 @@
+// Copyright 2021 The KCL Authors. All rights reserved.
+
+use indexmap::IndexMap;
+use kclvm_ast::ast;
+use kclvm_runtime::ValueRef;
+
+use crate::schema::SchemaEvalContextRef;
+use crate::scope::Scope;
+use crate::Evaluator;
+
+/// A function frame which denotes a KCL lambda, schema, rule or schema attribute
+/// function and the package it is defined in.
+pub struct Frame<'ctx> {
+    pub pkgpath: String,
+    pub filename: String,
+    pub proxy: Proxy<'ctx>,
+}
+
+/// The function proxy kinds.
+pub enum Proxy<'ctx> {
+    Lambda(LambdaCaller<'ctx>),
+    Schema(SchemaCaller<'ctx>),
+    Rule(RuleCaller<'ctx>),
+    SchemaAttr(SchemaAttrCaller<'ctx>),
+}
+
+/// A lambda function with its captured closure variables.
+pub struct LambdaCaller<'ctx> {
+    pub node: &'ctx ast::LambdaExpr,
+    /// Local variables captured when the lambda is defined.
+    pub closure: IndexMap<String, ValueRef>,
+    /// The schema context captured when the lambda is defined in a schema.
+    pub schema_ctx: Option<SchemaEvalContextRef>,
+}
+
+/// A schema constructor function.
+pub struct SchemaCaller<'ctx> {
+    pub node: &'ctx ast::SchemaStmt,
+    pub runtime_type: String,
+}
+
+/// A rule constructor function.
+pub struct RuleCaller<'ctx> {
+    pub node: &'ctx ast::RuleStmt,
+    pub runtime_type: String,
+}
+
+/// A schema attribute function which calculates the attribute `name`
+/// using the statement `stmt` in the schema `schema`.
+pub struct SchemaAttrCaller<'ctx> {
+    pub schema: &'ctx ast::SchemaStmt,
+    pub stmt: &'ctx ast::Node<ast::Stmt>,
+    pub name: String,
+    pub runtime_type: String,
+    /// The backtrack level of the if statement, 0 denotes a non-if statement.
+    pub if_level: usize,
+}
+
+/// The evaluator state saved before entering a function frame and restored after it.
+struct FrameState {
+    local_scopes: Vec<Scope>,
+    local_vars: std::collections::HashSet<String>,
+    target_vars: Vec<String>,
+    schema_stack: Vec<SchemaEvalContextRef>,
+    schema_expr_depth: usize,
+    backtrack_meta: Option<crate::schema::BacktrackMeta>,
+}
+
+impl<'ctx> Evaluator<'ctx> {
+    /// Append a function frame and return the proxy function index.
+    pub(crate) fn add_frame(&self, proxy: Proxy<'ctx>) -> u64 {
+        let mut frames = self.frames.borrow_mut();
+        frames.push(std::rc::Rc::new(Frame {
+            pkgpath: self.current_pkgpath(),
+            filename: self.current_filename(),
+            proxy,
+        }));
+        (frames.len() - 1) as u64
+    }
+
+    /// Get the function frame using the proxy function index.
+    pub(crate) fn get_frame(&self, index: u64) -> std::rc::Rc<Frame<'ctx>> {
+        self.frames
+            .borrow()
+            .get(index as usize)
+            .unwrap_or_else(|| panic!("function frame {} is not found", index))
+            .clone()
+    }
+
+    /// Run the function `f` in the frame `frame`: the package path, the local scopes
+    /// and the schema state of the caller are saved and restored after the call.
+    pub(crate) fn with_frame<F, R>(&self, frame: &Frame<'ctx>, is_lambda: bool, f: F) -> R
+    where
+        F: FnOnce() -> R,
+    {
+        self.push_pkgpath(&frame.pkgpath);
+        self.push_filename(&frame.filename);
+        let state = FrameState {
+            local_scopes: self.take_local_scopes(&frame.pkgpath),
+            local_vars: std::mem::take(&mut *self.local_vars.borrow_mut()),
+            target_vars: std::mem::replace(
+                &mut *self.target_vars.borrow_mut(),
+                vec![String::from("")],
+            ),
+            schema_stack: std::mem::take(&mut *self.schema_stack.borrow_mut()),
+            schema_expr_depth: std::mem::take(&mut *self.schema_expr_depth.borrow_mut()),
+            backtrack_meta: self.backtrack_meta.borrow_mut().take(),
+        };
+        self.lambda_stack.borrow_mut().push(is_lambda);
+        self.debug_enter_frame(frame);
+        let result = f();
+        self.debug_leave_frame();
+        self.lambda_stack.borrow_mut().pop();
+        self.restore_local_scopes(&frame.pkgpath, state.local_scopes);
+        *self.local_vars.borrow_mut() = state.local_vars;
+        *self.target_vars.borrow_mut() = state.target_vars;
+        *self.schema_stack.borrow_mut() = state.schema_stack;
+        *self.schema_expr_depth.borrow_mut() = state.schema_expr_depth;
+        *self.backtrack_meta.borrow_mut() = state.backtrack_meta;
+        self.pop_filename();
+        self.pop_pkgpath();
+        result
+    }
+}
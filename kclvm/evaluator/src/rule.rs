@@ -0,0 +1,87 @@
+// Copyright 2021 The KCL Authors. All rights reserved.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use kclvm_ast::walker::TypedResultWalker;
+use kclvm_runtime::ValueRef;
+
+use crate::error as kcl_error;
+use crate::proxy::RuleCaller;
+use crate::schema::SchemaEvalContextRef;
+use crate::Evaluator;
+
+impl<'ctx> Evaluator<'ctx> {
+    /// The rule body function.
+    pub(crate) fn rule_body(
+        &self,
+        caller: &RuleCaller<'ctx>,
+        s: &SchemaEvalContextRef,
+        args: &ValueRef,
+        kwargs: &ValueRef,
+    ) -> ValueRef {
+        let rule_stmt = caller.node;
+        s.borrow_mut().runtime_type = caller.runtime_type.clone();
+        self.runtime_ctx
+            .borrow_mut()
+            .set_kcl_filename(&self.current_filename());
+        self.enter_scope();
+        self.walk_arguments(&rule_stmt.args, args, kwargs);
+        self.schema_stack.borrow_mut().push(s.clone());
+        // Construct for protocol
+        if let Some(for_host_name) = &rule_stmt.for_host_name {
+            let index = self.base_schema_index(for_host_name);
+            let base_ctx = Rc::new(RefCell::new(s.borrow().to_base()));
+            let value = self.invoke_schema_body(index, &base_ctx);
+            s.borrow_mut().value = value;
+        }
+        let (record_instance, is_sub_schema) = {
+            let s = s.borrow();
+            (s.record_instance, s.is_sub_schema)
+        };
+        if record_instance {
+            // Rule decorators check
+            for decorator in &rule_stmt.decorators {
+                self.walk_decorator_with_name(&decorator.node, Some(&rule_stmt.name.node), true)
+                    .expect(kcl_error::RUNTIME_ERROR_MSG);
+            }
+        }
+        if is_sub_schema {
+            self.rule_check(caller, s, &ValueRef::list(None), &ValueRef::dict(None));
+        }
+        self.schema_stack.borrow_mut().pop();
+        self.leave_scope();
+        // Rule constructor function returns a rule
+        let value = s.borrow().value.clone();
+        value
+    }
+
+    /// The rule check function which evaluates all check expressions of
+    /// the rule and its parent rules.
+    pub(crate) fn rule_check(
+        &self,
+        caller: &RuleCaller<'ctx>,
+        s: &SchemaEvalContextRef,
+        args: &ValueRef,
+        kwargs: &ValueRef,
+    ) {
+        let rule_stmt = caller.node;
+        self.enter_scope();
+        let schema_stack = std::mem::take(&mut *self.schema_stack.borrow_mut());
+        self.walk_arguments(&rule_stmt.args, args, kwargs);
+        self.schema_stack.borrow_mut().push(s.clone());
+        // Call base check function
+        for parent_name in &rule_stmt.parent_rules {
+            let index = self.base_schema_index(parent_name);
+            let base_ctx = Rc::new(RefCell::new(s.borrow().to_base()));
+            self.invoke_schema_check(index, &base_ctx);
+        }
+        // Call self rule check expressions
+        for check_expr in &rule_stmt.checks {
+            self.walk_check_expr(&check_expr.node)
+                .expect(kcl_error::RUNTIME_ERROR_MSG);
+        }
+        *self.schema_stack.borrow_mut() = schema_stack;
+        self.leave_scope();
+    }
+}
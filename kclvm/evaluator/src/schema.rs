@@ -0,0 +1,853 @@
+// Copyright 2021 The KCL Authors. All rights reserved.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use kclvm_ast::ast;
+use kclvm_ast::walker::TypedResultWalker;
+use kclvm_runtime::{
+    schema_config_meta, schema_runtime_type, BacktraceFrame, ConfigEntryOperationKind, ProfileSpan,
+    ValueRef, CAL_MAP_META_LINE, CAL_MAP_RUNTIME_TYPE, MAIN_PKG_PATH, PROFILE_KIND_CHECK,
+    PROFILE_KIND_SCHEMA,
+};
+
+use crate::error as kcl_error;
+use crate::proxy::{Proxy, SchemaAttrCaller, SchemaCaller};
+use crate::Evaluator;
+
+pub type SchemaEvalContextRef = Rc<RefCell<SchemaEvalContext>>;
+
+/// The schema evaluation context which holds all the values shared by the schema
+/// body function, the schema check function and the schema attribute functions.
+#[derive(Clone, Debug)]
+pub struct SchemaEvalContext {
+    pub is_sub_schema: bool,
+    pub config_meta: ValueRef,
+    pub config: ValueRef,
+    pub value: ValueRef,
+    pub optional_mapping: ValueRef,
+    pub cal_map: ValueRef,
+    pub backtrack_level_map: ValueRef,
+    pub backtrack_cache: ValueRef,
+    pub record_instance: bool,
+    pub instance_pkgpath: String,
+    pub args: ValueRef,
+    pub kwargs: ValueRef,
+    /// The runtime type of the schema or rule function which is being evaluated.
+    pub runtime_type: String,
+}
+
+impl SchemaEvalContext {
+    /// New a schema evaluation context with empty schema value and the cal order map `cal_map`.
+    pub fn new(
+        config: &ValueRef,
+        config_meta: &ValueRef,
+        cal_map: &ValueRef,
+        args: &ValueRef,
+        kwargs: &ValueRef,
+        instance_pkgpath: &str,
+    ) -> Self {
+        SchemaEvalContext {
+            is_sub_schema: false,
+            config_meta: config_meta.clone(),
+            config: config.clone(),
+            value: ValueRef::dict(None),
+            optional_mapping: ValueRef::dict(None),
+            cal_map: cal_map.clone(),
+            backtrack_level_map: ValueRef::dict(None),
+            backtrack_cache: ValueRef::dict(None),
+            record_instance: false,
+            instance_pkgpath: instance_pkgpath.to_string(),
+            args: args.clone(),
+            kwargs: kwargs.clone(),
+            runtime_type: "".to_string(),
+        }
+    }
+
+    /// Get a context shared with the parent or mixin schema functions.
+    #[inline]
+    pub fn to_base(&self) -> Self {
+        SchemaEvalContext {
+            is_sub_schema: false,
+            args: ValueRef::list(None),
+            kwargs: ValueRef::dict(None),
+            ..self.clone()
+        }
+    }
+}
+
+/// Schema attr backtrack meta
+#[derive(Clone, Debug, Default)]
+pub struct BacktrackMeta {
+    pub target: String,
+    pub level: usize,
+    pub count: usize,
+    pub stop: bool,
+}
+
+impl<'ctx> Evaluator<'ctx> {
+    /// Construct a schema value using the schema function `func` which calls the
+    /// schema body twice: the first call only collects the attribute functions
+    /// into the cal order map and the second one evaluates the schema body.
+    pub(crate) fn construct_schema_value(
+        &self,
+        func: &ValueRef,
+        args: &ValueRef,
+        kwargs: &ValueRef,
+        config: &ValueRef,
+        config_meta: &ValueRef,
+        instance_pkgpath: &str,
+    ) -> ValueRef {
+        let (proxy, runtime_type) = {
+            let func_value = func.as_function();
+            (func_value.proxy, func_value.runtime_type.clone())
+        };
+        let now_meta_info = {
+            let mut ctx = self.runtime_ctx.borrow_mut();
+            ctx.enter_call();
+            ctx.profiler.begin(PROFILE_KIND_SCHEMA, &runtime_type);
+            let now_meta_info = ctx.panic_info.clone();
+            if ctx.cfg.debug_mode {
+                let frame = BacktraceFrame::from_panic_info(&ctx.panic_info);
+                ctx.backtrace.push(frame);
+                ctx.panic_info.kcl_func = runtime_type;
+            }
+            now_meta_info
+        };
+        let proxy = proxy.expect(kcl_error::INTERNAL_ERROR_MSG);
+        let cal_map = ValueRef::dict(None);
+        let schema_ctx = Rc::new(RefCell::new(SchemaEvalContext::new(
+            config,
+            config_meta,
+            &cal_map,
+            args,
+            kwargs,
+            instance_pkgpath,
+        )));
+        self.invoke_schema_body(proxy, &schema_ctx);
+        let mut sub_schema_ctx = SchemaEvalContext::new(
+            config,
+            config_meta,
+            &cal_map,
+            &args.deep_copy(),
+            &kwargs.deep_copy(),
+            instance_pkgpath,
+        );
+        sub_schema_ctx.is_sub_schema = true;
+        sub_schema_ctx.record_instance = true;
+        let value = self.invoke_schema_body(proxy, &Rc::new(RefCell::new(sub_schema_ctx)));
+        let mut ctx = self.runtime_ctx.borrow_mut();
+        ctx.panic_info = now_meta_info;
+        if ctx.cfg.debug_mode {
+            ctx.backtrace.pop();
+        }
+        ctx.profiler.end();
+        ctx.leave_call();
+        value
+    }
+
+    /// Construct a schema value with the schema expression config, or union the
+    /// schema value with the config when `schema_value_or_func` is not a function.
+    pub(crate) fn schema_value_new(
+        &self,
+        schema_value_or_func: &ValueRef,
+        args: &ValueRef,
+        kwargs: &ValueRef,
+        config: &ValueRef,
+        config_meta: &ValueRef,
+        pkgpath: &str,
+    ) -> ValueRef {
+        if schema_value_or_func.is_func() {
+            self.construct_schema_value(
+                schema_value_or_func,
+                args,
+                kwargs,
+                config,
+                config_meta,
+                pkgpath,
+            )
+        } else {
+            self.union_entry(
+                &mut schema_value_or_func.deep_copy(),
+                config,
+                true,
+                false,
+                true,
+                true,
+            )
+        }
+    }
+
+    /// Call the schema or rule body function of the proxy frame `index`.
+    pub(crate) fn invoke_schema_body(&self, index: u64, s: &SchemaEvalContextRef) -> ValueRef {
+        let frame = self.get_frame(index);
+        let (args, kwargs) = {
+            let s = s.borrow();
+            (s.args.clone(), s.kwargs.clone())
+        };
+        match &frame.proxy {
+            Proxy::Schema(caller) => self.with_frame(&frame, false, || {
+                self.schema_body(caller, s, &args, &kwargs)
+            }),
+            Proxy::Rule(caller) => {
+                self.with_frame(&frame, false, || self.rule_body(caller, s, &args, &kwargs))
+            }
+            _ => panic!("{} is not a schema or rule", index),
+        }
+    }
+
+    /// Call the schema or rule check function of the proxy frame `index`.
+    pub(crate) fn invoke_schema_check(&self, index: u64, s: &SchemaEvalContextRef) {
+        let frame = self.get_frame(index);
+        let (args, kwargs) = {
+            let s = s.borrow();
+            (s.args.clone(), s.kwargs.clone())
+        };
+        match &frame.proxy {
+            Proxy::Schema(caller) => self.with_frame(&frame, false, || {
+                self.schema_check(caller, s, &args, &kwargs)
+            }),
+            Proxy::Rule(caller) => {
+                self.with_frame(&frame, false, || self.rule_check(caller, s, &args, &kwargs))
+            }
+            _ => panic!("{} is not a schema or rule", index),
+        }
+    }
+
+    /// Get the proxy frame index of the parent, mixin or host schema identifier.
+    pub(crate) fn base_schema_index(&self, name: &'ctx ast::Node<ast::Identifier>) -> u64 {
+        let func = self
+            .walk_identifier_with_ctx(&name.node, &ast::ExprContext::Load, None)
+            .expect(kcl_error::RUNTIME_ERROR_MSG);
+        if !func.is_func() {
+            panic!("'{}' is not a schema", name.node.get_name());
+        }
+        let proxy = func.as_function().proxy;
+        proxy.unwrap_or_else(|| panic!("'{}' is not a schema", name.node.get_name()))
+    }
+
+    /// The schema body function.
+    pub(crate) fn schema_body(
+        &self,
+        caller: &SchemaCaller<'ctx>,
+        s: &SchemaEvalContextRef,
+        args: &ValueRef,
+        kwargs: &ValueRef,
+    ) -> ValueRef {
+        let schema_stmt = caller.node;
+        let runtime_type = &caller.runtime_type;
+        let filename = self.current_filename();
+        s.borrow_mut().runtime_type = runtime_type.to_string();
+        self.runtime_ctx.borrow_mut().set_kcl_filename(&filename);
+        self.update_ctx_pkgpath(&self.current_pkgpath());
+        self.enter_scope();
+        self.walk_arguments(&schema_stmt.args, args, kwargs);
+        // Schema parent function
+        if let Some(parent_name) = &schema_stmt.parent_name {
+            let index = self.base_schema_index(parent_name);
+            let base_ctx = Rc::new(RefCell::new(s.borrow().to_base()));
+            let value = self.invoke_schema_body(index, &base_ctx);
+            s.borrow_mut().value = value;
+            self.runtime_ctx.borrow_mut().set_kcl_filename(&filename);
+        }
+        self.schema_stack.borrow_mut().push(s.clone());
+        self.emit_schema_left_identifiers(schema_stmt, runtime_type, s);
+        let record_instance = s.borrow().record_instance;
+        if record_instance {
+            // Run schema body statements
+            for stmt in &schema_stmt.body {
+                self.walk_stmt(stmt).expect(kcl_error::RUNTIME_ERROR_MSG);
+            }
+            // Schema decorators check
+            for decorator in &schema_stmt.decorators {
+                self.walk_decorator_with_name(&decorator.node, Some(&schema_stmt.name.node), true)
+                    .expect(kcl_error::RUNTIME_ERROR_MSG);
+            }
+            // Append schema default settings
+            let (mut value, config) = {
+                let s = s.borrow();
+                (s.value.clone(), s.config.clone())
+            };
+            value.schema_default_settings(&config, runtime_type);
+        }
+        // Schema mixin
+        for mixin in &schema_stmt.mixins {
+            let index = self.base_schema_index(mixin);
+            let base_ctx = Rc::new(RefCell::new(s.borrow().to_base()));
+            self.invoke_schema_body(index, &base_ctx);
+            self.runtime_ctx.borrow_mut().set_kcl_filename(&filename);
+        }
+        // Schema Attribute optional check
+        {
+            let mut optional_mapping = s.borrow().optional_mapping.clone();
+            for stmt in &schema_stmt.body {
+                if let ast::Stmt::SchemaAttr(schema_attr) = &stmt.node {
+                    optional_mapping.dict_update_key_value(
+                        schema_attr.name.node.as_str(),
+                        ValueRef::bool(schema_attr.is_optional),
+                    );
+                }
+            }
+        }
+        let is_sub_schema = s.borrow().is_sub_schema;
+        if is_sub_schema {
+            // Schema runtime index signature and relaxed check
+            self.schema_value_check(schema_stmt, s);
+            let index_sign_key_name = match &schema_stmt.index_signature {
+                Some(index_signature) => index_signature.node.key_name.clone().unwrap_or_default(),
+                None => "".to_string(),
+            };
+            if index_sign_key_name.is_empty() {
+                self.schema_check(caller, s, args, kwargs);
+            } else {
+                self.schema_check_with_index_sign_attr(
+                    caller,
+                    s,
+                    args,
+                    kwargs,
+                    &index_sign_key_name,
+                );
+            }
+        }
+        let value = self.schema_with_config(s, &schema_stmt.name.node, &self.current_pkgpath());
+        self.schema_stack.borrow_mut().pop();
+        self.leave_scope();
+        value
+    }
+
+    /// The schema check function which evaluates all check expressions of the
+    /// schema, its parent schema and mixins.
+    pub(crate) fn schema_check(
+        &self,
+        caller: &SchemaCaller<'ctx>,
+        s: &SchemaEvalContextRef,
+        args: &ValueRef,
+        kwargs: &ValueRef,
+    ) {
+        let schema_stmt = caller.node;
+        self.enter_scope();
+        let schema_stack = std::mem::take(&mut *self.schema_stack.borrow_mut());
+        self.walk_arguments(&schema_stmt.args, args, kwargs);
+        self.schema_stack.borrow_mut().push(s.clone());
+        // Schema parent check
+        if let Some(parent_name) = &schema_stmt.parent_name {
+            let index = self.base_schema_index(parent_name);
+            let base_ctx = Rc::new(RefCell::new(s.borrow().to_base()));
+            self.invoke_schema_check(index, &base_ctx);
+        }
+        // Schema check expressions
+        {
+            let _span = ProfileSpan::begin(
+                &mut self.runtime_ctx.borrow_mut(),
+                PROFILE_KIND_CHECK,
+                || self.schema_runtime_type(&schema_stmt.name.node),
+            );
+            for check_expr in &schema_stmt.checks {
+                self.walk_check_expr(&check_expr.node)
+                    .expect(kcl_error::RUNTIME_ERROR_MSG);
+            }
+        }
+        // Schema mixin check
+        for mixin in &schema_stmt.mixins {
+            let index = self.base_schema_index(mixin);
+            let base_ctx = Rc::new(RefCell::new(s.borrow().to_base()));
+            self.invoke_schema_check(index, &base_ctx);
+        }
+        *self.schema_stack.borrow_mut() = schema_stack;
+        self.leave_scope();
+    }
+
+    /// Call the schema check function with the index signature attribute name
+    /// loop set for each relaxed key in the config.
+    fn schema_check_with_index_sign_attr(
+        &self,
+        caller: &SchemaCaller<'ctx>,
+        s: &SchemaEvalContextRef,
+        args: &ValueRef,
+        kwargs: &ValueRef,
+        attr_name: &str,
+    ) {
+        let (mut schema, config) = {
+            let s = s.borrow();
+            (s.value.clone(), s.config.clone())
+        };
+        let keys: Vec<String> = config.as_dict_ref().values.keys().cloned().collect();
+        for k in &keys {
+            // relaxed keys
+            if schema.attr_map_get(k).is_none() {
+                schema.dict_update_key_value(attr_name, ValueRef::str(k));
+                self.schema_check(caller, s, args, kwargs);
+            }
+        }
+        schema.dict_remove(attr_name);
+    }
+
+    /// Schema runtime index signature and relaxed check.
+    fn schema_value_check(&self, schema_stmt: &'ctx ast::SchemaStmt, s: &SchemaEvalContextRef) {
+        if self.runtime_ctx.borrow().cfg.disable_schema_check {
+            return;
+        }
+        let has_index_signature = schema_stmt.index_signature.is_some();
+        let index_sign_value = match &schema_stmt.index_signature {
+            Some(index_signature) => match &index_signature.node.value {
+                Some(value) => self.walk_expr(value).expect(kcl_error::RUNTIME_ERROR_MSG),
+                None => ValueRef::none(),
+            },
+            None => ValueRef::none(),
+        };
+        let (mut schema_value, config) = {
+            let s = s.borrow();
+            (s.value.clone(), s.config.clone())
+        };
+        let items: Vec<(String, ValueRef, ConfigEntryOperationKind)> = {
+            let config = config.as_dict_ref();
+            config
+                .values
+                .iter()
+                .map(|(k, v)| {
+                    let op = config
+                        .ops
+                        .get(k)
+                        .cloned()
+                        .unwrap_or(ConfigEntryOperationKind::Union);
+                    (k.clone(), v.clone(), op)
+                })
+                .collect()
+        };
+        for (key, value, op) in &items {
+            let is_not_in_schema = schema_value.dict_get_value(key).is_none();
+            if has_index_signature && is_not_in_schema {
+                let value = self.union_entry(
+                    &mut index_sign_value.deep_copy(),
+                    value,
+                    true,
+                    false,
+                    false,
+                    true,
+                );
+                schema_value.dict_update_entry(key.as_str(), &value, op, &-1);
+            } else if !has_index_signature && is_not_in_schema {
+                let schema_name = &schema_stmt.name.node;
+                panic!("{key}: No such member in the schema '{schema_name}'");
+            }
+        }
+    }
+
+    /// Convert the schema dict value to a schema value and record the instance.
+    pub(crate) fn schema_with_config(
+        &self,
+        s: &SchemaEvalContextRef,
+        name: &str,
+        pkgpath: &str,
+    ) -> ValueRef {
+        let s = s.borrow();
+        let config_keys: Vec<String> = s.config.as_dict_ref().values.keys().cloned().collect();
+        let runtime_type = schema_runtime_type(name, pkgpath);
+        let schema = s.value.dict_to_schema(
+            name,
+            pkgpath,
+            &config_keys,
+            &s.config_meta,
+            &s.optional_mapping,
+        );
+        if s.record_instance
+            && (s.instance_pkgpath.is_empty() || s.instance_pkgpath == MAIN_PKG_PATH)
+        {
+            // Record schema instance in the context
+            let ctx = self.runtime_ctx.borrow();
+            let mut instance_map = ctx.instances.borrow_mut();
+            instance_map
+                .entry(runtime_type)
+                .or_default()
+                .push(s.value.clone());
+        }
+        if s.is_sub_schema {
+            schema
+        } else {
+            s.value.clone()
+        }
+    }
+
+    /// Record all the schema attributes defined in the schema body into the schema
+    /// value and the attribute functions into the cal order map.
+    pub(crate) fn emit_schema_left_identifiers(
+        &self,
+        schema_stmt: &'ctx ast::SchemaStmt,
+        runtime_type: &str,
+        s: &SchemaEvalContextRef,
+    ) {
+        let mut if_levels = std::collections::HashMap::new();
+        self.emit_left_identifiers(
+            schema_stmt,
+            &schema_stmt.body,
+            runtime_type,
+            s,
+            false,
+            &mut if_levels,
+            &mut vec![],
+        );
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn emit_left_identifiers(
+        &self,
+        schema_stmt: &'ctx ast::SchemaStmt,
+        body: &'ctx [Box<ast::Node<ast::Stmt>>],
+        runtime_type: &str,
+        s: &SchemaEvalContextRef,
+        is_in_if: bool,
+        if_levels: &mut std::collections::HashMap<String, usize>,
+        in_if_names: &mut Vec<String>,
+    ) {
+        let mut schema_value = s.borrow().value.clone();
+        let value = ValueRef::undefined();
+        let add_name = |name: &str,
+                        stmt: &'ctx ast::Node<ast::Stmt>,
+                        if_levels: &mut std::collections::HashMap<String, usize>,
+                        in_if_names: &mut Vec<String>| {
+            if is_in_if {
+                in_if_names.push(name.to_string());
+            } else {
+                self.add_schema_attr_stmt(schema_stmt, name, stmt, runtime_type, s, if_levels);
+            }
+        };
+        for stmt in body {
+            match &stmt.node {
+                ast::Stmt::Unification(unification_stmt) => {
+                    let name = &unification_stmt.target.node.names[0];
+                    self.dict_merge(
+                        &mut schema_value,
+                        name,
+                        &value,
+                        &ConfigEntryOperationKind::Union,
+                        -1,
+                    );
+                    add_name(name, stmt, if_levels, in_if_names);
+                }
+                ast::Stmt::Assign(assign_stmt) => {
+                    for target in &assign_stmt.targets {
+                        let name = &target.node.names[0];
+                        self.dict_merge(
+                            &mut schema_value,
+                            name,
+                            &value,
+                            &ConfigEntryOperationKind::Union,
+                            -1,
+                        );
+                        add_name(name, stmt, if_levels, in_if_names);
+                    }
+                }
+                ast::Stmt::AugAssign(aug_assign_stmt) => {
+                    let name = &aug_assign_stmt.target.node.names[0];
+                    self.dict_merge(
+                        &mut schema_value,
+                        name,
+                        &value,
+                        &ConfigEntryOperationKind::Union,
+                        -1,
+                    );
+                    add_name(name, stmt, if_levels, in_if_names);
+                }
+                ast::Stmt::If(if_stmt) => {
+                    for body in [&if_stmt.body, &if_stmt.orelse] {
+                        let mut names: Vec<String> = vec![];
+                        self.emit_left_identifiers(
+                            schema_stmt,
+                            body,
+                            runtime_type,
+                            s,
+                            true,
+                            if_levels,
+                            &mut names,
+                        );
+                        for name in &names {
+                            add_name(name, stmt, if_levels, in_if_names);
+                        }
+                    }
+                }
+                ast::Stmt::SchemaAttr(schema_attr) => {
+                    let name = schema_attr.name.node.as_str();
+                    self.dict_merge(
+                        &mut schema_value,
+                        name,
+                        &value,
+                        &ConfigEntryOperationKind::Union,
+                        -1,
+                    );
+                    add_name(name, stmt, if_levels, in_if_names);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Add the schema attribute function of the statement `stmt` into the cal order map.
+    fn add_schema_attr_stmt(
+        &self,
+        schema_stmt: &'ctx ast::SchemaStmt,
+        name: &str,
+        stmt: &'ctx ast::Node<ast::Stmt>,
+        runtime_type: &str,
+        s: &SchemaEvalContextRef,
+        if_levels: &mut std::collections::HashMap<String, usize>,
+    ) {
+        let if_level = if_levels.entry(name.to_string()).or_insert(0);
+        if matches!(&stmt.node, ast::Stmt::If(..)) {
+            *if_level += 1;
+        } else {
+            *if_level = 0;
+        }
+        let if_level = *if_level;
+        // An if statement which sets the attribute several times has a frame for each
+        // if level, because the backtracking stops at the setter of the level.
+        let key = (
+            stmt as *const ast::Node<ast::Stmt> as usize,
+            name.to_string(),
+            if_level,
+        );
+        let existed = self.attr_frames.borrow().get(&key).cloned();
+        let index = match existed {
+            Some(index) => index,
+            None => {
+                let index = self.add_frame(Proxy::SchemaAttr(SchemaAttrCaller {
+                    schema: schema_stmt,
+                    stmt,
+                    name: name.to_string(),
+                    runtime_type: runtime_type.to_string(),
+                    if_level,
+                }));
+                self.attr_frames.borrow_mut().insert(key, index);
+                index
+            }
+        };
+        let mut cal_map = s.borrow().cal_map.clone();
+        default_collection_insert_value(&mut cal_map, name, &ValueRef::int(index as i64));
+        default_collection_insert_value(
+            &mut cal_map,
+            &format!("{}_{}", name, CAL_MAP_RUNTIME_TYPE),
+            &ValueRef::str(runtime_type),
+        );
+        default_collection_insert_value(
+            &mut cal_map,
+            &format!("{}_{}", name, CAL_MAP_META_LINE),
+            &ValueRef::int(stmt.line as i64),
+        );
+    }
+
+    /// Call the schema attribute function of the proxy frame `index`.
+    fn invoke_schema_attr(&self, index: u64, s: &SchemaEvalContextRef) {
+        let frame = self.get_frame(index);
+        if let Proxy::SchemaAttr(caller) = &frame.proxy {
+            // The attribute function may be defined in the parent schema, so the context
+            // which shares all the schema values is evaluated with the attribute runtime type.
+            let s = &Rc::new(RefCell::new(SchemaEvalContext {
+                runtime_type: caller.runtime_type.clone(),
+                ..s.borrow().clone()
+            }));
+            self.with_frame(&frame, false, || {
+                self.enter_scope();
+                self.runtime_ctx
+                    .borrow_mut()
+                    .set_kcl_filename(&frame.filename);
+                let (args, kwargs) = {
+                    let s = s.borrow();
+                    (s.args.clone(), s.kwargs.clone())
+                };
+                self.walk_arguments(&caller.schema.args, &args, &kwargs);
+                self.schema_stack.borrow_mut().push(s.clone());
+                // Backtrack meta begin
+                if caller.if_level > 0 {
+                    *self.backtrack_meta.borrow_mut() = Some(BacktrackMeta {
+                        target: caller.name.clone(),
+                        level: caller.if_level,
+                        count: 0,
+                        stop: false,
+                    });
+                }
+                self.walk_stmt(caller.stmt)
+                    .expect(kcl_error::RUNTIME_ERROR_MSG);
+                // Backtrack meta end
+                *self.backtrack_meta.borrow_mut() = None;
+                self.schema_stack.borrow_mut().pop();
+                self.leave_scope();
+            })
+        } else {
+            panic!("{} is not a schema attribute function", index)
+        }
+    }
+
+    /// Get the schema attribute value named `key`, and calculate it using the attribute
+    /// functions in the cal order map when it is not in the backtrack cache.
+    pub(crate) fn schema_get_value(
+        &self,
+        s: &SchemaEvalContextRef,
+        key: &str,
+        target_attr: &str,
+    ) -> ValueRef {
+        let (schema, cal_map, mut backtrack_level_map, mut backtrack_cache) = {
+            let s = s.borrow();
+            (
+                s.value.clone(),
+                s.cal_map.clone(),
+                s.backtrack_level_map.clone(),
+                s.backtrack_cache.clone(),
+            )
+        };
+        let level = backtrack_level_map
+            .dict_get_value(key)
+            .map(|v| v.as_int())
+            .unwrap_or(0);
+        let is_backtracking = level > 0;
+        // Deal in-place modify and return it self immediately
+        if key == target_attr && !is_backtracking {
+            return schema
+                .dict_get_value(key)
+                .unwrap_or_else(ValueRef::undefined);
+        }
+        if let Some(v) = backtrack_cache.dict_get_value(key) {
+            return v;
+        }
+        if let Some(attr_code) = cal_map.dict_get_value(key) {
+            let now_level = level + 1;
+            backtrack_level_map.dict_update_key_value(key, ValueRef::int(now_level));
+            let n = attr_code.len();
+            if now_level as usize > n {
+                return schema
+                    .dict_get_value(key)
+                    .unwrap_or_else(ValueRef::undefined);
+            }
+            let index = attr_code
+                .list_get((n - now_level as usize) as isize)
+                .expect(kcl_error::INTERNAL_ERROR_MSG)
+                .as_int();
+            // When we calculate other schema attribute values, we retain
+            // the row and column number information of the current schema attribute.
+            let panic_info = self.runtime_ctx.borrow().panic_info.clone();
+            self.invoke_schema_attr(index as u64, s);
+            self.runtime_ctx.borrow_mut().panic_info = panic_info;
+            backtrack_level_map.dict_update_key_value(key, ValueRef::int(level));
+            let value = schema
+                .dict_get_value(key)
+                .unwrap_or_else(ValueRef::undefined);
+            backtrack_cache.dict_update_key_value(key, value.clone());
+            return value;
+        }
+        schema
+            .dict_get_value(key)
+            .unwrap_or_else(ValueRef::undefined)
+    }
+
+    /// Cache the schema attribute value named `name` when it is calculated
+    /// by the last attribute statement.
+    pub(crate) fn schema_backtrack_cache(&self, s: &SchemaEvalContextRef, name: &str) {
+        let (schema, mut cache, cal_map, runtime_type) = {
+            let s = s.borrow();
+            (
+                s.value.clone(),
+                s.backtrack_cache.clone(),
+                s.cal_map.clone(),
+                s.runtime_type.clone(),
+            )
+        };
+        if let Some(v) = cal_map.dict_get_value(name) {
+            if v.len() == 1 {
+                if let Some(value) = schema.dict_get_value(name) {
+                    cache.dict_update_key_value(name, value);
+                }
+            } else if let (Some(cal_map_runtime_type_list), Some(cal_map_meta_line_list)) = (
+                cal_map.dict_get_value(&format!("{name}_{CAL_MAP_RUNTIME_TYPE}")),
+                cal_map.dict_get_value(&format!("{name}_{CAL_MAP_META_LINE}")),
+            ) {
+                if let (Some(cal_map_runtime_type), Some(cal_map_meta_line)) = (
+                    cal_map_runtime_type_list.list_get(-1),
+                    cal_map_meta_line_list.list_get(-1),
+                ) {
+                    let line = self.runtime_ctx.borrow().panic_info.kcl_line as i64;
+                    let cal_map_meta_line = cal_map_meta_line.as_int();
+                    if runtime_type == cal_map_runtime_type.as_str() && line >= cal_map_meta_line {
+                        if let Some(value) = schema.dict_get_value(name) {
+                            cache.dict_update_key_value(name, value);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Get the schema config meta including the filename, line and column
+    /// information of the schema expression and its config items.
+    pub(crate) fn get_schema_config_meta(
+        &self,
+        n: Option<&'ctx ast::Node<ast::Identifier>>,
+        t: &'ctx ast::ConfigExpr,
+    ) -> ValueRef {
+        let mut config_meta = match n {
+            Some(n) => schema_config_meta(&n.filename, n.line, n.column),
+            None => ValueRef::dict(None),
+        };
+        for item in &t.items {
+            if let Some(key) = &item.node.key {
+                let name = match &key.node {
+                    ast::Expr::Identifier(t) => t.names[0].clone(),
+                    ast::Expr::NumberLit(t) => match t.value {
+                        ast::NumberLitValue::Int(i) => i.to_string(),
+                        ast::NumberLitValue::Float(f) => f.to_string(),
+                    },
+                    ast::Expr::StringLit(t) => t.value.clone(),
+                    ast::Expr::NameConstantLit(t) => match t.value {
+                        ast::NameConstant::True => {
+                            kclvm_runtime::KCL_NAME_CONSTANT_TRUE.to_string()
+                        }
+                        ast::NameConstant::False => {
+                            kclvm_runtime::KCL_NAME_CONSTANT_FALSE.to_string()
+                        }
+                        ast::NameConstant::None => {
+                            kclvm_runtime::KCL_NAME_CONSTANT_NONE.to_string()
+                        }
+                        ast::NameConstant::Undefined => {
+                            kclvm_runtime::KCL_NAME_CONSTANT_UNDEFINED.to_string()
+                        }
+                    },
+                    _ => format!("{:?}", key.node),
+                };
+                let mut config_item_meta = ValueRef::dict(None);
+                config_item_meta.dict_update_key_value(
+                    kclvm_runtime::CONFIG_ITEM_META_FILENAME,
+                    ValueRef::str(&key.filename),
+                );
+                config_item_meta.dict_update_key_value(
+                    kclvm_runtime::CONFIG_ITEM_META_LINE,
+                    ValueRef::int(key.line as i64),
+                );
+                config_item_meta.dict_update_key_value(
+                    kclvm_runtime::CONFIG_ITEM_META_COLUMN,
+                    ValueRef::int(key.column as i64),
+                );
+                let value = match &item.node.value.node {
+                    ast::Expr::Config(config_expr) => {
+                        self.get_schema_config_meta(None, config_expr)
+                    }
+                    _ => ValueRef::dict(None),
+                };
+                config_item_meta.dict_update_key_value(kclvm_runtime::CONFIG_ITEM_META, value);
+                config_meta.dict_update_key_value(&name, config_item_meta)
+            }
+        }
+        config_meta
+    }
+}
+
+/// Append the `value` into the list `dict[key]` when it is not in the list.
+fn default_collection_insert_value(dict: &mut ValueRef, key: &str, value: &ValueRef) {
+    match dict.dict_get_value(key) {
+        Some(mut list) => {
+            if !value.r#in(&list) {
+                list.list_append(value);
+            }
+        }
+        None => {
+            let list = ValueRef::list(Some(&[value]));
+            dict.dict_update_key_value(key, list);
+        }
+    }
+}
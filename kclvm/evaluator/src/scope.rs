@@ -0,0 +1,354 @@
+// Copyright 2021 The KCL Authors. All rights reserved.
+
+use indexmap::{IndexMap, IndexSet};
+use kclvm_ast::ast;
+use kclvm_runtime::{
+    _kclvm_get_fn_ptr_by_name, schema_runtime_type, ValueRef, MAIN_PKG_PATH, PKG_PATH_PREFIX,
+};
+use kclvm_sema::{builtin, plugin};
+
+use crate::error as kcl_error;
+use crate::{EvalResult, Evaluator, GLOBAL_LEVEL};
+
+/// The evaluator scope.
+#[derive(Debug, Default)]
+pub struct Scope {
+    /// Scalars denotes the expression statement values without attribute.
+    pub scalars: Vec<ValueRef>,
+    /// schema_scalar_idx denotes whether a schema exists in the scalar list.
+    pub schema_scalar_idx: usize,
+    /// Scope normal variables
+    pub variables: IndexMap<String, ValueRef>,
+    /// Potential arguments in the current scope, such as schema/lambda arguments.
+    pub arguments: IndexSet<String>,
+}
+
+impl<'ctx> Evaluator<'ctx> {
+    /// Init a scope named `pkgpath` with all builtin functions
+    pub(crate) fn init_scope(&self, pkgpath: &str) {
+        {
+            let mut pkg_scopes = self.pkg_scopes.borrow_mut();
+            if pkg_scopes.contains_key(pkgpath) {
+                return;
+            }
+            let scopes = vec![Scope::default()];
+            pkg_scopes.insert(String::from(pkgpath), scopes);
+        }
+        let msg = format!("pkgpath {} is not found", pkgpath);
+        // Init all global types including schema and rule
+        let module_list: &Vec<ast::Module> = if self.program.pkgs.contains_key(pkgpath) {
+            self.program.pkgs.get(pkgpath).expect(&msg)
+        } else if pkgpath.starts_with(PKG_PATH_PREFIX)
+            && self.program.pkgs.contains_key(&pkgpath[1..])
+        {
+            self.program
+                .pkgs
+                .get(&pkgpath[1..])
+                .expect(kcl_error::INTERNAL_ERROR_MSG)
+        } else {
+            panic!("pkgpath {} not found", pkgpath);
+        };
+        for module in module_list {
+            for stmt in &module.body {
+                let name = match &stmt.node {
+                    ast::Stmt::Schema(schema_stmt) => schema_stmt.name.node.clone(),
+                    ast::Stmt::Rule(rule_stmt) => rule_stmt.name.node.clone(),
+                    _ => "".to_string(),
+                };
+                if !name.is_empty() {
+                    self.add_variable(&name, ValueRef::undefined());
+                }
+            }
+        }
+        // Init all builtin functions
+        for symbol in builtin::BUILTIN_FUNCTION_NAMES {
+            let function_name =
+                format!("{}_{}", builtin::KCL_BUILTIN_FUNCTION_MANGLE_PREFIX, symbol);
+            let function_ptr = _kclvm_get_fn_ptr_by_name(&function_name);
+            self.add_variable(
+                symbol,
+                ValueRef::func(function_ptr, 0, ValueRef::none(), &function_name, "", false),
+            );
+        }
+        self.enter_scope();
+    }
+
+    /// Get the scope level
+    pub(crate) fn scope_level(&self) -> usize {
+        let current_pkgpath = self.current_pkgpath();
+        let pkg_scopes = self.pkg_scopes.borrow();
+        let msg = format!("pkgpath {} is not found", current_pkgpath);
+        let scopes = pkg_scopes.get(&current_pkgpath).expect(&msg);
+        // Sub the builtin global scope
+        scopes.len() - 1
+    }
+
+    /// Enter scope
+    pub(crate) fn enter_scope(&self) {
+        let current_pkgpath = self.current_pkgpath();
+        let mut pkg_scopes = self.pkg_scopes.borrow_mut();
+        let msg = format!("pkgpath {} is not found", current_pkgpath);
+        let scopes = pkg_scopes.get_mut(&current_pkgpath).expect(&msg);
+        scopes.push(Scope::default());
+    }
+
+    /// Leave scope
+    pub(crate) fn leave_scope(&self) {
+        let current_pkgpath = self.current_pkgpath();
+        let mut pkg_scopes = self.pkg_scopes.borrow_mut();
+        let msg = format!("pkgpath {} is not found", current_pkgpath);
+        let scopes = pkg_scopes.get_mut(&current_pkgpath).expect(&msg);
+        scopes.pop();
+    }
+
+    /// Take all the local scopes of the package `pkgpath` out, which are restored
+    /// with `restore_local_scopes` after a function call.
+    pub(crate) fn take_local_scopes(&self, pkgpath: &str) -> Vec<Scope> {
+        let mut pkg_scopes = self.pkg_scopes.borrow_mut();
+        let msg = format!("pkgpath {} is not found", pkgpath);
+        let scopes = pkg_scopes.get_mut(pkgpath).expect(&msg);
+        if scopes.len() > GLOBAL_LEVEL + 1 {
+            scopes.split_off(GLOBAL_LEVEL + 1)
+        } else {
+            vec![]
+        }
+    }
+
+    /// Restore the local scopes of the package `pkgpath`.
+    pub(crate) fn restore_local_scopes(&self, pkgpath: &str, local_scopes: Vec<Scope>) {
+        let mut pkg_scopes = self.pkg_scopes.borrow_mut();
+        let msg = format!("pkgpath {} is not found", pkgpath);
+        let scopes = pkg_scopes.get_mut(pkgpath).expect(&msg);
+        scopes.truncate(GLOBAL_LEVEL + 1);
+        scopes.extend(local_scopes);
+    }
+
+    /// Get all the variables defined in the local scopes of the current package.
+    pub(crate) fn get_local_variables(&self) -> IndexMap<String, ValueRef> {
+        let current_pkgpath = self.current_pkgpath();
+        let pkg_scopes = self.pkg_scopes.borrow();
+        let msg = format!("pkgpath {} is not found", current_pkgpath);
+        let scopes = pkg_scopes.get(&current_pkgpath).expect(&msg);
+        let mut variables = IndexMap::new();
+        // Scopes 0 is builtin scope, Scopes 1 is the global scope, Scopes 2~ are the local scopes
+        for scope in scopes.iter().skip(GLOBAL_LEVEL + 1) {
+            for (name, value) in &scope.variables {
+                variables.insert(name.to_string(), value.clone());
+            }
+        }
+        variables
+    }
+
+    /// Append a scalar value into the scope.
+    pub(crate) fn add_scalar(&self, scalar: ValueRef, is_schema: bool) {
+        let current_pkgpath = self.current_pkgpath();
+        let mut pkg_scopes = self.pkg_scopes.borrow_mut();
+        let scopes = pkg_scopes
+            .get_mut(&current_pkgpath)
+            .unwrap_or_else(|| panic!("pkgpath {} is not found", current_pkgpath));
+        if let Some(last) = scopes.last_mut() {
+            // TODO: To avoid conflicts, only the last schema scalar expressions are allowed.
+            if is_schema {
+                // Remove the last schema scalar.
+                if last.schema_scalar_idx < last.scalars.len() {
+                    let idx = last.schema_scalar_idx;
+                    last.scalars.remove(idx);
+                }
+                // Override the last schema scalar.
+                last.scalars.push(scalar);
+                last.schema_scalar_idx = last.scalars.len() - 1;
+            } else {
+                last.scalars.push(scalar);
+            }
+        }
+    }
+
+    /// Append a variable into the scope
+    pub(crate) fn add_variable(&self, name: &str, value: ValueRef) {
+        let current_pkgpath = self.current_pkgpath();
+        let mut pkg_scopes = self.pkg_scopes.borrow_mut();
+        let msg = format!("pkgpath {} is not found", current_pkgpath);
+        let scopes = pkg_scopes.get_mut(&current_pkgpath).expect(&msg);
+        if let Some(last) = scopes.last_mut() {
+            last.variables.insert(name.to_string(), value);
+        }
+    }
+
+    /// Store the argument named `name` in the current scope.
+    pub(crate) fn store_argument_in_current_scope(&self, name: &str) {
+        let current_pkgpath = self.current_pkgpath();
+        let mut pkg_scopes = self.pkg_scopes.borrow_mut();
+        let msg = format!("pkgpath {} is not found", current_pkgpath);
+        let scopes = pkg_scopes.get_mut(&current_pkgpath).expect(&msg);
+        if let Some(last) = scopes.last_mut() {
+            last.arguments.insert(name.to_string());
+        }
+    }
+
+    /// Store the variable named `name` with `value` from the current scope, return false when not found
+    pub(crate) fn store_variable_in_current_scope(&self, name: &str, value: ValueRef) -> bool {
+        let current_pkgpath = self.current_pkgpath();
+        let mut pkg_scopes = self.pkg_scopes.borrow_mut();
+        let msg = format!("pkgpath {} is not found", current_pkgpath);
+        let scopes = pkg_scopes.get_mut(&current_pkgpath).expect(&msg);
+        if let Some(last) = scopes.last_mut() {
+            if let Some(var) = last.variables.get_mut(name) {
+                *var = value;
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Store the variable named `name` with `value` from the scope, return false when not found
+    pub(crate) fn store_variable(&self, name: &str, value: ValueRef) -> bool {
+        let current_pkgpath = self.current_pkgpath();
+        let mut pkg_scopes = self.pkg_scopes.borrow_mut();
+        let msg = format!("pkgpath {} is not found", current_pkgpath);
+        let scopes = pkg_scopes.get_mut(&current_pkgpath).expect(&msg);
+        for scope in scopes.iter_mut().rev() {
+            if let Some(var) = scope.variables.get_mut(name) {
+                *var = value;
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Resolve variable in scope, return false when not found
+    pub(crate) fn resolve_variable(&self, name: &str) -> bool {
+        let current_pkgpath = self.current_pkgpath();
+        let pkg_scopes = self.pkg_scopes.borrow();
+        let msg = format!("pkgpath {} is not found", current_pkgpath);
+        let scopes = pkg_scopes.get(&current_pkgpath).expect(&msg);
+        scopes
+            .iter()
+            .rev()
+            .any(|scope| scope.variables.contains_key(name))
+    }
+
+    /// Append a variable or update the existed local variable.
+    pub(crate) fn add_or_update_local_variable(&self, name: &str, value: ValueRef) {
+        let current_pkgpath = self.current_pkgpath();
+        let mut pkg_scopes = self.pkg_scopes.borrow_mut();
+        let msg = format!("pkgpath {} is not found", current_pkgpath);
+        let scopes = pkg_scopes.get_mut(&current_pkgpath).expect(&msg);
+        let is_local_var = self.local_vars.borrow().contains(name);
+        let mut existed = false;
+        // Query the variable in all scopes.
+        for (index, scope) in scopes.iter_mut().enumerate().rev() {
+            // If the local varibale is found, store the new value for the variable.
+            // We cannot update rule/lambda/schema arguments because they are read-only.
+            if index > GLOBAL_LEVEL && !is_local_var && !scope.arguments.contains(name) {
+                if let Some(var) = scope.variables.get_mut(name) {
+                    *var = value.clone();
+                    existed = true;
+                }
+            }
+        }
+        // If not found, add a new varibale into the current scope.
+        if !existed {
+            if let Some(last) = scopes.last_mut() {
+                last.variables.insert(name.to_string(), value);
+            }
+        }
+    }
+
+    /// Append a variable or update the existed variable
+    pub(crate) fn add_or_update_global_variable(&self, name: &str, value: ValueRef) {
+        let current_pkgpath = self.current_pkgpath();
+        let mut pkg_scopes = self.pkg_scopes.borrow_mut();
+        let msg = format!("pkgpath {} is not found", current_pkgpath);
+        let scopes = pkg_scopes.get_mut(&current_pkgpath).expect(&msg);
+        if let Some(last) = scopes.last_mut() {
+            last.variables.insert(name.to_string(), value);
+        }
+    }
+
+    /// Get the variable value named `name` from the scope, return Err when not found
+    pub(crate) fn get_variable(&self, name: &str) -> EvalResult {
+        let current_pkgpath = self.current_pkgpath();
+        self.get_variable_in_pkgpath(name, &current_pkgpath)
+    }
+
+    /// Get the variable value named `name` from the schema attributes firstly and
+    /// then the scope.
+    pub(crate) fn get_variable_in_schema(&self, name: &str) -> EvalResult {
+        let schema_ctx = self.schema_ctx();
+        let has_key = {
+            let ctx = schema_ctx.borrow();
+            ctx.cal_map.dict_get_value(name).is_some() || ctx.value.dict_get_value(name).is_some()
+        };
+        if has_key {
+            let target_attr = self
+                .target_vars
+                .borrow()
+                .last()
+                .expect(kcl_error::INTERNAL_ERROR_MSG)
+                .clone();
+            Ok(self.schema_get_value(&schema_ctx, name, &target_attr))
+        } else {
+            match self.get_variable(name) {
+                Ok(value) => Ok(value),
+                Err(_) => Ok(ValueRef::undefined()),
+            }
+        }
+    }
+
+    /// Get the variable value named `name` from the scope named `pkgpath`, return Err when not found
+    pub(crate) fn get_variable_in_pkgpath(&self, name: &str, pkgpath: &str) -> EvalResult {
+        let pkgpath = if !pkgpath.starts_with(PKG_PATH_PREFIX) && pkgpath != MAIN_PKG_PATH {
+            format!("{}{}", PKG_PATH_PREFIX, pkgpath)
+        } else {
+            pkgpath.to_string()
+        };
+        // System module
+        if builtin::STANDARD_SYSTEM_MODULE_NAMES_WITH_AT.contains(&pkgpath.as_str()) {
+            let pkgpath = &pkgpath[1..];
+            let value = if pkgpath == builtin::system_module::UNITS
+                && builtin::system_module::UNITS_FIELD_NAMES.contains(&name)
+            {
+                let value_float: f64 = kclvm_runtime::f64_unit_value(name);
+                let value_int: u64 = kclvm_runtime::u64_unit_value(name);
+                if value_int != 1 {
+                    ValueRef::int(value_int as i64)
+                } else {
+                    ValueRef::float(value_float)
+                }
+            } else {
+                let function_name = format!(
+                    "{}{}_{}",
+                    builtin::KCL_SYSTEM_MODULE_MANGLE_PREFIX,
+                    pkgpath,
+                    name
+                );
+                let function_ptr = _kclvm_get_fn_ptr_by_name(&function_name);
+                ValueRef::func(function_ptr, 0, ValueRef::none(), &function_name, "", false)
+            };
+            Ok(value)
+        }
+        // Plugin pkgpath
+        else if pkgpath.starts_with(plugin::PLUGIN_PREFIX_WITH_AT) {
+            let name = format!("{}.{}", &pkgpath[1..], name);
+            Ok(ValueRef::func(0, 0, ValueRef::none(), &name, "", true))
+        // User pkgpath
+        } else {
+            let pkg_scopes = self.pkg_scopes.borrow();
+            let scopes = pkg_scopes
+                .get(&pkgpath)
+                .unwrap_or_else(|| panic!("package {} is not found", pkgpath));
+            // Scopes 0 is builtin scope, Scopes 1 is the global scope, Scopes 2~ are the local scopes
+            for scope in scopes.iter().rev() {
+                if let Some(value) = scope.variables.get(name) {
+                    return Ok(value.clone());
+                }
+            }
+            Err(anyhow::anyhow!("name '{}' is not defined", name))
+        }
+    }
+
+    /// Get the schema runtime type using the schema name and the current pkgpath.
+    pub(crate) fn schema_runtime_type(&self, name: &str) -> String {
+        schema_runtime_type(name, &self.current_pkgpath())
+    }
+}
@@ -0,0 +1,217 @@
+use indexmap::IndexMap;
+use kclvm_ast::ast::{self, Program};
+use kclvm_ast::MAIN_PKG;
+use kclvm_parser::parse_file;
+use kclvm_runtime::Context;
+use kclvm_sema::resolver::resolve_program;
+
+use crate::{Debugger, Evaluator};
+
+const TEST_CASES: &[(&str, &str)] = &[
+    (
+        r#"
+a = 1
+b = a + 1
+c = "${a}-${b}"
+d = [i * 2 for i in [1, 2, 3] if i > 1]
+e = {k = v for k, v in {a = 1, b = 2}}
+f = lambda x, y = 2 { x + y }(1)
+g = all x in [1, 2] { x > 0 }
+h = 1 < 2 < 3
+_i = 1
+"#,
+        r#"a: 1
+b: 2
+c: 1-2
+d:
+  - 4
+  - 6
+e:
+  a: 1
+  b: 2
+f: 3
+g: true
+h: true
+"#,
+    ),
+    (
+        r#"
+schema Person:
+    name: str = "Alice"
+    age: int = 18
+    info: str = "${name} ${age}"
+
+    check:
+        age >= 0
+
+alice = Person {}
+bob = Person {name = "Bob", age = 10}
+"#,
+        r#"alice:
+  name: Alice
+  age: 18
+  info: Alice 18
+bob:
+  name: Bob
+  age: 10
+  info: Bob 10
+"#,
+    ),
+    (
+        r#"
+schema Base:
+    a: int = 1
+
+schema Sub(Base):
+    b: int = a + 1
+
+schema Config:
+    x: int = 1
+    if x > 0:
+        y: str = "positive"
+    else:
+        y: str = "negative"
+
+sub = Sub {a = 2}
+config0 = Config {}
+config1 = Config {x = -1}
+"#,
+        r#"sub:
+  a: 2
+  b: 3
+config0:
+  x: 1
+  y: positive
+config1:
+  x: -1
+  y: negative
+"#,
+    ),
+    (
+        r#"
+schema Data:
+    labels: {str:str} = {}
+
+data: Data = {labels.app = "nginx"}
+data |= {labels.env = "prod"}
+items = [data.labels[k] for k in data.labels]
+"#,
+        r#"data:
+  labels:
+    app: nginx
+    env: prod
+items:
+  - nginx
+  - prod
+"#,
+    ),
+    (
+        r#"
+schema Config:
+    a: int = b
+    b: int
+    if True:
+        b = 1
+        b += 1
+
+config = Config {}
+"#,
+        r#"config:
+  a: 2
+  b: 2
+"#,
+    ),
+];
+
+fn evaluate(code: &str) -> String {
+    let module = parse_file("test.k", Some(code.to_string())).unwrap();
+    let mut program = Program {
+        root: ".".to_string(),
+        main: MAIN_PKG.to_string(),
+        pkgs: std::collections::HashMap::from([(MAIN_PKG.to_string(), vec![module])]),
+    };
+    let scope = resolve_program(&mut program);
+    let mut ctx = Context::new();
+    let import_names: IndexMap<String, IndexMap<String, String>> = scope.import_names.clone();
+    let evaluator = Evaluator::new(&program, import_names, &mut ctx);
+    let (_, result) = evaluator.run().unwrap().plan(false);
+    result
+}
+
+#[test]
+fn test_evaluator_exec_cases() {
+    for (code, expected) in TEST_CASES {
+        assert_eq!(evaluate(code), expected.trim_end(), "code: {}", code);
+    }
+}
+
+/// The frame names, the line and the variable names of a statement.
+type Stop = (Vec<String>, u64, Vec<String>);
+
+/// A debugger recording the call stack and the variables of every statement.
+#[derive(Default)]
+struct RecordingDebugger {
+    stops: std::cell::RefCell<Vec<Stop>>,
+}
+
+impl Debugger for RecordingDebugger {
+    fn on_stmt(&self, evaluator: &Evaluator, stmt: &ast::Node<ast::Stmt>) {
+        let frames = evaluator
+            .debug_frames()
+            .iter()
+            .map(|f| f.name.clone())
+            .collect();
+        let variables = evaluator
+            .debug_scopes()
+            .iter()
+            .flat_map(|s| s.variables.keys().map(|k| format!("{}.{}", s.name, k)))
+            .collect();
+        self.stops.borrow_mut().push((frames, stmt.line, variables));
+    }
+}
+
+#[test]
+fn test_evaluator_debugger() {
+    let code = r#"
+schema Person:
+    name: str
+    age: int = 1
+
+f = lambda x {
+    y = x + 1
+    y
+}
+a = f(1)
+p = Person {name = "Alice"}
+"#;
+    let module = parse_file("test.k", Some(code.to_string())).unwrap();
+    let mut program = Program {
+        root: ".".to_string(),
+        main: MAIN_PKG.to_string(),
+        pkgs: std::collections::HashMap::from([(MAIN_PKG.to_string(), vec![module])]),
+    };
+    let scope = resolve_program(&mut program);
+    let debugger = RecordingDebugger::default();
+    let mut ctx = Context::new();
+    let evaluator =
+        Evaluator::new(&program, scope.import_names.clone(), &mut ctx).with_debugger(&debugger);
+    evaluator.run().unwrap();
+    let stops = debugger.stops.borrow();
+    let lines: Vec<u64> = stops.iter().map(|(_, line, _)| *line).collect();
+    assert_eq!(lines, vec![2, 6, 10, 7, 8, 11, 3, 4]);
+    // The lambda body is evaluated in the lambda frame with the local variables.
+    let (frames, _, variables) = &stops[4];
+    assert_eq!(frames, &vec!["__main__".to_string(), "lambda".to_string()]);
+    assert!(variables.contains(&"Locals.x".to_string()));
+    assert!(variables.contains(&"Locals.y".to_string()));
+    // The schema body is evaluated in the schema frame with the schema attributes.
+    let (frames, _, variables) = &stops[7];
+    assert_eq!(
+        frames,
+        &vec!["__main__".to_string(), "__main__.Person".to_string()]
+    );
+    assert!(variables.contains(&"Schema.name".to_string()));
+    assert!(variables.contains(&"Globals.a".to_string()));
+    // All the frames are left after the evaluation.
+    assert_eq!(evaluator.debug_frames().len(), 1);
+}
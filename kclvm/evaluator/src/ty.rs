@@ -0,0 +1,194 @@
+// Copyright 2021 The KCL Authors. All rights reserved.
+
+use kclvm_runtime::{
+    check_type, dereference_type, is_dict_type, is_list_type, is_type_union, schema_config_meta,
+    schema_runtime_type, separate_kv, ConfigEntryOperationKind, Context, ValueRef, BUILTIN_TYPES,
+    KCL_TYPE_ANY, MAIN_PKG_PATH,
+};
+
+use crate::Evaluator;
+
+impl<'ctx> Evaluator<'ctx> {
+    /// Use the schema instance to build a new schema instance using the schema construct function
+    pub(crate) fn resolve_schema(&self, schema: &ValueRef, keys: &[String]) -> ValueRef {
+        if !schema.is_schema() {
+            return schema.clone();
+        }
+        let schema_type_name = {
+            let schema_value = schema.as_schema();
+            schema_runtime_type(&schema_value.name, &schema_value.pkgpath)
+        };
+        let schema_type = self.schemas.borrow().get(&schema_type_name).cloned();
+        match schema_type {
+            Some(schema_type) => {
+                let keys = keys.iter().map(|v| v.as_str()).collect();
+                let config = schema.dict_get_entries(keys);
+                let (config_meta, instance_pkgpath) = {
+                    let ctx = self.runtime_ctx.borrow();
+                    let config_meta = schema_config_meta(
+                        &ctx.panic_info.kcl_file,
+                        ctx.panic_info.kcl_line as u64,
+                        ctx.panic_info.kcl_col as u64,
+                    );
+                    (config_meta, ctx.panic_info.kcl_pkgpath.clone())
+                };
+                self.construct_schema_value(
+                    &schema_type,
+                    &ValueRef::list(None),
+                    &ValueRef::dict(None),
+                    &config,
+                    &config_meta,
+                    &instance_pkgpath,
+                )
+            }
+            None => schema.clone(),
+        }
+    }
+
+    /// Type pack and check ValueRef with the expected type vector
+    pub(crate) fn type_pack_and_check(
+        &self,
+        value: &ValueRef,
+        expected_types: Vec<&str>,
+    ) -> ValueRef {
+        if value.is_none_or_undefined() || expected_types.is_empty() {
+            return value.clone();
+        }
+        let is_schema = value.is_schema();
+        let value_tpe = value.type_str();
+        let mut checked = false;
+        let mut convertted_value = value.clone();
+        let expected_type = &expected_types.join(" | ").replace('@', "");
+        for tpe in expected_types {
+            let tpe = type_alias(&self.runtime_ctx.borrow(), tpe);
+            if !is_schema {
+                convertted_value = self.convert_collection_value(value, &tpe);
+            }
+            // Runtime type check
+            checked = check_type(&convertted_value, &tpe);
+            if checked {
+                break;
+            }
+        }
+        if !checked {
+            panic!("expect {expected_type}, got {value_tpe}");
+        }
+        convertted_value
+    }
+
+    /// Convert collection value including dict/list to the potential schema
+    pub(crate) fn convert_collection_value(&self, value: &ValueRef, tpe: &str) -> ValueRef {
+        // May be a type alias.
+        let tpe = &type_alias(&self.runtime_ctx.borrow(), tpe);
+        if tpe.is_empty() || tpe == KCL_TYPE_ANY {
+            return value.clone();
+        }
+        let is_collection = value.is_list() || value.is_dict();
+        let invalid_match_dict = is_dict_type(tpe) && !value.is_dict();
+        let invalid_match_list = is_list_type(tpe) && !value.is_list();
+        let invalid_match = invalid_match_dict || invalid_match_list;
+        if !is_collection || invalid_match || is_type_union(tpe) {
+            return value.clone();
+        }
+        if is_dict_type(tpe) {
+            let (_, value_tpe) = separate_kv(&dereference_type(tpe));
+            let mut expected_dict = ValueRef::dict(None);
+            let items: Vec<(String, ValueRef, ConfigEntryOperationKind, i32)> = {
+                let dict_ref = value.as_dict_ref();
+                dict_ref
+                    .values
+                    .iter()
+                    .map(|(k, v)| {
+                        let op = dict_ref
+                            .ops
+                            .get(k)
+                            .cloned()
+                            .unwrap_or(ConfigEntryOperationKind::Union);
+                        let index = *dict_ref.insert_indexs.get(k).unwrap_or(&-1);
+                        (k.clone(), v.clone(), op, index)
+                    })
+                    .collect()
+            };
+            for (k, v, op, index) in &items {
+                let expected_value = self.convert_collection_value(v, &value_tpe);
+                expected_dict.dict_update_entry(k, &expected_value, op, index)
+            }
+            expected_dict
+        } else if is_list_type(tpe) {
+            let expected_type = dereference_type(tpe);
+            let mut expected_list = ValueRef::list(None);
+            let values: Vec<ValueRef> = value.as_list_ref().values.clone();
+            for v in &values {
+                let expected_value = self.convert_collection_value(v, &expected_type);
+                expected_list.list_append(&expected_value)
+            }
+            expected_list
+        } else if BUILTIN_TYPES.contains(&tpe.as_str()) {
+            value.clone()
+        } else {
+            let ctx = self.runtime_ctx.borrow();
+            let now_meta_info = ctx.panic_info.clone();
+            let mut schema_type_name = if tpe.contains('.') {
+                tpe.to_string()
+            } else {
+                format!(
+                    "{}.{}",
+                    if now_meta_info.kcl_pkgpath.is_empty() {
+                        MAIN_PKG_PATH
+                    } else {
+                        now_meta_info.kcl_pkgpath.as_str()
+                    },
+                    tpe
+                )
+            };
+            if schema_type_name.contains('.') {
+                let splits: Vec<&str> = schema_type_name.rsplitn(2, '.').collect();
+                let pkgname = splits[1];
+                let name = splits[0];
+                match ctx.import_names.get(&now_meta_info.kcl_file) {
+                    Some(mapping) => {
+                        if let Some(pkgpath) = mapping.get(pkgname) {
+                            schema_type_name = format!("{pkgpath}.{name}");
+                        }
+                    }
+                    None => {
+                        for mapping in ctx.import_names.values() {
+                            if let Some(pkgpath) = mapping.get(pkgname) {
+                                schema_type_name = format!("{pkgpath}.{name}");
+                                break;
+                            }
+                        }
+                    }
+                }
+            }
+            drop(ctx);
+            let schema_type = self.schemas.borrow().get(&schema_type_name).cloned();
+            let value = match schema_type {
+                Some(schema_type) => self.construct_schema_value(
+                    &schema_type,
+                    &ValueRef::list(None),
+                    &ValueRef::dict(None),
+                    value,
+                    &ValueRef::dict(None),
+                    &now_meta_info.kcl_pkgpath,
+                ),
+                None => value.clone(),
+            };
+            self.runtime_ctx.borrow_mut().panic_info = now_meta_info;
+            value
+        }
+    }
+}
+
+/// Get the real type of the type alias `tpe` using the import names mapping
+/// in the runtime context `ctx`.
+fn type_alias(ctx: &Context, tpe: &str) -> String {
+    if !tpe.contains('.') {
+        match ctx.import_names.get(tpe) {
+            Some(mapping) => mapping.keys().next().unwrap().to_string(),
+            None => tpe.to_string(),
+        }
+    } else {
+        tpe.to_string()
+    }
+}
@@ -0,0 +1,160 @@
+// Copyright 2021 The KCL Authors. All rights reserved.
+
+//! The value operations which may construct schema values and need to be
+//! evaluated with the schemas defined in the evaluator.
+
+use kclvm_runtime::{ConfigEntryOperationKind, Value, ValueRef};
+
+use crate::Evaluator;
+
+impl<'ctx> Evaluator<'ctx> {
+    /// Binary bit or operation a | b
+    pub(crate) fn bit_or(&self, a: &ValueRef, b: &ValueRef) -> ValueRef {
+        if let (Value::int_value(a), Value::int_value(b)) = (&*a.rc.borrow(), &*b.rc.borrow()) {
+            return ValueRef::int(*a | *b);
+        };
+        self.union_entry(&mut a.deep_copy(), b, true, false, true, true)
+    }
+
+    /// Binary aug bit or operation a |= b
+    pub(crate) fn aug_bit_or(&self, a: &mut ValueRef, b: &ValueRef) -> ValueRef {
+        let valid = match (&mut *a.rc.borrow_mut(), &*b.rc.borrow()) {
+            (Value::int_value(a), Value::int_value(b)) => {
+                *a |= *b;
+                true
+            }
+            _ => false,
+        };
+        if !valid {
+            if a.is_list_or_config() || b.is_list_or_config() {
+                self.union_entry(a, b, true, false, true, true);
+            } else {
+                panic!(
+                    "unsupported operand type(s) for |: '{}' and '{}'",
+                    a.type_str(),
+                    b.type_str()
+                );
+            }
+        }
+        a.clone()
+    }
+
+    /// Merge the value `v` into the dict or schema `p` with the key `key`, and
+    /// the value is type packed and checked when it is a schema attribute.
+    pub(crate) fn dict_merge(
+        &self,
+        p: &mut ValueRef,
+        key: &str,
+        v: &ValueRef,
+        op: &ConfigEntryOperationKind,
+        insert_index: i32,
+    ) {
+        let attr_type = match &*p.rc.borrow() {
+            Value::dict_value(dict) => dict.attr_map.get(key).cloned(),
+            Value::schema_value(schema) => schema.config.attr_map.get(key).cloned(),
+            _ => panic!("invalid object '{}' in attr_map", p.type_str()),
+        };
+        match attr_type {
+            Some(attr_type) => {
+                let v = self.type_pack_and_check(v, vec![&attr_type]);
+                p.dict_merge(
+                    &mut self.runtime_ctx.borrow_mut(),
+                    key,
+                    &v,
+                    op.clone(),
+                    insert_index,
+                );
+            }
+            None => p.dict_merge(
+                &mut self.runtime_ctx.borrow_mut(),
+                key,
+                v,
+                op.clone(),
+                insert_index,
+            ),
+        }
+    }
+
+    /// Union the config entry `b` into the schema value `a`, and the attribute
+    /// values are type packed and checked.
+    pub(crate) fn value_union(&self, a: &mut ValueRef, b: &ValueRef) -> ValueRef {
+        let attr_map = match &*a.rc.borrow() {
+            Value::dict_value(dict) => dict.attr_map.clone(),
+            Value::schema_value(schema) => schema.config.attr_map.clone(),
+            _ => panic!("invalid object '{}' in attr_map", a.type_str()),
+        };
+        if b.is_config() {
+            let items: Vec<(String, ValueRef)> = b
+                .as_dict_ref()
+                .values
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect();
+            let mut result = a.clone();
+            for (k, v) in &items {
+                let entry = match attr_map.get(k) {
+                    Some(attr_type) => {
+                        let v = self.type_pack_and_check(v, vec![attr_type]);
+                        let mut entry = b.dict_get_entry(k).unwrap().deep_copy();
+                        entry.dict_update_key_value(k, v);
+                        entry
+                    }
+                    None => b.dict_get_entry(k).unwrap(),
+                };
+                result = a.union_entry(
+                    &mut self.runtime_ctx.borrow_mut(),
+                    &entry,
+                    true,
+                    false,
+                    false,
+                    false,
+                );
+            }
+            result
+        } else {
+            a.union_entry(
+                &mut self.runtime_ctx.borrow_mut(),
+                b,
+                true,
+                false,
+                false,
+                false,
+            )
+        }
+    }
+
+    /// Unpack the dict or schema value `v` into the dict `p`.
+    pub(crate) fn dict_insert_unpack(&self, p: &mut ValueRef, v: &ValueRef) {
+        let mut union = false;
+        match (&*p.rc.borrow(), &*v.rc.borrow()) {
+            (
+                Value::dict_value(_) | Value::schema_value(_),
+                Value::dict_value(_) | Value::schema_value(_),
+            ) => {
+                union = true;
+            }
+            (Value::dict_value(_) | Value::schema_value(_), Value::none) => { /*Do nothing on unpacking None/Undefined*/
+            }
+            (Value::dict_value(_) | Value::schema_value(_), Value::undefined) => { /*Do nothing on unpacking None/Undefined*/
+            }
+            _ => panic!("only list, dict and schema object can be used with unpack operators * and **, got {v}"),
+        }
+        if union {
+            self.aug_bit_or(p, &v.schema_to_dict().deep_copy());
+        }
+    }
+
+    /// Set the attribute `key` of the dict or schema `p` with `val`, and the
+    /// schema value is resolved again with the new config keys.
+    pub(crate) fn dict_set_value(&self, p: &mut ValueRef, key: &str, val: &ValueRef) {
+        if p.is_config() {
+            p.dict_update_key_value(key, val.clone());
+        }
+        if p.is_schema() {
+            let mut config_keys = p.as_schema().config_keys.clone();
+            config_keys.push(key.to_string());
+            let schema = self.resolve_schema(p, &config_keys);
+            p.schema_update_with_schema(&schema);
+        }
+    }
+}
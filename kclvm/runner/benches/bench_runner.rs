@@ -1,13 +1,15 @@
 use std::path::Path;
 use std::sync::Arc;
 
-use criterion::{criterion_group, criterion_main, Criterion};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
 use walkdir::WalkDir;
 
 use kclvm_parser::{load_program, ParseSession};
-use kclvm_runner::{execute, runner::ExecProgramArgs};
+use kclvm_runner::{exec_program, execute, runner::ExecProgramArgs};
 
 const EXEC_DATA_PATH: &str = "./src/exec_data/";
+/// The number of schema instances in the generated large config.
+const LARGE_CONFIG_INSTANCES: usize = 10000;
 
 pub fn criterion_benchmark(c: &mut Criterion) {
     c.bench_function("refactor kclvm-runner", |b| {
@@ -26,19 +28,43 @@ pub fn criterion_benchmark(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, criterion_benchmark);
+/// Compare the LLVM optimization levels on a large config with many schema instances,
+/// the time includes both the compilation and the execution.
+pub fn opt_level_benchmark(c: &mut Criterion) {
+    let code = large_config(LARGE_CONFIG_INSTANCES);
+    let mut group = c.benchmark_group("opt_level");
+    group.sample_size(10);
+    for jit in [false, true] {
+        let backend = if jit { "jit" } else { "lib" };
+        for opt_level in 0..=3 {
+            let args = ExecProgramArgs {
+                k_filename_list: vec!["main.k".to_string()],
+                k_code_list: vec![code.clone()],
+                jit,
+                opt_level,
+                disable_yaml_result: true,
+                ..Default::default()
+            };
+            group.bench_with_input(BenchmarkId::new(backend, opt_level), &args, |b, args| {
+                b.iter(|| exec_program(Arc::new(ParseSession::default()), args).unwrap())
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark, opt_level_benchmark);
 criterion_main!(benches);
 
 fn exec(file: &str) -> Result<String, String> {
     let mut args = ExecProgramArgs::default();
     args.k_filename_list.push(file.to_string());
-    let plugin_agent = 0;
     let opts = args.get_load_program_options();
     let sess = Arc::new(ParseSession::default());
     // Load AST program
     let program = load_program(sess.clone(), &[file], Some(opts)).unwrap();
     // Resolve ATS, generate libs, link libs and execute.
-    execute(sess, program, plugin_agent, &args)
+    execute(sess, program, &args)
 }
 
 /// Get kcl files from path.
@@ -63,3 +89,28 @@ fn get_files<P: AsRef<Path>>(
     }
     files
 }
+
+/// Generate a config with `count` schema instances, which have default values,
+/// checks and computed attributes.
+fn large_config(count: usize) -> String {
+    let mut code = String::from(
+        r#"schema Resource:
+    name: str
+    replicas: int = 1
+    labels: {str:str} = {app = name}
+    ports: [int] = [8080]
+    cpu: int = replicas * 100
+
+    check:
+        replicas > 0
+        len(name) > 0
+"#,
+    );
+    for i in 0..count {
+        code.push_str(&format!(
+            "resource{i} = Resource {{name = \"resource{i}\", replicas = {}}}\n",
+            i % 10 + 1
+        ));
+    }
+    code
+}
@@ -1,14 +1,18 @@
 use compiler_base_macros::bug;
 use indexmap::IndexMap;
 use kclvm_ast::ast::{self, Program};
-use kclvm_compiler::codegen::{
-    llvm::{emit_code, OBJECT_FILE_SUFFIX},
-    EmitOptions,
-};
-use kclvm_config::cache::{load_pkg_cache, save_pkg_cache, CacheOption};
+#[cfg(feature = "cranelift")]
+use kclvm_compiler::codegen::cranelift;
+#[cfg(feature = "llvm")]
+use kclvm_compiler::codegen::llvm::{emit_code, emit_file_suffix};
+#[cfg(feature = "llvm")]
+use kclvm_compiler::codegen::WASM32_WASI_TARGET;
+use kclvm_compiler::codegen::{EmitKind, EmitOptions, OBJECT_FILE_SUFFIX};
+use kclvm_compiler::optimizer::optimize_program;
+use kclvm_config::cache::get_pkg_cache_keys;
 use kclvm_sema::resolver::scope::ProgramScope;
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     env,
     path::{Path, PathBuf},
 };
@@ -53,12 +57,18 @@ pub(crate) trait LibAssembler {
     ///
     /// "object_file_path" is the full filename of the generated intermediate code file with suffix.
     /// e.g. code_file_path : "/test_dir/test_code_file.o"
+    ///
+    /// "opt_level" is the optimization level from 0 to 3 of the generated code.
+    ///
+    /// "debug_info" denotes whether to emit the DWARF debug info of the KCL sources.
     fn assemble(
         &self,
         compile_prog: &Program,
         import_names: IndexMap<String, IndexMap<String, String>>,
         code_file: &str,
         code_file_path: &str,
+        opt_level: u32,
+        debug_info: bool,
     ) -> String;
 
     #[inline]
@@ -69,10 +79,76 @@ pub(crate) trait LibAssembler {
 }
 
 /// This enum lists all the intermediate code assemblers currently supported by kclvm.
-/// Currently only supports assemble llvm intermediate code into dynamic link library.
+/// Currently supports assemble llvm intermediate code into native dynamic link library
+/// and WebAssembly module, and assemble the code generated by Cranelift into native
+/// dynamic link library.
 #[derive(Clone)]
 pub(crate) enum KclvmLibAssembler {
+    #[cfg(feature = "llvm")]
     LLVM,
+    #[cfg(feature = "llvm")]
+    WASM,
+    #[cfg(feature = "cranelift")]
+    Cranelift,
+}
+
+impl KclvmLibAssembler {
+    /// Return the assembler of the host target, which is the LLVM assembler when
+    /// the LLVM backend is enabled, otherwise the Cranelift assembler.
+    pub(crate) fn host() -> Self {
+        #[cfg(feature = "llvm")]
+        return KclvmLibAssembler::LLVM;
+        #[cfg(not(feature = "llvm"))]
+        return KclvmLibAssembler::Cranelift;
+    }
+
+    /// Return the target triple of the assembled object files, which also names
+    /// the cache dir of the object files.
+    pub(crate) fn target(&self) -> String {
+        match &self {
+            #[cfg(feature = "llvm")]
+            KclvmLibAssembler::LLVM => env!("KCLVM_DEFAULT_TARGET").to_string(),
+            #[cfg(feature = "llvm")]
+            KclvmLibAssembler::WASM => WASM32_WASI_TARGET.to_string(),
+            // The object files generated by Cranelift are cached apart from the LLVM ones.
+            #[cfg(feature = "cranelift")]
+            KclvmLibAssembler::Cranelift => format!("{}-cranelift", env!("KCLVM_DEFAULT_TARGET")),
+        }
+    }
+
+    /// Return the target triple passed to the code generator, `None` denotes the host target.
+    fn emit_target(&self) -> Option<&'static str> {
+        match &self {
+            #[cfg(feature = "llvm")]
+            KclvmLibAssembler::LLVM => None,
+            #[cfg(feature = "llvm")]
+            KclvmLibAssembler::WASM => Some(WASM32_WASI_TARGET),
+            #[cfg(feature = "cranelift")]
+            KclvmLibAssembler::Cranelift => None,
+        }
+    }
+
+    /// Emit the code of the program with the code generator of the assembler,
+    /// and return the file suffix of the emitted code.
+    fn emit_code(
+        &self,
+        program: &Program,
+        import_names: IndexMap<String, IndexMap<String, String>>,
+        opt: &EmitOptions,
+    ) -> Result<&'static str, String> {
+        match &self {
+            #[cfg(feature = "llvm")]
+            KclvmLibAssembler::LLVM | KclvmLibAssembler::WASM => {
+                emit_code(program, import_names, opt).map_err(|err| err.to_string())?;
+                Ok(emit_file_suffix(opt.emit_kind))
+            }
+            #[cfg(feature = "cranelift")]
+            KclvmLibAssembler::Cranelift => {
+                cranelift::emit_code(program, import_names, opt).map_err(|err| err.to_string())?;
+                Ok(OBJECT_FILE_SUFFIX)
+            }
+        }
+    }
 }
 
 /// KclvmLibAssembler is a dispatcher, responsible for calling corresponding methods
@@ -88,13 +164,36 @@ impl LibAssembler for KclvmLibAssembler {
         import_names: IndexMap<String, IndexMap<String, String>>,
         code_file: &str,
         object_file_path: &str,
+        opt_level: u32,
+        debug_info: bool,
     ) -> String {
         match &self {
+            #[cfg(feature = "llvm")]
             KclvmLibAssembler::LLVM => LlvmLibAssembler::default().assemble(
                 compile_prog,
                 import_names,
                 code_file,
                 object_file_path,
+                opt_level,
+                debug_info,
+            ),
+            #[cfg(feature = "llvm")]
+            KclvmLibAssembler::WASM => LlvmLibAssembler::wasm().assemble(
+                compile_prog,
+                import_names,
+                code_file,
+                object_file_path,
+                opt_level,
+                debug_info,
+            ),
+            #[cfg(feature = "cranelift")]
+            KclvmLibAssembler::Cranelift => CraneliftLibAssembler.assemble(
+                compile_prog,
+                import_names,
+                code_file,
+                object_file_path,
+                opt_level,
+                debug_info,
             ),
         }
     }
@@ -102,29 +201,53 @@ impl LibAssembler for KclvmLibAssembler {
     #[inline]
     fn add_code_file_suffix(&self, code_file: &str) -> String {
         match &self {
+            #[cfg(feature = "llvm")]
             KclvmLibAssembler::LLVM => LlvmLibAssembler::default().add_code_file_suffix(code_file),
+            #[cfg(feature = "llvm")]
+            KclvmLibAssembler::WASM => LlvmLibAssembler::wasm().add_code_file_suffix(code_file),
+            #[cfg(feature = "cranelift")]
+            KclvmLibAssembler::Cranelift => CraneliftLibAssembler.add_code_file_suffix(code_file),
         }
     }
 
     #[inline]
     fn get_code_file_suffix(&self) -> String {
         match &self {
+            #[cfg(feature = "llvm")]
             KclvmLibAssembler::LLVM => LlvmLibAssembler::default().get_code_file_suffix(),
+            #[cfg(feature = "llvm")]
+            KclvmLibAssembler::WASM => LlvmLibAssembler::wasm().get_code_file_suffix(),
+            #[cfg(feature = "cranelift")]
+            KclvmLibAssembler::Cranelift => CraneliftLibAssembler.get_code_file_suffix(),
         }
     }
 }
 
 /// LlvmLibAssembler is mainly responsible for assembling the generated LLVM IR into a dynamic link library.
+#[cfg(feature = "llvm")]
 #[derive(Clone)]
-pub(crate) struct LlvmLibAssembler;
+pub(crate) struct LlvmLibAssembler {
+    /// The target triple of the object files, if not set, use the host target.
+    target: Option<&'static str>,
+}
 
+#[cfg(feature = "llvm")]
 impl LlvmLibAssembler {
     #[inline]
     fn new() -> Self {
-        Self {}
+        Self { target: None }
+    }
+
+    /// Constructs a LlvmLibAssembler which assembles the wasm32-wasi object files.
+    #[inline]
+    fn wasm() -> Self {
+        Self {
+            target: Some(WASM32_WASI_TARGET),
+        }
     }
 }
 
+#[cfg(feature = "llvm")]
 impl Default for LlvmLibAssembler {
     #[inline]
     fn default() -> Self {
@@ -133,6 +256,7 @@ impl Default for LlvmLibAssembler {
 }
 
 /// KclvmLibAssembler implements the LibAssembler trait,
+#[cfg(feature = "llvm")]
 impl LibAssembler for LlvmLibAssembler {
     /// "assemble_lib" will call the [kclvm_compiler::codegen::emit_code]
     /// to generate the `.o` object file.
@@ -143,6 +267,8 @@ impl LibAssembler for LlvmLibAssembler {
         import_names: IndexMap<String, IndexMap<String, String>>,
         code_file: &str,
         object_file_path: &str,
+        opt_level: u32,
+        debug_info: bool,
     ) -> String {
         // Clean the existed "*.o" object file.
         clean_path(object_file_path);
@@ -155,6 +281,10 @@ impl LibAssembler for LlvmLibAssembler {
                 from_path: None,
                 emit_path: Some(code_file),
                 no_link: true,
+                opt_level,
+                target: self.target,
+                emit_kind: EmitKind::Object,
+                debug_info,
             },
         )
         .expect("Compile KCL to LLVM error");
@@ -173,6 +303,57 @@ impl LibAssembler for LlvmLibAssembler {
     }
 }
 
+/// CraneliftLibAssembler is mainly responsible for assembling the native code generated
+/// by Cranelift into a dynamic link library, which does not require an LLVM installation.
+#[cfg(feature = "cranelift")]
+#[derive(Clone, Default)]
+pub(crate) struct CraneliftLibAssembler;
+
+#[cfg(feature = "cranelift")]
+impl LibAssembler for CraneliftLibAssembler {
+    /// "assemble_lib" will call the [kclvm_compiler::codegen::cranelift::emit_code]
+    /// to generate the `.o` object file.
+    #[inline]
+    fn assemble(
+        &self,
+        compile_prog: &Program,
+        import_names: IndexMap<String, IndexMap<String, String>>,
+        code_file: &str,
+        object_file_path: &str,
+        opt_level: u32,
+        debug_info: bool,
+    ) -> String {
+        // Clean the existed "*.o" object file.
+        clean_path(object_file_path);
+
+        // Compile KCL code into ".o" object file.
+        cranelift::emit_code(
+            compile_prog,
+            import_names,
+            &EmitOptions {
+                emit_path: Some(code_file),
+                no_link: true,
+                opt_level,
+                debug_info,
+                ..Default::default()
+            },
+        )
+        .expect("Compile KCL to Cranelift error");
+
+        object_file_path.to_string()
+    }
+
+    #[inline]
+    fn add_code_file_suffix(&self, code_file: &str) -> String {
+        format!("{}{}", code_file, OBJECT_FILE_SUFFIX)
+    }
+
+    #[inline]
+    fn get_code_file_suffix(&self) -> String {
+        OBJECT_FILE_SUFFIX.to_string()
+    }
+}
+
 /// KclvmAssembler is mainly responsible for assembling the generated bytecode
 /// LLVM IR or other IR code into dynamic link libraries, for multi-file kcl programs,
 /// and take the result of kclvm-parser, kclvm-sema and kclvm-compiler as input.
@@ -189,26 +370,102 @@ pub(crate) struct KclvmAssembler {
     single_file_assembler: KclvmLibAssembler,
     target: String,
     external_pkgs: HashMap<String, String>,
+    opt_level: u32,
+    debug_info: bool,
+}
+
+/// Get the compile options which affect the compiled package artifacts, and they
+/// are a part of the package cache keys.
+fn get_compile_options(
+    external_pkgs: &HashMap<String, String>,
+    opt_level: u32,
+    debug_info: bool,
+) -> String {
+    let mut external_pkgs: Vec<String> = external_pkgs
+        .iter()
+        .map(|(name, path)| format!("{}={}", name, path))
+        .collect();
+    external_pkgs.sort();
+    format!(
+        "{};opt_level={};debug_info={}",
+        external_pkgs.join(";"),
+        opt_level,
+        debug_info
+    )
+}
+
+/// Remove the object files and the lock files of the packages in `cache_dir` which are
+/// named by the superseded cache keys instead of the current `cache_keys`. The files of
+/// the packages not in the program and the files locked by other processes are kept.
+fn evict_superseded_pkg_caches(
+    cache_dir: &Path,
+    cache_keys: &HashMap<String, String>,
+    suffix: &str,
+) {
+    let entries = match std::fs::read_dir(cache_dir) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+    let lock_suffix = format!("{}.lock", suffix);
+    let mut superseded = HashSet::new();
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        let stem = match name
+            .strip_suffix(&lock_suffix)
+            .or_else(|| name.strip_suffix(suffix))
+        {
+            Some(stem) => stem,
+            None => continue,
+        };
+        // The cache files are named `<pkgpath>.<md5 cache key><suffix>`, and the temp
+        // files with the process id after the cache key are skipped.
+        if let Some((pkgpath, key)) = stem.rsplit_once('.') {
+            if key.len() == 32
+                && key.chars().all(|c| c.is_ascii_hexdigit())
+                && matches!(cache_keys.get(pkgpath), Some(current) if current != key)
+            {
+                superseded.insert(cache_dir.join(format!("{}{}", stem, suffix)));
+            }
+        }
+    }
+    for code_file_path in superseded {
+        let lock_file_path = format!("{}.lock", code_file_path.display());
+        if let Ok(mut file_lock) = fslock::LockFile::open(&lock_file_path) {
+            if file_lock.try_lock().unwrap_or(false) {
+                let _ = std::fs::remove_file(&code_file_path);
+                let _ = file_lock.unlock();
+                drop(file_lock);
+                let _ = std::fs::remove_file(&lock_file_path);
+            }
+        }
+    }
 }
 
 impl KclvmAssembler {
     /// Constructs an KclvmAssembler instance with a default value 4
     /// for the number of threads in multi-file compilation.
+    ///
+    /// The resolved program is optimized before the code generation.
     #[inline]
     pub(crate) fn new(
-        program: ast::Program,
+        mut program: ast::Program,
         scope: ProgramScope,
         entry_file: String,
         single_file_assembler: KclvmLibAssembler,
         external_pkgs: HashMap<String, String>,
+        opt_level: u32,
+        debug_info: bool,
     ) -> Self {
+        optimize_program(&mut program);
         Self {
             program,
             scope,
             entry_file,
+            target: single_file_assembler.target(),
             single_file_assembler,
-            target: env!("KCLVM_DEFAULT_TARGET").to_string(),
             external_pkgs,
+            opt_level,
+            debug_info,
         }
     }
 
@@ -252,6 +509,16 @@ impl KclvmAssembler {
             .join(&self.target)
     }
 
+    /// Get the content-addressed cache keys of the packages. The non-main packages
+    /// are cached by the hash of their sources, their dependencies and the compile options.
+    #[inline]
+    pub(crate) fn pkg_cache_keys(&self) -> HashMap<String, String> {
+        get_pkg_cache_keys(
+            &self.program,
+            &get_compile_options(&self.external_pkgs, self.opt_level, self.debug_info),
+        )
+    }
+
     /// Generate the dynamic link libraries and return file paths.
     ///
     /// In the method, multiple threads will be created to concurrently generate dynamic link libraries
@@ -268,6 +535,8 @@ impl KclvmAssembler {
             &self.single_file_assembler.get_code_file_suffix(),
         );
         let cache_dir = self.load_cache_dir(&self.program.root);
+        let cache_keys = self.pkg_cache_keys();
+        let code_file_suffix = self.single_file_assembler.get_code_file_suffix();
         let mut compile_progs: IndexMap<
             String,
             (
@@ -305,19 +574,17 @@ impl KclvmAssembler {
                 PathBuf::from(entry_file)
             } else {
                 // The path to the generated files(*.o or *.lock) when the non-main package is compiled.
-                cache_dir.join(&pkgpath)
+                cache_dir.join(format!("{}.{}", pkgpath, cache_keys[&pkgpath]))
             };
             let code_file = file.to_str().unwrap().to_string();
             let code_file_path = assembler.add_code_file_suffix(&code_file);
             let lock_file_path = format!("{}.lock", code_file_path);
-            let target = self.target.clone();
             {
                 // Locking file for parallel code generation.
                 let mut file_lock = fslock::LockFile::open(&lock_file_path)
                     .unwrap_or_else(|_| panic!("{} not found", lock_file_path));
                 file_lock.lock().unwrap();
 
-                let root = &compile_prog.root;
                 // The main package does not perform cache reading and writing,
                 // and other packages perform read and write caching. Because
                 // KCL supports multi-file compilation, it is impossible to
@@ -326,60 +593,79 @@ impl KclvmAssembler {
                 // written.
                 let file_path = if is_main_pkg {
                     // generate dynamic link library for single file kcl program
-                    assembler.assemble(&compile_prog, import_names, &code_file, &code_file_path)
+                    assembler.assemble(
+                        &compile_prog,
+                        import_names,
+                        &code_file,
+                        &code_file_path,
+                        self.opt_level,
+                        self.debug_info,
+                    )
+                } else if Path::new(&code_file_path).exists() {
+                    // The object file keyed by the same content hash is reused directly.
+                    code_file_path
                 } else {
-                    // Read the lib path cache
-                    let file_relative_path: Option<String> = load_pkg_cache(
-                        root,
-                        &target,
-                        &pkgpath,
-                        CacheOption::default(),
-                        &self.external_pkgs,
+                    // Generate the object file into a temp file for single file kcl program,
+                    // and then rename it, so that a broken object file is never cached.
+                    let tmp_code_file = format!("{}.{}", code_file, std::process::id());
+                    let tmp_code_file_path = assembler.add_code_file_suffix(&tmp_code_file);
+                    let file_path = assembler.assemble(
+                        &compile_prog,
+                        import_names,
+                        &tmp_code_file,
+                        &tmp_code_file_path,
+                        self.opt_level,
+                        self.debug_info,
                     );
-                    let file_abs_path = match file_relative_path {
-                        Some(file_relative_path) => {
-                            let path = if file_relative_path.starts_with('.') {
-                                file_relative_path.replacen('.', root, 1)
-                            } else {
-                                file_relative_path
-                            };
-                            if Path::new(&path).exists() {
-                                Some(path)
-                            } else {
-                                None
-                            }
-                        }
-                        None => None,
-                    };
-                    match file_abs_path {
-                        Some(path) => path,
-                        None => {
-                            // Generate the object file for single file kcl program.
-                            let file_path = assembler.assemble(
-                                &compile_prog,
-                                import_names,
-                                &code_file,
-                                &code_file_path,
-                            );
-                            let lib_relative_path = file_path.replacen(root, ".", 1);
-                            save_pkg_cache(
-                                root,
-                                &target,
-                                &pkgpath,
-                                lib_relative_path,
-                                CacheOption::default(),
-                            );
-                            file_path
-                        }
-                    }
+                    std::fs::rename(&file_path, &code_file_path).unwrap();
+                    code_file_path
                 };
                 file_lock.unlock().unwrap();
                 lib_paths.push(file_path);
             };
         }
         self.single_file_assembler.clean_lock_file(&self.entry_file);
+        evict_superseded_pkg_caches(&cache_dir, &cache_keys, &code_file_suffix);
         lib_paths
     }
+
+    /// Emit the generated code of each package into `output_dir` in the format of
+    /// `emit_kind` without linking, and return the emitted file paths. The files are
+    /// named by the package paths e.g., `__main__.ll` and `pkg.sub.ll`.
+    pub(crate) fn emit_pkgs(
+        self,
+        emit_kind: EmitKind,
+        output_dir: &Path,
+    ) -> Result<Vec<String>, String> {
+        std::fs::create_dir_all(output_dir).map_err(|err| err.to_string())?;
+        let target = self.single_file_assembler.emit_target();
+        let mut paths = vec![];
+        for (pkgpath, modules) in self.program.pkgs {
+            let code_file = output_dir.join(&pkgpath).to_str().unwrap().to_string();
+            let compile_prog = ast::Program {
+                root: self.program.root.clone(),
+                main: self.program.main.clone(),
+                pkgs: HashMap::from([(pkgpath, modules)]),
+            };
+            let opt = EmitOptions {
+                from_path: None,
+                emit_path: Some(&code_file),
+                no_link: true,
+                opt_level: self.opt_level,
+                target,
+                emit_kind,
+                debug_info: self.debug_info,
+            };
+            let suffix = self.single_file_assembler.emit_code(
+                &compile_prog,
+                self.scope.import_names.clone(),
+                &opt,
+            )?;
+            paths.push(format!("{}{}", code_file, suffix));
+        }
+        paths.sort();
+        Ok(paths)
+    }
 }
 
 #[inline]
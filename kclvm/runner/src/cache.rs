@@ -0,0 +1,261 @@
+use std::{
+    collections::{BTreeSet, HashSet},
+    fs,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    time::SystemTime,
+};
+
+use indexmap::IndexMap;
+use kclvm_ast::ast::Program;
+use kclvm_config::{
+    modfile::{get_pkg_root, KCL_FILE_EXTENSION, KCL_MOD_FILE, KCL_MOD_LOCK_FILE},
+    settings::DEFAULT_SETTING_FILE,
+};
+use kclvm_error::Handler;
+use kclvm_sema::resolver::scope::ProgramScope;
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use tempfile::{tempdir, TempDir};
+
+use crate::runner::ExecProgramArgs;
+
+/// The default maximum number of the programs kept by [`ProgramCache`].
+pub const DEFAULT_PROGRAM_CACHE_CAPACITY: usize = 32;
+
+/// The module and settings files which change the program when they are changed,
+/// created or removed in the package directories and the package roots.
+const PROGRAM_CONFIG_FILES: [&str; 3] = [KCL_MOD_FILE, KCL_MOD_LOCK_FILE, DEFAULT_SETTING_FILE];
+
+/// ProgramCache keeps the parsed and resolved programs and their compiled libraries
+/// in memory for the long-running server to serve repeated executions incrementally.
+///
+/// The package directories of a cached program are watched, and the program is loaded
+/// again when any of its source, module or settings files is changed, created or removed.
+/// At most `capacity` programs are kept, and the least recently used program is removed
+/// when a new program is inserted into a full cache.
+#[derive(Debug)]
+pub struct ProgramCache {
+    /// The cached programs in the order from the least to the most recently used.
+    programs: Mutex<IndexMap<String, Arc<Mutex<CachedProgram>>>>,
+    capacity: usize,
+}
+
+impl Default for ProgramCache {
+    fn default() -> Self {
+        Self::with_capacity(DEFAULT_PROGRAM_CACHE_CAPACITY)
+    }
+}
+
+impl ProgramCache {
+    /// New a program cache which keeps at most `capacity` programs, and the capacity
+    /// is at least 1.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            programs: Mutex::new(IndexMap::new()),
+            capacity: capacity.max(1),
+        }
+    }
+
+    /// Returns the cached program of `key` when its sources are not changed,
+    /// and the stale cached program will be removed.
+    ///
+    /// The program scope shares its scopes with its clones, so the cached program
+    /// must be locked when its scope is used.
+    pub(crate) fn get(&self, key: &str) -> Option<Arc<Mutex<CachedProgram>>> {
+        let mut programs = self.programs.lock().unwrap();
+        let (key, cached) = programs.shift_remove_entry(key)?;
+        if !cached.lock().unwrap().is_fresh() {
+            return None;
+        }
+        // The used program is moved to the most recently used end.
+        programs.insert(key, cached.clone());
+        Some(cached)
+    }
+
+    /// Insert the resolved program of `key` into the cache, and remove the least
+    /// recently used programs when the cache is full.
+    pub(crate) fn insert(&self, key: String, cached: CachedProgram) -> Arc<Mutex<CachedProgram>> {
+        let cached = Arc::new(Mutex::new(cached));
+        let mut programs = self.programs.lock().unwrap();
+        programs.shift_remove(&key);
+        programs.insert(key, cached.clone());
+        while programs.len() > self.capacity {
+            programs.shift_remove_index(0);
+        }
+        cached
+    }
+
+    /// Remove all the cached programs and their compiled libraries.
+    pub fn clear(&self) {
+        self.programs.lock().unwrap().clear();
+    }
+
+    /// Returns the number of the cached programs.
+    pub fn len(&self) -> usize {
+        self.programs.lock().unwrap().len()
+    }
+
+    /// Returns true if there is no cached program.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// CachedProgram denotes a resolved program, its program scope and the
+/// linked library built on demand for the LLVM backend.
+#[derive(Debug)]
+pub(crate) struct CachedProgram {
+    pub(crate) program: Arc<Program>,
+    pub(crate) scope: ProgramScope,
+    /// Whether any of the source files is changed since the program is loaded.
+    stale: Arc<AtomicBool>,
+    /// The watcher of the package directories, which stops watching when dropped.
+    _watcher: Option<RecommendedWatcher>,
+    /// The temp dir holding the linked library and the library path, the temp
+    /// dir is removed when the cached program and all its runs are dropped.
+    lib: Option<Arc<(TempDir, String)>>,
+}
+
+impl CachedProgram {
+    /// New a cached program loaded at `loaded_at` and watch its sources, and the module
+    /// and settings files in the package directories and the package roots. The program
+    /// is stale at once when its sources can not be watched.
+    pub(crate) fn new(program: Program, scope: ProgramScope, loaded_at: SystemTime) -> Self {
+        let mut files = HashSet::new();
+        let mut dirs = BTreeSet::new();
+        for modules in program.pkgs.values() {
+            for module in modules {
+                if let Ok(path) = fs::canonicalize(&module.filename) {
+                    if let Some(dir) = path.parent() {
+                        dirs.insert(dir.to_path_buf());
+                    }
+                    files.insert(path);
+                }
+            }
+        }
+        let roots: Vec<PathBuf> = dirs
+            .iter()
+            .filter_map(|dir| get_pkg_root(dir.to_str()?))
+            .chain((!program.root.is_empty()).then(|| program.root.clone()))
+            .filter_map(|root| fs::canonicalize(root).ok())
+            .collect();
+        dirs.extend(roots);
+        for dir in &dirs {
+            for name in PROGRAM_CONFIG_FILES {
+                let path = dir.join(name);
+                if path.is_file() {
+                    files.insert(path);
+                }
+            }
+        }
+        let stale = Arc::new(AtomicBool::new(false));
+        let watcher = watch_sources(&files, &dirs, stale.clone());
+        // The sources changed after they are loaded and before they are watched
+        // are found by their modification time.
+        let changed = dirs.iter().chain(files.iter()).any(|path| {
+            fs::metadata(path)
+                .and_then(|metadata| metadata.modified())
+                .map_or(true, |modified| modified >= loaded_at)
+        });
+        if watcher.is_none() || changed {
+            stale.store(true, Ordering::SeqCst);
+        }
+        Self {
+            program: Arc::new(program),
+            scope,
+            stale,
+            _watcher: watcher,
+            lib: None,
+        }
+    }
+
+    /// Whether all the source files are not changed.
+    fn is_fresh(&self) -> bool {
+        !self.stale.load(Ordering::SeqCst)
+    }
+
+    /// Returns the program scope used by the runners, which only has the import names
+    /// of the cached scope and shares no scopes with it, thus it can be used after the
+    /// cached program is unlocked.
+    pub(crate) fn runner_scope(&self) -> ProgramScope {
+        ProgramScope {
+            scope_map: Default::default(),
+            import_names: self.scope.import_names.clone(),
+            handler: Handler::default(),
+        }
+    }
+
+    /// Returns the temp dir and the path of the linked library, and the library is
+    /// built into the temp dir with `build` when it is first required. The temp dir
+    /// is kept until the returned value is dropped even if the program is removed.
+    pub(crate) fn lib<F>(&mut self, build: F) -> Arc<(TempDir, String)>
+    where
+        F: FnOnce(&Program, &ProgramScope, &str) -> String,
+    {
+        if let Some(lib) = &self.lib {
+            return lib.clone();
+        }
+        let temp_dir = tempdir().unwrap();
+        let lib_path = build(
+            &self.program,
+            &self.scope,
+            temp_dir.path().to_str().unwrap(),
+        );
+        let lib = Arc::new((temp_dir, lib_path));
+        self.lib = Some(lib.clone());
+        lib
+    }
+}
+
+/// Watch the package directories `dirs`, and set `stale` when any of the `files`, the KCL
+/// files or the module and settings files in the directories is changed, created or removed.
+fn watch_sources(
+    files: &HashSet<PathBuf>,
+    dirs: &BTreeSet<PathBuf>,
+    stale: Arc<AtomicBool>,
+) -> Option<RecommendedWatcher> {
+    let files = files.clone();
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        let changed = match event {
+            Ok(event) => {
+                !matches!(event.kind, EventKind::Access(_))
+                    && event.paths.iter().any(|path| {
+                        files.contains(path)
+                            || path
+                                .extension()
+                                .map_or(false, |ext| ext == KCL_FILE_EXTENSION)
+                            || path.file_name().map_or(false, |name| {
+                                PROGRAM_CONFIG_FILES.iter().any(|config| name == *config)
+                            })
+                    })
+            }
+            // The events may be lost, so the program is assumed to be changed.
+            Err(_) => true,
+        };
+        if changed {
+            stale.store(true, Ordering::SeqCst);
+        }
+    })
+    .ok()?;
+    for dir in dirs {
+        watcher.watch(dir, RecursiveMode::NonRecursive).ok()?;
+    }
+    Some(watcher)
+}
+
+/// Returns the cache key of the program built from `args`, which has all the serialized
+/// arguments except the top level `-D` options only used at runtime. Returns `None`
+/// when the program can not be cached e.g., the override AST needs to be printed.
+pub(crate) fn program_cache_key(args: &ExecProgramArgs) -> Option<String> {
+    if args.print_override_ast || args.debug > 0 {
+        return None;
+    }
+    let mut key = serde_json::to_value(args).ok()?;
+    let key_args = key.as_object_mut()?;
+    key_args.remove("args");
+    key_args.insert("load_plugins".to_string(), (args.plugin_agent > 0).into());
+    Some(key.to_string())
+}
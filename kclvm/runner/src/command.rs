@@ -1,3 +1,4 @@
+use kclvm_compiler::codegen::{WASM32_WASI_TARGET, WASM_FILE_SUFFIX};
 use kclvm_utils::path::PathPrefix;
 use std::env::consts::DLL_SUFFIX;
 use std::path::PathBuf;
@@ -5,6 +6,11 @@ use std::path::PathBuf;
 const KCLVM_CLI_BIN_PATH_ENV_VAR: &str = "KCLVM_CLI_BIN_PATH";
 const KCLVM_LIB_LINK_PATH_ENV_VAR: &str = "KCLVM_LIB_LINK_PATH";
 const KCLVM_LIB_SHORT_NAME: &str = "kclvm_cli_cdylib";
+const KCLVM_WASM_LIB_PATH_ENV_VAR: &str = "KCLVM_WASM_LIB_PATH";
+const KCLVM_WASM_LD_ENV_VAR: &str = "KCLVM_WASM_LD";
+/// The runtime functions implemented by the host, which are imported by the WebAssembly module.
+const KCLVM_UNDEFINED_WASM_SYMBOLS: &str =
+    include_str!("../../runtime/src/_kclvm_undefined_wasm.txt");
 
 #[derive(Debug)]
 pub struct Command {
@@ -68,6 +74,46 @@ impl Command {
         path.adjust_canonicalization()
     }
 
+    /// Link the wasm32-wasi object files and the wasm32-wasi runtime static library
+    /// into a WASI module using wasm-ld.
+    pub(crate) fn link_wasm_with_lld(&mut self, libs: &[String], wasm_path: &str) -> String {
+        let wasm_path = if wasm_path.ends_with(WASM_FILE_SUFFIX) {
+            wasm_path.to_string()
+        } else {
+            format!("{}{}", wasm_path, WASM_FILE_SUFFIX)
+        };
+        let undefined_file = format!("{}.undefined", wasm_path);
+        std::fs::write(&undefined_file, KCLVM_UNDEFINED_WASM_SYMBOLS)
+            .unwrap_or_else(|err| panic!("{undefined_file} can not be written, details: {err}"));
+        let wasm_ld =
+            std::env::var(KCLVM_WASM_LD_ENV_VAR).unwrap_or_else(|_| "wasm-ld".to_string());
+        let result = std::process::Command::new(&wasm_ld)
+            .args(libs)
+            .arg(format!("-L{}", self.get_wasm_lib_path()))
+            .arg("-lkclvm_runtime")
+            .arg("-lc")
+            .arg(format!("--allow-undefined-file={}", undefined_file))
+            .arg("--export=kclvm_main")
+            .arg("-o")
+            .arg(&wasm_path)
+            .output();
+        let _ = std::fs::remove_file(&undefined_file);
+        let result = result.unwrap_or_else(|err| panic!("run {wasm_ld} failed: {err}"));
+        if !result.status.success() {
+            panic!(
+                "run {} failed: stdout {}, stderr: {}",
+                wasm_ld,
+                String::from_utf8_lossy(&result.stdout),
+                String::from_utf8_lossy(&result.stderr)
+            )
+        }
+        // Use absolute path.
+        let path = PathBuf::from(&wasm_path)
+            .canonicalize()
+            .unwrap_or_else(|_| panic!("{} not found", wasm_path));
+        path.adjust_canonicalization()
+    }
+
     /// Add args for cc.
     pub(crate) fn add_args(
         &self,
@@ -182,6 +228,18 @@ impl Command {
             .unwrap_or(self.executable_root.clone())
     }
 
+    /// Get the path of the wasm32-wasi runtime static library `libkclvm_runtime.a`
+    /// and the WASI libc `libc.a`.
+    pub(crate) fn get_wasm_lib_path(&self) -> String {
+        std::env::var(KCLVM_WASM_LIB_PATH_ENV_VAR).unwrap_or_else(|_| {
+            std::path::Path::new(&self.executable_root)
+                .join("lib")
+                .join(WASM32_WASI_TARGET)
+                .to_string_lossy()
+                .to_string()
+        })
+    }
+
     /// Get KCLVM lib name
     pub(crate) fn get_lib_name() -> String {
         let suffix = Self::get_lib_suffix();
@@ -1,24 +1,39 @@
 use std::{collections::HashMap, path::Path, sync::Arc, time::SystemTime};
 
 use assembler::KclvmLibAssembler;
+use cache::{program_cache_key, CachedProgram, ProgramCache};
 use command::Command;
 use kclvm_ast::{
-    ast::{Module, Program},
+    ast::{Module, OverrideAction, OverrideSpec, Program},
     MAIN_PKG,
 };
+pub use kclvm_compiler::codegen::EmitKind;
+#[cfg(feature = "llvm")]
+use kclvm_compiler::codegen::WASM32_WASI_TARGET;
+use kclvm_compiler::codegen::WASM_FILE_SUFFIX;
 use kclvm_driver::canonicalize_input_files;
 use kclvm_error::{Diagnostic, Handler};
+use kclvm_evaluator::Debugger;
 use kclvm_parser::{load_program, ParseSession};
 use kclvm_query::apply_overrides;
-use kclvm_runtime::{PanicInfo, ValueRef};
-use kclvm_sema::resolver::resolve_program;
+use kclvm_runtime::{
+    kclvm_context_take_last_profile, CommentedYamlPlanner, Context, PanicInfo, ProfileReport,
+    ValueRef, PROFILE_KIND_PHASE,
+};
+use kclvm_sema::resolver::{resolve_program_with_opts, scope::ProgramScope, Options};
+use output::yaml_comments_of_documents;
 pub use runner::ExecProgramArgs;
-use runner::{ExecProgramResult, KclvmRunner, KclvmRunnerOptions};
+use runner::{
+    ExecProgramResult, FastRunner, JitRunner, KclvmRunner, KclvmRunnerOptions, RunOutput,
+    WasmRunner,
+};
 use tempfile::tempdir;
 
 pub mod assembler;
+pub mod cache;
 pub mod command;
 pub mod linker;
+pub mod output;
 pub mod runner;
 
 #[cfg(test)]
@@ -50,7 +65,9 @@ pub mod tests;
 ///
 /// At last, KclvmRunner will be constructed and call method "run" to execute the kcl program.
 ///
-/// **Note that it is not thread safe.**
+/// When "args.jit" is set, the program is compiled and executed in memory by JitRunner
+/// without any temp files, and when "args.fast_eval" is set, the program is evaluated
+/// by the AST evaluator FastRunner without the LLVM backend.
 ///
 /// # Examples
 ///
@@ -73,6 +90,219 @@ pub fn exec_program(
     sess: Arc<ParseSession>,
     args: &ExecProgramArgs,
 ) -> Result<ExecProgramResult, String> {
+    let mut profile = args.profile.then(ProfileReport::default);
+    let mut program = load_program_with_args(sess.clone(), args, &mut profile)?;
+    let start_time = SystemTime::now();
+    let scope = resolve_program_with_args(sess, &mut program, args, &mut profile);
+    let exec_result = match &scope {
+        Ok(scope) => execute_resolved_to_output(&program, scope, args, &mut profile),
+        Err(err) => Err(err.clone()),
+    };
+    build_exec_result(exec_result, start_time, args, scope.as_ref().ok(), profile)
+}
+
+/// Execute the KCL program like [`exec_program`], but the parsed and resolved program
+/// and the linked library are reused from `cache` until any of the program source files
+/// is changed, which is used by the long-running server to serve repeated executions.
+///
+/// # Examples
+///
+/// ```
+/// use kclvm_runner::{cache::ProgramCache, exec_program_with_cache, ExecProgramArgs};
+/// use kclvm_parser::ParseSession;
+/// use std::sync::Arc;
+///
+/// let cache = ProgramCache::default();
+/// let mut args = ExecProgramArgs::default();
+/// args.k_filename_list = vec!["./src/test_datas/init_check_order_0/main.k".to_string()];
+///
+/// exec_program_with_cache(Arc::new(ParseSession::default()), &args, &cache).unwrap();
+/// // The second execution reuses the cached program and the linked library.
+/// let result = exec_program_with_cache(Arc::new(ParseSession::default()), &args, &cache).unwrap();
+/// assert_eq!(cache.len(), 1);
+/// ```
+pub fn exec_program_with_cache(
+    sess: Arc<ParseSession>,
+    args: &ExecProgramArgs,
+    cache: &ProgramCache,
+) -> Result<ExecProgramResult, String> {
+    let key = match program_cache_key(args) {
+        Some(key) => key,
+        None => return exec_program(sess, args),
+    };
+    let mut profile = args.profile.then(ProfileReport::default);
+    let cached = match cache.get(&key) {
+        Some(cached) => cached,
+        None => {
+            let loaded_at = SystemTime::now();
+            let mut program = load_program_with_args(sess.clone(), args, &mut profile)?;
+            let scope = resolve_program_with_args(sess, &mut program, args, &mut profile)?;
+            cache.insert(key, CachedProgram::new(program, scope, loaded_at))
+        }
+    };
+    let start_time = SystemTime::now();
+    let exec_result = if args.fast_eval || args.jit {
+        let (program, scope) = {
+            let cached = cached.lock().unwrap();
+            (cached.program.clone(), cached.runner_scope())
+        };
+        execute_resolved_to_output(&program, &scope, args, &mut profile)
+    } else {
+        // The library is built once under the lock, and run after the lock is released.
+        let lib = cached.lock().unwrap().lib(|program, scope, temp_dir_path| {
+            build_lib(
+                program.clone(),
+                scope.clone(),
+                &temp_file(temp_dir_path),
+                args,
+                KclvmLibAssembler::host(),
+                &mut profile,
+            )
+        });
+        run_lib(&lib.1, args, &mut profile)
+    };
+    // The YAML comments are found in the cached program scope.
+    let cached = args.yaml_comments.then(|| cached.lock().unwrap());
+    build_exec_result(
+        exec_result,
+        start_time,
+        args,
+        cached.as_ref().map(|cached| &cached.scope),
+        profile,
+    )
+}
+
+/// Execute the KCL program like [`exec_program`] with the AST evaluator, and `debugger`
+/// is notified before every statement is evaluated, which is used by the debug adapter
+/// to stop at breakpoints and inspect the variables.
+pub fn exec_program_with_debugger(
+    sess: Arc<ParseSession>,
+    args: &ExecProgramArgs,
+    debugger: &dyn Debugger,
+) -> Result<ExecProgramResult, String> {
+    let mut program = load_program_with_args(sess.clone(), args, &mut None)?;
+    let scope = resolve_program_with_args(sess, &mut program, args, &mut None)?;
+    let start_time = SystemTime::now();
+    let runner = FastRunner::new(Some(KclvmRunnerOptions {
+        plugin_agent_ptr: args.plugin_agent,
+    }));
+    let exec_result = runner
+        .run_with_debugger(&program, &scope, args, Some(debugger))
+        .map_err(emit_runtime_error);
+    build_exec_result(exec_result, start_time, args, Some(&scope), None)
+}
+
+/// Compile the KCL program ahead of time into a shared library artifact at `output`,
+/// and return the artifact path. The overrides in `args` are applied before the program
+/// is compiled, and the artifact can be executed by [`exec_artifact`] many times with
+/// different arguments without recompiling.
+///
+/// # Examples
+///
+/// ```
+/// use kclvm_runner::{build_program, exec_artifact, ExecProgramArgs};
+/// use kclvm_parser::ParseSession;
+/// use std::sync::Arc;
+///
+/// let mut args = ExecProgramArgs::default();
+/// args.k_filename_list = vec!["./src/test_datas/init_check_order_0/main.k".to_string()];
+/// let output = std::env::temp_dir().join("init_check_order_0.so");
+/// let sess = Arc::new(ParseSession::default());
+/// let artifact = build_program(sess, &args, output.to_str().unwrap()).unwrap();
+///
+/// // Execute the artifact without the input files.
+/// let result = exec_artifact(&artifact, &ExecProgramArgs::default()).unwrap();
+/// ```
+pub fn build_program(
+    sess: Arc<ParseSession>,
+    args: &ExecProgramArgs,
+    output: &str,
+) -> Result<String, String> {
+    let assembler = lib_assembler_of_target(&args.target)?;
+    let mut program = load_program_with_args(sess.clone(), args, &mut None)?;
+    let scope = resolve_program_with_args(sess, &mut program, args, &mut None)?;
+    // Create a temp entry file and the temp dir will be delete automatically
+    let temp_dir = tempdir().map_err(|err| err.to_string())?;
+    let temp_dir_path = temp_dir.path().to_str().unwrap();
+    let temp_entry_file = temp_file(temp_dir_path);
+    let lib_path = match assembler {
+        #[cfg(feature = "llvm")]
+        KclvmLibAssembler::WASM => build_wasm_module(program, scope, &temp_entry_file, args),
+        assembler => build_lib(program, scope, &temp_entry_file, args, assembler, &mut None),
+    };
+    let result = std::fs::copy(&lib_path, output)
+        .map(|_| output.to_string())
+        .map_err(|err| format!("failed to write the artifact {output}, details: {err}"));
+    #[cfg(not(target_os = "windows"))]
+    remove_file(&lib_path);
+    result
+}
+
+/// Emit the generated code of each package of the program into `output_dir` in
+/// the format of `emit_kind` without linking them, and return the emitted file paths.
+/// It is used to inspect the generated code, e.g., `__main__.ll` for the main package.
+pub fn emit_program(
+    sess: Arc<ParseSession>,
+    args: &ExecProgramArgs,
+    emit_kind: EmitKind,
+    output_dir: &str,
+) -> Result<Vec<String>, String> {
+    let assembler = lib_assembler_of_target(&args.target)?;
+    let mut program = load_program_with_args(sess.clone(), args, &mut None)?;
+    let scope = resolve_program_with_args(sess, &mut program, args, &mut None)?;
+    assembler::KclvmAssembler::new(
+        program,
+        scope,
+        String::default(),
+        assembler,
+        args.get_package_maps_from_external_pkg(),
+        args.opt_level,
+        args.debug_info,
+    )
+    .emit_pkgs(emit_kind, Path::new(output_dir))
+}
+
+/// Get the assembler of the target triple, empty denotes the host target.
+fn lib_assembler_of_target(target: &str) -> Result<KclvmLibAssembler, String> {
+    match target {
+        #[cfg(feature = "llvm")]
+        WASM32_WASI_TARGET => Ok(KclvmLibAssembler::WASM),
+        target if target.is_empty() || target == env!("KCLVM_DEFAULT_TARGET") => {
+            Ok(KclvmLibAssembler::host())
+        }
+        target => Err(format!("unsupported target {target}")),
+    }
+}
+
+/// Execute the artifact compiled by [`build_program`] with the arguments in `args`,
+/// the input files of `args` are ignored.
+///
+/// The program is not recompiled, so the overrides in `args` are applied on the evaluated
+/// value instead of the program, and the values depending on them are not re-evaluated.
+pub fn exec_artifact(path: &str, args: &ExecProgramArgs) -> Result<ExecProgramResult, String> {
+    if !Path::new(path).is_file() {
+        return Err(format!("the artifact {path} is not found"));
+    }
+    let mut profile = args.profile.then(ProfileReport::default);
+    let start_time = SystemTime::now();
+    let exec_result = if path.ends_with(WASM_FILE_SUFFIX) {
+        WasmRunner::new(path)
+            .run(args)
+            .map(RunOutput::from)
+            .map_err(emit_runtime_error)
+    } else {
+        run_lib(path, args, &mut profile)
+    }
+    .and_then(|output| apply_value_overrides(output, &args.overrides));
+    build_exec_result(exec_result, start_time, args, None, profile)
+}
+
+/// Parse the KCL program from the input files and apply the overrides in `args`.
+fn load_program_with_args(
+    sess: Arc<ParseSession>,
+    args: &ExecProgramArgs,
+    profile: &mut Option<ProfileReport>,
+) -> Result<Program, String> {
     // parse args from json string
     let opts = args.get_load_program_options();
     let k_files = &args.k_filename_list;
@@ -81,31 +311,98 @@ pub fn exec_program(
 
     let kcl_paths_str = kcl_paths.iter().map(|s| s.as_str()).collect::<Vec<&str>>();
 
-    let mut program = load_program(sess.clone(), kcl_paths_str.as_slice(), Some(opts))?;
+    let mut program = profile_phase(profile, "parse", || {
+        load_program(sess, kcl_paths_str.as_slice(), Some(opts))
+    })?;
 
-    if let Err(err) = apply_overrides(
-        &mut program,
-        &args.overrides,
-        &[],
-        args.print_override_ast || args.debug > 0,
-    ) {
+    if let Err(err) = profile_phase(profile, "override", || {
+        apply_overrides(
+            &mut program,
+            &args.overrides,
+            &[],
+            args.print_override_ast || args.debug > 0,
+        )
+    }) {
         return Err(err.to_string());
     }
+    Ok(program)
+}
 
-    let start_time = SystemTime::now();
-    let exec_result = execute(sess, program, args);
+/// Run the execution phase `f`, and record its time into `profile` when the
+/// profiling is enabled.
+fn profile_phase<T>(profile: &mut Option<ProfileReport>, name: &str, f: impl FnOnce() -> T) -> T {
+    match profile {
+        Some(profile) => profile.measure(PROFILE_KIND_PHASE, name, f),
+        None => f(),
+    }
+}
+
+/// Merge the runtime profile report of the last execution into `profile`.
+fn merge_runtime_profile(profile: &mut Option<ProfileReport>, report: Option<ProfileReport>) {
+    if let (Some(profile), Some(report)) = (profile, report) {
+        profile.extend(report);
+    }
+}
+
+/// Apply the overrides on the evaluated value in the runner output. The override
+/// values are parsed like the top level arguments `-D`.
+fn apply_value_overrides(
+    output: RunOutput,
+    overrides: &[OverrideSpec],
+) -> Result<RunOutput, String> {
+    if overrides.is_empty() || output.output.is_empty() {
+        return Ok(output);
+    }
+    let (mut value, manifests) = output.into_value()?;
+    for spec in overrides {
+        if !spec.pkgpath.is_empty() && spec.pkgpath != MAIN_PKG {
+            return Err(format!(
+                "the override of the package {} is not supported by artifacts",
+                spec.pkgpath
+            ));
+        }
+        match spec.action {
+            OverrideAction::CreateOrUpdate => {
+                let field_value = match spec.field_value.as_str() {
+                    "True" => ValueRef::bool(true),
+                    "False" => ValueRef::bool(false),
+                    "None" => ValueRef::none(),
+                    field_value => ValueRef::from_json(&mut Context::new(), field_value)
+                        .unwrap_or_else(|_| ValueRef::str(field_value)),
+                };
+                value.set_by_path(&spec.field_path, &field_value)?
+            }
+            OverrideAction::Delete => value.remove_by_path(&spec.field_path)?,
+        }
+    }
+    Ok(RunOutput {
+        output: value.to_json_string(),
+        value: Some(value),
+        manifests: Some(manifests),
+    })
+}
+
+/// Build the program executing result from the runner output.
+fn build_exec_result(
+    exec_result: Result<RunOutput, String>,
+    start_time: SystemTime,
+    args: &ExecProgramArgs,
+    scope: Option<&ProgramScope>,
+    profile: Option<ProfileReport>,
+) -> Result<ExecProgramResult, String> {
     let escape_time = match SystemTime::now().duration_since(start_time) {
         Ok(dur) => dur.as_secs_f32(),
         Err(err) => return Err(err.to_string()),
     };
     let mut result = ExecProgramResult {
         escaped_time: escape_time.to_string(),
+        profile,
         ..Default::default()
     };
-    // Exec result is a JSON or YAML string.
+    // Exec result is a JSON or YAML string with the optional evaluated value.
     let exec_result = match exec_result {
         Ok(res) => {
-            if res.is_empty() {
+            if res.output.is_empty() {
                 return Ok(result);
             } else {
                 res
@@ -119,18 +416,36 @@ pub fn exec_program(
             }
         }
     };
-    let kcl_val = match ValueRef::from_yaml_stream(&exec_result) {
-        Ok(v) => v,
-        Err(err) => return Err(err.to_string()),
-    };
+    let (kcl_val, manifests) = exec_result.into_value()?;
     // Filter values with the path selector.
     let kcl_val = kcl_val.filter_by_path(&args.path_selector)?;
     // Plan values.
-    let (json_result, yaml_result) = kcl_val.plan(args.sort_keys);
+    let (kcl_val, json_result, yaml_result) = kcl_val.plan_with_value(args.sort_keys);
     result.json_result = json_result;
+    // The documents of the custom manifests and the path selector are not typed by the
+    // variables of the main package, so they have no comments.
+    if let Some(scope) = scope
+        .filter(|_| args.yaml_comments && manifests.is_empty() && args.path_selector.is_empty())
+    {
+        let documents = if kcl_val.is_list() {
+            kcl_val.as_list_ref().values.clone()
+        } else {
+            vec![kcl_val.clone()]
+        };
+        result.yaml_comments = yaml_comments_of_documents(scope, &documents);
+    }
     if !args.disable_yaml_result {
-        result.yaml_result = yaml_result;
+        result.yaml_result = if result.yaml_comments.is_empty() {
+            yaml_result
+        } else {
+            let planner = CommentedYamlPlanner {
+                comments: result.yaml_comments.clone(),
+            };
+            kcl_val.plan_with_planner(&planner, args.sort_keys)?
+        };
     }
+    result.manifests = manifests;
+    result.value = Some(kcl_val);
     Ok(result)
 }
 
@@ -160,8 +475,6 @@ pub fn exec_program(
 ///
 /// At last, KclvmRunner will be constructed and call method "run" to execute the kcl program.
 ///
-/// **Note that it is not thread safe.**
-///
 /// # Examples
 ///
 /// ```
@@ -186,67 +499,187 @@ pub fn exec_program(
 /// ```
 pub fn execute(
     sess: Arc<ParseSession>,
-    mut program: Program,
+    program: Program,
     args: &ExecProgramArgs,
 ) -> Result<String, String> {
+    execute_to_output(sess, program, args, &mut None).map(|output| output.output)
+}
+
+/// Execute the KCL program like [`execute`], and return the raw runner output which
+/// carries the evaluated value when the program is evaluated in the current process.
+fn execute_to_output(
+    sess: Arc<ParseSession>,
+    mut program: Program,
+    args: &ExecProgramArgs,
+    profile: &mut Option<ProfileReport>,
+) -> Result<RunOutput, String> {
+    let scope = resolve_program_with_args(sess, &mut program, args, profile)?;
+    execute_resolved_to_output(&program, &scope, args, profile)
+}
+
+/// Resolve the KCL program and emit the diagnostics to the string error.
+fn resolve_program_with_args(
+    sess: Arc<ParseSession>,
+    program: &mut Program,
+    args: &ExecProgramArgs,
+    profile: &mut Option<ProfileReport>,
+) -> Result<ProgramScope, String> {
     // Resolve ast
-    let scope = resolve_program(&mut program);
+    let scope = profile_phase(profile, "resolve", || {
+        resolve_program_with_opts(
+            program,
+            Options {
+                raise_err: true,
+                config_auto_fix: false,
+                lint_check: true,
+                sandbox: args.sandbox,
+            },
+        )
+    });
     scope.emit_diagnostics_to_string(sess.0.clone())?;
+    Ok(scope)
+}
+
+/// Execute the resolved KCL program with the runner selected by `args`.
+fn execute_resolved_to_output(
+    program: &Program,
+    scope: &ProgramScope,
+    args: &ExecProgramArgs,
+    profile: &mut Option<ProfileReport>,
+) -> Result<RunOutput, String> {
+    // Evaluate the program with the AST evaluator.
+    if args.fast_eval {
+        let runner = FastRunner::new(Some(KclvmRunnerOptions {
+            plugin_agent_ptr: args.plugin_agent,
+        }));
+        let result = profile_phase(profile, "evaluate", || runner.run(program, scope, args));
+        merge_runtime_profile(profile, kclvm_context_take_last_profile());
+        return result.map_err(emit_runtime_error);
+    }
+
+    // Compile and run the program in memory without temp files and dynamic link libraries.
+    if args.jit {
+        let runner = JitRunner::new(Some(KclvmRunnerOptions {
+            plugin_agent_ptr: args.plugin_agent,
+        }));
+        let result = profile_phase(profile, "jit", || runner.run(program, scope, args));
+        merge_runtime_profile(profile, kclvm_context_take_last_profile());
+        return result.map_err(emit_runtime_error);
+    }
 
     // Create a temp entry file and the temp dir will be delete automatically
     let temp_dir = tempdir().unwrap();
     let temp_dir_path = temp_dir.path().to_str().unwrap();
     let temp_entry_file = temp_file(temp_dir_path);
 
+    let lib_path = build_lib(
+        program.clone(),
+        scope.clone(),
+        &temp_entry_file,
+        args,
+        KclvmLibAssembler::host(),
+        profile,
+    );
+    let result = run_lib(&lib_path, args, profile);
+
+    // Clean temp files.
+    // FIXME(issue #346): On windows, sometimes there will be an error that the file cannot be accessed.
+    // Therefore, the function of automatically deleting dll files on windows is temporarily turned off.
+    #[cfg(not(target_os = "windows"))]
+    remove_file(&lib_path);
+    #[cfg(not(target_os = "windows"))]
+    clean_tmp_files(&temp_entry_file, &Command::get_lib_suffix());
+    result
+}
+
+/// Generate and link the libs of the resolved program, and return the linked lib path.
+fn build_lib(
+    program: Program,
+    scope: ProgramScope,
+    temp_entry_file: &str,
+    args: &ExecProgramArgs,
+    assembler: KclvmLibAssembler,
+    profile: &mut Option<ProfileReport>,
+) -> String {
     // Generate libs
+    let lib_paths = profile_phase(profile, "codegen", || {
+        assembler::KclvmAssembler::new(
+            program,
+            scope,
+            temp_entry_file.to_string(),
+            assembler,
+            args.get_package_maps_from_external_pkg(),
+            args.opt_level,
+            args.debug_info,
+        )
+        .gen_libs()
+    });
+
+    // Link libs
+    let lib_suffix = Command::get_lib_suffix();
+    let temp_out_lib_file = format!("{}{}", temp_entry_file, lib_suffix);
+    profile_phase(profile, "link", || {
+        linker::KclvmLinker::link_all_libs(lib_paths, temp_out_lib_file)
+    })
+}
+
+/// Compile the program into a WASI module which is run by [`WasmRunner`].
+#[cfg(feature = "llvm")]
+fn build_wasm_module(
+    program: Program,
+    scope: ProgramScope,
+    temp_entry_file: &str,
+    args: &ExecProgramArgs,
+) -> String {
     let lib_paths = assembler::KclvmAssembler::new(
         program,
         scope,
-        temp_entry_file.clone(),
-        KclvmLibAssembler::LLVM,
+        temp_entry_file.to_string(),
+        KclvmLibAssembler::WASM,
         args.get_package_maps_from_external_pkg(),
+        args.opt_level,
+        args.debug_info,
     )
     .gen_libs();
+    let wasm_path = format!("{}{}", temp_entry_file, WASM_FILE_SUFFIX);
+    linker::KclvmLinker::link_wasm_module(lib_paths, wasm_path)
+}
 
-    // Link libs
-    let lib_suffix = Command::get_lib_suffix();
-    let temp_out_lib_file = format!("{}{}", temp_entry_file, lib_suffix);
-    let lib_path = linker::KclvmLinker::link_all_libs(lib_paths, temp_out_lib_file);
-
-    // Run
+/// Run the linked lib with the KclvmRunner.
+fn run_lib(
+    lib_path: &str,
+    args: &ExecProgramArgs,
+    profile: &mut Option<ProfileReport>,
+) -> Result<RunOutput, String> {
     let runner = KclvmRunner::new(
-        lib_path.as_str(),
+        lib_path,
         Some(KclvmRunnerOptions {
             plugin_agent_ptr: args.plugin_agent,
         }),
     );
-    let result = runner.run(args);
-
-    // Clean temp files.
-    // FIXME(issue #346): On windows, sometimes there will be an error that the file cannot be accessed.
-    // Therefore, the function of automatically deleting dll files on windows is temporarily turned off.
-    #[cfg(not(target_os = "windows"))]
-    remove_file(&lib_path);
-    #[cfg(not(target_os = "windows"))]
-    clean_tmp_files(&temp_entry_file, &lib_suffix);
+    let result = profile_phase(profile, "run", || runner.run(args));
+    if profile.is_some() {
+        merge_runtime_profile(profile, runner.take_profile());
+    }
     // Wrap runtime error into diagnostic style string.
-    result.map_err(|err| {
-        match Handler::default()
-            .add_diagnostic(<PanicInfo as Into<Diagnostic>>::into(PanicInfo::from(err)))
-            .emit_to_string()
-        {
-            Ok(msg) => msg,
-            Err(err) => err.to_string(),
-        }
-    })
+    result.map(RunOutput::from).map_err(emit_runtime_error)
+}
+
+/// Wrap the runtime panic info json string into diagnostic style string.
+fn emit_runtime_error(err: String) -> String {
+    match Handler::default()
+        .add_diagnostic(<PanicInfo as Into<Diagnostic>>::into(PanicInfo::from(err)))
+        .emit_to_string()
+    {
+        Ok(msg) => msg,
+        Err(err) => err.to_string(),
+    }
 }
 
 /// `execute_module` can directly execute the ast `Module`.
 /// `execute_module` constructs `Program` with default pkg name `MAIN_PKG`,
 /// and calls method `execute` with default `plugin_agent` and `ExecProgramArgs`.
 /// For more information, see doc above method `execute`.
-///
-/// **Note that it is not thread safe.**
 pub fn execute_module(mut m: Module) -> Result<String, String> {
     m.pkg = MAIN_PKG.to_string();
 
@@ -10,4 +10,11 @@ impl KclvmLinker {
         // not allow external mounting of the implementation.
         cmd.link_libs_with_cc(&lib_paths, &lib_path)
     }
+
+    /// Link the wasm32-wasi object files generated by the WASM assembler and the
+    /// wasm32-wasi runtime library into a WASI module.
+    pub fn link_wasm_module(lib_paths: Vec<String>, wasm_path: String) -> String {
+        let mut cmd = Command::new();
+        cmd.link_wasm_with_lld(&lib_paths, &wasm_path)
+    }
 }
@@ -0,0 +1,254 @@
+use std::{
+    collections::HashSet,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use kclvm_runtime::{PlanFormat, ValueRef, YamlComments};
+use kclvm_sema::{
+    resolver::scope::{ProgramScope, ScopeObjectKind},
+    ty::{SchemaType, Type, TypeKind},
+};
+
+use crate::runner::ExecProgramResult;
+
+/// The default file name template of the split documents, e.g., `Deployment-nginx`.
+pub const DEFAULT_OUTPUT_TEMPLATE: &str = "{kind}-{metadata.name}";
+
+/// The placeholder of the document index in the file name template.
+const INDEX_PLACEHOLDER: &str = "index";
+
+/// Write each document of the KCL result into a separate file in the output directory
+/// and return the written file paths in the document order.
+///
+/// The file names are rendered from `template`, whose placeholders such as `{kind}` and
+/// `{metadata.name}` are replaced by the document fields and `{index}` is replaced by the
+/// document index. The documents with the same file name are suffixed with `-1`, `-2`, etc.
+/// in order, so the file names are stable as long as the document order is unchanged.
+pub fn write_documents(
+    result: &ExecProgramResult,
+    format: PlanFormat,
+    sort_keys: bool,
+    output_dir: &Path,
+    template: &str,
+) -> Result<Vec<PathBuf>, String> {
+    let documents = result.format_documents(format, sort_keys)?;
+    fs::create_dir_all(output_dir).map_err(|err| {
+        format!(
+            "failed to create the output directory {}: {err}",
+            output_dir.display()
+        )
+    })?;
+    let mut names = HashSet::new();
+    let mut files = vec![];
+    for (index, (document, content)) in documents.iter().enumerate() {
+        let name = document_file_name(document, template, index)?;
+        let mut unique_name = name.clone();
+        let mut suffix = 1;
+        while !names.insert(unique_name.clone()) {
+            unique_name = format!("{name}-{suffix}");
+            suffix += 1;
+        }
+        let file = output_dir.join(format!("{unique_name}.{}", format.extension()));
+        fs::write(&file, format!("{content}\n"))
+            .map_err(|err| format!("failed to write the file {}: {err}", file.display()))?;
+        files.push(file);
+    }
+    Ok(files)
+}
+
+/// Render the file name of the document without the file extension. The missing fields
+/// are rendered as empty strings, and the document index is used when the file name is empty.
+///
+/// # Examples
+///
+/// ```
+/// use kclvm_runner::output::{document_file_name, DEFAULT_OUTPUT_TEMPLATE};
+/// use kclvm_runtime::{Context, ValueRef};
+///
+/// let document = ValueRef::from_json(&mut Context::new(), r#"{"kind": "Service", "metadata": {"name": "nginx"}}"#).unwrap();
+/// assert_eq!(document_file_name(&document, DEFAULT_OUTPUT_TEMPLATE, 0).unwrap(), "Service-nginx");
+/// assert_eq!(document_file_name(&ValueRef::dict(None), DEFAULT_OUTPUT_TEMPLATE, 1).unwrap(), "1");
+/// ```
+pub fn document_file_name(
+    document: &ValueRef,
+    template: &str,
+    index: usize,
+) -> Result<String, String> {
+    let mut name = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        name.push_str(&rest[..start]);
+        let end = match rest[start..].find('}') {
+            Some(end) => start + end,
+            None => {
+                return Err(format!(
+                    "unclosed placeholder in the output template {template}"
+                ))
+            }
+        };
+        let field = rest[start + 1..end].trim();
+        if field == INDEX_PLACEHOLDER {
+            name.push_str(&index.to_string());
+        } else if let Some(value) = document.get_by_path(field) {
+            if !value.is_none_or_undefined() {
+                name.push_str(&value.to_string());
+            }
+        }
+        rest = &rest[end + 1..];
+    }
+    name.push_str(rest);
+    // Keep the file names portable and inside the output directory.
+    let name: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let name = name.trim_matches(|c| matches!(c, '-' | '_' | '.'));
+    if name.is_empty() {
+        Ok(index.to_string())
+    } else {
+        Ok(name.to_string())
+    }
+}
+
+/// Get the YAML comments of the planned documents from the schema docs, the top level
+/// attributes of the documents are typed by the variables of the main package, and the
+/// comment of an attribute is its schema attribute doc or the doc of its schema type.
+///
+/// # Examples
+///
+/// ```no_check
+/// schema Person:
+///     """Person is a human being.
+///
+///     Attributes
+///     ----------
+///     name : str
+///         The full name of the person.
+///     """
+///     name: str
+///
+/// alice = Person {name = "Alice"}
+/// ```
+///
+/// The YAML result with the comments is:
+///
+/// ```yaml
+/// # Person is a human being.
+/// alice:
+///   # The full name of the person.
+///   name: Alice
+/// ```
+pub fn yaml_comments_of_documents(
+    scope: &ProgramScope,
+    documents: &[ValueRef],
+) -> Vec<YamlComments> {
+    let main_scope = match scope.main_scope() {
+        Some(main_scope) => main_scope.borrow(),
+        None => return vec![],
+    };
+    documents
+        .iter()
+        .map(|document| {
+            let mut comments = YamlComments::default();
+            if document.is_config() {
+                for (key, value) in &document.as_dict_ref().values {
+                    let obj = match main_scope.elems.get(key) {
+                        Some(obj) => obj.borrow(),
+                        None => continue,
+                    };
+                    if obj.kind == ScopeObjectKind::Variable {
+                        let attr_comments = yaml_comments_of_value(value, &obj.ty);
+                        if !attr_comments.is_empty() {
+                            comments.attrs.insert(key.to_string(), attr_comments);
+                        }
+                    }
+                }
+            }
+            comments
+        })
+        .collect()
+}
+
+/// Get the YAML comments of the value with its static type, and the value guides the
+/// walking of the recursive schema types.
+fn yaml_comments_of_value(value: &ValueRef, ty: &Type) -> YamlComments {
+    let mut comments = YamlComments::default();
+    match &ty.kind {
+        TypeKind::Schema(schema_ty) if value.is_config() => {
+            if !schema_ty.doc.trim().is_empty() {
+                comments.comment = Some(schema_ty.doc.trim().to_string());
+            }
+            for (key, value) in &value.as_dict_ref().values {
+                let attr_comments = match schema_ty.get_obj_of_attr(key) {
+                    Some(attr) => {
+                        let mut attr_comments = yaml_comments_of_value(value, &attr.ty);
+                        if let Some(doc) = attr.doc.as_ref().filter(|doc| !doc.trim().is_empty()) {
+                            attr_comments.comment = Some(doc.trim().to_string());
+                        }
+                        attr_comments
+                    }
+                    None => match &schema_ty.index_signature {
+                        Some(index_signature) => {
+                            yaml_comments_of_value(value, &index_signature.val_ty)
+                        }
+                        None => continue,
+                    },
+                };
+                if !attr_comments.is_empty() {
+                    comments.attrs.insert(key.to_string(), attr_comments);
+                }
+            }
+        }
+        TypeKind::Dict(_, val_ty) if value.is_config() => {
+            for (key, value) in &value.as_dict_ref().values {
+                let attr_comments = yaml_comments_of_value(value, val_ty);
+                if !attr_comments.is_empty() {
+                    comments.attrs.insert(key.to_string(), attr_comments);
+                }
+            }
+        }
+        TypeKind::List(item_ty) if value.is_list() => {
+            comments.items = value
+                .as_list_ref()
+                .values
+                .iter()
+                .map(|item| yaml_comments_of_value(item, item_ty))
+                .collect();
+        }
+        TypeKind::Union(types) => {
+            if let Some(ty) = union_member_of_value(value, types) {
+                return yaml_comments_of_value(value, ty);
+            }
+        }
+        _ => {}
+    }
+    comments
+}
+
+/// Get the member type of the union type which the value most likely belongs to, and a
+/// config value belongs to the first schema type which has all the keys of the config.
+fn union_member_of_value<'a>(value: &ValueRef, types: &'a [std::rc::Rc<Type>]) -> Option<&'a Type> {
+    let has_all_keys = |schema_ty: &SchemaType| {
+        value
+            .as_dict_ref()
+            .values
+            .keys()
+            .all(|key| schema_ty.get_obj_of_attr(key).is_some())
+    };
+    types
+        .iter()
+        .find(|ty| match &ty.kind {
+            TypeKind::Schema(schema_ty) => value.is_config() && has_all_keys(schema_ty),
+            TypeKind::Dict(..) => value.is_config(),
+            TypeKind::List(_) => value.is_list(),
+            _ => false,
+        })
+        .map(|ty| ty.as_ref())
+}
@@ -1,15 +1,28 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, ffi::CString, os::raw::c_char, panic::AssertUnwindSafe};
 
 use kclvm_ast::ast;
+#[cfg(all(feature = "cranelift", not(feature = "llvm")))]
+use kclvm_compiler::codegen::cranelift::jit_code;
+#[cfg(feature = "llvm")]
+use kclvm_compiler::codegen::llvm::jit_code;
+use kclvm_compiler::optimizer::optimize_program;
 use kclvm_config::{
-    modfile::get_vendor_home,
+    modfile::{get_pkg_root_from_paths, get_vendor_home},
     settings::{SettingsFile, SettingsPathBuf},
 };
+use kclvm_driver::canonicalize_input_files;
+use kclvm_evaluator::{Debugger, Evaluator};
 use kclvm_query::r#override::parse_override_spec;
-use kclvm_runtime::ValueRef;
+use kclvm_runtime::{
+    kclvm_context_delete, kclvm_context_main_begin_hook, kclvm_context_new_with_options,
+    kclvm_plugin_init, mut_ptr_as_ref, ptr_as_ref, CommentedYamlPlanner, Context, ContextLimits,
+    ContextOptions, PlanFormat, ProfileReport, ValueRef, YamlComments,
+};
+use kclvm_sema::resolver::scope::ProgramScope;
 use serde::{Deserialize, Serialize};
 
 const RESULT_SIZE: usize = 2048 * 2048;
+const KCLVM_WASM_RUNTIME_ENV_VAR: &str = "KCLVM_WASM_RUNTIME";
 
 #[allow(non_camel_case_types)]
 pub type kclvm_char_t = i8;
@@ -53,6 +66,46 @@ pub struct ExecProgramArgs {
     // plugin_agent is the address of plugin.
     #[serde(skip)]
     pub plugin_agent: u64,
+    // Whether to evaluate the program with the AST evaluator instead of the LLVM backend.
+    #[serde(default)]
+    pub fast_eval: bool,
+    // Whether to compile and run the program in memory using the LLVM JIT execution engine.
+    #[serde(default)]
+    pub jit: bool,
+    // Wall-clock timeout of the evaluation in milliseconds, 0 denotes no limit.
+    #[serde(default)]
+    pub timeout: u64,
+    // Maximum depth of lambda and schema calls, 0 denotes no limit.
+    #[serde(default)]
+    pub max_call_depth: u64,
+    // Maximum number of allocated values tracked by the runtime context, 0 denotes no limit.
+    #[serde(default)]
+    pub max_objects: u64,
+    // Whether to refuse the plugins and the functions with file system and output side effects.
+    #[serde(default)]
+    pub sandbox: bool,
+    // Whether to record the time of the execution phases, files, schemas and lambdas.
+    #[serde(default)]
+    pub profile: bool,
+    // Optimization level of the LLVM generated code from 0 to 3, 0 denotes no optimization.
+    #[serde(default)]
+    pub opt_level: u32,
+    // Target triple of the artifact compiled ahead of time e.g., `wasm32-wasi`, empty denotes the host target.
+    #[serde(default)]
+    pub target: String,
+    // Whether to emit the DWARF debug info of the KCL sources into the LLVM generated code.
+    #[serde(default)]
+    pub debug_info: bool,
+    // Whether to emit the schema attribute docs as the comments of the YAML result.
+    #[serde(default)]
+    pub yaml_comments: bool,
+    // RFC 3339 time used as the current time of the datetime module for reproducible results,
+    // empty denotes the wall clock time.
+    #[serde(default)]
+    pub frozen_now: String,
+    // Whether to refuse the functions with random results e.g., `uuid.v4` for reproducible results.
+    #[serde(default)]
+    pub deterministic: bool,
 }
 
 impl ExecProgramArgs {
@@ -82,8 +135,130 @@ impl ExecProgramArgs {
 pub struct ExecProgramResult {
     pub json_result: String,
     pub yaml_result: String,
+    /// Per-document YAML manifests produced by `manifests.yaml_stream`.
+    #[serde(default)]
+    pub manifests: Vec<String>,
 
     pub escaped_time: String,
+    /// The evaluated top level value, which has the same content as `json_result`.
+    #[serde(skip)]
+    pub value: Option<ValueRef>,
+    /// The profile report, which is only set when `ExecProgramArgs.profile` is true.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<ProfileReport>,
+    /// The YAML comments of the planned documents, which are only set when
+    /// `ExecProgramArgs.yaml_comments` is true.
+    #[serde(skip)]
+    pub yaml_comments: Vec<YamlComments>,
+}
+
+impl ExecProgramResult {
+    /// Get the evaluated top level value as a [`serde_json::Value`].
+    pub fn json_value(&self) -> Option<serde_json::Value> {
+        self.value.as_ref().map(|v| v.to_json_value())
+    }
+
+    /// Get the result string in the output format. The YAML format returns `yaml_result`
+    /// which may be the custom manifests output, and other formats plan the evaluated value.
+    pub fn format_result(&self, format: PlanFormat, sort_keys: bool) -> Result<String, String> {
+        match (format, &self.value) {
+            (PlanFormat::Yaml, _) => Ok(self.yaml_result.clone()),
+            (format, Some(value)) => value.plan_to_format(format, sort_keys),
+            (_, None) => Ok("".to_string()),
+        }
+    }
+
+    /// Get the planned documents and their result strings in the output format. The YAML
+    /// format uses the custom manifests of `manifests.yaml_stream` when they are present.
+    pub fn format_documents(
+        &self,
+        format: PlanFormat,
+        sort_keys: bool,
+    ) -> Result<Vec<(ValueRef, String)>, String> {
+        let documents = match &self.value {
+            Some(value) if value.is_list() => value.as_list_ref().values.clone(),
+            Some(value) => vec![value.clone()],
+            None => vec![],
+        };
+        if format == PlanFormat::Yaml && self.manifests.len() == documents.len() {
+            return Ok(documents
+                .into_iter()
+                .zip(&self.manifests)
+                .map(|(document, manifest)| {
+                    let manifest = manifest.strip_suffix('\n').unwrap_or(manifest);
+                    (document, manifest.to_string())
+                })
+                .collect());
+        }
+        documents
+            .into_iter()
+            .enumerate()
+            .map(|(i, document)| {
+                let content = match self.yaml_comments.get(i) {
+                    Some(comments) if format == PlanFormat::Yaml => {
+                        let planner = CommentedYamlPlanner {
+                            comments: vec![comments.clone()],
+                        };
+                        document.plan_with_planner(&planner, sort_keys)?
+                    }
+                    _ => document.plan_to_format(format, sort_keys)?,
+                };
+                Ok((document, content))
+            })
+            .collect()
+    }
+}
+
+/// RunOutput denotes the raw output of a KCL program runner.
+#[derive(Debug, Default, Clone)]
+pub struct RunOutput {
+    /// The planned JSON string or the custom manifests YAML stream.
+    pub output: String,
+    /// The planned top level value, it is set by the runners which evaluate the
+    /// program with the runtime of the current process.
+    pub value: Option<ValueRef>,
+    /// Per-document YAML manifests produced by `manifests.yaml_stream`.
+    pub manifests: Option<Vec<String>>,
+}
+
+impl RunOutput {
+    /// Get the planned top level value and the per-document manifests, the output
+    /// string is decoded only when the runner does not provide the value, i.e., the
+    /// linked dynamic library and the WASI module which own a separate runtime.
+    pub fn into_value(self) -> Result<(ValueRef, Vec<String>), String> {
+        if let Some(value) = self.value {
+            return Ok((value, self.manifests.unwrap_or_default()));
+        }
+        // The planned output is a JSON string and the custom manifests output
+        // is a YAML stream.
+        if let Ok(value) = ValueRef::from_json(&mut Context::new(), &self.output) {
+            return Ok((value, self.manifests.unwrap_or_default()));
+        }
+        let value = ValueRef::from_yaml_stream(&mut Context::new(), &self.output)
+            .map_err(|err| err.to_string())?;
+        let manifests = self.manifests.unwrap_or_else(|| {
+            if value.is_list() {
+                value
+                    .as_list_ref()
+                    .values
+                    .iter()
+                    .map(|v| v.to_yaml_string())
+                    .collect()
+            } else {
+                vec![value.to_yaml_string()]
+            }
+        });
+        Ok((value, manifests))
+    }
+}
+
+impl From<String> for RunOutput {
+    fn from(output: String) -> Self {
+        Self {
+            output,
+            ..Default::default()
+        }
+    }
 }
 
 impl ExecProgramArgs {
@@ -101,6 +276,47 @@ impl ExecProgramArgs {
         self.k_filename_list.iter().map(|s| s.as_str()).collect()
     }
 
+    /// Get the working directory of the execution, which is the current directory
+    /// when `work_dir` is not set.
+    pub fn get_work_dir(&self) -> String {
+        match &self.work_dir {
+            Some(work_dir) if !work_dir.is_empty() => work_dir.clone(),
+            _ => std::env::current_dir()
+                .map(|dir| dir.to_string_lossy().to_string())
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Get the root path of the `kcl.mod` file of the input files, which is used to
+    /// resolve the paths of the `file` system module. It is the directory of the input
+    /// files when there is no `kcl.mod` file, and the working directory when there is
+    /// no input file.
+    pub fn get_module_path(&self) -> String {
+        let work_dir = self.get_work_dir();
+        canonicalize_input_files(&self.k_filename_list, work_dir.clone(), false)
+            .and_then(|paths| get_pkg_root_from_paths(&paths))
+            .ok()
+            .filter(|root| !root.is_empty())
+            .unwrap_or(work_dir)
+    }
+
+    /// Get the [`ContextOptions`] of the runtime context created for the execution.
+    pub fn get_context_options(&self) -> ContextOptions {
+        ContextOptions {
+            limits: ContextLimits {
+                timeout: self.timeout,
+                max_call_depth: self.max_call_depth,
+                max_objects: self.max_objects,
+            },
+            sandbox: self.sandbox,
+            profile: self.profile,
+            module_path: self.get_module_path(),
+            work_dir: self.get_work_dir(),
+            frozen_now: self.frozen_now.clone(),
+            deterministic: self.deterministic,
+        }
+    }
+
     /// Get the [`kclvm_parser::LoadProgramOptions`] from the [`kclvm_runner::ExecProgramArgs`]
     pub fn get_load_program_options(&self) -> kclvm_parser::LoadProgramOptions {
         kclvm_parser::LoadProgramOptions {
@@ -188,14 +404,40 @@ impl KclvmRunner {
 
     pub fn run(&self, args: &ExecProgramArgs) -> Result<String, String> {
         unsafe {
-            Self::lib_kclvm_plugin_init(&self.lib, self.opts.plugin_agent_ptr);
+            Self::lib_kclvm_plugin_init(&self.lib, self.opts.plugin_agent_ptr)?;
             Self::lib_kcl_run(&self.lib, args)
         }
     }
+
+    /// Take the profile report of the last run, the linked dynamic library owns
+    /// a separate runtime, so the report is read from it.
+    pub fn take_profile(&self) -> Option<ProfileReport> {
+        unsafe {
+            let kclvm_context_last_profile_json: libloading::Symbol<
+                unsafe extern "C" fn() -> *const kclvm_char_t,
+            > = self.lib.get(b"kclvm_context_last_profile_json").ok()?;
+            let json = std::ffi::CStr::from_ptr(kclvm_context_last_profile_json());
+            serde_json::from_slice(json.to_bytes()).ok()
+        }
+    }
 }
 
 impl KclvmRunner {
-    unsafe fn lib_kclvm_plugin_init(lib: &libloading::Library, plugin_method_ptr: u64) {
+    /// Get the symbol `name` of the linked library. An artifact built by an older version
+    /// may not have the symbols used by the current runner, so it must be rebuilt.
+    unsafe fn lib_symbol<'lib, T>(
+        lib: &'lib libloading::Library,
+        name: &str,
+    ) -> Result<libloading::Symbol<'lib, T>, String> {
+        lib.get(name.as_bytes()).map_err(|_| {
+            format!("the symbol '{name}' is not found in the library, which may be a stale artifact built by an older version, please rebuild it")
+        })
+    }
+
+    unsafe fn lib_kclvm_plugin_init(
+        lib: &libloading::Library,
+        plugin_method_ptr: u64,
+    ) -> Result<(), String> {
         // get kclvm_plugin_init
         let kclvm_plugin_init: libloading::Symbol<
             unsafe extern "C" fn(
@@ -205,7 +447,7 @@ impl KclvmRunner {
                     kwargs_json: *const i8,
                 ) -> *const i8,
             ),
-        > = lib.get(b"kclvm_plugin_init").unwrap();
+        > = Self::lib_symbol(lib, "kclvm_plugin_init")?;
 
         // get plugin_method
         let plugin_method_ptr = plugin_method_ptr;
@@ -223,112 +465,314 @@ impl KclvmRunner {
 
         // register plugin agent
         kclvm_plugin_init(plugin_method);
+        Ok(())
     }
 
     unsafe fn lib_kcl_run(
         lib: &libloading::Library,
         args: &ExecProgramArgs,
     ) -> Result<String, String> {
-        let kcl_run: libloading::Symbol<
-            unsafe extern "C" fn(
-                kclvm_main_ptr: u64, // main.k => kclvm_main
-                option_len: kclvm_size_t,
-                option_keys: *const *const kclvm_char_t,
-                option_values: *const *const kclvm_char_t,
-                strict_range_check: i32,
-                disable_none: i32,
-                disable_schema_check: i32,
-                list_option_mode: i32,
-                debug_mode: i32,
-                result_buffer_len: kclvm_size_t,
-                result_buffer: *mut kclvm_char_t,
-                warn_buffer_len: kclvm_size_t,
-                warn_buffer: *mut kclvm_char_t,
-            ) -> kclvm_size_t,
-        > = lib.get(b"_kcl_run").unwrap();
-
-        let kclvm_main: libloading::Symbol<u64> = lib.get(b"kclvm_main").unwrap();
+        // The linked dynamic library owns a separate runtime, so the context options
+        // are passed to its entry function instead of the current process runtime.
+        let kcl_run: libloading::Symbol<KclRunFn> = Self::lib_symbol(lib, "_kcl_run_with_options")?;
+
+        let kclvm_main: libloading::Symbol<u64> = Self::lib_symbol(lib, "kclvm_main")?;
         let kclvm_main_ptr = kclvm_main.into_raw().into_raw() as u64;
 
-        let option_len = args.args.len() as kclvm_size_t;
+        kcl_run_with_args(*kcl_run, kclvm_main_ptr, args)
+    }
+}
 
-        let cstr_argv: Vec<_> = args
-            .args
-            .iter()
-            .map(|arg| std::ffi::CString::new(arg.name.as_str()).unwrap())
-            .collect();
+/// The signature of the runtime entry function `_kcl_run_with_options`.
+type KclRunFn = unsafe extern "C" fn(
+    options_json: *const kclvm_char_t,
+    kclvm_main_ptr: u64, // main.k => kclvm_main
+    option_len: kclvm_size_t,
+    option_keys: *const *const kclvm_char_t,
+    option_values: *const *const kclvm_char_t,
+    strict_range_check: i32,
+    disable_none: i32,
+    disable_schema_check: i32,
+    list_option_mode: i32,
+    debug_mode: i32,
+    result_buffer_len: kclvm_size_t,
+    result_buffer: *mut kclvm_char_t,
+    warn_buffer_len: kclvm_size_t,
+    warn_buffer: *mut kclvm_char_t,
+) -> kclvm_size_t;
+
+/// Call the runtime entry function `_kcl_run_with_options` with the KCL main function
+/// address `kclvm_main_ptr` and the execute arguments `args`.
+unsafe fn kcl_run_with_args(
+    kcl_run: KclRunFn,
+    kclvm_main_ptr: u64,
+    args: &ExecProgramArgs,
+) -> Result<String, String> {
+    let options_json = CString::new(serde_json::to_string(&args.get_context_options()).unwrap())
+        .unwrap_or_default();
+    let option_len = args.args.len() as kclvm_size_t;
+
+    let cstr_argv: Vec<_> = args
+        .args
+        .iter()
+        .map(|arg| std::ffi::CString::new(arg.name.as_str()).unwrap())
+        .collect();
+
+    let mut p_argv: Vec<_> = cstr_argv
+        .iter() // do NOT into_iter()
+        .map(|arg| arg.as_ptr())
+        .collect();
+    p_argv.push(std::ptr::null());
+
+    let p: *const *const kclvm_char_t = p_argv.as_ptr();
+    let option_keys = p;
+
+    let cstr_argv: Vec<_> = args
+        .args
+        .iter()
+        .map(|arg| std::ffi::CString::new(arg.value.as_str()).unwrap())
+        .collect();
+
+    let mut p_argv: Vec<_> = cstr_argv
+        .iter() // do NOT into_iter()
+        .map(|arg| arg.as_ptr())
+        .collect();
+    p_argv.push(std::ptr::null());
+
+    let p: *const *const kclvm_char_t = p_argv.as_ptr();
+    let option_values = p;
+
+    let strict_range_check = args.strict_range_check as i32;
+    let disable_none = args.disable_none as i32;
+    let disable_schema_check = 0; // todo
+    let list_option_mode = 0; // todo
+    let debug_mode = args.debug;
+
+    let mut result = vec![0u8; RESULT_SIZE];
+    let result_buffer_len = result.len() as i32 - 1;
+    let result_buffer = result.as_mut_ptr() as *mut i8;
+
+    let mut warn_data = vec![0u8; RESULT_SIZE];
+    let warn_buffer_len = warn_data.len() as i32 - 1;
+    let warn_buffer = warn_data.as_mut_ptr() as *mut i8;
+
+    let n = kcl_run(
+        options_json.as_ptr() as *const kclvm_char_t,
+        kclvm_main_ptr,
+        option_len,
+        option_keys,
+        option_values,
+        strict_range_check,
+        disable_none,
+        disable_schema_check,
+        list_option_mode,
+        debug_mode,
+        result_buffer_len,
+        result_buffer,
+        warn_buffer_len,
+        warn_buffer,
+    );
+
+    if n == 0 {
+        Ok("".to_string())
+    } else if n > 0 {
+        let return_len = n;
+        let s = std::str::from_utf8(&result[0..return_len as usize]).unwrap();
+        wrap_msg_in_result(s)
+    } else {
+        let return_len = 0 - n;
+        let s = std::str::from_utf8(&warn_data[0..return_len as usize]).unwrap();
+        Err(s.to_string())
+    }
+}
 
-        let mut p_argv: Vec<_> = cstr_argv
-            .iter() // do NOT into_iter()
-            .map(|arg| arg.as_ptr())
-            .collect();
-        p_argv.push(std::ptr::null());
+/// FastRunner evaluates the KCL program with the tree-walking AST evaluator
+/// without generating and linking any dynamic link libraries.
+pub struct FastRunner {
+    opts: KclvmRunnerOptions,
+}
 
-        let p: *const *const kclvm_char_t = p_argv.as_ptr();
-        let option_keys = p;
+impl FastRunner {
+    pub fn new(opts: Option<KclvmRunnerOptions>) -> Self {
+        Self {
+            opts: opts.unwrap_or_default(),
+        }
+    }
 
-        let cstr_argv: Vec<_> = args
-            .args
-            .iter()
-            .map(|arg| std::ffi::CString::new(arg.value.as_str()).unwrap())
-            .collect();
+    pub fn run(
+        &self,
+        program: &ast::Program,
+        scope: &ProgramScope,
+        args: &ExecProgramArgs,
+    ) -> Result<RunOutput, String> {
+        self.run_with_debugger(program, scope, args, None)
+    }
 
-        let mut p_argv: Vec<_> = cstr_argv
-            .iter() // do NOT into_iter()
-            .map(|arg| arg.as_ptr())
-            .collect();
-        p_argv.push(std::ptr::null());
-
-        let p: *const *const kclvm_char_t = p_argv.as_ptr();
-        let option_values = p;
-
-        let strict_range_check = args.strict_range_check as i32;
-        let disable_none = args.disable_none as i32;
-        let disable_schema_check = 0; // todo
-        let list_option_mode = 0; // todo
-        let debug_mode = args.debug;
-
-        let mut result = vec![0u8; RESULT_SIZE];
-        let result_buffer_len = result.len() as i32 - 1;
-        let result_buffer = result.as_mut_ptr() as *mut i8;
-
-        let mut warn_data = vec![0u8; RESULT_SIZE];
-        let warn_buffer_len = warn_data.len() as i32 - 1;
-        let warn_buffer = warn_data.as_mut_ptr() as *mut i8;
-
-        let n = kcl_run(
-            kclvm_main_ptr,
-            option_len,
-            option_keys,
-            option_values,
-            strict_range_check,
-            disable_none,
-            disable_schema_check,
-            list_option_mode,
-            debug_mode,
-            result_buffer_len,
-            result_buffer,
-            warn_buffer_len,
-            warn_buffer,
-        );
-
-        if n == 0 {
-            Ok("".to_string())
-        } else if n > 0 {
-            let return_len = n;
-            let s = std::str::from_utf8(&result[0..return_len as usize]).unwrap();
-            wrap_msg_in_result(s)
+    /// Evaluate the program like [`FastRunner::run`], and `debugger` is notified
+    /// before every statement is evaluated.
+    pub fn run_with_debugger(
+        &self,
+        program: &ast::Program,
+        scope: &ProgramScope,
+        args: &ExecProgramArgs,
+        debugger: Option<&dyn Debugger>,
+    ) -> Result<RunOutput, String> {
+        run_in_process(args, self.opts.plugin_agent_ptr, |ctx| {
+            let value = {
+                let mut evaluator = Evaluator::new(program, scope.import_names.clone(), ctx);
+                if let Some(debugger) = debugger {
+                    evaluator = evaluator.with_debugger(debugger);
+                }
+                evaluator.run().unwrap_or_else(|err| panic!("{}", err))
+            };
+            value.plan_value(ctx)
+        })
+    }
+}
+
+/// JitRunner compiles the KCL program in memory using the LLVM JIT execution engine,
+/// or the Cranelift JIT module when the LLVM backend is disabled, and runs it with the
+/// runtime linked in the current process, thus no temp files and dynamic link libraries
+/// are needed.
+pub struct JitRunner {
+    opts: KclvmRunnerOptions,
+}
+
+impl JitRunner {
+    pub fn new(opts: Option<KclvmRunnerOptions>) -> Self {
+        Self {
+            opts: opts.unwrap_or_default(),
+        }
+    }
+
+    pub fn run(
+        &self,
+        program: &ast::Program,
+        scope: &ProgramScope,
+        args: &ExecProgramArgs,
+    ) -> Result<RunOutput, String> {
+        // The program is shared with other runners, so optimize a copy of it.
+        let mut program = program.clone();
+        optimize_program(&mut program);
+        jit_code(
+            &program,
+            scope.import_names.clone(),
+            args.opt_level,
+            |kclvm_main_ptr| {
+                run_in_process(args, self.opts.plugin_agent_ptr, |ctx| unsafe {
+                    let kclvm_main = (&kclvm_main_ptr as *const u64) as *const ()
+                        as *const extern "C" fn(ctx: *mut Context) -> *mut kclvm_value_ref_t;
+                    kclvm_context_main_begin_hook(ctx);
+                    (*kclvm_main)(ctx);
+                    // The main function plans the value before returning its JSON string.
+                    ctx.buffer
+                        .plan_value
+                        .take()
+                        .unwrap_or_else(|| ValueRef::dict(None))
+                })
+            },
+        )
+        .map_err(|err| err.to_string())?
+    }
+}
+
+/// Run `f` with a new runtime context of `args` in the current process, `f` returns the
+/// planned top level value, which is carried in the output without decoding the output
+/// string. Panics in `f` are returned as the panic info JSON string.
+fn run_in_process(
+    args: &ExecProgramArgs,
+    plugin_agent_ptr: u64,
+    f: impl FnOnce(&mut Context) -> ValueRef,
+) -> Result<RunOutput, String> {
+    let ctx = kclvm_context_new_with_options(&args.get_context_options());
+    Context::set_panic_hook();
+    let result = std::panic::catch_unwind(AssertUnwindSafe(|| {
+        unsafe { plugin_init(plugin_agent_ptr) };
+        let ctx = unsafe { mut_ptr_as_ref(ctx) };
+        ctx.cfg.strict_range_check = args.strict_range_check;
+        ctx.cfg.disable_none = args.disable_none;
+        ctx.cfg.debug_mode = args.debug > 0;
+        for arg in &args.args {
+            ctx.builtin_option_init(&arg.name, &arg.value);
+        }
+        let value = f(ctx);
+        let output = ValueRef::str(&value.plan_to_json_string(ctx)).into_raw(ctx);
+        let output = ptr_as_ref(ctx.main_end_hook(output)).as_str();
+        RunOutput {
+            // The custom manifests documents replace the top level value.
+            value: Some(ctx.buffer.custom_manifests_value.take().unwrap_or(value)),
+            output,
+            manifests: ctx.buffer.custom_manifests.clone(),
+        }
+    }));
+    Context::reset_panic_hook();
+    let result = result.map_err(|_| {
+        let ctx = unsafe { mut_ptr_as_ref(ctx) };
+        ctx.set_panic_info();
+        ctx.get_panic_info_json_string()
+    });
+    unsafe { kclvm_context_delete(ctx) };
+    result
+}
+
+/// WasmRunner runs the WASI module compiled from the KCL program with a WASI runtime
+/// command, which is `wasmtime` by default and can be set by the environment variable
+/// `KCLVM_WASM_RUNTIME`. The top level arguments are passed to the module as `-D name=value`.
+pub struct WasmRunner {
+    wasm_path: String,
+}
+
+impl WasmRunner {
+    pub fn new(wasm_path: &str) -> Self {
+        Self {
+            wasm_path: wasm_path.to_string(),
+        }
+    }
+
+    pub fn run(&self, args: &ExecProgramArgs) -> Result<String, String> {
+        let runtime =
+            std::env::var(KCLVM_WASM_RUNTIME_ENV_VAR).unwrap_or_else(|_| "wasmtime".to_string());
+        let mut cmd = std::process::Command::new(&runtime);
+        cmd.arg(&self.wasm_path);
+        for arg in &args.args {
+            cmd.arg("-D").arg(format!("{}={}", arg.name, arg.value));
+        }
+        let output = cmd
+            .output()
+            .map_err(|err| format!("run {runtime} failed: {err}"))?;
+        if output.status.success() {
+            Ok(String::from_utf8_lossy(&output.stdout)
+                .trim_end()
+                .to_string())
         } else {
-            let return_len = 0 - n;
-            let s = std::str::from_utf8(&warn_data[0..return_len as usize]).unwrap();
-            Err(s.to_string())
+            // The module prints the panic info JSON before it traps, and the
+            // trap message of the WASI runtime follows.
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            Err(stderr
+                .lines()
+                .find(|line| line.starts_with('{'))
+                .unwrap_or(&stderr)
+                .to_string())
         }
     }
 }
 
+/// Register the plugin agent address `plugin_agent_ptr` into the runtime
+/// linked in the current process.
+unsafe fn plugin_init(plugin_agent_ptr: u64) {
+    if plugin_agent_ptr > 0 {
+        let plugin_method: extern "C" fn(
+            method: *const i8,
+            args: *const c_char,
+            kwargs: *const c_char,
+        ) -> *const c_char = std::mem::transmute(plugin_agent_ptr);
+        kclvm_plugin_init(plugin_method);
+    }
+}
+
 fn wrap_msg_in_result(msg: &str) -> Result<String, String> {
     // YAML is compatible with JSON. We can use YAML library for result parsing.
-    let kcl_val = match ValueRef::from_yaml_stream(msg) {
+    let kcl_val = match ValueRef::from_yaml_stream(&mut Context::new(), msg) {
         Ok(msg) => msg,
         Err(err) => {
             return Err(err.to_string());
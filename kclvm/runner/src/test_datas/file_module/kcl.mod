@@ -0,0 +1,4 @@
+[package]
+name = "file_module"
+edition = "0.0.1"
+version = "0.0.1"
@@ -1,17 +1,31 @@
 use crate::assembler::clean_path;
 use crate::assembler::KclvmAssembler;
 use crate::assembler::KclvmLibAssembler;
+#[cfg(feature = "llvm")]
 use crate::assembler::LibAssembler;
+use crate::cache::{program_cache_key, ProgramCache};
+#[cfg(feature = "cranelift")]
+use crate::command::Command;
 use crate::exec_program;
+use crate::exec_program_with_cache;
+use crate::output::{write_documents, DEFAULT_OUTPUT_TEMPLATE};
 use crate::temp_file;
+use crate::{build_program, exec_artifact};
+#[cfg(feature = "llvm")]
+use crate::{emit_program, EmitKind};
 use crate::{execute, runner::ExecProgramArgs};
+#[cfg(feature = "cranelift")]
+use crate::{linker::KclvmLinker, runner::KclvmRunner};
+use crate::{FastRunner, JitRunner};
 use anyhow::Context;
 use anyhow::Result;
-use kclvm_ast::ast::{Module, Program};
-use kclvm_compiler::codegen::llvm::OBJECT_FILE_SUFFIX;
+use kclvm_ast::ast::{self, Module, Program};
+use kclvm_compiler::codegen::OBJECT_FILE_SUFFIX;
 use kclvm_config::settings::load_file;
 use kclvm_parser::load_program;
 use kclvm_parser::ParseSession;
+use kclvm_query::r#override::parse_override_spec;
+use kclvm_runtime::PlanFormat;
 use kclvm_sema::resolver::resolve_program;
 use std::fs::create_dir_all;
 use std::path::{Path, PathBuf};
@@ -178,12 +192,13 @@ fn construct_pkg_lib_path(
     suffix: String,
 ) -> Vec<PathBuf> {
     let cache_dir = assembler.construct_cache_dir(&prog.root);
+    let cache_keys = assembler.pkg_cache_keys();
     let mut result = vec![];
     for (pkgpath, _) in &prog.pkgs {
         if pkgpath == "__main__" {
             result.push(PathBuf::from(format!("{}{}", main_path, suffix)));
         } else {
-            result.push(cache_dir.join(format!("{}{}", pkgpath.clone(), suffix)));
+            result.push(cache_dir.join(format!("{}.{}{}", pkgpath, cache_keys[pkgpath], suffix)));
         }
     }
     result
@@ -217,8 +232,10 @@ fn gen_assembler(entry_file: &str, test_kcl_case_path: &str) -> KclvmAssembler {
         prog.clone(),
         scope,
         entry_file.to_string(),
-        KclvmLibAssembler::LLVM,
+        KclvmLibAssembler::host(),
         HashMap::new(),
+        0,
+        false,
     )
 }
 
@@ -248,6 +265,7 @@ fn gen_libs_for_test(entry_file: &str, test_kcl_case_path: &str) {
     assert_eq!(tmp_main_lib_path.exists(), false);
 }
 
+#[cfg(feature = "llvm")]
 fn assemble_lib_for_test(
     entry_file: &str,
     test_kcl_case_path: &str,
@@ -274,6 +292,8 @@ fn assemble_lib_for_test(
         scope.import_names,
         entry_file,
         temp_entry_file_path,
+        0,
+        false,
     )
 }
 
@@ -295,6 +315,175 @@ fn test_kclvm_runner_execute() {
     }
 }
 
+fn test_kclvm_runner_execute_with_jit() {
+    let args = ExecProgramArgs {
+        jit: true,
+        ..Default::default()
+    };
+    for case in TEST_CASES {
+        let kcl_path = &Path::new(&test_case_path())
+            .join(case)
+            .join(KCL_FILE_NAME)
+            .display()
+            .to_string();
+        let expected_path = &Path::new(&test_case_path())
+            .join(case)
+            .join(EXPECTED_JSON_FILE_NAME)
+            .display()
+            .to_string();
+        let program = load_test_program(kcl_path.to_string());
+        let result = execute(Arc::new(ParseSession::default()), program, &args).unwrap();
+        let expected_result = load_expect_file(expected_path.to_string());
+        assert_eq!(expected_result, format_str_by_json(result));
+    }
+}
+
+fn test_kclvm_runner_execute_with_fast_eval() {
+    let args = ExecProgramArgs {
+        fast_eval: true,
+        ..Default::default()
+    };
+    for case in TEST_CASES {
+        let kcl_path = &Path::new(&test_case_path())
+            .join(case)
+            .join(KCL_FILE_NAME)
+            .display()
+            .to_string();
+        let expected_path = &Path::new(&test_case_path())
+            .join(case)
+            .join(EXPECTED_JSON_FILE_NAME)
+            .display()
+            .to_string();
+        let program = load_test_program(kcl_path.to_string());
+        let result = execute(Arc::new(ParseSession::default()), program, &args).unwrap();
+        let expected_result = load_expect_file(expected_path.to_string());
+        assert_eq!(expected_result, format_str_by_json(result));
+    }
+}
+
+/// Run the grammar test cases with the fast evaluator and compare the YAML output
+/// with the stdout golden files.
+fn test_grammar_with_fast_eval() {
+    let grammar_path = Path::new(CARGO_PATH)
+        .join("..")
+        .join("..")
+        .join("test")
+        .join("grammar")
+        .canonicalize()
+        .unwrap();
+    let mut count = 0;
+    for entry in WalkDir::new(grammar_path).sort_by_file_name() {
+        let entry = entry.unwrap();
+        if entry.file_name() != KCL_FILE_NAME {
+            continue;
+        }
+        let case_path = entry.path().parent().unwrap();
+        let golden_file = case_path.join("stdout.golden");
+        // The cases with the command line options and the cases printing messages,
+        // which are not a part of the exec result, are skipped.
+        let has_options = fs::read_to_string(case_path.join("settings.yaml"))
+            .map(|settings| {
+                !settings
+                    .trim()
+                    .trim_start_matches("kcl_options:")
+                    .trim()
+                    .is_empty()
+            })
+            .unwrap_or_default();
+        if !golden_file.exists()
+            || has_options
+            || fs::read_to_string(entry.path()).unwrap().contains("print(")
+        {
+            continue;
+        }
+        let args = ExecProgramArgs {
+            k_filename_list: vec![entry.path().display().to_string()],
+            work_dir: Some(case_path.display().to_string()),
+            fast_eval: true,
+            ..Default::default()
+        };
+        let result = exec_program(Arc::new(ParseSession::default()), &args)
+            .unwrap_or_else(|err| panic!("{}: {err}", case_path.display()));
+        assert_eq!(
+            yaml_stream_value(&result.yaml_result),
+            yaml_stream_value(&fs::read_to_string(golden_file).unwrap()),
+            "{}",
+            case_path.display()
+        );
+        count += 1;
+    }
+    assert!(count > 0, "no grammar test cases are found");
+}
+
+/// Load the YAML stream `yaml` as a JSON value to compare the outputs regardless of the
+/// YAML format, the key order and the number type, e.g., `1.0` and `1`, because the
+/// golden files of the grammar test cases are emitted by the different KCL versions.
+fn yaml_stream_value(yaml: &str) -> serde_json::Value {
+    fn normalize(value: serde_json::Value) -> serde_json::Value {
+        match value {
+            serde_json::Value::Number(n) => serde_json::json!(n.as_f64()),
+            serde_json::Value::Array(values) => {
+                serde_json::Value::Array(values.into_iter().map(normalize).collect())
+            }
+            serde_json::Value::Object(values) => serde_json::Value::Object(
+                values
+                    .into_iter()
+                    .map(|(key, value)| (key, normalize(value)))
+                    .collect(),
+            ),
+            value => value,
+        }
+    }
+    let value = kclvm_runtime::ValueRef::from_yaml_stream(&mut kclvm_runtime::Context::new(), yaml)
+        .unwrap();
+    let json = value.to_json_string_with_option(&kclvm_runtime::JsonEncodeOptions {
+        sort_keys: true,
+        ..Default::default()
+    });
+    normalize(serde_json::from_str(&json).unwrap())
+}
+
+#[cfg(feature = "cranelift")]
+fn test_kclvm_runner_execute_with_cranelift() {
+    for case in TEST_CASES {
+        let kcl_path = &Path::new(&test_case_path())
+            .join(case)
+            .join(KCL_FILE_NAME)
+            .display()
+            .to_string();
+        let expected_path = &Path::new(&test_case_path())
+            .join(case)
+            .join(EXPECTED_JSON_FILE_NAME)
+            .display()
+            .to_string();
+        let temp_dir = tempdir().unwrap();
+        let temp_entry_file = temp_file(temp_dir.path().to_str().unwrap());
+        let mut program = parse_program(kcl_path);
+        let scope = resolve_program(&mut program);
+        // Generate the object files using Cranelift, link and execute them.
+        let lib_paths = KclvmAssembler::new(
+            program,
+            scope,
+            temp_entry_file.clone(),
+            KclvmLibAssembler::Cranelift,
+            HashMap::new(),
+            0,
+            false,
+        )
+        .gen_libs();
+        let lib_path = KclvmLinker::link_all_libs(
+            lib_paths,
+            format!("{}{}", temp_entry_file, Command::get_lib_suffix()),
+        );
+        let result = KclvmRunner::new(&lib_path, None)
+            .run(&ExecProgramArgs::default())
+            .unwrap();
+        let expected_result = load_expect_file(expected_path.to_string());
+        assert_eq!(expected_result, format_str_by_json(result));
+    }
+}
+
+#[cfg(feature = "llvm")]
 #[test]
 fn test_assemble_lib_llvm() {
     for case in TEST_CASES {
@@ -340,6 +529,39 @@ fn test_gen_libs() {
     }
 }
 
+#[test]
+fn test_gen_libs_evict_superseded_caches() {
+    let temp_dir = tempdir().unwrap();
+    let temp_dir_path = temp_dir.path().to_str().unwrap();
+    let temp_entry_file = format!("{}{}", temp_file(temp_dir_path), "4evict");
+    let kcl_path = gen_full_path(
+        Path::new(&test_case_path())
+            .join("multi_file_compilation")
+            .join("import_regular_module")
+            .join(KCL_FILE_NAME)
+            .display()
+            .to_string(),
+    )
+    .unwrap();
+    let assembler = gen_assembler(&temp_entry_file, &kcl_path);
+    let cache_dir = assembler.load_cache_dir(&parse_program(&kcl_path).root);
+    let cache_key = assembler.pkg_cache_keys()["mymodule"].clone();
+    let superseded_key = "0".repeat(32);
+    let superseded = cache_dir.join(format!("mymodule.{}.o", superseded_key));
+    let superseded_lock = cache_dir.join(format!("mymodule.{}.o.lock", superseded_key));
+    // The caches of the packages not in the program are kept.
+    let other = cache_dir.join(format!("other.{}.o", superseded_key));
+    for path in [&superseded, &superseded_lock, &other] {
+        File::create(path).unwrap();
+    }
+    assembler.gen_libs();
+    assert!(cache_dir.join(format!("mymodule.{}.o", cache_key)).exists());
+    assert!(!superseded.exists());
+    assert!(!superseded_lock.exists());
+    assert!(other.exists());
+    fs::remove_file(other).unwrap();
+}
+
 // Fixme: parallel string/identifier clone panic.
 // #[test]
 fn _test_gen_libs_parallel() {
@@ -391,8 +613,10 @@ fn test_clean_path_for_genlibs() {
         prog,
         scope,
         String::new(),
-        KclvmLibAssembler::LLVM,
+        KclvmLibAssembler::host(),
         HashMap::new(),
+        0,
+        false,
     );
 
     let temp_dir = tempdir().unwrap();
@@ -490,6 +714,606 @@ fn test_custom_manifests_output() {
     exec_with_result_at(&custom_manifests_data_path());
 }
 
+fn test_exec_program_result_value() {
+    let kcl_path = Path::new(&custom_manifests_data_path())
+        .join("list.k")
+        .display()
+        .to_string();
+    for fast_eval in [false, true] {
+        let args = ExecProgramArgs {
+            k_filename_list: vec![kcl_path.clone()],
+            fast_eval,
+            ..Default::default()
+        };
+        let result = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+        assert_eq!(result.manifests.len(), 3);
+        assert_eq!(result.manifests[2], "k5:\n  - 9\n  - 10\n");
+        let expected: serde_json::Value = serde_json::from_str(&result.json_result).unwrap();
+        assert_eq!(result.json_value(), Some(expected));
+    }
+}
+
+fn test_in_process_runner_value() {
+    let kcl_path = Path::new(&custom_manifests_data_path())
+        .join("list.k")
+        .display()
+        .to_string();
+    let args = ExecProgramArgs::default();
+    let mut program = load_test_program(kcl_path);
+    let scope = resolve_program(&mut program);
+    for output in [
+        FastRunner::new(None).run(&program, &scope, &args).unwrap(),
+        JitRunner::new(None).run(&program, &scope, &args).unwrap(),
+    ] {
+        // The evaluated documents are carried without decoding the output string.
+        let value = output.value.unwrap();
+        assert_eq!(value.len(), 3);
+        assert_eq!(
+            value.list_get(2).unwrap().to_json_string(),
+            r#"{"k5": [9, 10]}"#
+        );
+        assert_eq!(output.manifests.unwrap().len(), 3);
+    }
+}
+
+fn test_exec_with_limits() {
+    let kcl_path = |name: &str| {
+        Path::new(&test_case_path())
+            .join("limits")
+            .join(name)
+            .display()
+            .to_string()
+    };
+    for fast_eval in [false, true] {
+        let mut args = ExecProgramArgs {
+            k_filename_list: vec![kcl_path("recursion.k")],
+            fast_eval,
+            max_call_depth: 50,
+            ..Default::default()
+        };
+        let err = exec_program(Arc::new(ParseSession::default()), &args).unwrap_err();
+        assert!(err.contains("maximum recursion depth exceeded"), "{err}");
+        args.max_call_depth = 0;
+        let result = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+        assert_eq!(result.yaml_result, "a: 0");
+
+        let mut args = ExecProgramArgs {
+            k_filename_list: vec![kcl_path("objects.k")],
+            fast_eval,
+            max_objects: 20,
+            ..Default::default()
+        };
+        let err = exec_program(Arc::new(ParseSession::default()), &args).unwrap_err();
+        assert!(
+            err.contains("maximum number of allocated values exceeded"),
+            "{err}"
+        );
+        args.max_objects = 0;
+        let result = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+        assert_eq!(result.yaml_result, "a: 385");
+
+        // The program never finishes in time, so the timeout is always exceeded.
+        let args = ExecProgramArgs {
+            k_filename_list: vec![kcl_path("timeout.k")],
+            fast_eval,
+            timeout: 100,
+            ..Default::default()
+        };
+        let err = exec_program(Arc::new(ParseSession::default()), &args).unwrap_err();
+        assert!(err.contains("execution timeout"), "{err}");
+    }
+}
+
+fn test_exec_with_sandbox() {
+    let kcl_path = Path::new(&test_case_path())
+        .join("sandbox")
+        .join("main.k")
+        .display()
+        .to_string();
+    for fast_eval in [false, true] {
+        let mut args = ExecProgramArgs {
+            k_filename_list: vec![kcl_path.clone()],
+            fast_eval,
+            sandbox: true,
+            ..Default::default()
+        };
+        let err = exec_program(Arc::new(ParseSession::default()), &args).unwrap_err();
+        assert!(
+            err.contains("the function 'print' is not allowed in the sandbox mode"),
+            "{err}"
+        );
+        args.sandbox = false;
+        let result = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+        assert_eq!(result.yaml_result, "a: 1");
+    }
+}
+
+fn test_exec_with_profile() {
+    let kcl_path = Path::new(&test_case_path())
+        .join("profile")
+        .join("main.k")
+        .display()
+        .to_string();
+    for fast_eval in [false, true] {
+        let mut args = ExecProgramArgs {
+            k_filename_list: vec![kcl_path.clone()],
+            fast_eval,
+            profile: true,
+            ..Default::default()
+        };
+        let result = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+        let profile = result.profile.unwrap();
+        let has_record = |kind: &str, name: &str| {
+            profile
+                .records
+                .iter()
+                .any(|r| r.kind == kind && r.name == name)
+        };
+        assert!(has_record("phase", "parse"));
+        assert!(has_record("phase", "resolve"));
+        assert!(has_record("schema", "__main__.Person"));
+        assert!(has_record("check", "__main__.Person"));
+        assert!(profile.records.iter().any(|r| r.kind == "file"));
+        assert!(!profile.events.is_empty());
+        args.profile = false;
+        let result = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+        assert!(result.profile.is_none());
+    }
+}
+
+fn test_exec_with_opt_level() {
+    let kcl_path = Path::new(&test_case_path())
+        .join("profile")
+        .join("main.k")
+        .display()
+        .to_string();
+    for jit in [false, true] {
+        let mut args = ExecProgramArgs {
+            k_filename_list: vec![kcl_path.clone()],
+            jit,
+            ..Default::default()
+        };
+        let expected = exec_program(Arc::new(ParseSession::default()), &args)
+            .unwrap()
+            .yaml_result;
+        for opt_level in 1..=3 {
+            args.opt_level = opt_level;
+            let result = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+            assert_eq!(result.yaml_result, expected);
+        }
+    }
+}
+
+fn test_exec_with_const_fold() {
+    let kcl_path = Path::new(&test_case_path())
+        .join("const_fold")
+        .join("main.k")
+        .display()
+        .to_string();
+    // The AST evaluator runs the program without the optimization.
+    for (fast_eval, jit) in [(true, false), (false, false), (false, true)] {
+        let args = ExecProgramArgs {
+            k_filename_list: vec![kcl_path.clone()],
+            fast_eval,
+            jit,
+            ..Default::default()
+        };
+        let result = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+        assert_eq!(
+            result.yaml_result,
+            "debug: false\nlevel: info\nconfig:\n  name: app-v1\n  replicas: 6\n  labels:\n    env: prod\n    tier: backend\nratio: 4.0\nbig: 2147483648\nflags: 9\nnegative: 2\nmessage: \"hi, kcl\"\nempty: ~"
+        );
+    }
+}
+
+fn test_exec_with_output_dir() {
+    let kcl_path = Path::new(&test_case_path())
+        .join("output_dir")
+        .join("main.k")
+        .display()
+        .to_string();
+    let args = ExecProgramArgs {
+        k_filename_list: vec![kcl_path],
+        ..Default::default()
+    };
+    let result = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    let temp_dir = tempdir().unwrap();
+    let output_dir = temp_dir.path().join("deploy");
+    let files = write_documents(
+        &result,
+        PlanFormat::Yaml,
+        false,
+        &output_dir,
+        DEFAULT_OUTPUT_TEMPLATE,
+    )
+    .unwrap();
+    let names: Vec<String> = files
+        .iter()
+        .map(|f| f.file_name().unwrap().to_string_lossy().to_string())
+        .collect();
+    assert_eq!(
+        names,
+        [
+            "Deployment-nginx.yaml",
+            "Service-nginx.yaml",
+            "Service-nginx-1.yaml",
+            "3.yaml"
+        ]
+    );
+    // The YAML files keep the options of `manifests.yaml_stream`.
+    assert_eq!(
+        fs::read_to_string(output_dir.join("Service-nginx-1.yaml")).unwrap(),
+        "apiVersion: v1\nkind: Service\nmetadata:\n  name: nginx\nspec:\n  ports:\n    - port: 443\n"
+    );
+    let files = write_documents(
+        &result,
+        PlanFormat::Json,
+        false,
+        &output_dir,
+        "{index}-{kind}",
+    )
+    .unwrap();
+    assert_eq!(
+        fs::read_to_string(&files[3]).unwrap(),
+        "{\n    \"message\": \"no kind\"\n}\n"
+    );
+    assert!(output_dir.join("0-Deployment.json").is_file());
+    assert!(
+        write_documents(&result, PlanFormat::Toml, false, &output_dir, "{kind")
+            .unwrap_err()
+            .contains("unclosed placeholder")
+    );
+}
+
+fn test_exec_with_yaml_comments() {
+    let kcl_path = Path::new(&test_case_path())
+        .join("yaml_comments")
+        .join("main.k")
+        .display()
+        .to_string();
+    for fast_eval in [false, true] {
+        let mut args = ExecProgramArgs {
+            k_filename_list: vec![kcl_path.clone()],
+            fast_eval,
+            yaml_comments: true,
+            ..Default::default()
+        };
+        let result = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+        assert_eq!(
+            result.yaml_result,
+            r#"# Server is a backend server.
+server:
+  # The server name.
+  name: web
+  # The ports exposed by the server.
+  ports:
+    # Port is a network port of the server.
+    # The port number.
+    - port: 80
+      # The transport protocol,
+      # TCP by default.
+      protocol: TCP
+    # Port is a network port of the server.
+    # The port number.
+    - port: 443
+      # The transport protocol,
+      # TCP by default.
+      protocol: TCP
+  # The server labels.
+  labels:
+    tier: frontend
+  # Server is a backend server.
+  backup:
+    # The server name.
+    name: web-backup
+    # The server labels.
+    labels: {}
+replicas: 3"#
+        );
+        // The comments are opt-in and the YAML result is the same without them.
+        args.yaml_comments = false;
+        let uncommented = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+        let lines: Vec<&str> = result
+            .yaml_result
+            .lines()
+            .filter(|line| !line.trim_start().starts_with('#'))
+            .collect();
+        assert_eq!(lines.join("\n"), uncommented.yaml_result);
+    }
+}
+
+fn test_exec_with_file_module() {
+    // The input file is relative to the work directory, and the file paths in the
+    // program are relative to the `kcl.mod` root in the parent directory.
+    let work_dir = "src/test_datas/file_module/app";
+    let mut args = ExecProgramArgs {
+        k_filename_list: vec!["main.k".to_string()],
+        work_dir: Some(work_dir.to_string()),
+        ..Default::default()
+    };
+    exec_with_golden(&args, &format!("{work_dir}/main.stdout.golden"));
+    // Reading the files is rejected in the sandbox mode.
+    args.sandbox = true;
+    exec_with_err(
+        &args,
+        "the function 'file.read' is not allowed in the sandbox mode",
+    );
+}
+
+fn test_exec_with_frozen_now() {
+    let case_path = Path::new(&test_case_path()).join("frozen_now");
+    let mut args = ExecProgramArgs {
+        k_filename_list: vec![case_path.join("main.k").display().to_string()],
+        frozen_now: "2023-05-01T08:30:00+08:00".to_string(),
+        ..Default::default()
+    };
+    exec_with_golden(
+        &args,
+        &case_path.join("main.stdout.golden").display().to_string(),
+    );
+    // The frozen now time must be a RFC 3339 time.
+    args.frozen_now = "2023-05-01".to_string();
+    exec_with_err(&args, "invalid frozen now time '2023-05-01'");
+}
+
+fn test_exec_with_uuid() {
+    let case_path = Path::new(&test_case_path()).join("uuid");
+    let mut args = ExecProgramArgs {
+        k_filename_list: vec![case_path.join("main.k").display().to_string()],
+        ..Default::default()
+    };
+    exec_with_golden(
+        &args,
+        &case_path.join("main.stdout.golden").display().to_string(),
+    );
+    // The random UUIDs are not allowed in the deterministic mode.
+    args.deterministic = true;
+    exec_with_err(
+        &args,
+        "the function 'uuid.v4' is not allowed in the deterministic mode",
+    );
+}
+
+fn test_exec_artifact() {
+    let kcl_path = Path::new(&test_case_path())
+        .join("artifact")
+        .join("main.k")
+        .display()
+        .to_string();
+    let temp_dir = tempdir().unwrap();
+    let artifact_path = temp_dir.path().join("artifact.so");
+    let args = ExecProgramArgs {
+        k_filename_list: vec![kcl_path],
+        ..Default::default()
+    };
+    let artifact = build_program(
+        Arc::new(ParseSession::default()),
+        &args,
+        artifact_path.to_str().unwrap(),
+    )
+    .unwrap();
+    assert!(artifact_path.exists());
+
+    // Run the artifact with different arguments without the source files.
+    let mut args = ExecProgramArgs::default();
+    let result = exec_artifact(&artifact, &args).unwrap();
+    assert_eq!(
+        result.yaml_result,
+        "name: app\nreplicas: 1\napp:\n  name: app\n  replicas: 1"
+    );
+    args.args = vec![
+        ast::CmdArgSpec {
+            name: "name".to_string(),
+            value: "\"web\"".to_string(),
+        },
+        ast::CmdArgSpec {
+            name: "replicas".to_string(),
+            value: "3".to_string(),
+        },
+    ];
+    args.overrides = vec![
+        parse_override_spec("app.replicas=5").unwrap(),
+        parse_override_spec("app.labels.tier=frontend").unwrap(),
+        parse_override_spec("name-").unwrap(),
+    ];
+    let result = exec_artifact(&artifact, &args).unwrap();
+    assert_eq!(
+        result.yaml_result,
+        "replicas: 3\napp:\n  name: web\n  replicas: 5\n  labels:\n    tier: frontend"
+    );
+    assert!(exec_artifact(
+        temp_dir.path().join("not_found.so").to_str().unwrap(),
+        &args
+    )
+    .is_err());
+}
+
+#[cfg(feature = "llvm")]
+fn test_emit_program() {
+    let kcl_path = Path::new(&test_case_path())
+        .join("multi_file_compilation")
+        .join("import_regular_module")
+        .join("main.k")
+        .display()
+        .to_string();
+    let args = ExecProgramArgs {
+        k_filename_list: vec![kcl_path],
+        ..Default::default()
+    };
+    for (emit_kind, suffix) in [
+        (EmitKind::LlvmIr, ".ll"),
+        (EmitKind::Bitcode, ".bc"),
+        (EmitKind::Object, OBJECT_FILE_SUFFIX),
+    ] {
+        let temp_dir = tempdir().unwrap();
+        let output_dir = temp_dir.path().to_str().unwrap();
+        let paths = emit_program(
+            Arc::new(ParseSession::default()),
+            &args,
+            emit_kind,
+            output_dir,
+        )
+        .unwrap();
+        // One file for each package without linking.
+        assert_eq!(
+            paths,
+            vec![
+                format!("{}/__main__{}", output_dir, suffix),
+                format!("{}/mymodule{}", output_dir, suffix),
+            ]
+        );
+        assert!(paths.iter().all(|path| Path::new(path).is_file()));
+        if emit_kind == EmitKind::LlvmIr {
+            let main_ir = fs::read_to_string(&paths[0]).unwrap();
+            assert!(main_ir.contains("@kclvm_main"));
+            let module_ir = fs::read_to_string(&paths[1]).unwrap();
+            assert!(module_ir.contains("$mymodule.init"));
+        }
+    }
+}
+
+#[cfg(feature = "llvm")]
+fn test_exec_with_debug_info() {
+    let kcl_path = Path::new(&test_case_path())
+        .join("profile")
+        .join("main.k")
+        .display()
+        .to_string();
+    let mut args = ExecProgramArgs {
+        k_filename_list: vec![kcl_path],
+        ..Default::default()
+    };
+    let expected = exec_program(Arc::new(ParseSession::default()), &args)
+        .unwrap()
+        .yaml_result;
+    args.debug_info = true;
+    let result = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert_eq!(result.yaml_result, expected);
+    let temp_dir = tempdir().unwrap();
+    let output_dir = temp_dir.path().to_str().unwrap();
+    let paths = emit_program(
+        Arc::new(ParseSession::default()),
+        &args,
+        EmitKind::LlvmIr,
+        output_dir,
+    )
+    .unwrap();
+    let main_ir = fs::read_to_string(&paths[0]).unwrap();
+    assert!(main_ir.contains("!DICompileUnit("));
+    assert!(main_ir.contains("!DIFile(filename: \"main.k\""));
+    assert!(main_ir.contains("!DISubprogram(name: \"kclvm_main\""));
+    // The schema attributes are declared as the debug variables.
+    assert!(main_ir.contains("!DILocalVariable(name: \"age\""));
+    assert!(main_ir.contains("!DILocation(line: 8,"));
+}
+
+/// Requires the wasm32-wasi runtime library built by `make -C runtime build-wasm`,
+/// `wasm-ld` and a WASI runtime, run it with `make test-wasm`.
+#[cfg(feature = "llvm")]
+#[test]
+#[ignore]
+fn test_build_wasm_program() {
+    let kcl_path = Path::new(&test_case_path())
+        .join("artifact")
+        .join("main.k")
+        .display()
+        .to_string();
+    let temp_dir = tempdir().unwrap();
+    let wasm_path = temp_dir.path().join("main.wasm");
+    let mut args = ExecProgramArgs {
+        k_filename_list: vec![kcl_path],
+        target: "wasm32-wasi".to_string(),
+        ..Default::default()
+    };
+    let artifact = build_program(
+        Arc::new(ParseSession::default()),
+        &args,
+        wasm_path.to_str().unwrap(),
+    )
+    .unwrap();
+    args.args = vec![ast::CmdArgSpec {
+        name: "name".to_string(),
+        value: "\"web\"".to_string(),
+    }];
+    let result = exec_artifact(&artifact, &args).unwrap();
+    assert_eq!(
+        result.yaml_result,
+        "name: web\nreplicas: 1\napp:\n  name: web\n  replicas: 1\n"
+    );
+    args.target = "riscv64-unknown-elf".to_string();
+    assert!(build_program(
+        Arc::new(ParseSession::default()),
+        &args,
+        wasm_path.to_str().unwrap()
+    )
+    .is_err());
+}
+
+fn test_exec_program_with_cache() {
+    let temp_dir = tempdir().unwrap();
+    let kcl_path = temp_dir.path().join("main.k");
+    fs::write(&kcl_path, "a = 1\n").unwrap();
+    for fast_eval in [false, true] {
+        let cache = ProgramCache::default();
+        let args = ExecProgramArgs {
+            k_filename_list: vec![kcl_path.display().to_string()],
+            fast_eval,
+            ..Default::default()
+        };
+        for _ in 0..2 {
+            let result =
+                exec_program_with_cache(Arc::new(ParseSession::default()), &args, &cache).unwrap();
+            assert_eq!(result.yaml_result, "a: 1");
+            assert_eq!(cache.len(), 1);
+        }
+        // The changed program is loaded again after the change is watched.
+        fs::write(&kcl_path, "a = 10\n").unwrap();
+        let mut result = String::new();
+        for _ in 0..100 {
+            result = exec_program_with_cache(Arc::new(ParseSession::default()), &args, &cache)
+                .unwrap()
+                .yaml_result;
+            if result != "a: 1" {
+                break;
+            }
+            thread::sleep(std::time::Duration::from_millis(50));
+        }
+        assert_eq!(result, "a: 10");
+        assert_eq!(cache.len(), 1);
+        // The arguments except the top level options are in the cache key.
+        let key = program_cache_key(&args).unwrap();
+        let mut other_args = args.clone();
+        other_args.args = vec![ast::CmdArgSpec {
+            name: "a".to_string(),
+            value: "1".to_string(),
+        }];
+        assert_eq!(program_cache_key(&other_args).unwrap(), key);
+        other_args.frozen_now = "2024-01-01T00:00:00Z".to_string();
+        assert_ne!(program_cache_key(&other_args).unwrap(), key);
+        fs::write(&kcl_path, "a = 1\n").unwrap();
+        // The program is stale when a module file is created in the package directory.
+        exec_program_with_cache(Arc::new(ParseSession::default()), &args, &cache).unwrap();
+        let mod_path = temp_dir.path().join("kcl.mod");
+        fs::write(&mod_path, "[package]\n").unwrap();
+        let mut stale = false;
+        for _ in 0..100 {
+            if cache.get(&key).is_none() {
+                stale = true;
+                break;
+            }
+            thread::sleep(std::time::Duration::from_millis(50));
+        }
+        assert!(stale);
+        fs::remove_file(&mod_path).unwrap();
+        // The least recently used program is removed from the full cache.
+        let cache = ProgramCache::with_capacity(1);
+        exec_program_with_cache(Arc::new(ParseSession::default()), &args, &cache).unwrap();
+        exec_program_with_cache(Arc::new(ParseSession::default()), &other_args, &cache).unwrap();
+        assert_eq!(cache.len(), 1);
+        assert!(cache.get(&key).is_none());
+    }
+}
+
 fn test_exec_with_err_result() {
     exec_with_err_result_at(&exec_err_data_path());
 }
@@ -527,11 +1351,77 @@ fn test_exec() {
     test_kclvm_runner_execute();
     println!("test_kclvm_runner_execute - PASS");
 
+    test_kclvm_runner_execute_with_jit();
+    println!("test_kclvm_runner_execute_with_jit - PASS");
+
+    test_kclvm_runner_execute_with_fast_eval();
+    println!("test_kclvm_runner_execute_with_fast_eval - PASS");
+
+    test_grammar_with_fast_eval();
+    println!("test_grammar_with_fast_eval - PASS");
+
+    #[cfg(feature = "cranelift")]
+    {
+        test_kclvm_runner_execute_with_cranelift();
+        println!("test_kclvm_runner_execute_with_cranelift - PASS");
+    }
+
     test_custom_manifests_output();
     println!("test_custom_manifests_output - PASS");
 
+    test_exec_program_result_value();
+    println!("test_exec_program_result_value - PASS");
+
+    test_in_process_runner_value();
+    println!("test_in_process_runner_value - PASS");
+
     test_exec_with_err_result();
     println!("test_exec_with_err_result - PASS");
+
+    test_exec_with_limits();
+    println!("test_exec_with_limits - PASS");
+
+    test_exec_with_sandbox();
+    println!("test_exec_with_sandbox - PASS");
+
+    test_exec_with_profile();
+    println!("test_exec_with_profile - PASS");
+
+    test_exec_with_opt_level();
+    println!("test_exec_with_opt_level - PASS");
+
+    test_exec_with_const_fold();
+    println!("test_exec_with_const_fold - PASS");
+
+    test_exec_with_output_dir();
+    println!("test_exec_with_output_dir - PASS");
+
+    test_exec_with_yaml_comments();
+    println!("test_exec_with_yaml_comments - PASS");
+
+    test_exec_with_file_module();
+    println!("test_exec_with_file_module - PASS");
+
+    test_exec_with_frozen_now();
+    println!("test_exec_with_frozen_now - PASS");
+
+    test_exec_with_uuid();
+    println!("test_exec_with_uuid - PASS");
+
+    test_exec_artifact();
+    println!("test_exec_artifact - PASS");
+
+    #[cfg(feature = "llvm")]
+    {
+        test_emit_program();
+        println!("test_emit_program - PASS");
+
+        test_exec_with_debug_info();
+        println!("test_exec_with_debug_info - PASS");
+    }
+
+    test_exec_program_with_cache();
+    println!("test_exec_program_with_cache - PASS");
 }
 
 fn exec(file: &str) -> Result<String, String> {
@@ -554,21 +1444,53 @@ fn exec_with_result_at(path: &str) {
         args.k_filename_list.push(kcl_file.to_string());
         let result = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
 
-        #[cfg(not(target_os = "windows"))]
-        let newline = "\n";
-        #[cfg(target_os = "windows")]
-        let newline = "\r\n";
+        assert_eq!(result.yaml_result, load_golden_file(output_file));
+    }
+}
 
-        let expected = std::fs::read_to_string(output_file)
-            .unwrap()
-            .strip_suffix(newline)
-            .unwrap()
-            .to_string();
+/// Load the expected output of the golden file without the trailing newline.
+fn load_golden_file(path: &str) -> String {
+    #[cfg(not(target_os = "windows"))]
+    let newline = "\n";
+    #[cfg(target_os = "windows")]
+    let newline = "\r\n";
 
-        #[cfg(target_os = "windows")]
-        let expected = expected.replace("\r\n", "\n");
+    let expected = std::fs::read_to_string(path)
+        .unwrap()
+        .strip_suffix(newline)
+        .unwrap()
+        .to_string();
+
+    #[cfg(target_os = "windows")]
+    let expected = expected.replace("\r\n", "\n");
+
+    expected
+}
+
+/// Run the program with `args` under both the compiled runner and the fast evaluator,
+/// and compare the exec result with the expect output of the golden file.
+fn exec_with_golden(args: &ExecProgramArgs, golden_file: &str) {
+    let expected = load_golden_file(golden_file);
+    for fast_eval in [false, true] {
+        let args = ExecProgramArgs {
+            fast_eval,
+            ..args.clone()
+        };
+        let result = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+        assert_eq!(result.yaml_result, expected, "fast_eval: {fast_eval}");
+    }
+}
 
-        assert_eq!(result.yaml_result, expected);
+/// Run the program with `args` under both the compiled runner and the fast evaluator,
+/// and check the exec error contains the expect message.
+fn exec_with_err(args: &ExecProgramArgs, message: &str) {
+    for fast_eval in [false, true] {
+        let args = ExecProgramArgs {
+            fast_eval,
+            ..args.clone()
+        };
+        let err = exec_program(Arc::new(ParseSession::default()), &args).unwrap_err();
+        assert!(err.contains(message), "fast_eval: {fast_eval}, {err}");
     }
 }
 
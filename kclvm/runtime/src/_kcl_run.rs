@@ -55,17 +55,60 @@ pub unsafe extern "C" fn _kcl_run(
     warn_buffer_len: kclvm_size_t,
     warn_buffer: *mut kclvm_char_t,
 ) -> kclvm_size_t {
-    let ctx = kclvm_context_new();
-
-    let prev_hook = std::panic::take_hook();
-    std::panic::set_hook(Box::new(|info: &std::panic::PanicInfo| {
-        let ctx = Context::current_context_mut();
-        ctx.set_panic_info(info);
-        let _ = ctx;
-    }));
+    _kcl_run_with_options(
+        std::ptr::null(),
+        kclvm_main_ptr,
+        option_len,
+        option_keys,
+        option_values,
+        strict_range_check,
+        disable_none,
+        disable_schema_check,
+        list_option_mode,
+        debug_mode,
+        result_buffer_len,
+        result_buffer,
+        warn_buffer_len,
+        warn_buffer,
+    )
+}
 
-    let result = std::panic::catch_unwind(|| {
+/// Run the main function like `_kcl_run` with the runtime context created with the
+/// [`ContextOptions`] JSON string `options_json`, and the null pointer denotes the
+/// default options.
+#[no_mangle]
+#[runtime_fn]
+pub unsafe extern "C" fn _kcl_run_with_options(
+    options_json: *const kclvm_char_t,
+    kclvm_main_ptr: u64, // main.k => kclvm_main
+    option_len: kclvm_size_t,
+    option_keys: *const *const kclvm_char_t,
+    option_values: *const *const kclvm_char_t,
+    strict_range_check: i32,
+    disable_none: i32,
+    disable_schema_check: i32,
+    list_option_mode: i32,
+    debug_mode: i32,
+    result_buffer_len: kclvm_size_t,
+    result_buffer: *mut kclvm_char_t,
+    warn_buffer_len: kclvm_size_t,
+    warn_buffer: *mut kclvm_char_t,
+) -> kclvm_size_t {
+    let options: Result<ContextOptions, String> = if options_json.is_null() {
+        Ok(ContextOptions::default())
+    } else {
+        serde_json::from_str(c2str(options_json)).map_err(|err| err.to_string())
+    };
+    let ctx = kclvm_context_new_with_options(options.as_ref().unwrap_or(&Default::default()));
+
+    Context::set_panic_hook();
+
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        if let Err(err) = &options {
+            panic!("invalid context options: {err}");
+        }
         _kcl_run_in_closure(
+            ctx,
             kclvm_main_ptr,
             option_len,
             option_keys,
@@ -78,11 +121,11 @@ pub unsafe extern "C" fn _kcl_run(
             result_buffer_len,
             result_buffer,
         )
-    });
-    std::panic::set_hook(prev_hook);
+    }));
+    Context::reset_panic_hook();
     match result {
         Ok(n) => {
-            let json_panic_info = Context::current_context().get_panic_info_json_string();
+            let json_panic_info = mut_ptr_as_ref(ctx).get_panic_info_json_string();
 
             let c_str_ptr = json_panic_info.as_ptr() as *const i8;
             let c_str_len = json_panic_info.len() as i32;
@@ -97,7 +140,9 @@ pub unsafe extern "C" fn _kcl_run(
             n
         }
         Err(_) => {
-            let json_panic_info = Context::current_context().get_panic_info_json_string();
+            let ctx_ref = mut_ptr_as_ref(ctx);
+            ctx_ref.set_panic_info();
+            let json_panic_info = ctx_ref.get_panic_info_json_string();
 
             let c_str_ptr = json_panic_info.as_ptr() as *const i8;
             let c_str_len = json_panic_info.len() as i32;
@@ -121,6 +166,7 @@ pub unsafe extern "C" fn _kcl_run(
 
 #[allow(clippy::too_many_arguments)]
 unsafe fn _kcl_run_in_closure(
+    ctx: *mut kclvm_context_t,
     kclvm_main_ptr: u64, // main.k => kclvm_main
     option_len: kclvm_size_t,
     option_keys: *const *const kclvm_char_t,
@@ -133,8 +179,6 @@ unsafe fn _kcl_run_in_closure(
     result_buffer_len: kclvm_size_t,
     result_buffer: *mut kclvm_char_t,
 ) -> kclvm_size_t {
-    let ctx = kclvm_context_current();
-
     let kclvm_main = (&kclvm_main_ptr as *const u64) as *const ()
         as *const extern "C" fn(ctx: *mut kclvm_context_t) -> *mut kclvm_value_ref_t;
 
@@ -153,7 +197,7 @@ unsafe fn _kcl_run_in_closure(
         }
 
         let value = if kclvm_main.is_null() {
-            kclvm_value_Str(b"{}\0" as *const u8 as *const kclvm_char_t)
+            kclvm_value_Str(ctx, b"{}\0" as *const u8 as *const kclvm_char_t)
         } else {
             kclvm_context_main_begin_hook(ctx);
             let x = (*kclvm_main)(ctx);
@@ -176,3 +220,51 @@ unsafe fn _kcl_run_in_closure(
         return_len
     }
 }
+
+#[cfg(target_arch = "wasm32")]
+extern "C" {
+    /// The main function of the KCL program linked into the WebAssembly module.
+    fn kclvm_main(ctx: *mut kclvm_context_t) -> *mut kclvm_value_ref_t;
+}
+
+/// The entry of the WASI module compiled from a KCL program. It evaluates the program
+/// with the top level arguments `-D name=value` in the command line arguments, and
+/// prints the JSON result to the stdout.
+///
+/// Panics can not be caught on the wasm32 target, so the panic info JSON is printed
+/// to the stderr by the panic hook before the module traps.
+#[cfg(target_arch = "wasm32")]
+#[no_mangle]
+pub unsafe extern "C" fn _start() {
+    let mut options = vec![];
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let option = if arg == "-D" {
+            args.next()
+        } else {
+            arg.strip_prefix("-D").map(|option| option.to_string())
+        };
+        if let Some((name, value)) = option.as_deref().and_then(|o| o.split_once('=')) {
+            options.push((
+                std::ffi::CString::new(name).unwrap(),
+                std::ffi::CString::new(value).unwrap(),
+            ));
+        }
+    }
+    let ctx = kclvm_context_new();
+    // The context is alive until the module exits or traps on the panic.
+    let ctx_addr = ctx as usize;
+    std::panic::set_hook(Box::new(move |info: &std::panic::PanicInfo| {
+        let ctx = mut_ptr_as_ref(ctx_addr as *mut kclvm_context_t);
+        Context::record_panic(info);
+        ctx.set_panic_info();
+        eprintln!("{}", ctx.get_panic_info_json_string());
+    }));
+    for (name, value) in &options {
+        kclvm_builtin_option_init(ctx, name.as_ptr(), value.as_ptr());
+    }
+    kclvm_context_main_begin_hook(ctx);
+    let value = kclvm_context_main_end_hook(ctx, kclvm_main(ctx));
+    println!("{}", ptr_as_ref(value).as_str());
+    kclvm_context_delete(ctx);
+}
@@ -96,13 +96,15 @@ pub enum ApiFunc {
     kclvm_context_args_get,
     kclvm_context_args_set,
     kclvm_context_clear_all_types,
-    kclvm_context_current,
     kclvm_context_delete,
     kclvm_context_invoke,
+    kclvm_context_last_profile_json,
     kclvm_context_main_begin_hook,
     kclvm_context_main_end_hook,
     kclvm_context_new,
     kclvm_context_pkgpath_is_imported,
+    kclvm_context_profile_begin,
+    kclvm_context_profile_end,
     kclvm_context_put_type,
     kclvm_context_set_debug_mode,
     kclvm_context_set_disable_none,
@@ -125,10 +127,18 @@ pub enum ApiFunc {
     kclvm_crypto_sha256,
     kclvm_crypto_sha384,
     kclvm_crypto_sha512,
+    kclvm_datetime_add,
     kclvm_datetime_date,
+    kclvm_datetime_diff,
+    kclvm_datetime_format_duration,
     kclvm_datetime_now,
+    kclvm_datetime_parse_duration,
+    kclvm_datetime_strftime,
+    kclvm_datetime_strptime,
     kclvm_datetime_ticks,
+    kclvm_datetime_to_timezone,
     kclvm_datetime_today,
+    kclvm_datetime_validate,
     kclvm_default_collection_insert_int_pointer,
     kclvm_default_collection_insert_value,
     kclvm_dict_clear,
@@ -149,6 +159,11 @@ pub enum ApiFunc {
     kclvm_dict_update,
     kclvm_dict_update_key_value,
     kclvm_dict_values,
+    kclvm_file_exists,
+    kclvm_file_glob,
+    kclvm_file_modpath,
+    kclvm_file_read,
+    kclvm_file_workdir,
     kclvm_free,
     kclvm_iterator_cur_key,
     kclvm_iterator_cur_value,
@@ -231,6 +246,9 @@ pub enum ApiFunc {
     kclvm_strlen,
     kclvm_testing_arguments,
     kclvm_testing_setting_file,
+    kclvm_toml_decode,
+    kclvm_toml_dump_to_file,
+    kclvm_toml_encode,
     kclvm_type_BoolLit_value,
     kclvm_type_FloatLit_value,
     kclvm_type_IntLit_value,
@@ -262,6 +280,9 @@ pub enum ApiFunc {
     kclvm_units_to_m,
     kclvm_units_to_n,
     kclvm_units_to_u,
+    kclvm_uuid_short_id,
+    kclvm_uuid_v4,
+    kclvm_uuid_v5,
     kclvm_value_Bool,
     kclvm_value_Bool_ptr,
     kclvm_value_Decorator,
@@ -359,6 +380,7 @@ pub enum ApiFunc {
     kclvm_value_union,
     kclvm_value_union_all,
     kclvm_yaml_decode,
+    kclvm_yaml_decode_all,
     kclvm_yaml_dump_to_file,
     kclvm_yaml_encode,
 }
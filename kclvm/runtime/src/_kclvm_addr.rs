@@ -79,15 +79,19 @@ pub fn _kclvm_get_fn_ptr_by_name(name: &str) -> u64 {
         "kclvm_context_args_get" => crate::kclvm_context_args_get as *const () as u64,
         "kclvm_context_args_set" => crate::kclvm_context_args_set as *const () as u64,
         "kclvm_context_clear_all_types" => crate::kclvm_context_clear_all_types as *const () as u64,
-        "kclvm_context_current" => crate::kclvm_context_current as *const () as u64,
         "kclvm_context_delete" => crate::kclvm_context_delete as *const () as u64,
         "kclvm_context_invoke" => crate::kclvm_context_invoke as *const () as u64,
+        "kclvm_context_last_profile_json" => {
+            crate::kclvm_context_last_profile_json as *const () as u64
+        }
         "kclvm_context_main_begin_hook" => crate::kclvm_context_main_begin_hook as *const () as u64,
         "kclvm_context_main_end_hook" => crate::kclvm_context_main_end_hook as *const () as u64,
         "kclvm_context_new" => crate::kclvm_context_new as *const () as u64,
         "kclvm_context_pkgpath_is_imported" => {
             crate::kclvm_context_pkgpath_is_imported as *const () as u64
         }
+        "kclvm_context_profile_begin" => crate::kclvm_context_profile_begin as *const () as u64,
+        "kclvm_context_profile_end" => crate::kclvm_context_profile_end as *const () as u64,
         "kclvm_context_put_type" => crate::kclvm_context_put_type as *const () as u64,
         "kclvm_context_set_debug_mode" => crate::kclvm_context_set_debug_mode as *const () as u64,
         "kclvm_context_set_disable_none" => {
@@ -128,10 +132,20 @@ pub fn _kclvm_get_fn_ptr_by_name(name: &str) -> u64 {
         "kclvm_crypto_sha256" => crate::kclvm_crypto_sha256 as *const () as u64,
         "kclvm_crypto_sha384" => crate::kclvm_crypto_sha384 as *const () as u64,
         "kclvm_crypto_sha512" => crate::kclvm_crypto_sha512 as *const () as u64,
+        "kclvm_datetime_add" => crate::kclvm_datetime_add as *const () as u64,
         "kclvm_datetime_date" => crate::kclvm_datetime_date as *const () as u64,
+        "kclvm_datetime_diff" => crate::kclvm_datetime_diff as *const () as u64,
+        "kclvm_datetime_format_duration" => {
+            crate::kclvm_datetime_format_duration as *const () as u64
+        }
         "kclvm_datetime_now" => crate::kclvm_datetime_now as *const () as u64,
+        "kclvm_datetime_parse_duration" => crate::kclvm_datetime_parse_duration as *const () as u64,
+        "kclvm_datetime_strftime" => crate::kclvm_datetime_strftime as *const () as u64,
+        "kclvm_datetime_strptime" => crate::kclvm_datetime_strptime as *const () as u64,
         "kclvm_datetime_ticks" => crate::kclvm_datetime_ticks as *const () as u64,
+        "kclvm_datetime_to_timezone" => crate::kclvm_datetime_to_timezone as *const () as u64,
         "kclvm_datetime_today" => crate::kclvm_datetime_today as *const () as u64,
+        "kclvm_datetime_validate" => crate::kclvm_datetime_validate as *const () as u64,
         "kclvm_default_collection_insert_int_pointer" => {
             crate::kclvm_default_collection_insert_int_pointer as *const () as u64
         }
@@ -156,6 +170,11 @@ pub fn _kclvm_get_fn_ptr_by_name(name: &str) -> u64 {
         "kclvm_dict_update" => crate::kclvm_dict_update as *const () as u64,
         "kclvm_dict_update_key_value" => crate::kclvm_dict_update_key_value as *const () as u64,
         "kclvm_dict_values" => crate::kclvm_dict_values as *const () as u64,
+        "kclvm_file_exists" => crate::kclvm_file_exists as *const () as u64,
+        "kclvm_file_glob" => crate::kclvm_file_glob as *const () as u64,
+        "kclvm_file_modpath" => crate::kclvm_file_modpath as *const () as u64,
+        "kclvm_file_read" => crate::kclvm_file_read as *const () as u64,
+        "kclvm_file_workdir" => crate::kclvm_file_workdir as *const () as u64,
         "kclvm_free" => crate::kclvm_free as *const () as u64,
         "kclvm_iterator_cur_key" => crate::kclvm_iterator_cur_key as *const () as u64,
         "kclvm_iterator_cur_value" => crate::kclvm_iterator_cur_value as *const () as u64,
@@ -248,6 +267,9 @@ pub fn _kclvm_get_fn_ptr_by_name(name: &str) -> u64 {
         "kclvm_strlen" => crate::kclvm_strlen as *const () as u64,
         "kclvm_testing_arguments" => crate::kclvm_testing_arguments as *const () as u64,
         "kclvm_testing_setting_file" => crate::kclvm_testing_setting_file as *const () as u64,
+        "kclvm_toml_decode" => crate::kclvm_toml_decode as *const () as u64,
+        "kclvm_toml_dump_to_file" => crate::kclvm_toml_dump_to_file as *const () as u64,
+        "kclvm_toml_encode" => crate::kclvm_toml_encode as *const () as u64,
         "kclvm_type_BoolLit_value" => crate::kclvm_type_BoolLit_value as *const () as u64,
         "kclvm_type_FloatLit_value" => crate::kclvm_type_FloatLit_value as *const () as u64,
         "kclvm_type_IntLit_value" => crate::kclvm_type_IntLit_value as *const () as u64,
@@ -279,6 +301,9 @@ pub fn _kclvm_get_fn_ptr_by_name(name: &str) -> u64 {
         "kclvm_units_to_m" => crate::kclvm_units_to_m as *const () as u64,
         "kclvm_units_to_n" => crate::kclvm_units_to_n as *const () as u64,
         "kclvm_units_to_u" => crate::kclvm_units_to_u as *const () as u64,
+        "kclvm_uuid_short_id" => crate::kclvm_uuid_short_id as *const () as u64,
+        "kclvm_uuid_v4" => crate::kclvm_uuid_v4 as *const () as u64,
+        "kclvm_uuid_v5" => crate::kclvm_uuid_v5 as *const () as u64,
         "kclvm_value_Bool" => crate::kclvm_value_Bool as *const () as u64,
         "kclvm_value_Bool_ptr" => crate::kclvm_value_Bool_ptr as *const () as u64,
         "kclvm_value_Decorator" => crate::kclvm_value_Decorator as *const () as u64,
@@ -394,6 +419,7 @@ pub fn _kclvm_get_fn_ptr_by_name(name: &str) -> u64 {
         "kclvm_value_union" => crate::kclvm_value_union as *const () as u64,
         "kclvm_value_union_all" => crate::kclvm_value_union_all as *const () as u64,
         "kclvm_yaml_decode" => crate::kclvm_yaml_decode as *const () as u64,
+        "kclvm_yaml_decode_all" => crate::kclvm_yaml_decode_all as *const () as u64,
         "kclvm_yaml_dump_to_file" => crate::kclvm_yaml_dump_to_file as *const () as u64,
         "kclvm_yaml_encode" => crate::kclvm_yaml_encode as *const () as u64,
         _ => panic!("unknown {name}"),
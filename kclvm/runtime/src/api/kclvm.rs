@@ -2,7 +2,7 @@
 
 #[allow(non_camel_case_types)]
 type kclvm_value_ref_t = crate::ValueRef;
-use crate::{new_mut_ptr, IndexMap};
+use crate::{new_mut_ptr, plugin_handler_fn_ptr, IndexMap, Profiler};
 use indexmap::IndexSet;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
@@ -11,6 +11,7 @@ use std::{
     cmp::Ordering,
     hash::{Hash, Hasher},
     rc::Rc,
+    time::Instant,
 };
 
 #[allow(non_upper_case_globals)]
@@ -199,8 +200,8 @@ impl ValueRef {
         std::ptr::eq(&*self.rc.borrow(), &*x.rc.borrow())
     }
 
-    pub fn into_raw(self) -> *mut Self {
-        new_mut_ptr(self)
+    pub fn into_raw(self, ctx: &mut Context) -> *mut Self {
+        new_mut_ptr(ctx, self)
     }
 
     pub fn from_raw(&self) {
@@ -274,6 +275,9 @@ pub struct FuncValue {
     pub name: String,
     pub runtime_type: String,
     pub is_external: bool,
+    /// Proxy functions represent the saved functions of the runtime itself,
+    /// rather than executing KCL defined functions or plugin functions.
+    pub proxy: Option<u64>,
 }
 
 #[derive(PartialEq, Clone, Default, Debug)]
@@ -330,6 +334,42 @@ pub struct ContextConfig {
     pub disable_none: bool,
     // Whether to output empty list in the plan process.
     pub plan_empty_list: bool,
+    // Whether to refuse the functions with file system and plugin side effects.
+    pub sandbox: bool,
+}
+
+/// Resource limits of the KCL program execution, and zero denotes no limit.
+#[derive(PartialEq, Eq, Clone, Copy, Default, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ContextLimits {
+    /// Wall-clock timeout of the execution in milliseconds.
+    pub timeout: u64,
+    /// Maximum depth of the function and schema calls.
+    pub max_call_depth: u64,
+    /// Maximum number of the allocated values tracked in `Context::objects` and the
+    /// expression values evaluated by the AST evaluator.
+    pub max_objects: u64,
+}
+
+/// Options of the runtime context created for a KCL program execution.
+#[derive(PartialEq, Eq, Clone, Default, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ContextOptions {
+    /// Resource limits of the execution.
+    pub limits: ContextLimits,
+    /// Whether the functions with file system or plugin side effects are forbidden.
+    pub sandbox: bool,
+    /// Whether the files, schemas and lambdas are profiled.
+    pub profile: bool,
+    /// Root path of the `kcl.mod` file used to resolve the paths of the `file` module.
+    pub module_path: String,
+    /// Working directory of the execution.
+    pub work_dir: String,
+    /// RFC 3339 time used as the current time of the `datetime` module, and the empty
+    /// string denotes the wall clock time.
+    pub frozen_now: String,
+    /// Whether the functions with random outputs e.g., `uuid.v4` are forbidden.
+    pub deterministic: bool,
 }
 
 #[derive(PartialEq, Eq, Clone, Debug)]
@@ -337,6 +377,13 @@ pub struct ContextBuffer {
     pub kclvm_context_invoke_result: String,
     /// Custom manifest output string.
     pub custom_manifests_output: Option<String>,
+    /// Custom manifest documents, one YAML string for each document.
+    pub custom_manifests: Option<Vec<String>>,
+    /// Custom manifest value, which is the only document or the list of the documents.
+    pub custom_manifests_value: Option<ValueRef>,
+    /// Planned top level value of the main function, which has the same content as
+    /// the JSON string returned by it.
+    pub plan_value: Option<ValueRef>,
 }
 
 impl Default for ContextBuffer {
@@ -344,6 +391,9 @@ impl Default for ContextBuffer {
         Self {
             kclvm_context_invoke_result: "\0".to_string(),
             custom_manifests_output: None,
+            custom_manifests: None,
+            custom_manifests_value: None,
+            plan_value: None,
         }
     }
 }
@@ -390,6 +440,30 @@ pub struct Context {
     pub buffer: ContextBuffer,
     /// objects is to store all KCL object pointers.
     pub objects: IndexSet<usize>,
+    /// Number of the expression values evaluated by the AST evaluator, which are counted
+    /// into the objects limit but not stored in `objects`.
+    pub evaluated_objects: u64,
+    /// plugin_handler_fn_ptr is the plugin agent function address used by the context.
+    pub plugin_handler_fn_ptr: u64,
+    /// Resource limits of the execution.
+    pub limits: ContextLimits,
+    /// Current depth of the function and schema calls.
+    pub call_depth: u64,
+    /// Start time of the execution used by the timeout limit.
+    pub start_time: Option<Instant>,
+    /// Number of the objects limit checks since the timeout is checked last time.
+    pub timeout_ticks: u64,
+    /// Profiler of the files, schemas and lambdas.
+    pub profiler: Profiler,
+    /// Root path of the `kcl.mod` file used to resolve the paths of the `file` module.
+    pub module_path: String,
+    /// Working directory of the execution.
+    pub work_dir: String,
+    /// RFC 3339 time used as the current time of the `datetime` module, and the empty
+    /// string denotes the wall clock time.
+    pub frozen_now: String,
+    /// Whether the functions with random outputs e.g., `uuid.v4` are forbidden.
+    pub deterministic: bool,
 }
 
 #[derive(PartialEq, Eq, Clone, Debug, Serialize, Deserialize)]
@@ -423,12 +497,29 @@ impl BacktraceFrame {
 
 impl Context {
     pub fn new() -> Self {
+        Self::new_with_options(&ContextOptions::default())
+    }
+
+    /// New a context of an execution with `options`.
+    pub fn new_with_options(options: &ContextOptions) -> Self {
         Context {
             instances: RefCell::new(HashMap::new()),
             panic_info: PanicInfo {
                 kcl_func: "kclvm_main".to_string(),
                 ..Default::default()
             },
+            plugin_handler_fn_ptr: plugin_handler_fn_ptr(),
+            cfg: ContextConfig {
+                sandbox: options.sandbox,
+                ..Default::default()
+            },
+            limits: options.limits,
+            start_time: Some(Instant::now()),
+            profiler: Profiler::new(options.profile),
+            module_path: options.module_path.clone(),
+            work_dir: options.work_dir.clone(),
+            frozen_now: options.frozen_now.clone(),
+            deterministic: options.deterministic,
             ..Default::default()
         }
     }
@@ -1,15 +1,15 @@
 // Copyright 2021 The KCL Authors. All rights reserved.
 #![allow(clippy::missing_safety_doc)]
 
-use crate::{kclvm_value_Undefined, Context, ValueRef};
+use crate::{Context, ValueRef};
 
-/// New a mutable raw pointer.
-pub fn new_mut_ptr(x: ValueRef) -> *mut ValueRef {
+/// New a mutable raw pointer of the value allocated in the runtime context `ctx`.
+pub fn new_mut_ptr(ctx: &mut Context, x: ValueRef) -> *mut ValueRef {
     let ptr = Box::into_raw(Box::new(x));
-    let ctx = Context::current_context_mut();
     // Store the object pointer address to
     // drop it it after execution is complete
     ctx.objects.insert(ptr as usize);
+    ctx.check_objects_limit(0);
     ptr
 }
 
@@ -24,26 +24,14 @@ pub fn free_mut_ptr<T>(p: *mut T) {
 
 /// Convert a const raw pointer to a immutable borrow.
 pub fn ptr_as_ref<'a, T>(p: *const T) -> &'a T {
-    unsafe {
-        if p.is_null() {
-            let v = kclvm_value_Undefined();
-            ptr_as_ref(v as *const T)
-        } else {
-            &*p
-        }
-    }
+    assert!(!p.is_null());
+    unsafe { &*p }
 }
 
 /// Convert a mutable raw pointer to a mutable borrow.
 pub unsafe fn mut_ptr_as_ref<'a, T>(p: *mut T) -> &'a mut T {
     assert!(!p.is_null());
-
-    if p.is_null() {
-        let v = kclvm_value_Undefined();
-        mut_ptr_as_ref(v as *mut T)
-    } else {
-        unsafe { &mut *p }
-    }
+    unsafe { &mut *p }
 }
 
 /// Convert a C str pointer to a Rust &str.
@@ -38,30 +38,32 @@ type kclvm_float_t = f64;
 // new/delete
 // ----------------------------------------------------------------------------
 
-// singleton
-
-#[allow(non_camel_case_types, non_upper_case_globals)]
-static mut _kclvm_context_current: u64 = 0;
+// Each execution creates its own runtime context with `kclvm_context_new`, or
+// `kclvm_context_new_with_options` with the options of the execution, and
+// deletes it with `kclvm_context_delete`. The context pointer is passed to the
+// generated code and all the runtime functions which allocate values or raise
+// errors, so executions in the same or different threads never share a context.
+
+std::thread_local! {
+    // The NUL terminated profile report JSON string of the last deleted context.
+    static KCLVM_CONTEXT_LAST_PROFILE: std::cell::RefCell<String> = std::cell::RefCell::new("\0".to_string());
+}
 
-#[no_mangle]
-#[runtime_fn]
-pub unsafe extern "C" fn kclvm_context_current() -> *mut kclvm_context_t {
-    unsafe {
-        if _kclvm_context_current == 0 {
-            _kclvm_context_current = kclvm_context_new() as u64;
-        }
-        _kclvm_context_current as *mut kclvm_context_t
-    }
+/// Take the profile report of the last deleted runtime context in the current thread.
+pub fn kclvm_context_take_last_profile() -> Option<ProfileReport> {
+    let json = KCLVM_CONTEXT_LAST_PROFILE.with(|profile| profile.replace("\0".to_string()));
+    serde_json::from_str(json.trim_end_matches('\0')).ok()
 }
 
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_context_new() -> *mut kclvm_context_t {
-    let p = Box::into_raw(Box::new(Context::new()));
-    unsafe {
-        _kclvm_context_current = p as u64;
-    }
-    p
+    kclvm_context_new_with_options(&ContextOptions::default())
+}
+
+/// Create a runtime context with the execution `options`.
+pub fn kclvm_context_new_with_options(options: &ContextOptions) -> *mut kclvm_context_t {
+    Box::into_raw(Box::new(Context::new_with_options(options)))
 }
 
 #[no_mangle]
@@ -72,14 +74,43 @@ pub unsafe extern "C" fn kclvm_context_delete(p: *mut kclvm_context_t) {
         let ptr = (*o) as *mut kclvm_value_ref_t;
         kclvm_value_delete(ptr);
     }
-    unsafe {
-        //todo: remove global _kclvm_context_current
-        //set _kclvm_context_current to null to invoid internal unsoundness
-        _kclvm_context_current = 0;
-    }
+    // Keep the profile report after the context is deleted.
+    let profile = if ctx.profiler.enabled {
+        format!(
+            "{}\0",
+            serde_json::to_string(&ctx.profiler.finish()).unwrap()
+        )
+    } else {
+        "\0".to_string()
+    };
+    KCLVM_CONTEXT_LAST_PROFILE.with(|last_profile| *last_profile.borrow_mut() = profile);
     free_mut_ptr(p);
 }
 
+#[no_mangle]
+#[runtime_fn]
+pub unsafe extern "C" fn kclvm_context_last_profile_json() -> *const kclvm_char_t {
+    KCLVM_CONTEXT_LAST_PROFILE.with(|profile| profile.borrow().as_ptr() as *const kclvm_char_t)
+}
+
+#[no_mangle]
+#[runtime_fn]
+pub unsafe extern "C" fn kclvm_context_profile_begin(
+    p: *mut kclvm_context_t,
+    kind: *const kclvm_char_t,
+    name: *const kclvm_char_t,
+) {
+    let p = mut_ptr_as_ref(p);
+    p.profiler.begin(c2str(kind), c2str(name));
+}
+
+#[no_mangle]
+#[runtime_fn]
+pub unsafe extern "C" fn kclvm_context_profile_end(p: *mut kclvm_context_t) {
+    let p = mut_ptr_as_ref(p);
+    p.profiler.end();
+}
+
 // ----------------------------------------------------------------------------
 // main begin/end
 // ----------------------------------------------------------------------------
@@ -135,8 +166,11 @@ pub unsafe extern "C" fn kclvm_context_set_kcl_pkgpath(
 
 #[no_mangle]
 #[runtime_fn]
-pub unsafe extern "C" fn kclvm_context_set_kcl_filename(filename: *const i8) {
-    let p = Context::current_context_mut();
+pub unsafe extern "C" fn kclvm_context_set_kcl_filename(
+    p: *mut kclvm_context_t,
+    filename: *const i8,
+) {
+    let p = mut_ptr_as_ref(p);
     if !filename.is_null() {
         p.set_kcl_filename(c2str(filename));
     }
@@ -144,8 +178,12 @@ pub unsafe extern "C" fn kclvm_context_set_kcl_filename(filename: *const i8) {
 
 #[no_mangle]
 #[runtime_fn]
-pub unsafe extern "C" fn kclvm_context_set_kcl_line_col(line: i32, col: i32) {
-    let p = Context::current_context_mut();
+pub unsafe extern "C" fn kclvm_context_set_kcl_line_col(
+    p: *mut kclvm_context_t,
+    line: i32,
+    col: i32,
+) {
+    let p = mut_ptr_as_ref(p);
     p.set_kcl_line_col(line, col);
 }
 
@@ -329,8 +367,8 @@ pub unsafe extern "C" fn kclvm_context_invoke(
     let p = mut_ptr_as_ref(p);
     let method = c2str(method);
 
-    let args = kclvm_value_from_json(args);
-    let kwargs = kclvm_value_from_json(kwargs);
+    let args = kclvm_value_from_json(p, args);
+    let kwargs = kclvm_value_from_json(p, kwargs);
     let result = _kclvm_context_invoke(p, method, args, kwargs);
 
     p.buffer.kclvm_context_invoke_result = ptr_as_ref(result).to_json_string_with_null();
@@ -369,10 +407,11 @@ unsafe fn _kclvm_context_invoke(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_context_pkgpath_is_imported(
+    ctx: *mut kclvm_context_t,
     pkgpath: *const kclvm_char_t,
 ) -> kclvm_bool_t {
     let pkgpath = c2str(pkgpath);
-    let ctx = Context::current_context_mut();
+    let ctx = mut_ptr_as_ref(ctx);
     let result = ctx.imported_pkgpath.contains(pkgpath);
     ctx.imported_pkgpath.insert(pkgpath.to_string());
     result as kclvm_bool_t
@@ -381,3 +420,198 @@ pub unsafe extern "C" fn kclvm_context_pkgpath_is_imported(
 // ----------------------------------------------------------------------------
 // END
 // ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test_context {
+    use crate::*;
+    use std::panic::AssertUnwindSafe;
+
+    #[test]
+    fn test_context_panic_info() {
+        unsafe {
+            let ctx = kclvm_context_new();
+            Context::set_panic_hook();
+            let result = std::panic::catch_unwind(|| panic!("evaluation failed"));
+            Context::reset_panic_hook();
+            assert!(result.is_err());
+            // The recorded panic is moved into the context which catches it.
+            let ctx = mut_ptr_as_ref(ctx);
+            ctx.set_panic_info();
+            assert_eq!(ctx.panic_info.message, "evaluation failed");
+            assert_eq!(ctx.panic_info.rust_file, file!());
+            ctx.set_panic_info();
+            assert_eq!(ctx.panic_info.message, "");
+            kclvm_context_delete(ctx);
+        }
+    }
+
+    #[test]
+    fn test_context_limits() {
+        unsafe {
+            let ctx = kclvm_context_new_with_options(&ContextOptions {
+                limits: ContextLimits {
+                    timeout: 0,
+                    max_call_depth: 2,
+                    max_objects: 8,
+                },
+                ..Default::default()
+            });
+            let ctx_ref = mut_ptr_as_ref(ctx);
+            assert_panic(
+                "maximum recursion depth exceeded",
+                AssertUnwindSafe(|| {
+                    let ctx = mut_ptr_as_ref(ctx);
+                    ctx.enter_call();
+                    ctx.enter_call();
+                    ctx.enter_call();
+                }),
+            );
+            assert_eq!(
+                ctx_ref.panic_info.err_type_code,
+                ErrType::RecursionError_TYPE as i32
+            );
+            assert_panic(
+                "maximum number of allocated values exceeded",
+                AssertUnwindSafe(|| {
+                    mut_ptr_as_ref(ctx).check_objects_limit(16);
+                }),
+            );
+            assert_eq!(
+                ctx_ref.panic_info.err_type_code,
+                ErrType::EvaluationError_TYPE as i32
+            );
+            kclvm_context_delete(ctx);
+            // The limits are only applied to the context created with them.
+            let ctx = kclvm_context_new();
+            assert_eq!(mut_ptr_as_ref(ctx).limits, ContextLimits::default());
+            kclvm_context_delete(ctx);
+        }
+    }
+
+    #[test]
+    fn test_context_timeout() {
+        unsafe {
+            let ctx = kclvm_context_new_with_options(&ContextOptions {
+                limits: ContextLimits {
+                    timeout: 1,
+                    ..Default::default()
+                },
+                ..Default::default()
+            });
+            std::thread::sleep(std::time::Duration::from_millis(5));
+            // The timeout is checked periodically regardless of the allocated value counts.
+            assert_panic(
+                "execution timeout",
+                AssertUnwindSafe(|| {
+                    for _ in 0..1024 {
+                        mut_ptr_as_ref(ctx).check_objects_limit(3);
+                    }
+                }),
+            );
+            kclvm_context_delete(ctx);
+        }
+    }
+
+    #[test]
+    fn test_context_sandbox() {
+        unsafe {
+            let ctx = kclvm_context_new();
+            mut_ptr_as_ref(ctx).check_sandbox("print");
+            kclvm_context_delete(ctx);
+            let ctx = kclvm_context_new_with_options(&ContextOptions {
+                sandbox: true,
+                ..Default::default()
+            });
+            assert_panic(
+                "the function 'print' is not allowed in the sandbox mode",
+                AssertUnwindSafe(|| {
+                    mut_ptr_as_ref(ctx).check_sandbox("print");
+                }),
+            );
+            kclvm_context_delete(ctx);
+        }
+    }
+
+    #[test]
+    fn test_context_profile() {
+        unsafe {
+            let ctx = kclvm_context_new();
+            kclvm_context_delete(ctx);
+            assert_eq!(kclvm_context_take_last_profile(), None);
+            let ctx = kclvm_context_new_with_options(&ContextOptions {
+                profile: true,
+                ..Default::default()
+            });
+            let profiler = &mut mut_ptr_as_ref(ctx).profiler;
+            profiler.enter_file("main.k");
+            profiler.begin(PROFILE_KIND_SCHEMA, "__main__.Person");
+            profiler.begin(PROFILE_KIND_LAMBDA, "lambda@main.k:1");
+            profiler.end();
+            profiler.end();
+            profiler.begin(PROFILE_KIND_SCHEMA, "__main__.Person");
+            kclvm_context_delete(ctx);
+            let report = kclvm_context_take_last_profile().unwrap();
+            let kinds: Vec<(&str, &str, u64)> = report
+                .records
+                .iter()
+                .map(|r| (r.kind.as_str(), r.name.as_str(), r.count))
+                .collect();
+            assert_eq!(
+                kinds,
+                vec![
+                    (PROFILE_KIND_LAMBDA, "lambda@main.k:1", 1),
+                    (PROFILE_KIND_SCHEMA, "__main__.Person", 2),
+                    (PROFILE_KIND_FILE, "main.k", 1),
+                ]
+            );
+            assert_eq!(report.events.len(), 4);
+            // The last profile is taken only once.
+            assert_eq!(kclvm_context_take_last_profile(), None);
+        }
+    }
+
+    #[test]
+    fn test_profile_span() {
+        unsafe {
+            let ctx = kclvm_context_new();
+            // The disabled profiler does not begin the span nor end the outer spans.
+            drop(ProfileSpan::begin(
+                mut_ptr_as_ref(ctx),
+                PROFILE_KIND_CHECK,
+                || unreachable!(),
+            ));
+            kclvm_context_delete(ctx);
+            let ctx = kclvm_context_new_with_options(&ContextOptions {
+                profile: true,
+                ..Default::default()
+            });
+            let schema_span = ProfileSpan::begin(mut_ptr_as_ref(ctx), PROFILE_KIND_SCHEMA, || {
+                "Person".to_string()
+            });
+            assert_panic(
+                "check failed",
+                AssertUnwindSafe(|| {
+                    let _span = ProfileSpan::begin(mut_ptr_as_ref(ctx), PROFILE_KIND_CHECK, || {
+                        "Person".to_string()
+                    });
+                    panic!("check failed");
+                }),
+            );
+            drop(schema_span);
+            kclvm_context_delete(ctx);
+            let report = kclvm_context_take_last_profile().unwrap();
+            let kinds: Vec<(&str, &str, u64)> = report
+                .records
+                .iter()
+                .map(|r| (r.kind.as_str(), r.name.as_str(), r.count))
+                .collect();
+            assert_eq!(
+                kinds,
+                vec![
+                    (PROFILE_KIND_CHECK, "Person", 1),
+                    (PROFILE_KIND_SCHEMA, "Person", 1),
+                ]
+            );
+        }
+    }
+}
@@ -2,13 +2,71 @@
 
 pub mod api;
 pub use api::*;
+pub mod profile;
+pub use profile::*;
+use std::cell::RefCell;
 use std::fmt;
+use std::sync::{Arc, Mutex};
 
 use crate::{BacktraceFrame, PanicInfo};
 
 #[allow(non_camel_case_types)]
 type kclvm_value_ref_t = crate::ValueRef;
 
+/// The timeout is checked once every `TIMEOUT_CHECK_INTERVAL` objects limit checks.
+const TIMEOUT_CHECK_INTERVAL: u64 = 1024;
+
+type PanicHook = Arc<dyn Fn(&std::panic::PanicInfo) + Sync + Send + 'static>;
+
+/// The running execution count and the previous panic hook. The panic hook which
+/// records the panics of the running executions is set by the first running execution
+/// and the previous hook is restored by the last one, so that concurrent executions in
+/// different threads share the same panic hook.
+static PANIC_HOOK_STATE: Mutex<(usize, Option<PanicHook>)> = Mutex::new((0, None));
+
+/// The panic message and the Rust source location recorded by the panic hook, which
+/// are moved into the runtime context of the execution after the panic is caught.
+#[derive(Debug, Default, Clone)]
+pub struct PanicRecord {
+    pub message: String,
+    pub rust_file: String,
+    pub rust_line: i32,
+    pub rust_col: i32,
+}
+
+impl PanicRecord {
+    fn new(info: &std::panic::PanicInfo) -> Self {
+        let message = if let Some(s) = info.payload().downcast_ref::<&str>() {
+            s.to_string()
+        } else if let Some(s) = info.payload().downcast_ref::<&String>() {
+            (*s).clone()
+        } else if let Some(s) = info.payload().downcast_ref::<String>() {
+            (*s).clone()
+        } else {
+            "".to_string()
+        };
+        match info.location() {
+            Some(location) => Self {
+                message,
+                rust_file: location.file().to_string(),
+                rust_line: location.line() as i32,
+                rust_col: location.column() as i32,
+            },
+            None => Self {
+                message,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+std::thread_local! {
+    /// The running execution count of the current thread and the last panic recorded
+    /// by the panic hook in the current thread. The panics in the threads without
+    /// running executions are reported by the previous panic hook.
+    static PANIC_RECORD: RefCell<(usize, Option<PanicRecord>)> = RefCell::new((0, None));
+}
+
 impl fmt::Display for PanicInfo {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{self:?}")
@@ -80,17 +138,107 @@ impl crate::Context {
         Box::into_raw(Box::new(self))
     }
 
-    pub fn current_context() -> &'static crate::Context {
-        unsafe {
-            let ctx = kclvm_context_current();
-            &*ctx
+    /// Set the panic hook which records the panics of the running executions in the
+    /// current thread, the recorded panic is moved into the runtime context with
+    /// [`Context::set_panic_info`] after it is caught, and the hook must be paired
+    /// with [`Context::reset_panic_hook`].
+    pub fn set_panic_hook() {
+        PANIC_RECORD.with(|record| record.borrow_mut().0 += 1);
+        let mut state = PANIC_HOOK_STATE.lock().unwrap();
+        if state.0 == 0 {
+            let prev_hook: PanicHook = Arc::from(std::panic::take_hook());
+            let hook = prev_hook.clone();
+            std::panic::set_hook(Box::new(move |info: &std::panic::PanicInfo| {
+                if PANIC_RECORD.with(|record| record.borrow().0 > 0) {
+                    crate::Context::record_panic(info);
+                } else {
+                    hook(info);
+                }
+            }));
+            state.1 = Some(prev_hook);
+        }
+        state.0 += 1;
+    }
+
+    /// Restore the previous panic hook when there are no running executions.
+    pub fn reset_panic_hook() {
+        PANIC_RECORD.with(|record| record.borrow_mut().0 -= 1);
+        let mut state = PANIC_HOOK_STATE.lock().unwrap();
+        state.0 -= 1;
+        if state.0 == 0 {
+            if let Some(prev_hook) = state.1.take() {
+                std::panic::set_hook(Box::new(move |info: &std::panic::PanicInfo| {
+                    prev_hook(info)
+                }));
+            }
+        }
+    }
+
+    /// Enter a function or schema call, and raise a `RecursionError` when the
+    /// call depth exceeds the limit.
+    pub fn enter_call(&mut self) {
+        self.call_depth += 1;
+        let max_call_depth = self.limits.max_call_depth;
+        if max_call_depth > 0 && self.call_depth > max_call_depth {
+            self.set_err_type(&crate::ErrType::RecursionError_TYPE);
+            panic!("maximum recursion depth exceeded, the max call depth is {max_call_depth}");
         }
+        self.check_timeout();
     }
 
-    pub fn current_context_mut() -> &'static mut crate::Context {
-        unsafe {
-            let ctx = kclvm_context_current();
-            &mut *ctx
+    /// Leave a function or schema call.
+    pub fn leave_call(&mut self) {
+        self.call_depth = self.call_depth.saturating_sub(1);
+    }
+
+    /// Raise an `EvaluationError` when the execution time exceeds the timeout limit.
+    pub fn check_timeout(&mut self) {
+        let timeout = self.limits.timeout;
+        if let Some(start_time) = self.start_time {
+            if timeout > 0 && start_time.elapsed().as_millis() > timeout as u128 {
+                self.set_err_type(&crate::ErrType::EvaluationError_TYPE);
+                panic!("execution timeout, the timeout is {timeout} ms");
+            }
+        }
+    }
+
+    /// Raise an `EvaluationError` when the number of the allocated values adding
+    /// `additional` values exceeds the limit, and the timeout is checked periodically.
+    pub fn check_objects_limit(&mut self, additional: u64) {
+        let count = self.objects.len() as u64 + self.evaluated_objects + additional;
+        let max_objects = self.limits.max_objects;
+        if max_objects > 0 && count > max_objects {
+            self.set_err_type(&crate::ErrType::EvaluationError_TYPE);
+            panic!("maximum number of allocated values exceeded, the max object count is {max_objects}");
+        }
+        self.timeout_ticks += 1;
+        if self.timeout_ticks >= TIMEOUT_CHECK_INTERVAL {
+            self.timeout_ticks = 0;
+            self.check_timeout();
+        }
+    }
+
+    /// Count an expression value evaluated by the AST evaluator into the objects limit.
+    pub fn count_evaluated_object(&mut self) {
+        self.evaluated_objects += 1;
+        self.check_objects_limit(0);
+    }
+
+    /// Raise an `EvaluationError` when the function `name` with file system or
+    /// plugin side effects is called in the sandbox mode.
+    pub fn check_sandbox(&mut self, name: &str) {
+        if self.cfg.sandbox {
+            self.set_err_type(&crate::ErrType::EvaluationError_TYPE);
+            panic!("the function '{name}' is not allowed in the sandbox mode");
+        }
+    }
+
+    /// Raise an `EvaluationError` when the random function `name` is called in the
+    /// deterministic mode.
+    pub fn check_deterministic(&mut self, name: &str) {
+        if self.deterministic {
+            self.set_err_type(&crate::ErrType::EvaluationError_TYPE);
+            panic!("the function '{name}' is not allowed in the deterministic mode");
         }
     }
 
@@ -106,10 +254,10 @@ impl crate::Context {
 
         if self.cfg.list_option_mode {
             self.output.return_value =
-                crate::ValueRef::str(self.list_option_help().as_str()).into_raw();
+                crate::ValueRef::str(self.list_option_help().as_str()).into_raw(self);
         // If there is a custom manifests, output them.
-        } else if let Some(output) = &self.buffer.custom_manifests_output {
-            self.output.return_value = crate::ValueRef::str(output.as_str()).into_raw();
+        } else if let Some(output) = self.buffer.custom_manifests_output.clone() {
+            self.output.return_value = crate::ValueRef::str(output.as_str()).into_raw(self);
         }
 
         self.output.return_value
@@ -126,6 +274,7 @@ impl crate::Context {
     pub fn set_kcl_filename(&mut self, file: &str) {
         if !file.is_empty() {
             self.panic_info.kcl_file = file.to_string();
+            self.profiler.enter_file(file);
         }
     }
 
@@ -186,18 +335,20 @@ impl crate::Context {
         self.panic_info.is_warning = true;
     }
 
-    pub fn set_panic_info(&mut self, info: &std::panic::PanicInfo) {
-        self.panic_info.__kcl_PanicInfo__ = true;
+    /// Record the panic `info` as the last panic of the current thread, which is
+    /// moved into the runtime context with [`Context::set_panic_info`].
+    pub fn record_panic(info: &std::panic::PanicInfo) {
+        PANIC_RECORD.with(|record| record.borrow_mut().1 = Some(PanicRecord::new(info)));
+    }
 
-        self.panic_info.message = if let Some(s) = info.payload().downcast_ref::<&str>() {
-            s.to_string()
-        } else if let Some(s) = info.payload().downcast_ref::<&String>() {
-            (*s).clone()
-        } else if let Some(s) = info.payload().downcast_ref::<String>() {
-            (*s).clone()
-        } else {
-            "".to_string()
-        };
+    /// Move the last panic recorded by the panic hook in the current thread into the
+    /// panic info, which is called after the panic of the execution is caught.
+    pub fn set_panic_info(&mut self) {
+        let record = PANIC_RECORD
+            .with(|record| record.borrow_mut().1.take())
+            .unwrap_or_default();
+        self.panic_info.__kcl_PanicInfo__ = true;
+        self.panic_info.message = record.message;
         if self.cfg.debug_mode {
             self.panic_info.backtrace = self.backtrace.clone();
             self.panic_info.backtrace.push(BacktraceFrame {
@@ -207,15 +358,9 @@ impl crate::Context {
                 line: self.panic_info.kcl_line,
             });
         }
-        if let Some(location) = info.location() {
-            self.panic_info.rust_file = location.file().to_string();
-            self.panic_info.rust_line = location.line() as i32;
-            self.panic_info.rust_col = location.column() as i32;
-        } else {
-            self.panic_info.rust_file = "".to_string();
-            self.panic_info.rust_line = 0;
-            self.panic_info.rust_col = 0;
-        }
+        self.panic_info.rust_file = record.rust_file;
+        self.panic_info.rust_line = record.rust_line;
+        self.panic_info.rust_col = record.rust_col;
     }
 }
 
@@ -0,0 +1,284 @@
+// Copyright 2021 The KCL Authors. All rights reserved.
+
+use std::fmt;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::IndexMap;
+
+/// The profile record kind of the compiling and running phases.
+pub const PROFILE_KIND_PHASE: &str = "phase";
+/// The profile record kind of the KCL files.
+pub const PROFILE_KIND_FILE: &str = "file";
+/// The profile record kind of the schema constructions.
+pub const PROFILE_KIND_SCHEMA: &str = "schema";
+/// The profile record kind of the schema check blocks.
+pub const PROFILE_KIND_CHECK: &str = "check";
+/// The profile record kind of the lambda calls.
+pub const PROFILE_KIND_LAMBDA: &str = "lambda";
+
+/// The trace events exceeding the limit are dropped, and only their
+/// accumulated time is recorded.
+const MAX_TRACE_EVENTS: usize = 1 << 20;
+
+/// ProfileRecord denotes the accumulated time of a phase, a file, a schema,
+/// a schema check block or a lambda.
+#[derive(PartialEq, Clone, Default, Debug, Serialize, Deserialize)]
+pub struct ProfileRecord {
+    pub kind: String,
+    pub name: String,
+    /// The number of the measured spans.
+    pub count: u64,
+    /// The total time in seconds, and the time of nested calls is included
+    /// except for the files.
+    pub time: f64,
+}
+
+/// TraceEvent denotes a complete event `"ph": "X"` of the Chrome trace event format,
+/// which can be loaded by `chrome://tracing` or Perfetto.
+#[derive(PartialEq, Eq, Clone, Default, Debug, Serialize, Deserialize)]
+pub struct TraceEvent {
+    pub name: String,
+    pub cat: String,
+    pub ph: String,
+    /// The start timestamp in microseconds since the Unix epoch.
+    pub ts: u64,
+    /// The duration in microseconds.
+    pub dur: u64,
+    pub pid: u32,
+    pub tid: u32,
+}
+
+/// ProfileReport denotes the profile records and trace events of a KCL program execution.
+#[derive(PartialEq, Clone, Default, Debug, Serialize, Deserialize)]
+pub struct ProfileReport {
+    pub records: Vec<ProfileRecord>,
+    pub events: Vec<TraceEvent>,
+}
+
+impl ProfileReport {
+    /// Record the span `name` of `kind` which starts at `start` and lasts `dur`.
+    pub fn record(&mut self, kind: &str, name: &str, start: SystemTime, dur: Duration) {
+        match self
+            .records
+            .iter_mut()
+            .find(|r| r.kind == kind && r.name == name)
+        {
+            Some(record) => {
+                record.count += 1;
+                record.time += dur.as_secs_f64();
+            }
+            None => self.records.push(ProfileRecord {
+                kind: kind.to_string(),
+                name: name.to_string(),
+                count: 1,
+                time: dur.as_secs_f64(),
+            }),
+        }
+        if self.events.len() < MAX_TRACE_EVENTS {
+            self.events.push(trace_event(kind, name, start, dur));
+        }
+    }
+
+    /// Run `f` and record its running time as the span `name` of `kind`.
+    pub fn measure<T>(&mut self, kind: &str, name: &str, f: impl FnOnce() -> T) -> T {
+        let start = SystemTime::now();
+        let result = f();
+        self.record(kind, name, start, elapsed(start));
+        result
+    }
+
+    /// Merge the records and events of `other` into the report.
+    pub fn extend(&mut self, other: ProfileReport) {
+        for record in other.records {
+            match self
+                .records
+                .iter_mut()
+                .find(|r| r.kind == record.kind && r.name == record.name)
+            {
+                Some(r) => {
+                    r.count += record.count;
+                    r.time += record.time;
+                }
+                None => self.records.push(record),
+            }
+        }
+        self.events.extend(other.events);
+    }
+
+    /// Returns the Chrome trace event JSON string of the report.
+    pub fn to_trace_json(&self) -> String {
+        serde_json::json!({
+            "traceEvents": self.events,
+            "displayTimeUnit": "ms",
+        })
+        .to_string()
+    }
+}
+
+impl fmt::Display for ProfileReport {
+    /// The phases are displayed in the execution order, and the other records
+    /// are displayed in the descending order of their time.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (phases, mut records): (Vec<&ProfileRecord>, Vec<&ProfileRecord>) = self
+            .records
+            .iter()
+            .partition(|r| r.kind == PROFILE_KIND_PHASE);
+        records.sort_by(|a, b| b.time.total_cmp(&a.time));
+        writeln!(f, "{:<8} {:>10} {:>12}  name", "kind", "count", "time(s)")?;
+        for record in phases.iter().chain(records.iter()) {
+            writeln!(
+                f,
+                "{:<8} {:>10} {:>12.6}  {}",
+                record.kind, record.count, record.time, record.name
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Profiler records the running time of the files, schemas, schema check blocks
+/// and lambdas in the runtime context when the profiling is enabled.
+#[derive(PartialEq, Clone, Default, Debug)]
+pub struct Profiler {
+    pub enabled: bool,
+    /// The unfinished schema, check block and lambda spans.
+    spans: Vec<(String, String, SystemTime)>,
+    /// The current running file and its start time.
+    file: Option<(String, SystemTime)>,
+    records: IndexMap<(String, String), ProfileRecord>,
+    events: Vec<TraceEvent>,
+}
+
+impl Profiler {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            ..Default::default()
+        }
+    }
+
+    /// Begin the span `name` of `kind`, e.g., a schema construction.
+    pub fn begin(&mut self, kind: &str, name: &str) {
+        if self.enabled {
+            self.spans
+                .push((kind.to_string(), name.to_string(), SystemTime::now()));
+        }
+    }
+
+    /// End the last unfinished span.
+    pub fn end(&mut self) {
+        if let Some((kind, name, start)) = self.spans.pop() {
+            self.record(kind, name, start);
+        }
+    }
+
+    /// Switch the current running file, the time between two file switches
+    /// is recorded to the previous file.
+    pub fn enter_file(&mut self, filename: &str) {
+        if !self.enabled {
+            return;
+        }
+        if let Some((current, _)) = &self.file {
+            if current == filename {
+                return;
+            }
+        }
+        if let Some((current, start)) = self.file.take() {
+            self.record(PROFILE_KIND_FILE.to_string(), current, start);
+        }
+        self.file = Some((filename.to_string(), SystemTime::now()));
+    }
+
+    /// Finish all the unfinished spans and returns the profile report.
+    pub fn finish(&mut self) -> ProfileReport {
+        while !self.spans.is_empty() {
+            self.end();
+        }
+        if let Some((current, start)) = self.file.take() {
+            self.record(PROFILE_KIND_FILE.to_string(), current, start);
+        }
+        ProfileReport {
+            records: std::mem::take(&mut self.records).into_values().collect(),
+            events: std::mem::take(&mut self.events),
+        }
+    }
+
+    fn record(&mut self, kind: String, name: String, start: SystemTime) {
+        let dur = elapsed(start);
+        if self.events.len() < MAX_TRACE_EVENTS {
+            self.events.push(trace_event(&kind, &name, start, dur));
+        }
+        let record = self
+            .records
+            .entry((kind.clone(), name.clone()))
+            .or_insert_with(|| ProfileRecord {
+                kind,
+                name,
+                ..Default::default()
+            });
+        record.count += 1;
+        record.time += dur.as_secs_f64();
+    }
+}
+
+/// ProfileSpan begins a span of the context profiler when the profiling is enabled,
+/// and ends the span when it is dropped, thus every begun span is ended exactly once
+/// even if the profiled code panics. The context must outlive the span.
+pub struct ProfileSpan {
+    ctx: *mut crate::Context,
+    begun: bool,
+}
+
+impl ProfileSpan {
+    /// Begin the span of `kind` named by `name` in the profiler of `ctx`, and `name`
+    /// is only called when the profiling is enabled.
+    pub fn begin(ctx: &mut crate::Context, kind: &str, name: impl FnOnce() -> String) -> Self {
+        let begun = ctx.profiler.enabled;
+        if begun {
+            ctx.profiler.begin(kind, &name());
+        }
+        Self { ctx, begun }
+    }
+}
+
+impl Drop for ProfileSpan {
+    fn drop(&mut self) {
+        if self.begun {
+            unsafe { (*self.ctx).profiler.end() };
+        }
+    }
+}
+
+fn elapsed(start: SystemTime) -> Duration {
+    SystemTime::now().duration_since(start).unwrap_or_default()
+}
+
+#[cfg(not(target_os = "wasi"))]
+fn process_id() -> u32 {
+    std::process::id()
+}
+
+/// The process id is not supported by the WASI target.
+#[cfg(target_os = "wasi")]
+fn process_id() -> u32 {
+    0
+}
+
+fn trace_event(kind: &str, name: &str, start: SystemTime, dur: Duration) -> TraceEvent {
+    TraceEvent {
+        name: name.to_string(),
+        cat: kind.to_string(),
+        ph: "X".to_string(),
+        ts: start
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_micros() as u64,
+        dur: dur.as_micros() as u64,
+        pid: process_id(),
+        // The file spans are not nested in the call spans, so they are put into
+        // a separate track.
+        tid: (kind == PROFILE_KIND_FILE) as u32,
+    }
+}
@@ -19,15 +19,16 @@ type kclvm_value_ref_t = ValueRef;
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_crypto_md5(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     args: *const kclvm_value_ref_t,
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
 
     if let Some(s) = args.arg_i_str(0, None) {
         let hex = format!("{:x}", md5::compute(s));
-        return ValueRef::str(hex.as_ref()).into_raw();
+        return ValueRef::str(hex.as_ref()).into_raw(ctx);
     }
     panic!("md5() missing 1 required positional argument: 'value'");
 }
@@ -37,15 +38,16 @@ pub unsafe extern "C" fn kclvm_crypto_md5(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_crypto_sha1(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     args: *const kclvm_value_ref_t,
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
 
     if let Some(s) = args.arg_i_str(0, None) {
         let hex = sha1::Sha1::from(s).digest().to_string();
-        return ValueRef::str(hex.as_ref()).into_raw();
+        return ValueRef::str(hex.as_ref()).into_raw(ctx);
     }
     panic!("sha1() missing 1 required positional argument: 'value'");
 }
@@ -55,10 +57,11 @@ pub unsafe extern "C" fn kclvm_crypto_sha1(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_crypto_sha224(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     args: *const kclvm_value_ref_t,
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
 
     if let Some(s) = args.arg_i_str(0, None) {
@@ -73,7 +76,7 @@ pub unsafe extern "C" fn kclvm_crypto_sha224(
             let _ = write!(&mut hex, "{byte:02x}");
         }
 
-        return ValueRef::str(hex.as_ref()).into_raw();
+        return ValueRef::str(hex.as_ref()).into_raw(ctx);
     }
     panic!("sha224() missing 1 required positional argument: 'value'");
 }
@@ -83,10 +86,11 @@ pub unsafe extern "C" fn kclvm_crypto_sha224(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_crypto_sha256(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     args: *const kclvm_value_ref_t,
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
 
     if let Some(s) = args.arg_i_str(0, None) {
@@ -101,7 +105,7 @@ pub unsafe extern "C" fn kclvm_crypto_sha256(
             let _ = write!(&mut hex, "{byte:02x}");
         }
 
-        return ValueRef::str(hex.as_ref()).into_raw();
+        return ValueRef::str(hex.as_ref()).into_raw(ctx);
     }
     panic!("sha256() missing 1 required positional argument: 'value'");
 }
@@ -111,10 +115,11 @@ pub unsafe extern "C" fn kclvm_crypto_sha256(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_crypto_sha384(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     args: *const kclvm_value_ref_t,
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
 
     if let Some(s) = args.arg_i_str(0, None) {
@@ -129,7 +134,7 @@ pub unsafe extern "C" fn kclvm_crypto_sha384(
             let _ = write!(&mut hex, "{byte:02x}");
         }
 
-        return ValueRef::str(hex.as_ref()).into_raw();
+        return ValueRef::str(hex.as_ref()).into_raw(ctx);
     }
     panic!("sha384() missing 1 required positional argument: 'value'");
 }
@@ -139,10 +144,11 @@ pub unsafe extern "C" fn kclvm_crypto_sha384(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_crypto_sha512(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     args: *const kclvm_value_ref_t,
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
 
     if let Some(s) = args.arg_i_str(0, None) {
@@ -157,7 +163,7 @@ pub unsafe extern "C" fn kclvm_crypto_sha512(
             let _ = write!(&mut hex, "{byte:02x}");
         }
 
-        return ValueRef::str(hex.as_ref()).into_raw();
+        return ValueRef::str(hex.as_ref()).into_raw(ctx);
     }
     panic!("sha512() missing 1 required positional argument: 'value'");
 }
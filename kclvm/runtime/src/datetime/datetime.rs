@@ -1,11 +1,20 @@
 //! KCL datetime system module
 //!
+//! The times are the RFC 3339 strings e.g., `2023-05-01T08:30:00+08:00`, and the
+//! durations are the seconds or the duration strings e.g., `1h30m`. The current time
+//! is the frozen now time of the execution when it is set, so the outputs are
+//! reproducible.
+//!
 //! Copyright 2021 The KCL Authors. All rights reserved.
 #![allow(clippy::missing_safety_doc)]
 
 extern crate chrono;
 
+use chrono::format::{Item, StrftimeItems};
 use chrono::prelude::Local;
+use chrono::{
+    DateTime, Duration, FixedOffset, NaiveDate, NaiveDateTime, SecondsFormat, TimeZone, Utc,
+};
 
 use crate::*;
 
@@ -17,25 +26,33 @@ type kclvm_value_ref_t = ValueRef;
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_datetime_today(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     _args: *const kclvm_value_ref_t,
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
-    let s = Local::now().to_string();
-    return ValueRef::str(s.as_ref()).into_raw();
+    let ctx = mut_ptr_as_ref(ctx);
+    let s = now(ctx).to_string();
+    return ValueRef::str(s.as_ref()).into_raw(ctx);
 }
 
-// def KMANGLED_now() -> str:
+// def KMANGLED_now(format: str = None) -> str:
 
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_datetime_now(
-    _ctx: *mut kclvm_context_t,
-    _args: *const kclvm_value_ref_t,
-    _kwargs: *const kclvm_value_ref_t,
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
-    let s = Local::now().to_string();
-    return ValueRef::str(s.as_ref()).into_raw();
+    let ctx = mut_ptr_as_ref(ctx);
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+    let now = now(ctx);
+    let s = match opt_str_arg(ctx, args, kwargs, "now", 0, "format") {
+        Some(format) => strftime(ctx, &now, &format),
+        None => now.to_string(),
+    };
+    return ValueRef::str(s.as_ref()).into_raw(ctx);
 }
 
 // def KMANGLED_ticks() -> float:
@@ -43,12 +60,13 @@ pub unsafe extern "C" fn kclvm_datetime_now(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_datetime_ticks(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     _args: *const kclvm_value_ref_t,
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
-    let x = Local::now().timestamp();
-    ValueRef::float(x as f64).into_raw()
+    let ctx = mut_ptr_as_ref(ctx);
+    let x = now(ctx).timestamp();
+    ValueRef::float(x as f64).into_raw(ctx)
 }
 
 // def KMANGLED_date() -> str:
@@ -56,10 +74,519 @@ pub unsafe extern "C" fn kclvm_datetime_ticks(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_datetime_date(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     _args: *const kclvm_value_ref_t,
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
-    let s = Local::now().to_string();
-    return ValueRef::str(s.as_ref()).into_raw();
+    let ctx = mut_ptr_as_ref(ctx);
+    let s = now(ctx).to_string();
+    return ValueRef::str(s.as_ref()).into_raw(ctx);
+}
+
+// def KMANGLED_strftime(time: str, format: str) -> str:
+
+#[no_mangle]
+#[runtime_fn]
+pub unsafe extern "C" fn kclvm_datetime_strftime(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+    let time = time_arg(ctx, args, kwargs, "strftime", 0, "time");
+    let format = str_arg(ctx, args, kwargs, "strftime", 1, "format");
+    ValueRef::str(&strftime(ctx, &time, &format)).into_raw(ctx)
+}
+
+// def KMANGLED_strptime(value: str, format: str) -> str:
+
+#[no_mangle]
+#[runtime_fn]
+pub unsafe extern "C" fn kclvm_datetime_strptime(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+    let value = str_arg(ctx, args, kwargs, "strptime", 0, "value");
+    let format = str_arg(ctx, args, kwargs, "strptime", 1, "format");
+    match strptime(&value, &format) {
+        Some(time) => ValueRef::str(&to_rfc3339(&time)).into_raw(ctx),
+        None => {
+            ctx.set_err_type(&ErrType::EvaluationError_TYPE);
+            panic!("strptime() time '{value}' does not match the format '{format}'")
+        }
+    }
+}
+
+// def KMANGLED_validate(value: str, format: str = None) -> bool:
+
+#[no_mangle]
+#[runtime_fn]
+pub unsafe extern "C" fn kclvm_datetime_validate(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+    let value = str_arg(ctx, args, kwargs, "validate", 0, "value");
+    let valid = match opt_str_arg(ctx, args, kwargs, "validate", 1, "format") {
+        Some(format) => strptime(&value, &format).is_some(),
+        None => DateTime::parse_from_rfc3339(&value).is_ok(),
+    };
+    ValueRef::bool(valid).into_raw(ctx)
+}
+
+// def KMANGLED_to_timezone(time: str, timezone: str) -> str:
+
+#[no_mangle]
+#[runtime_fn]
+pub unsafe extern "C" fn kclvm_datetime_to_timezone(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+    let time = time_arg(ctx, args, kwargs, "to_timezone", 0, "time");
+    let timezone = str_arg(ctx, args, kwargs, "to_timezone", 1, "timezone");
+    match parse_timezone(&time, &timezone) {
+        Some(offset) => ValueRef::str(&to_rfc3339(&time.with_timezone(&offset))).into_raw(ctx),
+        None => {
+            ctx.set_err_type(&ErrType::EvaluationError_TYPE);
+            panic!("to_timezone() invalid timezone '{timezone}', expect 'UTC', 'Local' or an offset e.g., '+08:00'")
+        }
+    }
+}
+
+// def KMANGLED_parse_duration(value: str) -> float:
+
+#[no_mangle]
+#[runtime_fn]
+pub unsafe extern "C" fn kclvm_datetime_parse_duration(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+    let value = str_arg(ctx, args, kwargs, "parse_duration", 0, "value");
+    ValueRef::float(duration_str_seconds(ctx, "parse_duration", &value)).into_raw(ctx)
+}
+
+// def KMANGLED_format_duration(seconds: float) -> str:
+
+#[no_mangle]
+#[runtime_fn]
+pub unsafe extern "C" fn kclvm_datetime_format_duration(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+    let seconds = duration_arg(ctx, args, kwargs, "format_duration", 0, "seconds");
+    ValueRef::str(&format_duration(seconds)).into_raw(ctx)
+}
+
+// def KMANGLED_add(time: str, duration: str|float) -> str:
+
+#[no_mangle]
+#[runtime_fn]
+pub unsafe extern "C" fn kclvm_datetime_add(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+    let time = time_arg(ctx, args, kwargs, "add", 0, "time");
+    let seconds = duration_arg(ctx, args, kwargs, "add", 1, "duration");
+    let duration = Duration::nanoseconds((seconds * 1e9).round() as i64);
+    match time.checked_add_signed(duration) {
+        Some(time) => ValueRef::str(&to_rfc3339(&time)).into_raw(ctx),
+        None => {
+            ctx.set_err_type(&ErrType::EvaluationError_TYPE);
+            panic!("add() the time is out of range")
+        }
+    }
+}
+
+// def KMANGLED_diff(end: str, start: str) -> float:
+
+#[no_mangle]
+#[runtime_fn]
+pub unsafe extern "C" fn kclvm_datetime_diff(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+    let end = time_arg(ctx, args, kwargs, "diff", 0, "end");
+    let start = time_arg(ctx, args, kwargs, "diff", 1, "start");
+    let duration = end.signed_duration_since(start);
+    let seconds = match duration.num_nanoseconds() {
+        Some(nanos) => nanos as f64 / 1e9,
+        None => duration.num_milliseconds() as f64 / 1e3,
+    };
+    ValueRef::float(seconds).into_raw(ctx)
+}
+
+/// The current time, which is the frozen now time of the context when it is set.
+fn now(ctx: &mut Context) -> DateTime<FixedOffset> {
+    if ctx.frozen_now.is_empty() {
+        let now = Local::now();
+        return now.with_timezone(now.offset());
+    }
+    match DateTime::parse_from_rfc3339(&ctx.frozen_now) {
+        Ok(now) => now,
+        Err(err) => {
+            ctx.set_err_type(&ErrType::EvaluationError_TYPE);
+            panic!(
+                "invalid frozen now time '{}', expect a RFC 3339 time: {err}",
+                ctx.frozen_now
+            )
+        }
+    }
+}
+
+/// Encode the time to the RFC 3339 string, and the UTC offset is written as `Z`.
+fn to_rfc3339(time: &DateTime<FixedOffset>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::AutoSi, true)
+}
+
+/// Format the time with the strftime format, e.g., `%Y-%m-%d %H:%M:%S`.
+fn strftime(ctx: &mut Context, time: &DateTime<FixedOffset>, format: &str) -> String {
+    if StrftimeItems::new(format).any(|item| item == Item::Error) {
+        ctx.set_err_type(&ErrType::EvaluationError_TYPE);
+        panic!("invalid datetime format '{format}'")
+    }
+    time.format(format).to_string()
+}
+
+/// Parse the time with the strftime format, and the time without the offset is a UTC time
+/// and the date without the time is the midnight.
+fn strptime(value: &str, format: &str) -> Option<DateTime<FixedOffset>> {
+    if let Ok(time) = DateTime::parse_from_str(value, format) {
+        return Some(time);
+    }
+    let naive = NaiveDateTime::parse_from_str(value, format)
+        .ok()
+        .or_else(|| {
+            NaiveDate::parse_from_str(value, format)
+                .ok()
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+        })?;
+    let time = Utc.from_utc_datetime(&naive);
+    Some(time.with_timezone(&FixedOffset::east_opt(0)?))
+}
+
+/// Parse the timezone `UTC`, `Local` or the offset e.g., `+08:00`, `-0530` and `+08`,
+/// and the local offset is the offset of the local timezone at `time`.
+fn parse_timezone(time: &DateTime<FixedOffset>, timezone: &str) -> Option<FixedOffset> {
+    match timezone {
+        "UTC" | "utc" | "Z" => return FixedOffset::east_opt(0),
+        "Local" | "local" => return Some(*time.with_timezone(&Local).offset()),
+        _ => {}
+    }
+    let (sign, offset) = match (timezone.strip_prefix('+'), timezone.strip_prefix('-')) {
+        (Some(offset), _) => (1, offset),
+        (_, Some(offset)) => (-1, offset),
+        _ => return None,
+    };
+    let offset = offset.replace(':', "");
+    if !offset.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let (hours, minutes) = match offset.len() {
+        2 => (offset.parse::<i32>().ok()?, 0),
+        4 => (
+            offset[..2].parse::<i32>().ok()?,
+            offset[2..].parse::<i32>().ok()?,
+        ),
+        _ => return None,
+    };
+    if minutes >= 60 {
+        return None;
+    }
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+}
+
+/// The nanoseconds of the duration units.
+const DURATION_UNITS: [(&str, f64); 8] = [
+    ("ns", 1.0),
+    ("us", 1e3),
+    ("µs", 1e3),
+    ("ms", 1e6),
+    ("s", 1e9),
+    ("m", 6e10),
+    ("h", 3.6e12),
+    ("d", 8.64e13),
+];
+
+/// Parse the duration string to seconds. A duration string is a possibly signed sequence
+/// of decimal numbers with the units, e.g., `1h30m`, `1.5h`, `-30s` and `300ms`.
+fn parse_duration(value: &str) -> Option<f64> {
+    let (sign, mut rest) = match value.strip_prefix('-') {
+        Some(rest) => (-1.0, rest),
+        None => (1.0, value.strip_prefix('+').unwrap_or(value)),
+    };
+    if rest == "0" {
+        return Some(0.0);
+    }
+    if rest.is_empty() {
+        return None;
+    }
+    let mut nanos = 0.0;
+    while !rest.is_empty() {
+        let number_len = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(rest.len());
+        let unit_len = rest[number_len..]
+            .find(|c: char| c.is_ascii_digit() || c == '.')
+            .unwrap_or(rest.len() - number_len);
+        let number: f64 = rest[..number_len].parse().ok()?;
+        let unit = &rest[number_len..number_len + unit_len];
+        let (_, unit_nanos) = DURATION_UNITS.iter().find(|(name, _)| *name == unit)?;
+        nanos += number * unit_nanos;
+        rest = &rest[number_len + unit_len..];
+    }
+    Some(sign * nanos / 1e9)
+}
+
+/// Format the seconds to the duration string, e.g., `1h30m`, `1m30.5s` and `500ms`,
+/// which can be parsed by [`parse_duration`].
+fn format_duration(seconds: f64) -> String {
+    let nanos = (seconds * 1e9).round() as i64;
+    if nanos == 0 {
+        return "0s".to_string();
+    }
+    let sign = if nanos < 0 { "-" } else { "" };
+    let nanos = nanos.unsigned_abs();
+    let (hours, minutes) = (nanos / 3_600_000_000_000, nanos / 60_000_000_000 % 60);
+    let (secs, frac) = (nanos / 1_000_000_000 % 60, nanos % 1_000_000_000);
+    let mut result = sign.to_string();
+    if hours > 0 {
+        result.push_str(&format!("{hours}h"));
+    }
+    if minutes > 0 {
+        result.push_str(&format!("{minutes}m"));
+    }
+    if hours == 0 && minutes == 0 && secs == 0 {
+        // The durations less than one second are written with the sub-second units.
+        if frac % 1_000_000 == 0 {
+            result.push_str(&format!("{}ms", frac / 1_000_000));
+        } else if frac % 1_000 == 0 {
+            result.push_str(&format!("{}us", frac / 1_000));
+        } else {
+            result.push_str(&format!("{frac}ns"));
+        }
+    } else if frac > 0 {
+        let frac = format!("{frac:09}");
+        result.push_str(&format!("{secs}.{}s", frac.trim_end_matches('0')));
+    } else if secs > 0 {
+        result.push_str(&format!("{secs}s"));
+    }
+    result
+}
+
+/// Get the seconds of the duration string `value`.
+fn duration_str_seconds(ctx: &mut Context, func: &str, value: &str) -> f64 {
+    match parse_duration(value) {
+        Some(seconds) => seconds,
+        None => {
+            ctx.set_err_type(&ErrType::EvaluationError_TYPE);
+            panic!("{func}() invalid duration '{value}', expect e.g., '1h30m', '90s' or '500ms'")
+        }
+    }
+}
+
+/// Get the argument `name` from the positional argument at `index` or the keyword arguments.
+fn arg(args: &ValueRef, kwargs: &ValueRef, index: usize, name: &str) -> Option<ValueRef> {
+    args.arg_i(index)
+        .or_else(|| kwargs.kwarg(name))
+        .filter(|arg| !arg.is_none_or_undefined())
+}
+
+/// Get the optional string argument `name`.
+fn opt_str_arg(
+    ctx: &mut Context,
+    args: &ValueRef,
+    kwargs: &ValueRef,
+    func: &str,
+    index: usize,
+    name: &str,
+) -> Option<String> {
+    match arg(args, kwargs, index, name) {
+        Some(arg) if arg.is_str() => Some(arg.as_str()),
+        Some(arg) => {
+            ctx.set_err_type(&ErrType::TypeError_Runtime_TYPE);
+            panic!(
+                "{func}() argument '{name}' must be str, not {}",
+                arg.type_str()
+            )
+        }
+        None => None,
+    }
+}
+
+/// Get the required string argument `name`.
+fn str_arg(
+    ctx: &mut Context,
+    args: &ValueRef,
+    kwargs: &ValueRef,
+    func: &str,
+    index: usize,
+    name: &str,
+) -> String {
+    match opt_str_arg(ctx, args, kwargs, func, index, name) {
+        Some(arg) => arg,
+        None => panic!("{func}() missing required argument: '{name}'"),
+    }
+}
+
+/// Get the required RFC 3339 time argument `name`.
+fn time_arg(
+    ctx: &mut Context,
+    args: &ValueRef,
+    kwargs: &ValueRef,
+    func: &str,
+    index: usize,
+    name: &str,
+) -> DateTime<FixedOffset> {
+    let value = str_arg(ctx, args, kwargs, func, index, name);
+    match DateTime::parse_from_rfc3339(&value) {
+        Ok(time) => time,
+        Err(err) => {
+            ctx.set_err_type(&ErrType::EvaluationError_TYPE);
+            panic!("{func}() invalid RFC 3339 time '{value}': {err}")
+        }
+    }
+}
+
+/// Get the required duration argument `name` in seconds, which is a number of
+/// seconds or a duration string.
+fn duration_arg(
+    ctx: &mut Context,
+    args: &ValueRef,
+    kwargs: &ValueRef,
+    func: &str,
+    index: usize,
+    name: &str,
+) -> f64 {
+    let arg = match arg(args, kwargs, index, name) {
+        Some(arg) => arg,
+        None => panic!("{func}() missing required argument: '{name}'"),
+    };
+    let seconds = match &*arg.rc.borrow() {
+        Value::int_value(v) => Some(*v as f64),
+        Value::float_value(v) => Some(*v),
+        Value::str_value(_) => None,
+        _ => {
+            ctx.set_err_type(&ErrType::TypeError_Runtime_TYPE);
+            panic!(
+                "{func}() argument '{name}' must be int, float or str, not {}",
+                arg.type_str()
+            )
+        }
+    };
+    seconds.unwrap_or_else(|| duration_str_seconds(ctx, func, &arg.as_str()))
+}
+
+#[cfg(test)]
+mod test_datetime {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration() {
+        let cases = [
+            ("0", Some(0.0)),
+            ("90s", Some(90.0)),
+            ("1h30m", Some(5400.0)),
+            ("1.5h", Some(5400.0)),
+            ("-30s", Some(-30.0)),
+            ("1d2h", Some(93600.0)),
+            ("300ms", Some(0.3)),
+            ("", None),
+            ("1", None),
+            ("1x", None),
+            ("h", None),
+        ];
+        for (value, expected) in cases {
+            assert_eq!(parse_duration(value), expected, "{value}");
+        }
+    }
+
+    #[test]
+    fn test_format_duration() {
+        let cases = [
+            (0.0, "0s"),
+            (5400.0, "1h30m"),
+            (90.5, "1m30.5s"),
+            (-30.0, "-30s"),
+            (0.5, "500ms"),
+            (3661.0, "1h1m1s"),
+        ];
+        for (seconds, expected) in cases {
+            assert_eq!(format_duration(seconds), expected);
+            assert_eq!(parse_duration(expected), Some(seconds));
+        }
+    }
+
+    #[test]
+    fn test_strptime() {
+        let time = strptime("2023-05-01 08:30:00 +0800", "%Y-%m-%d %H:%M:%S %z").unwrap();
+        assert_eq!(to_rfc3339(&time), "2023-05-01T08:30:00+08:00");
+        let time = strptime("2023-05-01 08:30", "%Y-%m-%d %H:%M").unwrap();
+        assert_eq!(to_rfc3339(&time), "2023-05-01T08:30:00Z");
+        let time = strptime("01/05/2023", "%d/%m/%Y").unwrap();
+        assert_eq!(to_rfc3339(&time), "2023-05-01T00:00:00Z");
+        assert!(strptime("2023-05-01", "%d/%m/%Y").is_none());
+    }
+
+    #[test]
+    fn test_parse_timezone() {
+        let time = DateTime::parse_from_rfc3339("2023-05-01T08:30:00Z").unwrap();
+        let cases = [
+            ("UTC", Some(0)),
+            ("+08:00", Some(8 * 3600)),
+            ("-0530", Some(-(5 * 3600 + 30 * 60))),
+            ("+08", Some(8 * 3600)),
+            ("08:00", None),
+            ("+8:00", None),
+            ("+08:60", None),
+            ("Asia/Shanghai", None),
+        ];
+        for (timezone, expected) in cases {
+            assert_eq!(
+                parse_timezone(&time, timezone).map(|offset| offset.local_minus_utc()),
+                expected,
+                "{timezone}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_frozen_now() {
+        let mut ctx = Context::new();
+        ctx.frozen_now = "2023-05-01T08:30:00+08:00".to_string();
+        let now = now(&mut ctx);
+        assert_eq!(to_rfc3339(&now), "2023-05-01T08:30:00+08:00");
+        assert_eq!(
+            strftime(&mut ctx, &now, "%Y-%m-%d %H:%M"),
+            "2023-05-01 08:30"
+        );
+    }
 }
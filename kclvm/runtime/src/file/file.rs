@@ -0,0 +1,291 @@
+//! KCL file system module
+//!
+//! The relative file paths are resolved from the root path of the `kcl.mod` file,
+//! which is the directory of the input files when there is no `kcl.mod` file, and
+//! the paths outside the module root are not allowed.
+//!
+//! Copyright 2021 The KCL Authors. All rights reserved.
+#![allow(clippy::missing_safety_doc)]
+
+use std::path::{Component, Path, PathBuf};
+
+use crate::*;
+
+#[allow(non_camel_case_types)]
+type kclvm_value_ref_t = ValueRef;
+
+// def KMANGLED_read(filepath: str) -> str:
+
+#[no_mangle]
+#[runtime_fn]
+pub unsafe extern "C" fn kclvm_file_read(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
+    ctx.check_sandbox("file.read");
+    let filepath = str_arg(ctx, args, kwargs, "read", "filepath");
+    match std::fs::read_to_string(resolve_path(ctx, "read", &filepath)) {
+        Ok(content) => ValueRef::str(&content).into_raw(ctx),
+        Err(err) => {
+            ctx.set_err_type(&ErrType::EvaluationError_TYPE);
+            panic!("failed to read the file '{filepath}': {err}")
+        }
+    }
+}
+
+// def KMANGLED_glob(pattern: str) -> [str]:
+
+#[no_mangle]
+#[runtime_fn]
+pub unsafe extern "C" fn kclvm_file_glob(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
+    ctx.check_sandbox("file.glob");
+    let pattern = str_arg(ctx, args, kwargs, "glob", "pattern");
+    resolve_path(ctx, "glob", &pattern);
+    ValueRef::list_str(&glob_paths(ctx, &pattern)).into_raw(ctx)
+}
+
+// def KMANGLED_exists(filepath: str) -> bool:
+
+#[no_mangle]
+#[runtime_fn]
+pub unsafe extern "C" fn kclvm_file_exists(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
+    ctx.check_sandbox("file.exists");
+    let filepath = str_arg(ctx, args, kwargs, "exists", "filepath");
+    ValueRef::bool(resolve_path(ctx, "exists", &filepath).exists()).into_raw(ctx)
+}
+
+// def KMANGLED_modpath() -> str:
+
+#[no_mangle]
+#[runtime_fn]
+pub unsafe extern "C" fn kclvm_file_modpath(
+    ctx: *mut kclvm_context_t,
+    _args: *const kclvm_value_ref_t,
+    _kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
+    ValueRef::str(&module_path(ctx).to_string_lossy()).into_raw(ctx)
+}
+
+// def KMANGLED_workdir() -> str:
+
+#[no_mangle]
+#[runtime_fn]
+pub unsafe extern "C" fn kclvm_file_workdir(
+    ctx: *mut kclvm_context_t,
+    _args: *const kclvm_value_ref_t,
+    _kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
+    ValueRef::str(&work_dir(ctx).to_string_lossy()).into_raw(ctx)
+}
+
+/// The working directory of the execution, which is the current directory by default.
+fn work_dir(ctx: &Context) -> PathBuf {
+    if ctx.work_dir.is_empty() {
+        std::env::current_dir().unwrap_or_default()
+    } else {
+        PathBuf::from(&ctx.work_dir)
+    }
+}
+
+/// The root path of the `kcl.mod` file, which is the working directory by default.
+fn module_path(ctx: &Context) -> PathBuf {
+    if ctx.module_path.is_empty() {
+        work_dir(ctx)
+    } else {
+        PathBuf::from(&ctx.module_path)
+    }
+}
+
+/// Resolve the `filepath` argument of `func` from the module root, and raise an
+/// error when the path is outside the module root after the `..` components and
+/// the symbolic links of the existing paths are resolved.
+fn resolve_path(ctx: &mut Context, func: &str, filepath: &str) -> PathBuf {
+    let root = normalize_path(&module_path(ctx));
+    let path = normalize_path(&root.join(filepath));
+    let is_inside = path.starts_with(&root)
+        && match (path.canonicalize(), root.canonicalize()) {
+            (Ok(path), Ok(root)) => path.starts_with(root),
+            _ => true,
+        };
+    if !is_inside {
+        ctx.set_err_type(&ErrType::EvaluationError_TYPE);
+        panic!(
+            "{func}() path '{filepath}' is outside the module root '{}'",
+            root.display()
+        )
+    }
+    path
+}
+
+/// Get the sorted paths matched by the glob `pattern` from the module root. The matched
+/// paths outside the module root after the symbolic links are resolved are filtered out
+/// e.g., the files in a symbolic link to an outside directory.
+fn glob_paths(ctx: &mut Context, pattern: &str) -> Vec<String> {
+    let root = module_path(ctx);
+    // The matched paths of a relative pattern are relative to the module root, so
+    // they can be passed to `file.read` directly, and the root path is escaped in
+    // case that it contains the glob meta characters.
+    let is_relative = Path::new(&pattern).is_relative();
+    let full_pattern = if is_relative {
+        format!(
+            "{}/{}",
+            glob::Pattern::escape(&root.to_string_lossy()),
+            pattern
+        )
+    } else {
+        pattern.to_string()
+    };
+    let paths = match glob::glob(&full_pattern) {
+        Ok(paths) => paths,
+        Err(err) => {
+            ctx.set_err_type(&ErrType::EvaluationError_TYPE);
+            panic!("invalid glob pattern '{pattern}': {err}")
+        }
+    };
+    let real_root = root
+        .canonicalize()
+        .unwrap_or_else(|_| normalize_path(&root));
+    let mut matched = vec![];
+    for path in paths.flatten() {
+        if !matches!(path.canonicalize(), Ok(real_path) if real_path.starts_with(&real_root)) {
+            continue;
+        }
+        let path = if is_relative {
+            path.strip_prefix(&root)
+                .map(Path::to_path_buf)
+                .unwrap_or(path)
+        } else {
+            path
+        };
+        matched.push(path.to_string_lossy().to_string());
+    }
+    matched.sort();
+    matched
+}
+
+/// Remove the `.` and `..` components of the path without accessing the filesystem.
+fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => match normalized.components().next_back() {
+                Some(Component::Normal(_)) => {
+                    normalized.pop();
+                }
+                // The parent of the root directory is itself.
+                Some(Component::RootDir | Component::Prefix(_)) => {}
+                _ => normalized.push(component),
+            },
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+/// Get the required string argument `name` from the positional or keyword arguments.
+unsafe fn str_arg(
+    ctx: &mut Context,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+    func: &str,
+    name: &str,
+) -> String {
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+    match args.arg_i(0).or_else(|| kwargs.kwarg(name)) {
+        Some(arg) if arg.is_str() => arg.as_str(),
+        Some(arg) => {
+            ctx.set_err_type(&ErrType::TypeError_Runtime_TYPE);
+            panic!(
+                "{func}() argument '{name}' must be str, not {}",
+                arg.type_str()
+            )
+        }
+        None => {
+            ctx.set_err_type(&ErrType::TypeError_Runtime_TYPE);
+            panic!("{func}() missing 1 required positional argument: '{name}'")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_panic<F: FnOnce() + std::panic::UnwindSafe>(func: F) {
+        let result = std::panic::catch_unwind(func);
+        assert!(result.is_err())
+    }
+
+    #[test]
+    fn test_normalize_path() {
+        assert_eq!(
+            normalize_path(Path::new("a/./b/../c")),
+            PathBuf::from("a/c")
+        );
+        assert_eq!(
+            normalize_path(Path::new("a/../../b")),
+            PathBuf::from("../b")
+        );
+        assert_eq!(normalize_path(Path::new("/a/../..")), PathBuf::from("/"));
+    }
+
+    #[test]
+    fn test_resolve_path() {
+        let root = env!("CARGO_MANIFEST_DIR");
+        let mut ctx = Context::new();
+        ctx.module_path = root.to_string();
+        assert_eq!(
+            resolve_path(&mut ctx, "read", "src/../Cargo.toml"),
+            Path::new(root).join("Cargo.toml")
+        );
+        assert_eq!(
+            resolve_path(&mut ctx, "read", &format!("{root}/Cargo.toml")),
+            Path::new(root).join("Cargo.toml")
+        );
+        assert_eq!(
+            resolve_path(&mut ctx, "glob", "src/*/*.rs"),
+            Path::new(root).join("src/*/*.rs")
+        );
+        for filepath in ["../Cargo.toml", "src/../../Cargo.toml", "/"] {
+            let mut ctx = Context::new();
+            ctx.module_path = root.to_string();
+            assert_panic(std::panic::AssertUnwindSafe(move || {
+                resolve_path(&mut ctx, "read", filepath);
+            }));
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_glob_paths_with_symlink() {
+        let temp_dir = std::env::temp_dir().join(format!("kcl_glob_{}", std::process::id()));
+        let root = temp_dir.join("root");
+        let outside = temp_dir.join("outside");
+        std::fs::create_dir_all(root.join("pkg")).unwrap();
+        std::fs::create_dir_all(&outside).unwrap();
+        std::fs::write(root.join("pkg").join("a.k"), "").unwrap();
+        std::fs::write(outside.join("b.k"), "").unwrap();
+        std::os::unix::fs::symlink(&outside, root.join("link")).unwrap();
+        let mut ctx = Context::new();
+        ctx.module_path = root.to_string_lossy().to_string();
+        // The files in the symbolic link to the outside directory are not matched.
+        assert_eq!(glob_paths(&mut ctx, "*/*.k"), vec!["pkg/a.k".to_string()]);
+        std::fs::remove_dir_all(temp_dir).unwrap();
+    }
+}
@@ -13,10 +13,11 @@ type kclvm_value_ref_t = ValueRef;
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_json_encode(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     args: *const kclvm_value_ref_t,
     kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
     let kwargs = ptr_as_ref(kwargs);
 
@@ -36,7 +37,7 @@ pub unsafe extern "C" fn kclvm_json_encode(
 
     if let Some(arg0) = args.arg_i(0) {
         let s = ValueRef::str(arg0.to_json_string_with_option(&opt).as_ref());
-        return s.into_raw();
+        return s.into_raw(ctx);
     }
     panic!("encode() missing 1 required positional argument: 'value'")
 }
@@ -44,15 +45,16 @@ pub unsafe extern "C" fn kclvm_json_encode(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_json_decode(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     args: *const kclvm_value_ref_t,
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
 
     if let Some(arg0) = args.arg_i(0) {
-        match ValueRef::from_json(arg0.as_str().as_ref()) {
-            Ok(x) => return x.into_raw(),
+        match ValueRef::from_json(ctx, arg0.as_str().as_ref()) {
+            Ok(x) => return x.into_raw(ctx),
             Err(err) => panic!("{}", err),
         }
     }
@@ -62,10 +64,12 @@ pub unsafe extern "C" fn kclvm_json_decode(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_json_dump_to_file(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     args: *const kclvm_value_ref_t,
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
+    ctx.check_sandbox("json.dump_to_file");
     let args = ptr_as_ref(args);
 
     if let Some(data) = args.arg_i(0) {
@@ -80,6 +80,9 @@ pub use self::crypto::*;
 pub mod datetime;
 pub use self::datetime::*;
 
+pub mod file;
+pub use self::file::*;
+
 pub mod json;
 pub use self::json::*;
 
@@ -101,9 +104,15 @@ pub use self::stdlib::*;
 pub mod testing;
 pub use self::testing::*;
 
+pub mod toml;
+pub use self::toml::*;
+
 pub mod units;
 pub use self::units::*;
 
+pub mod uuid;
+pub use self::uuid::*;
+
 pub mod yaml;
 pub use self::yaml::*;
 
@@ -29,7 +29,8 @@ type kclvm_value_ref_t = ValueRef;
 ///     sort_keys: bool = False
 ///     ignore_private: bool = True
 ///     ignore_none: bool = False
-///     separator: str = "---\n"
+///     sep: str = "---"
+///     indent: int = 2
 ///
 /// manifests.yaml_stream(values: [any], * , opts: ManifestsYamlStreamOptions = ManifestsYamlStreamOptions {})
 /// ```
@@ -55,7 +56,7 @@ pub unsafe extern "C" fn kclvm_manifests_yaml_stream(
     ctx: *mut kclvm_context_t,
     args: *const kclvm_value_ref_t,
     kwargs: *const kclvm_value_ref_t,
-) {
+) -> *const kclvm_value_ref_t {
     let args = ptr_as_ref(args);
     let kwargs = ptr_as_ref(kwargs);
     let ctx = mut_ptr_as_ref(ctx);
@@ -65,24 +66,7 @@ pub unsafe extern "C" fn kclvm_manifests_yaml_stream(
         Some(opts) => {
             if opts.is_config() {
                 // Get options or default.
-                YamlEncodeOptions {
-                    sort_keys: opts
-                        .get_by_key("sort_keys")
-                        .unwrap_or(ValueRef::bool(false))
-                        .as_bool(),
-                    ignore_private: opts
-                        .get_by_key("ignore_private")
-                        .unwrap_or(ValueRef::bool(false))
-                        .as_bool(),
-                    ignore_none: opts
-                        .get_by_key("ignore_none")
-                        .unwrap_or(ValueRef::bool(false))
-                        .as_bool(),
-                    sep: opts
-                        .get_by_key("sep")
-                        .unwrap_or(ValueRef::str("---"))
-                        .as_str(),
-                }
+                crate::yaml::yaml_encode_options(ctx, &opts)
             } else {
                 panic!(
                     "Invalid options arguments in yaml_stream(): expect config, got {}",
@@ -95,6 +79,7 @@ pub unsafe extern "C" fn kclvm_manifests_yaml_stream(
 
     if let Some(value) = args.arg_i(0) {
         self::yaml::encode_yaml_stream_to_manifests(ctx, &value, opts);
+        kclvm_value_None(ctx)
     } else {
         panic!("yaml_stream() missing 1 required positional argument: 'values'");
     }
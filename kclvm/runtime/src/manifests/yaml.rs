@@ -7,15 +7,19 @@ pub(crate) fn encode_yaml_stream_to_manifests(
     values: &ValueRef,
     opts: YamlEncodeOptions,
 ) {
-    ctx.buffer.custom_manifests_output = Some(
-        values
-            .as_list_ref()
-            .values
-            .iter()
-            .map(|v| v.to_yaml_string_with_options(&opts))
-            .collect::<Vec<String>>()
-            .join(&format!("\n{}\n", opts.sep)),
-    );
+    let manifests = values
+        .as_list_ref()
+        .values
+        .iter()
+        .map(|v| v.to_yaml_string_with_options(&opts))
+        .collect::<Vec<String>>();
+    ctx.buffer.custom_manifests_output = Some(manifests.join(&format!("\n{}\n", opts.sep)));
+    ctx.buffer.custom_manifests = Some(manifests);
+    ctx.buffer.custom_manifests_value = Some(match values.len() {
+        0 => ValueRef::dict(None),
+        1 => values.list_get(0).unwrap(),
+        _ => values.clone(),
+    });
 }
 
 #[cfg(test)]
@@ -79,6 +83,20 @@ mod test_manifests_yaml {
                     ..Default::default()
                 },
             ),
+            (
+                "a:\n    b:\n        - 1\n\n---\nc: 2\n",
+                ValueRef::list(Some(&[
+                    &ValueRef::dict(Some(&[(
+                        "a",
+                        &ValueRef::dict(Some(&[("b", &ValueRef::list_int(&[1]))])),
+                    )])),
+                    &ValueRef::dict(Some(&[("c", &ValueRef::int(2))])),
+                ])),
+                YamlEncodeOptions {
+                    indent: 4,
+                    ..Default::default()
+                },
+            ),
         ];
         let mut ctx = Context::default();
         for (yaml_str, value, opts) in cases {
@@ -87,6 +105,10 @@ mod test_manifests_yaml {
                 Some(yaml_str.to_string()),
                 ctx.buffer.custom_manifests_output
             );
+            assert_eq!(
+                ctx.buffer.custom_manifests.as_ref().map(|m| m.len()),
+                Some(value.len())
+            );
         }
     }
 
@@ -17,17 +17,18 @@ type kclvm_value_ref_t = ValueRef;
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_math_ceil(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     args: *const kclvm_value_ref_t,
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
 
     if let Some(x) = args.arg_i_int(0, None) {
-        return kclvm_value_Int(x);
+        return kclvm_value_Int(ctx, x);
     }
     if let Some(x) = args.arg_i_float(0, None) {
-        return kclvm_value_Int(x.ceil() as i64);
+        return kclvm_value_Int(ctx, x.ceil() as i64);
     }
 
     panic!("ceil() takes exactly one argument (0 given)");
@@ -36,10 +37,11 @@ pub unsafe extern "C" fn kclvm_math_ceil(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_math_factorial(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     args: *const kclvm_value_ref_t,
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     fn factorial(num: i64) -> i64 {
         if num >= 21 {
             // overflow: 21! = 51090942171709440000
@@ -57,12 +59,12 @@ pub unsafe extern "C" fn kclvm_math_factorial(
 
     if let Some(x) = args.arg_i_int(0, None) {
         if x >= 0 {
-            return kclvm_value_Int(factorial(x));
+            return kclvm_value_Int(ctx, factorial(x));
         }
     }
     if let Some(x) = args.arg_i_float(0, None) {
         if x >= 0.0 && (x as i64 as f64) == x {
-            return kclvm_value_Float(factorial(x as i64) as f64);
+            return kclvm_value_Float(ctx, factorial(x as i64) as f64);
         }
     }
     if args.args_len() > 0 {
@@ -74,17 +76,18 @@ pub unsafe extern "C" fn kclvm_math_factorial(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_math_floor(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     args: *const kclvm_value_ref_t,
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
 
     if let Some(x) = args.arg_i_int(0, None) {
-        return kclvm_value_Int(x);
+        return kclvm_value_Int(ctx, x);
     }
     if let Some(x) = args.arg_i_float(0, None) {
-        return kclvm_value_Float(x.floor());
+        return kclvm_value_Float(ctx, x.floor());
     }
 
     panic!("floor() takes exactly one argument (0 given)");
@@ -93,15 +96,16 @@ pub unsafe extern "C" fn kclvm_math_floor(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_math_gcd(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     args: *const kclvm_value_ref_t,
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
 
     if let Some(a) = args.arg_i_int(0, None) {
         if let Some(b) = args.arg_i_int(1, None) {
-            return kclvm_value_Int(num_integer::gcd(a, b));
+            return kclvm_value_Int(ctx, num_integer::gcd(a, b));
         }
     }
 
@@ -171,28 +175,29 @@ pub unsafe extern "C" fn kclvm_math_isnan(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_math_modf(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     args: *const kclvm_value_ref_t,
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
 
     if let Some(x) = args.arg_i_int(0, None) {
         let list = ValueRef::list_float(&[0.0, x as f64]);
-        return list.into_raw();
+        return list.into_raw(ctx);
     }
     if let Some(x) = args.arg_i_float(0, None) {
         if !x.is_finite() {
             if x.is_infinite() {
                 let list = ValueRef::list_float(&[0.0_f64.copysign(x), x]);
-                return list.into_raw();
+                return list.into_raw(ctx);
             } else if x.is_nan() {
                 let list = ValueRef::list_float(&[x, x]);
-                return list.into_raw();
+                return list.into_raw(ctx);
             }
         }
         let list = ValueRef::list_float(&[x.fract(), x.trunc()]);
-        return list.into_raw();
+        return list.into_raw(ctx);
     }
 
     panic!("modf() takes exactly one argument (0 given)");
@@ -201,17 +206,18 @@ pub unsafe extern "C" fn kclvm_math_modf(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_math_exp(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     args: *const kclvm_value_ref_t,
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
 
     if let Some(x) = args.arg_i_int(0, None) {
-        return kclvm_value_Float((x as f64).exp());
+        return kclvm_value_Float(ctx, (x as f64).exp());
     }
     if let Some(x) = args.arg_i_float(0, None) {
-        return kclvm_value_Float(x.exp());
+        return kclvm_value_Float(ctx, x.exp());
     }
     panic!("exp() takes exactly one argument (0 given)");
 }
@@ -219,17 +225,18 @@ pub unsafe extern "C" fn kclvm_math_exp(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_math_expm1(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     args: *const kclvm_value_ref_t,
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
 
     if let Some(x) = args.arg_i_int(0, None) {
-        return kclvm_value_Float((x as f64).exp_m1());
+        return kclvm_value_Float(ctx, (x as f64).exp_m1());
     }
     if let Some(x) = args.arg_i_float(0, None) {
-        return kclvm_value_Float(x.exp_m1());
+        return kclvm_value_Float(ctx, x.exp_m1());
     }
     panic!("expm1() takes exactly one argument (0 given)");
 }
@@ -237,20 +244,21 @@ pub unsafe extern "C" fn kclvm_math_expm1(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_math_log(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     args: *const kclvm_value_ref_t,
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
 
     if let Some(x) = args.arg_i_int(0, None) {
         if let Some(base) = args.arg_i_float(1, Some(std::f64::consts::E)) {
-            return kclvm_value_Int((x as f64).log(base) as i64);
+            return kclvm_value_Int(ctx, (x as f64).log(base) as i64);
         }
     }
     if let Some(x) = args.arg_i_float(0, None) {
         if let Some(base) = args.arg_i_float(1, Some(std::f64::consts::E)) {
-            return kclvm_value_Float(x.log(base));
+            return kclvm_value_Float(ctx, x.log(base));
         }
     }
     panic!("log() takes exactly one argument (0 given)");
@@ -259,17 +267,18 @@ pub unsafe extern "C" fn kclvm_math_log(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_math_log1p(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     args: *const kclvm_value_ref_t,
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
 
     if let Some(x) = args.arg_i_int(0, None) {
-        return kclvm_value_Float(((x + 1) as f64).ln_1p());
+        return kclvm_value_Float(ctx, ((x + 1) as f64).ln_1p());
     }
     if let Some(x) = args.arg_i_float(0, None) {
-        return kclvm_value_Float((x + 1.0).ln_1p());
+        return kclvm_value_Float(ctx, (x + 1.0).ln_1p());
     }
     panic!("log1p() takes exactly one argument (0 given)");
 }
@@ -277,17 +286,18 @@ pub unsafe extern "C" fn kclvm_math_log1p(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_math_log2(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     args: *const kclvm_value_ref_t,
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
 
     if let Some(x) = args.arg_i_int(0, None) {
-        return kclvm_value_Int((x as f64).log2() as i64);
+        return kclvm_value_Int(ctx, (x as f64).log2() as i64);
     }
     if let Some(x) = args.arg_i_float(0, None) {
-        return kclvm_value_Float(x.log2());
+        return kclvm_value_Float(ctx, x.log2());
     }
     panic!("log2() takes exactly one argument (0 given)");
 }
@@ -295,17 +305,18 @@ pub unsafe extern "C" fn kclvm_math_log2(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_math_log10(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     args: *const kclvm_value_ref_t,
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
 
     if let Some(x) = args.arg_i_int(0, None) {
-        return kclvm_value_Float((x as f64).log10());
+        return kclvm_value_Float(ctx, (x as f64).log10());
     }
     if let Some(x) = args.arg_i_float(0, None) {
-        return kclvm_value_Float(x.log10());
+        return kclvm_value_Float(ctx, x.log10());
     }
     panic!("log10() takes exactly one argument (0 given)");
 }
@@ -313,30 +324,31 @@ pub unsafe extern "C" fn kclvm_math_log10(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_math_pow(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     args: *const kclvm_value_ref_t,
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
 
     if let Some(x) = args.arg_i_int(0, None) {
         if let Some(n) = args.arg_i_int(1, None) {
             if n < 0 {
-                return kclvm_value_Float((x as f64).powf(n as f64));
+                return kclvm_value_Float(ctx, (x as f64).powf(n as f64));
             } else {
-                return kclvm_value_Int(x.pow(n as u32));
+                return kclvm_value_Int(ctx, x.pow(n as u32));
             }
         }
         if let Some(n) = args.arg_i_float(1, None) {
-            return kclvm_value_Float((x as f64).powf(n));
+            return kclvm_value_Float(ctx, (x as f64).powf(n));
         }
     }
     if let Some(x) = args.arg_i_float(0, None) {
         if let Some(n) = args.arg_i_int(1, None) {
-            return kclvm_value_Float(x.powi(n as i32));
+            return kclvm_value_Float(ctx, x.powi(n as i32));
         }
         if let Some(n) = args.arg_i_float(1, None) {
-            return kclvm_value_Float(x.powf(n));
+            return kclvm_value_Float(ctx, x.powf(n));
         }
     }
     panic!("pow() takes exactly one argument (0 given)");
@@ -345,17 +357,18 @@ pub unsafe extern "C" fn kclvm_math_pow(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_math_sqrt(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     args: *const kclvm_value_ref_t,
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
 
     if let Some(x) = args.arg_i_int(0, None) {
-        return kclvm_value_Float((x as f64).sqrt());
+        return kclvm_value_Float(ctx, (x as f64).sqrt());
     }
     if let Some(x) = args.arg_i_float(0, None) {
-        return kclvm_value_Float(x.sqrt());
+        return kclvm_value_Float(ctx, x.sqrt());
     }
     panic!("sqrt() takes exactly one argument (0 given)");
 }
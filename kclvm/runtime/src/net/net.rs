@@ -17,10 +17,11 @@ type kclvm_value_ref_t = ValueRef;
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_net_split_host_port(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     args: *const kclvm_value_ref_t,
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
 
     if let Some(string) = args.arg_i_str(0, None) {
@@ -28,7 +29,7 @@ pub unsafe extern "C" fn kclvm_net_split_host_port(
         for s in string.split(':') {
             list.list_append(&ValueRef::str(s));
         }
-        return list.into_raw();
+        return list.into_raw(ctx);
     }
 
     panic!("split_host_port() missing 1 required positional argument: 'ip_end_point'");
@@ -39,20 +40,21 @@ pub unsafe extern "C" fn kclvm_net_split_host_port(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_net_join_host_port(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     args: *const kclvm_value_ref_t,
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
 
     if let Some(host) = args.arg_i_str(0, None) {
         if let Some(port) = args.arg_i_int(1, None) {
             let s = format!("{host}:{port}");
-            return ValueRef::str(s.as_ref()).into_raw();
+            return ValueRef::str(s.as_ref()).into_raw(ctx);
         }
         if let Some(port) = args.arg_i_str(1, None) {
             let s = format!("{host}:{port}");
-            return ValueRef::str(s.as_ref()).into_raw();
+            return ValueRef::str(s.as_ref()).into_raw(ctx);
         }
     }
     panic!("join_host_port() missing 2 required positional arguments: 'host' and 'port'");
@@ -84,6 +86,7 @@ pub unsafe extern "C" fn kclvm_net_parse_IP(
     args: *const kclvm_value_ref_t,
     kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     kclvm_net_IP_string(ctx, args, kwargs)
 }
 
@@ -96,6 +99,7 @@ pub unsafe extern "C" fn kclvm_net_to_IP4(
     args: *const kclvm_value_ref_t,
     kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     kclvm_net_IP_string(ctx, args, kwargs)
 }
 
@@ -108,6 +112,7 @@ pub unsafe extern "C" fn kclvm_net_to_IP16(
     args: *const kclvm_value_ref_t,
     kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     kclvm_net_IP_string(ctx, args, kwargs)
 }
 
@@ -116,20 +121,21 @@ pub unsafe extern "C" fn kclvm_net_to_IP16(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_net_IP_string(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     args: *const kclvm_value_ref_t,
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
 
     if let Some(ip) = args.arg_i_str(0, None) {
         if let Ok(addr) = Ipv4Addr::from_str(ip.as_ref()) {
             let s = format!("{addr}");
-            return ValueRef::str(s.as_ref()).into_raw();
+            return ValueRef::str(s.as_ref()).into_raw(ctx);
         }
         if let Ok(addr) = Ipv6Addr::from_str(ip.as_ref()) {
             let s = format!("{addr}");
-            return ValueRef::str(s.as_ref()).into_raw();
+            return ValueRef::str(s.as_ref()).into_raw(ctx);
         }
 
         return kclvm_value_False();
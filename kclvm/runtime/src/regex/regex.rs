@@ -51,10 +51,11 @@ pub unsafe extern "C" fn kclvm_regex_match(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_regex_replace(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     args: *const kclvm_value_ref_t,
     _kwargs: *const kclvm_value_ref_t,
 ) -> *mut kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
 
     if let Some(string) = args.arg_i_str(0, None) {
@@ -63,7 +64,7 @@ pub unsafe extern "C" fn kclvm_regex_replace(
                 if let Some(count) = args.arg_i_int(3, Some(0)) {
                     let re = fancy_regex::Regex::new(pattern.as_ref()).unwrap();
                     let s = re.replacen(string.as_ref(), count as usize, replace.as_ref() as &str);
-                    return ValueRef::str(&s).into_raw();
+                    return ValueRef::str(&s).into_raw(ctx);
                 }
             }
         }
@@ -96,10 +97,11 @@ pub unsafe extern "C" fn kclvm_regex_compile(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_regex_findall(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     args: *const kclvm_value_ref_t,
     _kwargs: *const kclvm_value_ref_t,
 ) -> *mut kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
 
     if let Some(string) = args.arg_i_str(0, None) {
@@ -123,7 +125,7 @@ pub unsafe extern "C" fn kclvm_regex_findall(
                 }
             }
 
-            return list.into_raw();
+            return list.into_raw(ctx);
         }
     }
 
@@ -159,10 +161,11 @@ pub unsafe extern "C" fn kclvm_regex_search(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_regex_split(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     args: *const kclvm_value_ref_t,
     _kwargs: *const kclvm_value_ref_t,
 ) -> *mut kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
 
     if let Some(string) = args.arg_i_str(0, None) {
@@ -193,7 +196,7 @@ pub unsafe extern "C" fn kclvm_regex_split(
                 for s in fields {
                     list.list_append(&ValueRef::str(s.as_ref()));
                 }
-                return list.into_raw();
+                return list.into_raw(ctx);
             }
         }
     }
@@ -8,14 +8,15 @@ type kclvm_value_ref_t = ValueRef;
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_assert(
+    ctx: *mut kclvm_context_t,
     value: *const kclvm_value_ref_t,
     msg: *const kclvm_value_ref_t,
 ) {
+    let ctx = mut_ptr_as_ref(ctx);
     let value = ptr_as_ref(value);
     let msg = ptr_as_ref(msg);
 
     if !value.is_truthy() {
-        let ctx = Context::current_context_mut();
         ctx.set_err_type(&ErrType::AssertionError_TYPE);
 
         let msg = msg.as_str();
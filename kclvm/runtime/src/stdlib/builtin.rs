@@ -6,12 +6,13 @@ use crate::*;
 
 impl Context {
     pub fn builtin_option_init(&mut self, key: &str, value: &str) {
-        if let Ok(x) = ValueRef::from_json(value) {
-            self.app_args.insert(key.to_string(), x.into_raw() as u64);
+        if let Ok(x) = ValueRef::from_json(self, value) {
+            let x = x.into_raw(self);
+            self.app_args.insert(key.to_string(), x as u64);
             return;
         }
-        self.app_args
-            .insert(key.to_string(), ValueRef::str(value).into_raw() as u64);
+        let x = ValueRef::str(value).into_raw(self);
+        self.app_args.insert(key.to_string(), x as u64);
     }
 
     pub fn builtin_option_reset(&mut self) {
@@ -153,8 +154,7 @@ impl ValueRef {
         }
     }
 
-    pub fn convert_to_int(&self, base: Option<&ValueRef>) -> ValueRef {
-        let ctx = crate::Context::current_context_mut();
+    pub fn convert_to_int(&self, ctx: &mut Context, base: Option<&ValueRef>) -> ValueRef {
         let strict_range_check_i32 = ctx.cfg.strict_range_check;
         let strict_range_check_i64 = ctx.cfg.debug_mode || !ctx.cfg.strict_range_check;
 
@@ -168,13 +168,11 @@ impl ValueRef {
 
                 if ctx.cfg.debug_mode {
                     if int_32_overflow {
-                        let ctx = Context::current_context_mut();
                         ctx.set_err_type(&ErrType::IntOverflow_TYPE);
 
                         panic!("{v_i128}: A 32 bit integer overflow");
                     }
                     if int_64_overflow {
-                        let ctx = Context::current_context_mut();
                         ctx.set_err_type(&ErrType::IntOverflow_TYPE);
 
                         panic!("{v_i128}: A 64 bit integer overflow");
@@ -200,8 +198,7 @@ impl ValueRef {
         }
     }
 
-    pub fn convert_to_float(&self) -> ValueRef {
-        let ctx = crate::Context::current_context_mut();
+    pub fn convert_to_float(&self, ctx: &mut Context) -> ValueRef {
         let strict_range_check_i32 = ctx.cfg.strict_range_check;
         let strict_range_check_i64 = ctx.cfg.debug_mode || !ctx.cfg.strict_range_check;
 
@@ -361,7 +358,7 @@ impl ValueRef {
         }
     }
 
-    pub fn sum(&self, init_value: &ValueRef) -> ValueRef {
+    pub fn sum(&self, ctx: &mut Context, init_value: &ValueRef) -> ValueRef {
         match &*self.rc.borrow() {
             Value::list_value(list) => {
                 let mut result = match &*init_value.rc.borrow() {
@@ -370,7 +367,7 @@ impl ValueRef {
                 };
                 for val in list.values.iter() {
                     //xx_bin_aug_add() might modify the value of init_value
-                    result = result.bin_add(val)
+                    result = result.bin_add(ctx, val)
                 }
                 result
             }
@@ -451,7 +448,7 @@ pub fn list(iterable: Option<&ValueRef>) -> ValueRef {
     }
 }
 
-pub fn dict(iterable: Option<&ValueRef>) -> ValueRef {
+pub fn dict(ctx: &mut Context, iterable: Option<&ValueRef>) -> ValueRef {
     match iterable {
         Some(val) => {
             let mut iter = val.iter();
@@ -462,7 +459,7 @@ pub fn dict(iterable: Option<&ValueRef>) -> ValueRef {
                 let k = iter.cur_key.clone();
                 match &*k.rc.borrow() {
                     Value::str_value(str) => {
-                        result.dict_insert(str.as_str(), &elem, Default::default(), 0);
+                        result.dict_insert(ctx, str.as_str(), &elem, Default::default(), 0);
                     }
                     _ => {
                         let mut elem_iter = elem.iter();
@@ -471,7 +468,7 @@ pub fn dict(iterable: Option<&ValueRef>) -> ValueRef {
                         }
                         let k = elem_iter.next(val).unwrap().to_string();
                         let v = elem_iter.next(val).unwrap();
-                        result.dict_insert(k.as_str(), v, Default::default(), 0);
+                        result.dict_insert(ctx, k.as_str(), v, Default::default(), 0);
                     }
                 };
             }
@@ -481,12 +478,16 @@ pub fn dict(iterable: Option<&ValueRef>) -> ValueRef {
     }
 }
 
-pub fn range(start: &ValueRef, stop: &ValueRef, step: &ValueRef) -> ValueRef {
+pub fn range(ctx: &mut Context, start: &ValueRef, stop: &ValueRef, step: &ValueRef) -> ValueRef {
     match (&*start.rc.borrow(), &*stop.rc.borrow(), &*step.rc.borrow()) {
         (Value::int_value(start), Value::int_value(stop), Value::int_value(step)) => {
             if *step == 0 {
                 panic!("range() step argument must not be zero");
             }
+            // Check the limit before allocating all the range values.
+            let len = (*stop as i128 - *start as i128 + *step as i128 - step.signum() as i128)
+                / *step as i128;
+            ctx.check_objects_limit(len.max(0) as u64);
             let mut cur = *start;
             let mut list = ValueRef::list(None);
             let cmp = if *step > 0 {
@@ -641,6 +642,7 @@ pub fn type_of(x: &ValueRef, full_name: &ValueRef) -> ValueRef {
 mod test_builtin {
 
     use crate::*;
+    use std::panic::AssertUnwindSafe;
 
     #[test]
     fn test_sorted() {
@@ -834,7 +836,13 @@ mod test_builtin {
 
     #[test]
     fn test_range() {
-        let mut list = range(&ValueRef::int(1), &ValueRef::int(5), &ValueRef::int(1));
+        let mut ctx = Context::new();
+        let mut list = range(
+            &mut ctx,
+            &ValueRef::int(1),
+            &ValueRef::int(5),
+            &ValueRef::int(1),
+        );
         let mut expect_list = ValueRef::list(Some(&[
             &ValueRef::int(1),
             &ValueRef::int(2),
@@ -842,11 +850,21 @@ mod test_builtin {
             &ValueRef::int(4),
         ]));
         assert!(expect_list.cmp_equal(&list));
-        list = range(&ValueRef::int(1), &ValueRef::int(5), &ValueRef::int(2));
+        list = range(
+            &mut ctx,
+            &ValueRef::int(1),
+            &ValueRef::int(5),
+            &ValueRef::int(2),
+        );
         expect_list = ValueRef::list(Some(&[&ValueRef::int(1), &ValueRef::int(3)]));
         assert!(expect_list.cmp_equal(&list));
 
-        list = range(&ValueRef::int(5), &ValueRef::int(1), &ValueRef::int(-1));
+        list = range(
+            &mut ctx,
+            &ValueRef::int(5),
+            &ValueRef::int(1),
+            &ValueRef::int(-1),
+        );
         expect_list = ValueRef::list(Some(&[
             &ValueRef::int(5),
             &ValueRef::int(4),
@@ -880,6 +898,35 @@ mod test_builtin {
         assert!(list.min_value().cmp_equal(&ValueRef::int(1)));
     }
 
+    #[test]
+    fn test_range_limit() {
+        unsafe {
+            let ctx = kclvm_context_new_with_options(&ContextOptions {
+                limits: ContextLimits {
+                    max_objects: 10,
+                    ..Default::default()
+                },
+                ..Default::default()
+            });
+            let range = |stop: i64| {
+                builtin::range(
+                    mut_ptr_as_ref(ctx),
+                    &ValueRef::int(0),
+                    &ValueRef::int(stop),
+                    &ValueRef::int(1),
+                )
+            };
+            assert_eq!(range(5).len(), 5);
+            assert_panic(
+                "the max object count is 10",
+                AssertUnwindSafe(move || {
+                    range(100);
+                }),
+            );
+            kclvm_context_delete(ctx);
+        }
+    }
+
     #[test]
     fn test_sorted_normal() {
         //list=[]
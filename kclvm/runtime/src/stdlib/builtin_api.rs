@@ -27,7 +27,7 @@ pub unsafe extern "C" fn kclvm_builtin_option_reset(
     let ctx = mut_ptr_as_ref(ctx);
 
     ctx.builtin_option_reset();
-    kclvm_value_Undefined()
+    kclvm_value_Undefined(ctx)
 }
 
 // def kcl_option(name: str, *, type="", required=False, default=None, help="", file="", line=0) -> typing.Any:
@@ -215,28 +215,29 @@ pub unsafe extern "C" fn kclvm_builtin_option(
     if let Some(arg0) = args.arg_0() {
         if let Some(x) = ctx.app_args.get(&arg0.as_str()) {
             if *x == 0 {
-                return kclvm_value_Undefined();
+                return kclvm_value_Undefined(ctx);
             }
 
             let opt_value = mut_ptr_as_ref((*x) as *mut kclvm_value_ref_t);
 
             if let Some(kwarg_type) = kwargs.kwarg_str("type", None) {
-                return _value_to_type(opt_value, kwarg_type, ctx.cfg.list_option_mode).into_raw();
+                return _value_to_type(opt_value, kwarg_type, ctx.cfg.list_option_mode)
+                    .into_raw(ctx);
             }
 
             return (*x) as *mut kclvm_value_ref_t;
         } else if let Some(kwarg_default) = kwargs.kwarg("default") {
             if let Some(kwarg_type) = kwargs.kwarg_str("type", None) {
                 return _value_to_type(&kwarg_default, kwarg_type, ctx.cfg.list_option_mode)
-                    .into_raw();
+                    .into_raw(ctx);
             }
 
-            return kwarg_default.into_raw();
+            return kwarg_default.into_raw(ctx);
         }
     }
 
     if list_option_mode {
-        return kclvm_value_None();
+        return kclvm_value_None(ctx);
     }
 
     let required = kwargs.kwarg_bool("required", Some(false)).unwrap();
@@ -245,7 +246,7 @@ pub unsafe extern "C" fn kclvm_builtin_option(
         panic!("option('{name}') must be initialized, try '-D {name}=?' argument");
     }
 
-    kclvm_value_None()
+    kclvm_value_None(ctx)
 }
 
 #[no_mangle]
@@ -255,7 +256,8 @@ pub unsafe extern "C" fn kclvm_builtin_print(
     args: *const kclvm_value_ref_t,
     kwargs: *const kclvm_value_ref_t,
 ) -> *mut kclvm_value_ref_t {
-    let _ctx = mut_ptr_as_ref(ctx);
+    let ctx = mut_ptr_as_ref(ctx);
+    ctx.check_sandbox("print");
     let args = ptr_as_ref(args);
     let kwargs = ptr_as_ref(kwargs);
     // args
@@ -271,20 +273,21 @@ pub unsafe extern "C" fn kclvm_builtin_print(
     } else {
         println!();
     }
-    kclvm_value_None()
+    kclvm_value_None(ctx)
 }
 
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_builtin_len(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     args: *const kclvm_value_ref_t,
     _kwargs: *const kclvm_value_ref_t,
 ) -> *mut kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
 
     if let Some(arg) = args.arg_0() {
-        return kclvm_value_Int(arg.len() as i64);
+        return kclvm_value_Int(ctx, arg.len() as i64);
     }
     panic!("len() takes exactly one argument (0 given)");
 }
@@ -330,13 +333,13 @@ pub unsafe extern "C" fn kclvm_builtin_sorted(
     args: *const kclvm_value_ref_t,
     kwargs: *const kclvm_value_ref_t,
 ) -> *mut kclvm_value_ref_t {
-    let _ctx = mut_ptr_as_ref(ctx);
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
     let kwargs = ptr_as_ref(kwargs);
 
     if let Some(arg0) = args.arg_0() {
         let reverse = kwargs.kwarg("reverse");
-        return arg0.sorted(reverse.as_ref()).into_raw();
+        return arg0.sorted(reverse.as_ref()).into_raw(ctx);
     }
     panic!("sorted() takes exactly one argument (0 given)");
 }
@@ -348,13 +351,13 @@ pub unsafe extern "C" fn kclvm_builtin_int(
     args: *const kclvm_value_ref_t,
     kwargs: *const kclvm_value_ref_t,
 ) -> *mut kclvm_value_ref_t {
-    let _ctx = mut_ptr_as_ref(ctx);
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
     let kwargs = ptr_as_ref(kwargs);
 
     if let Some(arg0) = args.arg_0() {
         let base = args.arg_i(1).or_else(|| kwargs.kwarg("base"));
-        return arg0.convert_to_int(base.as_ref()).into_raw();
+        return arg0.convert_to_int(ctx, base.as_ref()).into_raw(ctx);
     }
     panic!("int() takes exactly one argument (0 given)");
 }
@@ -366,12 +369,12 @@ pub unsafe extern "C" fn kclvm_builtin_float(
     args: *const kclvm_value_ref_t,
     kwargs: *const kclvm_value_ref_t,
 ) -> *mut kclvm_value_ref_t {
-    let _ctx = mut_ptr_as_ref(ctx);
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
     let _kwargs = ptr_as_ref(kwargs);
 
     if let Some(arg0) = args.arg_0() {
-        return arg0.convert_to_float().into_raw();
+        return arg0.convert_to_float(ctx).into_raw(ctx);
     }
     panic!("float() takes exactly one argument (0 given)");
 }
@@ -379,14 +382,15 @@ pub unsafe extern "C" fn kclvm_builtin_float(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_builtin_bool(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     args: *const kclvm_value_ref_t,
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
 
     if let Some(arg0) = args.arg_0() {
-        return ValueRef::bool(arg0.is_truthy()).into_raw();
+        return ValueRef::bool(arg0.is_truthy()).into_raw(ctx);
     }
     panic!("bool() takes exactly one argument (0 given)");
 }
@@ -394,16 +398,17 @@ pub unsafe extern "C" fn kclvm_builtin_bool(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_builtin_str(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     args: *const kclvm_value_ref_t,
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
 
     if let Some(arg0) = args.arg_0() {
-        return ValueRef::str(&arg0.to_string()).into_raw();
+        return ValueRef::str(&arg0.to_string()).into_raw(ctx);
     }
-    ValueRef::str("").into_raw()
+    ValueRef::str("").into_raw(ctx)
 }
 
 #[no_mangle]
@@ -413,14 +418,14 @@ pub unsafe extern "C" fn kclvm_builtin_max(
     args: *const kclvm_value_ref_t,
     kwargs: *const kclvm_value_ref_t,
 ) -> *mut kclvm_value_ref_t {
-    let _ctx = mut_ptr_as_ref(ctx);
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
     let _kwargs = ptr_as_ref(kwargs);
     if args.args_len() > 1 {
-        return args.max_value().into_raw();
+        return args.max_value().into_raw(ctx);
     }
     if let Some(arg0) = args.arg_0() {
-        return arg0.max_value().into_raw();
+        return arg0.max_value().into_raw(ctx);
     }
     panic!("max() takes exactly one argument (0 given)");
 }
@@ -432,14 +437,14 @@ pub unsafe extern "C" fn kclvm_builtin_min(
     args: *const kclvm_value_ref_t,
     kwargs: *const kclvm_value_ref_t,
 ) -> *mut kclvm_value_ref_t {
-    let _ctx = mut_ptr_as_ref(ctx);
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
     let _kwargs = ptr_as_ref(kwargs);
     if args.args_len() > 1 {
-        return args.min_value().into_raw();
+        return args.min_value().into_raw(ctx);
     }
     if let Some(arg0) = args.arg_0() {
-        return arg0.min_value().into_raw();
+        return arg0.min_value().into_raw(ctx);
     }
     panic!("min() takes exactly one argument (0 given)");
 }
@@ -451,11 +456,11 @@ pub unsafe extern "C" fn kclvm_builtin_multiplyof(
     args: *const kclvm_value_ref_t,
     kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
-    let _ctx = mut_ptr_as_ref(ctx);
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
     let _kwargs = ptr_as_ref(kwargs);
     if let (Some(arg0), Some(arg1)) = (args.arg_i(0), args.arg_i(1)) {
-        return builtin::multiplyof(&arg0, &arg1).into_raw();
+        return builtin::multiplyof(&arg0, &arg1).into_raw(ctx);
     }
     panic!(
         "multiplyof() takes exactly two argument ({} given)",
@@ -470,11 +475,11 @@ pub unsafe extern "C" fn kclvm_builtin_abs(
     args: *const kclvm_value_ref_t,
     kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
-    let _ctx = mut_ptr_as_ref(ctx);
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
     let _kwargs = ptr_as_ref(kwargs);
     if let Some(arg0) = args.arg_0() {
-        return arg0.abs().into_raw();
+        return arg0.abs().into_raw(ctx);
     }
     panic!("abs() takes exactly one argument (0 given)");
 }
@@ -502,11 +507,11 @@ pub unsafe extern "C" fn kclvm_builtin_hex(
     args: *const kclvm_value_ref_t,
     kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
-    let _ctx = mut_ptr_as_ref(ctx);
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
     let _kwargs = ptr_as_ref(kwargs);
     if let Some(arg0) = args.arg_0() {
-        return arg0.hex().into_raw();
+        return arg0.hex().into_raw(ctx);
     }
     panic!("hex() takes exactly one argument (0 given)");
 }
@@ -518,15 +523,15 @@ pub unsafe extern "C" fn kclvm_builtin_sum(
     args: *const kclvm_value_ref_t,
     kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
-    let _ctx = mut_ptr_as_ref(ctx);
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
     let _kwargs = ptr_as_ref(kwargs);
     match args.arg_i(0) {
         Some(arg0) => match args.arg_i(1) {
-            Some(arg1) => arg0.sum(&arg1).into_raw(),
-            _ => arg0.sum(&ValueRef::int(0)).into_raw(),
+            Some(arg1) => arg0.sum(ctx, &arg1).into_raw(ctx),
+            _ => arg0.sum(ctx, &ValueRef::int(0)).into_raw(ctx),
         },
-        _ => kclvm_value_Undefined(),
+        _ => kclvm_value_Undefined(ctx),
     }
 }
 
@@ -537,15 +542,15 @@ pub unsafe extern "C" fn kclvm_builtin_pow(
     args: *const kclvm_value_ref_t,
     kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
-    let _ctx = mut_ptr_as_ref(ctx);
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
     let _kwargs = ptr_as_ref(kwargs);
     match (args.arg_i(0), args.arg_i(1)) {
         (Some(arg0), Some(arg1)) => match args.arg_i(2) {
-            Some(arg2) => builtin::pow(&arg0, &arg1, &arg2).into_raw(),
-            _ => builtin::pow(&arg0, &arg1, &ValueRef::none()).into_raw(),
+            Some(arg2) => builtin::pow(&arg0, &arg1, &arg2).into_raw(ctx),
+            _ => builtin::pow(&arg0, &arg1, &ValueRef::none()).into_raw(ctx),
         },
-        _ => kclvm_value_Undefined(),
+        _ => kclvm_value_Undefined(ctx),
     }
 }
 
@@ -556,15 +561,15 @@ pub unsafe extern "C" fn kclvm_builtin_round(
     args: *const kclvm_value_ref_t,
     kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
-    let _ctx = mut_ptr_as_ref(ctx);
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
     let _kwargs = ptr_as_ref(kwargs);
     match args.arg_i(0) {
         Some(arg0) => match args.arg_i(1) {
-            Some(arg1) => builtin::round(&arg0, &arg1).into_raw(),
-            _ => builtin::round(&arg0, &ValueRef::none()).into_raw(),
+            Some(arg1) => builtin::round(&arg0, &arg1).into_raw(ctx),
+            _ => builtin::round(&arg0, &ValueRef::none()).into_raw(ctx),
         },
-        _ => kclvm_value_Undefined(),
+        _ => kclvm_value_Undefined(ctx),
     }
 }
 
@@ -575,10 +580,10 @@ pub unsafe extern "C" fn kclvm_builtin_zip(
     args: *const kclvm_value_ref_t,
     kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
-    let _ctx = mut_ptr_as_ref(ctx);
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
     let _kwargs = ptr_as_ref(kwargs);
-    args.zip().into_raw()
+    args.zip().into_raw(ctx)
 }
 
 #[no_mangle]
@@ -588,16 +593,16 @@ pub unsafe extern "C" fn kclvm_builtin_list(
     args: *const kclvm_value_ref_t,
     kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
-    let _ctx = mut_ptr_as_ref(ctx);
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
     let _kwargs = ptr_as_ref(kwargs);
     if args.args_len() > 0 {
         if let Some(arg0) = args.arg_0() {
-            return builtin::list(Some(&arg0)).into_raw();
+            return builtin::list(Some(&arg0)).into_raw(ctx);
         }
         panic!("invalid arguments in list() function");
     } else {
-        builtin::list(None).into_raw()
+        builtin::list(None).into_raw(ctx)
     }
 }
 
@@ -608,15 +613,17 @@ pub unsafe extern "C" fn kclvm_builtin_dict(
     args: *const kclvm_value_ref_t,
     kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
-    let _ctx = mut_ptr_as_ref(ctx);
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
     let kwargs = ptr_as_ref(kwargs);
     let mut dict = ValueRef::dict(None);
     if let Some(arg0) = args.arg_0() {
-        dict.dict_insert_unpack(&builtin::dict(Some(&arg0)));
+        let v = builtin::dict(ctx, Some(&arg0));
+        dict.dict_insert_unpack(ctx, &v);
     }
-    dict.dict_insert_unpack(&builtin::dict(Some(kwargs)));
-    dict.into_raw()
+    let v = builtin::dict(ctx, Some(kwargs));
+    dict.dict_insert_unpack(ctx, &v);
+    dict.into_raw(ctx)
 }
 
 #[no_mangle]
@@ -626,14 +633,14 @@ pub unsafe extern "C" fn kclvm_builtin_typeof(
     args: *const kclvm_value_ref_t,
     kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
-    let _ctx = mut_ptr_as_ref(ctx);
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
     let kwargs = ptr_as_ref(kwargs);
     if let Some(arg0) = args.arg_0() {
         if let Some(full_name) = kwargs.kwarg("full_name") {
-            return builtin::type_of(&arg0, &full_name).into_raw();
+            return builtin::type_of(&arg0, &full_name).into_raw(ctx);
         }
-        return builtin::type_of(&arg0, &ValueRef::bool(false)).into_raw();
+        return builtin::type_of(&arg0, &ValueRef::bool(false)).into_raw(ctx);
     }
 
     panic!("typeof() missing 1 required positional argument: 'x'");
@@ -646,11 +653,11 @@ pub unsafe extern "C" fn kclvm_builtin_bin(
     args: *const kclvm_value_ref_t,
     kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
-    let _ctx = mut_ptr_as_ref(ctx);
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
     let _kwargs = ptr_as_ref(kwargs);
     if let Some(arg0) = args.arg_0() {
-        return arg0.bin().into_raw();
+        return arg0.bin().into_raw(ctx);
     }
     panic!("bin() takes exactly one argument (0 given)");
 }
@@ -662,11 +669,11 @@ pub unsafe extern "C" fn kclvm_builtin_oct(
     args: *const kclvm_value_ref_t,
     kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
-    let _ctx = mut_ptr_as_ref(ctx);
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
     let _kwargs = ptr_as_ref(kwargs);
     if let Some(arg0) = args.arg_0() {
-        return arg0.oct().into_raw();
+        return arg0.oct().into_raw(ctx);
     }
     panic!("oct() takes exactly one argument (0 given)");
 }
@@ -678,14 +685,14 @@ pub unsafe extern "C" fn kclvm_builtin_ord(
     args: *const kclvm_value_ref_t,
     kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
-    let _ctx = mut_ptr_as_ref(ctx);
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
     let _kwargs = ptr_as_ref(kwargs);
     let _ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
     let _kwargs = ptr_as_ref(kwargs);
     if let Some(arg0) = args.arg_0() {
-        return arg0.ord().into_raw();
+        return arg0.ord().into_raw(ctx);
     }
     panic!("ord() takes exactly one argument (0 given)");
 }
@@ -697,17 +704,17 @@ pub unsafe extern "C" fn kclvm_builtin_range(
     args: *const kclvm_value_ref_t,
     kwargs: *const kclvm_value_ref_t,
 ) -> *mut kclvm_value_ref_t {
-    let _ctx = mut_ptr_as_ref(ctx);
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
     let _kwargs = ptr_as_ref(kwargs);
     match args.arg_i(0) {
         Some(arg0) => match args.arg_i(1) {
             Some(arg1) => match args.arg_i(2) {
-                Some(arg2) => builtin::range(&arg0, &arg1, &arg2).into_raw(),
-                _ => builtin::range(&arg0, &arg1, &ValueRef::int(1)).into_raw(),
+                Some(arg2) => builtin::range(ctx, &arg0, &arg1, &arg2).into_raw(ctx),
+                _ => builtin::range(ctx, &arg0, &arg1, &ValueRef::int(1)).into_raw(ctx),
             },
-            _ => builtin::range(&ValueRef::int(0), &arg0, &ValueRef::int(1)).into_raw(),
+            _ => builtin::range(ctx, &ValueRef::int(0), &arg0, &ValueRef::int(1)).into_raw(ctx),
         },
-        _ => kclvm_value_Undefined(),
+        _ => kclvm_value_Undefined(ctx),
     }
 }
@@ -3,10 +3,19 @@
 
 use crate::*;
 
+use std::cell::Cell;
 use std::os::raw::c_char;
 
-#[allow(non_upper_case_globals)]
-static mut _plugin_handler_fn_ptr: u64 = 0;
+std::thread_local! {
+    /// The plugin handler registered in the current thread, which is used by
+    /// the runtime contexts created in the thread.
+    static PLUGIN_HANDLER_FN_PTR: Cell<u64> = Cell::new(0);
+}
+
+/// Get the plugin handler registered in the current thread.
+pub fn plugin_handler_fn_ptr() -> u64 {
+    PLUGIN_HANDLER_FN_PTR.with(|fn_ptr| fn_ptr.get())
+}
 
 #[no_mangle]
 #[runtime_fn]
@@ -17,9 +26,8 @@ pub extern "C" fn kclvm_plugin_init(
         kwargs_json: *const c_char,
     ) -> *const c_char,
 ) {
-    unsafe {
-        _plugin_handler_fn_ptr = fn_ptr as usize as u64;
-    }
+    let fn_ptr = fn_ptr as usize as u64;
+    PLUGIN_HANDLER_FN_PTR.with(|handler| handler.set(fn_ptr));
 }
 
 // import kcl_plugin.hello
@@ -30,26 +38,28 @@ pub extern "C" fn kclvm_plugin_init(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_plugin_invoke(
+    ctx: *mut kclvm_context_t,
     method: *const i8,
     args: *const kclvm_value_ref_t,
     kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
-    let args_s = kclvm_value_to_json_value_with_null(args);
-    let kwargs_s = kclvm_value_to_json_value_with_null(kwargs);
+    let ctx = mut_ptr_as_ref(ctx);
+    ctx.check_sandbox(c2str(method));
+    let args_s = kclvm_value_to_json_value_with_null(ctx, args);
+    let kwargs_s = kclvm_value_to_json_value_with_null(ctx, kwargs);
 
     let args_json = kclvm_value_Str_ptr(args_s);
     let kwargs_json = kclvm_value_Str_ptr(kwargs_s);
 
-    let result_json = kclvm_plugin_invoke_json(method, args_json, kwargs_json);
+    let result_json = kclvm_plugin_invoke_json(ctx, method, args_json, kwargs_json);
 
     // Value delete by context.
     // kclvm_value_delete(args_s);
     // kclvm_value_delete(kwargs_s);
 
-    let ptr = kclvm_value_from_json(result_json);
+    let ptr = kclvm_value_from_json(ctx, result_json);
     {
         if let Some(msg) = ptr_as_ref(ptr).dict_get_value("__kcl_PanicInfo__") {
-            let ctx = Context::current_context_mut();
             ctx.set_err_type(&ErrType::EvaluationError_TYPE);
 
             panic!("{}", msg.as_str());
@@ -62,17 +72,19 @@ pub unsafe extern "C" fn kclvm_plugin_invoke(
 #[cfg(not(target_arch = "wasm32"))]
 #[no_mangle]
 #[runtime_fn]
-pub extern "C" fn kclvm_plugin_invoke_json(
+pub unsafe extern "C" fn kclvm_plugin_invoke_json(
+    ctx: *mut kclvm_context_t,
     method: *const i8,
     args: *const c_char,
     kwargs: *const c_char,
 ) -> *const c_char {
+    let plugin_handler_fn_ptr = mut_ptr_as_ref(ctx).plugin_handler_fn_ptr;
     unsafe {
-        if _plugin_handler_fn_ptr == 0 {
+        if plugin_handler_fn_ptr == 0 {
             panic!("plugin is nil, should call kclvm_plugin_init at first");
         }
 
-        let ptr = (&_plugin_handler_fn_ptr as *const u64) as *const ()
+        let ptr = (&plugin_handler_fn_ptr as *const u64) as *const ()
             as *const extern "C" fn(
                 method: *const i8,
                 args: *const c_char,
@@ -86,7 +98,8 @@ pub extern "C" fn kclvm_plugin_invoke_json(
 #[cfg(target_arch = "wasm32")]
 #[no_mangle]
 #[runtime_fn]
-pub extern "C" fn kclvm_plugin_invoke_json(
+pub unsafe extern "C" fn kclvm_plugin_invoke_json(
+    _ctx: *mut kclvm_context_t,
     method: *const i8,
     args: *const c_char,
     kwargs: *const c_char,
@@ -0,0 +1,111 @@
+//! KCL toml system module
+//!
+//! Copyright 2021 The KCL Authors. All rights reserved.
+#![allow(clippy::missing_safety_doc)]
+
+use crate::*;
+
+#[allow(non_camel_case_types)]
+type kclvm_value_ref_t = ValueRef;
+
+// def KMANGLED_encode(data, sort_keys=False, ignore_private=False, ignore_none=False):
+
+#[no_mangle]
+#[runtime_fn]
+pub unsafe extern "C" fn kclvm_toml_encode(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+
+    let opt = toml_encode_options(kwargs);
+    if let Some(arg0) = args.arg_i(0) {
+        return ValueRef::str(&encode_toml(ctx, &arg0, &opt)).into_raw(ctx);
+    }
+    panic!("encode() missing 1 required positional argument: 'data'")
+}
+
+// def KMANGLED_decode(value: str):
+
+#[no_mangle]
+#[runtime_fn]
+pub unsafe extern "C" fn kclvm_toml_decode(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    _kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
+    let args = ptr_as_ref(args);
+
+    if let Some(arg0) = args.arg_i(0) {
+        match ValueRef::from_toml(arg0.as_str().as_ref()) {
+            Ok(x) => return x.into_raw(ctx),
+            Err(err) => {
+                ctx.set_err_type(&ErrType::EvaluationError_TYPE);
+                panic!("{}", err)
+            }
+        }
+    }
+    panic!("decode() missing 1 required positional argument: 'value'")
+}
+
+// def KMANGLED_dump_to_file(data, filename: str, sort_keys=False, ignore_private=False, ignore_none=False):
+
+#[no_mangle]
+#[runtime_fn]
+pub unsafe extern "C" fn kclvm_toml_dump_to_file(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
+    ctx.check_sandbox("toml.dump_to_file");
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+
+    let opt = toml_encode_options(kwargs);
+    match (
+        args.arg_i(0),
+        args.arg_i(1).or_else(|| kwargs.kwarg("filename")),
+    ) {
+        (Some(data), Some(filename)) => {
+            let toml = encode_toml(ctx, &data, &opt);
+            let filename = filename.as_str();
+            if let Err(err) = std::fs::write(&filename, toml) {
+                ctx.set_err_type(&ErrType::EvaluationError_TYPE);
+                panic!("failed to write the file '{filename}': {err}")
+            }
+            kclvm_value_None(ctx)
+        }
+        _ => {
+            panic!("dump_to_file() missing 2 required positional arguments: 'data' and 'filename'")
+        }
+    }
+}
+
+fn toml_encode_options(kwargs: &ValueRef) -> TomlEncodeOptions {
+    let mut opt = TomlEncodeOptions::default();
+    if let Some(sort_keys) = kwargs.kwarg_bool("sort_keys", None) {
+        opt.sort_keys = sort_keys;
+    }
+    if let Some(ignore_private) = kwargs.kwarg_bool("ignore_private", None) {
+        opt.ignore_private = ignore_private;
+    }
+    if let Some(ignore_none) = kwargs.kwarg_bool("ignore_none", None) {
+        opt.ignore_none = ignore_none;
+    }
+    opt
+}
+
+fn encode_toml(ctx: &mut Context, data: &ValueRef, opt: &TomlEncodeOptions) -> String {
+    match data.to_toml_string_with_options(opt) {
+        Ok(toml) => toml,
+        Err(err) => {
+            ctx.set_err_type(&ErrType::TypeError_Runtime_TYPE);
+            panic!("{}", err)
+        }
+    }
+}
@@ -52,13 +52,13 @@ pub unsafe extern "C" fn kclvm_units_to_n(
     args: *const kclvm_value_ref_t,
     kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
-    let _ctx = mut_ptr_as_ref(ctx);
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
     let _kwargs = ptr_as_ref(kwargs);
 
-    let num = args.arg_0().unwrap().convert_to_float().as_float();
+    let num = args.arg_0().unwrap().convert_to_float(ctx).as_float();
     let s = to_unit(num, to_unit_suffix::n);
-    return ValueRef::str(s.as_ref()).into_raw();
+    return ValueRef::str(s.as_ref()).into_raw(ctx);
 }
 
 // to_u(num: int) -> str
@@ -70,13 +70,13 @@ pub unsafe extern "C" fn kclvm_units_to_u(
     args: *const kclvm_value_ref_t,
     kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
-    let _ctx = mut_ptr_as_ref(ctx);
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
     let _kwargs = ptr_as_ref(kwargs);
 
-    let num = args.arg_0().unwrap().convert_to_float().as_float();
+    let num = args.arg_0().unwrap().convert_to_float(ctx).as_float();
     let s = to_unit(num, to_unit_suffix::u);
-    return ValueRef::str(s.as_ref()).into_raw();
+    return ValueRef::str(s.as_ref()).into_raw(ctx);
 }
 
 // to_m(num: int) -> str
@@ -88,13 +88,13 @@ pub unsafe extern "C" fn kclvm_units_to_m(
     args: *const kclvm_value_ref_t,
     kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
-    let _ctx = mut_ptr_as_ref(ctx);
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
     let _kwargs = ptr_as_ref(kwargs);
 
-    let num = args.arg_0().unwrap().convert_to_float().as_float();
+    let num = args.arg_0().unwrap().convert_to_float(ctx).as_float();
     let s = to_unit(num, to_unit_suffix::m);
-    return ValueRef::str(s.as_ref()).into_raw();
+    return ValueRef::str(s.as_ref()).into_raw(ctx);
 }
 
 // to_K(num: int) -> str
@@ -106,13 +106,13 @@ pub unsafe extern "C" fn kclvm_units_to_K(
     args: *const kclvm_value_ref_t,
     kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
-    let _ctx = mut_ptr_as_ref(ctx);
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
     let _kwargs = ptr_as_ref(kwargs);
 
     if let Some(num) = args.arg_i_num(0, None) {
         let s = to_unit(num, to_unit_suffix::K);
-        return ValueRef::str(s.as_ref()).into_raw();
+        return ValueRef::str(s.as_ref()).into_raw(ctx);
     }
     panic!("to_K() missing 1 required positional argument: 'num'");
 }
@@ -126,13 +126,13 @@ pub unsafe extern "C" fn kclvm_units_to_M(
     args: *const kclvm_value_ref_t,
     kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
-    let _ctx = mut_ptr_as_ref(ctx);
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
     let _kwargs = ptr_as_ref(kwargs);
 
     if let Some(num) = args.arg_i_num(0, None) {
         let s = to_unit(num, to_unit_suffix::M);
-        return ValueRef::str(s.as_ref()).into_raw();
+        return ValueRef::str(s.as_ref()).into_raw(ctx);
     }
     panic!("to_M() missing 1 required positional argument: 'num'");
 }
@@ -146,13 +146,13 @@ pub unsafe extern "C" fn kclvm_units_to_G(
     args: *const kclvm_value_ref_t,
     kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
-    let _ctx = mut_ptr_as_ref(ctx);
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
     let _kwargs = ptr_as_ref(kwargs);
 
     if let Some(num) = args.arg_i_num(0, None) {
         let s = to_unit(num, to_unit_suffix::G);
-        return ValueRef::str(s.as_ref()).into_raw();
+        return ValueRef::str(s.as_ref()).into_raw(ctx);
     }
     panic!("to_G() missing 1 required positional argument: 'num'");
 }
@@ -166,13 +166,13 @@ pub unsafe extern "C" fn kclvm_units_to_T(
     args: *const kclvm_value_ref_t,
     kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
-    let _ctx = mut_ptr_as_ref(ctx);
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
     let _kwargs = ptr_as_ref(kwargs);
 
     if let Some(num) = args.arg_i_num(0, None) {
         let s = to_unit(num, to_unit_suffix::T);
-        return ValueRef::str(s.as_ref()).into_raw();
+        return ValueRef::str(s.as_ref()).into_raw(ctx);
     }
     panic!("to_T() missing 1 required positional argument: 'num'");
 }
@@ -186,13 +186,13 @@ pub unsafe extern "C" fn kclvm_units_to_P(
     args: *const kclvm_value_ref_t,
     kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
-    let _ctx = mut_ptr_as_ref(ctx);
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
     let _kwargs = ptr_as_ref(kwargs);
 
     if let Some(num) = args.arg_i_num(0, None) {
         let s = to_unit(num, to_unit_suffix::P);
-        return ValueRef::str(s.as_ref()).into_raw();
+        return ValueRef::str(s.as_ref()).into_raw(ctx);
     }
     panic!("to_P() missing 1 required positional argument: 'num'");
 }
@@ -206,13 +206,13 @@ pub unsafe extern "C" fn kclvm_units_to_Ki(
     args: *const kclvm_value_ref_t,
     kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
-    let _ctx = mut_ptr_as_ref(ctx);
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
     let _kwargs = ptr_as_ref(kwargs);
 
     if let Some(num) = args.arg_i_num(0, None) {
         let s = to_unit(num, to_unit_suffix::Ki);
-        return ValueRef::str(s.as_ref()).into_raw();
+        return ValueRef::str(s.as_ref()).into_raw(ctx);
     }
     panic!("to_Ki() missing 1 required positional argument: 'num'");
 }
@@ -226,13 +226,13 @@ pub unsafe extern "C" fn kclvm_units_to_Mi(
     args: *const kclvm_value_ref_t,
     kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
-    let _ctx = mut_ptr_as_ref(ctx);
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
     let _kwargs = ptr_as_ref(kwargs);
 
     if let Some(num) = args.arg_i_num(0, None) {
         let s = to_unit(num, to_unit_suffix::Mi);
-        return ValueRef::str(s.as_ref()).into_raw();
+        return ValueRef::str(s.as_ref()).into_raw(ctx);
     }
     panic!("to_Mi() missing 1 required positional argument: 'num'");
 }
@@ -246,13 +246,13 @@ pub unsafe extern "C" fn kclvm_units_to_Gi(
     args: *const kclvm_value_ref_t,
     kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
-    let _ctx = mut_ptr_as_ref(ctx);
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
     let _kwargs = ptr_as_ref(kwargs);
 
     if let Some(num) = args.arg_i_num(0, None) {
         let s = to_unit(num, to_unit_suffix::Gi);
-        return ValueRef::str(s.as_ref()).into_raw();
+        return ValueRef::str(s.as_ref()).into_raw(ctx);
     }
     panic!("to_Gi() missing 1 required positional argument: 'num'");
 }
@@ -266,13 +266,13 @@ pub unsafe extern "C" fn kclvm_units_to_Ti(
     args: *const kclvm_value_ref_t,
     kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
-    let _ctx = mut_ptr_as_ref(ctx);
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
     let _kwargs = ptr_as_ref(kwargs);
 
     if let Some(num) = args.arg_i_num(0, None) {
         let s = to_unit(num, to_unit_suffix::Ti);
-        return ValueRef::str(s.as_ref()).into_raw();
+        return ValueRef::str(s.as_ref()).into_raw(ctx);
     }
     panic!("to_Ti() missing 1 required positional argument: 'num'");
 }
@@ -286,13 +286,13 @@ pub unsafe extern "C" fn kclvm_units_to_Pi(
     args: *const kclvm_value_ref_t,
     kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
-    let _ctx = mut_ptr_as_ref(ctx);
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
     let _kwargs = ptr_as_ref(kwargs);
 
     if let Some(num) = args.arg_i_num(0, None) {
         let s = to_unit(num, to_unit_suffix::Pi);
-        return ValueRef::str(s.as_ref()).into_raw();
+        return ValueRef::str(s.as_ref()).into_raw(ctx);
     }
     panic!("to_Pi() missing 1 required positional argument: 'num'");
 }
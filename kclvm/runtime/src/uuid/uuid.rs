@@ -0,0 +1,265 @@
+//! KCL uuid system module
+//!
+//! The UUIDs are the lowercase hyphenated strings e.g., `6ba7b810-9dad-11d1-80b4-00c04fd430c8`.
+//!
+//! Copyright 2021 The KCL Authors. All rights reserved.
+#![allow(clippy::missing_safety_doc)]
+
+extern crate rand;
+extern crate sha1;
+extern crate sha2;
+
+use sha2::{Digest, Sha256};
+
+use crate::*;
+
+#[allow(non_camel_case_types)]
+type kclvm_value_ref_t = ValueRef;
+
+/// The well-known namespaces of the name-based UUIDs defined in RFC 4122.
+const UUID_NAMESPACES: [(&str, &str); 4] = [
+    ("dns", "6ba7b810-9dad-11d1-80b4-00c04fd430c8"),
+    ("url", "6ba7b811-9dad-11d1-80b4-00c04fd430c8"),
+    ("oid", "6ba7b812-9dad-11d1-80b4-00c04fd430c8"),
+    ("x500", "6ba7b814-9dad-11d1-80b4-00c04fd430c8"),
+];
+
+/// The default length of the short IDs.
+const DEFAULT_SHORT_ID_LENGTH: i64 = 8;
+
+// def KMANGLED_v4() -> str:
+
+#[no_mangle]
+#[runtime_fn]
+pub unsafe extern "C" fn kclvm_uuid_v4(
+    ctx: *mut kclvm_context_t,
+    _args: *const kclvm_value_ref_t,
+    _kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
+    // The random UUIDs make the outputs not reproducible.
+    ctx.check_deterministic("uuid.v4");
+    let mut bytes: [u8; 16] = rand::random();
+    set_version(&mut bytes, 4);
+    ValueRef::str(&format_uuid(&bytes)).into_raw(ctx)
+}
+
+// def KMANGLED_v5(namespace: str, name: str) -> str:
+
+#[no_mangle]
+#[runtime_fn]
+pub unsafe extern "C" fn kclvm_uuid_v5(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+    let namespace = str_arg(ctx, args, kwargs, "v5", 0, "namespace");
+    let name = str_arg(ctx, args, kwargs, "v5", 1, "name");
+    let namespace_bytes = match parse_namespace(&namespace) {
+        Some(bytes) => bytes,
+        None => {
+            ctx.set_err_type(&ErrType::EvaluationError_TYPE);
+            panic!("v5() invalid namespace '{namespace}', expect 'dns', 'url', 'oid', 'x500' or a UUID")
+        }
+    };
+    ValueRef::str(&uuid_v5(&namespace_bytes, &name)).into_raw(ctx)
+}
+
+// def KMANGLED_short_id(value: any, length: int = 8) -> str:
+
+#[no_mangle]
+#[runtime_fn]
+pub unsafe extern "C" fn kclvm_uuid_short_id(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+    let value = match args.arg_i(0).or_else(|| kwargs.kwarg("value")) {
+        Some(value) => value,
+        None => {
+            ctx.set_err_type(&ErrType::TypeError_Runtime_TYPE);
+            panic!("short_id() missing 1 required positional argument: 'value'")
+        }
+    };
+    let length = args
+        .arg_i_int(1, None)
+        .or_else(|| kwargs.kwarg_int("length", None))
+        .unwrap_or(DEFAULT_SHORT_ID_LENGTH);
+    if !(1..=64).contains(&length) {
+        ctx.set_err_type(&ErrType::EvaluationError_TYPE);
+        panic!("short_id() length must be between 1 and 64, got {length}")
+    }
+    ValueRef::str(&short_id(&value)[..length as usize]).into_raw(ctx)
+}
+
+/// Set the version and the RFC 4122 variant bits of the UUID bytes.
+fn set_version(bytes: &mut [u8; 16], version: u8) {
+    bytes[6] = (bytes[6] & 0x0f) | (version << 4);
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+}
+
+/// Format the UUID bytes to the lowercase hyphenated string.
+fn format_uuid(bytes: &[u8; 16]) -> String {
+    let hex: String = bytes.iter().map(|b| format!("{b:02x}")).collect();
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
+
+/// Parse the UUID string with or without the hyphens.
+fn parse_uuid(value: &str) -> Option<[u8; 16]> {
+    let hex = if value.len() == 36 {
+        let hyphens = [8, 13, 18, 23];
+        let valid = value
+            .char_indices()
+            .all(|(i, c)| hyphens.contains(&i) == (c == '-'));
+        if !valid {
+            return None;
+        }
+        value.replace('-', "")
+    } else {
+        value.to_string()
+    };
+    if hex.len() != 32 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let mut bytes = [0u8; 16];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).ok()?;
+    }
+    Some(bytes)
+}
+
+/// Parse the well-known namespace name e.g., `dns` or the namespace UUID.
+fn parse_namespace(namespace: &str) -> Option<[u8; 16]> {
+    let namespace = UUID_NAMESPACES
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(namespace))
+        .map(|(_, uuid)| *uuid)
+        .unwrap_or(namespace);
+    parse_uuid(namespace)
+}
+
+/// Generate the name-based UUID version 5 with the SHA-1 hash of the namespace and the name.
+fn uuid_v5(namespace: &[u8; 16], name: &str) -> String {
+    let mut hasher = sha1::Sha1::new();
+    hasher.update(namespace);
+    hasher.update(name.as_bytes());
+    let mut bytes = [0u8; 16];
+    bytes.copy_from_slice(&hasher.digest().bytes()[..16]);
+    set_version(&mut bytes, 5);
+    format_uuid(&bytes)
+}
+
+/// The hex SHA-256 hash of the value encoded as JSON with the sorted keys, so the equal
+/// configs have the same ID regardless of the attribute order.
+fn short_id(value: &ValueRef) -> String {
+    let json = value.to_json_string_with_option(&JsonEncodeOptions {
+        sort_keys: true,
+        ..Default::default()
+    });
+    let result = Sha256::digest(json.as_bytes());
+    result.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Get the required string argument `name` from the positional argument at `index`
+/// or the keyword arguments.
+fn str_arg(
+    ctx: &mut Context,
+    args: &ValueRef,
+    kwargs: &ValueRef,
+    func: &str,
+    index: usize,
+    name: &str,
+) -> String {
+    match args.arg_i(index).or_else(|| kwargs.kwarg(name)) {
+        Some(arg) if arg.is_str() => arg.as_str(),
+        Some(arg) => {
+            ctx.set_err_type(&ErrType::TypeError_Runtime_TYPE);
+            panic!(
+                "{func}() argument '{name}' must be str, not {}",
+                arg.type_str()
+            )
+        }
+        None => {
+            ctx.set_err_type(&ErrType::TypeError_Runtime_TYPE);
+            panic!("{func}() missing required argument: '{name}'")
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_uuid {
+    use super::*;
+
+    #[test]
+    fn test_uuid_v5() {
+        // The test vectors of the Python `uuid.uuid5` function.
+        let dns = parse_namespace("dns").unwrap();
+        assert_eq!(
+            uuid_v5(&dns, "python.org"),
+            "886313e1-3b8a-5372-9b90-0c9aee199e5d"
+        );
+        let url = parse_namespace("6ba7b811-9dad-11d1-80b4-00c04fd430c8").unwrap();
+        assert_eq!(
+            uuid_v5(&url, "https://kcl-lang.io"),
+            uuid_v5(&parse_namespace("URL").unwrap(), "https://kcl-lang.io")
+        );
+    }
+
+    #[test]
+    fn test_parse_uuid() {
+        let bytes = parse_uuid("6ba7b8109dad11d180b400c04fd430c8").unwrap();
+        assert_eq!(format_uuid(&bytes), "6ba7b810-9dad-11d1-80b4-00c04fd430c8");
+        assert!(parse_uuid("6ba7b810-9dad-11d1-80b4-00c04fd430c").is_none());
+        assert!(parse_uuid("6ba7b8109-dad-11d1-80b4-00c04fd430c8").is_none());
+        assert!(parse_uuid("zba7b810-9dad-11d1-80b4-00c04fd430c8").is_none());
+        assert!(parse_namespace("kcl").is_none());
+    }
+
+    #[test]
+    fn test_set_version() {
+        let mut bytes = [0xffu8; 16];
+        set_version(&mut bytes, 4);
+        assert_eq!(format_uuid(&bytes), "ffffffff-ffff-4fff-bfff-ffffffffffff");
+    }
+
+    #[test]
+    fn test_short_id() {
+        let mut ctx = Context::new();
+        let a = ValueRef::from_json(&mut ctx, r#"{"name": "app", "port": 80}"#).unwrap();
+        let b = ValueRef::from_json(&mut ctx, r#"{"port": 80, "name": "app"}"#).unwrap();
+        let c = ValueRef::from_json(&mut ctx, r#"{"name": "app", "port": 8080}"#).unwrap();
+        assert_eq!(short_id(&a), short_id(&b));
+        assert_ne!(short_id(&a), short_id(&c));
+        assert_eq!(short_id(&a).len(), 64);
+    }
+
+    #[test]
+    fn test_uuid_v4_deterministic() {
+        let prev_hook = std::panic::take_hook();
+        // Disable print panic info in stderr.
+        std::panic::set_hook(Box::new(|_| {}));
+        let result = std::panic::catch_unwind(|| {
+            let mut ctx = Context::new();
+            ctx.deterministic = true;
+            ctx.check_deterministic("uuid.v4");
+        });
+        std::panic::set_hook(prev_hook);
+        assert_eq!(
+            result.unwrap_err().downcast_ref::<String>().unwrap(),
+            "the function 'uuid.v4' is not allowed in the deterministic mode"
+        );
+    }
+}
@@ -1,7 +1,7 @@
 // Copyright 2021 The KCL Authors. All rights reserved.
 #![allow(clippy::missing_safety_doc)]
 
-use std::mem::transmute_copy;
+use std::{cell::Cell, mem::transmute_copy};
 
 use crate::*;
 
@@ -67,36 +67,58 @@ pub unsafe extern "C" fn kclvm_context_set_import_names(
 
 // singleton
 
-#[allow(non_camel_case_types, non_upper_case_globals)]
-static mut kclvm_value_Undefined_obj: usize = 0;
-
-#[allow(non_camel_case_types, non_upper_case_globals)]
-static mut kclvm_value_None_obj: usize = 0;
-
-#[allow(non_camel_case_types, non_upper_case_globals)]
-static mut kclvm_value_Bool_true_obj: usize = 0;
-
-#[allow(non_camel_case_types, non_upper_case_globals)]
-static mut kclvm_value_Bool_false_obj: usize = 0;
-
-#[allow(non_camel_case_types, non_upper_case_globals)]
-static mut kclvm_value_Int_0_obj: usize = 0;
+// The singleton values are cached in the thread local storage, because the
+// values are not thread safe and can not be shared between threads, and they
+// are not owned by any runtime context thus they are kept after the context
+// is deleted.
+std::thread_local! {
+    static KCLVM_VALUE_BOOL_TRUE_OBJ: Cell<usize> = Cell::new(0);
+    static KCLVM_VALUE_BOOL_FALSE_OBJ: Cell<usize> = Cell::new(0);
+    static KCLVM_VALUE_INT_0_OBJ: Cell<usize> = Cell::new(0);
+    static KCLVM_VALUE_FLOAT_0_OBJ: Cell<usize> = Cell::new(0);
+}
+
+/// Get the singleton value pointer stored in `key` or new it using `value`.
+fn singleton_value(
+    key: &'static std::thread::LocalKey<Cell<usize>>,
+    value: impl FnOnce() -> ValueRef,
+) -> *mut kclvm_value_ref_t {
+    key.with(|obj| {
+        if obj.get() == 0 {
+            obj.set(Box::into_raw(Box::new(value())) as usize);
+        }
+        obj.get() as *mut kclvm_value_ref_t
+    })
+}
 
-#[allow(non_camel_case_types, non_upper_case_globals)]
-static mut kclvm_value_Float_0_obj: usize = 0;
+/// Whether the value pointer `p` is a singleton value pointer.
+fn is_singleton_value(p: *mut kclvm_value_ref_t) -> bool {
+    [
+        &KCLVM_VALUE_BOOL_TRUE_OBJ,
+        &KCLVM_VALUE_BOOL_FALSE_OBJ,
+        &KCLVM_VALUE_INT_0_OBJ,
+        &KCLVM_VALUE_FLOAT_0_OBJ,
+    ]
+    .iter()
+    .any(|key| key.with(|obj| obj.get() == p as usize))
+}
 
 // Undefine/None
 
 #[no_mangle]
 #[runtime_fn]
-pub extern "C" fn kclvm_value_Undefined() -> *mut kclvm_value_ref_t {
-    new_mut_ptr(ValueRef::undefined())
+pub unsafe extern "C" fn kclvm_value_Undefined(
+    ctx: *mut kclvm_context_t,
+) -> *mut kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
+    new_mut_ptr(ctx, ValueRef::undefined())
 }
 
 #[no_mangle]
 #[runtime_fn]
-pub extern "C" fn kclvm_value_None() -> *mut kclvm_value_ref_t {
-    new_mut_ptr(ValueRef::none())
+pub unsafe extern "C" fn kclvm_value_None(ctx: *mut kclvm_context_t) -> *mut kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
+    new_mut_ptr(ctx, ValueRef::none())
 }
 
 // bool/int/float/str
@@ -116,82 +138,80 @@ pub extern "C" fn kclvm_value_False() -> *mut kclvm_value_ref_t {
 #[no_mangle]
 #[runtime_fn]
 pub extern "C" fn kclvm_value_Bool(v: kclvm_bool_t) -> *mut kclvm_value_ref_t {
-    unsafe {
-        if v != 0 {
-            if kclvm_value_Bool_true_obj == 0 {
-                kclvm_value_Bool_true_obj = new_mut_ptr(ValueRef::bool(true)) as usize;
-            }
-            kclvm_value_Bool_true_obj as *mut kclvm_value_ref_t
-        } else {
-            if kclvm_value_Bool_false_obj == 0 {
-                kclvm_value_Bool_false_obj = new_mut_ptr(ValueRef::bool(false)) as usize;
-            }
-            kclvm_value_Bool_false_obj as *mut kclvm_value_ref_t
-        }
+    if v != 0 {
+        singleton_value(&KCLVM_VALUE_BOOL_TRUE_OBJ, || ValueRef::bool(true))
+    } else {
+        singleton_value(&KCLVM_VALUE_BOOL_FALSE_OBJ, || ValueRef::bool(false))
     }
 }
 
 #[no_mangle]
 #[runtime_fn]
-pub extern "C" fn kclvm_value_Int(v: kclvm_int_t) -> *mut kclvm_value_ref_t {
+pub unsafe extern "C" fn kclvm_value_Int(
+    ctx: *mut kclvm_context_t,
+    v: kclvm_int_t,
+) -> *mut kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     if v == 0 {
-        unsafe {
-            if kclvm_value_Int_0_obj == 0 {
-                kclvm_value_Int_0_obj = new_mut_ptr(ValueRef::int(0)) as usize;
-            }
-            return kclvm_value_Int_0_obj as *mut kclvm_value_ref_t;
-        }
+        return singleton_value(&KCLVM_VALUE_INT_0_OBJ, || ValueRef::int(0));
     }
-    new_mut_ptr(ValueRef::int(v))
+    new_mut_ptr(ctx, ValueRef::int(v))
 }
 
 #[no_mangle]
 #[runtime_fn]
-pub extern "C" fn kclvm_value_Float(v: kclvm_float_t) -> *mut kclvm_value_ref_t {
+pub unsafe extern "C" fn kclvm_value_Float(
+    ctx: *mut kclvm_context_t,
+    v: kclvm_float_t,
+) -> *mut kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     if v == 0.0 {
-        unsafe {
-            if kclvm_value_Float_0_obj == 0 {
-                kclvm_value_Float_0_obj = new_mut_ptr(ValueRef::float(0.0)) as usize;
-            }
-            return kclvm_value_Float_0_obj as *mut kclvm_value_ref_t;
-        }
+        return singleton_value(&KCLVM_VALUE_FLOAT_0_OBJ, || ValueRef::float(0.0));
     }
-    new_mut_ptr(ValueRef::float(v))
+    new_mut_ptr(ctx, ValueRef::float(v))
 }
 
 #[no_mangle]
 #[runtime_fn]
-pub extern "C" fn kclvm_value_Unit(
+pub unsafe extern "C" fn kclvm_value_Unit(
+    ctx: *mut kclvm_context_t,
     v: kclvm_float_t,
     raw: kclvm_int_t,
     unit: *const kclvm_char_t,
 ) -> *mut kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let unit = c2str(unit);
-    new_mut_ptr(ValueRef::unit(v, raw, unit))
+    new_mut_ptr(ctx, ValueRef::unit(v, raw, unit))
 }
 
 #[no_mangle]
 #[runtime_fn]
-pub unsafe extern "C" fn kclvm_value_Str(v: *const kclvm_char_t) -> *mut kclvm_value_ref_t {
+pub unsafe extern "C" fn kclvm_value_Str(
+    ctx: *mut kclvm_context_t,
+    v: *const kclvm_char_t,
+) -> *mut kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     unsafe {
         if v.is_null() || *v == '\0' as i8 {
-            return new_mut_ptr(ValueRef::str(""));
+            return new_mut_ptr(ctx, ValueRef::str(""));
         }
     }
-    return new_mut_ptr(ValueRef::str(c2str(v)));
+    return new_mut_ptr(ctx, ValueRef::str(c2str(v)));
 }
 
 // list/dict/schema
 
 #[no_mangle]
 #[runtime_fn]
-pub extern "C" fn kclvm_value_List() -> *mut kclvm_value_ref_t {
-    new_mut_ptr(ValueRef::list(None))
+pub unsafe extern "C" fn kclvm_value_List(ctx: *mut kclvm_context_t) -> *mut kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
+    new_mut_ptr(ctx, ValueRef::list(None))
 }
 
 #[no_mangle]
 #[runtime_fn]
-pub extern "C" fn kclvm_value_List6(
+pub unsafe extern "C" fn kclvm_value_List6(
+    ctx: *mut kclvm_context_t,
     v1: *const kclvm_value_ref_t,
     v2: *const kclvm_value_ref_t,
     v3: *const kclvm_value_ref_t,
@@ -199,16 +219,18 @@ pub extern "C" fn kclvm_value_List6(
     v5: *const kclvm_value_ref_t,
     v6: *const kclvm_value_ref_t,
 ) -> *mut kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let values: Vec<&ValueRef> = vec![v1, v2, v3, v4, v5, v6]
         .into_iter()
         .map(ptr_as_ref)
         .collect();
-    new_mut_ptr(ValueRef::list(Some(values.as_slice())))
+    new_mut_ptr(ctx, ValueRef::list(Some(values.as_slice())))
 }
 
 #[no_mangle]
 #[runtime_fn]
-pub extern "C" fn kclvm_value_List10(
+pub unsafe extern "C" fn kclvm_value_List10(
+    ctx: *mut kclvm_context_t,
     v1: *const kclvm_value_ref_t,
     v2: *const kclvm_value_ref_t,
     v3: *const kclvm_value_ref_t,
@@ -220,19 +242,22 @@ pub extern "C" fn kclvm_value_List10(
     v9: *const kclvm_value_ref_t,
     v10: *const kclvm_value_ref_t,
 ) -> *mut kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let values: Vec<&ValueRef> = vec![v1, v2, v3, v4, v5, v6, v7, v8, v9, v10]
         .into_iter()
         .map(ptr_as_ref)
         .collect();
-    new_mut_ptr(ValueRef::list(Some(values.as_slice())))
+    new_mut_ptr(ctx, ValueRef::list(Some(values.as_slice())))
 }
 
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_value_ListN(
+    ctx: *mut kclvm_context_t,
     n: kclvm_int_t,
     elem_values: *const *const kclvm_value_ref_t,
 ) -> *mut kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let mut list = ListValue::default();
 
     unsafe {
@@ -241,25 +266,28 @@ pub unsafe extern "C" fn kclvm_value_ListN(
             list.values.push(v.clone());
         }
 
-        ValueRef::from(Value::list_value(Box::new(list))).into_raw()
+        ValueRef::from(Value::list_value(Box::new(list))).into_raw(ctx)
     }
 }
 
 #[no_mangle]
 #[runtime_fn]
-pub extern "C" fn kclvm_value_Dict() -> *mut kclvm_value_ref_t {
-    new_mut_ptr(ValueRef::dict(None))
+pub unsafe extern "C" fn kclvm_value_Dict(ctx: *mut kclvm_context_t) -> *mut kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
+    new_mut_ptr(ctx, ValueRef::dict(None))
 }
 
 #[no_mangle]
 #[runtime_fn]
-pub extern "C" fn kclvm_value_Schema() -> *mut kclvm_value_ref_t {
-    new_mut_ptr(ValueRef::schema())
+pub unsafe extern "C" fn kclvm_value_Schema(ctx: *mut kclvm_context_t) -> *mut kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
+    new_mut_ptr(ctx, ValueRef::schema())
 }
 
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_value_schema_with_config(
+    ctx: *mut kclvm_context_t,
     schema_dict: *const kclvm_value_ref_t,
     config: *const kclvm_value_ref_t,
     config_meta: *const kclvm_value_ref_t,
@@ -270,6 +298,7 @@ pub unsafe extern "C" fn kclvm_value_schema_with_config(
     instance_pkgpath: *const kclvm_value_ref_t,
     optional_mapping: *const kclvm_value_ref_t,
 ) -> *mut kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let schema_dict = ptr_as_ref(schema_dict);
     // Config dict
     let config = ptr_as_ref(config);
@@ -293,7 +322,6 @@ pub unsafe extern "C" fn kclvm_value_schema_with_config(
     let schema =
         schema_dict.dict_to_schema(name, pkgpath, &config_keys, config_meta, optional_mapping);
     // Runtime context
-    let ctx = Context::current_context();
     if record_instance.is_truthy()
         && (instance_pkgpath.is_empty() || instance_pkgpath == MAIN_PKG_PATH)
     {
@@ -309,56 +337,61 @@ pub unsafe extern "C" fn kclvm_value_schema_with_config(
     }
     // Dict to schema
     if is_sub_schema.is_truthy() {
-        schema.into_raw()
+        schema.into_raw(ctx)
     } else {
-        schema_dict.clone().into_raw()
+        schema_dict.clone().into_raw(ctx)
     }
 }
 
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_value_Function(
+    ctx: *mut kclvm_context_t,
     fn_ptr: *const u64,
     closure: *const kclvm_value_ref_t,
     name: *const kclvm_char_t,
     is_external: kclvm_bool_t,
 ) -> *mut kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let closure = ptr_as_ref(closure);
     let name = c2str(name);
-    new_mut_ptr(ValueRef::func(
-        fn_ptr as u64,
-        0,
-        closure.clone(),
-        name,
-        "",
-        is_external != 0,
-    ))
+    new_mut_ptr(
+        ctx,
+        ValueRef::func(
+            fn_ptr as u64,
+            0,
+            closure.clone(),
+            name,
+            "",
+            is_external != 0,
+        ),
+    )
 }
 
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_value_Function_using_ptr(
+    ctx: *mut kclvm_context_t,
     fn_ptr: *const u64,
     name: *const kclvm_char_t,
 ) -> *mut kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let name = c2str(name);
-    new_mut_ptr(ValueRef::func(
-        fn_ptr as u64,
-        0,
-        ValueRef::none(),
-        name,
-        "",
-        false,
-    ))
+    new_mut_ptr(
+        ctx,
+        ValueRef::func(fn_ptr as u64, 0, ValueRef::none(), name, "", false),
+    )
 }
 
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_value_schema_function(
+    ctx: *mut kclvm_context_t,
     fn_ptr: *const u64,
     check_fn_ptr: *const u64,
     tpe: *const kclvm_char_t,
 ) -> *mut kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     // Schema function closures
     let is_sub_schema = ValueRef::bool(false);
     let config_meta = ValueRef::dict(None);
@@ -394,10 +427,10 @@ pub unsafe extern "C" fn kclvm_value_schema_function(
         runtime_type,
         false,
     );
-    let ctx = Context::current_context_mut();
-    let mut all_schemas = ctx.all_schemas.borrow_mut();
-    all_schemas.insert(runtime_type.to_string(), schema_func.clone());
-    new_mut_ptr(schema_func)
+    ctx.all_schemas
+        .borrow_mut()
+        .insert(runtime_type.to_string(), schema_func.clone());
+    new_mut_ptr(ctx, schema_func)
 }
 
 // ----------------------------------------------------------------------------
@@ -406,13 +439,17 @@ pub unsafe extern "C" fn kclvm_value_schema_function(
 
 #[no_mangle]
 #[runtime_fn]
-pub unsafe extern "C" fn kclvm_value_from_json(s: *const kclvm_char_t) -> *mut kclvm_value_ref_t {
+pub unsafe extern "C" fn kclvm_value_from_json(
+    ctx: *mut kclvm_context_t,
+    s: *const kclvm_char_t,
+) -> *mut kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     if s.is_null() {
-        return kclvm_value_Undefined();
+        return kclvm_value_Undefined(ctx);
     }
-    match ValueRef::from_json(c2str(s)) {
-        Ok(x) => x.into_raw(),
-        _ => kclvm_value_Undefined(),
+    match ValueRef::from_json(ctx, c2str(s)) {
+        Ok(x) => x.into_raw(ctx),
+        _ => kclvm_value_Undefined(ctx),
     }
 }
 
@@ -428,83 +465,98 @@ pub unsafe extern "C" fn kclvm_value_to_json(p: *const kclvm_value_ref_t) -> *mu
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_value_to_json_value(
+    ctx: *mut kclvm_context_t,
     p: *const kclvm_value_ref_t,
 ) -> *mut kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     if p.is_null() {
-        return kclvm_value_Str(std::ptr::null());
+        return kclvm_value_Str(ctx, std::ptr::null());
     }
 
     let p = ptr_as_ref(p);
     let s = p.to_json_string();
 
-    return new_mut_ptr(ValueRef::str(s.as_ref()));
+    return new_mut_ptr(ctx, ValueRef::str(s.as_ref()));
 }
 
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_value_to_json_value_with_null(
+    ctx: *mut kclvm_context_t,
     p: *const kclvm_value_ref_t,
 ) -> *mut kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     if p.is_null() {
-        return kclvm_value_Str(std::ptr::null());
+        return kclvm_value_Str(ctx, std::ptr::null());
     }
 
     let p = ptr_as_ref(p);
     let s = p.to_json_string_with_null();
 
-    return new_mut_ptr(ValueRef::str(s.as_ref()));
+    return new_mut_ptr(ctx, ValueRef::str(s.as_ref()));
 }
 
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_value_plan_to_json(
+    ctx: *mut kclvm_context_t,
     p: *const kclvm_value_ref_t,
 ) -> *mut kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let p = ptr_as_ref(p);
-    let s = p.plan_to_json_string();
+    let s = p.plan_to_json_string(ctx);
+    // Keep the planned value, so the runners in the current process get it
+    // without decoding the JSON string.
+    ctx.buffer.plan_value = Some(p.plan_value(ctx));
 
-    return new_mut_ptr(ValueRef::str(s.as_ref()));
+    return new_mut_ptr(ctx, ValueRef::str(s.as_ref()));
 }
 
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_value_plan_to_yaml(
+    ctx: *mut kclvm_context_t,
     p: *const kclvm_value_ref_t,
 ) -> *mut kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let p = ptr_as_ref(p);
-    let s = p.plan_to_yaml_string();
+    let s = p.plan_to_yaml_string(ctx);
 
-    return new_mut_ptr(ValueRef::str(s.as_ref()));
+    return new_mut_ptr(ctx, ValueRef::str(s.as_ref()));
 }
 
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_value_to_yaml_value(
+    ctx: *mut kclvm_context_t,
     p: *const kclvm_value_ref_t,
 ) -> *mut kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     if p.is_null() {
-        return kclvm_value_Str(std::ptr::null());
+        return kclvm_value_Str(ctx, std::ptr::null());
     }
 
     let p = ptr_as_ref(p);
     let s = p.to_yaml_string();
 
-    return new_mut_ptr(ValueRef::str(s.as_ref()));
+    return new_mut_ptr(ctx, ValueRef::str(s.as_ref()));
 }
 
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_value_to_str_value(
+    ctx: *mut kclvm_context_t,
     p: *const kclvm_value_ref_t,
 ) -> *mut kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     if p.is_null() {
-        return kclvm_value_Str(std::ptr::null());
+        return kclvm_value_Str(ctx, std::ptr::null());
     }
 
     let p = ptr_as_ref(p);
     let s = p.to_string();
 
-    return new_mut_ptr(ValueRef::str(s.as_ref()));
+    return new_mut_ptr(ctx, ValueRef::str(s.as_ref()));
 }
 
 // ----------------------------------------------------------------------------
@@ -602,17 +654,19 @@ pub unsafe extern "C" fn kclvm_value_function_is_external(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_value_function_external_invoke(
+    ctx: *mut kclvm_context_t,
     p: *const kclvm_value_ref_t,
     args: *const kclvm_value_ref_t,
     kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let p = ptr_as_ref(p);
     match &*p.rc.borrow() {
         Value::func_value(ref v) => {
             let name = format!("{}\0", v.name);
-            kclvm_plugin_invoke(name.as_ptr() as *const i8, args, kwargs)
+            kclvm_plugin_invoke(ctx, name.as_ptr() as *const i8, args, kwargs)
         }
-        _ => kclvm_value_None(),
+        _ => kclvm_value_None(ctx),
     }
 }
 
@@ -626,6 +680,7 @@ pub unsafe extern "C" fn kclvm_value_function_invoke(
     pkgpath: *const kclvm_char_t,
     is_in_schema: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let func = ptr_as_ref(p);
     let args_ref = mut_ptr_as_ref(args);
     if func.is_func() {
@@ -634,6 +689,14 @@ pub unsafe extern "C" fn kclvm_value_function_invoke(
         let closure = &func.closure;
         let is_schema = !func.runtime_type.is_empty();
         let ctx_ref = mut_ptr_as_ref(ctx);
+        ctx_ref.enter_call();
+        if is_schema {
+            ctx_ref
+                .profiler
+                .begin(PROFILE_KIND_SCHEMA, &func.runtime_type);
+        } else {
+            ctx_ref.profiler.begin(PROFILE_KIND_LAMBDA, &func.name);
+        }
         if ctx_ref.cfg.debug_mode {
             ctx_ref
                 .backtrace
@@ -654,7 +717,7 @@ pub unsafe extern "C" fn kclvm_value_function_invoke(
                 let record_instance_index = closure.len() - 2;
                 let instance_pkgpath_index = closure.len() - 1;
                 args_ref.list_append_unpack(closure);
-                let args = args_ref.clone().into_raw();
+                let args = args_ref.clone().into_raw(ctx);
                 call_fn(ctx, args, kwargs);
                 let cal_map = closure.list_get(cal_map_index).unwrap();
                 // is sub schema
@@ -673,40 +736,44 @@ pub unsafe extern "C" fn kclvm_value_function_invoke(
                 );
                 closure_new.list_set(config_meta_index as usize, &config_meta);
                 args_new.list_append_unpack(&closure_new);
-                call_fn(ctx, args_new.into_raw(), kwargs)
+                call_fn(ctx, args_new.into_raw(ctx), kwargs)
             // Normal kcl function, call directly
             } else if func.is_external {
                 let name = format!("{}\0", func.name);
-                kclvm_plugin_invoke(name.as_ptr() as *const i8, args, kwargs)
+                kclvm_plugin_invoke(ctx, name.as_ptr() as *const i8, args, kwargs)
             } else {
                 args_ref.list_append_unpack_first(closure);
-                let args = args_ref.clone().into_raw();
+                let args = args_ref.clone().into_raw(ctx);
                 call_fn(ctx, args, kwargs)
             };
             let is_in_schema = ptr_as_ref(is_in_schema);
             if is_schema && !is_in_schema.is_truthy() {
                 let schema_value = ptr_as_ref(value);
-                schema_value.schema_check_attr_optional(true);
+                schema_value.schema_check_attr_optional(ctx, true);
             }
             if ctx_ref.cfg.debug_mode {
                 ctx_ref.backtrace.pop();
             }
+            ctx_ref.profiler.end();
+            ctx_ref.leave_call();
             ctx_ref.panic_info = now_meta_info;
             return value;
         };
     }
-    kclvm_value_None()
+    kclvm_value_None(ctx)
 }
 
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_value_function_get_closure(
+    ctx: *mut kclvm_context_t,
     p: *const kclvm_value_ref_t,
 ) -> *mut kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let p = ptr_as_ref(p);
     match &*p.rc.borrow() {
-        Value::func_value(ref v) => v.closure.deep_copy().into_raw(),
-        Value::none | Value::undefined => kclvm_value_None(),
+        Value::func_value(ref v) => v.closure.deep_copy().into_raw(ctx),
+        Value::none | Value::undefined => kclvm_value_None(ctx),
         _ => panic!("invalid value of function self value function"),
     }
 }
@@ -729,10 +796,12 @@ pub unsafe extern "C" fn kclvm_value_kind(p: *const kclvm_value_ref_t) -> kclvm_
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_value_deep_copy(
+    ctx: *mut kclvm_context_t,
     p: *const kclvm_value_ref_t,
 ) -> *mut kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let p = ptr_as_ref(p);
-    p.deep_copy().into_raw()
+    p.deep_copy().into_raw(ctx)
 }
 
 // delete
@@ -743,25 +812,8 @@ pub unsafe extern "C" fn kclvm_value_delete(p: *mut kclvm_value_ref_t) {
     if p.is_null() {
         return;
     }
-    unsafe {
-        if p as usize == kclvm_value_Undefined_obj {
-            return;
-        }
-        if p as usize == kclvm_value_None_obj {
-            return;
-        }
-        if p as usize == kclvm_value_Bool_true_obj {
-            return;
-        }
-        if p as usize == kclvm_value_Bool_false_obj {
-            return;
-        }
-        if p as usize == kclvm_value_Int_0_obj {
-            return;
-        }
-        if p as usize == kclvm_value_Float_0_obj {
-            return;
-        }
+    if is_singleton_value(p) {
+        return;
     }
     let val = ptr_as_ref(p);
     val.from_raw();
@@ -861,28 +913,32 @@ pub unsafe extern "C" fn kclvm_list_clear(p: *mut kclvm_value_ref_t) {
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_list_count(
+    ctx: *mut kclvm_context_t,
     p: *const kclvm_value_ref_t,
     item: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let p = ptr_as_ref(p);
     let item = ptr_as_ref(item);
     let count = p.list_count(item);
     let count_value = ValueRef::int(count as i64);
-    count_value.into_raw()
+    count_value.into_raw(ctx)
 }
 
 /// Return first index of the list value. Panic if the value is not present.
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_list_find(
+    ctx: *mut kclvm_context_t,
     p: *const kclvm_value_ref_t,
     item: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let p = ptr_as_ref(p);
     let item = ptr_as_ref(item);
     let index = p.list_find(item);
     let index_value = ValueRef::int(index as i64);
-    index_value.into_raw()
+    index_value.into_raw(ctx)
 }
 
 /// Insert object before index of the list value.
@@ -902,12 +958,14 @@ pub unsafe extern "C" fn kclvm_list_insert(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_list_get(
+    ctx: *mut kclvm_context_t,
     p: *const kclvm_value_ref_t,
     i: kclvm_size_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let p = ptr_as_ref(p);
     match p.list_get(i as isize) {
-        Some(x) => x.into_raw(),
+        Some(x) => x.into_raw(ctx),
         _ => panic!("list index out of range"),
     }
 }
@@ -915,13 +973,15 @@ pub unsafe extern "C" fn kclvm_list_get(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_list_get_option(
+    ctx: *mut kclvm_context_t,
     p: *const kclvm_value_ref_t,
     i: kclvm_size_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let p = ptr_as_ref(p);
     match p.list_get_option(i as isize) {
-        Some(x) => x.into_raw(),
-        _ => kclvm_value_Undefined(),
+        Some(x) => x.into_raw(ctx),
+        _ => kclvm_value_Undefined(ctx),
     }
 }
 
@@ -939,23 +999,29 @@ pub unsafe extern "C" fn kclvm_list_set(
 
 #[no_mangle]
 #[runtime_fn]
-pub unsafe extern "C" fn kclvm_list_pop(p: *mut kclvm_value_ref_t) -> *const kclvm_value_ref_t {
+pub unsafe extern "C" fn kclvm_list_pop(
+    ctx: *mut kclvm_context_t,
+    p: *mut kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let p = mut_ptr_as_ref(p);
     match p.list_pop() {
-        Some(x) => x.into_raw(),
-        _ => kclvm_value_Undefined(),
+        Some(x) => x.into_raw(ctx),
+        _ => kclvm_value_Undefined(ctx),
     }
 }
 
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_list_pop_first(
+    ctx: *mut kclvm_context_t,
     p: *mut kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let p = mut_ptr_as_ref(p);
     match p.list_pop_first() {
-        Some(x) => x.into_raw(),
-        _ => kclvm_value_Undefined(),
+        Some(x) => x.into_raw(ctx),
+        _ => kclvm_value_Undefined(ctx),
     }
 }
 
@@ -1040,15 +1106,17 @@ pub unsafe extern "C" fn kclvm_dict_clear(p: *mut kclvm_value_ref_t) {
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_dict_get(
+    ctx: *mut kclvm_context_t,
     p: *const kclvm_value_ref_t,
     key: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let p = ptr_as_ref(p);
     let key = ptr_as_ref(key);
 
     match p.dict_get(key) {
-        Some(x) => x.into_raw(),
-        None => kclvm_value_Undefined(),
+        Some(x) => x.into_raw(ctx),
+        None => kclvm_value_Undefined(ctx),
     }
 }
 
@@ -1069,52 +1137,60 @@ pub unsafe extern "C" fn kclvm_dict_has_value(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_dict_get_value(
+    ctx: *mut kclvm_context_t,
     p: *const kclvm_value_ref_t,
     key: *const kclvm_char_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let p = ptr_as_ref(p);
     let key = c2str(key);
     match p.dict_get_value(key) {
-        Some(x) => x.into_raw(),
-        None => kclvm_value_Undefined(),
+        Some(x) => x.into_raw(ctx),
+        None => kclvm_value_Undefined(ctx),
     }
 }
 
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_dict_get_entry(
+    ctx: *mut kclvm_context_t,
     p: *const kclvm_value_ref_t,
     key: *const kclvm_char_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let p = ptr_as_ref(p);
     let key = c2str(key);
     match p.dict_get_entry(key) {
-        Some(x) => x.into_raw(),
-        None => kclvm_value_Undefined(),
+        Some(x) => x.into_raw(ctx),
+        None => kclvm_value_Undefined(ctx),
     }
 }
 
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_dict_get_value_by_path(
+    ctx: *mut kclvm_context_t,
     p: *const kclvm_value_ref_t,
     path: *const kclvm_char_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let p = ptr_as_ref(p);
     let path = c2str(path);
     match p.get_by_path(path) {
-        Some(x) => x.into_raw(),
-        None => kclvm_value_Undefined(),
+        Some(x) => x.into_raw(ctx),
+        None => kclvm_value_Undefined(ctx),
     }
 }
 
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_dict_set_value(
+    ctx: *mut kclvm_context_t,
     p: *mut kclvm_value_ref_t,
     key: *const kclvm_char_t,
     val: *const kclvm_value_ref_t,
 ) {
+    let ctx = mut_ptr_as_ref(ctx);
     let p = mut_ptr_as_ref(p);
     let key = c2str(key);
     let val = ptr_as_ref(val);
@@ -1127,7 +1203,7 @@ pub unsafe extern "C" fn kclvm_dict_set_value(
             let schema_value = p.as_schema();
             let mut config_keys = schema_value.config_keys.clone();
             config_keys.push(key.to_string());
-            schema = resolve_schema(p, &config_keys);
+            schema = resolve_schema(ctx, p, &config_keys);
         }
         p.schema_update_with_schema(&schema);
     }
@@ -1137,35 +1213,44 @@ pub unsafe extern "C" fn kclvm_dict_set_value(
 #[no_mangle]
 #[runtime_fn]
 /// Return all dict keys.
-pub unsafe extern "C" fn kclvm_dict_keys(p: *const kclvm_value_ref_t) -> *const kclvm_value_ref_t {
+pub unsafe extern "C" fn kclvm_dict_keys(
+    ctx: *mut kclvm_context_t,
+    p: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let p = ptr_as_ref(p);
     let r = p.dict_keys();
-    r.into_raw()
+    r.into_raw(ctx)
 }
 
 #[no_mangle]
 #[runtime_fn]
 /// Return all dict values.
 pub unsafe extern "C" fn kclvm_dict_values(
+    ctx: *mut kclvm_context_t,
     p: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let p = ptr_as_ref(p);
     let r = p.dict_values();
-    r.into_raw()
+    r.into_raw(ctx)
 }
 
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_dict_insert(
+    ctx: *mut kclvm_context_t,
     p: *mut kclvm_value_ref_t,
     key: *const kclvm_char_t,
     v: *const kclvm_value_ref_t,
     op: kclvm_size_t,
     insert_index: kclvm_size_t,
 ) {
+    let ctx = mut_ptr_as_ref(ctx);
     let p = mut_ptr_as_ref(p);
     let v = ptr_as_ref(v);
     p.dict_insert(
+        ctx,
         c2str(key),
         v,
         ConfigEntryOperationKind::from_i32(op),
@@ -1176,12 +1261,14 @@ pub unsafe extern "C" fn kclvm_dict_insert(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_dict_merge(
+    ctx: *mut kclvm_context_t,
     p: *mut kclvm_value_ref_t,
     key: *const kclvm_char_t,
     v: *const kclvm_value_ref_t,
     op: kclvm_size_t,
     insert_index: kclvm_size_t,
 ) {
+    let ctx = mut_ptr_as_ref(ctx);
     let p = mut_ptr_as_ref(p);
     let v = ptr_as_ref(v);
     let key = c2str(key);
@@ -1193,32 +1280,42 @@ pub unsafe extern "C" fn kclvm_dict_merge(
         }
     };
     if attr_map.contains_key(key) {
-        let v = type_pack_and_check(v, vec![attr_map.get(key).unwrap()]);
+        let v = type_pack_and_check(ctx, v, vec![attr_map.get(key).unwrap()]);
         p.dict_merge(
+            ctx,
             key,
             &v,
             ConfigEntryOperationKind::from_i32(op),
             insert_index,
         );
     } else {
-        p.dict_merge(key, v, ConfigEntryOperationKind::from_i32(op), insert_index);
+        p.dict_merge(
+            ctx,
+            key,
+            v,
+            ConfigEntryOperationKind::from_i32(op),
+            insert_index,
+        );
     }
 }
 
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_dict_insert_value(
+    ctx: *mut kclvm_context_t,
     p: *mut kclvm_value_ref_t,
     key: *const kclvm_value_ref_t,
     v: *const kclvm_value_ref_t,
     op: kclvm_size_t,
     insert_index: kclvm_size_t,
 ) {
+    let ctx = mut_ptr_as_ref(ctx);
     let p = mut_ptr_as_ref(p);
     let v = ptr_as_ref(v);
     let key = ptr_as_ref(key);
     let key = key.attr_str();
     p.dict_insert(
+        ctx,
         key.as_str(),
         v,
         ConfigEntryOperationKind::from_i32(op),
@@ -1243,27 +1340,31 @@ pub unsafe extern "C" fn kclvm_dict_update_key_value(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_dict_safe_insert(
+    ctx: *mut kclvm_context_t,
     p: *mut kclvm_value_ref_t,
     key: *const kclvm_char_t,
     v: *const kclvm_value_ref_t,
     op: kclvm_size_t,
     insert_index: kclvm_size_t,
 ) {
+    let ctx = mut_ptr_as_ref(ctx);
     if p.is_null() || key.is_null() || v.is_null() {
         return;
     }
-    kclvm_dict_insert(p, key, v, op, insert_index);
+    kclvm_dict_insert(ctx, p, key, v, op, insert_index);
 }
 
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_dict_insert_unpack(
+    ctx: *mut kclvm_context_t,
     p: *mut kclvm_value_ref_t,
     v: *const kclvm_value_ref_t,
 ) {
+    let ctx = mut_ptr_as_ref(ctx);
     let p = mut_ptr_as_ref(p);
     let v = ptr_as_ref(v);
-    p.dict_insert_unpack(v);
+    p.dict_insert_unpack(ctx, v);
 }
 
 #[no_mangle]
@@ -1355,79 +1456,91 @@ pub unsafe extern "C" fn kclvm_value_len(p: *const kclvm_value_ref_t) -> kclvm_s
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_value_cmp_equal_to(
+    ctx: *mut kclvm_context_t,
     a: *const kclvm_value_ref_t,
     b: *const kclvm_value_ref_t,
 ) -> *mut kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     if a == b {
         return kclvm_value_Bool(1);
     }
     let a = ptr_as_ref(a);
     let b = ptr_as_ref(b);
-    ValueRef::bool(a.cmp_equal(b)).into_raw()
+    ValueRef::bool(a.cmp_equal(b)).into_raw(ctx)
 }
 
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_value_cmp_not_equal_to(
+    ctx: *mut kclvm_context_t,
     a: *const kclvm_value_ref_t,
     b: *const kclvm_value_ref_t,
 ) -> *mut kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     if a == b {
         return kclvm_value_Bool(0);
     }
     let a = ptr_as_ref(a);
     let b = ptr_as_ref(b);
-    ValueRef::bool(!a.cmp_equal(b)).into_raw()
+    ValueRef::bool(!a.cmp_equal(b)).into_raw(ctx)
 }
 
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_value_cmp_less_than(
+    ctx: *mut kclvm_context_t,
     a: *const kclvm_value_ref_t,
     b: *const kclvm_value_ref_t,
 ) -> *mut kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     if a == b {
         return kclvm_value_Bool(0);
     }
     let a = ptr_as_ref(a);
     let b = ptr_as_ref(b);
-    ValueRef::bool(a.cmp_less_than(b)).into_raw()
+    ValueRef::bool(a.cmp_less_than(b)).into_raw(ctx)
 }
 
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_value_cmp_less_than_or_equal(
+    ctx: *mut kclvm_context_t,
     a: *const kclvm_value_ref_t,
     b: *const kclvm_value_ref_t,
 ) -> *mut kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let a = ptr_as_ref(a);
     let b = ptr_as_ref(b);
-    ValueRef::bool(a.cmp_less_than_or_equal(b)).into_raw()
+    ValueRef::bool(a.cmp_less_than_or_equal(b)).into_raw(ctx)
 }
 
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_value_cmp_greater_than(
+    ctx: *mut kclvm_context_t,
     a: *const kclvm_value_ref_t,
     b: *const kclvm_value_ref_t,
 ) -> *mut kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     if a == b {
         return kclvm_value_Bool(0);
     }
     let a = ptr_as_ref(a);
     let b = ptr_as_ref(b);
-    ValueRef::bool(a.cmp_greater_than(b)).into_raw()
+    ValueRef::bool(a.cmp_greater_than(b)).into_raw(ctx)
 }
 
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_value_cmp_greater_than_or_equal(
+    ctx: *mut kclvm_context_t,
     a: *const kclvm_value_ref_t,
     b: *const kclvm_value_ref_t,
 ) -> *mut kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let a = ptr_as_ref(a);
     let b = ptr_as_ref(b);
-    ValueRef::bool(a.cmp_greater_than_or_equal(b)).into_raw()
+    ValueRef::bool(a.cmp_greater_than_or_equal(b)).into_raw(ctx)
 }
 
 // is/in
@@ -1435,6 +1548,7 @@ pub unsafe extern "C" fn kclvm_value_cmp_greater_than_or_equal(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_value_is(
+    _ctx: *mut kclvm_context_t,
     a: *const kclvm_value_ref_t,
     b: *const kclvm_value_ref_t,
 ) -> *mut kclvm_value_ref_t {
@@ -1449,6 +1563,7 @@ pub unsafe extern "C" fn kclvm_value_is(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_value_is_not(
+    _ctx: *mut kclvm_context_t,
     a: *const kclvm_value_ref_t,
     b: *const kclvm_value_ref_t,
 ) -> *mut kclvm_value_ref_t {
@@ -1463,36 +1578,42 @@ pub unsafe extern "C" fn kclvm_value_is_not(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_value_in(
+    ctx: *mut kclvm_context_t,
     a: *const kclvm_value_ref_t,
     b: *const kclvm_value_ref_t,
 ) -> *mut kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let a = ptr_as_ref(a);
     let b = ptr_as_ref(b);
-    ValueRef::bool(a.r#in(b)).into_raw()
+    ValueRef::bool(a.r#in(b)).into_raw(ctx)
 }
 
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_value_not_in(
+    ctx: *mut kclvm_context_t,
     a: *const kclvm_value_ref_t,
     b: *const kclvm_value_ref_t,
 ) -> *mut kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let a = ptr_as_ref(a);
     let b = ptr_as_ref(b);
-    ValueRef::bool(a.not_in(b)).into_raw()
+    ValueRef::bool(a.not_in(b)).into_raw(ctx)
 }
 
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_value_as(
+    ctx: *mut kclvm_context_t,
     a: *const kclvm_value_ref_t,
     b: *const kclvm_value_ref_t,
 ) -> *mut kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let a = ptr_as_ref(a);
     let b = ptr_as_ref(b);
     let ty_str = b.as_str();
-    let value = type_pack_and_check(a, vec![ty_str.as_str()]);
-    value.into_raw()
+    let value = type_pack_and_check(ctx, a, vec![ty_str.as_str()]);
+    value.into_raw(ctx)
 }
 
 // unary-xxx
@@ -1500,37 +1621,45 @@ pub unsafe extern "C" fn kclvm_value_as(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_value_unary_plus(
+    ctx: *mut kclvm_context_t,
     a: *const kclvm_value_ref_t,
 ) -> *mut kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let a = ptr_as_ref(a);
-    a.unary_plus().into_raw()
+    a.unary_plus().into_raw(ctx)
 }
 
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_value_unary_minus(
+    ctx: *mut kclvm_context_t,
     a: *const kclvm_value_ref_t,
 ) -> *mut kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let a = ptr_as_ref(a);
-    a.unary_minus().into_raw()
+    a.unary_minus().into_raw(ctx)
 }
 
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_value_unary_not(
+    ctx: *mut kclvm_context_t,
     a: *const kclvm_value_ref_t,
 ) -> *mut kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let a = ptr_as_ref(a);
-    a.unary_not().into_raw()
+    a.unary_not().into_raw(ctx)
 }
 
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_value_unary_l_not(
+    ctx: *mut kclvm_context_t,
     a: *const kclvm_value_ref_t,
 ) -> *mut kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let a = ptr_as_ref(a);
-    a.unary_l_not().into_raw()
+    a.unary_l_not().into_raw(ctx)
 }
 
 // op-xxx
@@ -1538,133 +1667,157 @@ pub unsafe extern "C" fn kclvm_value_unary_l_not(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_value_op_add(
+    ctx: *mut kclvm_context_t,
     a: *const kclvm_value_ref_t,
     b: *const kclvm_value_ref_t,
 ) -> *mut kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let a = ptr_as_ref(a);
     let b = ptr_as_ref(b);
-    a.bin_add(b).into_raw()
+    a.bin_add(ctx, b).into_raw(ctx)
 }
 
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_value_op_sub(
+    ctx: *mut kclvm_context_t,
     a: *const kclvm_value_ref_t,
     b: *const kclvm_value_ref_t,
 ) -> *mut kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let a = ptr_as_ref(a);
     let b = ptr_as_ref(b);
-    a.bin_sub(b).into_raw()
+    a.bin_sub(ctx, b).into_raw(ctx)
 }
 
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_value_op_mul(
+    ctx: *mut kclvm_context_t,
     a: *const kclvm_value_ref_t,
     b: *const kclvm_value_ref_t,
 ) -> *mut kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let a = ptr_as_ref(a);
     let b = ptr_as_ref(b);
-    a.bin_mul(b).into_raw()
+    a.bin_mul(ctx, b).into_raw(ctx)
 }
 
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_value_op_div(
+    ctx: *mut kclvm_context_t,
     a: *const kclvm_value_ref_t,
     b: *const kclvm_value_ref_t,
 ) -> *mut kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let a = ptr_as_ref(a);
     let b = ptr_as_ref(b);
-    a.bin_div(b).into_raw()
+    a.bin_div(b).into_raw(ctx)
 }
 
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_value_op_mod(
+    ctx: *mut kclvm_context_t,
     a: *const kclvm_value_ref_t,
     b: *const kclvm_value_ref_t,
 ) -> *mut kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let a = ptr_as_ref(a);
     let b = ptr_as_ref(b);
-    a.bin_mod(b).into_raw()
+    a.bin_mod(b).into_raw(ctx)
 }
 
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_value_op_pow(
+    ctx: *mut kclvm_context_t,
     a: *const kclvm_value_ref_t,
     b: *const kclvm_value_ref_t,
 ) -> *mut kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let a = ptr_as_ref(a);
     let b = ptr_as_ref(b);
-    a.bin_pow(b).into_raw()
+    a.bin_pow(ctx, b).into_raw(ctx)
 }
 
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_value_op_floor_div(
+    ctx: *mut kclvm_context_t,
     a: *const kclvm_value_ref_t,
     b: *const kclvm_value_ref_t,
 ) -> *mut kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let a = ptr_as_ref(a);
     let b = ptr_as_ref(b);
-    a.bin_floor_div(b).into_raw()
+    a.bin_floor_div(b).into_raw(ctx)
 }
 
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_value_op_bit_lshift(
+    ctx: *mut kclvm_context_t,
     a: *const kclvm_value_ref_t,
     b: *const kclvm_value_ref_t,
 ) -> *mut kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let a = ptr_as_ref(a);
     let b = ptr_as_ref(b);
-    a.bin_bit_lshift(b).into_raw()
+    a.bin_bit_lshift(ctx, b).into_raw(ctx)
 }
 
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_value_op_bit_rshift(
+    ctx: *mut kclvm_context_t,
     a: *const kclvm_value_ref_t,
     b: *const kclvm_value_ref_t,
 ) -> *mut kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let a = ptr_as_ref(a);
     let b = ptr_as_ref(b);
-    a.bin_bit_rshift(b).into_raw()
+    a.bin_bit_rshift(ctx, b).into_raw(ctx)
 }
 
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_value_op_bit_and(
+    ctx: *mut kclvm_context_t,
     a: *const kclvm_value_ref_t,
     b: *const kclvm_value_ref_t,
 ) -> *mut kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let a = ptr_as_ref(a);
     let b = ptr_as_ref(b);
-    a.bin_bit_and(b).into_raw()
+    a.bin_bit_and(b).into_raw(ctx)
 }
 
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_value_op_bit_xor(
+    ctx: *mut kclvm_context_t,
     a: *const kclvm_value_ref_t,
     b: *const kclvm_value_ref_t,
 ) -> *mut kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let a = ptr_as_ref(a);
     let b = ptr_as_ref(b);
-    a.bin_bit_xor(b).into_raw()
+    a.bin_bit_xor(b).into_raw(ctx)
 }
 
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_value_op_bit_or(
+    ctx: *mut kclvm_context_t,
     a: *const kclvm_value_ref_t,
     b: *const kclvm_value_ref_t,
 ) -> *mut kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let a = ptr_as_ref(a);
     let b = ptr_as_ref(b);
-    a.bin_bit_or(b).into_raw()
+    a.bin_bit_or(ctx, b).into_raw(ctx)
 }
 
 // op-aug-xxx
@@ -1672,39 +1825,46 @@ pub unsafe extern "C" fn kclvm_value_op_bit_or(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_value_op_aug_add(
+    ctx: *mut kclvm_context_t,
     a: *mut kclvm_value_ref_t,
     b: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let a = mut_ptr_as_ref(a);
     let b = ptr_as_ref(b);
-    return a.bin_aug_add(b) as *const kclvm_value_ref_t;
+    return a.bin_aug_add(ctx, b) as *const kclvm_value_ref_t;
 }
 
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_value_op_aug_sub(
+    ctx: *mut kclvm_context_t,
     a: *mut kclvm_value_ref_t,
     b: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let a = mut_ptr_as_ref(a);
     let b = ptr_as_ref(b);
-    return a.bin_aug_sub(b) as *const kclvm_value_ref_t;
+    return a.bin_aug_sub(ctx, b) as *const kclvm_value_ref_t;
 }
 
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_value_op_aug_mul(
+    ctx: *mut kclvm_context_t,
     a: *mut kclvm_value_ref_t,
     b: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let a = mut_ptr_as_ref(a);
     let b = ptr_as_ref(b);
-    return a.bin_aug_mul(b) as *const kclvm_value_ref_t;
+    return a.bin_aug_mul(ctx, b) as *const kclvm_value_ref_t;
 }
 
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_value_op_aug_div(
+    _ctx: *mut kclvm_context_t,
     a: *mut kclvm_value_ref_t,
     b: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
@@ -1716,6 +1876,7 @@ pub unsafe extern "C" fn kclvm_value_op_aug_div(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_value_op_aug_mod(
+    _ctx: *mut kclvm_context_t,
     a: *mut kclvm_value_ref_t,
     b: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
@@ -1727,17 +1888,20 @@ pub unsafe extern "C" fn kclvm_value_op_aug_mod(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_value_op_aug_pow(
+    ctx: *mut kclvm_context_t,
     a: *mut kclvm_value_ref_t,
     b: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let a = mut_ptr_as_ref(a);
     let b = ptr_as_ref(b);
-    return a.bin_aug_pow(b) as *const kclvm_value_ref_t;
+    return a.bin_aug_pow(ctx, b) as *const kclvm_value_ref_t;
 }
 
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_value_op_aug_floor_div(
+    _ctx: *mut kclvm_context_t,
     a: *mut kclvm_value_ref_t,
     b: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
@@ -1749,28 +1913,33 @@ pub unsafe extern "C" fn kclvm_value_op_aug_floor_div(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_value_op_aug_bit_lshift(
+    ctx: *mut kclvm_context_t,
     a: *mut kclvm_value_ref_t,
     b: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let a = mut_ptr_as_ref(a);
     let b = ptr_as_ref(b);
-    a.bin_aug_bit_lshift(b) as *const kclvm_value_ref_t
+    a.bin_aug_bit_lshift(ctx, b) as *const kclvm_value_ref_t
 }
 
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_value_op_aug_bit_rshift(
+    ctx: *mut kclvm_context_t,
     a: *mut kclvm_value_ref_t,
     b: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let a = mut_ptr_as_ref(a);
     let b = ptr_as_ref(b);
-    a.bin_aug_bit_rshift(b) as *const kclvm_value_ref_t
+    a.bin_aug_bit_rshift(ctx, b) as *const kclvm_value_ref_t
 }
 
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_value_op_aug_bit_and(
+    _ctx: *mut kclvm_context_t,
     a: *mut kclvm_value_ref_t,
     b: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
@@ -1782,6 +1951,7 @@ pub unsafe extern "C" fn kclvm_value_op_aug_bit_and(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_value_op_aug_bit_xor(
+    _ctx: *mut kclvm_context_t,
     a: *mut kclvm_value_ref_t,
     b: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
@@ -1793,20 +1963,24 @@ pub unsafe extern "C" fn kclvm_value_op_aug_bit_xor(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_value_op_aug_bit_or(
+    ctx: *mut kclvm_context_t,
     a: *mut kclvm_value_ref_t,
     b: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let a = mut_ptr_as_ref(a);
     let b = ptr_as_ref(b);
-    a.bin_aug_bit_or(b) as *const kclvm_value_ref_t
+    a.bin_aug_bit_or(ctx, b) as *const kclvm_value_ref_t
 }
 
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_value_union(
+    ctx: *mut kclvm_context_t,
     schema: *mut kclvm_value_ref_t,
     b: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let a = mut_ptr_as_ref(schema);
     let b = ptr_as_ref(b);
     let attr_map = match &*a.rc.borrow() {
@@ -1819,24 +1993,25 @@ pub unsafe extern "C" fn kclvm_value_union(
         let mut result = schema;
         for (k, v) in &dict.values {
             if attr_map.contains_key(k) {
-                let v = type_pack_and_check(v, vec![attr_map.get(k).unwrap()]);
+                let v = type_pack_and_check(ctx, v, vec![attr_map.get(k).unwrap()]);
                 let mut entry = b.dict_get_entry(k).unwrap().deep_copy();
                 entry.dict_update_key_value(k, v);
                 result = a
-                    .union_entry(&entry, true, false, false, false)
+                    .union_entry(ctx, &entry, true, false, false, false)
                     .clone()
-                    .into_raw();
+                    .into_raw(ctx);
             } else {
                 let entry = b.dict_get_entry(k).unwrap();
                 result = a
-                    .union_entry(&entry, true, false, false, false)
+                    .union_entry(ctx, &entry, true, false, false, false)
                     .clone()
-                    .into_raw();
+                    .into_raw(ctx);
             }
         }
         result
     } else {
-        a.union_entry(b, true, false, false, false).into_raw()
+        a.union_entry(ctx, b, true, false, false, false)
+            .into_raw(ctx)
     }
 }
 
@@ -1845,70 +2020,80 @@ pub unsafe extern "C" fn kclvm_value_union(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_value_logic_and(
+    ctx: *mut kclvm_context_t,
     a: *const kclvm_value_ref_t,
     b: *const kclvm_value_ref_t,
 ) -> *mut kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let a = ptr_as_ref(a);
     let b = ptr_as_ref(b);
-    ValueRef::bool(a.logic_and(b)).into_raw()
+    ValueRef::bool(a.logic_and(b)).into_raw(ctx)
 }
 
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_value_logic_or(
+    ctx: *mut kclvm_context_t,
     a: *const kclvm_value_ref_t,
     b: *const kclvm_value_ref_t,
 ) -> *mut kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let a = ptr_as_ref(a);
     let b = ptr_as_ref(b);
-    ValueRef::bool(a.logic_or(b)).into_raw()
+    ValueRef::bool(a.logic_or(b)).into_raw(ctx)
 }
 
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_value_subscr(
+    ctx: *mut kclvm_context_t,
     a: *const kclvm_value_ref_t,
     b: *const kclvm_value_ref_t,
 ) -> *mut kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let a = ptr_as_ref(a);
     let b = ptr_as_ref(b);
-    a.bin_subscr(b).into_raw()
+    a.bin_subscr(b).into_raw(ctx)
 }
 
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_value_subscr_option(
+    ctx: *mut kclvm_context_t,
     a: *const kclvm_value_ref_t,
     b: *const kclvm_value_ref_t,
 ) -> *mut kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let a = ptr_as_ref(a);
     let b = ptr_as_ref(b);
-    a.bin_subscr_option(b).into_raw()
+    a.bin_subscr_option(b).into_raw(ctx)
 }
 
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_value_load_attr(
+    ctx: *mut kclvm_context_t,
     obj: *const kclvm_value_ref_t,
     key: *const kclvm_char_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let p = ptr_as_ref(obj);
     let key = c2str(key);
     // load_attr including str/dict/schema.
     if p.is_dict() {
         match p.dict_get_value(key) {
             Some(x) => {
-                return x.into_raw();
+                return x.into_raw(ctx);
             }
             None => {
-                return kclvm_value_Undefined();
+                return kclvm_value_Undefined(ctx);
             }
         }
     } else if p.is_schema() {
         let dict = p.schema_to_dict();
         match dict.dict_get_value(key) {
             Some(x) => {
-                return x.into_raw();
+                return x.into_raw(ctx);
             }
             None => panic!("schema '{}' attribute '{}' not found", p.type_str(), key),
         }
@@ -1946,14 +2131,10 @@ pub unsafe extern "C" fn kclvm_value_load_attr(
             _ => panic!("str object attr '{key}' not found"),
         };
         let closure = ValueRef::list(Some(&[p]));
-        return new_mut_ptr(ValueRef::func(
-            function as usize as u64,
-            0,
-            closure,
-            "",
-            "",
-            false,
-        ));
+        return new_mut_ptr(
+            ctx,
+            ValueRef::func(function as usize as u64, 0, closure, "", "", false),
+        );
     }
     // schema instance
     else if p.is_func() {
@@ -1962,14 +2143,10 @@ pub unsafe extern "C" fn kclvm_value_load_attr(
             _ => panic!("schema object attr '{key}' not found"),
         };
         let closure = ValueRef::list(Some(&[p]));
-        return new_mut_ptr(ValueRef::func(
-            function as usize as u64,
-            0,
-            closure,
-            "",
-            "",
-            false,
-        ));
+        return new_mut_ptr(
+            ctx,
+            ValueRef::func(function as usize as u64, 0, closure, "", "", false),
+        );
     }
     panic!(
         "invalid value '{}' to load attribute '{}'",
@@ -1981,14 +2158,16 @@ pub unsafe extern "C" fn kclvm_value_load_attr(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_value_load_attr_option(
+    ctx: *mut kclvm_context_t,
     p: *const kclvm_value_ref_t,
     key: *const kclvm_char_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let p_ref = ptr_as_ref(p);
     if p_ref.is_truthy() {
-        kclvm_value_load_attr(p, key)
+        kclvm_value_load_attr(ctx, p, key)
     } else {
-        kclvm_value_None()
+        kclvm_value_None(ctx)
     }
 }
 
@@ -2012,31 +2191,35 @@ pub unsafe extern "C" fn kclvm_value_remove_item(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_value_slice(
+    ctx: *mut kclvm_context_t,
     x: *const kclvm_value_ref_t,
     a: *const kclvm_value_ref_t,
     b: *const kclvm_value_ref_t,
     step: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let x = ptr_as_ref(x);
     let a = ptr_as_ref(a);
     let b = ptr_as_ref(b);
     let step = ptr_as_ref(step);
-    x.list_slice(a, b, step).into_raw()
+    x.list_slice(a, b, step).into_raw(ctx)
 }
 
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_value_slice_option(
+    ctx: *mut kclvm_context_t,
     x: *const kclvm_value_ref_t,
     a: *const kclvm_value_ref_t,
     b: *const kclvm_value_ref_t,
     step: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let value = ptr_as_ref(x);
     if value.is_truthy() {
-        kclvm_value_slice(x, a, b, step)
+        kclvm_value_slice(ctx, x, a, b, step)
     } else {
-        kclvm_value_None()
+        kclvm_value_None(ctx)
     }
 }
 
@@ -2047,12 +2230,14 @@ pub unsafe extern "C" fn kclvm_value_slice_option(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_schema_backtrack_cache(
+    ctx: *mut kclvm_context_t,
     schema: *const kclvm_value_ref_t,
     cache: *mut kclvm_value_ref_t,
     cal_map: *const kclvm_value_ref_t,
     name: *const kclvm_char_t,
     runtime_type: *const kclvm_value_ref_t,
 ) {
+    let ctx = mut_ptr_as_ref(ctx);
     let schema = ptr_as_ref(schema);
     let cache = mut_ptr_as_ref(cache);
     let cal_map = ptr_as_ref(cal_map);
@@ -2071,7 +2256,7 @@ pub unsafe extern "C" fn kclvm_schema_backtrack_cache(
                 cal_map_meta_line_list.list_get(-1),
             ) {
                 let runtime_type = ptr_as_ref(runtime_type);
-                let line = Context::current_context().panic_info.kcl_line as i64;
+                let line = ctx.panic_info.kcl_line as i64;
                 let cal_map_meta_line = cal_map_meta_line.as_int();
                 if runtime_type == &cal_map_runtime_type && line >= cal_map_meta_line {
                     if let Some(value) = schema.dict_get_value(name) {
@@ -2090,7 +2275,7 @@ pub unsafe extern "C" fn kclvm_schema_instances(
     args: *const kclvm_value_ref_t,
     kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
-    let ctx = ptr_as_ref(ctx);
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
     let kwargs = ptr_as_ref(kwargs);
     if let Some(val) = args.pop_arg_first() {
@@ -2102,10 +2287,10 @@ pub unsafe extern "C" fn kclvm_schema_instances(
             true
         };
         let runtime_type = &function.runtime_type;
-        let instance_map = ctx.instances.borrow_mut();
-        if instance_map.contains_key(runtime_type) {
+        let instances = ctx.instances.borrow().get(runtime_type).cloned();
+        if let Some(instances) = instances {
             let mut list = ValueRef::list(None);
-            for v in instance_map.get(runtime_type).unwrap() {
+            for v in &instances {
                 if v.is_schema() {
                     let schema = v.as_schema();
                     if main_pkg {
@@ -2137,18 +2322,19 @@ pub unsafe extern "C" fn kclvm_schema_instances(
                     list.list_append(&v);
                 }
             }
-            list.into_raw()
+            list.into_raw(ctx)
         } else {
-            kclvm_value_List()
+            kclvm_value_List(ctx)
         }
     } else {
-        kclvm_value_None()
+        kclvm_value_None(ctx)
     }
 }
 
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_schema_value_check(
+    ctx: *mut kclvm_context_t,
     schema_value: *mut kclvm_value_ref_t,
     schema_config: *const kclvm_value_ref_t,
     _config_meta: *const kclvm_value_ref_t,
@@ -2160,14 +2346,13 @@ pub unsafe extern "C" fn kclvm_schema_value_check(
     _any_other: kclvm_bool_t,
     is_relaxed: kclvm_bool_t,
 ) {
+    let ctx = mut_ptr_as_ref(ctx);
     let schema_value = mut_ptr_as_ref(schema_value);
     let schema_config = ptr_as_ref(schema_config);
     let index_sign_value = ptr_as_ref(index_sign_value);
     let key_type = c2str(key_type);
     let has_index_signature = !key_type.is_empty();
     let should_add_attr = is_relaxed != 0 || has_index_signature;
-
-    let ctx = Context::current_context_mut();
     if ctx.cfg.disable_schema_check {
         return;
     }
@@ -2178,7 +2363,7 @@ pub unsafe extern "C" fn kclvm_schema_value_check(
         if should_add_attr && is_not_in_schema {
             let value = index_sign_value
                 .deep_copy()
-                .union_entry(value, true, false, false, true);
+                .union_entry(ctx, value, true, false, false, true);
             let op = config
                 .ops
                 .get(key)
@@ -2200,6 +2385,7 @@ pub unsafe extern "C" fn kclvm_schema_do_check_with_index_sign_attr(
     check_fn_ptr: *const u64,
     attr_name: *const kclvm_char_t,
 ) {
+    let ctx = mut_ptr_as_ref(ctx);
     let check_fn_ptr = check_fn_ptr as u64;
     let args_value = ptr_as_ref(args);
     let attr_name = c2str(attr_name);
@@ -2226,7 +2412,7 @@ pub unsafe extern "C" fn kclvm_schema_do_check_with_index_sign_attr(
                 args.list_append(&cal_map);
                 args.list_append(&backtrack_level_map);
                 args.list_append(&backtrack_cache);
-                let args = args.clone().into_raw();
+                let args = args.clone().into_raw(ctx);
                 check_fn(ctx, args, kwargs);
             }
         }
@@ -2236,12 +2422,14 @@ pub unsafe extern "C" fn kclvm_schema_do_check_with_index_sign_attr(
 
 #[no_mangle]
 #[runtime_fn]
-pub unsafe extern "C" fn kclvm_schema_optional_check(p: *const kclvm_value_ref_t) {
+pub unsafe extern "C" fn kclvm_schema_optional_check(
+    ctx: *mut kclvm_context_t,
+    p: *const kclvm_value_ref_t,
+) {
+    let ctx = mut_ptr_as_ref(ctx);
     let p = ptr_as_ref(p);
-
-    let ctx = Context::current_context();
     if !ctx.cfg.disable_schema_check {
-        p.schema_check_attr_optional(true);
+        p.schema_check_attr_optional(ctx, true);
     }
 }
 
@@ -2261,15 +2449,16 @@ pub unsafe extern "C" fn kclvm_schema_default_settings(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_schema_assert(
+    ctx: *mut kclvm_context_t,
     value: *const kclvm_value_ref_t,
     msg: *const kclvm_value_ref_t,
     config_meta: *const kclvm_value_ref_t,
 ) {
+    let ctx = mut_ptr_as_ref(ctx);
     let value = ptr_as_ref(value);
     let msg = ptr_as_ref(msg);
     let config_meta = ptr_as_ref(config_meta);
     if !value.is_truthy() {
-        let ctx = Context::current_context_mut();
         ctx.set_err_type(&ErrType::SchemaCheckFailure_TYPE);
         if let Some(config_meta_file) = config_meta.get_by_key(CONFIG_META_FILENAME) {
             let config_meta_line = config_meta.get_by_key(CONFIG_META_LINE).unwrap();
@@ -2299,11 +2488,16 @@ pub unsafe extern "C" fn kclvm_schema_value_new(
     config_meta: *const kclvm_value_ref_t,
     pkgpath: *const kclvm_char_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let schema_value_or_func = ptr_as_ref(schema_value_or_func);
     if schema_value_or_func.is_func() {
         let schema_func = schema_value_or_func.as_function();
         let schema_fn_ptr = schema_func.fn_ptr;
         let ctx_ref = mut_ptr_as_ref(ctx);
+        ctx_ref.enter_call();
+        ctx_ref
+            .profiler
+            .begin(PROFILE_KIND_SCHEMA, &schema_func.runtime_type);
         let now_meta_info = ctx_ref.panic_info.clone();
         if ctx_ref.cfg.debug_mode {
             ctx_ref
@@ -2314,8 +2508,8 @@ pub unsafe extern "C" fn kclvm_schema_value_new(
         let value = unsafe {
             let org_args = ptr_as_ref(args).deep_copy();
             let schema_fn: SchemaTypeFunc = transmute_copy(&schema_fn_ptr);
-            let cal_map = kclvm_value_Dict();
-            let instance_pkgpath = kclvm_value_Str(pkgpath);
+            let cal_map = kclvm_value_Dict(ctx);
+            let instance_pkgpath = kclvm_value_Str(ctx, pkgpath);
             // Schema function closures
             let values = [
                 // is_sub_schema
@@ -2325,15 +2519,15 @@ pub unsafe extern "C" fn kclvm_schema_value_new(
                 // Config value
                 config,
                 // Schema value
-                kclvm_value_Dict(),
+                kclvm_value_Dict(ctx),
                 // optional_mapping
-                kclvm_value_Dict(),
+                kclvm_value_Dict(ctx),
                 // cal order map
                 cal_map,
                 // backtrack level map
-                kclvm_value_Dict(),
+                kclvm_value_Dict(ctx),
                 // backtrack cache
-                kclvm_value_Dict(),
+                kclvm_value_Dict(ctx),
                 // record instance
                 kclvm_value_Bool(0),
                 // instance pkgpath
@@ -2344,7 +2538,7 @@ pub unsafe extern "C" fn kclvm_schema_value_new(
             }
             schema_fn(ctx, args, kwargs);
             // schema args
-            let args = org_args.into_raw();
+            let args = org_args.into_raw(ctx);
             let values = [
                 // is_sub_schema
                 kclvm_value_Bool(1),
@@ -2353,15 +2547,15 @@ pub unsafe extern "C" fn kclvm_schema_value_new(
                 // Config value
                 config,
                 // Schema value
-                kclvm_value_Dict(),
+                kclvm_value_Dict(ctx),
                 // optional_mapping
-                kclvm_value_Dict(),
+                kclvm_value_Dict(ctx),
                 // cal order map
                 cal_map,
                 // backtrack level map
-                kclvm_value_Dict(),
+                kclvm_value_Dict(ctx),
                 // backtrack cache
-                kclvm_value_Dict(),
+                kclvm_value_Dict(ctx),
                 // record instance
                 kclvm_value_Bool(1),
                 // instance pkgpath
@@ -2376,31 +2570,36 @@ pub unsafe extern "C" fn kclvm_schema_value_new(
         if ctx_ref.cfg.debug_mode {
             ctx_ref.backtrace.pop();
         }
+        ctx_ref.profiler.end();
+        ctx_ref.leave_call();
         value
     } else {
         let config = ptr_as_ref(config);
         let result = schema_value_or_func
             .deep_copy()
-            .union_entry(config, true, false, true, true);
-        result.into_raw()
+            .union_entry(ctx, config, true, false, true, true);
+        result.into_raw(ctx)
     }
 }
 
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_convert_collection_value(
+    ctx: *mut kclvm_context_t,
     value: *const kclvm_value_ref_t,
     tpe: *const kclvm_char_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let value = ptr_as_ref(value);
     let tpe = c2str(tpe);
-    let value = type_pack_and_check(value, vec![tpe]);
-    value.into_raw()
+    let value = type_pack_and_check(ctx, value, vec![tpe]);
+    value.into_raw(ctx)
 }
 
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_schema_get_value(
+    ctx: *mut kclvm_context_t,
     p: *const kclvm_value_ref_t,
     key: *const kclvm_char_t,
     config: *const kclvm_value_ref_t,
@@ -2412,6 +2611,7 @@ pub unsafe extern "C" fn kclvm_schema_get_value(
     args: *mut kclvm_value_ref_t,
     kwargs: *mut kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let schema = ptr_as_ref(p);
     let key = c2str(key);
     let cal_map = ptr_as_ref(cal_map);
@@ -2429,12 +2629,12 @@ pub unsafe extern "C" fn kclvm_schema_get_value(
     // Deal in-place modify and return it self immediately
     if key == target_attr && !is_backtracking {
         match schema.dict_get_value(key) {
-            Some(x) => return x.into_raw(),
-            None => return kclvm_value_Undefined(),
+            Some(x) => return x.into_raw(ctx),
+            None => return kclvm_value_Undefined(ctx),
         }
     }
     if let Some(v) = backtrack_cache.dict_get_value(key) {
-        return v.into_raw();
+        return v.into_raw(ctx);
     }
     if let Some(attr_code) = cal_map.dict_get_value(key) {
         let now_level = level + 1;
@@ -2447,13 +2647,12 @@ pub unsafe extern "C" fn kclvm_schema_get_value(
                 Some(x) => x,
                 None => ValueRef::undefined(),
             };
-            return value.into_raw();
+            return value.into_raw(ctx);
         }
         let fn_ptr = &attr_code.values[index];
         let fn_ptr = fn_ptr.as_int();
         // When we calculate other schema attribute values, we retain
         // the row and column number information of the current schema attribute.
-        let ctx = Context::current_context_mut();
         let panic_info = ctx.panic_info.clone();
         unsafe {
             let attr_fn: SchemaTypeFunc = transmute_copy(&fn_ptr);
@@ -2472,9 +2671,8 @@ pub unsafe extern "C" fn kclvm_schema_get_value(
             args.list_append(cal_map);
             args.list_append(backtrack_level_map);
             args.list_append(backtrack_cache);
-            let args = args.into_raw();
-            let kwargs = kwargs.clone().into_raw();
-            let ctx = kclvm_context_current();
+            let args = args.into_raw(ctx);
+            let kwargs = kwargs.clone().into_raw(ctx);
             attr_fn(ctx, args, kwargs);
         };
         // Restore the panic info of current schema attribute.
@@ -2487,8 +2685,8 @@ pub unsafe extern "C" fn kclvm_schema_get_value(
         backtrack_cache.dict_update_key_value(key, value);
     }
     match schema.dict_get_value(key) {
-        Some(x) => x.into_raw(),
-        None => kclvm_value_Undefined(),
+        Some(x) => x.into_raw(ctx),
+        None => kclvm_value_Undefined(ctx),
     }
 }
 
@@ -2512,6 +2710,7 @@ pub unsafe extern "C" fn kclvm_config_attr_map(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_value_Decorator(
+    ctx: *mut kclvm_context_t,
     name: *const kclvm_char_t,
     args: *const kclvm_value_ref_t,
     kwargs: *const kclvm_value_ref_t,
@@ -2520,6 +2719,7 @@ pub unsafe extern "C" fn kclvm_value_Decorator(
     config_value: *const kclvm_value_ref_t,
     is_schema_target: *const kclvm_value_ref_t,
 ) -> *const kclvm_decorator_value_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let name = c2str(name);
     let args = ptr_as_ref(args);
     let kwargs = ptr_as_ref(kwargs);
@@ -2529,6 +2729,7 @@ pub unsafe extern "C" fn kclvm_value_Decorator(
     let is_schema_target = ptr_as_ref(is_schema_target);
     let decorator = DecoratorValue::new(name, args, kwargs);
     decorator.run(
+        ctx,
         attr_name,
         is_schema_target.as_bool(),
         config_value,
@@ -2544,13 +2745,14 @@ pub unsafe extern "C" fn kclvm_value_Decorator(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_builtin_str_lower(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     args: *const kclvm_value_ref_t,
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
     if let Some(val) = args.pop_arg_first() {
-        val.str_lower().into_raw()
+        val.str_lower().into_raw(ctx)
     } else {
         panic!("invalid self value in str_lower");
     }
@@ -2559,13 +2761,14 @@ pub unsafe extern "C" fn kclvm_builtin_str_lower(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_builtin_str_upper(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     args: *const kclvm_value_ref_t,
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
     if let Some(val) = args.pop_arg_first() {
-        val.str_upper().into_raw()
+        val.str_upper().into_raw(ctx)
     } else {
         panic!("invalid self value in str_upper");
     }
@@ -2574,13 +2777,14 @@ pub unsafe extern "C" fn kclvm_builtin_str_upper(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_builtin_str_capitalize(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     args: *const kclvm_value_ref_t,
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
     if let Some(val) = args.pop_arg_first() {
-        val.str_capitalize().into_raw()
+        val.str_capitalize().into_raw(ctx)
     } else {
         panic!("invalid self value in str_capitalize");
     }
@@ -2589,16 +2793,18 @@ pub unsafe extern "C" fn kclvm_builtin_str_capitalize(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_builtin_str_count(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     args: *const kclvm_value_ref_t,
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
     if let Some(val) = args.pop_arg_first() {
         if let Some(sub) = args.arg_0() {
             let start = args.arg_i(1);
             let end = args.arg_i(2);
-            val.str_count(&sub, start.as_ref(), end.as_ref()).into_raw()
+            val.str_count(&sub, start.as_ref(), end.as_ref())
+                .into_raw(ctx)
         } else {
             panic!("count() takes at least 1 argument (0 given)");
         }
@@ -2610,17 +2816,18 @@ pub unsafe extern "C" fn kclvm_builtin_str_count(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_builtin_str_endswith(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     args: *const kclvm_value_ref_t,
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
     if let Some(val) = args.pop_arg_first() {
         if let Some(suffix) = args.arg_0() {
             let start = args.arg_i(1);
             let end = args.arg_i(2);
             val.str_endswith(&suffix, start.as_ref(), end.as_ref())
-                .into_raw()
+                .into_raw(ctx)
         } else {
             panic!("endswith() takes at least 1 argument (0 given)");
         }
@@ -2632,16 +2839,18 @@ pub unsafe extern "C" fn kclvm_builtin_str_endswith(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_builtin_str_find(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     args: *const kclvm_value_ref_t,
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
     if let Some(val) = args.pop_arg_first() {
         if let Some(sub) = args.arg_0() {
             let start = args.arg_i(1);
             let end = args.arg_i(2);
-            val.str_find(&sub, start.as_ref(), end.as_ref()).into_raw()
+            val.str_find(&sub, start.as_ref(), end.as_ref())
+                .into_raw(ctx)
         } else {
             panic!("find() takes at least 1 argument (0 given)");
         }
@@ -2653,14 +2862,15 @@ pub unsafe extern "C" fn kclvm_builtin_str_find(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_builtin_str_format(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     args: *const kclvm_value_ref_t,
     kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
     let kwargs = ptr_as_ref(kwargs);
     if let Some(val) = args.pop_arg_first() {
-        val.str_format(args, kwargs).into_raw()
+        val.str_format(args, kwargs).into_raw(ctx)
     } else {
         panic!("invalid self value in str_format");
     }
@@ -2669,16 +2879,18 @@ pub unsafe extern "C" fn kclvm_builtin_str_format(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_builtin_str_index(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     args: *const kclvm_value_ref_t,
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
     if let Some(val) = args.pop_arg_first() {
         if let Some(sub) = args.arg_0() {
             let start = args.arg_i(1);
             let end = args.arg_i(2);
-            val.str_index(&sub, start.as_ref(), end.as_ref()).into_raw()
+            val.str_index(&sub, start.as_ref(), end.as_ref())
+                .into_raw(ctx)
         } else {
             panic!("index() takes at least 1 argument (0 given)");
         }
@@ -2690,13 +2902,14 @@ pub unsafe extern "C" fn kclvm_builtin_str_index(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_builtin_str_isalnum(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     args: *const kclvm_value_ref_t,
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
     if let Some(val) = args.pop_arg_first() {
-        val.str_isalnum().into_raw()
+        val.str_isalnum().into_raw(ctx)
     } else {
         panic!("invalid self value in str_isalnum");
     }
@@ -2705,13 +2918,14 @@ pub unsafe extern "C" fn kclvm_builtin_str_isalnum(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_builtin_str_isalpha(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     args: *const kclvm_value_ref_t,
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
     if let Some(val) = args.pop_arg_first() {
-        val.str_isalpha().into_raw()
+        val.str_isalpha().into_raw(ctx)
     } else {
         panic!("invalid self value in str_isalpha");
     }
@@ -2720,13 +2934,14 @@ pub unsafe extern "C" fn kclvm_builtin_str_isalpha(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_builtin_str_isdigit(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     args: *const kclvm_value_ref_t,
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
     if let Some(val) = args.pop_arg_first() {
-        val.str_isdigit().into_raw()
+        val.str_isdigit().into_raw(ctx)
     } else {
         panic!("invalid self value in str_isdigit");
     }
@@ -2735,13 +2950,14 @@ pub unsafe extern "C" fn kclvm_builtin_str_isdigit(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_builtin_str_islower(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     args: *const kclvm_value_ref_t,
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
     if let Some(val) = args.pop_arg_first() {
-        val.str_islower().into_raw()
+        val.str_islower().into_raw(ctx)
     } else {
         panic!("invalid self value in str_islower");
     }
@@ -2750,13 +2966,14 @@ pub unsafe extern "C" fn kclvm_builtin_str_islower(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_builtin_str_isspace(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     args: *const kclvm_value_ref_t,
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
     if let Some(val) = args.pop_arg_first() {
-        val.str_isspace().into_raw()
+        val.str_isspace().into_raw(ctx)
     } else {
         panic!("invalid self value in str_isspace");
     }
@@ -2765,13 +2982,14 @@ pub unsafe extern "C" fn kclvm_builtin_str_isspace(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_builtin_str_istitle(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     args: *const kclvm_value_ref_t,
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
     if let Some(val) = args.pop_arg_first() {
-        val.str_istitle().into_raw()
+        val.str_istitle().into_raw(ctx)
     } else {
         panic!("invalid self value in str_istitle");
     }
@@ -2780,13 +2998,14 @@ pub unsafe extern "C" fn kclvm_builtin_str_istitle(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_builtin_str_isupper(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     args: *const kclvm_value_ref_t,
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
     if let Some(val) = args.pop_arg_first() {
-        val.str_isupper().into_raw()
+        val.str_isupper().into_raw(ctx)
     } else {
         panic!("invalid self value in str_isupper");
     }
@@ -2795,14 +3014,15 @@ pub unsafe extern "C" fn kclvm_builtin_str_isupper(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_builtin_str_join(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     args: *const kclvm_value_ref_t,
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
     if let Some(val) = args.pop_arg_first() {
         let iter = args.arg_i(0).unwrap();
-        val.str_join(&iter).into_raw()
+        val.str_join(&iter).into_raw(ctx)
     } else {
         panic!("invalid self value in str_join");
     }
@@ -2811,14 +3031,15 @@ pub unsafe extern "C" fn kclvm_builtin_str_join(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_builtin_str_lstrip(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     args: *const kclvm_value_ref_t,
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
     if let Some(val) = args.pop_arg_first() {
         let chars = args.arg_i(0);
-        val.str_lstrip(chars.as_ref()).into_raw()
+        val.str_lstrip(chars.as_ref()).into_raw(ctx)
     } else {
         panic!("invalid self value in str_lstrip");
     }
@@ -2827,14 +3048,15 @@ pub unsafe extern "C" fn kclvm_builtin_str_lstrip(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_builtin_str_rstrip(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     args: *const kclvm_value_ref_t,
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
     if let Some(val) = args.pop_arg_first() {
         let chars = args.arg_i(0);
-        val.str_rstrip(chars.as_ref()).into_raw()
+        val.str_rstrip(chars.as_ref()).into_raw(ctx)
     } else {
         panic!("invalid self value in str_rstrip");
     }
@@ -2843,16 +3065,17 @@ pub unsafe extern "C" fn kclvm_builtin_str_rstrip(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_builtin_str_replace(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     args: *const kclvm_value_ref_t,
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
     if let Some(val) = args.pop_arg_first() {
         let old = args.arg_i(0).unwrap();
         let new = args.arg_i(1).unwrap();
         let count = args.arg_i(2);
-        val.str_replace(&old, &new, count.as_ref()).into_raw()
+        val.str_replace(&old, &new, count.as_ref()).into_raw(ctx)
     } else {
         panic!("invalid self value in str_replace");
     }
@@ -2863,14 +3086,15 @@ pub unsafe extern "C" fn kclvm_builtin_str_replace(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_builtin_str_removeprefix(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     args: *const kclvm_value_ref_t,
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
     if let Some(val) = args.pop_arg_first() {
         let prefix = args.arg_i(0).unwrap();
-        val.str_removeprefix(&prefix).into_raw()
+        val.str_removeprefix(&prefix).into_raw(ctx)
     } else {
         panic!("invalid self value in str_removeprefix");
     }
@@ -2881,14 +3105,15 @@ pub unsafe extern "C" fn kclvm_builtin_str_removeprefix(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_builtin_str_removesuffix(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     args: *const kclvm_value_ref_t,
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
     if let Some(val) = args.pop_arg_first() {
         let suffix = args.arg_i(0).unwrap();
-        val.str_removesuffix(&suffix).into_raw()
+        val.str_removesuffix(&suffix).into_raw(ctx)
     } else {
         panic!("invalid self value in str_removesuffix");
     }
@@ -2897,16 +3122,18 @@ pub unsafe extern "C" fn kclvm_builtin_str_removesuffix(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_builtin_str_rfind(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     args: *const kclvm_value_ref_t,
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
     if let Some(val) = args.pop_arg_first() {
         if let Some(sub) = args.arg_0() {
             let start = args.arg_i(1);
             let end = args.arg_i(2);
-            val.str_rfind(&sub, start.as_ref(), end.as_ref()).into_raw()
+            val.str_rfind(&sub, start.as_ref(), end.as_ref())
+                .into_raw(ctx)
         } else {
             panic!("rfind() takes at least 1 argument (0 given)");
         }
@@ -2918,17 +3145,18 @@ pub unsafe extern "C" fn kclvm_builtin_str_rfind(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_builtin_str_rindex(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     args: *const kclvm_value_ref_t,
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
     if let Some(val) = args.pop_arg_first() {
         if let Some(sub) = args.arg_0() {
             let start = args.arg_i(1);
             let end = args.arg_i(2);
             val.str_rindex(&sub, start.as_ref(), end.as_ref())
-                .into_raw()
+                .into_raw(ctx)
         } else {
             panic!("rindex() takes at least 1 argument (0 given)");
         }
@@ -2940,10 +3168,11 @@ pub unsafe extern "C" fn kclvm_builtin_str_rindex(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_builtin_str_rsplit(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     args: *const kclvm_value_ref_t,
     kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
     let kwargs = ptr_as_ref(kwargs);
     if let Some(val) = args.pop_arg_first() {
@@ -2957,7 +3186,8 @@ pub unsafe extern "C" fn kclvm_builtin_str_rsplit(
         } else {
             kwargs.kwarg("maxsplit")
         };
-        val.str_rsplit(sep.as_ref(), maxsplit.as_ref()).into_raw()
+        val.str_rsplit(sep.as_ref(), maxsplit.as_ref())
+            .into_raw(ctx)
     } else {
         panic!("invalid self value in str_rsplit");
     }
@@ -2966,10 +3196,11 @@ pub unsafe extern "C" fn kclvm_builtin_str_rsplit(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_builtin_str_split(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     args: *const kclvm_value_ref_t,
     kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
     let kwargs = ptr_as_ref(kwargs);
     if let Some(val) = args.pop_arg_first() {
@@ -2984,7 +3215,7 @@ pub unsafe extern "C" fn kclvm_builtin_str_split(
             kwargs.kwarg("maxsplit")
         };
         let x = val.str_split(sep.as_ref(), maxsplit.as_ref());
-        x.into_raw()
+        x.into_raw(ctx)
     } else {
         panic!("invalid self value in str_split");
     }
@@ -2993,19 +3224,20 @@ pub unsafe extern "C" fn kclvm_builtin_str_split(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_builtin_str_splitlines(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     args: *const kclvm_value_ref_t,
     kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
     let kwargs = ptr_as_ref(kwargs);
     if let Some(val) = args.pop_arg_first() {
         if let Some(keepends) = args.arg_i(0) {
-            val.str_splitlines(Some(&keepends)).into_raw()
+            val.str_splitlines(Some(&keepends)).into_raw(ctx)
         } else if let Some(keepends) = kwargs.kwarg("keepends") {
-            val.str_splitlines(Some(&keepends)).into_raw()
+            val.str_splitlines(Some(&keepends)).into_raw(ctx)
         } else {
-            val.str_splitlines(None).into_raw()
+            val.str_splitlines(None).into_raw(ctx)
         }
     } else {
         panic!("invalid self value in str_splitlines");
@@ -3015,17 +3247,18 @@ pub unsafe extern "C" fn kclvm_builtin_str_splitlines(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_builtin_str_startswith(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     args: *const kclvm_value_ref_t,
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
     if let Some(val) = args.pop_arg_first() {
         if let Some(suffix) = args.arg_0() {
             let start = args.arg_i(1);
             let end = args.arg_i(2);
             val.str_startswith(&suffix, start.as_ref(), end.as_ref())
-                .into_raw()
+                .into_raw(ctx)
         } else {
             panic!("startswith() takes at least 1 argument (0 given)");
         }
@@ -3037,14 +3270,15 @@ pub unsafe extern "C" fn kclvm_builtin_str_startswith(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_builtin_str_strip(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     args: *const kclvm_value_ref_t,
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
     if let Some(val) = args.pop_arg_first() {
         let chars = args.arg_i(0);
-        val.str_strip(chars.as_ref()).into_raw()
+        val.str_strip(chars.as_ref()).into_raw(ctx)
     } else {
         panic!("invalid self value in str_strip");
     }
@@ -3053,13 +3287,14 @@ pub unsafe extern "C" fn kclvm_builtin_str_strip(
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_builtin_str_title(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     args: *const kclvm_value_ref_t,
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
     if let Some(val) = args.pop_arg_first() {
-        val.str_title().into_raw()
+        val.str_title().into_raw(ctx)
     } else {
         panic!("invalid self value in str_title");
     }
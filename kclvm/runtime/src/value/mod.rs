@@ -75,6 +75,9 @@ pub use val_bin::*;
 pub mod val_plan;
 pub use val_plan::*;
 
+pub mod val_planner;
+pub use val_planner::*;
+
 pub mod val_str;
 pub use val_str::*;
 
@@ -86,3 +89,6 @@ pub use val_union::*;
 
 pub mod val_yaml;
 pub use val_yaml::*;
+
+pub mod val_toml;
+pub use val_toml::*;
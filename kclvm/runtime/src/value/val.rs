@@ -81,6 +81,21 @@ impl ValueRef {
             name: name.to_string(),
             runtime_type: runtime_type.to_string(),
             is_external,
+            proxy: None,
+        })))
+    }
+
+    /// New a proxy function with the proxy index and runtime type,
+    /// the proxy function is invoked by the AST evaluator rather than the fn ptr.
+    pub fn proxy_func(proxy: u64, name: &str, runtime_type: &str) -> Self {
+        Self::from(Value::func_value(Box::new(FuncValue {
+            fn_ptr: 0,
+            check_fn_ptr: 0,
+            closure: ValueRef::undefined(),
+            name: name.to_string(),
+            runtime_type: runtime_type.to_string(),
+            is_external: false,
+            proxy: Some(proxy),
         })))
     }
 }
@@ -3,39 +3,38 @@
 use crate::*;
 
 impl ValueRef {
-    pub fn bin_aug_add(&mut self, x: &Self) -> &mut Self {
-        let ctx = crate::Context::current_context_mut();
+    pub fn bin_aug_add(&mut self, ctx: &mut Context, x: &Self) -> &mut Self {
         let strict_range_check_32 = ctx.cfg.strict_range_check;
         let strict_range_check_64 = ctx.cfg.debug_mode || !ctx.cfg.strict_range_check;
 
         let valid = match (&mut *self.rc.borrow_mut(), &*x.rc.borrow()) {
             (Value::int_value(a), Value::int_value(b)) => {
                 if strict_range_check_32 && is_i32_overflow_add(*a, *b) {
-                    panic_i32_overflow!(*a as i128 + *b as i128);
+                    panic_i32_overflow!(ctx, *a as i128 + *b as i128);
                 }
                 if strict_range_check_64 && is_i64_overflow_add(*a, *b) {
-                    panic_i64_overflow!(*a as i128 + *b as i128);
+                    panic_i64_overflow!(ctx, *a as i128 + *b as i128);
                 }
                 *a += *b;
                 true
             }
             (Value::float_value(a), Value::float_value(b)) => {
                 if strict_range_check_32 && is_f32_overflow_add(*a, *b) {
-                    panic_f32_overflow!(*a + *b);
+                    panic_f32_overflow!(ctx, *a + *b);
                 }
                 *a += *b;
                 true
             }
             (Value::int_value(a), Value::float_value(b)) => {
                 if is_f32_overflow_add(*a as f64, *b) {
-                    panic_f32_overflow!(*a as f64 + *b);
+                    panic_f32_overflow!(ctx, *a as f64 + *b);
                 }
                 *a += *b as i64;
                 true
             }
             (Value::float_value(a), Value::int_value(b)) => {
                 if is_f32_overflow_add(*a, *b as f64) {
-                    panic_f32_overflow!(*a + *b as f64);
+                    panic_f32_overflow!(ctx, *a + *b as f64);
                 }
                 *a += *b as f64;
                 true
@@ -61,19 +60,18 @@ impl ValueRef {
         self
     }
 
-    pub fn bin_aug_sub(&mut self, x: &Self) -> &mut Self {
-        let ctx = crate::Context::current_context_mut();
+    pub fn bin_aug_sub(&mut self, ctx: &mut Context, x: &Self) -> &mut Self {
         let strict_range_check_32 = ctx.cfg.strict_range_check;
         let strict_range_check_64 = ctx.cfg.debug_mode || !ctx.cfg.strict_range_check;
 
         let valid = match (&mut *self.rc.borrow_mut(), &*x.rc.borrow()) {
             (Value::int_value(a), Value::int_value(b)) => {
                 if strict_range_check_32 && is_i32_overflow_sub(*a, *b) {
-                    panic_i32_overflow!(*a as i128 - *b as i128);
+                    panic_i32_overflow!(ctx, *a as i128 - *b as i128);
                 }
                 if strict_range_check_64 && is_i64_overflow_sub(*a, *b) {
                     {
-                        panic_i32_overflow!(*a as i128 - *b as i128);
+                        panic_i32_overflow!(ctx, *a as i128 - *b as i128);
                     }
                 }
                 *a -= *b;
@@ -81,21 +79,21 @@ impl ValueRef {
             }
             (Value::float_value(a), Value::float_value(b)) => {
                 if strict_range_check_32 && is_f32_overflow_sub(*a, *b) {
-                    panic_f32_overflow!(*a - *b);
+                    panic_f32_overflow!(ctx, *a - *b);
                 }
                 *a -= *b;
                 true
             }
             (Value::int_value(a), Value::float_value(b)) => {
                 if strict_range_check_32 && is_f32_overflow_sub(*a as f64, *b) {
-                    panic_f32_overflow!(*a as f64 - *b);
+                    panic_f32_overflow!(ctx, *a as f64 - *b);
                 }
                 *a -= *b as i64;
                 true
             }
             (Value::float_value(a), Value::int_value(b)) => {
                 if strict_range_check_32 && is_f32_overflow_sub(*a, *b as f64) {
-                    panic_f32_overflow!(*a - *b as f64);
+                    panic_f32_overflow!(ctx, *a - *b as f64);
                 }
                 *a -= *b as f64;
                 true
@@ -108,39 +106,38 @@ impl ValueRef {
         self
     }
 
-    pub fn bin_aug_mul(&mut self, x: &Self) -> &mut Self {
-        let ctx = crate::Context::current_context_mut();
+    pub fn bin_aug_mul(&mut self, ctx: &mut Context, x: &Self) -> &mut Self {
         let strict_range_check_32 = ctx.cfg.strict_range_check;
         let strict_range_check_64 = ctx.cfg.debug_mode || !ctx.cfg.strict_range_check;
 
         let valid = match (&mut *self.rc.borrow_mut(), &*x.rc.borrow()) {
             (Value::int_value(a), Value::int_value(b)) => {
                 if strict_range_check_32 && is_i32_overflow_mul(*a, *b) {
-                    panic_i32_overflow!(*a as i128 * *b as i128);
+                    panic_i32_overflow!(ctx, *a as i128 * *b as i128);
                 }
                 if strict_range_check_64 && is_i64_overflow_mul(*a, *b) {
-                    panic_i64_overflow!(*a as i128 * *b as i128);
+                    panic_i64_overflow!(ctx, *a as i128 * *b as i128);
                 }
                 *a *= *b;
                 true
             }
             (Value::float_value(a), Value::float_value(b)) => {
                 if strict_range_check_32 && is_f32_overflow_mul(*a, *b) {
-                    panic_f32_overflow!(*a * *b);
+                    panic_f32_overflow!(ctx, *a * *b);
                 }
                 *a *= *b;
                 true
             }
             (Value::int_value(a), Value::float_value(b)) => {
                 if strict_range_check_32 && is_f32_overflow_mul(*a as f64, *b) {
-                    panic_f32_overflow!(*a as f64 * *b);
+                    panic_f32_overflow!(ctx, *a as f64 * *b);
                 }
                 *a *= *b as i64;
                 true
             }
             (Value::float_value(a), Value::int_value(b)) => {
                 if strict_range_check_32 && is_f32_overflow_mul(*a, *b as f64) {
-                    panic_f32_overflow!(*a * *b as f64);
+                    panic_f32_overflow!(ctx, *a * *b as f64);
                 }
                 *a *= *b as f64;
                 true
@@ -227,39 +224,38 @@ impl ValueRef {
         self
     }
 
-    pub fn bin_aug_pow(&mut self, x: &Self) -> &mut Self {
-        let ctx = crate::Context::current_context_mut();
+    pub fn bin_aug_pow(&mut self, ctx: &mut Context, x: &Self) -> &mut Self {
         let strict_range_check_32 = ctx.cfg.strict_range_check;
         let strict_range_check_64 = ctx.cfg.debug_mode || !ctx.cfg.strict_range_check;
 
         let valid = match (&mut *self.rc.borrow_mut(), &*x.rc.borrow()) {
             (Value::int_value(a), Value::int_value(b)) => {
                 if strict_range_check_32 && is_i32_overflow_pow(*a, *b) {
-                    panic_i32_overflow!((*a as i128).pow(*b as u32));
+                    panic_i32_overflow!(ctx, (*a as i128).pow(*b as u32));
                 }
                 if strict_range_check_64 && is_i64_overflow_pow(*a, *b) {
-                    panic_i64_overflow!((*a as i128).pow(*b as u32));
+                    panic_i64_overflow!(ctx, (*a as i128).pow(*b as u32));
                 }
                 *a = a.pow(*b as u32);
                 true
             }
             (Value::float_value(a), Value::float_value(b)) => {
                 if strict_range_check_32 && is_f32_overflow_pow(*a, *b) {
-                    panic_f32_overflow!(a.powf(*b));
+                    panic_f32_overflow!(ctx, a.powf(*b));
                 }
                 *a = a.powf(*b);
                 true
             }
             (Value::int_value(a), Value::float_value(b)) => {
                 if strict_range_check_32 && is_f32_overflow_pow(*a as f64, *b) {
-                    panic_f32_overflow!((*a as f64).powf(*b));
+                    panic_f32_overflow!(ctx, (*a as f64).powf(*b));
                 }
                 *a = a.pow(*b as u32);
                 true
             }
             (Value::float_value(a), Value::int_value(b)) => {
                 if strict_range_check_32 && is_f32_overflow_pow(*a, *b as f64) {
-                    panic_f32_overflow!(a.powf(*b as f64));
+                    panic_f32_overflow!(ctx, a.powf(*b as f64));
                 }
                 *a = a.powf(*b as f64);
                 true
@@ -304,18 +300,17 @@ impl ValueRef {
         self
     }
 
-    pub fn bin_aug_bit_lshift(&mut self, x: &Self) -> &mut Self {
-        let ctx = crate::Context::current_context_mut();
+    pub fn bin_aug_bit_lshift(&mut self, ctx: &mut Context, x: &Self) -> &mut Self {
         let strict_range_check_32 = ctx.cfg.strict_range_check;
         let strict_range_check_64 = ctx.cfg.debug_mode || !ctx.cfg.strict_range_check;
 
         let valid = match (&mut *self.rc.borrow_mut(), &*x.rc.borrow()) {
             (Value::int_value(a), Value::int_value(b)) => {
                 if strict_range_check_32 && is_i32_overflow_shl(*a, *b) {
-                    panic_i32_overflow!((*a as i128) << (*b as u32));
+                    panic_i32_overflow!(ctx, (*a as i128) << (*b as u32));
                 }
                 if strict_range_check_64 && is_i64_overflow_shl(*a, *b) {
-                    panic_i64_overflow!((*a as i128) << (*b as u32));
+                    panic_i64_overflow!(ctx, (*a as i128) << (*b as u32));
                 }
                 *a <<= *b as usize;
                 true
@@ -328,18 +323,17 @@ impl ValueRef {
         self
     }
 
-    pub fn bin_aug_bit_rshift(&mut self, x: &Self) -> &mut Self {
-        let ctx = crate::Context::current_context_mut();
+    pub fn bin_aug_bit_rshift(&mut self, ctx: &mut Context, x: &Self) -> &mut Self {
         let strict_range_check_32 = ctx.cfg.strict_range_check;
         let strict_range_check_64 = ctx.cfg.debug_mode || !ctx.cfg.strict_range_check;
 
         let valid = match (&mut *self.rc.borrow_mut(), &*x.rc.borrow()) {
             (Value::int_value(a), Value::int_value(b)) => {
                 if strict_range_check_32 && is_i32_overflow_shr(*a, *b) {
-                    panic_i32_overflow!((*a as i128) >> (*b as u32));
+                    panic_i32_overflow!(ctx, (*a as i128) >> (*b as u32));
                 }
                 if strict_range_check_64 && is_i64_overflow_shr(*a, *b) {
-                    panic_i64_overflow!((*a as i128) >> (*b as u32));
+                    panic_i64_overflow!(ctx, (*a as i128) >> (*b as u32));
                 }
                 *a >>= *b as usize;
                 true
@@ -380,7 +374,7 @@ impl ValueRef {
         self
     }
 
-    pub fn bin_aug_bit_or(&mut self, x: &Self) -> &mut Self {
+    pub fn bin_aug_bit_or(&mut self, ctx: &mut Context, x: &Self) -> &mut Self {
         let valid = match (&mut *self.rc.borrow_mut(), &*x.rc.borrow()) {
             (Value::int_value(a), Value::int_value(b)) => {
                 *a |= *b;
@@ -390,7 +384,7 @@ impl ValueRef {
         };
         if !valid {
             if self.is_list_or_config() || x.is_list_or_config() {
-                self.union_entry(x, true, false, true, true);
+                self.union_entry(ctx, x, true, false, true, true);
             } else {
                 panic_unsupported_bin_op!("|", self.type_str(), x.type_str());
             }
@@ -399,8 +393,8 @@ impl ValueRef {
     }
 
     /// Binary aug union a | b
-    pub fn bin_aug_union_with(&mut self, x: &Self) -> &mut Self {
-        self.bin_aug_bit_or(x)
+    pub fn bin_aug_union_with(&mut self, ctx: &mut Context, x: &Self) -> &mut Self {
+        self.bin_aug_bit_or(ctx, x)
     }
 }
 
@@ -410,6 +404,7 @@ mod test_value_bin_aug {
 
     #[test]
     fn test_int_bin() {
+        let mut ctx = Context::new();
         let cases = [
             (0, 0, "+", 0),
             (1, 1, "-", 0),
@@ -428,17 +423,17 @@ mod test_value_bin_aug {
             let mut left = ValueRef::int(left);
             let right = ValueRef::int(right);
             let result = match op {
-                "+" => left.bin_aug_add(&right),
-                "-" => left.bin_aug_sub(&right),
-                "*" => left.bin_aug_mul(&right),
+                "+" => left.bin_aug_add(&mut ctx, &right),
+                "-" => left.bin_aug_sub(&mut ctx, &right),
+                "*" => left.bin_aug_mul(&mut ctx, &right),
                 "/" => left.bin_aug_div(&right),
                 "//" => left.bin_aug_floor_div(&right),
                 "%" => left.bin_aug_mod(&right),
-                "**" => left.bin_aug_pow(&right),
-                "<<" => left.bin_aug_bit_lshift(&right),
-                ">>" => left.bin_aug_bit_rshift(&right),
+                "**" => left.bin_aug_pow(&mut ctx, &right),
+                "<<" => left.bin_aug_bit_lshift(&mut ctx, &right),
+                ">>" => left.bin_aug_bit_rshift(&mut ctx, &right),
                 "&" => left.bin_aug_bit_and(&right),
-                "|" => left.bin_aug_bit_or(&right),
+                "|" => left.bin_aug_bit_or(&mut ctx, &right),
                 "^" => left.bin_aug_bit_xor(&right),
                 _ => panic!("invalid op {}", op),
             };
@@ -448,16 +443,19 @@ mod test_value_bin_aug {
 
     #[test]
     fn test_aug_add() {
+        let mut ctx = Context::new();
         // int
         assert_eq!(
-            ValueRef::int(1).bin_aug_add(&ValueRef::int(2)).as_int(),
+            ValueRef::int(1)
+                .bin_aug_add(&mut ctx, &ValueRef::int(2))
+                .as_int(),
             1 + 2
         );
 
         // float
         assert_eq!(
             ValueRef::float(1.5)
-                .bin_aug_add(&ValueRef::float(2.0))
+                .bin_aug_add(&mut ctx, &ValueRef::float(2.0))
                 .as_float(),
             3.5
         );
@@ -468,14 +466,16 @@ mod test_value_bin_aug {
 
         // int + float => int
         assert_eq!(
-            ValueRef::int(1).bin_aug_add(&ValueRef::float(2.5)).as_int(),
+            ValueRef::int(1)
+                .bin_aug_add(&mut ctx, &ValueRef::float(2.5))
+                .as_int(),
             1 + 2
         );
 
         // float + int => float
         assert_eq!(
             ValueRef::float(1.5)
-                .bin_aug_add(&ValueRef::int(2))
+                .bin_aug_add(&mut ctx, &ValueRef::int(2))
                 .as_float(),
             1.5 + 2.0
         );
@@ -16,6 +16,7 @@ impl DecoratorValue {
 
     pub fn run(
         &self,
+        ctx: &mut Context,
         attr_name: &str,
         is_schema_target: bool,
         config_value: &ValueRef,
@@ -58,7 +59,6 @@ impl DecoratorValue {
                         if !msg.is_empty() {
                             err_msg.push_str(&msg);
                         }
-                        let ctx = Context::current_context_mut();
                         if let (Some(filename), Some(line)) = (filename, line) {
                             ctx.set_kcl_filename(&filename.as_str());
                             ctx.panic_info.kcl_line = line.as_int() as i32;
@@ -72,13 +72,10 @@ impl DecoratorValue {
                     if !msg.is_empty() {
                         err_msg.push_str(&msg);
                     }
-
-                    let ctx = Context::current_context_mut();
                     ctx.set_err_type(&ErrType::Deprecated_Warning_TYPE);
 
                     ctx.set_warnning_message(err_msg.as_str());
                 } else {
-                    let ctx = Context::current_context_mut();
                     let err_msg = format!("{attr_name} was deprecated ");
                     ctx.set_err_type(&ErrType::Deprecated_Warning_TYPE);
                     ctx.set_warnning_message(err_msg.as_str());
@@ -115,7 +112,13 @@ mod test_value_decorator {
         let schema_name = "Data";
         let config_meta = ValueRef::dict(None);
         let config_value = ValueRef::dict_str(&[("key1", "value1")]);
-        test_deprecated_decorator.run(schema_name, true, &config_value, &config_meta);
+        test_deprecated_decorator.run(
+            &mut Context::new(),
+            schema_name,
+            true,
+            &config_value,
+            &config_meta,
+        );
     }
 
     #[test]
@@ -128,7 +131,13 @@ mod test_value_decorator {
             let schema_name = "Data";
             let config_meta = ValueRef::dict(None);
             let config_value = ValueRef::dict_str(&[("key1", "value1")]);
-            test_deprecated_decorator.run(schema_name, true, &config_value, &config_meta);
+            test_deprecated_decorator.run(
+                &mut Context::new(),
+                schema_name,
+                true,
+                &config_value,
+                &config_meta,
+            );
         });
     }
 }
@@ -27,6 +27,73 @@ impl ValueRef {
         }
         Some(val)
     }
+
+    /// Set the value at the dot separated `path`, and the missing configs on
+    /// the path are created.
+    pub fn set_by_path(&mut self, path: &str, value: &ValueRef) -> Result<(), String> {
+        let (mut parent, key) = self.get_parent_by_path(path, true)?;
+        if parent.is_config() {
+            parent.dict_update_key_value(key, value.clone());
+            return Ok(());
+        }
+        match key.parse::<usize>() {
+            Ok(i) if parent.is_list() && i < parent.len() => {
+                parent.list_set(i, value);
+                Ok(())
+            }
+            _ => Err(format!("invalid path {path}, {key} can not be set")),
+        }
+    }
+
+    /// Remove the value at the dot separated `path`, and it is not an error
+    /// when the value does not exist.
+    pub fn remove_by_path(&mut self, path: &str) -> Result<(), String> {
+        let (mut parent, key) = match self.get_parent_by_path(path, false) {
+            Ok(parent) => parent,
+            Err(_) => return Ok(()),
+        };
+        if parent.is_config() {
+            // Keep the order of the remaining keys in the output.
+            parent.as_dict_mut_ref().values.shift_remove(key);
+        } else if let Ok(i) = key.parse::<usize>() {
+            if parent.is_list() && i < parent.len() {
+                parent.list_remove_at(i);
+            }
+        }
+        Ok(())
+    }
+
+    /// Get the parent value and the last key of the dot separated `path`.
+    fn get_parent_by_path<'a>(
+        &self,
+        path: &'a str,
+        create: bool,
+    ) -> Result<(Self, &'a str), String> {
+        let (parent_path, key) = match path.rsplit_once('.') {
+            Some((parent_path, key)) => (Some(parent_path), key),
+            None => (None, path),
+        };
+        let mut val: Self = self.clone();
+        for k in parent_path.map(|p| p.split('.')).into_iter().flatten() {
+            val = match val.get_by_key(k) {
+                Some(x) => x,
+                None if create && val.is_config() => {
+                    let x = ValueRef::dict(None);
+                    val.dict_update_key_value(k, x.clone());
+                    x
+                }
+                None => return Err(format!("invalid path {path}, {k} not found")),
+            };
+        }
+        if val.is_config() || val.is_list() {
+            Ok((val, key))
+        } else {
+            Err(format!(
+                "invalid path {path}, {} is not a config or list",
+                val.type_str()
+            ))
+        }
+    }
 }
 
 #[cfg(test)]
@@ -35,11 +102,24 @@ mod test_value_get {
 
     #[test]
     fn test_get() {
+        let mut ctx = Context::new();
         let mut list_int = ValueRef::list_int(&[10_i64, 20, 30]);
 
         let mut dict = ValueRef::dict(None);
-        dict.dict_insert("a", &ValueRef::str("a-value"), Default::default(), 0);
-        dict.dict_insert("b", &ValueRef::str("b-value"), Default::default(), 0);
+        dict.dict_insert(
+            &mut ctx,
+            "a",
+            &ValueRef::str("a-value"),
+            Default::default(),
+            0,
+        );
+        dict.dict_insert(
+            &mut ctx,
+            "b",
+            &ValueRef::str("b-value"),
+            Default::default(),
+            0,
+        );
 
         list_int.list_set(1, &dict);
         list_int.list_set(2, &ValueRef::list_int(&[100_i64, 200, 300]));
@@ -64,4 +144,26 @@ mod test_value_get {
         assert_eq!(dict.get_by_path("bbb.1").unwrap().as_str(), "b");
         assert_eq!(dict.get_by_path("bbb.2.key0").unwrap().as_int(), 12345);
     }
+
+    #[test]
+    fn test_set_and_remove_by_path() {
+        let mut dict = ValueRef::dict(Some(&[
+            ("aaa", &ValueRef::int(111)),
+            ("bbb", &ValueRef::list_int(&[1_i64, 2, 3])),
+        ]));
+        dict.set_by_path("aaa", &ValueRef::int(222)).unwrap();
+        dict.set_by_path("bbb.1", &ValueRef::int(20)).unwrap();
+        dict.set_by_path("ccc.ddd", &ValueRef::str("d")).unwrap();
+        assert_eq!(dict.get_by_path("aaa").unwrap().as_int(), 222);
+        assert_eq!(dict.get_by_path("bbb.1").unwrap().as_int(), 20);
+        assert_eq!(dict.get_by_path("ccc.ddd").unwrap().as_str(), "d");
+        assert!(dict.set_by_path("bbb.3", &ValueRef::int(4)).is_err());
+        assert!(dict.set_by_path("aaa.bbb", &ValueRef::int(4)).is_err());
+
+        dict.remove_by_path("ccc.ddd").unwrap();
+        dict.remove_by_path("bbb.0").unwrap();
+        dict.remove_by_path("eee.fff").unwrap();
+        assert!(dict.get_by_path("ccc.ddd").is_none());
+        assert_eq!(dict.get_by_path("bbb.0").unwrap().as_int(), 20);
+    }
 }
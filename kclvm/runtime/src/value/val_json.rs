@@ -7,7 +7,7 @@ use serde::{
     Deserialize, Serialize,
 };
 
-use crate::{ConfigEntryOperationKind, ValueRef, KCL_PRIVATE_VAR_PREFIX};
+use crate::{ConfigEntryOperationKind, Context, ValueRef, KCL_PRIVATE_VAR_PREFIX};
 
 macro_rules! tri {
     ($e:expr $(,)?) => {
@@ -356,20 +356,22 @@ where
 }
 
 impl ValueRef {
-    pub fn from_json(s: &str) -> Result<Self, serde_json::Error> {
+    pub fn from_json(ctx: &mut Context, s: &str) -> Result<Self, serde_json::Error> {
         match serde_json::de::from_str::<JsonValue>(s) {
-            Ok(json) => Ok(Self::parse_json(&json)),
+            Ok(json) => Ok(Self::parse_json(ctx, &json)),
             Err(err) => Err(err),
         }
     }
-    pub(crate) fn parse_json(json: &JsonValue) -> Self {
+    pub(crate) fn parse_json(ctx: &mut Context, json: &JsonValue) -> Self {
         match json {
             JsonValue::Object(values) => {
                 let mut dict = Self::dict(None);
                 for (name, value) in values {
+                    let value = Self::parse_json(ctx, value);
                     dict.dict_insert(
+                        ctx,
                         name.as_ref(),
-                        &Self::parse_json(value),
+                        &value,
                         ConfigEntryOperationKind::Union,
                         0,
                     );
@@ -379,7 +381,7 @@ impl ValueRef {
             JsonValue::Array(values) => {
                 let mut list = Self::list(None);
                 for value in values {
-                    list.list_append(&Self::parse_json(value));
+                    list.list_append(&Self::parse_json(ctx, value));
                 }
                 list
             }
@@ -432,6 +434,12 @@ impl ValueRef {
         writer.to_str().unwrap().to_string()
     }
 
+    /// Convert the value to a [`serde_json::Value`]. The key order is preserved
+    /// only when the `preserve_order` feature of `serde_json` is enabled.
+    pub fn to_json_value(&self) -> serde_json::Value {
+        serde_json::to_value(self.build_json(&Default::default())).unwrap()
+    }
+
     pub fn to_json_string_with_null(&self) -> String {
         let json = self.build_json(&Default::default());
         let formatter = JsonFormatter::new();
@@ -550,6 +558,7 @@ mod test_value_json {
 
     #[test]
     fn test_value_from_correct_json() {
+        let mut ctx = Context::new();
         let cases = [
             (
                 "{\"a\": 1}\n",
@@ -569,13 +578,14 @@ mod test_value_json {
             ("\n{}", ValueRef::dict(Some(&[]))),
         ];
         for (json_str, expected) in cases {
-            let result = ValueRef::from_json(json_str).unwrap();
+            let result = ValueRef::from_json(&mut ctx, json_str).unwrap();
             assert_eq!(result, expected);
         }
     }
 
     #[test]
     fn test_value_from_err_json() {
+        let mut ctx = Context::new();
         let cases = [
             ("{", "EOF while parsing an object at line 1 column 1"),
             ("{\"a\": 1,}", "trailing comma at line 1 column 9"),
@@ -583,11 +593,25 @@ mod test_value_json {
             ("[}", "expected value at line 1 column 2"),
         ];
         for (json_str, expected) in cases {
-            let result = ValueRef::from_json(json_str);
+            let result = ValueRef::from_json(&mut ctx, json_str);
             assert_eq!(result.err().unwrap().to_string(), expected);
         }
     }
 
+    #[test]
+    fn test_value_to_json_value() {
+        let value = ValueRef::dict(Some(&[
+            ("b", &ValueRef::float(1.5)),
+            ("a", &ValueRef::list_int(&[1, 2, 3])),
+            ("c", &ValueRef::none()),
+        ]));
+        let json_value = value.to_json_value();
+        assert_eq!(json_value.as_object().unwrap().len(), 3);
+        assert_eq!(json_value["b"], serde_json::json!(1.5));
+        assert_eq!(json_value["a"], serde_json::json!([1, 2, 3]));
+        assert_eq!(json_value["c"], serde_json::Value::Null);
+    }
+
     #[test]
     fn test_value_to_json_string() {
         let cases = [
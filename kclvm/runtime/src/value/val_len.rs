@@ -29,9 +29,12 @@ mod test_value_len {
 
     #[test]
     fn test_len() {
+        let mut ctx = Context::new();
         assert_eq!(ValueRef::str("abc").len(), 3);
         assert_eq!(
-            ValueRef::str("abc").bin_aug_mul(&ValueRef::int(10)).len(),
+            ValueRef::str("abc")
+                .bin_aug_mul(&mut ctx, &ValueRef::int(10))
+                .len(),
             3 * 10
         );
         assert_eq!(ValueRef::list_n(10, &ValueRef::undefined()).len(), 10);
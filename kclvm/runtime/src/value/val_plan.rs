@@ -7,21 +7,20 @@ use std::rc::Rc;
 
 pub const KCL_PRIVATE_VAR_PREFIX: &str = "_";
 const LIST_DICT_TEMP_KEY: &str = "$";
-const YAML_STREAM_SEP: &str = "\n---\n";
+pub(crate) const YAML_STREAM_SEP: &str = "\n---\n";
 
-fn filter_results(key_values: &ValueRef) -> Vec<ValueRef> {
+fn filter_results(ctx: &mut Context, key_values: &ValueRef) -> Vec<ValueRef> {
     let mut results: Vec<ValueRef> = vec![];
     // Plan list value with the yaml stream format.
     if key_values.is_list() {
         let key_values_list = &key_values.as_list_ref().values;
         for key_values in key_values_list {
-            results.append(&mut filter_results(key_values));
+            results.append(&mut filter_results(ctx, key_values));
         }
         results
     }
     // Plan dict value
     else if key_values.is_config() {
-        let ctx = Context::current_context();
         // index 0 for in-line keyvalues output, index 1: for standalone keyvalues outputs
         let result = ValueRef::dict(None);
         results.push(result);
@@ -33,7 +32,7 @@ fn filter_results(key_values: &ValueRef) -> Vec<ValueRef> {
             if key.starts_with(KCL_PRIVATE_VAR_PREFIX) || value.is_undefined() || value.is_func() {
                 continue;
             } else if value.is_schema() || value.has_key(SCHEMA_SETTINGS_ATTR_NAME) {
-                let (filtered, standalone) = handle_schema(value);
+                let (filtered, standalone) = handle_schema(ctx, value);
                 if !filtered.is_empty() {
                     if standalone {
                         // if the instance is marked as 'STANDALONE', treat it as a separate one and
@@ -54,7 +53,7 @@ fn filter_results(key_values: &ValueRef) -> Vec<ValueRef> {
                     }
                 }
             } else if value.is_dict() {
-                let filtered = filter_results(value);
+                let filtered = filter_results(ctx, value);
                 if !results.is_empty() {
                     let result = results.get_mut(0).unwrap();
                     if !filtered.is_empty() {
@@ -74,7 +73,7 @@ fn filter_results(key_values: &ValueRef) -> Vec<ValueRef> {
                 let list_value = value.as_list_ref();
                 for v in &list_value.values {
                     if v.is_schema() || v.has_key(SCHEMA_SETTINGS_ATTR_NAME) {
-                        let (filtered, standalone) = handle_schema(v);
+                        let (filtered, standalone) = handle_schema(ctx, v);
                         if filtered.is_empty() {
                             ignore_schema_count += 1;
                             continue;
@@ -88,7 +87,7 @@ fn filter_results(key_values: &ValueRef) -> Vec<ValueRef> {
                             }
                         }
                     } else if v.is_dict() {
-                        let filtered = filter_results(v);
+                        let filtered = filter_results(ctx, v);
                         for v in filtered {
                             filtered_list.push(v);
                         }
@@ -96,7 +95,7 @@ fn filter_results(key_values: &ValueRef) -> Vec<ValueRef> {
                         continue;
                     } else if !v.is_undefined() {
                         let list_dict = ValueRef::dict(Some(&[(LIST_DICT_TEMP_KEY, v)]));
-                        let filtered = filter_results(&list_dict);
+                        let filtered = filter_results(ctx, &list_dict);
                         if !filtered.is_empty() {
                             if let Some(v) = filtered[0].get_by_key(LIST_DICT_TEMP_KEY) {
                                 filtered_list.push(v.clone());
@@ -143,8 +142,8 @@ fn filter_results(key_values: &ValueRef) -> Vec<ValueRef> {
     }
 }
 
-fn handle_schema(value: &ValueRef) -> (Vec<ValueRef>, bool) {
-    let filtered = filter_results(value);
+fn handle_schema(ctx: &mut Context, value: &ValueRef) -> (Vec<ValueRef>, bool) {
+    let filtered = filter_results(ctx, value);
     if filtered.is_empty() {
         return (filtered, false);
     }
@@ -175,22 +174,31 @@ impl ValueRef {
         self.is_dict() && !self.is_truthy()
     }
 
-    pub fn plan_to_json_string(&self) -> String {
-        let result = self.filter_results();
+    /// Plan the value to the JSON string with the plan config of the runtime context `ctx`.
+    pub fn plan_to_json_string(&self, ctx: &mut Context) -> String {
+        let result = self.filter_results(ctx);
         if result.is_planned_empty() {
             return "".to_string();
         }
         result.to_json_string()
     }
 
-    pub fn plan_to_yaml_string(&self) -> String {
-        let result = self.filter_results();
+    /// Plan the value to a new value without private, function and undefined items,
+    /// which has the same content as [`ValueRef::plan_to_json_string`].
+    pub fn plan_value(&self, ctx: &mut Context) -> ValueRef {
+        self.filter_results(ctx)
+    }
+
+    /// Plan the value to the YAML string with the plan config of the runtime context `ctx`.
+    pub fn plan_to_yaml_string(&self, ctx: &mut Context) -> String {
+        let result = self.filter_results(ctx);
         result.to_yaml_string()
     }
 
-    /// Plan the value to the YAML string with delimiter `---`.
-    pub fn plan_to_yaml_string_with_delimiter(&self) -> String {
-        let results = filter_results(self);
+    /// Plan the value to the YAML string with delimiter `---` with the plan config of
+    /// the runtime context `ctx`.
+    pub fn plan_to_yaml_string_with_delimiter(&self, ctx: &mut Context) -> String {
+        let results = filter_results(ctx, self);
         let results = results
             .iter()
             .map(|r| r.to_yaml_string())
@@ -200,6 +208,16 @@ impl ValueRef {
 
     /// Plan the value to JSON and YAML strings.
     pub fn plan(&self, sort_keys: bool) -> (String, String) {
+        let (_, json_result, yaml_result) = self.plan_with_value(sort_keys);
+        (json_result, yaml_result)
+    }
+
+    /// Plan the value to the planned value, JSON and YAML strings, and the planned
+    /// value has the same content as the JSON string.
+    ///
+    /// The value is planned after the execution with a new default context, and the
+    /// none values are already removed in the execution when they are disabled.
+    pub fn plan_with_value(&self, sort_keys: bool) -> (ValueRef, String, String) {
         let json_opt = JsonEncodeOptions {
             sort_keys,
             ..Default::default()
@@ -209,31 +227,35 @@ impl ValueRef {
             ..Default::default()
         };
         if self.is_list_or_config() {
-            let results = filter_results(self);
-            let yaml_result = results
-                .iter()
-                .map(|r| {
-                    r.to_yaml_string_with_options(&yaml_opt)
-                        .strip_suffix('\n')
-                        .unwrap()
-                        .to_string()
-                })
-                .collect::<Vec<String>>()
-                .join(YAML_STREAM_SEP);
+            let results = filter_results(&mut Context::new(), self);
+            let yaml_result = YamlPlanner
+                .plan(&results, sort_keys)
+                .expect("the YAML planner never fails");
             let mut list_result = ValueRef::list(None);
             for r in results {
                 list_result.list_append(&r);
             }
             let json_result = list_result.to_json_string_with_option(&json_opt);
-            (json_result, yaml_result)
+            (list_result, json_result, yaml_result)
         } else {
             (
+                self.clone(),
                 self.to_json_string_with_option(&json_opt),
                 self.to_yaml_string_with_options(&yaml_opt),
             )
         }
     }
 
+    /// Plan the value to the documents without private, function and undefined items,
+    /// and a value which is neither a list nor a config is the only document.
+    pub(crate) fn plan_documents(&self) -> Vec<ValueRef> {
+        if self.is_list_or_config() {
+            filter_results(&mut Context::new(), self)
+        } else {
+            vec![self.clone()]
+        }
+    }
+
     /// Filter values using path selectors.
     pub fn filter_by_path(&self, path_selector: &[String]) -> Result<ValueRef, String> {
         if self.is_config() && !path_selector.is_empty() {
@@ -267,8 +289,7 @@ impl ValueRef {
         }
     }
 
-    fn filter_results(&self) -> ValueRef {
-        let ctx = Context::current_context();
+    fn filter_results(&self, ctx: &mut Context) -> ValueRef {
         match &*self.rc.borrow() {
             Value::undefined => ValueRef {
                 rc: Rc::new(RefCell::new(Value::undefined)),
@@ -302,7 +323,7 @@ impl ValueRef {
                 };
                 for x in v.values.iter() {
                     if !(x.is_undefined() || x.is_func() || ctx.cfg.disable_none && x.is_none()) {
-                        list.list_append(&x.filter_results());
+                        list.list_append(&x.filter_results(ctx));
                     }
                 }
                 list
@@ -321,12 +342,8 @@ impl ValueRef {
                         || val.is_func()
                         || ctx.cfg.disable_none && val.is_none())
                     {
-                        dict.dict_insert(
-                            key,
-                            &val.filter_results(),
-                            ConfigEntryOperationKind::Override,
-                            0,
-                        );
+                        let val = val.filter_results(ctx);
+                        dict.dict_insert(ctx, key, &val, ConfigEntryOperationKind::Override, 0);
                     }
                 }
                 dict
@@ -349,12 +366,8 @@ impl ValueRef {
                 };
                 for (key, val) in v.config.values.iter() {
                     if !val.is_undefined() && !val.is_func() {
-                        schema.dict_insert(
-                            key,
-                            &val.filter_results(),
-                            ConfigEntryOperationKind::Union,
-                            0,
-                        );
+                        let val = val.filter_results(ctx);
+                        schema.dict_insert(ctx, key, &val, ConfigEntryOperationKind::Union, 0);
                     }
                 }
                 schema
@@ -365,7 +378,7 @@ impl ValueRef {
 
 #[cfg(test)]
 mod test_value_plan {
-    use crate::ValueRef;
+    use crate::{Context, ValueRef};
 
     use super::filter_results;
 
@@ -377,17 +390,33 @@ mod test_value_plan {
         let dict_list = vec![&dict1, &dict2, &dict3];
         let list_data = ValueRef::list(Some(&dict_list));
         assert_eq!(
-            filter_results(&list_data),
+            filter_results(&mut Context::new(), &list_data),
             dict_list
                 .iter()
                 .map(|v| v.deep_copy())
                 .collect::<Vec<ValueRef>>()
         );
         for dict in dict_list {
-            assert_eq!(filter_results(dict), vec![dict.deep_copy()]);
+            assert_eq!(
+                filter_results(&mut Context::new(), dict),
+                vec![dict.deep_copy()]
+            );
         }
     }
 
+    #[test]
+    fn test_plan_with_value() {
+        let mut ctx = Context::new();
+        let dict = ValueRef::dict_int(&[("k1", 1), ("_k2", 2)]);
+        let (value, json_result, yaml_result) = dict.plan_with_value(false);
+        assert_eq!(
+            value,
+            ValueRef::list(Some(&[&ValueRef::dict_int(&[("k1", 1)])]))
+        );
+        assert_eq!(ValueRef::from_json(&mut ctx, &json_result).unwrap(), value);
+        assert_eq!(yaml_result, "k1: 1");
+    }
+
     #[test]
     fn test_filter_by_path() {
         let dict = ValueRef::dict_int(&[("k1", 1)]);
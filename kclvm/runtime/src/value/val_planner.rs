@@ -0,0 +1,520 @@
+// Copyright 2021 The KCL Authors. All rights reserved.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::str::FromStr;
+
+use crate::*;
+
+/// The key separator of the flattened properties keys.
+const PROPERTIES_KEY_SEP: &str = ".";
+/// The key separator of the flattened environment variable names.
+const ENV_KEY_SEP: &str = "_";
+
+/// PlanFormat denotes the output format of the planned KCL result.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum PlanFormat {
+    Json,
+    #[default]
+    Yaml,
+    Toml,
+    /// The Java properties format, e.g., `app.ports[0]=80`.
+    Properties,
+    /// The dotenv format, e.g., `APP_PORTS_0=80`.
+    Env,
+}
+
+impl PlanFormat {
+    /// Get the file extension of the format without the leading dot.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            PlanFormat::Json => "json",
+            PlanFormat::Yaml => "yaml",
+            PlanFormat::Toml => "toml",
+            PlanFormat::Properties => "properties",
+            PlanFormat::Env => "env",
+        }
+    }
+
+    /// Get the built-in planner of the format.
+    pub fn planner(&self) -> Box<dyn Planner> {
+        match self {
+            PlanFormat::Json => Box::new(JsonPlanner),
+            PlanFormat::Yaml => Box::new(YamlPlanner),
+            PlanFormat::Toml => Box::new(TomlPlanner),
+            PlanFormat::Properties => Box::new(PropertiesPlanner),
+            PlanFormat::Env => Box::new(EnvPlanner),
+        }
+    }
+}
+
+impl FromStr for PlanFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(PlanFormat::Json),
+            "yaml" => Ok(PlanFormat::Yaml),
+            "toml" => Ok(PlanFormat::Toml),
+            "properties" => Ok(PlanFormat::Properties),
+            "env" => Ok(PlanFormat::Env),
+            _ => Err(format!(
+                "unknown output format {s}, expected one of json, yaml, toml, properties and env"
+            )),
+        }
+    }
+}
+
+/// Planner writes the planned documents of the KCL result into an output format
+/// without the trailing newline.
+///
+/// The documents are planned by [`ValueRef::plan_to_format`] before, so the private
+/// attributes, functions, undefined values and the none values with the `disable_none`
+/// option are already filtered out, and a planner only decides how to write them.
+pub trait Planner {
+    /// Write the planned documents into a string, the keys of the configs are sorted
+    /// when `sort_keys` is true.
+    fn plan(&self, docs: &[ValueRef], sort_keys: bool) -> Result<String, String>;
+}
+
+/// JsonPlanner writes a single document as a JSON value and multiple documents as
+/// a JSON array.
+pub struct JsonPlanner;
+
+impl Planner for JsonPlanner {
+    fn plan(&self, docs: &[ValueRef], sort_keys: bool) -> Result<String, String> {
+        let opt = JsonEncodeOptions {
+            sort_keys,
+            indent: 4,
+            ..Default::default()
+        };
+        Ok(match docs {
+            [doc] => doc.to_json_string_with_option(&opt),
+            docs => ValueRef::list(Some(&docs.iter().collect::<Vec<&ValueRef>>()))
+                .to_json_string_with_option(&opt),
+        })
+    }
+}
+
+/// YamlPlanner writes the documents as a YAML stream separated by `---`.
+pub struct YamlPlanner;
+
+impl Planner for YamlPlanner {
+    fn plan(&self, docs: &[ValueRef], sort_keys: bool) -> Result<String, String> {
+        let opt = YamlEncodeOptions {
+            sort_keys,
+            ..Default::default()
+        };
+        Ok(docs
+            .iter()
+            .map(|doc| {
+                let yaml = doc.to_yaml_string_with_options(&opt);
+                yaml.strip_suffix('\n').unwrap_or(&yaml).to_string()
+            })
+            .collect::<Vec<String>>()
+            .join(YAML_STREAM_SEP))
+    }
+}
+
+/// CommentedYamlPlanner writes the documents like [`YamlPlanner`], and the comments of
+/// each document e.g., the schema attribute docs are emitted as the YAML comments.
+#[derive(Debug, Default, Clone)]
+pub struct CommentedYamlPlanner {
+    /// The comments of the documents by index.
+    pub comments: Vec<YamlComments>,
+}
+
+impl Planner for CommentedYamlPlanner {
+    fn plan(&self, docs: &[ValueRef], sort_keys: bool) -> Result<String, String> {
+        let opt = YamlEncodeOptions {
+            sort_keys,
+            ..Default::default()
+        };
+        let empty = YamlComments::default();
+        Ok(docs
+            .iter()
+            .enumerate()
+            .map(|(i, doc)| {
+                let comments = self.comments.get(i).unwrap_or(&empty);
+                let yaml = doc.to_yaml_string_with_comments(&opt, comments);
+                yaml.strip_suffix('\n').unwrap_or(&yaml).to_string()
+            })
+            .collect::<Vec<String>>()
+            .join(YAML_STREAM_SEP))
+    }
+}
+
+/// TomlPlanner writes a single config document as a TOML document. The none values are
+/// omitted because TOML has no null value.
+pub struct TomlPlanner;
+
+impl Planner for TomlPlanner {
+    fn plan(&self, docs: &[ValueRef], sort_keys: bool) -> Result<String, String> {
+        let doc = single_config_doc(docs, "toml")?;
+        let opt = TomlEncodeOptions {
+            sort_keys,
+            ..Default::default()
+        };
+        Ok(doc
+            .to_toml_string_with_options(&opt)?
+            .trim_matches('\n')
+            .to_string())
+    }
+}
+
+/// PropertiesPlanner writes a single config document as the Java properties, the nested
+/// keys are joined by `.` and the list indices are written as `[index]`.
+pub struct PropertiesPlanner;
+
+impl Planner for PropertiesPlanner {
+    fn plan(&self, docs: &[ValueRef], sort_keys: bool) -> Result<String, String> {
+        let doc = single_config_doc(docs, "properties")?;
+        let lines = flatten_value(doc, sort_keys, &properties_key)
+            .iter()
+            .map(|(key, value)| {
+                format!(
+                    "{}={}",
+                    escape_properties(key, true),
+                    escape_properties(value, false)
+                )
+            })
+            .collect::<Vec<String>>();
+        Ok(lines.join("\n"))
+    }
+}
+
+/// EnvPlanner writes a single config document as the dotenv variables, the nested keys
+/// and list indices are joined by `_` and converted to the upper case variable names.
+/// It is an error when different keys e.g., `a-b`, `a_b` and the nested `a.b` are
+/// converted to the same variable name.
+pub struct EnvPlanner;
+
+impl Planner for EnvPlanner {
+    fn plan(&self, docs: &[ValueRef], sort_keys: bool) -> Result<String, String> {
+        let doc = single_config_doc(docs, "env")?;
+        let vars = flatten_value(doc, sort_keys, &|parent, key| {
+            let key = match key {
+                FlattenKey::Key(key) => env_name(key),
+                FlattenKey::Index(index) => index.to_string(),
+            };
+            if parent.is_empty() {
+                key
+            } else {
+                format!("{parent}{ENV_KEY_SEP}{key}")
+            }
+        });
+        // The keys are flattened in the same order, so the variables are paired with
+        // the property keys of the values, which are reported on the name collisions.
+        let keys = flatten_value(doc, sort_keys, &properties_key);
+        let mut names: HashMap<&str, &str> = HashMap::new();
+        for ((name, _), (key, _)) in vars.iter().zip(keys.iter()) {
+            if let Some(other) = names.insert(name, key) {
+                return Err(format!(
+                    "the env output format has the conflicting keys '{other}' and '{key}' of the variable '{name}'"
+                ));
+            }
+        }
+        let lines = vars
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, quote_env_value(value)))
+            .collect::<Vec<String>>();
+        Ok(lines.join("\n"))
+    }
+}
+
+impl ValueRef {
+    /// Plan the value to the string of the output format, the private attributes, functions,
+    /// undefined values and the none values with the `disable_none` option are filtered out
+    /// in the same way as [`ValueRef::plan_with_value`].
+    pub fn plan_to_format(&self, format: PlanFormat, sort_keys: bool) -> Result<String, String> {
+        self.plan_with_planner(format.planner().as_ref(), sort_keys)
+    }
+
+    /// Plan the value to the string with a custom planner.
+    pub fn plan_with_planner(
+        &self,
+        planner: &dyn Planner,
+        sort_keys: bool,
+    ) -> Result<String, String> {
+        planner.plan(&self.plan_documents(), sort_keys)
+    }
+}
+
+/// Get the only config document for the formats which do not support multiple documents.
+fn single_config_doc<'a>(docs: &'a [ValueRef], format: &str) -> Result<&'a ValueRef, String> {
+    match docs {
+        [doc] if doc.is_config() => Ok(doc),
+        [_] => Err(format!(
+            "the {format} output format only supports the config result"
+        )),
+        _ => Err(format!(
+            "the {format} output format does not support multiple documents"
+        )),
+    }
+}
+
+/// Get the key values of the config value.
+fn config_items(value: &ValueRef, sort_keys: bool) -> Vec<(String, ValueRef)> {
+    let mut items: Vec<(String, ValueRef)> = value
+        .as_dict_ref()
+        .values
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.clone()))
+        .collect();
+    if sort_keys {
+        items.sort_by(|a, b| a.0.cmp(&b.0));
+    }
+    items
+}
+
+/// Get the string of the scalar value, and the none value is an empty string.
+fn scalar_to_string(value: &ValueRef) -> String {
+    match &*value.rc.borrow() {
+        Value::none | Value::undefined => "".to_string(),
+        Value::bool_value(v) => v.to_string(),
+        Value::int_value(v) => v.to_string(),
+        Value::float_value(v) => float_to_string(*v),
+        Value::unit_value(v, _, _) => float_to_string(*v),
+        Value::str_value(v) => v.to_string(),
+        _ => value.to_json_string(),
+    }
+}
+
+/// The key of a flattened value in its parent config or list.
+enum FlattenKey<'a> {
+    Key(&'a str),
+    Index(usize),
+}
+
+/// Flatten the value into the key and scalar string pairs, the key of a nested value is
+/// joined with the key of its parent by `join`, and the empty configs and lists are omitted.
+fn flatten_value(
+    value: &ValueRef,
+    sort_keys: bool,
+    join: &dyn Fn(&str, FlattenKey) -> String,
+) -> Vec<(String, String)> {
+    fn walk(
+        prefix: &str,
+        value: &ValueRef,
+        sort_keys: bool,
+        join: &dyn Fn(&str, FlattenKey) -> String,
+        output: &mut Vec<(String, String)>,
+    ) {
+        if value.is_config() {
+            for (key, value) in config_items(value, sort_keys) {
+                walk(
+                    &join(prefix, FlattenKey::Key(&key)),
+                    &value,
+                    sort_keys,
+                    join,
+                    output,
+                );
+            }
+        } else if value.is_list() {
+            for (index, value) in value.as_list_ref().values.iter().enumerate() {
+                walk(
+                    &join(prefix, FlattenKey::Index(index)),
+                    value,
+                    sort_keys,
+                    join,
+                    output,
+                );
+            }
+        } else {
+            output.push((prefix.to_string(), scalar_to_string(value)));
+        }
+    }
+    let mut output = vec![];
+    walk("", value, sort_keys, join, &mut output);
+    output
+}
+
+/// Join the key of a nested value with the key of its parent as the Java properties key.
+fn properties_key(parent: &str, key: FlattenKey) -> String {
+    match key {
+        FlattenKey::Key(key) if parent.is_empty() => key.to_string(),
+        FlattenKey::Key(key) => format!("{parent}{PROPERTIES_KEY_SEP}{key}"),
+        FlattenKey::Index(index) => format!("{parent}[{index}]"),
+    }
+}
+
+/// Escape the key or value of the Java properties, the non-ASCII characters are escaped
+/// as `\uXXXX` because the properties files are read in ISO 8859-1 by default.
+fn escape_properties(s: &str, is_key: bool) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for (i, c) in s.chars().enumerate() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            '\x0c' => escaped.push_str("\\f"),
+            '=' | ':' | '#' | '!' if is_key || i == 0 => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            ' ' if is_key || i == 0 => escaped.push_str("\\ "),
+            c if c.is_ascii() && !c.is_ascii_control() => escaped.push(c),
+            c => {
+                let mut buf = [0u16; 2];
+                for unit in c.encode_utf16(&mut buf) {
+                    write!(escaped, "\\u{unit:04x}").unwrap();
+                }
+            }
+        }
+    }
+    escaped
+}
+
+/// Convert the config key to the environment variable name, the characters other than
+/// the ASCII letters and digits are replaced by `_`.
+fn env_name(key: &str) -> String {
+    key.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// Quote the dotenv value with double quotes when it contains characters other than
+/// the ASCII letters, digits and `_./:@+-`.
+fn quote_env_value(value: &str) -> String {
+    if value
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || "_./:@+-".contains(c))
+    {
+        return value.to_string();
+    }
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '\\' => quoted.push_str("\\\\"),
+            '"' => quoted.push_str("\\\""),
+            '$' => quoted.push_str("\\$"),
+            '`' => quoted.push_str("\\`"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod test_value_planner {
+    use crate::*;
+
+    fn config() -> ValueRef {
+        let mut ctx = Context::new();
+        ValueRef::from_json(
+            &mut ctx,
+            r#"{"name": "app", "port": 80, "ratio": 1.0, "debug": false, "env": null,
+                "tags": ["a b", "c"], "db": {"host": "localhost", "user": "admin"},
+                "servers": [{"ip": "10.0.0.1"}, {"ip": "10.0.0.2"}], "_private": 1}"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_plan_format_from_str() {
+        assert_eq!("toml".parse::<PlanFormat>().unwrap(), PlanFormat::Toml);
+        assert_eq!("env".parse::<PlanFormat>().unwrap(), PlanFormat::Env);
+        assert!("xml".parse::<PlanFormat>().is_err());
+    }
+
+    #[test]
+    fn test_plan_to_toml() {
+        assert_eq!(
+            config().plan_to_format(PlanFormat::Toml, false).unwrap(),
+            r#"name = "app"
+port = 80
+ratio = 1.0
+debug = false
+tags = ["a b", "c"]
+
+[db]
+host = "localhost"
+user = "admin"
+
+[[servers]]
+ip = "10.0.0.1"
+
+[[servers]]
+ip = "10.0.0.2""#
+        );
+    }
+
+    #[test]
+    fn test_plan_to_properties() {
+        let mut ctx = Context::new();
+        assert_eq!(
+            config()
+                .plan_to_format(PlanFormat::Properties, true)
+                .unwrap(),
+            r#"db.host=localhost
+db.user=admin
+debug=false
+env=
+name=app
+port=80
+ratio=1.0
+servers[0].ip=10.0.0.1
+servers[1].ip=10.0.0.2
+tags[0]=a b
+tags[1]=c"#
+        );
+        let value = ValueRef::from_json(&mut ctx, r#"{"a key": "x=1", "k": "中"}"#).unwrap();
+        assert_eq!(
+            value.plan_to_format(PlanFormat::Properties, false).unwrap(),
+            "a\\ key=x=1\nk=\\u4e2d"
+        );
+    }
+
+    #[test]
+    fn test_plan_to_env() {
+        let mut ctx = Context::new();
+        assert_eq!(
+            config().plan_to_format(PlanFormat::Env, false).unwrap(),
+            r#"NAME=app
+PORT=80
+RATIO=1.0
+DEBUG=false
+ENV=
+TAGS_0="a b"
+TAGS_1=c
+DB_HOST=localhost
+DB_USER=admin
+SERVERS_0_IP=10.0.0.1
+SERVERS_1_IP=10.0.0.2"#
+        );
+        let value = ValueRef::from_json(&mut ctx, r#"{"a-b": 1, "a": {"b": 2}}"#).unwrap();
+        assert_eq!(
+            value.plan_to_format(PlanFormat::Env, false).unwrap_err(),
+            "the env output format has the conflicting keys 'a-b' and 'a.b' of the variable 'A_B'"
+        );
+        let value = ValueRef::from_json(&mut ctx, r#"{"a_b": 1, "a-b": 2}"#).unwrap();
+        assert!(value.plan_to_format(PlanFormat::Env, false).is_err());
+    }
+
+    #[test]
+    fn test_plan_multiple_documents() {
+        let docs = ValueRef::list(Some(&[
+            &ValueRef::dict_int(&[("k1", 1)]),
+            &ValueRef::dict_int(&[("k2", 2)]),
+        ]));
+        assert_eq!(
+            docs.plan_to_format(PlanFormat::Yaml, false).unwrap(),
+            "k1: 1\n---\nk2: 2"
+        );
+        assert_eq!(
+            docs.plan_to_format(PlanFormat::Toml, false).unwrap_err(),
+            "the toml output format does not support multiple documents"
+        );
+    }
+}
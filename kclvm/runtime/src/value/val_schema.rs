@@ -101,7 +101,7 @@ impl ValueRef {
     }
 
     /// Check schema optional attributes.
-    pub fn schema_check_attr_optional(&self, recursive: bool) {
+    pub fn schema_check_attr_optional(&self, ctx: &mut Context, recursive: bool) {
         let binding = self.rc.borrow();
         let attr_map = match &*binding {
             Value::schema_value(schema) => &schema.config.values,
@@ -120,7 +120,6 @@ impl ValueRef {
                     if is_required && value.is_none_or_undefined() {
                         let filename = config_meta.get_by_key(CONFIG_META_FILENAME);
                         let line = config_meta.get_by_key(CONFIG_META_LINE);
-                        let ctx = Context::current_context_mut();
                         if let Some(filename) = filename {
                             ctx.set_kcl_filename(&filename.as_str());
                         }
@@ -134,7 +133,7 @@ impl ValueRef {
                         );
                     }
                     if recursive && value.is_schema() {
-                        value.schema_check_attr_optional(recursive);
+                        value.schema_check_attr_optional(ctx, recursive);
                     }
                 }
             }
@@ -277,8 +276,8 @@ mod test_value_schema {
             &config_meta,
             &optional_mapping,
         );
-        schema.schema_check_attr_optional(true);
-        schema.schema_check_attr_optional(false);
+        schema.schema_check_attr_optional(&mut Context::new(), true);
+        schema.schema_check_attr_optional(&mut Context::new(), false);
     }
 
     #[test]
@@ -294,7 +293,7 @@ mod test_value_schema {
                 &config_meta,
                 &optional_mapping,
             );
-            schema.schema_check_attr_optional(true);
+            schema.schema_check_attr_optional(&mut Context::new(), true);
         });
         assert!(err.is_err())
     }
@@ -0,0 +1,282 @@
+// Copyright 2021 The KCL Authors. All rights reserved.
+
+use std::fmt::Write;
+
+use crate::*;
+
+/// TOML encode options.
+/// - sort_keys: Sort the encode result by keys (defaults to false).
+/// - ignore_private: Whether to ignore the attribute whose name starts with
+///   a character `_` (defaults to false).
+/// - ignore_none: Whether to ignore the `None` values in the arrays (defaults to false).
+///   TOML has no null value, so the `None` values of the tables are always omitted
+///   and the `None` values of the arrays are errors unless they are ignored.
+#[derive(Debug, Clone, Default)]
+pub struct TomlEncodeOptions {
+    pub sort_keys: bool,
+    pub ignore_private: bool,
+    pub ignore_none: bool,
+}
+
+impl ValueRef {
+    /// Decode a TOML document string to a config value, and the date times
+    /// are decoded as the RFC 3339 strings.
+    pub fn from_toml(s: &str) -> Result<Self, ::toml::de::Error> {
+        let value: ::toml::Value = ::toml::from_str(s)?;
+        Ok(Self::from_toml_value(&value))
+    }
+
+    fn from_toml_value(value: &::toml::Value) -> Self {
+        match value {
+            ::toml::Value::String(v) => Self::str(v),
+            ::toml::Value::Integer(v) => Self::int(*v),
+            ::toml::Value::Float(v) => Self::float(*v),
+            ::toml::Value::Boolean(v) => Self::bool(*v),
+            ::toml::Value::Datetime(v) => Self::str(&v.to_string()),
+            ::toml::Value::Array(values) => {
+                let values: Vec<Self> = values.iter().map(Self::from_toml_value).collect();
+                Self::list_value(Some(&values))
+            }
+            ::toml::Value::Table(table) => {
+                let mut dict = Self::dict(None);
+                for (key, value) in table {
+                    dict.dict_update_key_value(key, Self::from_toml_value(value));
+                }
+                dict
+            }
+        }
+    }
+
+    /// Encode the config value to a TOML document string with the default options.
+    pub fn to_toml_string(&self) -> Result<String, String> {
+        self.to_toml_string_with_options(&TomlEncodeOptions::default())
+    }
+
+    /// Encode the config value to a TOML document string, the scalar values of a table
+    /// are written before its nested tables and arrays of tables.
+    pub fn to_toml_string_with_options(&self, opt: &TomlEncodeOptions) -> Result<String, String> {
+        if !self.is_config() {
+            return Err(format!(
+                "only the config value can be encoded as TOML, not '{}'",
+                self.type_str()
+            ));
+        }
+        let mut output = String::new();
+        write_toml_table(&mut output, &[], self, opt)?;
+        Ok(format!("{}\n", output.trim_matches('\n')))
+    }
+}
+
+/// Get the key values of the TOML table, and the `None` and function values are omitted.
+fn toml_table_items(value: &ValueRef, opt: &TomlEncodeOptions) -> Vec<(String, ValueRef)> {
+    let mut items: Vec<(String, ValueRef)> = value
+        .as_dict_ref()
+        .values
+        .iter()
+        .filter(|(k, v)| {
+            let is_private = opt.ignore_private && k.starts_with(KCL_PRIVATE_VAR_PREFIX);
+            !(v.is_none_or_undefined() || v.is_func() || is_private)
+        })
+        .map(|(k, v)| (k.to_string(), v.clone()))
+        .collect();
+    if opt.sort_keys {
+        items.sort_by(|a, b| a.0.cmp(&b.0));
+    }
+    items
+}
+
+/// Whether the list value is written as an array of tables, e.g., `[[servers]]`.
+fn is_toml_table_array(value: &ValueRef) -> bool {
+    if !value.is_list() {
+        return false;
+    }
+    let list = value.as_list_ref();
+    !list.values.is_empty() && list.values.iter().all(|v| v.is_config())
+}
+
+/// Write the TOML key values of the table at `path`, the scalar values are written before
+/// the nested tables and arrays of tables as required by TOML.
+fn write_toml_table(
+    output: &mut String,
+    path: &[String],
+    value: &ValueRef,
+    opt: &TomlEncodeOptions,
+) -> Result<(), String> {
+    let items = toml_table_items(value, opt);
+    let is_table = |v: &ValueRef| v.is_config() && !v.as_dict_ref().values.is_empty();
+    for (key, value) in &items {
+        if is_table(value) || is_toml_table_array(value) {
+            continue;
+        }
+        writeln!(
+            output,
+            "{} = {}",
+            toml_key(key),
+            toml_inline_value(value, opt)?
+        )
+        .unwrap();
+    }
+    for (key, value) in &items {
+        let mut path = path.to_vec();
+        path.push(toml_key(key));
+        if is_table(value) {
+            write!(output, "\n[{}]\n", path.join(".")).unwrap();
+            write_toml_table(output, &path, value, opt)?;
+        } else if is_toml_table_array(value) {
+            for item in &value.as_list_ref().values {
+                write!(output, "\n[[{}]]\n", path.join(".")).unwrap();
+                write_toml_table(output, &path, item, opt)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Get the inline TOML value, e.g., the scalars, arrays and inline tables.
+fn toml_inline_value(value: &ValueRef, opt: &TomlEncodeOptions) -> Result<String, String> {
+    Ok(match &*value.rc.borrow() {
+        Value::none | Value::undefined => {
+            return Err("the none value in a list can not be written as TOML".to_string())
+        }
+        Value::bool_value(v) => v.to_string(),
+        Value::int_value(v) => v.to_string(),
+        Value::float_value(v) | Value::unit_value(v, _, _) => toml_float(*v),
+        Value::str_value(v) => toml_string(v),
+        Value::list_value(list) => {
+            let values = list
+                .values
+                .iter()
+                .filter(|v| !(v.is_func() || opt.ignore_none && v.is_none_or_undefined()))
+                .map(|v| toml_inline_value(v, opt))
+                .collect::<Result<Vec<String>, String>>()?;
+            format!("[{}]", values.join(", "))
+        }
+        Value::dict_value(_) | Value::schema_value(_) => {
+            let values = toml_table_items(value, opt)
+                .iter()
+                .map(|(k, v)| Ok(format!("{} = {}", toml_key(k), toml_inline_value(v, opt)?)))
+                .collect::<Result<Vec<String>, String>>()?;
+            if values.is_empty() {
+                "{}".to_string()
+            } else {
+                format!("{{ {} }}", values.join(", "))
+            }
+        }
+        Value::func_value(_) => {
+            return Err("the function value can not be written as TOML".to_string())
+        }
+    })
+}
+
+/// Get the TOML key, which is quoted unless it is a bare key.
+fn toml_key(key: &str) -> String {
+    if !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        key.to_string()
+    } else {
+        toml_string(key)
+    }
+}
+
+/// Get the TOML basic string with the escaped characters.
+fn toml_string(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            '\x08' => quoted.push_str("\\b"),
+            '\x0c' => quoted.push_str("\\f"),
+            c if c.is_control() => write!(quoted, "\\u{:04X}", c as u32).unwrap(),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Get the TOML float, which always has a fractional part or an exponent part.
+fn toml_float(v: f64) -> String {
+    if v.is_nan() {
+        "nan".to_string()
+    } else if v.is_infinite() {
+        if v > 0.0 { "inf" } else { "-inf" }.to_string()
+    } else {
+        float_to_string(v)
+    }
+}
+
+#[cfg(test)]
+mod test_value_toml {
+    use crate::*;
+
+    #[test]
+    fn test_value_from_toml() {
+        let value = ValueRef::from_toml(
+            r#"name = "app"
+port = 80
+ratio = 0.5
+debug = false
+created = 1979-05-27T07:32:00Z
+tags = ["a", "b"]
+
+[db]
+host = "localhost"
+
+[[servers]]
+ip = "10.0.0.1"
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            value.to_json_string(),
+            r#"{"name": "app", "port": 80, "ratio": 0.5, "debug": false, "created": "1979-05-27T07:32:00Z", "tags": ["a", "b"], "db": {"host": "localhost"}, "servers": [{"ip": "10.0.0.1"}]}"#
+        );
+        assert!(ValueRef::from_toml("name = ").is_err());
+    }
+
+    #[test]
+    fn test_value_to_toml_string_with_options() {
+        let mut ctx = Context::new();
+        let value = ValueRef::from_json(
+            &mut ctx,
+            r#"{"name": "app", "_secret": "x", "env": null, "ports": [80, null, 443], "db": {"user": "admin", "host": "localhost"}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            value.to_toml_string().unwrap_err(),
+            "the none value in a list can not be written as TOML"
+        );
+        let opt = TomlEncodeOptions {
+            sort_keys: true,
+            ignore_private: true,
+            ignore_none: true,
+        };
+        assert_eq!(
+            value.to_toml_string_with_options(&opt).unwrap(),
+            r#"name = "app"
+ports = [80, 443]
+
+[db]
+host = "localhost"
+user = "admin"
+"#
+        );
+        assert_eq!(
+            ValueRef::list_int(&[1]).to_toml_string().unwrap_err(),
+            "only the config value can be encoded as TOML, not 'list'"
+        );
+        let value = ValueRef::from_toml(&value.to_toml_string_with_options(&opt).unwrap()).unwrap();
+        assert_eq!(
+            value.to_json_string(),
+            r#"{"name": "app", "ports": [80, 443], "db": {"host": "localhost", "user": "admin"}}"#
+        );
+    }
+}
@@ -64,13 +64,12 @@ impl ValueRef {
 }
 
 /// Use the schema instance to build a new schema instance using the schema construct function
-pub fn resolve_schema(schema: &ValueRef, keys: &[String]) -> ValueRef {
+pub fn resolve_schema(ctx: &mut Context, schema: &ValueRef, keys: &[String]) -> ValueRef {
     if !schema.is_schema() {
         return schema.clone();
     }
     let schema_value = schema.as_schema();
     let schema_type_name = schema_runtime_type(&schema_value.name, &schema_value.pkgpath);
-    let ctx = Context::current_context_mut();
     let now_meta_info = ctx.panic_info.clone();
     let has_schema_type = {
         let all_schemas = ctx.all_schemas.borrow_mut();
@@ -94,54 +93,59 @@ pub fn resolve_schema(schema: &ValueRef, keys: &[String]) -> ValueRef {
         let config_meta_new = config_meta.clone();
         let value = unsafe {
             let schema_fn: SchemaTypeFunc = transmute_copy(&schema_fn_ptr);
-            let ctx = kclvm_context_current();
-            let cal_map = kclvm_value_Dict();
-            let list = kclvm_value_List();
+            let cal_map = kclvm_value_Dict(ctx);
+            let list = kclvm_value_List(ctx);
             // Schema function closures
             // is sub schema
-            kclvm_list_append(list, ValueRef::bool(false).into_raw());
+            kclvm_list_append(list, ValueRef::bool(false).into_raw(ctx));
             // config meta
-            kclvm_list_append(list, config_meta.into_raw());
+            kclvm_list_append(list, config_meta.into_raw(ctx));
             // schema
-            kclvm_list_append(list, config.into_raw());
+            kclvm_list_append(list, config.into_raw(ctx));
             // config
-            kclvm_list_append(list, kclvm_value_Dict());
+            kclvm_list_append(list, kclvm_value_Dict(ctx));
             // optional mapping
-            kclvm_list_append(list, kclvm_value_Dict());
+            kclvm_list_append(list, kclvm_value_Dict(ctx));
             // cal order map
             kclvm_list_append(list, cal_map);
             // backtrack level map
-            kclvm_list_append(list, kclvm_value_Dict());
+            kclvm_list_append(list, kclvm_value_Dict(ctx));
             // backtrack cache
-            kclvm_list_append(list, kclvm_value_Dict());
+            kclvm_list_append(list, kclvm_value_Dict(ctx));
             // record instance
-            kclvm_list_append(list, ValueRef::bool(false).into_raw());
+            kclvm_list_append(list, ValueRef::bool(false).into_raw(ctx));
             // instance pkgpath
-            kclvm_list_append(list, ValueRef::str(&now_meta_info.kcl_pkgpath).into_raw());
-            let dict = kclvm_value_Dict();
+            kclvm_list_append(
+                list,
+                ValueRef::str(&now_meta_info.kcl_pkgpath).into_raw(ctx),
+            );
+            let dict = kclvm_value_Dict(ctx);
             schema_fn(ctx, list, dict);
-            let list = kclvm_value_List();
+            let list = kclvm_value_List(ctx);
             // Schema function closures
             // is sub schema
-            kclvm_list_append(list, ValueRef::bool(true).into_raw());
+            kclvm_list_append(list, ValueRef::bool(true).into_raw(ctx));
             // config meta
-            kclvm_list_append(list, config_meta_new.into_raw());
+            kclvm_list_append(list, config_meta_new.into_raw(ctx));
             // schema
-            kclvm_list_append(list, config_new.into_raw());
+            kclvm_list_append(list, config_new.into_raw(ctx));
             // config
-            kclvm_list_append(list, kclvm_value_Dict());
+            kclvm_list_append(list, kclvm_value_Dict(ctx));
             // optional mapping
-            kclvm_list_append(list, kclvm_value_Dict());
+            kclvm_list_append(list, kclvm_value_Dict(ctx));
             // cal order map
             kclvm_list_append(list, cal_map);
             // backtrack level map
-            kclvm_list_append(list, kclvm_value_Dict());
+            kclvm_list_append(list, kclvm_value_Dict(ctx));
             // backtrack cache
-            kclvm_list_append(list, kclvm_value_Dict());
+            kclvm_list_append(list, kclvm_value_Dict(ctx));
             // record instance
-            kclvm_list_append(list, ValueRef::bool(true).into_raw());
+            kclvm_list_append(list, ValueRef::bool(true).into_raw(ctx));
             // instance pkgpath
-            kclvm_list_append(list, ValueRef::str(&now_meta_info.kcl_pkgpath).into_raw());
+            kclvm_list_append(
+                list,
+                ValueRef::str(&now_meta_info.kcl_pkgpath).into_raw(ctx),
+            );
             let value = schema_fn(ctx, list, dict);
             ptr_as_ref(value)
         };
@@ -153,7 +157,11 @@ pub fn resolve_schema(schema: &ValueRef, keys: &[String]) -> ValueRef {
 }
 
 /// Type pack and check ValueRef with the expected type vector
-pub fn type_pack_and_check(value: &ValueRef, expected_types: Vec<&str>) -> ValueRef {
+pub fn type_pack_and_check(
+    ctx: &mut Context,
+    value: &ValueRef,
+    expected_types: Vec<&str>,
+) -> ValueRef {
     if value.is_none_or_undefined() || expected_types.is_empty() {
         return value.clone();
     }
@@ -164,19 +172,18 @@ pub fn type_pack_and_check(value: &ValueRef, expected_types: Vec<&str>) -> Value
     let expected_type = &expected_types.join(" | ").replace('@', "");
     for tpe in expected_types {
         let tpe = if !tpe.contains('.') {
-            let ctx = Context::current_context_mut();
             match ctx.import_names.get(tpe) {
-                Some(mapping) => mapping.keys().next().unwrap(),
-                None => tpe,
+                Some(mapping) => mapping.keys().next().unwrap().to_string(),
+                None => tpe.to_string(),
             }
         } else {
-            tpe
+            tpe.to_string()
         };
         if !is_schema {
-            convertted_value = convert_collection_value(value, tpe);
+            convertted_value = convert_collection_value(ctx, value, &tpe);
         }
         // Runtime type check
-        checked = check_type(&convertted_value, tpe);
+        checked = check_type(&convertted_value, &tpe);
         if checked {
             break;
         }
@@ -188,10 +195,9 @@ pub fn type_pack_and_check(value: &ValueRef, expected_types: Vec<&str>) -> Value
 }
 
 /// Convert collection value including dict/list to the potential schema
-pub fn convert_collection_value(value: &ValueRef, tpe: &str) -> ValueRef {
+pub fn convert_collection_value(ctx: &mut Context, value: &ValueRef, tpe: &str) -> ValueRef {
     // May be a type alias.
     let tpe = if !tpe.contains('.') {
-        let ctx = Context::current_context_mut();
         match ctx.import_names.get(tpe) {
             Some(mapping) => mapping.keys().next().unwrap(),
             None => tpe,
@@ -215,7 +221,7 @@ pub fn convert_collection_value(value: &ValueRef, tpe: &str) -> ValueRef {
         let mut expected_dict = ValueRef::dict(None);
         let dict_ref = value.as_dict_ref();
         for (k, v) in &dict_ref.values {
-            let expected_value = convert_collection_value(v, &value_tpe);
+            let expected_value = convert_collection_value(ctx, v, &value_tpe);
             let op = dict_ref
                 .ops
                 .get(k)
@@ -229,14 +235,13 @@ pub fn convert_collection_value(value: &ValueRef, tpe: &str) -> ValueRef {
         let mut expected_list = ValueRef::list(None);
         let list_ref = value.as_list_ref();
         for v in &list_ref.values {
-            let expected_value = convert_collection_value(v, &expected_type);
+            let expected_value = convert_collection_value(ctx, v, &expected_type);
             expected_list.list_append(&expected_value)
         }
         expected_list
     } else if BUILTIN_TYPES.contains(&tpe) {
         value.clone()
     } else {
-        let ctx = Context::current_context_mut();
         let now_meta_info = ctx.panic_info.clone();
         let mut schema_type_name = if tpe.contains('.') {
             tpe.to_string()
@@ -285,54 +290,59 @@ pub fn convert_collection_value(value: &ValueRef, tpe: &str) -> ValueRef {
             let schema_fn_ptr = schema_type.fn_ptr;
             let value = unsafe {
                 let schema_fn: SchemaTypeFunc = transmute_copy(&schema_fn_ptr);
-                let ctx = kclvm_context_current();
-                let cal_order = kclvm_value_Dict();
-                let list = kclvm_value_List();
+                let cal_order = kclvm_value_Dict(ctx);
+                let list = kclvm_value_List(ctx);
                 // Schema function closures
                 // is_sub_schema
-                kclvm_list_append(list, ValueRef::bool(false).into_raw());
+                kclvm_list_append(list, ValueRef::bool(false).into_raw(ctx));
                 // config meta
-                kclvm_list_append(list, kclvm_value_Dict());
+                kclvm_list_append(list, kclvm_value_Dict(ctx));
                 // config
-                kclvm_list_append(list, value.clone().into_raw());
+                kclvm_list_append(list, value.clone().into_raw(ctx));
                 // schema
-                kclvm_list_append(list, kclvm_value_Dict());
+                kclvm_list_append(list, kclvm_value_Dict(ctx));
                 // optional mapping
-                kclvm_list_append(list, kclvm_value_Dict());
+                kclvm_list_append(list, kclvm_value_Dict(ctx));
                 // cal order map
                 kclvm_list_append(list, cal_order);
                 // backtrack level map
-                kclvm_list_append(list, kclvm_value_Dict());
+                kclvm_list_append(list, kclvm_value_Dict(ctx));
                 // backtrack cache
-                kclvm_list_append(list, kclvm_value_Dict());
+                kclvm_list_append(list, kclvm_value_Dict(ctx));
                 // record instance
-                kclvm_list_append(list, ValueRef::bool(false).into_raw());
+                kclvm_list_append(list, ValueRef::bool(false).into_raw(ctx));
                 // instance pkgpath
-                kclvm_list_append(list, ValueRef::str(&now_meta_info.kcl_pkgpath).into_raw());
-                let dict = kclvm_value_Dict();
+                kclvm_list_append(
+                    list,
+                    ValueRef::str(&now_meta_info.kcl_pkgpath).into_raw(ctx),
+                );
+                let dict = kclvm_value_Dict(ctx);
                 schema_fn(ctx, list, dict);
-                let list = kclvm_value_List();
+                let list = kclvm_value_List(ctx);
                 // Schema function closures
                 // is_sub_schema
-                kclvm_list_append(list, ValueRef::bool(true).into_raw());
+                kclvm_list_append(list, ValueRef::bool(true).into_raw(ctx));
                 // config meta
-                kclvm_list_append(list, kclvm_value_Dict());
+                kclvm_list_append(list, kclvm_value_Dict(ctx));
                 // config
-                kclvm_list_append(list, value.clone().into_raw());
+                kclvm_list_append(list, value.clone().into_raw(ctx));
                 // schema
-                kclvm_list_append(list, kclvm_value_Dict());
+                kclvm_list_append(list, kclvm_value_Dict(ctx));
                 // optional mapping
-                kclvm_list_append(list, kclvm_value_Dict());
+                kclvm_list_append(list, kclvm_value_Dict(ctx));
                 // cal order map
                 kclvm_list_append(list, cal_order);
                 // backtrack level map
-                kclvm_list_append(list, kclvm_value_Dict());
+                kclvm_list_append(list, kclvm_value_Dict(ctx));
                 // backtrack cache
-                kclvm_list_append(list, kclvm_value_Dict());
+                kclvm_list_append(list, kclvm_value_Dict(ctx));
                 // record instance
-                kclvm_list_append(list, ValueRef::bool(true).into_raw());
+                kclvm_list_append(list, ValueRef::bool(true).into_raw(ctx));
                 // instance pkgpath
-                kclvm_list_append(list, ValueRef::str(&now_meta_info.kcl_pkgpath).into_raw());
+                kclvm_list_append(
+                    list,
+                    ValueRef::str(&now_meta_info.kcl_pkgpath).into_raw(ctx),
+                );
                 let value = schema_fn(ctx, list, dict);
                 ptr_as_ref(value)
             };
@@ -14,6 +14,7 @@ struct UnionContext {
 impl ValueRef {
     fn do_union(
         &mut self,
+        ctx: &mut Context,
         x: &Self,
         should_list_override: bool,
         should_idempotent_check: bool,
@@ -71,6 +72,7 @@ impl ValueRef {
                                 return;
                             }
                             obj_value.union(
+                                ctx,
                                 v,
                                 false,
                                 should_list_override,
@@ -153,6 +155,7 @@ impl ValueRef {
                             obj.values.push(delta.values[idx].clone());
                         } else if idx < delta_len {
                             obj.values[idx].union(
+                                ctx,
                                 &delta.values[idx],
                                 false,
                                 should_list_override,
@@ -222,7 +225,7 @@ impl ValueRef {
                 &optional_mapping,
             );
             if should_config_resolve {
-                *self = resolve_schema(&schema, &common_keys);
+                *self = resolve_schema(ctx, &schema, &common_keys);
             } else {
                 *self = schema;
             }
@@ -231,6 +234,7 @@ impl ValueRef {
     }
     fn union(
         &mut self,
+        ctx: &mut Context,
         x: &Self,
         or_mode: bool,
         should_list_override: bool,
@@ -247,6 +251,7 @@ impl ValueRef {
         }
         if self.is_list_or_config() && x.is_list_or_config() {
             self.do_union(
+                ctx,
                 x,
                 should_list_override,
                 should_idempotent_check,
@@ -273,6 +278,7 @@ impl ValueRef {
 
     pub fn union_entry(
         &mut self,
+        ctx: &mut Context,
         x: &Self,
         or_mode: bool,
         should_list_override: bool,
@@ -281,6 +287,7 @@ impl ValueRef {
     ) -> Self {
         let mut union_context = UnionContext::default();
         let ret = self.union(
+            ctx,
             x,
             or_mode,
             should_list_override,
@@ -332,21 +339,23 @@ mod test_value_union {
 
     #[test]
     fn test_list_union() {
+        let mut ctx = Context::new();
         let cases = [
             ("[0]", "[1, 2]", "[1, 2]"),
             ("[1, 2]", "[2]", "[2, 2]"),
             ("[0, 0]", "[1, 2]", "[1, 2]"),
         ];
         for (left, right, expected) in cases {
-            let left_value = ValueRef::from_json(left).unwrap();
-            let right_value = ValueRef::from_json(right).unwrap();
-            let value = left_value.bin_bit_or(&right_value);
+            let left_value = ValueRef::from_json(&mut ctx, left).unwrap();
+            let right_value = ValueRef::from_json(&mut ctx, right).unwrap();
+            let value = left_value.bin_bit_or(&mut ctx, &right_value);
             assert_eq!(value.to_json_string(), expected);
         }
     }
 
     #[test]
     fn test_dict_union() {
+        let mut ctx = Context::new();
         let cases = [
             (
                 vec![("key", "value", ConfigEntryOperationKind::Union, -1)],
@@ -407,7 +416,7 @@ mod test_value_union {
             for (key, val, op, index) in right_entries {
                 right_value.dict_update_entry(key, &ValueRef::str(val), &op, &index);
             }
-            let result = left_value.bin_bit_or(&right_value);
+            let result = left_value.bin_bit_or(&mut ctx, &right_value);
             for (key, val, op, index) in expected {
                 let result_dict = result.as_dict_ref();
                 let result_val = result_dict.values.get(key).unwrap().as_str();
@@ -421,6 +430,7 @@ mod test_value_union {
     }
     #[test]
     fn test_dict_union_insert() {
+        let mut ctx = Context::new();
         let cases = [
             (
                 vec![("key", vec![0, 1], ConfigEntryOperationKind::Override, -1)],
@@ -457,7 +467,7 @@ mod test_value_union {
                     &index,
                 );
             }
-            let result = left_value.bin_bit_or(&right_value);
+            let result = left_value.bin_bit_or(&mut ctx, &right_value);
             for (key, val, op, index) in expected {
                 let result_dict = result.as_dict_ref();
                 let result_val = result_dict.values.get(key).unwrap();
@@ -472,6 +482,7 @@ mod test_value_union {
 
     #[test]
     fn test_dict_union_same_ref() {
+        let mut ctx = Context::new();
         let cases = [
             (
                 vec![("key1", "value", ConfigEntryOperationKind::Union, -1)],
@@ -551,7 +562,7 @@ mod test_value_union {
                 left_value.dict_update_entry(key, &both_val, &op, &index);
                 left_value.dict_update_entry(key, &both_val, &op, &index);
             }
-            let result = left_value.bin_bit_or(&right_value);
+            let result = left_value.bin_bit_or(&mut ctx, &right_value);
             for (key, val, op, index) in expected {
                 let result_dict = result.as_dict_ref();
                 let result_val = result_dict.values.get(key).unwrap().as_str();
@@ -652,9 +663,10 @@ try operator '=' to override the attribute, like:
         ];
         for (left, right, expected) in cases {
             assert_panic(expected, || {
-                let left_value = ValueRef::from_json(left).unwrap();
-                let right_value = ValueRef::from_json(right).unwrap();
-                left_value.bin_bit_or(&right_value);
+                let mut ctx = Context::new();
+                let left_value = ValueRef::from_json(&mut ctx, left).unwrap();
+                let right_value = ValueRef::from_json(&mut ctx, right).unwrap();
+                left_value.bin_bit_or(&mut ctx, &right_value);
             });
         }
         std::panic::set_hook(pre_hook);
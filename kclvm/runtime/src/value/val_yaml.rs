@@ -6,6 +6,11 @@ extern crate serde_yaml;
 use crate::*;
 
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// The default number of spaces of each YAML indentation level, which is the
+/// indentation of the serde_yaml emitter.
+const DEFAULT_YAML_INDENT: usize = 2;
 
 /// YAML encode options.
 /// - sort_keys: Sort the encode result by keys (defaults to false).
@@ -13,11 +18,11 @@ use serde::{Deserialize, Serialize};
 ///     a character `_` (defaults to false).
 /// - ignore_none: Whether to ignore the attribute whose value is `None` (defaults to false).
 /// - sep: Which separator to use between YAML documents (defaults to "---").
+/// - indent: The number of spaces of each indentation level when emitting (defaults to 2).
 ///
 /// TODO: We have not yet supported the following options because serde_yaml
 /// does not support these capabilities yet.
 /// Ref: https://github.com/dtolnay/serde-yaml/issues/337
-/// - indent: Which kind of indentation to use when emitting (defaults to 2).
 /// - width: The character width to use when folding text (defaults to 80).
 /// - use_fold: Force folding of text when emitting (defaults to false).
 /// - use_block: Force all text to be literal when emitting (defaults to false).
@@ -29,6 +34,7 @@ pub struct YamlEncodeOptions {
     pub ignore_private: bool,
     pub ignore_none: bool,
     pub sep: String,
+    pub indent: usize,
 }
 
 impl Default for YamlEncodeOptions {
@@ -38,6 +44,7 @@ impl Default for YamlEncodeOptions {
             ignore_private: false,
             ignore_none: false,
             sep: "---".to_string(),
+            indent: DEFAULT_YAML_INDENT,
         }
     }
 }
@@ -45,21 +52,28 @@ impl Default for YamlEncodeOptions {
 impl ValueRef {
     /// Decode a yaml single document string to a ValueRef.
     /// Returns [serde_yaml::Error] when decoding fails.
-    pub fn from_yaml(s: &str) -> Result<Self, serde_yaml::Error> {
+    pub fn from_yaml(ctx: &mut Context, s: &str) -> Result<Self, serde_yaml::Error> {
         // We use JsonValue to implement the KCL universal serialization object.
         let json_value: JsonValue = serde_yaml::from_str(s)?;
-        Ok(Self::from_json(serde_json::to_string(&json_value).unwrap().as_ref()).unwrap())
+        Ok(Self::from_json(ctx, serde_json::to_string(&json_value).unwrap().as_ref()).unwrap())
     }
 
-    /// Decode yaml stream string that contains `---` to a ValueRef.
+    /// Decode yaml stream string that contains `---` to a list of all the documents.
     /// Returns [serde_yaml::Error] when decoding fails.
-    pub fn from_yaml_stream(s: &str) -> Result<Self, serde_yaml::Error> {
+    pub fn from_yaml_all(ctx: &mut Context, s: &str) -> Result<Self, serde_yaml::Error> {
         let documents = serde_yaml::Deserializer::from_str(s);
         let mut result = ValueRef::list_value(None);
         for document in documents {
             let json_value: JsonValue = JsonValue::deserialize(document)?;
-            result.list_append(&ValueRef::parse_json(&json_value))
+            result.list_append(&ValueRef::parse_json(ctx, &json_value))
         }
+        Ok(result)
+    }
+
+    /// Decode yaml stream string that contains `---` to a ValueRef.
+    /// Returns [serde_yaml::Error] when decoding fails.
+    pub fn from_yaml_stream(ctx: &mut Context, s: &str) -> Result<Self, serde_yaml::Error> {
+        let result = Self::from_yaml_all(ctx, s)?;
         if result.is_empty() {
             // Empty result returns a empty dict.
             Ok(ValueRef::dict(None))
@@ -102,6 +116,13 @@ impl ValueRef {
         };
         let json = self.to_json_string_with_option(&json_opt);
         let yaml_value: serde_yaml::Value = serde_json::from_str(json.as_ref()).unwrap();
+        if opt.indent != DEFAULT_YAML_INDENT {
+            // The serde_yaml emitter only supports the default indentation.
+            let mut emitter = YamlEmitter::new(opt.indent);
+            emitter.emit_node(&yaml_value, None);
+            emitter.output.push('\n');
+            return emitter.output;
+        }
         match serde_yaml::to_string(&yaml_value) {
             Ok(s) => {
                 let s = s.strip_prefix("---\n").unwrap_or_else(|| s.as_ref());
@@ -112,12 +133,234 @@ impl ValueRef {
     }
 }
 
+/// The comments of a YAML node and its children, which are emitted as the YAML comments
+/// by [`ValueRef::to_yaml_string_with_comments`].
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct YamlComments {
+    /// The comment of the node, which is emitted above its key or list item.
+    pub comment: Option<String>,
+    /// The comments of the config attributes.
+    pub attrs: HashMap<String, YamlComments>,
+    /// The comments of the list items by index.
+    pub items: Vec<YamlComments>,
+}
+
+impl YamlComments {
+    /// Whether there are no comments in the node and its children.
+    pub fn is_empty(&self) -> bool {
+        self.comment.is_none()
+            && self.attrs.values().all(|c| c.is_empty())
+            && self.items.iter().all(|c| c.is_empty())
+    }
+
+    fn attr(&self, key: &serde_yaml::Value) -> Option<&YamlComments> {
+        key.as_str().and_then(|key| self.attrs.get(key))
+    }
+}
+
+impl ValueRef {
+    /// Encode the value to the YAML string with the comments. The YAML layout is the same
+    /// as [`ValueRef::to_yaml_string_with_options`], and each comment is emitted above the
+    /// key or the list item it belongs to.
+    pub fn to_yaml_string_with_comments(
+        &self,
+        opt: &YamlEncodeOptions,
+        comments: &YamlComments,
+    ) -> String {
+        let json_opt = JsonEncodeOptions {
+            sort_keys: opt.sort_keys,
+            indent: 0,
+            ignore_private: opt.ignore_private,
+            ignore_none: opt.ignore_none,
+        };
+        let json = self.to_json_string_with_option(&json_opt);
+        let yaml_value: serde_yaml::Value = serde_json::from_str(json.as_ref()).unwrap();
+        let mut emitter = YamlEmitter::new(opt.indent);
+        if let Some(comment) = &comments.comment {
+            emitter.write_comment(comment);
+        }
+        emitter.emit_node(&yaml_value, Some(comments));
+        emitter.output.push('\n');
+        emitter.output
+    }
+}
+
+/// YamlEmitter writes the same block style layout as the serde_yaml emitter with the
+/// indentation `indent` and the optional comments. The entries of a compact list item e.g.,
+/// `- name: x` are aligned after `- `, and the comments of its first entry are emitted above
+/// the list item, because a comment can not be placed after `-`.
+struct YamlEmitter {
+    output: String,
+    indent: usize,
+    /// The column of the current block node.
+    column: usize,
+}
+
+impl YamlEmitter {
+    fn new(indent: usize) -> Self {
+        Self {
+            output: String::new(),
+            indent,
+            column: 0,
+        }
+    }
+
+    fn write_newline(&mut self) {
+        self.output.push('\n');
+        self.output.push_str(&" ".repeat(self.column));
+    }
+
+    /// Write the comment lines at the current indent, which must be at the line start.
+    fn write_comment(&mut self, comment: &str) {
+        for line in comment.lines() {
+            let line = line.trim_end();
+            if line.is_empty() {
+                self.output.push('#');
+            } else {
+                self.output.push_str("# ");
+                self.output.push_str(line);
+            }
+            self.write_newline();
+        }
+    }
+
+    fn emit_node(&mut self, value: &serde_yaml::Value, comments: Option<&YamlComments>) {
+        match value {
+            serde_yaml::Value::Sequence(values) => self.emit_sequence(values, comments, false),
+            serde_yaml::Value::Mapping(mapping) => self.emit_mapping(mapping, comments, false),
+            scalar => self.emit_scalar(scalar),
+        }
+    }
+
+    fn emit_scalar(&mut self, scalar: &serde_yaml::Value) {
+        let yaml = serde_yaml::to_string(scalar).unwrap();
+        let yaml = yaml.strip_prefix("---\n").unwrap_or(&yaml);
+        self.output
+            .push_str(yaml.strip_suffix('\n').unwrap_or(yaml));
+    }
+
+    /// Emit the sequence, and the comment of the first item has been emitted when `inline`.
+    fn emit_sequence(
+        &mut self,
+        values: &[serde_yaml::Value],
+        comments: Option<&YamlComments>,
+        inline: bool,
+    ) {
+        if values.is_empty() {
+            self.output.push_str("[]");
+            return;
+        }
+        for (i, value) in values.iter().enumerate() {
+            let item_comments = comments.and_then(|c| c.items.get(i));
+            if i > 0 {
+                self.write_newline();
+            }
+            if i > 0 || !inline {
+                for comment in leading_comments(value, item_comments) {
+                    self.write_comment(comment);
+                }
+            }
+            self.output.push('-');
+            // The item is written after `- ` on the same line.
+            self.column += 2;
+            self.emit_value(value, item_comments, true);
+            self.column -= 2;
+        }
+    }
+
+    /// Emit the mapping, and the comment of the first entry has been emitted when `inline`.
+    fn emit_mapping(
+        &mut self,
+        mapping: &serde_yaml::Mapping,
+        comments: Option<&YamlComments>,
+        inline: bool,
+    ) {
+        if mapping.is_empty() {
+            self.output.push_str("{}");
+            return;
+        }
+        for (i, (key, value)) in mapping.iter().enumerate() {
+            let attr_comments = comments.and_then(|c| c.attr(key));
+            if i > 0 {
+                self.write_newline();
+            }
+            if i > 0 || !inline {
+                if let Some(comment) = attr_comments.and_then(|c| c.comment.as_ref()) {
+                    self.write_comment(comment);
+                }
+            }
+            self.emit_node(key, None);
+            self.output.push(':');
+            self.emit_value(value, attr_comments, false);
+        }
+    }
+
+    fn emit_value(
+        &mut self,
+        value: &serde_yaml::Value,
+        comments: Option<&YamlComments>,
+        inline: bool,
+    ) {
+        let is_empty = match value {
+            serde_yaml::Value::Sequence(values) => values.is_empty(),
+            serde_yaml::Value::Mapping(mapping) => mapping.is_empty(),
+            _ => {
+                self.output.push(' ');
+                self.emit_scalar(value);
+                return;
+            }
+        };
+        let column = self.column;
+        if inline || is_empty {
+            self.output.push(' ');
+        } else {
+            self.column += self.indent;
+            self.write_newline();
+        }
+        match value {
+            serde_yaml::Value::Sequence(values) => self.emit_sequence(values, comments, inline),
+            serde_yaml::Value::Mapping(mapping) => self.emit_mapping(mapping, comments, inline),
+            _ => unreachable!(),
+        }
+        self.column = column;
+    }
+}
+
+/// Get the comments emitted above a list item, which are the comment of the item and the
+/// comments of its first entries written on the same line as `-`.
+fn leading_comments<'a>(
+    value: &serde_yaml::Value,
+    comments: Option<&'a YamlComments>,
+) -> Vec<&'a String> {
+    let comments = match comments {
+        Some(comments) => comments,
+        None => return vec![],
+    };
+    let mut result: Vec<&String> = comments.comment.iter().collect();
+    match value {
+        serde_yaml::Value::Sequence(values) if !values.is_empty() => {
+            result.extend(leading_comments(&values[0], comments.items.first()));
+        }
+        serde_yaml::Value::Mapping(mapping) => {
+            if let Some((key, _)) = mapping.iter().next() {
+                if let Some(comment) = comments.attr(key).and_then(|c| c.comment.as_ref()) {
+                    result.push(comment);
+                }
+            }
+        }
+        _ => {}
+    }
+    result
+}
+
 #[cfg(test)]
 mod test_value_yaml {
     use crate::*;
+    use std::collections::HashMap;
 
     #[test]
     fn test_value_from_yaml() {
+        let mut ctx = Context::new();
         let cases = [
             ("a: 1\n", ValueRef::dict(Some(&[("a", &ValueRef::int(1))]))),
             (
@@ -142,13 +385,14 @@ mod test_value_yaml {
             ),
         ];
         for (yaml_str, expected) in cases {
-            let result = ValueRef::from_yaml(yaml_str);
+            let result = ValueRef::from_yaml(&mut ctx, yaml_str);
             assert_eq!(result.unwrap(), expected);
         }
     }
 
     #[test]
     fn test_value_from_yaml_fail() {
+        let mut ctx = Context::new();
         let cases = [
             (
                 "a: 1\n  b: 2\nc: 3",
@@ -160,13 +404,14 @@ mod test_value_yaml {
             ),
         ];
         for (yaml_str, expected) in cases {
-            let result = ValueRef::from_yaml(yaml_str);
+            let result = ValueRef::from_yaml(&mut ctx, yaml_str);
             assert_eq!(result.err().unwrap().to_string(), expected);
         }
     }
 
     #[test]
     fn test_value_from_yaml_stream() {
+        let mut ctx = Context::new();
         let cases = [
             ("a: 1\n", ValueRef::dict(Some(&[("a", &ValueRef::int(1))]))),
             (
@@ -178,13 +423,38 @@ mod test_value_yaml {
             ),
         ];
         for (yaml_str, expected) in cases {
-            let result = ValueRef::from_yaml_stream(yaml_str);
+            let result = ValueRef::from_yaml_stream(&mut ctx, yaml_str);
+            assert_eq!(result.unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_value_from_yaml_all() {
+        let mut ctx = Context::new();
+        let cases = [
+            ("", ValueRef::list_value(None)),
+            (
+                "a: 1\n",
+                ValueRef::list_value(Some(&[ValueRef::dict(Some(&[("a", &ValueRef::int(1))]))])),
+            ),
+            (
+                "a: 1\n---\n- 2\n---\nb\n",
+                ValueRef::list_value(Some(&[
+                    ValueRef::dict(Some(&[("a", &ValueRef::int(1))])),
+                    ValueRef::list_int(&[2]),
+                    ValueRef::str("b"),
+                ])),
+            ),
+        ];
+        for (yaml_str, expected) in cases {
+            let result = ValueRef::from_yaml_all(&mut ctx, yaml_str);
             assert_eq!(result.unwrap(), expected);
         }
     }
 
     #[test]
     fn test_value_from_yaml_stream_fail() {
+        let mut ctx = Context::new();
         let cases = [
             (
                 "a: 1\n---\na: 1\n  b: 2\nc: 3",
@@ -196,7 +466,7 @@ mod test_value_yaml {
             ),
         ];
         for (yaml_str, expected) in cases {
-            let result = ValueRef::from_yaml_stream(yaml_str);
+            let result = ValueRef::from_yaml_stream(&mut ctx, yaml_str);
             assert_eq!(result.err().unwrap().to_string(), expected);
         }
     }
@@ -225,6 +495,7 @@ mod test_value_yaml {
 
     #[test]
     fn test_value_to_yaml_string_with_opts() {
+        let mut ctx = Context::new();
         let cases = [
             (
                 ValueRef::dict(Some(&[("b", &ValueRef::int(2)), ("a", &ValueRef::int(1))])),
@@ -234,6 +505,7 @@ mod test_value_yaml {
                     ignore_private: false,
                     ignore_none: false,
                     sep: "---".to_string(),
+                    indent: 2,
                 },
             ),
             (
@@ -244,6 +516,7 @@ mod test_value_yaml {
                     ignore_private: false,
                     ignore_none: false,
                     sep: "---".to_string(),
+                    indent: 2,
                 },
             ),
             (
@@ -254,6 +527,7 @@ mod test_value_yaml {
                     ignore_private: true,
                     ignore_none: false,
                     sep: "---".to_string(),
+                    indent: 2,
                 },
             ),
             (
@@ -264,6 +538,7 @@ mod test_value_yaml {
                     ignore_private: true,
                     ignore_none: true,
                     sep: "---".to_string(),
+                    indent: 2,
                 },
             ),
             (
@@ -277,6 +552,19 @@ mod test_value_yaml {
                     ignore_private: false,
                     ignore_none: false,
                     sep: "---".to_string(),
+                    indent: 2,
+                },
+            ),
+            (
+                ValueRef::from_json(
+                    &mut ctx,
+                    r#"{"b": [1, {"c": 2, "d": [3]}, [4, 5]], "a": {"e": {"f": "s"}, "g": []}}"#,
+                )
+                .unwrap(),
+                "b:\n    - 1\n    - c: 2\n      d:\n          - 3\n    - - 4\n      - 5\na:\n    e:\n        f: s\n    g: []\n",
+                YamlEncodeOptions {
+                    indent: 4,
+                    ..Default::default()
                 },
             ),
         ];
@@ -285,4 +573,70 @@ mod test_value_yaml {
             assert_eq!(result, expected);
         }
     }
+
+    #[test]
+    fn test_value_to_yaml_string_with_comments() {
+        let mut ctx = Context::new();
+        let value = ValueRef::from_json(
+            &mut ctx,
+            r#"{"a": [{"x": 1, "y": [1, 2]}, [], [[1, 2], 3]], "b": {}, "c": [{}], "d": "a\nb: c"}"#,
+        )
+        .unwrap();
+        let opts = YamlEncodeOptions::default();
+        // The layout is the same as the serde_yaml emitter without comments.
+        assert_eq!(
+            value.to_yaml_string_with_comments(&opts, &YamlComments::default()),
+            value.to_yaml_string_with_options(&opts)
+        );
+        let comment = |c: &str| YamlComments {
+            comment: Some(c.to_string()),
+            ..Default::default()
+        };
+        let item = YamlComments {
+            attrs: HashMap::from([
+                ("x".to_string(), comment("The x.")),
+                ("y".to_string(), comment("The y.\n\nThe list.")),
+            ]),
+            ..Default::default()
+        };
+        let comments = YamlComments {
+            comment: Some("The config.".to_string()),
+            attrs: HashMap::from([
+                (
+                    "a".to_string(),
+                    YamlComments {
+                        comment: Some("The a.".to_string()),
+                        items: vec![item],
+                        ..Default::default()
+                    },
+                ),
+                ("d".to_string(), comment("The d.")),
+            ]),
+            ..Default::default()
+        };
+        assert_eq!(
+            value.to_yaml_string_with_comments(&opts, &comments),
+            r#"# The config.
+# The a.
+a:
+  # The x.
+  - x: 1
+    # The y.
+    #
+    # The list.
+    y:
+      - 1
+      - 2
+  - []
+  - - - 1
+      - 2
+    - 3
+b: {}
+c:
+  - {}
+# The d.
+d: "a\nb: c"
+"#
+        );
+    }
 }
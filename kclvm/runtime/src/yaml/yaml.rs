@@ -8,70 +8,139 @@ use crate::*;
 #[allow(non_camel_case_types)]
 type kclvm_value_ref_t = ValueRef;
 
-// def KMANGLED_encode(data, sort_keys=False, ignore_private=False, ignore_none=False):
+// def KMANGLED_encode(data, sort_keys=False, ignore_private=False, ignore_none=False, indent=2):
 
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_yaml_encode(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     args: *const kclvm_value_ref_t,
     kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
     let kwargs = ptr_as_ref(kwargs);
 
-    let mut opt = YamlEncodeOptions::default();
-    if let Some(sort_keys) = kwargs.kwarg_bool("sort_keys", None) {
-        opt.sort_keys = sort_keys;
-    }
-    if let Some(ignore_private) = kwargs.kwarg_bool("ignore_private", None) {
-        opt.ignore_private = ignore_private;
-    }
-    if let Some(ignore_none) = kwargs.kwarg_bool("ignore_none", None) {
-        opt.ignore_none = ignore_none;
-    }
-
-    if let Some(arg0) = args.arg_i(0) {
+    let opt = yaml_encode_options(ctx, kwargs);
+    if let Some(arg0) = args.arg_i(0).or_else(|| kwargs.kwarg("data")) {
         let s = ValueRef::str(arg0.to_yaml_string_with_options(&opt).as_ref());
-        return s.into_raw();
+        return s.into_raw(ctx);
     }
-    panic!("encode() missing 1 required positional argument: 'value'")
+    panic!("encode() missing 1 required positional argument: 'data'")
 }
 
+// def KMANGLED_decode(value: str):
+
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_yaml_decode(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     args: *const kclvm_value_ref_t,
-    _kwargs: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
     let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
 
-    if let Some(arg0) = args.arg_i(0) {
-        match ValueRef::from_yaml(arg0.as_str().as_ref()) {
-            Ok(x) => return x.into_raw(),
-            Err(err) => panic!("{}", err),
+    if let Some(arg0) = args.arg_i(0).or_else(|| kwargs.kwarg("value")) {
+        match ValueRef::from_yaml(ctx, arg0.as_str().as_ref()) {
+            Ok(x) => return x.into_raw(ctx),
+            Err(err) => {
+                ctx.set_err_type(&ErrType::EvaluationError_TYPE);
+                panic!("{}", err)
+            }
         }
     }
     panic!("decode() missing 1 required positional argument: 'value'")
 }
 
+// def KMANGLED_decode_all(value: str) -> [any]:
+
+#[no_mangle]
+#[runtime_fn]
+pub unsafe extern "C" fn kclvm_yaml_decode_all(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+
+    if let Some(arg0) = args.arg_i(0).or_else(|| kwargs.kwarg("value")) {
+        match ValueRef::from_yaml_all(ctx, arg0.as_str().as_ref()) {
+            Ok(x) => return x.into_raw(ctx),
+            Err(err) => {
+                ctx.set_err_type(&ErrType::EvaluationError_TYPE);
+                panic!("{}", err)
+            }
+        }
+    }
+    panic!("decode_all() missing 1 required positional argument: 'value'")
+}
+
+// def KMANGLED_dump_to_file(data, filename: str, sort_keys=False, ignore_private=False, ignore_none=False, indent=2):
+
 #[no_mangle]
 #[runtime_fn]
 pub unsafe extern "C" fn kclvm_yaml_dump_to_file(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     args: *const kclvm_value_ref_t,
-    _kwargs: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
+    ctx.check_sandbox("yaml.dump_to_file");
     let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
 
-    if let Some(data) = args.arg_i(0) {
-        if let Some(filename) = args.arg_i(0) {
-            let yaml = data.to_yaml_string();
+    let opt = yaml_encode_options(ctx, kwargs);
+    match (
+        args.arg_i(0).or_else(|| kwargs.kwarg("data")),
+        args.arg_i(1).or_else(|| kwargs.kwarg("filename")),
+    ) {
+        (Some(data), Some(filename)) => {
+            let yaml = data.to_yaml_string_with_options(&opt);
             let filename = filename.as_str();
+            if let Err(err) = std::fs::write(&filename, yaml) {
+                ctx.set_err_type(&ErrType::EvaluationError_TYPE);
+                panic!("failed to write the file '{filename}': {err}")
+            }
+            kclvm_value_None(ctx)
+        }
+        _ => {
+            panic!("dump_to_file() missing 2 required positional arguments: 'data' and 'filename'")
+        }
+    }
+}
 
-            std::fs::write(filename, yaml).expect("Unable to write file");
+/// Get the YAML encode options from the config `opts`, which are the keyword arguments of
+/// `yaml.encode` and `yaml.dump_to_file` or the `opts` of `manifests.yaml_stream`, so the
+/// same options produce the same YAML output in all of them.
+pub(crate) fn yaml_encode_options(ctx: &mut Context, opts: &ValueRef) -> YamlEncodeOptions {
+    let mut opt = YamlEncodeOptions::default();
+    // The `None` options are the same as the unset options.
+    let get = |key: &str| opts.get_by_key(key).filter(|v| !v.is_none_or_undefined());
+    let get_bool = |key: &str| get(key).map(|v| v.is_truthy());
+    if let Some(sort_keys) = get_bool("sort_keys") {
+        opt.sort_keys = sort_keys;
+    }
+    if let Some(ignore_private) = get_bool("ignore_private") {
+        opt.ignore_private = ignore_private;
+    }
+    if let Some(ignore_none) = get_bool("ignore_none") {
+        opt.ignore_none = ignore_none;
+    }
+    if let Some(sep) = get("sep") {
+        opt.sep = sep.as_str();
+    }
+    if let Some(indent) = get("indent") {
+        match *indent.rc.borrow() {
+            Value::int_value(indent) if indent > 0 => opt.indent = indent as usize,
+            _ => {
+                ctx.set_err_type(&ErrType::TypeError_Runtime_TYPE);
+                panic!("the YAML indent must be a positive int, got {indent}")
+            }
         }
     }
-    panic!("dump_to_file() missing 2 required positional arguments: 'data' and 'filename'")
+    opt
 }
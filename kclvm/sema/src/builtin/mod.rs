@@ -17,6 +17,8 @@ pub use system_module::*;
 pub const KCL_BUILTIN_FUNCTION_MANGLE_PREFIX: &str = "kclvm_builtin";
 pub const KCL_SYSTEM_MODULE_MANGLE_PREFIX: &str = "kclvm_";
 pub const BUILTIN_FUNCTION_PREFIX: &str = "$builtin";
+/// The builtin functions with side effects, which are rejected in the sandbox mode.
+pub const SANDBOX_DISABLED_BUILTIN_FUNCTIONS: [&str; 1] = ["print"];
 
 macro_rules! register_builtin {
     ($($name:ident => $ty:expr)*) => (
@@ -47,18 +47,34 @@ pub const MATH_FUNCTION_NAMES: [&str; 16] = [
 ];
 
 pub const DATETIME: &str = "datetime";
-pub const DATETIME_FUNCTION_NAMES: [&str; 4] = ["today", "now", "ticks", "date"];
+pub const DATETIME_FUNCTION_NAMES: [&str; 12] = [
+    "today",
+    "now",
+    "ticks",
+    "date",
+    "strftime",
+    "strptime",
+    "validate",
+    "to_timezone",
+    "parse_duration",
+    "format_duration",
+    "add",
+    "diff",
+];
 
 pub const REGEX: &str = "regex";
 pub const REGEX_FUNCTION_NAMES: [&str; 6] =
     ["replace", "match", "compile", "findall", "search", "split"];
 
 pub const YAML: &str = "yaml";
-pub const YAML_FUNCTION_NAMES: [&str; 3] = ["encode", "decode", "dump_to_file"];
+pub const YAML_FUNCTION_NAMES: [&str; 4] = ["encode", "decode", "decode_all", "dump_to_file"];
 
 pub const JSON: &str = "json";
 pub const JSON_FUNCTION_NAMES: [&str; 3] = ["encode", "decode", "dump_to_file"];
 
+pub const TOML: &str = "toml";
+pub const TOML_FUNCTION_NAMES: [&str; 3] = ["encode", "decode", "dump_to_file"];
+
 pub const CRYPTO: &str = "crypto";
 pub const CRYPTO_FUNCTION_NAMES: [&str; 6] =
     ["md5", "sha1", "sha224", "sha256", "sha384", "sha512"];
@@ -93,11 +109,18 @@ pub const UNITS_FIELD_NAMES: [&str; 15] = [
 pub const COLLECTION: &str = "collection";
 pub const COLLECTION_FUNCTION_NAMES: [&str; 1] = ["union_all"];
 
-pub const STANDARD_SYSTEM_MODULES: [&str; 12] = [
-    COLLECTION, NET, MANIFESTS, MATH, DATETIME, REGEX, YAML, JSON, CRYPTO, BASE64, TESTING, UNITS,
+pub const FILE: &str = "file";
+pub const FILE_FUNCTION_NAMES: [&str; 5] = ["read", "glob", "exists", "modpath", "workdir"];
+
+pub const UUID: &str = "uuid";
+pub const UUID_FUNCTION_NAMES: [&str; 3] = ["v4", "v5", "short_id"];
+
+pub const STANDARD_SYSTEM_MODULES: [&str; 15] = [
+    COLLECTION, NET, MANIFESTS, MATH, DATETIME, REGEX, YAML, JSON, TOML, CRYPTO, BASE64, TESTING,
+    UNITS, FILE, UUID,
 ];
 
-pub const STANDARD_SYSTEM_MODULE_NAMES_WITH_AT: [&str; 12] = [
+pub const STANDARD_SYSTEM_MODULE_NAMES_WITH_AT: [&str; 15] = [
     "@collection",
     "@net",
     "@manifests",
@@ -106,10 +129,24 @@ pub const STANDARD_SYSTEM_MODULE_NAMES_WITH_AT: [&str; 12] = [
     "@regex",
     "@yaml",
     "@json",
+    "@toml",
     "@crypto",
     "@base64",
     "@testing",
     "@units",
+    "@file",
+    "@uuid",
+];
+
+/// The system module functions with file system side effects, which are
+/// rejected in the sandbox mode.
+pub const SANDBOX_DISABLED_SYSTEM_FUNCTIONS: [(&str, &str); 6] = [
+    (YAML, "dump_to_file"),
+    (JSON, "dump_to_file"),
+    (TOML, "dump_to_file"),
+    (FILE, "read"),
+    (FILE, "glob"),
+    (FILE, "exists"),
 ];
 
 /// Get the system module members
@@ -123,6 +160,7 @@ pub fn get_system_module_members(name: &str) -> Vec<&str> {
         REGEX => REGEX_FUNCTION_NAMES.to_vec(),
         YAML => YAML_FUNCTION_NAMES.to_vec(),
         JSON => JSON_FUNCTION_NAMES.to_vec(),
+        TOML => TOML_FUNCTION_NAMES.to_vec(),
         CRYPTO => CRYPTO_FUNCTION_NAMES.to_vec(),
         TESTING => TESTING_FUNCTION_NAMES.to_vec(),
         UNITS => {
@@ -131,6 +169,8 @@ pub fn get_system_module_members(name: &str) -> Vec<&str> {
             members
         }
         COLLECTION => COLLECTION_FUNCTION_NAMES.to_vec(),
+        FILE => FILE_FUNCTION_NAMES.to_vec(),
+        UUID => UUID_FUNCTION_NAMES.to_vec(),
         _ => bug!("invalid system module name '{}'", name),
     }
 }
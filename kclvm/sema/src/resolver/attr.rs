@@ -85,6 +85,7 @@ impl<'ctx> Resolver<'ctx> {
                         None => (false, self.any_ty()),
                     },
                     ModuleKind::System => {
+                        self.check_sandbox_system_function(&module_ty.pkgpath, attr, pos.clone());
                         if module_ty.pkgpath == UNITS && attr == UNITS_NUMBER_MULTIPLIER {
                             (true, Rc::new(Type::number_multiplier_non_lit_ty()))
                         } else {
@@ -28,6 +28,14 @@ impl<'ctx> Resolver<'ctx> {
                         }
                         // Plugin module.
                         if pkgpath.starts_with(PLUGIN_MODULE_PREFIX) {
+                            self.check_sandbox_plugin_import(
+                                &import_stmt.rawpath,
+                                Position {
+                                    filename: m.filename.clone(),
+                                    line: stmt.line,
+                                    column: None,
+                                },
+                            );
                             continue;
                         }
                         let real_path =
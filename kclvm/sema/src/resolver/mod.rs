@@ -9,6 +9,7 @@ mod import;
 mod r#loop;
 mod node;
 mod para;
+mod sandbox;
 mod schema;
 pub mod scope;
 mod ty;
@@ -138,19 +139,27 @@ pub struct Options {
     pub raise_err: bool,
     pub config_auto_fix: bool,
     pub lint_check: bool,
+    /// Reject the plugin modules and the functions with file system side effects.
+    pub sandbox: bool,
 }
 
 /// Resolve program
 pub fn resolve_program(program: &mut Program) -> ProgramScope {
-    pre_process_program(program);
-    let mut resolver = Resolver::new(
+    resolve_program_with_opts(
         program,
         Options {
             raise_err: true,
             config_auto_fix: false,
             lint_check: true,
+            sandbox: false,
         },
-    );
+    )
+}
+
+/// Resolve program with the resolve options.
+pub fn resolve_program_with_opts(program: &mut Program, opts: Options) -> ProgramScope {
+    pre_process_program(program);
+    let mut resolver = Resolver::new(program, opts);
     resolver.resolve_import();
     let scope = resolver.check_and_lint(kclvm_ast::MAIN_PKG);
     let type_alias_mapping = resolver.ctx.type_alias_mapping.clone();
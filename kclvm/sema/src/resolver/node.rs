@@ -961,6 +961,9 @@ impl<'ctx> MutSelfTypedResultWalker<'ctx> for Resolver<'ctx> {
     }
 
     fn walk_identifier(&mut self, identifier: &'ctx ast::Identifier) -> Self::Result {
+        if identifier.names.len() == 1 {
+            self.check_sandbox_builtin_function(&identifier.names[0], self.ctx.start_pos.clone());
+        }
         self.resolve_var(
             &identifier.names,
             &identifier.pkgpath,
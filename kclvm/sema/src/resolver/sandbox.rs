@@ -0,0 +1,57 @@
+use std::rc::Rc;
+
+use crate::builtin::{SANDBOX_DISABLED_BUILTIN_FUNCTIONS, SANDBOX_DISABLED_SYSTEM_FUNCTIONS};
+use crate::resolver::Resolver;
+use kclvm_error::*;
+
+impl<'ctx> Resolver<'ctx> {
+    /// Reject the plugin module import `pkgpath` in the sandbox mode.
+    pub(crate) fn check_sandbox_plugin_import(&mut self, pkgpath: &str, pos: Position) {
+        if self.options.sandbox {
+            self.handler.add_compile_error(
+                &format!("the plugin module '{pkgpath}' is not allowed in the sandbox mode"),
+                pos,
+            );
+        }
+    }
+
+    /// Reject the system module function `pkgpath.name` with file system side
+    /// effects in the sandbox mode.
+    pub(crate) fn check_sandbox_system_function(
+        &mut self,
+        pkgpath: &str,
+        name: &str,
+        pos: Position,
+    ) {
+        if self.options.sandbox && SANDBOX_DISABLED_SYSTEM_FUNCTIONS.contains(&(pkgpath, name)) {
+            self.handler.add_compile_error(
+                &format!("the function '{pkgpath}.{name}' is not allowed in the sandbox mode"),
+                pos,
+            );
+        }
+    }
+
+    /// Reject the builtin function `name` with side effects in the sandbox mode,
+    /// the user defined variables with the same name are not affected.
+    pub(crate) fn check_sandbox_builtin_function(&mut self, name: &str, pos: Position) {
+        if !self.options.sandbox
+            || self.ctx.l_value
+            || !SANDBOX_DISABLED_BUILTIN_FUNCTIONS.contains(&name)
+        {
+            return;
+        }
+        let is_builtin = match (
+            self.scope.borrow().lookup(name),
+            self.builtin_scope.borrow().elems.get(name),
+        ) {
+            (Some(obj), Some(builtin_obj)) => Rc::ptr_eq(&obj, builtin_obj),
+            _ => false,
+        };
+        if is_builtin {
+            self.handler.add_compile_error(
+                &format!("the function '{name}' is not allowed in the sandbox mode"),
+                pos,
+            );
+        }
+    }
+}
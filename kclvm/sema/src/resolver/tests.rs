@@ -2,8 +2,8 @@ use super::Options;
 use super::Resolver;
 use crate::builtin::BUILTIN_FUNCTION_NAMES;
 use crate::pre_process::pre_process_program;
-use crate::resolver::resolve_program;
 use crate::resolver::scope::*;
+use crate::resolver::{resolve_program, resolve_program_with_opts};
 use crate::ty::{Type, TypeKind};
 use kclvm_ast::ast;
 use kclvm_error::*;
@@ -120,6 +120,33 @@ fn test_resolve_program_mismatch_type_fail() {
     );
 }
 
+#[test]
+fn test_resolve_program_sandbox_fail() {
+    let path = "./src/resolver/test_fail_data/sandbox.k";
+    let mut program = parse_program(path).unwrap();
+    let scope = resolve_program(&mut program);
+    assert_eq!(scope.handler.diagnostics.len(), 0);
+    let mut program = parse_program(path).unwrap();
+    let scope = resolve_program_with_opts(
+        &mut program,
+        Options {
+            raise_err: true,
+            config_auto_fix: false,
+            lint_check: true,
+            sandbox: true,
+        },
+    );
+    let err_messages = [
+        "the plugin module 'kcl_plugin.hello' is not allowed in the sandbox mode",
+        "the function 'yaml.dump_to_file' is not allowed in the sandbox mode",
+        "the function 'print' is not allowed in the sandbox mode",
+    ];
+    assert_eq!(scope.handler.diagnostics.len(), err_messages.len());
+    for (diag, msg) in scope.handler.diagnostics.iter().zip(err_messages.iter()) {
+        assert_eq!(diag.messages[0].message, msg.to_string());
+    }
+}
+
 #[test]
 fn test_resolve_program_cycle_reference_fail() {
     let sess = Arc::new(ParseSession::default());
@@ -263,6 +290,7 @@ fn test_lint() {
             raise_err: true,
             config_auto_fix: false,
             lint_check: true,
+            sandbox: false,
         },
     );
     resolver.resolve_import();
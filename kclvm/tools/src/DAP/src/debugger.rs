@@ -0,0 +1,214 @@
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{Receiver, Sender};
+use std::sync::{Arc, Mutex};
+
+use kclvm_ast::ast;
+use kclvm_evaluator::{DebugFrame, Debugger, Evaluator};
+use kclvm_runtime::ValueRef;
+
+use crate::server::Input;
+
+/// Line breakpoints keyed by the canonicalized source file path, which are shared
+/// by the debug adapter and the evaluation thread.
+pub(crate) type Breakpoints = Arc<Mutex<HashMap<PathBuf, HashSet<u64>>>>;
+
+/// The events sent from the evaluation thread to the debug adapter.
+#[derive(Debug)]
+pub(crate) enum EvalEvent {
+    /// The evaluation is stopped and waits for a [`Resume`] command.
+    Stopped(Stopped),
+    /// The evaluation is finished with the YAML output or the error message.
+    Exited(Result<String, String>),
+}
+
+/// The commands sent from the debug adapter to resume a stopped evaluation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Resume {
+    Continue,
+    Next,
+    StepIn,
+    StepOut,
+}
+
+/// The snapshot of a stopped evaluation, the values are converted to variables
+/// because the runtime values can not be sent across threads.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Stopped {
+    pub reason: &'static str,
+    /// The call frames from the innermost to the outermost.
+    pub frames: Vec<DebugFrame>,
+    /// The named scopes of the innermost frame and their variables.
+    pub scopes: Vec<(String, Vec<Variable>)>,
+}
+
+/// The snapshot of a variable and its items or attributes.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Variable {
+    pub name: String,
+    pub value: String,
+    pub ty: String,
+    pub children: Vec<Variable>,
+}
+
+impl Variable {
+    pub(crate) fn new(name: &str, value: &ValueRef) -> Self {
+        let children = if value.is_list() {
+            value
+                .as_list_ref()
+                .values
+                .iter()
+                .enumerate()
+                .map(|(i, v)| Variable::new(&format!("[{i}]"), v))
+                .collect()
+        } else if value.is_config() {
+            value
+                .as_dict_ref()
+                .values
+                .iter()
+                .map(|(k, v)| Variable::new(k, v))
+                .collect()
+        } else {
+            vec![]
+        };
+        let display = if value.is_func() {
+            let func = value.as_function();
+            if func.runtime_type.is_empty() {
+                "<function>".to_string()
+            } else {
+                format!("<schema {}>", func.runtime_type)
+            }
+        } else {
+            value.to_json_string()
+        };
+        Variable {
+            name: name.to_string(),
+            value: display,
+            ty: value.type_str(),
+            children,
+        }
+    }
+}
+
+/// How the evaluation is resumed, the depth is the frame count where the step starts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StepMode {
+    Entry,
+    Continue,
+    StepIn,
+    Next(usize),
+    StepOut(usize),
+}
+
+/// DapDebugger stops the evaluation at the breakpoints and the steps, and then
+/// waits for the resume commands from the debug adapter.
+pub(crate) struct DapDebugger {
+    breakpoints: Breakpoints,
+    pause: Arc<AtomicBool>,
+    events: Sender<Input>,
+    commands: Receiver<Resume>,
+    mode: Cell<StepMode>,
+    /// The canonicalized paths of the source files.
+    paths: RefCell<HashMap<String, PathBuf>>,
+}
+
+impl DapDebugger {
+    pub(crate) fn new(
+        breakpoints: Breakpoints,
+        pause: Arc<AtomicBool>,
+        stop_on_entry: bool,
+        events: Sender<Input>,
+        commands: Receiver<Resume>,
+    ) -> Self {
+        Self {
+            breakpoints,
+            pause,
+            events,
+            commands,
+            mode: Cell::new(if stop_on_entry {
+                StepMode::Entry
+            } else {
+                StepMode::Continue
+            }),
+            paths: RefCell::new(HashMap::new()),
+        }
+    }
+
+    fn stop_reason(&self, frame: &DebugFrame, depth: usize) -> Option<&'static str> {
+        if self.pause.swap(false, Ordering::SeqCst) {
+            return Some("pause");
+        }
+        let reason = match self.mode.get() {
+            StepMode::Entry => Some("entry"),
+            StepMode::StepIn => Some("step"),
+            StepMode::Next(start) if depth <= start => Some("step"),
+            StepMode::StepOut(start) if depth < start => Some("step"),
+            _ => None,
+        };
+        reason.or_else(|| self.is_breakpoint(frame).then_some("breakpoint"))
+    }
+
+    fn is_breakpoint(&self, frame: &DebugFrame) -> bool {
+        let breakpoints = self.breakpoints.lock().unwrap();
+        if breakpoints.is_empty() {
+            return false;
+        }
+        let mut paths = self.paths.borrow_mut();
+        let path = paths
+            .entry(frame.filename.clone())
+            .or_insert_with(|| canonicalize(&frame.filename));
+        matches!(breakpoints.get(path), Some(lines) if lines.contains(&frame.line))
+    }
+}
+
+impl Debugger for DapDebugger {
+    fn on_stmt(&self, evaluator: &Evaluator, _stmt: &ast::Node<ast::Stmt>) {
+        let mut frames = evaluator.debug_frames();
+        let depth = frames.len();
+        let reason = match frames.last() {
+            Some(frame) => self.stop_reason(frame, depth),
+            None => None,
+        };
+        if let Some(reason) = reason {
+            frames.reverse();
+            let scopes = evaluator
+                .debug_scopes()
+                .iter()
+                .map(|scope| {
+                    let variables = scope
+                        .variables
+                        .iter()
+                        .map(|(name, value)| Variable::new(name, value))
+                        .collect();
+                    (scope.name.clone(), variables)
+                })
+                .collect();
+            let stopped = Stopped {
+                reason,
+                frames,
+                scopes,
+            };
+            // When the debug adapter is disconnected, the evaluation runs to the end.
+            let mode = match self
+                .events
+                .send(Input::Eval(EvalEvent::Stopped(stopped)))
+                .ok()
+                .and_then(|_| self.commands.recv().ok())
+            {
+                Some(Resume::StepIn) => StepMode::StepIn,
+                Some(Resume::Next) => StepMode::Next(depth),
+                Some(Resume::StepOut) => StepMode::StepOut(depth),
+                Some(Resume::Continue) | None => StepMode::Continue,
+            };
+            self.mode.set(mode);
+        }
+    }
+}
+
+/// Canonicalize the source file path, and the path is kept when it does not exist.
+pub(crate) fn canonicalize(path: impl AsRef<Path>) -> PathBuf {
+    let path = path.as_ref();
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
@@ -0,0 +1,44 @@
+use std::io::{self, BufReader};
+use std::sync::mpsc::channel;
+
+use protocol::Message;
+use server::{DebugAdapter, Input};
+
+mod debugger;
+mod protocol;
+mod server;
+
+#[cfg(test)]
+mod tests;
+
+/// Main entry point for the debug adapter, which communicates with the client
+/// through the stdio.
+pub fn run_server() -> anyhow::Result<()> {
+    let (input_sender, receiver) = channel::<Input>();
+    let (writer_sender, writer_receiver) = channel::<Message>();
+    let sender = input_sender.clone();
+    std::thread::spawn(move || {
+        let mut stdin = BufReader::new(io::stdin());
+        while let Ok(Some(message)) = Message::read(&mut stdin) {
+            if sender.send(Input::Client(message)).is_err() {
+                return;
+            }
+        }
+        let _ = sender.send(Input::Closed);
+    });
+    let writer = std::thread::spawn(move || -> io::Result<()> {
+        let mut stdout = io::stdout();
+        for message in writer_receiver {
+            message.write(&mut stdout)?;
+        }
+        Ok(())
+    });
+    DebugAdapter::new(writer_sender, input_sender).run(receiver)?;
+    writer.join().unwrap()?;
+    Ok(())
+}
+
+/// Main entry point for the `kcl-debug-adapter` executable.
+fn main() -> Result<(), anyhow::Error> {
+    run_server()
+}
@@ -0,0 +1,88 @@
+//! The base protocol of the Debug Adapter Protocol, see
+//! https://microsoft.github.io/debug-adapter-protocol/specification for more information.
+//! A message consists of a `Content-Length` header and a JSON content.
+
+use std::io::{self, BufRead, Write};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// A DAP message sent by the client or the debug adapter.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub(crate) enum Message {
+    Request(Request),
+    Response(Response),
+    Event(Event),
+}
+
+/// A client request to the debug adapter.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub(crate) struct Request {
+    pub seq: i64,
+    pub command: String,
+    #[serde(default)]
+    pub arguments: Value,
+}
+
+/// The response of a client request.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub(crate) struct Response {
+    pub seq: i64,
+    pub request_seq: i64,
+    pub success: bool,
+    pub command: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(default, skip_serializing_if = "Value::is_null")]
+    pub body: Value,
+}
+
+/// An event sent by the debug adapter, e.g., the `stopped` event.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub(crate) struct Event {
+    pub seq: i64,
+    pub event: String,
+    #[serde(default, skip_serializing_if = "Value::is_null")]
+    pub body: Value,
+}
+
+impl Message {
+    /// Read a message from `r`, returns `None` at the end of the input.
+    pub(crate) fn read(r: &mut impl BufRead) -> io::Result<Option<Message>> {
+        let mut content_length = None;
+        loop {
+            let mut header = String::new();
+            if r.read_line(&mut header)? == 0 {
+                return Ok(None);
+            }
+            let header = header.trim_end();
+            if header.is_empty() {
+                break;
+            }
+            if let Some((name, value)) = header.split_once(':') {
+                if name.eq_ignore_ascii_case("Content-Length") {
+                    content_length = Some(value.trim().parse::<usize>().map_err(invalid_data)?);
+                }
+            }
+        }
+        let content_length =
+            content_length.ok_or_else(|| invalid_data("the Content-Length header is not found"))?;
+        let mut content = vec![0; content_length];
+        r.read_exact(&mut content)?;
+        serde_json::from_slice(&content)
+            .map(Some)
+            .map_err(invalid_data)
+    }
+
+    /// Write the message into `w`.
+    pub(crate) fn write(&self, w: &mut impl Write) -> io::Result<()> {
+        let content = serde_json::to_string(self)?;
+        write!(w, "Content-Length: {}\r\n\r\n{}", content.len(), content)?;
+        w.flush()
+    }
+}
+
+fn invalid_data(err: impl ToString) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err.to_string())
+}
@@ -0,0 +1,375 @@
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::Arc;
+
+use kclvm_ast::ast;
+use kclvm_parser::ParseSession;
+use kclvm_runner::{exec_program_with_debugger, ExecProgramArgs};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::debugger::{
+    canonicalize, Breakpoints, DapDebugger, EvalEvent, Resume, Stopped, Variable,
+};
+use crate::protocol::{Event, Message, Request, Response};
+
+/// The only thread of the KCL evaluation.
+const THREAD_ID: i64 = 1;
+
+/// The arguments of the `launch` request.
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LaunchArgs {
+    /// The KCL files to run.
+    #[serde(default)]
+    pub program: Vec<String>,
+    pub cwd: Option<String>,
+    /// The top level arguments like `-D name=value`, where the value is a JSON value.
+    #[serde(default)]
+    pub args: serde_json::Map<String, Value>,
+    #[serde(default)]
+    pub stop_on_entry: bool,
+}
+
+impl LaunchArgs {
+    fn exec_program_args(&self) -> ExecProgramArgs {
+        ExecProgramArgs {
+            work_dir: self.cwd.clone(),
+            k_filename_list: self.program.clone(),
+            args: self
+                .args
+                .iter()
+                .map(|(name, value)| ast::CmdArgSpec {
+                    name: name.clone(),
+                    value: value.to_string(),
+                })
+                .collect(),
+            // Only the AST evaluator supports the debugging.
+            fast_eval: true,
+            ..Default::default()
+        }
+    }
+}
+
+/// The inputs of the debug adapter from the client and the evaluation thread.
+#[derive(Debug)]
+pub(crate) enum Input {
+    Client(Message),
+    /// The client connection is closed.
+    Closed,
+    Eval(EvalEvent),
+}
+
+/// DebugAdapter handles the DAP requests from the client, launches the KCL program
+/// in an evaluation thread and forwards the stopped state to the client.
+pub(crate) struct DebugAdapter {
+    /// Channel to send messages to the client
+    sender: Sender<Message>,
+    /// Channel to send the evaluation events to the debug adapter itself
+    input_sender: Sender<Input>,
+    seq: i64,
+    breakpoints: Breakpoints,
+    pause: Arc<AtomicBool>,
+    /// The launch arguments waiting for the `configurationDone` request.
+    launch: Option<LaunchArgs>,
+    configured: bool,
+    /// Channel to resume the running evaluation
+    evaluation: Option<Sender<Resume>>,
+    stopped: Option<Stopped>,
+    /// The variable lists referred by the `variablesReference` starting from 1.
+    variables: Vec<Vec<Variable>>,
+    /// True if the client requested that we disconnect
+    shutdown_requested: bool,
+}
+
+impl DebugAdapter {
+    pub(crate) fn new(sender: Sender<Message>, input_sender: Sender<Input>) -> Self {
+        Self {
+            sender,
+            input_sender,
+            seq: 0,
+            breakpoints: Default::default(),
+            pause: Arc::new(AtomicBool::new(false)),
+            launch: None,
+            configured: false,
+            evaluation: None,
+            stopped: None,
+            variables: vec![],
+            shutdown_requested: false,
+        }
+    }
+
+    /// Runs the debug adapter until the client disconnects.
+    pub(crate) fn run(mut self, receiver: Receiver<Input>) -> anyhow::Result<()> {
+        while !self.shutdown_requested {
+            match receiver.recv() {
+                Ok(Input::Client(Message::Request(request))) => self.on_request(request)?,
+                Ok(Input::Client(_)) => {}
+                Ok(Input::Eval(event)) => self.on_eval_event(event)?,
+                Ok(Input::Closed) | Err(_) => return Ok(()),
+            }
+        }
+        Ok(())
+    }
+
+    fn on_request(&mut self, request: Request) -> anyhow::Result<()> {
+        let args = &request.arguments;
+        let body = match request.command.as_str() {
+            "initialize" => {
+                self.respond(
+                    &request,
+                    Ok(json!({ "supportsConfigurationDoneRequest": true })),
+                )?;
+                return self.send_event("initialized", Value::Null);
+            }
+            "launch" => match launch_args(args) {
+                Ok(launch) if launch.program.is_empty() => {
+                    Err("the program to launch is not specified".to_string())
+                }
+                Ok(_) if self.evaluation.is_some() || self.launch.is_some() => {
+                    Err("the program has been launched".to_string())
+                }
+                Ok(launch) => {
+                    self.launch = Some(launch);
+                    self.start_if_ready();
+                    Ok(Value::Null)
+                }
+                Err(err) => Err(err),
+            },
+            "setBreakpoints" => Ok(self.set_breakpoints(args)),
+            "setExceptionBreakpoints" => Ok(Value::Null),
+            "configurationDone" => {
+                self.configured = true;
+                self.start_if_ready();
+                Ok(Value::Null)
+            }
+            "threads" => Ok(json!({ "threads": [{ "id": THREAD_ID, "name": "main" }] })),
+            "stackTrace" => Ok(self.stack_trace()),
+            "scopes" => Ok(self.scopes(args["frameId"].as_i64().unwrap_or(0))),
+            "variables" => Ok(self.variables(args["variablesReference"].as_i64().unwrap_or(0))),
+            "continue" => self
+                .resume(Resume::Continue)
+                .map(|_| json!({ "allThreadsContinued": true })),
+            "next" => self.resume(Resume::Next).map(|_| Value::Null),
+            "stepIn" => self.resume(Resume::StepIn).map(|_| Value::Null),
+            "stepOut" => self.resume(Resume::StepOut).map(|_| Value::Null),
+            "pause" => {
+                self.pause.store(true, Ordering::SeqCst);
+                Ok(Value::Null)
+            }
+            "disconnect" | "terminate" => {
+                self.shutdown_requested = true;
+                Ok(Value::Null)
+            }
+            command => Err(format!("unsupported request '{command}'")),
+        };
+        self.respond(&request, body)
+    }
+
+    fn on_eval_event(&mut self, event: EvalEvent) -> anyhow::Result<()> {
+        match event {
+            EvalEvent::Stopped(stopped) => {
+                let reason = stopped.reason;
+                self.stopped = Some(stopped);
+                self.send_event(
+                    "stopped",
+                    json!({ "reason": reason, "threadId": THREAD_ID, "allThreadsStopped": true }),
+                )
+            }
+            EvalEvent::Exited(result) => {
+                self.evaluation = None;
+                let (category, output, exit_code) = match result {
+                    Ok(output) => ("stdout", output, 0),
+                    Err(err) => ("stderr", err, 1),
+                };
+                self.send_event(
+                    "output",
+                    json!({ "category": category, "output": format!("{output}\n") }),
+                )?;
+                self.send_event("exited", json!({ "exitCode": exit_code }))?;
+                self.send_event("terminated", Value::Null)
+            }
+        }
+    }
+
+    /// Start the evaluation thread when the program is launched and the
+    /// breakpoints are configured.
+    fn start_if_ready(&mut self) {
+        if !self.configured {
+            return;
+        }
+        let launch = match self.launch.take() {
+            Some(launch) => launch,
+            None => return,
+        };
+        let event_sender = self.input_sender.clone();
+        let (commands, command_receiver) = channel();
+        let breakpoints = self.breakpoints.clone();
+        let pause = self.pause.clone();
+        std::thread::spawn(move || {
+            let debugger = DapDebugger::new(
+                breakpoints,
+                pause,
+                launch.stop_on_entry,
+                event_sender.clone(),
+                command_receiver,
+            );
+            let result = exec_program_with_debugger(
+                Arc::new(ParseSession::default()),
+                &launch.exec_program_args(),
+                &debugger,
+            )
+            .map(|result| result.yaml_result);
+            let _ = event_sender.send(Input::Eval(EvalEvent::Exited(result)));
+        });
+        self.evaluation = Some(commands);
+    }
+
+    fn set_breakpoints(&mut self, args: &Value) -> Value {
+        let path = args["source"]["path"].as_str().unwrap_or_default();
+        let lines: Vec<u64> = args["breakpoints"]
+            .as_array()
+            .map(|breakpoints| {
+                breakpoints
+                    .iter()
+                    .filter_map(|b| b["line"].as_u64())
+                    .collect()
+            })
+            .unwrap_or_default();
+        self.breakpoints
+            .lock()
+            .unwrap()
+            .insert(canonicalize(path), lines.iter().cloned().collect());
+        let breakpoints: Vec<Value> = lines
+            .iter()
+            .map(|line| json!({ "verified": true, "line": line }))
+            .collect();
+        json!({ "breakpoints": breakpoints })
+    }
+
+    fn stack_trace(&self) -> Value {
+        let frames: Vec<Value> = match &self.stopped {
+            Some(stopped) => stopped
+                .frames
+                .iter()
+                .enumerate()
+                .map(|(id, frame)| {
+                    let name = Path::new(&frame.filename)
+                        .file_name()
+                        .map(|name| name.to_string_lossy().to_string())
+                        .unwrap_or_default();
+                    json!({
+                        "id": id,
+                        "name": frame.name,
+                        "source": { "name": name, "path": frame.filename },
+                        "line": frame.line,
+                        "column": 1,
+                    })
+                })
+                .collect(),
+            None => vec![],
+        };
+        json!({ "stackFrames": frames, "totalFrames": frames.len() })
+    }
+
+    /// Only the variables of the innermost frame are available.
+    fn scopes(&mut self, frame_id: i64) -> Value {
+        let scopes = match &self.stopped {
+            Some(stopped) if frame_id == 0 => stopped.scopes.clone(),
+            _ => vec![],
+        };
+        let scopes: Vec<Value> = scopes
+            .into_iter()
+            .map(|(name, variables)| {
+                let reference = self.add_variables(variables);
+                json!({ "name": name, "variablesReference": reference, "expensive": false })
+            })
+            .collect();
+        json!({ "scopes": scopes })
+    }
+
+    fn variables(&mut self, reference: i64) -> Value {
+        let variables = match self.variables.get((reference - 1) as usize) {
+            Some(variables) if reference > 0 => variables.clone(),
+            _ => vec![],
+        };
+        let variables: Vec<Value> = variables
+            .into_iter()
+            .map(|variable| {
+                let reference = if variable.children.is_empty() {
+                    0
+                } else {
+                    self.add_variables(variable.children)
+                };
+                json!({
+                    "name": variable.name,
+                    "value": variable.value,
+                    "type": variable.ty,
+                    "variablesReference": reference,
+                })
+            })
+            .collect();
+        json!({ "variables": variables })
+    }
+
+    fn add_variables(&mut self, variables: Vec<Variable>) -> usize {
+        self.variables.push(variables);
+        self.variables.len()
+    }
+
+    fn resume(&mut self, resume: Resume) -> Result<(), String> {
+        match (&self.evaluation, self.stopped.take()) {
+            (Some(commands), Some(_)) => {
+                self.variables.clear();
+                commands.send(resume).map_err(|err| err.to_string())
+            }
+            _ => Err("the program is not stopped".to_string()),
+        }
+    }
+
+    fn respond(&mut self, request: &Request, body: Result<Value, String>) -> anyhow::Result<()> {
+        let (success, message, body) = match body {
+            Ok(body) => (true, None, body),
+            Err(message) => (false, Some(message), Value::Null),
+        };
+        let response = Response {
+            seq: self.next_seq(),
+            request_seq: request.seq,
+            success,
+            command: request.command.clone(),
+            message,
+            body,
+        };
+        self.send(Message::Response(response))
+    }
+
+    fn send_event(&mut self, event: &str, body: Value) -> anyhow::Result<()> {
+        let event = Event {
+            seq: self.next_seq(),
+            event: event.to_string(),
+            body,
+        };
+        self.send(Message::Event(event))
+    }
+
+    fn send(&self, message: Message) -> anyhow::Result<()> {
+        self.sender
+            .send(message)
+            .map_err(|_| anyhow::anyhow!("the client is disconnected"))
+    }
+
+    fn next_seq(&mut self) -> i64 {
+        self.seq += 1;
+        self.seq
+    }
+}
+
+/// The `program` launch argument can be a file or a list of files.
+fn launch_args(args: &Value) -> Result<LaunchArgs, String> {
+    let mut args = args.clone();
+    if let Some(program) = args.get("program").and_then(|p| p.as_str()) {
+        args["program"] = json!([program]);
+    }
+    LaunchArgs::deserialize(&args).map_err(|err| format!("invalid launch arguments: {err}"))
+}
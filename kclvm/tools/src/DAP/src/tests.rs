@@ -0,0 +1,171 @@
+use std::io::BufReader;
+use std::path::PathBuf;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::time::Duration;
+
+use serde_json::{json, Value};
+
+use crate::protocol::{Event, Message, Request, Response};
+use crate::server::{DebugAdapter, Input};
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+fn test_file() -> String {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push("src/test_data/main.k");
+    path.to_str().unwrap().to_string()
+}
+
+/// A test client connected to a debug adapter running in another thread.
+struct Client {
+    seq: i64,
+    sender: Sender<Input>,
+    receiver: Receiver<Message>,
+}
+
+impl Client {
+    fn start() -> Self {
+        let (sender, adapter_receiver) = channel();
+        let (adapter_sender, receiver) = channel();
+        let input_sender = sender.clone();
+        std::thread::spawn(move || {
+            DebugAdapter::new(adapter_sender, input_sender).run(adapter_receiver)
+        });
+        Client {
+            seq: 0,
+            sender,
+            receiver,
+        }
+    }
+
+    fn request(&mut self, command: &str, arguments: Value) -> Response {
+        self.seq += 1;
+        self.sender
+            .send(Input::Client(Message::Request(Request {
+                seq: self.seq,
+                command: command.to_string(),
+                arguments,
+            })))
+            .unwrap();
+        loop {
+            match self.receiver.recv_timeout(TIMEOUT).unwrap() {
+                Message::Response(response) if response.request_seq == self.seq => return response,
+                _ => {}
+            }
+        }
+    }
+
+    fn wait_event(&mut self, event: &str) -> Event {
+        loop {
+            match self.receiver.recv_timeout(TIMEOUT).unwrap() {
+                Message::Event(e) if e.event == event => return e,
+                _ => {}
+            }
+        }
+    }
+
+    fn variables(&mut self, reference: &Value) -> Vec<Value> {
+        let response = self.request("variables", json!({ "variablesReference": reference }));
+        response.body["variables"].as_array().unwrap().clone()
+    }
+}
+
+#[test]
+fn test_message_read_write() {
+    let message = Message::Event(Event {
+        seq: 1,
+        event: "stopped".to_string(),
+        body: json!({ "reason": "breakpoint", "threadId": 1 }),
+    });
+    let mut buf = vec![];
+    message.write(&mut buf).unwrap();
+    message.write(&mut buf).unwrap();
+    assert!(buf.starts_with(b"Content-Length: "));
+    let mut reader = BufReader::new(buf.as_slice());
+    assert_eq!(Message::read(&mut reader).unwrap(), Some(message.clone()));
+    assert_eq!(Message::read(&mut reader).unwrap(), Some(message));
+    assert_eq!(Message::read(&mut reader).unwrap(), None);
+}
+
+#[test]
+fn test_breakpoint_and_step() {
+    let file = test_file();
+    let mut client = Client::start();
+    let response = client.request("initialize", json!({ "adapterID": "kcl" }));
+    assert!(response.success);
+    client.wait_event("initialized");
+    // Stop in the lambda body and the schema body.
+    let response = client.request(
+        "setBreakpoints",
+        json!({ "source": { "path": file }, "breakpoints": [{ "line": 6 }, { "line": 3 }] }),
+    );
+    assert_eq!(response.body["breakpoints"][0]["verified"], true);
+    assert!(client.request("launch", json!({ "program": file })).success);
+    assert!(client.request("configurationDone", Value::Null).success);
+
+    let event = client.wait_event("stopped");
+    assert_eq!(event.body["reason"], "breakpoint");
+    let response = client.request("stackTrace", json!({ "threadId": 1 }));
+    let frames = response.body["stackFrames"].as_array().unwrap();
+    assert_eq!(frames.len(), 2);
+    assert_eq!(frames[0]["name"], "lambda");
+    assert_eq!(frames[0]["line"], 6);
+    assert_eq!(frames[1]["line"], 9);
+    let response = client.request("scopes", json!({ "frameId": 0 }));
+    let scopes = response.body["scopes"].as_array().unwrap();
+    assert_eq!(scopes[0]["name"], "Locals");
+    let locals = client.variables(&scopes[0]["variablesReference"]);
+    assert_eq!(locals[0]["name"], "x");
+    assert_eq!(locals[0]["value"], "1");
+
+    // Step over the lambda body statement.
+    assert!(client.request("next", json!({ "threadId": 1 })).success);
+    let event = client.wait_event("stopped");
+    assert_eq!(event.body["reason"], "step");
+    let response = client.request("stackTrace", json!({ "threadId": 1 }));
+    assert_eq!(response.body["stackFrames"][0]["line"], 7);
+
+    // Continue to the schema attribute breakpoint.
+    assert!(client.request("continue", json!({ "threadId": 1 })).success);
+    client.wait_event("stopped");
+    let response = client.request("stackTrace", json!({ "threadId": 1 }));
+    assert_eq!(response.body["stackFrames"][0]["name"], "__main__.Person");
+    assert_eq!(response.body["stackFrames"][0]["line"], 3);
+    let response = client.request("scopes", json!({ "frameId": 0 }));
+    let scopes = response.body["scopes"].as_array().unwrap().clone();
+    assert_eq!(scopes[1]["name"], "Schema");
+    let attrs = client.variables(&scopes[1]["variablesReference"]);
+    assert!(attrs
+        .iter()
+        .any(|v| v["name"] == "name" && v["value"] == "\"Alice\""));
+    let globals = client.variables(&scopes[2]["variablesReference"]);
+    assert!(globals
+        .iter()
+        .any(|v| v["name"] == "a" && v["value"] == "2"));
+
+    assert!(client.request("continue", json!({ "threadId": 1 })).success);
+    let event = client.wait_event("output");
+    assert_eq!(
+        event.body["output"],
+        "a: 2\nalice:\n  name: Alice\n  age: 18\n"
+    );
+    client.wait_event("terminated");
+    assert!(client.request("disconnect", Value::Null).success);
+}
+
+#[test]
+fn test_stop_on_entry() {
+    let file = test_file();
+    let mut client = Client::start();
+    client.request("initialize", json!({ "adapterID": "kcl" }));
+    client.request("launch", json!({ "program": file, "stopOnEntry": true }));
+    client.request("configurationDone", Value::Null);
+    let event = client.wait_event("stopped");
+    assert_eq!(event.body["reason"], "entry");
+    let response = client.request("stackTrace", json!({ "threadId": 1 }));
+    assert_eq!(response.body["stackFrames"][0]["line"], 1);
+    // The program is not stopped after it is continued.
+    assert!(client.request("continue", json!({ "threadId": 1 })).success);
+    assert!(!client.request("next", json!({ "threadId": 1 })).success);
+    client.wait_event("terminated");
+}
@@ -0,0 +1,4 @@
+[package]
+name = "read_0"
+edition = "0.0.1"
+version = "0.0.1"